@@ -0,0 +1,301 @@
+use std::hint::black_box;
+
+use char_ranges::{find_char_range, CharRangesExt};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+const TEXT: &str = "Hello 🗻12∈45🌏 World, Ω ¢ naïve café ";
+
+fn text() -> String {
+    TEXT.repeat(256)
+}
+
+fn bench_next_range(c: &mut Criterion) {
+    let text = text();
+
+    let mut group = c.benchmark_group("next_range");
+    group.bench_function("next", |b| {
+        b.iter(|| {
+            let chars = black_box(text.as_str()).char_ranges();
+            let mut sum = 0;
+            for (r, _c) in chars {
+                sum += r.end;
+            }
+            sum
+        })
+    });
+    group.bench_function("next_range", |b| {
+        b.iter(|| {
+            let mut chars = black_box(text.as_str()).char_ranges();
+            let mut sum = 0;
+            while let Some(r) = chars.next_range() {
+                sum += r.end;
+            }
+            sum
+        })
+    });
+    group.bench_function("next_back", |b| {
+        b.iter(|| {
+            let chars = black_box(text.as_str()).char_ranges();
+            let mut sum = 0;
+            for (r, _c) in chars.rev() {
+                sum += r.start;
+            }
+            sum
+        })
+    });
+    group.bench_function("next_back_range", |b| {
+        b.iter(|| {
+            let mut chars = black_box(text.as_str()).char_ranges();
+            let mut sum = 0;
+            while let Some(r) = chars.next_back_range() {
+                sum += r.start;
+            }
+            sum
+        })
+    });
+    group.finish();
+}
+
+fn bench_find_range(c: &mut Criterion) {
+    let haystack = "abcdefghijklmnopqrstuvwxyz ".repeat(1024) + "!";
+
+    let mut group = c.benchmark_group("find_range");
+    group.bench_function("find", |b| {
+        b.iter(|| {
+            let mut chars = black_box(haystack.as_str()).char_ranges();
+            chars.find(|&(_, c)| c == '!')
+        })
+    });
+    group.bench_function("find_range", |b| {
+        b.iter(|| {
+            let mut chars = black_box(haystack.as_str()).char_ranges();
+            chars.find_range(|c| c == '!')
+        })
+    });
+    group.finish();
+}
+
+fn bench_remaining_char_count(c: &mut Criterion) {
+    let text = text();
+
+    let mut group = c.benchmark_group("remaining_char_count");
+    group.bench_function("clone_count", |b| {
+        b.iter(|| {
+            let chars = black_box(text.as_str()).char_ranges();
+            chars.count()
+        })
+    });
+    group.bench_function("remaining_char_count", |b| {
+        b.iter(|| {
+            let chars = black_box(text.as_str()).char_ranges();
+            chars.remaining_char_count()
+        })
+    });
+    group.finish();
+}
+
+fn bench_nth(c: &mut Criterion) {
+    let inputs = [
+        (
+            "ascii",
+            "The quick brown fox jumps over the lazy dog. ".repeat(256),
+        ),
+        ("emoji", "🗻🌏👋🦀🎉🌈🍕🚀 ".repeat(256)),
+    ];
+
+    let mut group = c.benchmark_group("nth");
+    for (name, text) in &inputs {
+        let n = text.chars().count() - 1;
+        group.bench_function(format!("{name}/char_indices_nth"), |b| {
+            b.iter(|| black_box(text.as_str()).char_indices().nth(black_box(n)))
+        });
+        group.bench_function(format!("{name}/nth"), |b| {
+            b.iter(|| black_box(text.as_str()).char_ranges().nth(black_box(n)))
+        });
+        group.bench_function(format!("{name}/char_indices_nth_back"), |b| {
+            b.iter(|| {
+                black_box(text.as_str())
+                    .char_indices()
+                    .nth_back(black_box(n))
+            })
+        });
+        group.bench_function(format!("{name}/nth_back"), |b| {
+            b.iter(|| {
+                black_box(text.as_str())
+                    .char_ranges()
+                    .nth_back(black_box(n))
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_ascii(c: &mut Criterion) {
+    let text = "The quick brown fox jumps over the lazy dog. ".repeat(4096);
+    let n = text.len() / 2;
+
+    let mut group = c.benchmark_group("ascii");
+    group.bench_function("count", |b| {
+        b.iter(|| black_box(text.as_str()).char_ranges().count())
+    });
+    group.bench_function("ascii_count", |b| {
+        b.iter(|| {
+            black_box(text.as_str())
+                .char_ranges()
+                .ascii()
+                .map(|chars| chars.len())
+        })
+    });
+    group.bench_function("nth", |b| {
+        b.iter(|| black_box(text.as_str()).char_ranges().nth(black_box(n)))
+    });
+    group.bench_function("ascii_nth", |b| {
+        b.iter(|| {
+            black_box(text.as_str())
+                .char_ranges()
+                .ascii()
+                .and_then(|mut chars| chars.nth(black_box(n)))
+        })
+    });
+    group.bench_function("next", |b| {
+        b.iter(|| {
+            let mut sum = 0;
+            for (r, _c) in black_box(text.as_str()).char_ranges() {
+                sum += r.end;
+            }
+            sum
+        })
+    });
+    group.bench_function("ascii_next", |b| {
+        b.iter(|| {
+            let mut sum = 0;
+            for (r, _c) in black_box(text.as_str()).char_ranges().ascii().unwrap() {
+                sum += r.end;
+            }
+            sum
+        })
+    });
+    group.finish();
+}
+
+fn bench_fold(c: &mut Criterion) {
+    // ~4 MiB
+    let text = TEXT.repeat(4 * 1024 * 1024 / TEXT.len());
+
+    let mut group = c.benchmark_group("fold");
+    group.bench_function("next", |b| {
+        b.iter(|| {
+            let mut sum = 0;
+            for (r, _c) in black_box(text.as_str()).char_ranges() {
+                sum += r.end;
+            }
+            sum
+        })
+    });
+    group.bench_function("fold", |b| {
+        b.iter(|| {
+            black_box(text.as_str())
+                .char_ranges()
+                .fold(0, |sum, (r, _c)| sum + r.end)
+        })
+    });
+    group.bench_function("rfold", |b| {
+        b.iter(|| {
+            black_box(text.as_str())
+                .char_ranges()
+                .rfold(0, |sum, (r, _c)| sum + r.end)
+        })
+    });
+    group.bench_function("for_each_offset", |b| {
+        b.iter(|| {
+            let mut sum = 0;
+            black_box(text.as_str())
+                .char_ranges_offset(black_box(1))
+                .for_each(|(r, _c)| sum += r.end);
+            sum
+        })
+    });
+    group.finish();
+}
+
+fn bench_find_char_range(c: &mut Criterion) {
+    let haystack = TEXT.repeat(1024) + "\"";
+
+    let mut group = c.benchmark_group("find_char_range");
+    group.bench_function("find", |b| {
+        b.iter(|| {
+            let mut chars = black_box(haystack.as_str()).char_ranges();
+            chars.find(|&(_, c)| c == '"')
+        })
+    });
+    group.bench_function("find_char_range", |b| {
+        b.iter(|| find_char_range(black_box(haystack.as_str()), '"'))
+    });
+    group.bench_function("find_char_range_multibyte", |b| {
+        b.iter(|| find_char_range(black_box(haystack.as_str()), '🦀'))
+    });
+    group.finish();
+}
+
+#[cfg(feature = "alloc")]
+fn bench_char_index_map(c: &mut Criterion) {
+    use char_ranges::CharIndexMap;
+
+    let text = text();
+    let positions = text
+        .char_indices()
+        .map(|(i, _c)| i)
+        .step_by(7)
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("char_index_map");
+    group.bench_function("byte_to_char_index", |b| {
+        b.iter(|| {
+            let text = black_box(text.as_str());
+            let mut sum = 0;
+            for &pos in &positions {
+                sum += text.byte_to_char_index(pos).unwrap();
+            }
+            sum
+        })
+    });
+    group.bench_function("char_index", |b| {
+        let map = CharIndexMap::new(black_box(text.as_str()));
+        b.iter(|| {
+            let mut sum = 0;
+            for &pos in &positions {
+                sum += map.char_index(pos).unwrap();
+            }
+            sum
+        })
+    });
+    group.bench_function("byte_of_char", |b| {
+        let map = CharIndexMap::new(black_box(text.as_str()));
+        b.iter(|| {
+            let mut sum = 0;
+            for n in (0..map.char_count()).step_by(7) {
+                sum += map.byte_of_char(n).unwrap();
+            }
+            sum
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_next_range,
+    bench_find_range,
+    bench_remaining_char_count,
+    bench_nth,
+    bench_ascii,
+    bench_fold,
+    bench_find_char_range
+);
+#[cfg(feature = "alloc")]
+criterion_group!(alloc_benches, bench_char_index_map);
+
+#[cfg(not(feature = "alloc"))]
+criterion_main!(benches);
+#[cfg(feature = "alloc")]
+criterion_main!(benches, alloc_benches);
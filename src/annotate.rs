@@ -0,0 +1,305 @@
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::fmt;
+use core::ops::Range;
+
+/// The number of columns a `'\t'` is expanded to.
+const TAB_WIDTH: usize = 4;
+
+/// Writes the line(s) of `text` containing the byte positions `range`
+/// to `out`, each prefixed by a gutter with its 1-based line number, and
+/// followed by an underline row with a `^` under each [`char`] in `range`.
+///
+/// The carets align per [`char`], not per byte, i.e. a multi-byte [`char`]
+/// gets one caret. A `'\t'` is expanded to 4 spaces, and gets 4 carets.
+/// Other wide [`char`]s, e.g. emoji, are assumed to be a single column.
+///
+/// If `range` spans multiple lines, then the first line is underlined to its
+/// end, and the last line from its start. A range only covering the line
+/// break of a line, or an empty range, is shown as a single caret.
+///
+/// Only `'\n'` starts a new line, and a trailing `'\r'` is not printed.
+/// The range is clamped to the bounds of `text`, and widened outwards to
+/// the nearest [`char`] boundaries.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::render_annotation;
+///
+/// let text = "let x = 1;\nlet 🌏 = x ∈ 2;\n";
+///
+/// let mut out = String::new();
+/// render_annotation(text, 24..27, &mut out).unwrap();
+///
+/// assert_eq!(out, "2 | let 🌏 = x ∈ 2;\n  |           ^\n");
+/// ```
+pub fn render_annotation<W>(text: &str, range: Range<usize>, out: &mut W) -> fmt::Result
+where
+    W: fmt::Write + ?Sized,
+{
+    let len = text.len();
+    let mut start = range.start.min(len);
+    while !text.is_char_boundary(start) {
+        start -= 1;
+    }
+    let mut end = range.end.clamp(start, len);
+    while !text.is_char_boundary(end) {
+        end += 1;
+    }
+
+    let line_start = text[..start].rfind('\n').map_or(0, |i| i + 1);
+    let first_line = count_lines(&text[..line_start]) + 1;
+    let last = if end > start { end - 1 } else { start };
+    let last_line = first_line + count_lines(&text.as_bytes()[line_start..last]);
+    let width = digits(last_line);
+
+    let mut pos = line_start;
+    let mut empty = true;
+    let lines = text[line_start..].split_inclusive('\n');
+    for (line, n) in lines.zip(first_line..=last_line) {
+        let content = line.strip_suffix('\n').unwrap_or(line);
+        let content = content.strip_suffix('\r').unwrap_or(content);
+
+        let seg_start = (start.max(pos) - pos).min(content.len());
+        let seg_end = end.min(pos + content.len()).max(pos + seg_start) - pos;
+
+        write!(out, "{n:>width$} | ")?;
+        write_expanded(out, content)?;
+        writeln!(out)?;
+
+        write!(out, "{:width$} | ", "")?;
+        for c in content[..seg_start].chars() {
+            write_column(out, c, ' ')?;
+        }
+        if seg_start == seg_end {
+            out.write_char('^')?;
+        } else {
+            for c in content[seg_start..seg_end].chars() {
+                write_column(out, c, '^')?;
+            }
+        }
+        writeln!(out)?;
+
+        pos += line.len();
+        empty = false;
+    }
+
+    // The range is at the end of `text`, after a trailing line break
+    if empty {
+        writeln!(out, "{first_line:>width$} | ")?;
+        writeln!(out, "{:width$} | ^", "")?;
+    }
+
+    Ok(())
+}
+
+/// Returns the annotation of `range` in `text` as a [`String`].
+///
+/// See [`render_annotation()`] for more information.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::annotate;
+///
+/// let text = "foo(\n    bar\n)";
+///
+/// assert_eq!(
+///     annotate(text, 3..14),
+///     concat!(
+///         "1 | foo(\n",
+///         "  |    ^\n",
+///         "2 |     bar\n",
+///         "  | ^^^^^^^\n",
+///         "3 | )\n",
+///         "  | ^\n",
+///     ),
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn annotate(text: &str, range: Range<usize>) -> String {
+    let mut out = String::new();
+    render_annotation(text, range, &mut out).unwrap();
+    out
+}
+
+#[inline]
+fn count_lines(text: impl AsRef<[u8]>) -> usize {
+    text.as_ref().iter().filter(|&&b| b == b'\n').count()
+}
+
+#[inline]
+fn digits(mut n: usize) -> usize {
+    let mut digits = 1;
+    while n >= 10 {
+        n /= 10;
+        digits += 1;
+    }
+    digits
+}
+
+fn write_expanded<W>(out: &mut W, text: &str) -> fmt::Result
+where
+    W: fmt::Write + ?Sized,
+{
+    for (i, part) in text.split('\t').enumerate() {
+        if i > 0 {
+            write!(out, "{:TAB_WIDTH$}", "")?;
+        }
+        out.write_str(part)?;
+    }
+    Ok(())
+}
+
+fn write_column<W>(out: &mut W, c: char, fill: char) -> fmt::Result
+where
+    W: fmt::Write + ?Sized,
+{
+    let n = if c == '\t' { TAB_WIDTH } else { 1 };
+    for _ in 0..n {
+        out.write_char(fill)?;
+    }
+    Ok(())
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::annotate;
+
+    #[test]
+    fn test_annotate() {
+        let text = "fn main() {\n    let x = 🗻 ∈ 🌏;\n}\n";
+
+        assert_eq!(
+            annotate(text, 0..2),
+            concat!(
+                "1 | fn main() {\n", //
+                "  | ^^\n",
+            ),
+        );
+
+        // One caret per char, not per byte
+        let start = text.find('🗻').unwrap();
+        assert_eq!(
+            annotate(text, start..(start + 8)),
+            concat!(
+                "2 |     let x = 🗻 ∈ 🌏;\n", //
+                "  |             ^^^\n",
+            ),
+        );
+
+        // Empty range
+        assert_eq!(
+            annotate(text, 1..1),
+            concat!(
+                "1 | fn main() {\n", //
+                "  |  ^\n",
+            ),
+        );
+
+        // Only the line break
+        assert_eq!(
+            annotate(text, 11..12),
+            concat!(
+                "1 | fn main() {\n", //
+                "  |            ^\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn test_annotate_multiline() {
+        let text = "a = [\n  1,\n\n  🌏,\n]";
+
+        assert_eq!(
+            annotate(text, 4..text.len()),
+            concat!(
+                "1 | a = [\n",
+                "  |     ^\n",
+                "2 |   1,\n",
+                "  | ^^^^\n",
+                "3 | \n",
+                "  | ^\n",
+                "4 |   🌏,\n",
+                "  | ^^^^\n",
+                "5 | ]\n",
+                "  | ^\n",
+            ),
+        );
+
+        // Ending right after a line break doesn't include the next line
+        assert_eq!(
+            annotate(text, 8..11),
+            concat!(
+                "2 |   1,\n", //
+                "  |   ^^\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn test_annotate_tabs() {
+        let text = "\tif x {\r\n\t\treturn\t1;\r\n\t}";
+
+        let start = text.find("return").unwrap();
+        assert_eq!(
+            annotate(text, start..(start + 7)),
+            concat!(
+                "2 |         return    1;\n", //
+                "  |         ^^^^^^^^^^\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn test_annotate_gutter() {
+        let text = "x\n".repeat(9) + "foo\nbar";
+
+        assert_eq!(
+            annotate(&text, 17..23),
+            concat!(
+                " 9 | x\n",
+                "   |  ^\n",
+                "10 | foo\n",
+                "   | ^^^\n",
+                "11 | bar\n",
+                "   | ^\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn test_annotate_clamped() {
+        assert_eq!(
+            annotate("a🌏", 2..100),
+            concat!(
+                "1 | a🌏\n", //
+                "  |  ^\n",
+            ),
+        );
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = 5..1;
+        assert_eq!(
+            annotate("ab", reversed),
+            concat!(
+                "1 | ab\n", //
+                "  |   ^\n",
+            ),
+        );
+        assert_eq!(
+            annotate("ab\n", 3..3),
+            concat!(
+                "2 | \n", //
+                "  | ^\n",
+            ),
+        );
+        assert_eq!(
+            annotate("", 0..1),
+            concat!(
+                "1 | \n", //
+                "  | ^\n",
+            ),
+        );
+    }
+}
@@ -0,0 +1,196 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::CharRangesOffset;
+
+/// An iterator over [`char`]s and their start and end byte positions,
+/// for text which is entirely ASCII.
+///
+/// Every [`char`] is a single byte, so the length is known and
+/// [`nth()`] and [`nth_back()`] are _O(1)_.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRanges::ascii()`] for more information.
+///
+/// [`nth()`]: Iterator::nth
+/// [`nth_back()`]: DoubleEndedIterator::nth_back
+/// [`CharRanges::ascii()`]: crate::CharRanges::ascii
+#[derive(Clone)]
+pub struct AsciiCharRanges<'a> {
+    iter: CharRangesOffset<'a>,
+}
+
+impl<'a> AsciiCharRanges<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>) -> Option<Self> {
+        iter.as_str().is_ascii().then_some(Self { iter })
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+
+    /// Returns the general iterator over the remaining [`char`]s.
+    #[inline]
+    pub fn into_inner(self) -> CharRangesOffset<'a> {
+        self.iter
+    }
+
+    #[inline]
+    fn item(&self, i: usize) -> (Range<usize>, char) {
+        let c = char::from(self.iter.iter.text.as_bytes()[i]);
+        (self.iter.apply_offset(i..(i + 1)), c)
+    }
+}
+
+impl Iterator for AsciiCharRanges<'_> {
+    type Item = (Range<usize>, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.nth(0)
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let chars = &mut self.iter.iter;
+        if n >= chars.end - chars.start {
+            chars.start = chars.end;
+            return None;
+        }
+        let i = chars.start + n;
+        chars.start = i + 1;
+        Some(self.item(i))
+    }
+}
+
+impl DoubleEndedIterator for AsciiCharRanges<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.nth_back(0)
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        let chars = &mut self.iter.iter;
+        if n >= chars.end - chars.start {
+            chars.end = chars.start;
+            return None;
+        }
+        let i = chars.end - n - 1;
+        chars.end = i;
+        Some(self.item(i))
+    }
+}
+
+impl ExactSizeIterator for AsciiCharRanges<'_> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.iter.iter.end - self.iter.iter.start
+    }
+}
+
+impl FusedIterator for AsciiCharRanges<'_> {}
+
+impl fmt::Debug for AsciiCharRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "AsciiCharRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use crate::CharRangesExt;
+
+    const TEXT: &str = "Hello 12 World! {[(<~>)]}";
+
+    #[test]
+    fn test_ascii_char_ranges() {
+        let mut chars = TEXT.char_ranges().ascii().unwrap();
+        assert_eq!(chars.len(), TEXT.len());
+        assert_eq!(chars.next(), Some((0..1, 'H')));
+        assert_eq!(chars.next_back(), Some((24..25, '}')));
+        assert_eq!(chars.len(), 23);
+        assert_eq!(chars.as_str(), &TEXT[1..24]);
+
+        assert!(TEXT.char_ranges().ascii().unwrap().eq(TEXT.char_ranges()));
+        assert!(TEXT
+            .char_ranges()
+            .ascii()
+            .unwrap()
+            .rev()
+            .eq(TEXT.char_ranges().rev()));
+
+        assert_eq!("".char_ranges().ascii().map(|chars| chars.len()), Some(0));
+        assert!("Hello 🌏".char_ranges().ascii().is_none());
+
+        // Only the remaining text must be ASCII
+        let mut chars = "🌏 World".char_ranges();
+        chars.next();
+        assert!(chars.ascii().unwrap().eq(" World".char_ranges_offset(4)));
+    }
+
+    #[test]
+    fn test_ascii_char_ranges_nth() {
+        let expected = TEXT.char_ranges().collect::<Vec<_>>();
+
+        for n in 0..=(expected.len() + 1) {
+            let mut chars = TEXT.char_ranges().ascii().unwrap();
+            assert_eq!(chars.nth(n), expected.get(n).cloned());
+            assert_eq!(chars.len(), expected.len().saturating_sub(n + 1));
+            assert!(chars.eq(expected.iter().skip(n + 1).cloned()));
+
+            let mut chars = TEXT.char_ranges().ascii().unwrap();
+            let i = expected.len().checked_sub(n + 1);
+            assert_eq!(chars.nth_back(n), i.map(|i| expected[i].clone()));
+            assert_eq!(chars.len(), i.unwrap_or(0));
+            assert!(chars.eq(expected[..i.unwrap_or(0)].iter().cloned()));
+        }
+    }
+
+    #[test]
+    fn test_ascii_char_ranges_offset() {
+        let mut chars = TEXT[6..].char_ranges_offset(6).ascii().unwrap();
+        assert_eq!(chars.offset(), 6);
+        assert_eq!(chars.next(), Some((6..7, '1')));
+        assert_eq!(chars.nth(2), Some((9..10, 'W')));
+        assert_eq!(chars.nth_back(1), Some((23..24, ']')));
+        assert!(chars.clone().eq(TEXT[10..23].char_ranges_offset(10)));
+
+        let chars = chars.into_inner();
+        assert!(chars.eq(TEXT[10..23].char_ranges_offset(10)));
+    }
+}
@@ -0,0 +1,118 @@
+use core::ops::Range;
+
+use crate::UnbalancedError;
+
+/// Returns the range from the `open` at `from` to its matching `close`,
+/// including both, while tracking the nesting depth.
+///
+/// See [`CharRangesExt::find_balanced_range()`] for more information.
+///
+/// [`CharRangesExt::find_balanced_range()`]: crate::CharRangesExt::find_balanced_range
+pub(crate) fn find_balanced_range(
+    text: &str,
+    open: char,
+    close: char,
+    from: usize,
+) -> Result<Range<usize>, UnbalancedError> {
+    let mut chars = match text.get(from..) {
+        Some(rest) if rest.starts_with(open) => rest.char_indices(),
+        _ => return Err(UnbalancedError::NotOpen(from)),
+    };
+    chars.next();
+
+    let mut depth = 1;
+    for (i, c) in chars {
+        // Checking `close` first, allows `open` and `close` to be the same
+        if c == close {
+            depth -= 1;
+            if depth == 0 {
+                return Ok(from..(from + i + c.len_utf8()));
+            }
+        } else if c == open {
+            depth += 1;
+        }
+    }
+
+    Err(UnbalancedError::Unclosed {
+        end: text.len(),
+        depth,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CharRangesExt, UnbalancedError};
+
+    #[test]
+    fn test_find_balanced_range() {
+        let text = "f(a, (b, (c)), d) + (e)";
+        assert_eq!(text.find_balanced_range('(', ')', 1), Ok(1..17));
+        assert_eq!(text.find_balanced_range('(', ')', 5), Ok(5..13));
+        assert_eq!(text.find_balanced_range('(', ')', 9), Ok(9..12));
+        assert_eq!(text.find_balanced_range('(', ')', 20), Ok(20..23));
+        assert_eq!(&text[1..17], "(a, (b, (c)), d)");
+
+        // Adjacent pairs
+        let text = "()(())[]";
+        assert_eq!(text.find_balanced_range('(', ')', 0), Ok(0..2));
+        assert_eq!(text.find_balanced_range('(', ')', 2), Ok(2..6));
+        assert_eq!(text.find_balanced_range('(', ')', 3), Ok(3..5));
+        assert_eq!(text.find_balanced_range('[', ']', 6), Ok(6..8));
+    }
+
+    #[test]
+    fn test_find_balanced_range_multi_byte() {
+        let text = "「a「🗻」」「∈」";
+        assert_eq!(text.find_balanced_range('「', '」', 0), Ok(0..17));
+        assert_eq!(text.find_balanced_range('「', '」', 4), Ok(4..14));
+        assert_eq!(text.find_balanced_range('「', '」', 17), Ok(17..26));
+        assert_eq!(&text[4..14], "「🗻」");
+
+        let text = "${ a ${ 🌏 } }";
+        let (r, _c) = text.char_ranges().find(|(_r, c)| *c == '{').unwrap();
+        assert_eq!(text.find_balanced_range('{', '}', r.start), Ok(1..16));
+    }
+
+    #[test]
+    fn test_find_balanced_range_unbalanced() {
+        let text = "((a) (b";
+        assert_eq!(
+            text.find_balanced_range('(', ')', 0),
+            Err(UnbalancedError::Unclosed { end: 7, depth: 2 })
+        );
+        assert_eq!(
+            text.find_balanced_range('(', ')', 5),
+            Err(UnbalancedError::Unclosed { end: 7, depth: 1 })
+        );
+        assert_eq!(text.find_balanced_range('(', ')', 1), Ok(1..4));
+
+        // Not pointing at `open`
+        assert_eq!(
+            text.find_balanced_range('(', ')', 2),
+            Err(UnbalancedError::NotOpen(2))
+        );
+        assert_eq!(
+            text.find_balanced_range('(', ')', 7),
+            Err(UnbalancedError::NotOpen(7))
+        );
+        assert_eq!(
+            text.find_balanced_range('(', ')', 100),
+            Err(UnbalancedError::NotOpen(100))
+        );
+        assert_eq!(
+            "「」".find_balanced_range('「', '」', 1),
+            Err(UnbalancedError::NotOpen(1))
+        );
+    }
+
+    #[test]
+    fn test_find_balanced_range_same_delimiter() {
+        let text = "a |b| |c|";
+        assert_eq!(text.find_balanced_range('|', '|', 2), Ok(2..5));
+        assert_eq!(text.find_balanced_range('|', '|', 6), Ok(6..9));
+        assert_eq!(
+            text.find_balanced_range('|', '|', 8),
+            Err(UnbalancedError::Unclosed { end: 9, depth: 1 })
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use core::fmt;
+use core::iter::FusedIterator;
+
+use crate::CharRangesOffset;
+
+/// An iterator over the [`char`] boundaries of a string, i.e. the byte
+/// positions where [`char`]s start, including the end of the string.
+///
+/// Both the start and end of the remaining substring are produced
+/// exactly once, i.e. an empty substring produces a single position.
+/// [`char`]s are not decoded, only their leading bytes are scanned.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRangesExt::char_boundaries()`] for more information.
+///
+/// [`CharRangesExt::char_boundaries()`]: crate::CharRangesExt::char_boundaries
+#[derive(Clone)]
+pub struct CharBoundaries<'a> {
+    iter: CharRangesOffset<'a>,
+    /// Whether the last boundary, where the front and back meet,
+    /// has been produced.
+    done: bool,
+}
+
+impl<'a> CharBoundaries<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>) -> Self {
+        Self { iter, done: false }
+    }
+
+    /// Returns the remaining substring between the boundaries, which
+    /// have not yet been produced.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+}
+
+impl Iterator for CharBoundaries<'_> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let pos = self.iter.iter.start;
+        if self.iter.as_str().is_empty() {
+            self.done = true;
+        } else {
+            _ = self.iter.advance_by(1);
+        }
+        Some(self.iter.apply_offset_to(pos))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        let len = self.iter.as_str().len();
+        (len.div_ceil(4) + 1, Some(len + 1))
+    }
+}
+
+impl DoubleEndedIterator for CharBoundaries<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let pos = self.iter.iter.end;
+        if self.iter.as_str().is_empty() {
+            self.done = true;
+        } else {
+            _ = self.iter.advance_back_by(1);
+        }
+        Some(self.iter.apply_offset_to(pos))
+    }
+}
+
+impl FusedIterator for CharBoundaries<'_> {}
+
+impl fmt::Debug for CharBoundaries<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CharBoundaries(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use crate::CharRangesExt;
+
+    const TEXT: &str = "Hello 🗻12∈45🌏 World ¢";
+
+    #[test]
+    fn test_char_boundaries() {
+        let boundaries = TEXT.char_boundaries().collect::<Vec<_>>();
+        assert_eq!(boundaries.first(), Some(&0));
+        assert_eq!(boundaries.last(), Some(&TEXT.len()));
+        assert_eq!(boundaries.len(), TEXT.boundary_count());
+        assert_eq!(boundaries.len(), TEXT.chars().count() + 1);
+
+        for pair in boundaries.windows(2) {
+            assert!((1..=4).contains(&(pair[1] - pair[0])));
+        }
+
+        // Adjacent pairs are the `char` ranges
+        let ranges = boundaries.windows(2).map(|pair| pair[0]..pair[1]);
+        assert!(ranges.eq(TEXT.char_ranges().map(|(r, _c)| r)));
+
+        // Reverse is the mirror sequence
+        let mut rev = TEXT.char_boundaries().rev().collect::<Vec<_>>();
+        rev.reverse();
+        assert_eq!(rev, boundaries);
+
+        assert!("".char_boundaries().eq([0]));
+        assert!("".char_boundaries().rev().eq([0]));
+        assert_eq!("".boundary_count(), 1);
+    }
+
+    #[test]
+    fn test_char_boundaries_both_ends() {
+        let mut boundaries = "a🌏b".char_boundaries();
+        assert_eq!(boundaries.next(), Some(0));
+        assert_eq!(boundaries.next_back(), Some(6));
+        assert_eq!(boundaries.as_str(), "🌏");
+        assert_eq!(boundaries.next_back(), Some(5));
+        assert_eq!(boundaries.next(), Some(1));
+        // The front and back meet at the same position
+        assert_eq!(boundaries.next_back(), None);
+        assert_eq!(boundaries.next(), None);
+
+        let mut boundaries = "a🌏b".char_boundaries();
+        assert_eq!(boundaries.next(), Some(0));
+        assert_eq!(boundaries.next(), Some(1));
+        assert_eq!(boundaries.next_back(), Some(6));
+        assert_eq!(boundaries.next_back(), Some(5));
+        assert_eq!(boundaries.next(), None);
+    }
+
+    #[test]
+    fn test_char_boundaries_offset() {
+        let boundaries = TEXT[6..].char_ranges_offset(6).boundaries();
+        assert_eq!(boundaries.offset(), 6);
+        assert!(boundaries.eq(TEXT.char_boundaries().skip(6)));
+
+        let mut chars = TEXT.char_ranges();
+        chars.advance_by(6).unwrap();
+        chars.advance_back_by(9).unwrap();
+        assert!(chars.boundaries().eq([6, 10, 11, 12, 15, 16, 17]));
+    }
+}
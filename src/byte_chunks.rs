@@ -0,0 +1,187 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::{CharRangesExt, CharRangesOffset, CharTooLargeError};
+
+/// An iterator over chunks of a string and their start and end byte
+/// positions, where each chunk is at most a maximum number of bytes,
+/// and never splits a [`char`].
+///
+/// Chunks are produced greedily from the front, i.e. every chunk is as
+/// large as possible. A [`char`] larger than the maximum produces a
+/// [`CharTooLargeError`], after which iteration continues.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRanges::chunk_by_bytes()`] for more information.
+///
+/// [`CharRanges::chunk_by_bytes()`]: crate::CharRanges::chunk_by_bytes
+#[derive(Clone)]
+pub struct ByteChunks<'a> {
+    iter: CharRangesOffset<'a>,
+    max_bytes: usize,
+}
+
+impl<'a> ByteChunks<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>, max_bytes: usize) -> Self {
+        Self { iter, max_bytes }
+    }
+
+    /// Returns the maximum number of bytes per chunk.
+    #[inline]
+    pub fn max_bytes(&self) -> usize {
+        self.max_bytes
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+}
+
+impl<'a> Iterator for ByteChunks<'a> {
+    type Item = Result<(Range<usize>, &'a str), CharTooLargeError>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = self.iter.as_str();
+        if rest.is_empty() {
+            return None;
+        }
+
+        let len = CharRangesExt::floor_char_boundary(rest, self.max_bytes);
+        if len == 0 {
+            let (r, _c) = self.iter.next()?;
+            return Some(Err(CharTooLargeError::new(r, self.max_bytes)));
+        }
+
+        let chars = &mut self.iter.iter;
+        let start = chars.start;
+        chars.start += len;
+        let r = self.iter.apply_offset(start..(start + len));
+        Some(Ok((r, &rest[..len])))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.iter.as_str().len();
+        (usize::from(len > 0), Some(len))
+    }
+}
+
+impl FusedIterator for ByteChunks<'_> {}
+
+impl fmt::Debug for ByteChunks<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ByteChunks(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::String;
+    use std::vec::Vec;
+
+    use crate::CharRangesExt;
+
+    const TEXT: &str = "Hello 🗻12∈45🌏 World ¢";
+
+    #[test]
+    fn test_chunk_by_bytes() {
+        // Exact fit
+        let chunks = "abcdef".char_ranges().chunk_by_bytes(3);
+        assert!(chunks.eq([Ok((0..3, "abc")), Ok((3..6, "def"))]));
+
+        let chunks = "🗻🌏".char_ranges().chunk_by_bytes(4);
+        assert!(chunks.eq([Ok((0..4, "🗻")), Ok((4..8, "🌏"))]));
+
+        let chunks = "🗻🌏".char_ranges().chunk_by_bytes(7);
+        assert!(chunks.eq([Ok((0..4, "🗻")), Ok((4..8, "🌏"))]));
+
+        assert_eq!("".char_ranges().chunk_by_bytes(4).next(), None);
+    }
+
+    #[test]
+    fn test_chunk_by_bytes_reassemble() {
+        for max_bytes in 4..(TEXT.len() + 2) {
+            let chunks = TEXT
+                .char_ranges()
+                .chunk_by_bytes(max_bytes)
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+
+            let mut text = String::new();
+            let mut end = 0;
+            for (i, (r, chunk)) in chunks.iter().enumerate() {
+                assert_eq!(r.start, end);
+                assert_eq!(&TEXT[r.clone()], *chunk);
+                assert!(!chunk.is_empty() && (chunk.len() <= max_bytes));
+
+                // As large as possible
+                if let Some(c) = TEXT[r.end..].chars().next() {
+                    assert!(chunk.len() + c.len_utf8() > max_bytes, "chunk {i}");
+                }
+
+                text.push_str(chunk);
+                end = r.end;
+            }
+            assert_eq!(text, TEXT);
+        }
+    }
+
+    #[test]
+    fn test_chunk_by_bytes_too_large() {
+        let items = "a∈b".char_ranges().chunk_by_bytes(1).collect::<Vec<_>>();
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0], Ok((0..1, "a")));
+        let err = items[1].clone().unwrap_err();
+        assert_eq!(err.range(), 1..4);
+        assert_eq!(err.max_bytes(), 1);
+        assert_eq!(items[2], Ok((4..5, "b")));
+
+        // Every `char` is too large, but progress is still made
+        let chunks = TEXT.char_ranges().chunk_by_bytes(0);
+        assert_eq!(chunks.count(), TEXT.chars().count());
+        assert!(TEXT
+            .char_ranges()
+            .chunk_by_bytes(0)
+            .all(|item| item.is_err()));
+    }
+
+    #[test]
+    fn test_chunk_by_bytes_offset() {
+        let start = 6;
+        let chunks = TEXT[start..]
+            .char_ranges_offset(start)
+            .chunk_by_bytes(6)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            chunks,
+            [
+                Ok((6..12, "🗻12")),
+                Ok((12..17, "∈45")),
+                Ok((17..23, "🌏 W")),
+                Ok((23..28, "orld ")),
+                Ok((28..30, "¢")),
+            ],
+        );
+
+        let mut chars = TEXT.char_ranges();
+        chars.advance_by(6).unwrap();
+        assert!(chars.chunk_by_bytes(6).eq(chunks));
+    }
+}
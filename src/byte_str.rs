@@ -0,0 +1,88 @@
+use core::ops::Range;
+
+use bstr::BStr;
+#[cfg(feature = "alloc")]
+use bstr::BString;
+
+use crate::{Utf8CharRanges, Utf8CharRangesExt};
+
+/// Converts an item produced by bstr's [`char_indices()`], i.e. the start
+/// and end byte positions and the [`char`], into the start and end byte
+/// positions and [`char`] produced by this crate.
+///
+/// # Example
+///
+/// ```rust
+/// use bstr::ByteSlice;
+/// use char_ranges::{from_bstr_char_index, Utf8CharRangesExt};
+///
+/// let bs = b"\xE2\x98\x83\xFF\xF0\x9D\x9E\x83\xE2\x98\x61";
+///
+/// let chars = bs.char_indices().map(from_bstr_char_index);
+/// assert!(chars.eq(bs.utf8_char_ranges_lossy()));
+/// ```
+///
+/// [`char_indices()`]: bstr::ByteSlice::char_indices
+#[inline]
+pub fn from_bstr_char_index((start, end, c): (usize, usize, char)) -> (Range<usize>, char) {
+    (start..end, c)
+}
+
+/// Converts the start and end byte positions and [`char`] produced by
+/// this crate, into an item as produced by bstr's [`char_indices()`],
+/// i.e. the start and end byte positions and the [`char`].
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::{to_bstr_char_index, CharRangesExt};
+///
+/// let mut chars = "a🗻".char_ranges().map(to_bstr_char_index);
+/// assert_eq!(chars.next(), Some((0, 1, 'a')));
+/// assert_eq!(chars.next(), Some((1, 5, '🗻')));
+/// assert_eq!(chars.next(), None);
+/// ```
+///
+/// [`char_indices()`]: bstr::ByteSlice::char_indices
+#[inline]
+pub fn to_bstr_char_index((r, c): (Range<usize>, char)) -> (usize, usize, char) {
+    (r.start, r.end, c)
+}
+
+/// The [`char`]s of a [`BStr`] are decoded the same as for `[u8]`, i.e.
+/// invalid UTF-8 byte sequences are split and replaced the same way as
+/// bstr's [`char_indices()`].
+///
+/// # Example
+///
+/// ```rust
+/// use bstr::BStr;
+/// use char_ranges::Utf8CharRangesExt;
+///
+/// let bs = BStr::new(b"a\xFF\xE2\x88\x88");
+///
+/// let mut chars = bs.utf8_char_ranges_lossy();
+/// assert_eq!(chars.next(), Some((0..1, 'a')));
+/// assert_eq!(chars.next(), Some((1..2, '\u{FFFD}')));
+/// assert_eq!(chars.next(), Some((2..5, '∈')));
+/// assert_eq!(chars.next(), None);
+/// ```
+///
+/// [`char_indices()`]: bstr::ByteSlice::char_indices
+impl Utf8CharRangesExt for BStr {
+    #[inline]
+    fn utf8_char_ranges(&self) -> Utf8CharRanges<'_> {
+        <[u8]>::utf8_char_ranges(self)
+    }
+}
+
+/// The [`char`]s of a [`BString`] are decoded the same as for `[u8]`.
+///
+/// See the implementation for [`BStr`] for more information.
+#[cfg(feature = "alloc")]
+impl Utf8CharRangesExt for BString {
+    #[inline]
+    fn utf8_char_ranges(&self) -> Utf8CharRanges<'_> {
+        <[u8]>::utf8_char_ranges(self)
+    }
+}
@@ -0,0 +1,278 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::{is_utf8_continuation_byte, utf8_char_width, CharRangesOffset};
+
+/// An iterator over the bytes of a string, producing the byte position of
+/// each byte, the byte, and the start and end byte positions of the
+/// [`char`] it belongs to.
+///
+/// All bytes of a multi-byte [`char`] share the same range. The range is
+/// computed once per [`char`], from its leading byte, i.e. [`char`]s are
+/// not decoded.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRangesExt::bytes_with_char_ranges()`] for more information.
+///
+/// [`CharRangesExt::bytes_with_char_ranges()`]: crate::CharRangesExt::bytes_with_char_ranges
+#[derive(Clone)]
+pub struct BytesWithCharRanges<'a> {
+    /// The remaining whole [`char`]s, between `front` and `back`.
+    iter: CharRangesOffset<'a>,
+    /// The range of the [`char`] iterated from the front.
+    front_char: Range<usize>,
+    /// The remaining bytes of `front_char`.
+    front: Range<usize>,
+    /// The range of the [`char`] iterated from the back.
+    back_char: Range<usize>,
+    /// The remaining bytes of `back_char`.
+    back: Range<usize>,
+}
+
+impl<'a> BytesWithCharRanges<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>) -> Self {
+        let start = iter.iter.start;
+        let end = iter.iter.end;
+        Self {
+            iter,
+            front_char: start..start,
+            front: start..start,
+            back_char: end..end,
+            back: end..end,
+        }
+    }
+
+    /// Returns the remaining bytes.
+    ///
+    /// This is not necessarily a valid [`str`], as iteration can stop
+    /// within a [`char`].
+    #[inline]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        let chars = &self.iter.iter;
+        let start = match (self.front.is_empty(), chars.start < chars.end) {
+            (false, _) => self.front.start,
+            (true, true) => chars.start,
+            (true, false) => self.back.start,
+        };
+        let end = match (self.back.is_empty(), chars.start < chars.end) {
+            (false, _) => self.back.end,
+            (true, true) => chars.end,
+            (true, false) => self.front.end,
+        };
+        chars.text.as_bytes().get(start..end).unwrap_or_default()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+
+    #[inline]
+    fn item(&self, i: usize, r: Range<usize>) -> (usize, u8, Range<usize>) {
+        let b = self.iter.iter.text.as_bytes()[i];
+        (self.iter.apply_offset_to(i), b, self.iter.apply_offset(r))
+    }
+}
+
+impl Iterator for BytesWithCharRanges<'_> {
+    type Item = (usize, u8, Range<usize>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front.is_empty() {
+            let chars = &mut self.iter.iter;
+            if chars.start < chars.end {
+                let start = chars.start;
+                chars.start += utf8_char_width(chars.text.as_bytes()[start]);
+
+                self.front_char = start..chars.start;
+                self.front = self.front_char.clone();
+            } else if !self.back.is_empty() {
+                // Only a part of the `char` iterated from the back remains
+                let i = self.back.start;
+                self.back.start += 1;
+                return Some(self.item(i, self.back_char.clone()));
+            } else {
+                return None;
+            }
+        }
+
+        let i = self.front.start;
+        self.front.start += 1;
+        Some(self.item(i, self.front_char.clone()))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.front.len() + self.iter.as_str().len() + self.back.len();
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for BytesWithCharRanges<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.back.is_empty() {
+            let chars = &mut self.iter.iter;
+            if chars.start < chars.end {
+                let end = chars.end;
+                let bytes = chars.text.as_bytes();
+                while is_utf8_continuation_byte(bytes[chars.end - 1]) {
+                    chars.end -= 1;
+                }
+                chars.end -= 1;
+
+                self.back_char = chars.end..end;
+                self.back = self.back_char.clone();
+            } else if !self.front.is_empty() {
+                // Only a part of the `char` iterated from the front remains
+                self.front.end -= 1;
+                return Some(self.item(self.front.end, self.front_char.clone()));
+            } else {
+                return None;
+            }
+        }
+
+        self.back.end -= 1;
+        Some(self.item(self.back.end, self.back_char.clone()))
+    }
+}
+
+impl ExactSizeIterator for BytesWithCharRanges<'_> {}
+
+impl FusedIterator for BytesWithCharRanges<'_> {}
+
+impl fmt::Debug for BytesWithCharRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BytesWithCharRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::String;
+    use std::vec::Vec;
+
+    use crate::CharRangesExt;
+
+    const CHARS: [char; 8] = ['a', '\n', '\u{80}', 'é', '∈', '⊂', '🌏', '🗻'];
+
+    #[test]
+    fn test_bytes_with_char_ranges() {
+        let text = "a∈🌏";
+
+        let bytes = text.bytes_with_char_ranges().collect::<Vec<_>>();
+        assert_eq!(
+            bytes,
+            [
+                (0, b'a', 0..1),
+                (1, 0xE2, 1..4),
+                (2, 0x88, 1..4),
+                (3, 0x88, 1..4),
+                (4, 0xF0, 4..8),
+                (5, 0x9F, 4..8),
+                (6, 0x8C, 4..8),
+                (7, 0x8F, 4..8),
+            ]
+        );
+
+        let mut rev = text.bytes_with_char_ranges().rev().collect::<Vec<_>>();
+        rev.reverse();
+        assert_eq!(rev, bytes);
+
+        assert_eq!("".bytes_with_char_ranges().next(), None);
+        assert_eq!("".bytes_with_char_ranges().next_back(), None);
+    }
+
+    #[test]
+    fn test_bytes_with_char_ranges_meet() {
+        // Front and back meet within the same char
+        let mut bytes = "🌏".bytes_with_char_ranges();
+        assert_eq!(bytes.next(), Some((0, 0xF0, 0..4)));
+        assert_eq!(bytes.next_back(), Some((3, 0x8F, 0..4)));
+        assert_eq!(bytes.len(), 2);
+        assert_eq!(bytes.as_bytes(), [0x9F, 0x8C]);
+        assert_eq!(bytes.next_back(), Some((2, 0x8C, 0..4)));
+        assert_eq!(bytes.next_back(), Some((1, 0x9F, 0..4)));
+        assert_eq!(bytes.next_back(), None);
+        assert_eq!(bytes.next(), None);
+
+        let mut bytes = "∈".bytes_with_char_ranges();
+        assert_eq!(bytes.next_back(), Some((2, 0x88, 0..3)));
+        assert_eq!(bytes.next(), Some((0, 0xE2, 0..3)));
+        assert_eq!(bytes.next(), Some((1, 0x88, 0..3)));
+        assert_eq!(bytes.next(), None);
+    }
+
+    #[test]
+    fn test_bytes_with_char_ranges_offset() {
+        let text = "x∈";
+
+        let bytes = text.char_ranges_offset(10).bytes_with_char_ranges();
+        assert_eq!(bytes.offset(), 10);
+        assert!(bytes.eq([
+            (10, b'x', 10..11),
+            (11, 0xE2, 11..14),
+            (12, 0x88, 11..14),
+            (13, 0x88, 11..14),
+        ]));
+    }
+
+    #[test]
+    fn test_bytes_with_char_ranges_random() {
+        let mut next = crate::test_util::random();
+
+        for _ in 0..300 {
+            let len = next() % 24;
+            let text = (0..len)
+                .map(|_| CHARS[next() % CHARS.len()])
+                .collect::<String>();
+
+            let bytes = text.bytes_with_char_ranges();
+            assert_eq!(bytes.len(), text.len());
+
+            // Byte values match `as_bytes()`
+            let items = bytes.clone().collect::<Vec<_>>();
+            assert!(items.iter().map(|&(i, b, _)| (i, b)).eq(text
+                .as_bytes()
+                .iter()
+                .copied()
+                .enumerate()));
+
+            // Grouping consecutive items by range reproduces `char_ranges()`
+            let mut ranges = items.iter().map(|(_, _, r)| r.clone()).collect::<Vec<_>>();
+            ranges.dedup();
+            assert!(ranges.into_iter().eq(text.char_ranges().map(|(r, _c)| r)));
+
+            // Alternating from both ends
+            let mut bytes = bytes;
+            let mut front = Vec::new();
+            let mut back = Vec::new();
+            loop {
+                if next().is_multiple_of(2) {
+                    match bytes.next() {
+                        Some(item) => front.push(item),
+                        None => break,
+                    }
+                } else {
+                    match bytes.next_back() {
+                        Some(item) => back.push(item),
+                        None => break,
+                    }
+                }
+                assert_eq!(bytes.len(), text.len() - front.len() - back.len());
+                assert_eq!(bytes.as_bytes().len(), bytes.len());
+            }
+            back.reverse();
+            front.extend(back);
+            assert_eq!(front, items, "{text:?}");
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use core::ops::Range;
+
+#[cfg(feature = "unicode-segmentation")]
+use unicode_segmentation::GraphemeCursor;
+
+/// Returns the range of the [`char`] before `pos`, or both `"\r\n"` if
+/// `pos` is after a `"\r\n"`.
+///
+/// Returns `None` if `pos == 0`. `pos` must be a [`char`] boundary.
+#[cfg(not(feature = "unicode-segmentation"))]
+pub(crate) fn backspace_range(text: &str, pos: usize) -> Option<Range<usize>> {
+    let before = &text[..pos];
+    if before.ends_with("\r\n") {
+        return Some((pos - 2)..pos);
+    }
+    let c = before.chars().next_back()?;
+    Some((pos - c.len_utf8())..pos)
+}
+
+/// Returns the range of the [`char`] after `pos`, or both `"\r\n"` if
+/// `pos` is before a `"\r\n"`.
+///
+/// Returns `None` if `pos == len`. `pos` must be a [`char`] boundary.
+#[cfg(not(feature = "unicode-segmentation"))]
+pub(crate) fn delete_forward_range(text: &str, pos: usize) -> Option<Range<usize>> {
+    let after = &text[pos..];
+    if after.starts_with("\r\n") {
+        return Some(pos..(pos + 2));
+    }
+    let c = after.chars().next()?;
+    Some(pos..(pos + c.len_utf8()))
+}
+
+/// Returns the range from the previous extended grapheme cluster
+/// boundary before `pos` to `pos`.
+///
+/// Returns `None` if `pos == 0`. `pos` must be a [`char`] boundary.
+#[cfg(feature = "unicode-segmentation")]
+pub(crate) fn backspace_range(text: &str, pos: usize) -> Option<Range<usize>> {
+    let mut cursor = GraphemeCursor::new(pos, text.len(), true);
+    // The whole text is given as the chunk, so it is never incomplete
+    let start = cursor.prev_boundary(text, 0).ok()??;
+    Some(start..pos)
+}
+
+/// Returns the range from `pos` to the next extended grapheme cluster
+/// boundary after `pos`.
+///
+/// Returns `None` if `pos == len`. `pos` must be a [`char`] boundary.
+#[cfg(feature = "unicode-segmentation")]
+pub(crate) fn delete_forward_range(text: &str, pos: usize) -> Option<Range<usize>> {
+    let mut cursor = GraphemeCursor::new(pos, text.len(), true);
+    // The whole text is given as the chunk, so it is never incomplete
+    let end = cursor.next_boundary(text, 0).ok()??;
+    Some(pos..end)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::CharRangesExt;
+
+    #[test]
+    fn test_backspace_range() {
+        let text = "ab 🌏\r\nc";
+        assert_eq!(text.backspace_range(0), None);
+        assert_eq!(text.backspace_range(1), Some(0..1));
+        assert_eq!(text.backspace_range(2), Some(1..2));
+        assert_eq!(text.backspace_range(7), Some(3..7));
+        // CRLF is deleted as a whole
+        assert_eq!(text.backspace_range(9), Some(7..9));
+        assert_eq!(text.backspace_range(10), Some(9..10));
+        assert_eq!(text.backspace_range(11), None);
+
+        // Within '🌏', so snapped down to the start of it
+        assert_eq!(text.backspace_range(5), Some(2..3));
+
+        // Between '\r' and '\n'
+        assert_eq!("a\r\n".backspace_range(2), Some(1..2));
+        assert_eq!("a\n".backspace_range(2), Some(1..2));
+        assert_eq!("".backspace_range(0), None);
+    }
+
+    #[test]
+    fn test_delete_forward_range() {
+        let text = "ab 🌏\r\nc";
+        assert_eq!(text.delete_forward_range(0), Some(0..1));
+        assert_eq!(text.delete_forward_range(3), Some(3..7));
+        // CRLF is deleted as a whole
+        assert_eq!(text.delete_forward_range(7), Some(7..9));
+        assert_eq!(text.delete_forward_range(9), Some(9..10));
+        assert_eq!(text.delete_forward_range(10), None);
+        assert_eq!(text.delete_forward_range(11), None);
+
+        // Within '🌏', so snapped down to the start of it
+        assert_eq!(text.delete_forward_range(5), Some(3..7));
+
+        assert_eq!("a\r\n".delete_forward_range(2), Some(2..3));
+        assert_eq!("".delete_forward_range(0), None);
+    }
+
+    #[cfg(not(feature = "unicode-segmentation"))]
+    #[test]
+    fn test_backspace_range_chars() {
+        // "e" followed by U+0301 COMBINING ACUTE ACCENT
+        let text = "cafe\u{301}!";
+        assert_eq!(text.backspace_range(6), Some(4..6));
+        assert_eq!(text.backspace_range(4), Some(3..4));
+        assert_eq!(text.delete_forward_range(3), Some(3..4));
+
+        // Family emoji, i.e. 3 emoji joined by U+200D ZERO WIDTH JOINER
+        let text = "a👨\u{200D}👩\u{200D}👧";
+        assert_eq!(text.backspace_range(text.len()), Some(15..19));
+        assert_eq!(text.backspace_range(15), Some(12..15));
+        assert_eq!(text.delete_forward_range(1), Some(1..5));
+    }
+
+    #[cfg(feature = "unicode-segmentation")]
+    #[test]
+    fn test_backspace_range_graphemes() {
+        // "e" followed by U+0301 COMBINING ACUTE ACCENT
+        let text = "cafe\u{301}!";
+        assert_eq!(text.backspace_range(6), Some(3..6));
+        assert_eq!(text.backspace_range(7), Some(6..7));
+        assert_eq!(text.delete_forward_range(3), Some(3..6));
+        // Between 'e' and the accent
+        assert_eq!(text.backspace_range(4), Some(3..4));
+
+        // Family emoji, i.e. 3 emoji joined by U+200D ZERO WIDTH JOINER
+        let text = "a👨\u{200D}👩\u{200D}👧";
+        assert_eq!(text.backspace_range(text.len()), Some(1..19));
+        assert_eq!(text.delete_forward_range(1), Some(1..19));
+        assert_eq!(text.backspace_range(1), Some(0..1));
+    }
+}
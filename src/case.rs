@@ -0,0 +1,268 @@
+use core::char::{ToLowercase, ToUppercase};
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::CharRangesOffset;
+
+/// An iterator over [`char`]s mapped to lowercase, and the start and end
+/// byte positions of the source [`char`] they originate from.
+///
+/// See [`CharRangesExt::char_ranges_lowercase()`](crate::CharRangesExt::char_ranges_lowercase).
+pub type CharRangesLowercase<'a> = CharRangesCaseMap<'a, ToLowercase>;
+
+/// An iterator over [`char`]s mapped to uppercase, and the start and end
+/// byte positions of the source [`char`] they originate from.
+///
+/// See [`CharRangesExt::char_ranges_uppercase()`](crate::CharRangesExt::char_ranges_uppercase).
+pub type CharRangesUppercase<'a> = CharRangesCaseMap<'a, ToUppercase>;
+
+/// An iterator over case mapped [`char`]s, and the start and end byte
+/// positions of the source [`char`] they originate from.
+///
+/// When a single source [`char`] maps to multiple [`char`]s, e.g.
+/// `'ß'` uppercases to `"SS"`, then all of them share the same range.
+///
+/// See [`CharRangesLowercase`] and [`CharRangesUppercase`].
+#[derive(Clone)]
+pub struct CharRangesCaseMap<'a, I> {
+    iter: CharRangesOffset<'a>,
+    map: fn(char) -> I,
+    front: Option<(Range<usize>, I)>,
+    back: Option<(Range<usize>, I)>,
+}
+
+impl<'a, I> CharRangesCaseMap<'a, I>
+where
+    I: DoubleEndedIterator<Item = char>,
+{
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>, map: fn(char) -> I) -> Self {
+        Self {
+            iter,
+            map,
+            front: None,
+            back: None,
+        }
+    }
+
+    /// Returns the remaining substring, of source [`char`]s that
+    /// have not yet been mapped.
+    ///
+    /// Note that if a source [`char`] mapped to multiple [`char`]s, then
+    /// it is no longer included, even if not all of them have been
+    /// returned yet.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "ßa";
+    ///
+    /// let mut chars = text.char_ranges_uppercase();
+    /// assert_eq!(chars.as_str(), "ßa");
+    ///
+    /// assert_eq!(chars.next(), Some((0..2, 'S')));
+    /// assert_eq!(chars.as_str(), "a");
+    ///
+    /// assert_eq!(chars.next(), Some((0..2, 'S')));
+    /// assert_eq!(chars.as_str(), "a");
+    /// ```
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+}
+
+impl<I> Iterator for CharRangesCaseMap<'_, I>
+where
+    I: DoubleEndedIterator<Item = char>,
+{
+    type Item = (Range<usize>, char);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((r, mapped)) = &mut self.front {
+                if let Some(c) = mapped.next() {
+                    return Some((r.clone(), c));
+                }
+                self.front = None;
+            }
+
+            match self.iter.next() {
+                Some((r, c)) => self.front = Some((r, (self.map)(c))),
+                None => {
+                    let (r, mapped) = self.back.as_mut()?;
+                    let c = mapped.next();
+                    let item = c.map(|c| (r.clone(), c));
+                    if item.is_none() {
+                        self.back = None;
+                    }
+                    return item;
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Every source `char` maps to at least 1 and at most 3 `char`s
+        let (lower, upper) = self.iter.size_hint();
+        let buffered = |mapped: &Option<(Range<usize>, I)>| match mapped {
+            Some((_, mapped)) => mapped.size_hint(),
+            None => (0, Some(0)),
+        };
+        let (front_lower, front_upper) = buffered(&self.front);
+        let (back_lower, back_upper) = buffered(&self.back);
+
+        let lower = lower.saturating_add(front_lower).saturating_add(back_lower);
+        let upper = match (upper, front_upper, back_upper) {
+            (Some(upper), Some(front), Some(back)) => upper
+                .checked_mul(3)
+                .and_then(|upper| upper.checked_add(front))
+                .and_then(|upper| upper.checked_add(back)),
+            _ => None,
+        };
+        (lower, upper)
+    }
+}
+
+impl<I> DoubleEndedIterator for CharRangesCaseMap<'_, I>
+where
+    I: DoubleEndedIterator<Item = char>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((r, mapped)) = &mut self.back {
+                if let Some(c) = mapped.next_back() {
+                    return Some((r.clone(), c));
+                }
+                self.back = None;
+            }
+
+            match self.iter.next_back() {
+                Some((r, c)) => self.back = Some((r, (self.map)(c))),
+                None => {
+                    let (r, mapped) = self.front.as_mut()?;
+                    let c = mapped.next_back();
+                    let item = c.map(|c| (r.clone(), c));
+                    if item.is_none() {
+                        self.front = None;
+                    }
+                    return item;
+                }
+            }
+        }
+    }
+}
+
+impl<I> FusedIterator for CharRangesCaseMap<'_, I> where I: DoubleEndedIterator<Item = char> {}
+
+impl<I> fmt::Debug for CharRangesCaseMap<'_, I>
+where
+    I: DoubleEndedIterator<Item = char> + Clone,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CharRangesCaseMap(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use crate::CharRangesExt;
+
+    #[test]
+    fn test_lowercase_ascii() {
+        let text = "Hello WORLD";
+
+        let mut chars = text.char_ranges_lowercase();
+        for ((r, c), (r_lower, c_lower)) in text.char_ranges().zip(&mut chars) {
+            assert_eq!(r, r_lower);
+            assert_eq!(c.to_ascii_lowercase(), c_lower);
+        }
+        assert_eq!(chars.next(), None);
+
+        let mut chars = text.char_ranges_uppercase();
+        for ((r, c), (r_upper, c_upper)) in text.char_ranges().zip(&mut chars) {
+            assert_eq!(r, r_upper);
+            assert_eq!(c.to_ascii_uppercase(), c_upper);
+        }
+        assert_eq!(chars.next(), None);
+    }
+
+    #[test]
+    fn test_uppercase_sharp_s() {
+        let text = "Straße";
+
+        let mut chars = text.char_ranges_uppercase();
+        assert_eq!(chars.next(), Some((0..1, 'S')));
+        assert_eq!(chars.next(), Some((1..2, 'T')));
+        assert_eq!(chars.next(), Some((2..3, 'R')));
+        assert_eq!(chars.next(), Some((3..4, 'A')));
+        assert_eq!(chars.next(), Some((4..6, 'S')));
+        assert_eq!(chars.next(), Some((4..6, 'S')));
+        assert_eq!(chars.next(), Some((6..7, 'E')));
+        assert_eq!(chars.next(), None);
+
+        let mut chars = text.char_ranges_lowercase();
+        assert_eq!(chars.next(), Some((0..1, 's')));
+        assert_eq!(chars.nth(3), Some((4..6, 'ß')));
+        assert_eq!(chars.next(), Some((6..7, 'e')));
+        assert_eq!(chars.next(), None);
+    }
+
+    #[test]
+    fn test_turkish_i() {
+        // U+0130 LATIN CAPITAL LETTER I WITH DOT ABOVE
+        // U+0131 LATIN SMALL LETTER DOTLESS I
+        let text = "İı";
+
+        let mut chars = text.char_ranges_lowercase();
+        assert_eq!(chars.next(), Some((0..2, 'i')));
+        assert_eq!(chars.next(), Some((0..2, '\u{307}')));
+        assert_eq!(chars.next(), Some((2..4, 'ı')));
+        assert_eq!(chars.next(), None);
+
+        let mut chars = text.char_ranges_uppercase();
+        assert_eq!(chars.next(), Some((0..2, 'İ')));
+        assert_eq!(chars.next(), Some((2..4, 'I')));
+        assert_eq!(chars.next(), None);
+    }
+
+    #[test]
+    fn test_case_map_next_back() {
+        let text = "ßİ";
+
+        let mut chars = text.char_ranges_uppercase();
+        assert_eq!(chars.next_back(), Some((2..4, 'İ')));
+        assert_eq!(chars.next_back(), Some((0..2, 'S')));
+        assert_eq!(chars.next(), Some((0..2, 'S')));
+        assert_eq!(chars.next(), None);
+        assert_eq!(chars.next_back(), None);
+
+        let mut chars = text.char_ranges_lowercase();
+        assert_eq!(chars.next(), Some((0..2, 'ß')));
+        assert_eq!(chars.next_back(), Some((2..4, '\u{307}')));
+        assert_eq!(chars.next(), Some((2..4, 'i')));
+        assert_eq!(chars.next_back(), None);
+
+        let text = "Straße İs";
+        let mut forward = text.char_ranges_lowercase().collect::<Vec<_>>();
+        forward.reverse();
+        assert!(text.char_ranges_lowercase().rev().eq(forward));
+    }
+}
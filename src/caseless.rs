@@ -0,0 +1,185 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::CharRangesOffset;
+
+/// An iterator over the non-overlapping case-insensitive matches of a
+/// needle, and their start and end byte positions.
+///
+/// [`char`]s are compared using simple case folding, i.e. [`char`]s are
+/// equal if their [`to_lowercase()`] is equal, where [`char`]s which do
+/// not lowercase to a single [`char`] are compared as is. As a result,
+/// a match always has the same number of [`char`]s as the needle, while
+/// its byte length may differ.
+///
+/// Note: Full case folding is not supported, e.g. `"ß"` does not match
+/// `"ss"`, while it does match `"ẞ"`.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRangesExt::caseless_match_ranges()`] for more information.
+///
+/// [`to_lowercase()`]: char::to_lowercase
+/// [`CharRangesExt::caseless_match_ranges()`]: crate::CharRangesExt::caseless_match_ranges
+#[derive(Clone)]
+pub struct CaselessMatchRanges<'a, 'b> {
+    iter: CharRangesOffset<'a>,
+    needle: &'b str,
+}
+
+impl<'a, 'b> CaselessMatchRanges<'a, 'b> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>, needle: &'b str) -> Self {
+        Self { iter, needle }
+    }
+
+    /// Returns the needle.
+    #[inline]
+    pub fn needle(&self) -> &'b str {
+        self.needle
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+
+    /// Returns the end of the match at the front of `chars`, if any.
+    fn match_end(&self, mut chars: CharRangesOffset<'a>) -> Option<CharRangesOffset<'a>> {
+        for n in self.needle.chars() {
+            let (_r, c) = chars.next()?;
+            if fold(c) != fold(n) {
+                return None;
+            }
+        }
+        Some(chars)
+    }
+}
+
+/// Returns the simple case folding of `c`.
+#[inline]
+fn fold(c: char) -> char {
+    let mut lower = c.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(lower), None) => lower,
+        _ => c,
+    }
+}
+
+impl<'a> Iterator for CaselessMatchRanges<'a, '_> {
+    type Item = (Range<usize>, &'a str);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.needle.is_empty() {
+            self.iter.iter.start = self.iter.iter.end;
+            return None;
+        }
+
+        loop {
+            if let Some(rest) = self.match_end(self.iter) {
+                let start = self.iter.iter.start;
+                let end = rest.iter.start;
+                self.iter = rest;
+
+                let s = &self.iter.original_str()[start..end];
+                return Some((self.iter.apply_offset(start..end), s));
+            }
+
+            self.iter.next()?;
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, upper) = self.iter.size_hint();
+        (0, upper)
+    }
+}
+
+impl FusedIterator for CaselessMatchRanges<'_, '_> {}
+
+impl fmt::Debug for CaselessMatchRanges<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CaselessMatchRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use crate::CharRangesExt;
+
+    #[test]
+    fn test_caseless_match_ranges() {
+        let text = "warning: WARNING, Warning! wArNiNg";
+        let matches = text.caseless_match_ranges("Warning").collect::<Vec<_>>();
+        assert_eq!(
+            matches,
+            [
+                (0..7, "warning"),
+                (9..16, "WARNING"),
+                (18..25, "Warning"),
+                (27..34, "wArNiNg"),
+            ],
+        );
+
+        // Non-overlapping, left to right
+        let matches = "AaAaA".caseless_match_ranges("aa").collect::<Vec<_>>();
+        assert_eq!(matches, [(0..2, "Aa"), (2..4, "Aa")]);
+
+        let mut matches = text.char_ranges_offset(10).caseless_matches("warning");
+        assert_eq!(matches.offset(), 10);
+        assert_eq!(matches.needle(), "warning");
+        assert_eq!(matches.next(), Some((10..17, "warning")));
+        assert_eq!(matches.as_str(), ": WARNING, Warning! wArNiNg");
+    }
+
+    #[test]
+    fn test_caseless_match_ranges_cyrillic() {
+        let text = "Привет, ПРИВЕТ и привет";
+        let matches = text
+            .caseless_match_ranges("пРиВеТ")
+            .map(|(_r, s)| s)
+            .collect::<Vec<_>>();
+        assert_eq!(matches, ["Привет", "ПРИВЕТ", "привет"]);
+
+        for (r, s) in text.caseless_match_ranges("привет") {
+            assert_eq!(&text[r], s);
+        }
+    }
+
+    #[test]
+    fn test_caseless_match_ranges_sharp_s() {
+        // Simple folding only, 'ẞ' lowercases to 'ß'
+        let text = "Straße STRASSE STRAẞE";
+        let matches = text.caseless_match_ranges("straße").collect::<Vec<_>>();
+        assert_eq!(matches, [(0..7, "Straße"), (16..24, "STRAẞE")]);
+
+        // Full folding of 'ß' to "ss" is not supported
+        let matches = text.caseless_match_ranges("strasse").collect::<Vec<_>>();
+        assert_eq!(matches, [(8..15, "STRASSE")]);
+    }
+
+    #[test]
+    fn test_caseless_match_ranges_no_match() {
+        assert_eq!("warn warnin".caseless_match_ranges("warning").next(), None);
+        assert_eq!("".caseless_match_ranges("warning").next(), None);
+        assert_eq!("warning".caseless_match_ranges("").next(), None);
+        assert_eq!("wärning".caseless_match_ranges("WARNING").next(), None);
+    }
+}
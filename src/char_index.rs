@@ -0,0 +1,419 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::CharRangesOffset;
+
+/// An iterator over [`char`]s, their start and end [`char`] indices,
+/// and their start and end byte positions.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRanges::char_index_ranges()`] for more information.
+///
+/// [`CharRanges::char_index_ranges()`]: crate::CharRanges::char_index_ranges
+#[derive(Clone)]
+pub struct CharIndexRanges<'a> {
+    iter: CharRangesOffset<'a>,
+    char_offset: usize,
+    /// [`char`] index of the next [`char`] from the front,
+    /// excluding `char_offset`.
+    front: usize,
+    /// [`char`] index following the next [`char`] from the back,
+    /// excluding `char_offset`. Computed on the first [`next_back()`].
+    ///
+    /// [`next_back()`]: DoubleEndedIterator::next_back
+    back: Option<usize>,
+}
+
+impl<'a> CharIndexRanges<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>, char_offset: usize) -> Self {
+        Self {
+            iter,
+            char_offset,
+            front: 0,
+            back: None,
+        }
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all byte positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+
+    /// Returns the `char_offset` applied to all [`char`] indices.
+    #[inline]
+    pub fn char_offset(&self) -> usize {
+        self.char_offset
+    }
+
+    #[inline]
+    fn char_range(&self, i: usize) -> Range<usize> {
+        let start = match self.char_offset.checked_add(i) {
+            Some(start) => start,
+            None => panic!(
+                "char index {i} with offset {} overflows usize",
+                self.char_offset,
+            ),
+        };
+        start..(start + 1)
+    }
+
+    #[inline]
+    fn back(&mut self) -> usize {
+        match self.back {
+            Some(back) => back,
+            None => {
+                let back = self.front + self.iter.count();
+                self.back = Some(back);
+                back
+            }
+        }
+    }
+}
+
+impl Iterator for CharIndexRanges<'_> {
+    type Item = (Range<usize>, Range<usize>, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (r, c) = self.iter.next()?;
+        let i = self.front;
+        self.front += 1;
+        Some((self.char_range(i), r, c))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        match self.back {
+            Some(back) => back - self.front,
+            None => self.iter.count(),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.back {
+            Some(back) => {
+                let len = back - self.front;
+                (len, Some(len))
+            }
+            None => self.iter.size_hint(),
+        }
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        match self.iter.nth(n) {
+            Some((r, c)) => {
+                let i = self.front + n;
+                self.front = i + 1;
+                Some((self.char_range(i), r, c))
+            }
+            None => {
+                if let Some(back) = self.back {
+                    self.front = back;
+                }
+                None
+            }
+        }
+    }
+}
+
+/// The [`char`] index of the last [`char`] requires counting the
+/// remaining [`char`]s, which is done once on the first call to
+/// [`next_back()`], [`nth_back()`], or [`last()`], i.e. that first
+/// call is _O(n)_.
+///
+/// [`next_back()`]: DoubleEndedIterator::next_back
+/// [`nth_back()`]: DoubleEndedIterator::nth_back
+/// [`last()`]: Iterator::last
+impl DoubleEndedIterator for CharIndexRanges<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let back = self.back();
+        let (r, c) = self.iter.next_back()?;
+        let i = back - 1;
+        self.back = Some(i);
+        Some((self.char_range(i), r, c))
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        let back = self.back();
+        match self.iter.nth_back(n) {
+            Some((r, c)) => {
+                let i = back - n - 1;
+                self.back = Some(i);
+                Some((self.char_range(i), r, c))
+            }
+            None => {
+                self.back = Some(self.front);
+                None
+            }
+        }
+    }
+}
+
+impl FusedIterator for CharIndexRanges<'_> {}
+
+impl fmt::Debug for CharIndexRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CharIndexRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+/// An iterator over [`char`]s, their [`char`] index, and their start
+/// and end byte positions.
+///
+/// This is the same as [`CharIndexRanges`], except that the [`char`] index
+/// is produced as a single `usize`. Unlike <code>.[enumerate()]</code>,
+/// the index remains correct when skipping using [`nth()`] and when
+/// iterating from the back.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRanges::enumerate_chars()`] for more information.
+///
+/// [enumerate()]: Iterator::enumerate
+/// [`nth()`]: Iterator::nth
+/// [`CharRanges::enumerate_chars()`]: crate::CharRanges::enumerate_chars
+#[derive(Clone)]
+pub struct EnumerateChars<'a> {
+    iter: CharIndexRanges<'a>,
+}
+
+impl<'a> EnumerateChars<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>) -> Self {
+        Self {
+            iter: CharIndexRanges::new(iter, 0),
+        }
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all byte positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+
+    #[inline]
+    fn map((i, r, c): (Range<usize>, Range<usize>, char)) -> (usize, Range<usize>, char) {
+        (i.start, r, c)
+    }
+}
+
+impl Iterator for EnumerateChars<'_> {
+    type Item = (usize, Range<usize>, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(Self::map)
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.iter.count()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    #[inline]
+    fn last(self) -> Option<Self::Item> {
+        self.iter.last().map(Self::map)
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth(n).map(Self::map)
+    }
+}
+
+/// The [`char`] index of the last [`char`] requires counting the
+/// remaining [`char`]s, which is done once on the first call to
+/// [`next_back()`], [`nth_back()`], or [`last()`], i.e. that first
+/// call is _O(n)_.
+///
+/// [`next_back()`]: DoubleEndedIterator::next_back
+/// [`nth_back()`]: DoubleEndedIterator::nth_back
+/// [`last()`]: Iterator::last
+impl DoubleEndedIterator for EnumerateChars<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(Self::map)
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth_back(n).map(Self::map)
+    }
+}
+
+impl FusedIterator for EnumerateChars<'_> {}
+
+impl fmt::Debug for EnumerateChars<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "EnumerateChars(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use core::ops::Range;
+    use std::vec::Vec;
+
+    use crate::CharRangesExt;
+
+    const TEXT: &str = "Hello 🗻12∈45🌏 World ¢";
+
+    fn expected(text: &str) -> Vec<(Range<usize>, Range<usize>, char)> {
+        text.char_indices()
+            .enumerate()
+            .map(|(i, (start, c))| (i..(i + 1), start..(start + c.len_utf8()), c))
+            .collect()
+    }
+
+    #[test]
+    fn test_char_index_ranges() {
+        let mut chars = "aØb".char_ranges().char_index_ranges();
+        assert_eq!(chars.next(), Some((0..1, 0..1, 'a')));
+        assert_eq!(chars.next(), Some((1..2, 1..3, 'Ø')));
+        assert_eq!(chars.next(), Some((2..3, 3..4, 'b')));
+        assert_eq!(chars.next(), None);
+
+        let expected = expected(TEXT);
+        assert!(TEXT.char_ranges().char_index_ranges().eq(expected.clone()));
+        assert!(TEXT
+            .char_ranges()
+            .char_index_ranges()
+            .rev()
+            .eq(expected.into_iter().rev()));
+    }
+
+    #[test]
+    fn test_char_index_ranges_mixed() {
+        let expected = expected(TEXT);
+
+        let mut chars = TEXT.char_ranges().char_index_ranges();
+        assert_eq!(chars.next(), Some(expected[0].clone()));
+        assert_eq!(chars.nth(5), Some(expected[6].clone()));
+        assert_eq!(chars.next_back(), Some(expected[20].clone()));
+        assert_eq!(chars.size_hint(), (13, Some(13)));
+        assert_eq!(chars.nth_back(2), Some(expected[17].clone()));
+        assert_eq!(chars.next(), Some(expected[7].clone()));
+        assert_eq!(chars.as_str(), "2∈45🌏 Wor");
+        assert_eq!(chars.clone().count(), 9);
+        assert_eq!(chars.clone().last(), Some(expected[16].clone()));
+        assert!(chars.eq(expected[8..17].iter().cloned()));
+
+        for n in 0..=expected.len() {
+            let mut chars = TEXT.char_ranges().char_index_ranges();
+            assert_eq!(chars.nth(n), expected.get(n).cloned());
+            assert_eq!(
+                chars.next_back(),
+                expected.get(n + 1..).and_then(|e| e.last()).cloned()
+            );
+
+            let mut chars = TEXT.char_ranges().char_index_ranges();
+            let i = expected.len().checked_sub(n + 1);
+            assert_eq!(chars.nth_back(n), i.map(|i| expected[i].clone()));
+            assert_eq!(
+                chars.next(),
+                i.filter(|&i| i > 0).map(|_| expected[0].clone())
+            );
+        }
+    }
+
+    #[test]
+    fn test_char_index_ranges_offset() {
+        let text = "Hello 👋 World 🌏";
+
+        let start = 11;
+        let mut chars = text[start..]
+            .char_ranges_offset(start)
+            .char_index_ranges_offset(8);
+        assert_eq!(chars.offset(), start);
+        assert_eq!(chars.char_offset(), 8);
+        assert_eq!(chars.next(), Some((8..9, 11..12, 'W')));
+        assert_eq!(chars.next_back(), Some((14..15, 17..21, '🌏')));
+        assert_eq!(chars.next_back(), Some((13..14, 16..17, ' ')));
+
+        // Both coordinate systems match the whole text
+        let expected = expected(text);
+        let chars = text[start..]
+            .char_ranges_offset(start)
+            .char_index_ranges_offset(8);
+        assert!(chars.eq(expected[8..].iter().cloned()));
+    }
+
+    #[test]
+    fn test_enumerate_chars() {
+        let expected = expected(TEXT)
+            .into_iter()
+            .map(|(i, r, c)| (i.start, r, c))
+            .collect::<Vec<_>>();
+
+        assert!(TEXT.char_ranges().enumerate_chars().eq(expected.clone()));
+        assert!(TEXT
+            .char_ranges()
+            .enumerate_chars()
+            .rev()
+            .eq(expected.iter().rev().cloned()));
+
+        // Indices from both ends meet in the middle
+        let mut chars = TEXT.char_ranges().enumerate_chars();
+        assert_eq!(chars.nth(3), Some(expected[3].clone()));
+        assert_eq!(chars.nth_back(4), Some(expected[16].clone()));
+        assert_eq!(chars.size_hint(), (12, Some(12)));
+        assert_eq!(chars.as_str(), "o 🗻12∈45🌏 Wo");
+        for i in 4..10 {
+            assert_eq!(chars.next(), Some(expected[i].clone()));
+            assert_eq!(chars.next_back(), Some(expected[19 - i].clone()));
+        }
+        assert_eq!(chars.next(), None);
+        assert_eq!(chars.next_back(), None);
+
+        // Skipping past the back
+        let mut chars = TEXT.char_ranges().enumerate_chars();
+        assert_eq!(chars.next_back(), Some(expected[20].clone()));
+        assert_eq!(chars.nth(20), None);
+        assert_eq!(chars.next_back(), None);
+
+        let mut chars = "🌏".char_ranges_offset(4).enumerate_chars();
+        assert_eq!(chars.offset(), 4);
+        assert_eq!(chars.clone().last(), Some((0, 4..8, '🌏')));
+        assert_eq!(chars.next(), Some((0, 4..8, '🌏')));
+        assert_eq!(chars.next(), None);
+        assert_eq!("".char_ranges().enumerate_chars().next_back(), None);
+    }
+}
@@ -0,0 +1,241 @@
+use alloc::vec::Vec;
+use core::ops::RangeBounds;
+
+/// A precomputed index of the number of [`char`]s preceding every
+/// block of bytes in a text, for repeatedly converting between byte
+/// positions and [`char`] indices.
+///
+/// Building the index is a single pass over the text, storing one
+/// cumulative [`char`] count every [`block_size()`](Self::block_size)
+/// bytes. Afterwards, each query is a lookup of the nearest preceding
+/// block, _O(1)_ for byte positions and _O(log n)_ for [`char`] indices,
+/// followed by counting the leading bytes of at most one block.
+///
+/// A smaller block size makes queries faster, at the cost of storing
+/// more counts, i.e. the index uses `len / block_size` `usize`s.
+///
+/// Queries produce the same results as
+/// [`CharRangesExt::byte_to_char_index()`] and
+/// [`CharRangesExt::count_chars_in_range()`].
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::CharIndexMap;
+///
+/// let text = "Hello 🗻∈🌏 World".repeat(100);
+///
+/// let map = CharIndexMap::new(&text);
+/// assert_eq!(map.char_count(), 1500);
+///
+/// assert_eq!(map.char_index(1_869), Some(1_221));
+/// assert_eq!(map.byte_of_char(1_221), Some(1_869));
+/// assert_eq!(map.chars_in_range(1_869..1_892), Some(15));
+///
+/// // Not a char boundary
+/// assert_eq!(map.char_index(1_871), None);
+/// ```
+///
+/// [`CharRangesExt::byte_to_char_index()`]: crate::CharRangesExt::byte_to_char_index
+/// [`CharRangesExt::count_chars_in_range()`]: crate::CharRangesExt::count_chars_in_range
+#[derive(Clone, Debug)]
+pub struct CharIndexMap<'a> {
+    text: &'a str,
+    block_size: usize,
+    /// Number of [`char`]s starting before each multiple of `block_size`,
+    /// i.e. before `i * block_size`, where the first is always `0`.
+    counts: Vec<usize>,
+    char_count: usize,
+}
+
+impl<'a> CharIndexMap<'a> {
+    /// The block size used by [`new()`](Self::new).
+    pub const DEFAULT_BLOCK_SIZE: usize = 256;
+
+    /// Builds the index of `text`, using the
+    /// [`DEFAULT_BLOCK_SIZE`](Self::DEFAULT_BLOCK_SIZE).
+    #[inline]
+    pub fn new(text: &'a str) -> Self {
+        Self::with_block_size(text, Self::DEFAULT_BLOCK_SIZE)
+    }
+
+    /// Builds the index of `text`, storing a [`char`] count every
+    /// `block_size` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `block_size` is `0`.
+    pub fn with_block_size(text: &'a str, block_size: usize) -> Self {
+        assert!(block_size > 0, "block size must be greater than 0");
+
+        let mut blocks = text.as_bytes().chunks_exact(block_size);
+
+        let mut counts = Vec::with_capacity((text.len() / block_size) + 1);
+        counts.push(0);
+
+        let mut count = 0;
+        for block in blocks.by_ref() {
+            count += crate::count_leading_bytes(block);
+            counts.push(count);
+        }
+        let char_count = count + crate::count_leading_bytes(blocks.remainder());
+
+        Self {
+            text,
+            block_size,
+            counts,
+            char_count,
+        }
+    }
+
+    /// Returns the text this index was built from.
+    #[inline]
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// Returns the number of bytes between each stored [`char`] count.
+    #[inline]
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /// Returns the number of [`char`]s in the text.
+    #[inline]
+    pub fn char_count(&self) -> usize {
+        self.char_count
+    }
+
+    /// Returns the [`char`] index of the [`char`] starting at the byte
+    /// position `byte`, i.e. the number of [`char`]s before `byte`.
+    ///
+    /// The position at the end of the text is valid, and returns
+    /// [`char_count()`](Self::char_count).
+    ///
+    /// Returns `None` if `byte` is out of bounds, or not on a [`char`]
+    /// boundary.
+    pub fn char_index(&self, byte: usize) -> Option<usize> {
+        if !self.text.is_char_boundary(byte) {
+            return None;
+        }
+
+        let block = byte / self.block_size;
+        let start = block * self.block_size;
+        let bytes = &self.text.as_bytes()[start..byte];
+        Some(self.counts[block] + crate::count_leading_bytes(bytes))
+    }
+
+    /// Returns the start byte position of the [`char`] at [`char`]
+    /// index `n`.
+    ///
+    /// The [`char`] index [`char_count()`](Self::char_count) is valid,
+    /// and returns the length of the text.
+    ///
+    /// Returns `None` if `n` is greater than the number of [`char`]s.
+    pub fn byte_of_char(&self, n: usize) -> Option<usize> {
+        if n >= self.char_count {
+            return (n == self.char_count).then_some(self.text.len());
+        }
+
+        // The last block starting at or before the `char`
+        let block = self.counts.partition_point(|&count| count <= n) - 1;
+        let start = block * self.block_size;
+        let bytes = &self.text.as_bytes()[start..];
+        crate::skip_chars(bytes, n - self.counts[block])
+            .ok()
+            .map(|i| start + i)
+    }
+
+    /// Returns the number of [`char`]s within the byte `range`.
+    ///
+    /// Returns `None` if `range` is out of bounds, reversed, or if its
+    /// start or end is not a [`char`] boundary.
+    pub fn chars_in_range<R>(&self, range: R) -> Option<usize>
+    where
+        R: RangeBounds<usize>,
+    {
+        let range = crate::bounds_to_range(range, self.text.len());
+        if range.start > range.end {
+            return None;
+        }
+        let start = self.char_index(range.start)?;
+        let end = self.char_index(range.end)?;
+        Some(end - start)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::String;
+    use std::vec::Vec;
+
+    use super::CharIndexMap;
+
+    fn documents() -> Vec<String> {
+        std::vec![
+            String::new(),
+            String::from("a"),
+            String::from("🗻"),
+            String::from("Hello 🗻12∈45🌏 World ¢"),
+            "The quick brown fox jumps over the lazy dog. ".repeat(20),
+            "🗻🌏👋🦀 ∈ ¢ naïve café\n".repeat(20),
+        ]
+    }
+
+    #[test]
+    fn test_char_index_map() {
+        for text in documents() {
+            for block_size in [1, 2, 3, 4, 5, 8, 64, CharIndexMap::DEFAULT_BLOCK_SIZE] {
+                let map = CharIndexMap::with_block_size(&text, block_size);
+                assert_eq!(map.block_size(), block_size);
+                assert_eq!(map.char_count(), text.chars().count());
+
+                for byte in 0..=(text.len() + 1) {
+                    let expected = text.get(..byte).map(|s| s.chars().count());
+                    assert_eq!(map.char_index(byte), expected, "{text:?} at {byte}");
+                }
+
+                let starts = text
+                    .char_indices()
+                    .map(|(i, _c)| i)
+                    .chain([text.len()])
+                    .collect::<Vec<_>>();
+                for n in 0..=(starts.len() + 1) {
+                    let expected = starts.get(n).copied();
+                    assert_eq!(map.byte_of_char(n), expected, "{text:?} at {n}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_char_index_map_chars_in_range() {
+        for text in documents().iter().take(4) {
+            for block_size in [1, 3, 4, 7] {
+                let map = CharIndexMap::with_block_size(text, block_size);
+                for start in 0..=(text.len() + 1) {
+                    for end in 0..=(text.len() + 1) {
+                        #[allow(clippy::reversed_empty_ranges)]
+                        let expected = text.get(start..end).map(|s| s.chars().count());
+                        assert_eq!(map.chars_in_range(start..end), expected);
+                    }
+                }
+            }
+        }
+
+        let text = "🗻∈🌏 World";
+        let map = CharIndexMap::new(text);
+        assert_eq!(map.chars_in_range(..), Some(9));
+        assert_eq!(map.chars_in_range(..7), Some(2));
+        assert_eq!(map.chars_in_range(11..), Some(6));
+        assert_eq!(map.chars_in_range(4..=10), Some(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "block size must be greater than 0")]
+    fn test_char_index_map_zero_block_size() {
+        CharIndexMap::with_block_size("", 0);
+    }
+}
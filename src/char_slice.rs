@@ -0,0 +1,304 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+/// Extension trait for iterating slices of [`char`]s, where positions are
+/// the byte positions the [`char`]s would have, if the slice was encoded
+/// as UTF-8.
+///
+/// This is useful when text is stored as e.g. a `Vec<char>` for constant
+/// time indexing, but positions must be reported in terms of the UTF-8
+/// encoded text, without encoding it.
+pub trait CharSliceRangesExt {
+    /// Returns an iterator over [`char`]s and their start and end byte
+    /// positions, if the slice was encoded as UTF-8.
+    ///
+    /// The produced ranges are identical to [`CharRangesExt::char_ranges()`]
+    /// over the encoded string.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharSliceRangesExt;
+    ///
+    /// let chars = ['a', '🌏', '∈', 'b'];
+    ///
+    /// let mut ranges = chars.char_ranges();
+    /// assert_eq!(ranges.next(), Some((0..1, 'a')));
+    /// assert_eq!(ranges.next(), Some((1..5, '🌏')));
+    /// assert_eq!(ranges.next_back(), Some((8..9, 'b')));
+    /// assert_eq!(ranges.next_back(), Some((5..8, '∈')));
+    /// assert_eq!(ranges.next(), None);
+    /// ```
+    ///
+    /// [`CharRangesExt::char_ranges()`]: crate::CharRangesExt::char_ranges
+    fn char_ranges(&self) -> CharSliceRanges<'_>;
+
+    /// Returns an iterator over [`char`]s, their start and end byte
+    /// positions, if the slice was encoded as UTF-8, and their index
+    /// in the slice.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharSliceRangesExt;
+    ///
+    /// let chars = ['a', '🌏', '∈', 'b'];
+    ///
+    /// let mut ranges = chars.char_index_ranges();
+    /// assert_eq!(ranges.next(), Some((0..1, 0, 'a')));
+    /// assert_eq!(ranges.next(), Some((1..5, 1, '🌏')));
+    /// assert_eq!(ranges.next_back(), Some((8..9, 3, 'b')));
+    /// assert_eq!(ranges.next_back(), Some((5..8, 2, '∈')));
+    /// assert_eq!(ranges.next(), None);
+    /// ```
+    #[inline]
+    fn char_index_ranges(&self) -> CharSliceIndexRanges<'_> {
+        CharSliceIndexRanges {
+            iter: self.char_ranges(),
+        }
+    }
+}
+
+impl CharSliceRangesExt for [char] {
+    #[inline]
+    fn char_ranges(&self) -> CharSliceRanges<'_> {
+        CharSliceRanges {
+            chars: self,
+            start: 0,
+            end: self.len(),
+            front: 0,
+            back: None,
+        }
+    }
+}
+
+/// An iterator over the [`char`]s of a slice of [`char`]s, and their
+/// start and end byte positions, if the slice was encoded as UTF-8.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharSliceRangesExt::char_ranges()`] for more information.
+#[derive(Clone)]
+pub struct CharSliceRanges<'a> {
+    chars: &'a [char],
+    /// Index of the next [`char`] from the front.
+    start: usize,
+    /// Index following the next [`char`] from the back.
+    end: usize,
+    /// Byte position of the next [`char`] from the front.
+    front: usize,
+    /// Byte position following the next [`char`] from the back.
+    /// Computed on the first [`next_back()`].
+    ///
+    /// [`next_back()`]: DoubleEndedIterator::next_back
+    back: Option<usize>,
+}
+
+impl<'a> CharSliceRanges<'a> {
+    /// Returns the remaining [`char`]s.
+    #[inline]
+    pub fn as_slice(&self) -> &'a [char] {
+        &self.chars[self.start..self.end]
+    }
+}
+
+impl Iterator for CharSliceRanges<'_> {
+    type Item = (Range<usize>, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            return None;
+        }
+
+        let c = self.chars[self.start];
+        self.start += 1;
+
+        let start = self.front;
+        self.front += c.len_utf8();
+        Some((start..self.front, c))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for CharSliceRanges<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            return None;
+        }
+
+        let back = match self.back {
+            Some(back) => back,
+            None => {
+                let len = self.as_slice().iter().map(|c| c.len_utf8()).sum::<usize>();
+                self.front + len
+            }
+        };
+
+        self.end -= 1;
+        let c = self.chars[self.end];
+
+        let start = back - c.len_utf8();
+        self.back = Some(start);
+        Some((start..back, c))
+    }
+}
+
+impl ExactSizeIterator for CharSliceRanges<'_> {}
+
+impl FusedIterator for CharSliceRanges<'_> {}
+
+impl fmt::Debug for CharSliceRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CharSliceRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+/// An iterator over the [`char`]s of a slice of [`char`]s, their start
+/// and end byte positions, if the slice was encoded as UTF-8, and their
+/// index in the slice.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharSliceRangesExt::char_index_ranges()`] for more information.
+#[derive(Clone)]
+pub struct CharSliceIndexRanges<'a> {
+    iter: CharSliceRanges<'a>,
+}
+
+impl<'a> CharSliceIndexRanges<'a> {
+    /// Returns the remaining [`char`]s.
+    #[inline]
+    pub fn as_slice(&self) -> &'a [char] {
+        self.iter.as_slice()
+    }
+}
+
+impl Iterator for CharSliceIndexRanges<'_> {
+    type Item = (Range<usize>, usize, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let i = self.iter.start;
+        let (r, c) = self.iter.next()?;
+        Some((r, i, c))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for CharSliceIndexRanges<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (r, c) = self.iter.next_back()?;
+        Some((r, self.iter.end, c))
+    }
+}
+
+impl ExactSizeIterator for CharSliceIndexRanges<'_> {}
+
+impl FusedIterator for CharSliceIndexRanges<'_> {}
+
+impl fmt::Debug for CharSliceIndexRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CharSliceIndexRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::String;
+    use std::vec::Vec;
+
+    use super::CharSliceRangesExt;
+    use crate::CharRangesExt;
+
+    const CHARS: [char; 8] = ['a', '\n', '\u{80}', 'é', '∈', '⊂', '🌏', '🗻'];
+
+    #[test]
+    fn test_char_slice_ranges() {
+        let chars = ['f', 'o', '🗻', '∈', 'é'];
+        let text = String::from_iter(chars);
+
+        let ranges = chars.char_ranges();
+        assert_eq!(ranges.len(), 5);
+        assert!(ranges.clone().eq(text.char_ranges()));
+        assert!(ranges.rev().eq(text.char_ranges().rev()));
+
+        let ranges = chars.char_index_ranges();
+        assert!(ranges
+            .clone()
+            .eq(text.char_ranges().enumerate().map(|(i, (r, c))| (r, i, c))));
+
+        let mut ranges = ranges;
+        assert_eq!(ranges.next_back(), Some((9..11, 4, 'é')));
+        assert_eq!(ranges.next(), Some((0..1, 0, 'f')));
+        assert_eq!(ranges.as_slice(), ['o', '🗻', '∈']);
+
+        let empty: [char; 0] = [];
+        assert_eq!(empty.char_ranges().next_back(), None);
+        assert_eq!(empty.char_index_ranges().next(), None);
+    }
+
+    #[test]
+    fn test_char_slice_ranges_random() {
+        let mut next = crate::test_util::random();
+
+        for _ in 0..300 {
+            let len = next() % 24;
+            let chars = (0..len)
+                .map(|_| CHARS[next() % CHARS.len()])
+                .collect::<Vec<_>>();
+            let text = String::from_iter(&chars);
+
+            let expected = text
+                .char_ranges()
+                .enumerate()
+                .map(|(i, (r, c))| (r, i, c))
+                .collect::<Vec<_>>();
+            assert!(chars.char_index_ranges().eq(expected.iter().cloned()));
+            assert!(chars
+                .char_index_ranges()
+                .rev()
+                .eq(expected.iter().cloned().rev()));
+
+            // Alternating from both ends
+            let mut ranges = chars.char_index_ranges();
+            let mut front = Vec::new();
+            let mut back = Vec::new();
+            loop {
+                if next().is_multiple_of(2) {
+                    match ranges.next() {
+                        Some(item) => front.push(item),
+                        None => break,
+                    }
+                } else {
+                    match ranges.next_back() {
+                        Some(item) => back.push(item),
+                        None => break,
+                    }
+                }
+            }
+            back.reverse();
+            front.extend(back);
+            assert_eq!(front, expected, "{chars:?}");
+        }
+    }
+}
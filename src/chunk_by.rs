@@ -0,0 +1,198 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::CharRangesOffset;
+
+/// An iterator over groups of adjacent [`char`]s, where a predicate on
+/// each pair of adjacent [`char`]s decides whether they are in the same
+/// group, i.e. the same as [`slice::chunk_by()`] for [`char`]s.
+///
+/// The groups together cover the whole input, and each group contains
+/// at least one [`char`].
+///
+/// See [`CharRanges::chunk_by()`](crate::CharRanges::chunk_by).
+#[derive(Clone)]
+pub struct ChunkBy<'a, F> {
+    iter: CharRangesOffset<'a>,
+    same_group: F,
+}
+
+impl<'a, F> ChunkBy<'a, F>
+where
+    F: FnMut(char, char) -> bool,
+{
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>, same_group: F) -> Self {
+        Self { iter, same_group }
+    }
+
+    /// Returns the remaining substring, i.e. the part of the input
+    /// which is not covered by any group returned so far.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all produced ranges.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+}
+
+impl<'a, F> Iterator for ChunkBy<'a, F>
+where
+    F: FnMut(char, char) -> bool,
+{
+    type Item = (Range<usize>, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let text = self.iter.as_str();
+        let (first, mut prev) = self.iter.next()?;
+
+        let mut len = first.len();
+        while let Some((r, c)) = self.iter.peek() {
+            if !(self.same_group)(prev, c) {
+                break;
+            }
+            self.iter.next();
+            len += r.len();
+            prev = c;
+        }
+
+        Some((first.start..(first.start + len), &text[..len]))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (lower.min(1), upper)
+    }
+}
+
+impl<F> DoubleEndedIterator for ChunkBy<'_, F>
+where
+    F: FnMut(char, char) -> bool,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let text = self.iter.as_str();
+        let (last, mut next) = self.iter.next_back()?;
+
+        let mut len = last.len();
+        while let Some((r, c)) = self.iter.peek_back() {
+            if !(self.same_group)(c, next) {
+                break;
+            }
+            self.iter.next_back();
+            len += r.len();
+            next = c;
+        }
+
+        let s = &text[(text.len() - len)..];
+        Some(((last.end - len)..last.end, s))
+    }
+}
+
+impl<F> FusedIterator for ChunkBy<'_, F> where F: FnMut(char, char) -> bool {}
+
+impl<F> fmt::Debug for ChunkBy<'_, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChunkBy")
+            .field("iter", &self.iter)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use crate::CharRangesExt;
+
+    fn camel_case(a: char, b: char) -> bool {
+        !(a.is_lowercase() && b.is_uppercase())
+    }
+
+    #[test]
+    fn test_chunk_by_camel_case() {
+        let text = "parseHttpRequestÜberÉtat";
+
+        let words = text
+            .char_ranges()
+            .chunk_by(camel_case)
+            .map(|(_r, s)| s)
+            .collect::<Vec<_>>();
+        assert_eq!(words, ["parse", "Http", "Request", "Über", "État"]);
+
+        let mut words = text.char_ranges().chunk_by(camel_case);
+        assert_eq!(words.next(), Some((0..5, "parse")));
+        assert_eq!(words.next_back(), Some((21..26, "État")));
+        assert_eq!(words.next_back(), Some((16..21, "Über")));
+        assert_eq!(words.as_str(), "HttpRequest");
+        assert_eq!(words.next(), Some((5..9, "Http")));
+        assert_eq!(words.next(), Some((9..16, "Request")));
+        assert_eq!(words.next(), None);
+        assert_eq!(words.next_back(), None);
+    }
+
+    #[test]
+    fn test_chunk_by_digits() {
+        let text = "🗻12∈45🌏6";
+        let same_group = |a: char, b: char| a.is_ascii_digit() == b.is_ascii_digit();
+
+        let groups = text.char_ranges().chunk_by(same_group).collect::<Vec<_>>();
+        assert_eq!(
+            groups,
+            [
+                (0..4, "🗻"),
+                (4..6, "12"),
+                (6..9, "∈"),
+                (9..11, "45"),
+                (11..15, "🌏"),
+                (15..16, "6"),
+            ]
+        );
+
+        let mut rev = text
+            .char_ranges()
+            .chunk_by(same_group)
+            .rev()
+            .collect::<Vec<_>>();
+        rev.reverse();
+        assert_eq!(rev, groups);
+
+        // Groups tile the input
+        let mut end = 0;
+        for (r, s) in text.char_ranges().chunk_by(|a, b| a < b) {
+            assert_eq!(r.start, end);
+            assert_eq!(&text[r.clone()], s);
+            end = r.end;
+        }
+        assert_eq!(end, text.len());
+    }
+
+    #[test]
+    fn test_chunk_by_edge_cases() {
+        assert_eq!("".char_ranges().chunk_by(|_, _| true).next(), None);
+
+        let mut groups = "🌏".char_ranges().chunk_by(|_, _| false);
+        assert_eq!(groups.next(), Some((0..4, "🌏")));
+        assert_eq!(groups.next(), None);
+
+        let mut groups = "∈🌏".char_ranges().chunk_by(|_, _| true);
+        assert_eq!(groups.next(), Some((0..7, "∈🌏")));
+        assert_eq!(groups.next(), None);
+
+        let groups = "ab∈".char_ranges().chunk_by(|_, _| false).count();
+        assert_eq!(groups, 3);
+
+        let mut groups = "aA bB".char_ranges_offset(10).chunk_by(camel_case);
+        assert_eq!(groups.offset(), 10);
+        assert_eq!(groups.next(), Some((10..11, "a")));
+        assert_eq!(groups.next(), Some((11..14, "A b")));
+        assert_eq!(groups.next(), Some((14..15, "B")));
+    }
+}
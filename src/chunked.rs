@@ -0,0 +1,394 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+use core::str;
+
+use crate::{CharRanges, CharRangesExt, ChunkedUtf8Error};
+
+/// A state machine for iterating the [`char`]s of a UTF-8 encoded stream,
+/// which is pushed in chunks of bytes, e.g. when reading a large file
+/// through a fixed-size buffer.
+///
+/// All positions are `u64` byte positions relative to the start of the
+/// stream, regardless of the target's pointer width.
+///
+/// [`char`]s split across chunks are handled by buffering the (at most 3)
+/// trailing bytes of an incomplete [`char`] until the next chunk is pushed.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::ChunkedCharRanges;
+///
+/// let mut chars = ChunkedCharRanges::new();
+///
+/// // "a🌏" split in the middle of '🌏'
+/// let items = chars.push(b"a\xF0\x9F").collect::<Vec<_>>();
+/// assert_eq!(items, [Ok((0..1, 'a'))]);
+///
+/// let items = chars.push(b"\x8C\x8Fb").collect::<Vec<_>>();
+/// assert_eq!(items, [Ok((1..5, '🌏')), Ok((5..6, 'b'))]);
+///
+/// assert!(chars.finish().is_ok());
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct ChunkedCharRanges {
+    /// Byte position of the first byte not yet produced,
+    /// i.e. the start of `pending`.
+    pos: u64,
+    /// Trailing bytes of an incomplete [`char`] from the previous chunk.
+    pending: [u8; 4],
+    pending_len: usize,
+}
+
+impl ChunkedCharRanges {
+    /// Creates a state machine where positions start at `0`.
+    #[inline]
+    pub const fn new() -> Self {
+        Self::with_offset(0)
+    }
+
+    /// Creates a state machine where positions start at `offset`,
+    /// e.g. when resuming in the middle of a stream.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::ChunkedCharRanges;
+    ///
+    /// let mut chars = ChunkedCharRanges::with_offset(1 << 40);
+    /// assert_eq!(chars.push(b"a").next(), Some(Ok(((1 << 40)..((1 << 40) + 1), 'a'))));
+    /// ```
+    #[inline]
+    pub const fn with_offset(offset: u64) -> Self {
+        Self {
+            pos: offset,
+            pending: [0; 4],
+            pending_len: 0,
+        }
+    }
+
+    /// Returns the byte position following all bytes pushed so far.
+    #[inline]
+    pub const fn position(&self) -> u64 {
+        self.pos + (self.pending_len as u64)
+    }
+
+    /// Pushes the next `chunk` of the stream, and returns an iterator over
+    /// the [`char`]s completed by it and their start and end byte positions.
+    ///
+    /// Invalid byte sequences produce a [`ChunkedUtf8Error`], after which
+    /// iteration continues. Invalid bytes are split the same way as
+    /// [`utf8_char_ranges()`](crate::Utf8CharRangesExt::utf8_char_ranges).
+    ///
+    /// If the returned iterator is dropped before it is exhausted,
+    /// then the remaining items are skipped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a position overflows `u64`.
+    #[inline]
+    pub fn push<'a>(&'a mut self, chunk: &'a [u8]) -> ChunkCharRanges<'a> {
+        ChunkCharRanges {
+            state: self,
+            bytes: chunk,
+            chars: "".char_ranges(),
+            base: 0,
+        }
+    }
+
+    /// Finishes the stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stream ended in the middle of a [`char`],
+    /// with the range of the dangling bytes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::ChunkedCharRanges;
+    ///
+    /// let mut chars = ChunkedCharRanges::new();
+    /// chars.push(b"ab\xE2\x88").for_each(drop);
+    ///
+    /// let err = chars.finish().unwrap_err();
+    /// assert_eq!(err.range(), 2..4);
+    /// assert!(err.is_incomplete());
+    /// ```
+    #[inline]
+    pub fn finish(mut self) -> Result<(), ChunkedUtf8Error> {
+        match self.take_incomplete() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    /// Clears and returns the dangling incomplete sequence, if any.
+    #[inline]
+    pub(crate) fn take_incomplete(&mut self) -> Option<ChunkedUtf8Error> {
+        if self.pending_len == 0 {
+            return None;
+        }
+        let len = self.pending_len;
+        self.pending_len = 0;
+        Some(ChunkedUtf8Error::new(self.advance(len), true))
+    }
+
+    #[inline]
+    fn advance(&mut self, len: usize) -> Range<u64> {
+        let start = self.pos;
+        self.pos = match start.checked_add(len as u64) {
+            Some(end) => end,
+            None => panic!("position {start} with length {len} overflows u64"),
+        };
+        start..self.pos
+    }
+
+    /// Decodes the next step of `bytes`, and advances `bytes` past it.
+    ///
+    /// Returns `None` when `bytes` is exhausted, after buffering any
+    /// trailing incomplete [`char`].
+    pub(crate) fn step<'b>(&mut self, bytes: &mut &'b [u8]) -> Option<ChunkStep<'b>> {
+        if self.pending_len > 0 {
+            return self.step_pending(bytes);
+        }
+
+        if bytes.is_empty() {
+            return None;
+        }
+
+        let valid_up_to = match str::from_utf8(bytes) {
+            Ok(_) => bytes.len(),
+            Err(err) if err.valid_up_to() > 0 => err.valid_up_to(),
+            Err(err) => match err.error_len() {
+                Some(len) => {
+                    *bytes = &bytes[len..];
+                    let r = self.advance(len);
+                    return Some(ChunkStep::Item(Err(ChunkedUtf8Error::new(r, false))));
+                }
+                None => {
+                    self.pending[..bytes.len()].copy_from_slice(bytes);
+                    self.pending_len = bytes.len();
+                    *bytes = &[];
+                    return None;
+                }
+            },
+        };
+
+        let (valid, rest) = bytes.split_at(valid_up_to);
+        *bytes = rest;
+        let base = self.advance(valid.len()).start;
+        Some(ChunkStep::Valid(base, str::from_utf8(valid).unwrap()))
+    }
+
+    /// Completes the pending bytes of the previous chunk.
+    fn step_pending<'b>(&mut self, bytes: &mut &'b [u8]) -> Option<ChunkStep<'b>> {
+        let pending_len = self.pending_len;
+
+        let mut buf = self.pending;
+        let n = (buf.len() - pending_len).min(bytes.len());
+        buf[pending_len..][..n].copy_from_slice(&bytes[..n]);
+        let buf = &buf[..(pending_len + n)];
+
+        let valid = match str::from_utf8(buf) {
+            Ok(s) => s,
+            Err(err) if err.valid_up_to() > 0 => str::from_utf8(&buf[..err.valid_up_to()]).unwrap(),
+            Err(err) => match err.error_len() {
+                Some(len) => {
+                    *bytes = &bytes[(len - pending_len)..];
+                    self.pending_len = 0;
+                    let r = self.advance(len);
+                    return Some(ChunkStep::Item(Err(ChunkedUtf8Error::new(r, false))));
+                }
+                None => {
+                    *bytes = &[];
+                    self.pending[..buf.len()].copy_from_slice(buf);
+                    self.pending_len = buf.len();
+                    return None;
+                }
+            },
+        };
+
+        let c = valid.chars().next().unwrap();
+        let len = c.len_utf8();
+        *bytes = &bytes[(len - pending_len)..];
+        self.pending_len = 0;
+        let r = self.advance(len);
+        Some(ChunkStep::Item(Ok((r, c))))
+    }
+}
+
+/// A step decoded by [`ChunkedCharRanges::step()`].
+pub(crate) enum ChunkStep<'b> {
+    /// A valid UTF-8 sequence and its start byte position.
+    Valid(u64, &'b str),
+    /// A [`char`] completing pending bytes, or an invalid byte sequence.
+    Item(Result<(Range<u64>, char), ChunkedUtf8Error>),
+}
+
+/// An iterator over the [`char`]s completed by a chunk pushed to
+/// [`ChunkedCharRanges`], and their start and end byte positions.
+///
+/// See [`ChunkedCharRanges::push()`] for more information.
+pub struct ChunkCharRanges<'a> {
+    state: &'a mut ChunkedCharRanges,
+    /// Remaining bytes of the chunk following `chars`.
+    bytes: &'a [u8],
+    /// Remaining [`char`]s of the current valid UTF-8 sequence.
+    chars: CharRanges<'a>,
+    /// Byte position of the start of `chars`.
+    base: u64,
+}
+
+impl Iterator for ChunkCharRanges<'_> {
+    type Item = Result<(Range<u64>, char), ChunkedUtf8Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((r, c)) = self.chars.next() {
+                let r = (self.base + (r.start as u64))..(self.base + (r.end as u64));
+                return Some(Ok((r, c)));
+            }
+
+            match self.state.step(&mut self.bytes)? {
+                ChunkStep::Valid(base, s) => {
+                    self.base = base;
+                    self.chars = s.char_ranges();
+                }
+                ChunkStep::Item(item) => return Some(item),
+            }
+        }
+    }
+}
+
+impl FusedIterator for ChunkCharRanges<'_> {}
+
+impl Drop for ChunkCharRanges<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        // Consume the remaining bytes, such that any trailing
+        // incomplete char is buffered for the next chunk
+        self.chars = "".char_ranges();
+        self.for_each(drop);
+    }
+}
+
+impl fmt::Debug for ChunkCharRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChunkCharRanges")
+            .field("state", &self.state)
+            .field("chars", &self.chars)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use core::ops::Range;
+    use std::vec::Vec;
+
+    use super::ChunkedCharRanges;
+    use crate::{ChunkedUtf8Error, Utf8CharRangesExt};
+
+    type Item = Result<(Range<u64>, char), Range<u64>>;
+
+    fn expected(bytes: &[u8]) -> Vec<Item> {
+        bytes
+            .utf8_char_ranges()
+            .map(|item| match item {
+                Ok((r, c)) => Ok(((r.start as u64)..(r.end as u64), c)),
+                Err(err) => Err((err.range().start as u64)..(err.range().end as u64)),
+            })
+            .collect()
+    }
+
+    fn push_all<'a>(chunks: impl IntoIterator<Item = &'a [u8]>) -> Vec<Item> {
+        let mut chars = ChunkedCharRanges::new();
+        let mut items = Vec::new();
+        for chunk in chunks {
+            items.extend(
+                chars
+                    .push(chunk)
+                    .map(|item| item.map_err(|err| err.range())),
+            );
+        }
+        if let Err(err) = chars.finish() {
+            assert!(err.is_incomplete());
+            items.push(Err(err.range()));
+        }
+        items
+    }
+
+    fn assert_all_splits(bytes: &[u8]) {
+        let expected = expected(bytes);
+
+        assert_eq!(push_all([bytes]), expected);
+        assert_eq!(push_all(bytes.chunks(1)), expected);
+
+        for i in 0..=bytes.len() {
+            let (a, b) = bytes.split_at(i);
+            assert_eq!(push_all([a, b]), expected, "split at {i}");
+
+            for j in i..=bytes.len() {
+                let (b, c) = (&bytes[i..j], &bytes[j..]);
+                assert_eq!(push_all([a, b, c]), expected, "split at {i} and {j}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_chunked_char_ranges() {
+        assert_all_splits("Hello 🗻12∈45🌏 World ¢".as_bytes());
+        assert_all_splits("🗻∈🌏¢".as_bytes());
+        assert_all_splits(b"");
+    }
+
+    #[test]
+    fn test_chunked_char_ranges_invalid() {
+        assert_all_splits(b"a\xF0\x9F\x97\xBB\xFFb\xE2\x88c");
+        assert_all_splits(b"\xE2\x88\xE2\x88\x88\xC0\x80\xF0\x9F");
+        assert_all_splits(b"\xED\xA0\x80\xF4\x90\x80\x80\x80");
+    }
+
+    #[test]
+    fn test_chunked_char_ranges_finish() {
+        let mut chars = ChunkedCharRanges::new();
+        assert!(chars
+            .push(b"ab\xF0\x9F")
+            .eq([Ok((0..1, 'a')), Ok((1..2, 'b'))]));
+        assert_eq!(chars.position(), 4);
+        assert!(chars.push(b"\x97").eq([]));
+        assert_eq!(chars.finish(), Err(ChunkedUtf8Error::new(2..5, true)),);
+
+        let mut chars = ChunkedCharRanges::new();
+        assert!(chars.push(b"ab").eq([Ok((0..1, 'a')), Ok((1..2, 'b'))]));
+        assert_eq!(chars.finish(), Ok(()));
+    }
+
+    #[test]
+    fn test_chunked_char_ranges_drop() {
+        let mut chars = ChunkedCharRanges::with_offset(10);
+
+        let mut items = chars.push(b"a\xE2\x88\x88b\xE2");
+        assert_eq!(items.next(), Some(Ok((10..11, 'a'))));
+        drop(items);
+        assert_eq!(chars.position(), 16);
+
+        // The trailing incomplete char is still buffered
+        assert!(chars.push(b"\x88\x88").eq([Ok((15..18, '∈'))]));
+        assert_eq!(chars.finish(), Ok(()));
+    }
+
+    #[test]
+    fn test_chunked_char_ranges_large_offset() {
+        let offset = u64::MAX - 5;
+        let mut chars = ChunkedCharRanges::with_offset(offset);
+        assert!(chars.push("a🌏".as_bytes()).eq([
+            Ok((offset..(offset + 1), 'a')),
+            Ok(((offset + 1)..u64::MAX, '🌏'))
+        ]));
+    }
+}
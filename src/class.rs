@@ -0,0 +1,349 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::CharRangesOffset;
+
+/// Ranges of non-ASCII punctuation, i.e. an approximation of the general
+/// categories `Pc`, `Pd`, `Ps`, `Pe`, `Pi`, `Pf`, and `Po`, covering
+/// Latin-1, general, CJK, and fullwidth punctuation.
+const PUNCTUATION: &[(char, char)] = &[
+    // Latin-1 Supplement, e.g. '¡', '«', '»', and '¿'
+    ('\u{00A1}', '\u{00A1}'),
+    ('\u{00A7}', '\u{00A7}'),
+    ('\u{00AB}', '\u{00AB}'),
+    ('\u{00B6}', '\u{00B7}'),
+    ('\u{00BB}', '\u{00BB}'),
+    ('\u{00BF}', '\u{00BF}'),
+    // General Punctuation, e.g. '–', '—', '‘', '’', '“', '”', and '…'
+    ('\u{2010}', '\u{2027}'),
+    ('\u{2030}', '\u{205E}'),
+    // CJK Symbols and Punctuation, e.g. '、', '。', '「', and '」'
+    ('\u{3001}', '\u{3003}'),
+    ('\u{3008}', '\u{3011}'),
+    ('\u{3014}', '\u{301F}'),
+    // Halfwidth and Fullwidth Forms, e.g. '！', '（', '）', and '，'
+    ('\u{FF01}', '\u{FF0F}'),
+    ('\u{FF1A}', '\u{FF20}'),
+    ('\u{FF3B}', '\u{FF40}'),
+    ('\u{FF5B}', '\u{FF65}'),
+];
+
+/// A coarse class of a [`char`], e.g. for a pre-pass before tokenization.
+///
+/// See [`CharClass::of()`] for the exact rules, and
+/// [`CharRanges::classify_runs()`] for more information.
+///
+/// [`CharRanges::classify_runs()`]: crate::CharRanges::classify_runs
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum CharClass {
+    /// See [`char::is_alphabetic()`].
+    Alphabetic,
+    /// See [`char::is_numeric()`].
+    Numeric,
+    /// See [`char::is_whitespace()`].
+    Whitespace,
+    /// ASCII punctuation, see [`char::is_ascii_punctuation()`], along with
+    /// common non-ASCII punctuation.
+    Punctuation,
+    /// Everything else, e.g. symbols like `'€'`, emoji, combining marks,
+    /// and control characters.
+    Other,
+}
+
+impl CharClass {
+    /// Returns the class of `c`.
+    ///
+    /// The rules are checked in the following order, and the first one
+    /// matching decides the class:
+    ///
+    /// 1. [`Whitespace`] if [`char::is_whitespace()`]
+    /// 2. [`Alphabetic`] if [`char::is_alphabetic()`], e.g. `'a'`, `'é'`,
+    ///    and `'日'`
+    /// 3. [`Numeric`] if [`char::is_numeric()`], e.g. `'7'` and `'½'`
+    /// 4. [`Punctuation`] if [`char::is_ascii_punctuation()`], which
+    ///    includes `'_'` and ASCII symbols like `'$'` and `'+'`, or if `c`
+    ///    is in a small embedded table of common non-ASCII punctuation,
+    ///    e.g. `'¿'`, `'—'`, `'…'`, `'。'`, and `'「'`
+    /// 5. [`Other`] for everything else
+    ///
+    /// Note that `'_'` is [`Punctuation`], i.e. an identifier like
+    /// `foo_bar` produces 3 runs. Use [`CharRanges::classify_runs_by()`]
+    /// to classify it as [`Alphabetic`] instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharClass;
+    ///
+    /// assert_eq!(CharClass::of('é'), CharClass::Alphabetic);
+    /// assert_eq!(CharClass::of('٣'), CharClass::Numeric);
+    /// assert_eq!(CharClass::of('\u{3000}'), CharClass::Whitespace);
+    /// assert_eq!(CharClass::of('_'), CharClass::Punctuation);
+    /// assert_eq!(CharClass::of('。'), CharClass::Punctuation);
+    /// assert_eq!(CharClass::of('€'), CharClass::Other);
+    /// assert_eq!(CharClass::of('🌏'), CharClass::Other);
+    /// ```
+    ///
+    /// [`Whitespace`]: Self::Whitespace
+    /// [`Alphabetic`]: Self::Alphabetic
+    /// [`Numeric`]: Self::Numeric
+    /// [`Punctuation`]: Self::Punctuation
+    /// [`Other`]: Self::Other
+    /// [`CharRanges::classify_runs_by()`]: crate::CharRanges::classify_runs_by
+    pub fn of(c: char) -> Self {
+        if c.is_whitespace() {
+            Self::Whitespace
+        } else if c.is_alphabetic() {
+            Self::Alphabetic
+        } else if c.is_numeric() {
+            Self::Numeric
+        } else if c.is_ascii_punctuation() || is_punctuation(c) {
+            Self::Punctuation
+        } else {
+            Self::Other
+        }
+    }
+}
+
+#[inline]
+fn is_punctuation(c: char) -> bool {
+    PUNCTUATION
+        .iter()
+        .any(|&(start, end)| (start..=end).contains(&c))
+}
+
+/// An iterator over maximal runs of [`char`]s of the same [`CharClass`],
+/// producing the start and end byte positions of each run, the substring,
+/// and the class.
+///
+/// Adjacent runs always have different classes, and together they cover
+/// the whole input.
+///
+/// See [`CharRanges::classify_runs()`] and [`CharRanges::classify_runs_by()`]
+/// for more information.
+///
+/// [`CharRanges::classify_runs()`]: crate::CharRanges::classify_runs
+/// [`CharRanges::classify_runs_by()`]: crate::CharRanges::classify_runs_by
+#[derive(Clone)]
+pub struct ClassifyRuns<'a, F> {
+    iter: CharRangesOffset<'a>,
+    classify: F,
+    /// The class of the first [`char`] of the next run, if it has
+    /// already been evaluated.
+    class: Option<CharClass>,
+}
+
+impl<'a, F> ClassifyRuns<'a, F>
+where
+    F: FnMut(char) -> CharClass,
+{
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>, classify: F) -> Self {
+        Self {
+            iter,
+            classify,
+            class: None,
+        }
+    }
+
+    /// Returns the remaining substring, i.e. the part of the input
+    /// which is not covered by any run returned so far.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all produced ranges.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+}
+
+impl<'a, F> Iterator for ClassifyRuns<'a, F>
+where
+    F: FnMut(char) -> CharClass,
+{
+    type Item = (Range<usize>, &'a str, CharClass);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let text = self.iter.as_str();
+
+        let (first, c) = self.iter.next()?;
+        let class = match self.class.take() {
+            Some(class) => class,
+            None => (self.classify)(c),
+        };
+
+        let mut count = 0;
+        let mut len = first.end - first.start;
+        for c in self.iter.as_str().chars() {
+            let next = (self.classify)(c);
+            if next != class {
+                self.class = Some(next);
+                break;
+            }
+            count += 1;
+            len += c.len_utf8();
+        }
+
+        if count > 0 {
+            _ = self.iter.nth(count - 1);
+        }
+
+        Some((first.start..(first.start + len), &text[..len], class))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (lower.min(1), upper)
+    }
+}
+
+impl<F> FusedIterator for ClassifyRuns<'_, F> where F: FnMut(char) -> CharClass {}
+
+impl<F> fmt::Debug for ClassifyRuns<'_, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClassifyRuns")
+            .field("iter", &self.iter)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use super::{CharClass, PUNCTUATION};
+    use crate::CharRangesExt;
+
+    use CharClass::*;
+
+    #[test]
+    fn test_classify_runs_code() {
+        let text = "let foo_bar = x2+ 42;\n";
+
+        let runs = text.char_ranges().classify_runs().collect::<Vec<_>>();
+        assert_eq!(
+            runs,
+            [
+                (0..3, "let", Alphabetic),
+                (3..4, " ", Whitespace),
+                (4..7, "foo", Alphabetic),
+                (7..8, "_", Punctuation),
+                (8..11, "bar", Alphabetic),
+                (11..12, " ", Whitespace),
+                (12..13, "=", Punctuation),
+                (13..14, " ", Whitespace),
+                (14..15, "x", Alphabetic),
+                (15..16, "2", Numeric),
+                (16..17, "+", Punctuation),
+                (17..18, " ", Whitespace),
+                (18..20, "42", Numeric),
+                (20..21, ";", Punctuation),
+                (21..22, "\n", Whitespace),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_classify_runs_cjk() {
+        let text = "日本語の文章、「引用」です。";
+
+        let runs = text
+            .char_ranges()
+            .classify_runs()
+            .map(|(_r, s, class)| (s, class))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            runs,
+            [
+                ("日本語の文章", Alphabetic),
+                ("、「", Punctuation),
+                ("引用", Alphabetic),
+                ("」", Punctuation),
+                ("です", Alphabetic),
+                ("。", Punctuation),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_classify_runs_emoji() {
+        let text = "hi 🌏🗻! €5";
+
+        let runs = text.char_ranges().classify_runs().collect::<Vec<_>>();
+        assert_eq!(
+            runs,
+            [
+                (0..2, "hi", Alphabetic),
+                (2..3, " ", Whitespace),
+                (3..11, "🌏🗻", Other),
+                (11..12, "!", Punctuation),
+                (12..13, " ", Whitespace),
+                (13..16, "€", Other),
+                (16..17, "5", Numeric),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_classify_runs_tiling() {
+        let text = "  foo—bar…  ¿½? \u{301}🌏\t";
+
+        let runs = text.char_ranges().offset(3).classify_runs();
+
+        let mut end = 3;
+        let mut prev = None;
+        for (r, s, class) in runs {
+            assert_eq!(r.start, end);
+            assert_eq!(&text[(r.start - 3)..(r.end - 3)], s);
+            assert!(s.chars().all(|c| CharClass::of(c) == class));
+            assert_ne!(prev, Some(class));
+            end = r.end;
+            prev = Some(class);
+        }
+        assert_eq!(end, text.len() + 3);
+    }
+
+    #[test]
+    fn test_classify_runs_by() {
+        let text = "foo_bar = 1";
+
+        let runs = text
+            .char_ranges()
+            .classify_runs_by(|c| match c {
+                '_' => Alphabetic,
+                c => CharClass::of(c),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            runs,
+            [
+                (0..7, "foo_bar", Alphabetic),
+                (7..8, " ", Whitespace),
+                (8..9, "=", Punctuation),
+                (9..10, " ", Whitespace),
+                (10..11, "1", Numeric),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_punctuation_table() {
+        for &(start, end) in PUNCTUATION {
+            assert!(start <= end);
+            assert!(!start.is_alphanumeric() && !start.is_whitespace());
+            assert!(!end.is_alphanumeric() && !end.is_whitespace());
+        }
+        for pair in PUNCTUATION.windows(2) {
+            assert!(pair[0].1 < pair[1].0);
+        }
+    }
+}
@@ -0,0 +1,299 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::CharRangesOffset;
+
+/// Ranges of combining marks, i.e. an approximation of the general
+/// categories `Mn`, `Mc`, and `Me`, covering the combining diacritical
+/// mark blocks, the most common marks of major scripts, and variation
+/// selectors.
+const COMBINING_MARKS: &[(char, char)] = &[
+    // Combining Diacritical Marks
+    ('\u{0300}', '\u{036F}'),
+    // Cyrillic
+    ('\u{0483}', '\u{0489}'),
+    // Hebrew
+    ('\u{0591}', '\u{05BD}'),
+    ('\u{05BF}', '\u{05BF}'),
+    ('\u{05C1}', '\u{05C2}'),
+    ('\u{05C4}', '\u{05C5}'),
+    ('\u{05C7}', '\u{05C7}'),
+    // Arabic
+    ('\u{0610}', '\u{061A}'),
+    ('\u{064B}', '\u{065F}'),
+    ('\u{0670}', '\u{0670}'),
+    ('\u{06D6}', '\u{06DC}'),
+    ('\u{06DF}', '\u{06E4}'),
+    ('\u{06E7}', '\u{06E8}'),
+    ('\u{06EA}', '\u{06ED}'),
+    // Syriac
+    ('\u{0711}', '\u{0711}'),
+    ('\u{0730}', '\u{074A}'),
+    // Thaana
+    ('\u{07A6}', '\u{07B0}'),
+    // Devanagari
+    ('\u{0900}', '\u{0903}'),
+    ('\u{093A}', '\u{093C}'),
+    ('\u{093E}', '\u{094F}'),
+    ('\u{0951}', '\u{0957}'),
+    ('\u{0962}', '\u{0963}'),
+    // Bengali
+    ('\u{0981}', '\u{0983}'),
+    ('\u{09BC}', '\u{09BC}'),
+    ('\u{09BE}', '\u{09C4}'),
+    ('\u{09C7}', '\u{09C8}'),
+    ('\u{09CB}', '\u{09CD}'),
+    ('\u{09D7}', '\u{09D7}'),
+    ('\u{09E2}', '\u{09E3}'),
+    // Thai
+    ('\u{0E31}', '\u{0E31}'),
+    ('\u{0E34}', '\u{0E3A}'),
+    ('\u{0E47}', '\u{0E4E}'),
+    // Combining Diacritical Marks Extended
+    ('\u{1AB0}', '\u{1AFF}'),
+    // Combining Diacritical Marks Supplement
+    ('\u{1DC0}', '\u{1DFF}'),
+    // Combining Diacritical Marks for Symbols
+    ('\u{20D0}', '\u{20FF}'),
+    // CJK ideographic tone marks
+    ('\u{302A}', '\u{302F}'),
+    // Kana voiced sound marks
+    ('\u{3099}', '\u{309A}'),
+    // Variation Selectors
+    ('\u{FE00}', '\u{FE0F}'),
+    // Combining Half Marks
+    ('\u{FE20}', '\u{FE2F}'),
+    // Variation Selectors Supplement
+    ('\u{E0100}', '\u{E01EF}'),
+];
+
+/// Returns `true` if `c` is a combining mark, according to the
+/// approximation used by [`LegacyClusterRanges`].
+pub(crate) fn is_combining_mark(c: char) -> bool {
+    if c < '\u{0300}' {
+        return false;
+    }
+    COMBINING_MARKS
+        .binary_search_by(|&(start, end)| {
+            if end < c {
+                core::cmp::Ordering::Less
+            } else if start > c {
+                core::cmp::Ordering::Greater
+            } else {
+                core::cmp::Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+/// An iterator over clusters of a base [`char`] followed by any
+/// combining marks, producing the start and end byte positions of each
+/// cluster, along with the substring.
+///
+/// Combining marks are approximated by a small embedded table, covering
+/// the combining diacritical mark blocks, e.g. U+0301 COMBINING ACUTE
+/// ACCENT, the most common marks of major scripts, and variation
+/// selectors. Combining marks at the start of the input, i.e. without
+/// a base [`char`], form a cluster of their own.
+///
+/// This is a lightweight alternative to [extended grapheme clusters],
+/// which does not require the `unicode-segmentation` feature. Unlike
+/// extended grapheme clusters, emoji sequences joined by U+200D ZERO
+/// WIDTH JOINER, regional indicator pairs, i.e. flags, Hangul syllable
+/// sequences, and `"\r\n"` are not kept together, i.e. every [`char`]
+/// which is not a combining mark starts a new cluster.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRanges::legacy_cluster_ranges()`] for more information.
+///
+/// [extended grapheme clusters]: https://www.unicode.org/reports/tr29/#Grapheme_Cluster_Boundaries
+/// [`CharRanges::legacy_cluster_ranges()`]: crate::CharRanges::legacy_cluster_ranges
+#[derive(Clone)]
+pub struct LegacyClusterRanges<'a> {
+    iter: CharRangesOffset<'a>,
+}
+
+impl<'a> LegacyClusterRanges<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>) -> Self {
+        Self { iter }
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+}
+
+impl<'a> Iterator for LegacyClusterRanges<'a> {
+    type Item = (Range<usize>, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let text = self.iter.as_str();
+        let (first, _c) = self.iter.next()?;
+
+        let mut len = first.len();
+        while let Some((r, c)) = self.iter.peek() {
+            if !is_combining_mark(c) {
+                break;
+            }
+            self.iter.next();
+            len += r.len();
+        }
+
+        Some((first.start..(first.start + len), &text[..len]))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (lower.min(1), upper)
+    }
+}
+
+impl DoubleEndedIterator for LegacyClusterRanges<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let text = self.iter.as_str();
+        let (last, mut c) = self.iter.next_back()?;
+
+        let mut len = last.len();
+        while is_combining_mark(c) {
+            let Some((r, prev)) = self.iter.peek_back() else {
+                break;
+            };
+            self.iter.next_back();
+            len += r.len();
+            c = prev;
+        }
+
+        let s = &text[(text.len() - len)..];
+        Some(((last.end - len)..last.end, s))
+    }
+}
+
+impl FusedIterator for LegacyClusterRanges<'_> {}
+
+impl fmt::Debug for LegacyClusterRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "LegacyClusterRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use super::{is_combining_mark, COMBINING_MARKS};
+    use crate::CharRangesExt;
+
+    #[test]
+    fn test_legacy_cluster_ranges() {
+        // "e" followed by U+0301 COMBINING ACUTE ACCENT
+        let text = "cafe\u{301}!";
+
+        let clusters = text.char_ranges().legacy_cluster_ranges();
+        assert!(clusters.eq([
+            (0..1, "c"),
+            (1..2, "a"),
+            (2..3, "f"),
+            (3..6, "e\u{301}"),
+            (6..7, "!"),
+        ]));
+    }
+
+    #[test]
+    fn test_legacy_cluster_ranges_stacked() {
+        // Multiple stacked marks
+        let text = "a\u{301}\u{323}\u{20DD}b";
+
+        let clusters = text
+            .char_ranges()
+            .legacy_cluster_ranges()
+            .collect::<Vec<_>>();
+        assert_eq!(clusters, [(0..8, "a\u{301}\u{323}\u{20DD}"), (8..9, "b")]);
+
+        let mut rev = text
+            .char_ranges()
+            .legacy_cluster_ranges()
+            .rev()
+            .collect::<Vec<_>>();
+        rev.reverse();
+        assert_eq!(rev, clusters);
+    }
+
+    #[test]
+    fn test_legacy_cluster_ranges_leading_mark() {
+        let text = "\u{301}\u{302}a\u{301}";
+
+        let clusters = text
+            .char_ranges()
+            .legacy_cluster_ranges()
+            .collect::<Vec<_>>();
+        assert_eq!(clusters, [(0..4, "\u{301}\u{302}"), (4..7, "a\u{301}")]);
+
+        let mut clusters = text.char_ranges().legacy_cluster_ranges();
+        assert_eq!(clusters.next_back(), Some((4..7, "a\u{301}")));
+        assert_eq!(clusters.next_back(), Some((0..4, "\u{301}\u{302}")));
+        assert_eq!(clusters.next_back(), None);
+    }
+
+    #[test]
+    fn test_legacy_cluster_ranges_ascii() {
+        let text = "Hello, World!\r\n";
+
+        let clusters = text.char_ranges().legacy_cluster_ranges();
+        assert!(clusters.eq(text.char_ranges().map(|(r, _c)| (r.clone(), &text[r]))));
+
+        // No emoji sequences
+        let text = "👩\u{200D}🔬";
+        let clusters = text
+            .char_ranges()
+            .legacy_cluster_ranges()
+            .map(|(_r, s)| s)
+            .collect::<Vec<_>>();
+        assert_eq!(clusters, ["👩", "\u{200D}", "🔬"]);
+
+        // Variation selectors are marks
+        let clusters = "❤\u{FE0F}".char_ranges().legacy_cluster_ranges();
+        assert!(clusters.eq([(0..6, "❤\u{FE0F}")]));
+    }
+
+    #[test]
+    fn test_legacy_cluster_ranges_offset() {
+        let text = "e\u{301}x";
+
+        let clusters = text.char_ranges().offset(10).legacy_cluster_ranges();
+        assert!(clusters.eq([(10..13, "e\u{301}"), (13..14, "x")]));
+    }
+
+    #[test]
+    fn test_combining_marks_sorted() {
+        for &(start, end) in COMBINING_MARKS {
+            assert!(start <= end);
+            assert!(is_combining_mark(start));
+            assert!(is_combining_mark(end));
+        }
+        for pair in COMBINING_MARKS.windows(2) {
+            assert!(pair[0].1 < pair[1].0);
+        }
+
+        assert!(!is_combining_mark('a'));
+        assert!(!is_combining_mark('\u{2FF}'));
+        assert!(!is_combining_mark('\u{370}'));
+        assert!(!is_combining_mark('🌏'));
+    }
+}
@@ -0,0 +1,173 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::CharRangesOffset;
+
+/// An iterator over the byte ranges of maximal runs of consecutive
+/// [`char`]s matching a predicate, skipping all [`char`]s which don't.
+///
+/// Unlike [`CharRuns`], which produces both matching and non-matching
+/// runs, this only produces the matching runs.
+///
+/// See [`CharRanges::coalesce_by()`](crate::CharRanges::coalesce_by).
+///
+/// [`CharRuns`]: crate::CharRuns
+#[derive(Clone)]
+pub struct CoalesceBy<'a, P> {
+    iter: CharRangesOffset<'a>,
+    pred: P,
+}
+
+impl<'a, P> CoalesceBy<'a, P>
+where
+    P: FnMut(char) -> bool,
+{
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>, pred: P) -> Self {
+        Self { iter, pred }
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all produced ranges.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+}
+
+impl<P> Iterator for CoalesceBy<'_, P>
+where
+    P: FnMut(char) -> bool,
+{
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pred = &mut self.pred;
+        self.iter.skip_while_range(|c| !pred(c));
+        let r = self.iter.skip_while_range(&mut self.pred);
+        if r.is_empty() {
+            None
+        } else {
+            Some(r)
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_lower, upper) = self.iter.size_hint();
+        (0, upper)
+    }
+}
+
+impl<P> DoubleEndedIterator for CoalesceBy<'_, P>
+where
+    P: FnMut(char) -> bool,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let pred = &mut self.pred;
+        self.iter.skip_back_while_range(|c| !pred(c));
+        let r = self.iter.skip_back_while_range(&mut self.pred);
+        if r.is_empty() {
+            None
+        } else {
+            Some(r)
+        }
+    }
+}
+
+impl<P> FusedIterator for CoalesceBy<'_, P> where P: FnMut(char) -> bool {}
+
+impl<P> fmt::Debug for CoalesceBy<'_, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CoalesceBy")
+            .field("iter", &self.iter)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::ops::Range;
+
+    use crate::CharRangesExt;
+
+    #[test]
+    fn test_coalesce_by() {
+        let text = "  foo 123  bar ";
+
+        let mut ranges = text.char_ranges().coalesce_by(char::is_whitespace);
+        assert_eq!(ranges.next(), Some(0..2));
+        assert_eq!(ranges.as_str(), "foo 123  bar ");
+        assert_eq!(ranges.next(), Some(5..6));
+        assert_eq!(ranges.next(), Some(9..11));
+        assert_eq!(ranges.next(), Some(14..15));
+        assert_eq!(ranges.as_str(), "");
+        assert_eq!(ranges.next(), None);
+
+        let ranges = text.char_ranges().coalesce_by(char::is_whitespace).rev();
+        assert!(ranges.eq([14..15, 9..11, 5..6, 0..2]));
+    }
+
+    #[test]
+    fn test_coalesce_by_alternating() {
+        let text = "a1b2c3";
+
+        let ranges = text.char_ranges().coalesce_by(|c| c.is_ascii_digit());
+        assert!(ranges.eq([1..2, 3..4, 5..6]));
+
+        let ranges = text.char_ranges().coalesce_by(char::is_alphabetic);
+        assert!(ranges.eq([0..1, 2..3, 4..5]));
+
+        let mut ranges = text.char_ranges().coalesce_by(char::is_alphabetic);
+        assert_eq!(ranges.next(), Some(0..1));
+        assert_eq!(ranges.next_back(), Some(4..5));
+        assert_eq!(ranges.next(), Some(2..3));
+        assert_eq!(ranges.next_back(), None);
+        assert_eq!(ranges.next(), None);
+    }
+
+    #[test]
+    fn test_coalesce_by_end() {
+        let text = "foo  ";
+
+        let mut ranges = text.char_ranges().coalesce_by(char::is_whitespace);
+        assert_eq!(ranges.next(), Some(3..5));
+        assert_eq!(ranges.next(), None);
+
+        // A run spanning the whole input
+        let text = "🗻∈🌏";
+        let mut ranges = text.char_ranges().coalesce_by(|_| true);
+        assert_eq!(ranges.next(), Some(0..11));
+        assert_eq!(ranges.next(), None);
+
+        let ranges = text.char_ranges().coalesce_by(|_| false);
+        assert!(ranges.eq([] as [Range<usize>; 0]));
+
+        assert!(""
+            .char_ranges()
+            .coalesce_by(|_| true)
+            .eq([] as [Range<usize>; 0]));
+    }
+
+    #[test]
+    fn test_coalesce_by_multi_byte() {
+        let text = "a🗻∈🌏b¢¢c";
+
+        let ranges = text.char_ranges().coalesce_by(|c| !c.is_ascii());
+        assert!(ranges.eq([1..12, 13..17]));
+
+        let ranges = text[1..]
+            .char_ranges_offset(1)
+            .coalesce_by(|c| !c.is_ascii());
+        assert!(ranges.eq([1..12, 13..17]));
+
+        let ranges = text.char_ranges().coalesce_by(|c| !c.is_ascii()).rev();
+        assert!(ranges.eq([13..17, 1..12]));
+    }
+}
@@ -0,0 +1,113 @@
+use core::ops::Range;
+
+use codespan_reporting::diagnostic::Label;
+
+use crate::Spanned;
+
+/// Returns a primary [`Label`] in `file_id` spanning the byte positions `range`.
+///
+/// This is shorthand for [`Label::primary()`], e.g. for the ranges produced by
+/// [`char_ranges()`](crate::CharRangesExt::char_ranges).
+///
+/// Note: Labels extending past the end of the file panic when rendered.
+/// Use [`clamp_label()`] for ranges which are not guaranteed to be in bounds.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::{label_primary, CharRangesExt};
+///
+/// let text = "let x = 1 ∈ 2;";
+///
+/// let (r, _c) = text.char_ranges().find(|(_r, c)| *c == '∈').unwrap();
+///
+/// let label = label_primary(0, r).with_message("expected `==`");
+/// assert_eq!(label.range, 10..13);
+/// ```
+#[inline]
+pub fn label_primary<FileId>(file_id: FileId, range: Range<usize>) -> Label<FileId> {
+    Label::primary(file_id, range)
+}
+
+/// Returns a secondary [`Label`] in `file_id` spanning the byte positions `range`.
+///
+/// See [`label_primary()`] for more information.
+#[inline]
+pub fn label_secondary<FileId>(file_id: FileId, range: Range<usize>) -> Label<FileId> {
+    Label::secondary(file_id, range)
+}
+
+/// Returns `label` with its range clamped into the bounds of `source`,
+/// i.e. the file `label` is in.
+///
+/// The start and end positions are clamped to `source.len()`, and then
+/// widened outwards to the nearest [`char`] boundaries, such that the
+/// label can be rendered without panicking.
+///
+/// This is useful for ranges produced by [`char_ranges_offset()`] with an
+/// offset, which aren't guaranteed to lie within the file.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::{clamp_label, label_primary};
+///
+/// let source = "a🌏";
+///
+/// // Past the end of the file
+/// let label = clamp_label(source, label_primary((), 3..10));
+/// assert_eq!(label.range, 1..5);
+///
+/// // Reversed ranges become empty
+/// let label = clamp_label(source, label_primary((), 1..0));
+/// assert_eq!(label.range, 1..1);
+/// ```
+///
+/// [`char_ranges_offset()`]: crate::CharRangesExt::char_ranges_offset
+pub fn clamp_label<FileId>(source: &str, mut label: Label<FileId>) -> Label<FileId> {
+    let len = source.len();
+
+    let mut start = label.range.start.min(len);
+    while !source.is_char_boundary(start) {
+        start -= 1;
+    }
+
+    let mut end = label.range.end.clamp(start, len);
+    while !source.is_char_boundary(end) {
+        end += 1;
+    }
+
+    label.range = start..end;
+    label
+}
+
+impl<T> Spanned<T> {
+    /// Returns a primary [`Label`] in `file_id` spanning `self.range`.
+    ///
+    /// See [`label_primary()`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "a = 🗻";
+    ///
+    /// let mountain = text.char_ranges().spanned().last().unwrap();
+    ///
+    /// let label = mountain.label_primary(0).with_message("mountains are not allowed");
+    /// assert_eq!(label.range, 4..8);
+    /// ```
+    #[inline]
+    pub fn label_primary<FileId>(&self, file_id: FileId) -> Label<FileId> {
+        label_primary(file_id, self.range.clone())
+    }
+
+    /// Returns a secondary [`Label`] in `file_id` spanning `self.range`.
+    ///
+    /// See [`label_primary()`] for more information.
+    #[inline]
+    pub fn label_secondary<FileId>(&self, file_id: FileId) -> Label<FileId> {
+        label_secondary(file_id, self.range.clone())
+    }
+}
@@ -0,0 +1,349 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::{CharRangesExt, CharRangesOffset};
+
+/// An iterator over [`char`]s, their start and end byte positions,
+/// and their start and end visual columns, where `'\t'` advances to
+/// the next tab stop.
+///
+/// Produced items are `(range, char, start_column, end_column)`, where
+/// columns are zero-based. A `'\t'` advances to the next multiple of
+/// `tab_width`, a `'\n'` resets the column to `0` for the next [`char`],
+/// and every other [`char`] advances by a single column.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRanges::visual_columns()`] for more information.
+///
+/// [`CharRanges::visual_columns()`]: crate::CharRanges::visual_columns
+#[derive(Clone)]
+pub struct VisualColumns<'a> {
+    iter: CharRangesOffset<'a>,
+    tab_width: usize,
+    column: usize,
+}
+
+impl<'a> VisualColumns<'a> {
+    #[inline]
+    #[track_caller]
+    pub(crate) fn new(iter: CharRangesOffset<'a>, tab_width: usize) -> Self {
+        assert!(tab_width > 0, "tab width must be greater than 0");
+        Self {
+            iter,
+            tab_width,
+            column: 0,
+        }
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+
+    /// Returns the visual column of the next [`char`].
+    #[inline]
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Returns the tab width.
+    #[inline]
+    pub fn tab_width(&self) -> usize {
+        self.tab_width
+    }
+}
+
+impl Iterator for VisualColumns<'_> {
+    type Item = (Range<usize>, char, usize, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (r, c) = self.iter.next()?;
+
+        let start = self.column;
+        let end = match c {
+            '\t' => (start / self.tab_width + 1) * self.tab_width,
+            _ => start + 1,
+        };
+        self.column = if c == '\n' { 0 } else { end };
+
+        Some((r, c, start, end))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl FusedIterator for VisualColumns<'_> {}
+
+impl fmt::Debug for VisualColumns<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "VisualColumns(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+/// Returns the zero-based visual column of the byte position `pos`
+/// in `text`, where `'\t'` advances to the next multiple of `tab_width`.
+///
+/// Returns `None` if `pos` is out of bounds or not on a [`char`] boundary.
+///
+/// See [`VisualColumns`] for more information.
+///
+/// # Panics
+///
+/// Panics if `tab_width` is `0`.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::column_at;
+///
+/// let text = "a\tb\n🌏\tc";
+///
+/// assert_eq!(column_at(text, 2, 4), Some(4));
+/// assert_eq!(column_at(text, 2, 8), Some(8));
+/// assert_eq!(column_at(text, 8, 4), Some(1));
+/// assert_eq!(column_at(text, 9, 4), Some(4));
+/// assert_eq!(column_at(text, 6, 4), None);
+/// ```
+#[inline]
+#[track_caller]
+pub fn column_at(text: &str, pos: usize, tab_width: usize) -> Option<usize> {
+    let line = text.get(..pos)?;
+    let line_start = line.rfind('\n').map_or(0, |i| i + 1);
+    let column = line[line_start..]
+        .char_ranges()
+        .visual_columns(tab_width)
+        .last()
+        .map_or(0, |(_, _, _, end)| end);
+    Some(column)
+}
+
+/// Returns the [`char`] and its start and end byte positions, whose
+/// visual columns cover the zero-based `column` of `line`, where `'\t'`
+/// advances to the next multiple of `tab_width`, i.e. the inverse of
+/// [`VisualColumns`] and [`column_at()`], e.g. for mapping a mouse click
+/// to a position.
+///
+/// If `column` lands in the middle of a `'\t'`, i.e. within its expansion
+/// to the next tab stop, then the `'\t'` is returned.
+///
+/// Only the first line of `line` is considered, i.e. up to the first
+/// `'\n'` or `"\r\n"`, which is excluded. Returns `None` if `column` is
+/// past the end of the line. Use `line.len()` as the position in that
+/// case, e.g. for placing a caret at the end of the line.
+///
+/// # Panics
+///
+/// Panics if `tab_width` is `0`.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::char_at_visual_column;
+///
+/// let line = "a\t🌏b";
+///
+/// assert_eq!(char_at_visual_column(line, 0, 4), Some((0..1, 'a')));
+/// // Within the expansion of '\t'
+/// assert_eq!(char_at_visual_column(line, 1, 4), Some((1..2, '\t')));
+/// assert_eq!(char_at_visual_column(line, 3, 4), Some((1..2, '\t')));
+/// assert_eq!(char_at_visual_column(line, 4, 4), Some((2..6, '🌏')));
+/// assert_eq!(char_at_visual_column(line, 5, 4), Some((6..7, 'b')));
+/// // Past the end of the line
+/// assert_eq!(char_at_visual_column(line, 6, 4), None);
+/// ```
+#[inline]
+#[track_caller]
+pub fn char_at_visual_column(
+    line: &str,
+    column: usize,
+    tab_width: usize,
+) -> Option<(Range<usize>, char)> {
+    let line = line.lines().next().unwrap_or_default();
+    line.char_ranges()
+        .visual_columns(tab_width)
+        .find(|&(_, _, start, end)| (start..end).contains(&column))
+        .map(|(r, c, _, _)| (r, c))
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use super::{char_at_visual_column, column_at};
+    use crate::CharRangesExt;
+
+    fn columns(text: &str, tab_width: usize) -> Vec<(usize, usize)> {
+        text.char_ranges()
+            .visual_columns(tab_width)
+            .map(|(_, _, start, end)| (start, end))
+            .collect()
+    }
+
+    #[test]
+    fn test_visual_columns() {
+        let mut cols = "a\tb".char_ranges().visual_columns(4);
+        assert_eq!(cols.next(), Some((0..1, 'a', 0, 1)));
+        assert_eq!(cols.next(), Some((1..2, '\t', 1, 4)));
+        assert_eq!(cols.column(), 4);
+        assert_eq!(cols.next(), Some((2..3, 'b', 4, 5)));
+        assert_eq!(cols.next(), None);
+
+        // Tabs at every phase relative to the tab stop
+        assert_eq!(columns("\tx", 4), [(0, 4), (4, 5)]);
+        assert_eq!(columns("a\tx", 4), [(0, 1), (1, 4), (4, 5)]);
+        assert_eq!(columns("ab\tx", 4), [(0, 1), (1, 2), (2, 4), (4, 5)]);
+        assert_eq!(
+            columns("abc\tx", 4),
+            [(0, 1), (1, 2), (2, 3), (3, 4), (4, 5)],
+        );
+        assert_eq!(
+            columns("abcd\tx", 4),
+            [(0, 1), (1, 2), (2, 3), (3, 4), (4, 8), (8, 9)],
+        );
+        assert_eq!(columns("\t\t", 4), [(0, 4), (4, 8)]);
+    }
+
+    #[test]
+    fn test_visual_columns_multi_byte() {
+        let text = "🗻∈\t🌏\tx";
+
+        let mut cols = text.char_ranges().visual_columns(4);
+        assert_eq!(cols.next(), Some((0..4, '🗻', 0, 1)));
+        assert_eq!(cols.next(), Some((4..7, '∈', 1, 2)));
+        assert_eq!(cols.next(), Some((7..8, '\t', 2, 4)));
+        assert_eq!(cols.next(), Some((8..12, '🌏', 4, 5)));
+        assert_eq!(cols.next(), Some((12..13, '\t', 5, 8)));
+        assert_eq!(cols.next(), Some((13..14, 'x', 8, 9)));
+        assert_eq!(cols.next(), None);
+    }
+
+    #[test]
+    fn test_visual_columns_lines() {
+        assert_eq!(
+            columns("a\tb\n\tc\r\n", 4),
+            [
+                (0, 1),
+                (1, 4),
+                (4, 5),
+                (5, 6),
+                (0, 4),
+                (4, 5),
+                (5, 6),
+                (6, 7)
+            ],
+        );
+
+        let cols = "x\t\ny".char_ranges_offset(10).visual_columns(2);
+        assert_eq!(cols.offset(), 10);
+        assert!(cols.eq([
+            (10..11, 'x', 0, 1),
+            (11..12, '\t', 1, 2),
+            (12..13, '\n', 2, 3),
+            (13..14, 'y', 0, 1),
+        ]));
+    }
+
+    #[test]
+    fn test_visual_columns_tab_width_1() {
+        assert_eq!(
+            columns("a\t\tb🌏\t", 1),
+            [(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 6)],
+        );
+        assert_eq!(column_at("a\t\tb", 3, 1), Some(3));
+    }
+
+    #[test]
+    #[should_panic(expected = "tab width must be greater than 0")]
+    fn test_visual_columns_tab_width_0() {
+        _ = "a\tb".char_ranges().visual_columns(0);
+    }
+
+    #[test]
+    fn test_column_at() {
+        let text = "fn main() {\n\tlet 🗻 =\t1;\n}";
+
+        assert_eq!(column_at(text, 0, 4), Some(0));
+        assert_eq!(column_at(text, 11, 4), Some(11));
+        assert_eq!(column_at(text, 12, 4), Some(0));
+        assert_eq!(column_at(text, 13, 4), Some(4));
+        assert_eq!(column_at(text, 17, 4), Some(8));
+        assert_eq!(column_at(text, 21, 4), Some(9));
+        assert_eq!(column_at(text, 23, 4), Some(11));
+        assert_eq!(column_at(text, 24, 4), Some(12));
+        assert_eq!(column_at(text, 24, 8), Some(16));
+        assert_eq!(column_at(text, text.len(), 4), Some(1));
+
+        // Within '🗻'
+        assert_eq!(column_at(text, 18, 4), None);
+        assert_eq!(column_at(text, text.len() + 1, 4), None);
+
+        // Consistent with the iterator
+        for (r, _c, start, _end) in text.char_ranges().visual_columns(3) {
+            assert_eq!(column_at(text, r.start, 3), Some(start));
+        }
+    }
+
+    #[test]
+    fn test_char_at_visual_column() {
+        let line = "\tlet 🗻 =\t中;";
+
+        assert_eq!(char_at_visual_column(line, 0, 4), Some((0..1, '\t')));
+        assert_eq!(char_at_visual_column(line, 3, 4), Some((0..1, '\t')));
+        assert_eq!(char_at_visual_column(line, 4, 4), Some((1..2, 'l')));
+        assert_eq!(char_at_visual_column(line, 8, 4), Some((5..9, '🗻')));
+        assert_eq!(char_at_visual_column(line, 11, 4), Some((11..12, '\t')));
+        assert_eq!(char_at_visual_column(line, 12, 4), Some((12..15, '中')));
+        assert_eq!(char_at_visual_column(line, 13, 4), Some((15..16, ';')));
+        assert_eq!(char_at_visual_column(line, 14, 4), None);
+        assert_eq!(char_at_visual_column(line, usize::MAX, 4), None);
+
+        // Only the first line, excluding the terminator
+        assert_eq!(char_at_visual_column("ab\r\ncd", 1, 4), Some((1..2, 'b')));
+        assert_eq!(char_at_visual_column("ab\r\ncd", 2, 4), None);
+        assert_eq!(char_at_visual_column("ab\ncd", 2, 4), None);
+        assert_eq!(char_at_visual_column("", 0, 4), None);
+    }
+
+    #[test]
+    fn test_char_at_visual_column_round_trip() {
+        for line in ["\tlet 🗻 =\t中;", "a\t\tb🌏\t", "中文\t🗻∈🌏\tx", "\t\t\t"] {
+            for tab_width in [1, 2, 3, 4, 8] {
+                let mut end_column = 0;
+                for (r, c, start, end) in line.char_ranges().visual_columns(tab_width) {
+                    // Every column covered by the char maps back to it
+                    for column in start..end {
+                        let found = char_at_visual_column(line, column, tab_width);
+                        assert_eq!(found, Some((r.clone(), c)), "{line:?} at {column}");
+                    }
+                    assert_eq!(column_at(line, r.start, tab_width), Some(start));
+                    end_column = end;
+                }
+                assert_eq!(char_at_visual_column(line, end_column, tab_width), None);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "tab width must be greater than 0")]
+    fn test_char_at_visual_column_tab_width_0() {
+        _ = char_at_visual_column("a\tb", 0, 0);
+    }
+}
@@ -0,0 +1,174 @@
+use core::ops::Range;
+
+type CharPair = ((Range<usize>, char), (Range<usize>, char));
+
+/// Returns the byte ranges of the longest common prefix of `a` and `b`,
+/// in `a` and `b` respectively.
+///
+/// The prefix always ends on a [`char`] boundary, i.e. [`char`]s which
+/// only share some of their leading bytes are not part of the prefix.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::common_prefix_range;
+///
+/// assert_eq!(common_prefix_range("foo 🗻∈🌏", "foo 🗻 bar"), (0..8, 0..8));
+///
+/// // '🌏' and '🌎' only differ in their last byte
+/// assert_eq!(common_prefix_range("🗻🌏", "🗻🌎"), (0..4, 0..4));
+/// ```
+#[inline]
+pub fn common_prefix_range(a: &str, b: &str) -> (Range<usize>, Range<usize>) {
+    let len = common_prefix_len(a, b);
+    (0..len, 0..len)
+}
+
+/// Returns the byte ranges of the longest common suffix of `a` and `b`,
+/// in `a` and `b` respectively.
+///
+/// The suffix always starts on a [`char`] boundary, and never overlaps
+/// the [common prefix](common_prefix_range), e.g. if `a` is a prefix of
+/// `b`, then the common suffix is empty. This makes it possible to
+/// compute the minimal changed range between `a` and `b`.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::{common_prefix_range, common_suffix_range};
+///
+/// let a = "let x = 🗻;";
+/// let b = "let x = 🌏🌏;";
+///
+/// let (prefix, _) = common_prefix_range(a, b);
+/// let (suffix_a, suffix_b) = common_suffix_range(a, b);
+/// assert_eq!((prefix, suffix_a.clone(), suffix_b.clone()), (0..8, 12..13, 16..17));
+///
+/// // The changed range in each string
+/// assert_eq!(&a[8..suffix_a.start], "🗻");
+/// assert_eq!(&b[8..suffix_b.start], "🌏🌏");
+///
+/// // The suffix doesn't overlap the prefix
+/// assert_eq!(common_suffix_range("ab", "abab"), (2..2, 4..4));
+/// ```
+#[inline]
+pub fn common_suffix_range(a: &str, b: &str) -> (Range<usize>, Range<usize>) {
+    let prefix = common_prefix_len(a, b);
+    let max = a.len().min(b.len()) - prefix;
+
+    let mut len = a
+        .bytes()
+        .rev()
+        .zip(b.bytes().rev())
+        .take(max)
+        .take_while(|(a, b)| a == b)
+        .count();
+    // The bytes of `a` and `b` are the same, so a boundary
+    // in `a` is also a boundary in `b`
+    while !a.is_char_boundary(a.len() - len) {
+        len -= 1;
+    }
+
+    ((a.len() - len)..a.len(), (b.len() - len)..b.len())
+}
+
+/// Returns the first [`char`] in `a` and `b` respectively, which differ,
+/// along with their start and end byte positions.
+///
+/// Returns `None` if `a` and `b` are equal, or if one is a prefix of
+/// the other, i.e. if there is no [`char`] to compare against.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::first_mismatch;
+///
+/// assert_eq!(
+///     first_mismatch("🗻∈🌏", "🗻∉🌏"),
+///     Some(((4..7, '∈'), (4..7, '∉'))),
+/// );
+/// assert_eq!(first_mismatch("🗻∈🌏", "🗻∈🌏"), None);
+/// assert_eq!(first_mismatch("🗻", "🗻∈🌏"), None);
+/// ```
+#[inline]
+pub fn first_mismatch(a: &str, b: &str) -> Option<CharPair> {
+    let start = common_prefix_len(a, b);
+    let c_a = a[start..].chars().next()?;
+    let c_b = b[start..].chars().next()?;
+    Some((
+        (start..(start + c_a.len_utf8()), c_a),
+        (start..(start + c_b.len_utf8()), c_b),
+    ))
+}
+
+#[inline]
+fn common_prefix_len(a: &str, b: &str) -> usize {
+    let mut len = a.bytes().zip(b.bytes()).take_while(|(a, b)| a == b).count();
+    // The bytes of `a` and `b` are the same, so a boundary
+    // in `a` is also a boundary in `b`
+    while !a.is_char_boundary(len) {
+        len -= 1;
+    }
+    len
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{common_prefix_range, common_suffix_range, first_mismatch};
+
+    #[test]
+    fn test_common_prefix_suffix_range() {
+        let text = "Hello 🗻12∈45🌏 World ¢";
+
+        // Identical
+        assert_eq!(common_prefix_range(text, text), (0..30, 0..30));
+        assert_eq!(common_suffix_range(text, text), (30..30, 30..30));
+        assert_eq!(first_mismatch(text, text), None);
+
+        // Disjoint
+        assert_eq!(common_prefix_range("🗻∈", "🌏∉"), (0..0, 0..0));
+        assert_eq!(common_suffix_range("🗻∈", "🌏∉"), (7..7, 7..7));
+        assert_eq!(
+            first_mismatch("🗻∈", "🌏∉"),
+            Some(((0..4, '🗻'), (0..4, '🌏'))),
+        );
+
+        // Empty
+        assert_eq!(common_prefix_range("", text), (0..0, 0..0));
+        assert_eq!(common_suffix_range(text, ""), (30..30, 0..0));
+        assert_eq!(first_mismatch("", text), None);
+    }
+
+    #[test]
+    fn test_common_range_partial_char() {
+        // '🌏' is F0 9F 8C 8F and '🌎' is F0 9F 8C 8E
+        let a = "x🌏y";
+        let b = "x🌎y";
+        assert_eq!(common_prefix_range(a, b), (0..1, 0..1));
+        assert_eq!(common_suffix_range(a, b), (5..6, 5..6));
+        assert_eq!(first_mismatch(a, b), Some(((1..5, '🌏'), (1..5, '🌎'))));
+
+        // '∈' is E2 88 88 and '∉' is E2 88 89, and '∈' and '≈' is E2 89 88
+        assert_eq!(common_prefix_range("∈", "∉"), (0..0, 0..0));
+        assert_eq!(common_suffix_range("∈", "≈"), (3..3, 3..3));
+        assert_eq!(common_suffix_range("a∈", "b∈"), (1..4, 1..4));
+    }
+
+    #[test]
+    fn test_common_suffix_no_overlap() {
+        assert_eq!(common_suffix_range("ab", "abab"), (2..2, 4..4));
+        assert_eq!(common_suffix_range("abab", "ab"), (4..4, 2..2));
+        assert_eq!(common_suffix_range("🌏", "🌏🌏"), (4..4, 8..8));
+        assert_eq!(common_suffix_range("aXa", "aXaXa"), (3..3, 5..5));
+        assert_eq!(common_suffix_range("aba", "abca"), (2..3, 3..4));
+        assert_eq!(common_suffix_range("aa", "aba"), (1..2, 2..3));
+
+        // Prefix and suffix combined never exceed the shorter string
+        for (a, b) in [("aaa", "aaaa"), ("🗻🗻", "🗻🗻🗻"), ("a∈a", "a∈∈a")] {
+            let (prefix, _) = common_prefix_range(a, b);
+            let (suffix_a, suffix_b) = common_suffix_range(a, b);
+            assert!(prefix.end <= suffix_a.start);
+            assert!(prefix.end <= suffix_b.start);
+        }
+    }
+}
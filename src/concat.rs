@@ -0,0 +1,292 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::{CharRangesExt, CharRangesOffset};
+
+/// An iterator over the [`char`]s of multiple string segments and their
+/// start and end byte positions, as if the segments were concatenated,
+/// without allocating the concatenation.
+///
+/// Positions are continuous across segments, i.e. the first [`char`] of a
+/// segment starts where the last [`char`] of the previous segment ended.
+/// Empty segments are skipped. Since every segment is a [`str`], no
+/// [`char`] can be split across two segments.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`char_ranges_concat()`] for more information.
+#[derive(Clone)]
+pub struct ConcatCharRanges<'a> {
+    /// The segment currently iterated from the front, if any.
+    front: Option<CharRangesOffset<'a>>,
+    /// The segment currently iterated from the back, if any.
+    back: Option<CharRangesOffset<'a>>,
+    /// The remaining whole segments between `front` and `back`.
+    segments: &'a [&'a str],
+    /// Start byte position of `segments`.
+    start: usize,
+    /// End byte position of `segments`.
+    end: usize,
+}
+
+impl<'a> ConcatCharRanges<'a> {
+    #[inline]
+    fn new(segments: &'a [&'a str]) -> Self {
+        Self {
+            front: None,
+            back: None,
+            segments,
+            start: 0,
+            end: segments.iter().map(|s| s.len()).sum(),
+        }
+    }
+
+    /// Returns the remaining substrings, i.e. the remaining part of the
+    /// segment iterated from the front, the remaining whole segments, and
+    /// the remaining part of the segment iterated from the back.
+    ///
+    /// The partial segments are empty if the iteration from respectively
+    /// the front and the back has not started a segment.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::char_ranges_concat;
+    ///
+    /// let segments = ["foo", "∈", "", "bar"];
+    ///
+    /// let mut chars = char_ranges_concat(&segments);
+    /// assert_eq!(chars.as_strs(), ("", &["foo", "∈", "", "bar"][..], ""));
+    ///
+    /// assert_eq!(chars.next(), Some((0..1, 'f')));
+    /// assert_eq!(chars.next_back(), Some((8..9, 'r')));
+    /// assert_eq!(chars.as_strs(), ("oo", &["∈", ""][..], "ba"));
+    /// ```
+    #[inline]
+    pub fn as_strs(&self) -> (&'a str, &'a [&'a str], &'a str) {
+        let front = self.front.as_ref().map_or("", |iter| iter.as_str());
+        let back = self.back.as_ref().map_or("", |iter| iter.as_str());
+        (front, self.segments, back)
+    }
+}
+
+impl Iterator for ConcatCharRanges<'_> {
+    type Item = (Range<usize>, char);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(front) = &mut self.front {
+                if let Some(item) = front.next() {
+                    return Some(item);
+                }
+                self.front = None;
+            }
+
+            match self.segments.split_first() {
+                Some((segment, rest)) => {
+                    self.front = Some(segment.char_ranges_offset(self.start));
+                    self.start += segment.len();
+                    self.segments = rest;
+                }
+                None => return self.back.as_mut()?.next(),
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (front_lower, front_upper) = self
+            .front
+            .as_ref()
+            .map_or((0, Some(0)), |iter| iter.size_hint());
+        let (back_lower, back_upper) = self
+            .back
+            .as_ref()
+            .map_or((0, Some(0)), |iter| iter.size_hint());
+
+        let len = self.end - self.start;
+        let lower = front_lower + back_lower + len.div_ceil(4);
+        let upper = front_upper
+            .zip(back_upper)
+            .and_then(|(front, back)| front.checked_add(back))
+            .and_then(|upper| upper.checked_add(len));
+        (lower, upper)
+    }
+}
+
+impl DoubleEndedIterator for ConcatCharRanges<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(back) = &mut self.back {
+                if let Some(item) = back.next_back() {
+                    return Some(item);
+                }
+                self.back = None;
+            }
+
+            match self.segments.split_last() {
+                Some((segment, rest)) => {
+                    self.end -= segment.len();
+                    self.back = Some(segment.char_ranges_offset(self.end));
+                    self.segments = rest;
+                }
+                None => return self.front.as_mut()?.next_back(),
+            }
+        }
+    }
+}
+
+impl FusedIterator for ConcatCharRanges<'_> {}
+
+impl fmt::Debug for ConcatCharRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ConcatCharRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+/// Returns an iterator over the [`char`]s of `segments` and their start
+/// and end byte positions, as if `segments` were concatenated, e.g. the
+/// pieces of a gap buffer or a list of lines.
+///
+/// See [`ConcatCharRanges`] for more information.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::char_ranges_concat;
+///
+/// let segments = ["a🌏", "", "∈b"];
+///
+/// let mut chars = char_ranges_concat(&segments);
+/// assert_eq!(chars.next(), Some((0..1, 'a')));
+/// assert_eq!(chars.next(), Some((1..5, '🌏')));
+/// assert_eq!(chars.next(), Some((5..8, '∈')));
+/// assert_eq!(chars.next(), Some((8..9, 'b')));
+/// assert_eq!(chars.next(), None);
+/// ```
+#[inline]
+pub fn char_ranges_concat<'a>(segments: &'a [&'a str]) -> ConcatCharRanges<'a> {
+    ConcatCharRanges::new(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::String;
+    use std::vec::Vec;
+
+    use super::char_ranges_concat;
+    use crate::CharRangesExt;
+
+    const CHARS: [char; 8] = ['a', '\n', '\u{80}', 'é', '∈', '⊂', '🌏', '🗻'];
+
+    #[test]
+    fn test_char_ranges_concat() {
+        let segments = ["", "foo", "", "", "🗻∈", ""];
+
+        let chars = char_ranges_concat(&segments).collect::<Vec<_>>();
+        assert_eq!(
+            chars,
+            [
+                (0..1, 'f'),
+                (1..2, 'o'),
+                (2..3, 'o'),
+                (3..7, '🗻'),
+                (7..10, '∈')
+            ]
+        );
+
+        let mut rev = char_ranges_concat(&segments).rev().collect::<Vec<_>>();
+        rev.reverse();
+        assert_eq!(rev, chars);
+
+        assert_eq!(char_ranges_concat(&[]).next(), None);
+        assert_eq!(char_ranges_concat(&["", ""]).next_back(), None);
+    }
+
+    #[test]
+    fn test_char_ranges_concat_meet() {
+        // Front and back meet within the same segment
+        let segments = ["ab", "cde"];
+        let mut chars = char_ranges_concat(&segments);
+        assert_eq!(chars.next_back(), Some((4..5, 'e')));
+        assert_eq!(chars.next(), Some((0..1, 'a')));
+        assert_eq!(chars.next(), Some((1..2, 'b')));
+        assert_eq!(chars.next(), Some((2..3, 'c')));
+        assert_eq!(chars.as_strs(), ("", &[][..], "d"));
+        assert_eq!(chars.next(), Some((3..4, 'd')));
+        assert_eq!(chars.next(), None);
+        assert_eq!(chars.next_back(), None);
+
+        let mut chars = char_ranges_concat(&segments);
+        assert_eq!(chars.next(), Some((0..1, 'a')));
+        assert_eq!(chars.next_back(), Some((4..5, 'e')));
+        assert_eq!(chars.next_back(), Some((3..4, 'd')));
+        assert_eq!(chars.next_back(), Some((2..3, 'c')));
+        assert_eq!(chars.next_back(), Some((1..2, 'b')));
+        assert_eq!(chars.next_back(), None);
+        assert_eq!(chars.next(), None);
+    }
+
+    #[test]
+    fn test_char_ranges_concat_random() {
+        let mut next = crate::test_util::random();
+
+        for _ in 0..300 {
+            let len = next() % 24;
+            let text = (0..len)
+                .map(|_| CHARS[next() % CHARS.len()])
+                .collect::<String>();
+
+            // Split at random `char` boundaries, including empty segments
+            let mut segments = Vec::new();
+            let mut rest = text.as_str();
+            while !rest.is_empty() || next().is_multiple_of(4) {
+                let n = next() % 4;
+                let at = rest.char_indices().nth(n).map_or(rest.len(), |(i, _c)| i);
+                let (segment, tail) = rest.split_at(at);
+                segments.push(segment);
+                rest = tail;
+                if segments.len() > 64 {
+                    break;
+                }
+            }
+            assert_eq!(segments.concat(), text);
+
+            let expected = text.char_ranges().collect::<Vec<_>>();
+            let chars = char_ranges_concat(&segments);
+            assert!(chars.clone().eq(expected.iter().cloned()), "{segments:?}");
+            assert!(chars.clone().rev().eq(expected.iter().cloned().rev()));
+
+            let (lower, upper) = chars.size_hint();
+            assert!(lower <= expected.len());
+            assert!(upper.unwrap() >= expected.len());
+
+            // Alternating from both ends
+            let mut chars = chars;
+            let mut front = Vec::new();
+            let mut back = Vec::new();
+            loop {
+                if next().is_multiple_of(2) {
+                    match chars.next() {
+                        Some(item) => front.push(item),
+                        None => break,
+                    }
+                } else {
+                    match chars.next_back() {
+                        Some(item) => back.push(item),
+                        None => break,
+                    }
+                }
+            }
+            back.reverse();
+            front.extend(back);
+            assert_eq!(front, expected, "{segments:?}");
+        }
+    }
+}
@@ -0,0 +1,357 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::{CharRangesExt, CharRangesOffset};
+
+/// A [`char`] and its start and end byte positions, along with the
+/// previous and next [`char`]s, produced by [`WithContext`].
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct CharContext {
+    /// The previous [`char`], or `None` if `current` is the first [`char`].
+    pub prev: Option<(Range<usize>, char)>,
+    /// The current [`char`].
+    pub current: (Range<usize>, char),
+    /// The next [`char`], or `None` if `current` is the last [`char`].
+    pub next: Option<(Range<usize>, char)>,
+}
+
+/// A span of text, along with at most `n` [`char`]s of context on
+/// each side, produced by [`CharRangesExt::context_around()`].
+///
+/// The context never splits a [`char`], and is truncated at the bounds
+/// of the text. Whether more text precedes or follows the context is
+/// reported by [`truncated_before`](Self::truncated_before) and
+/// [`truncated_after`](Self::truncated_after), e.g. for adding `…`.
+///
+/// [`CharRangesExt::context_around()`]: crate::CharRangesExt::context_around
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ContextAround<'a> {
+    /// The start and end byte positions of the whole context, i.e.
+    /// `before`, `span`, and `after`.
+    pub range: Range<usize>,
+    /// The context preceding `span`.
+    pub before: &'a str,
+    /// The text within the range, snapped outwards to [`char`] boundaries.
+    pub span: &'a str,
+    /// The context following `span`.
+    pub after: &'a str,
+    /// Whether text precedes `before`, i.e. if the context was truncated.
+    pub truncated_before: bool,
+    /// Whether text follows `after`, i.e. if the context was truncated.
+    pub truncated_after: bool,
+}
+
+impl<'a> ContextAround<'a> {
+    /// Returns the context around `range` in `text`, where `range` must
+    /// already be on [`char`] boundaries.
+    pub(crate) fn new(text: &'a str, range: Range<usize>, n: usize) -> Self {
+        let (before, _) = text[..range.start].last_n(n);
+        let after = text[range.end..].char_ranges().take_chars(n).as_str();
+        let end = range.end + after.len();
+
+        Self {
+            range: before.start..end,
+            before: &text[before.clone()],
+            span: &text[range],
+            after,
+            truncated_before: before.start > 0,
+            truncated_after: end < text.len(),
+        }
+    }
+}
+
+/// An iterator over [`char`]s and their start and end byte positions,
+/// along with the previous and next [`char`]s.
+///
+/// Only a window of three [`char`]s is kept, i.e. the previous [`char`]
+/// is retained and the next [`char`] is decoded ahead of time.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRanges::with_context()`] for more information.
+///
+/// [`CharRanges::with_context()`]: crate::CharRanges::with_context
+#[derive(Clone)]
+pub struct WithContext<'a> {
+    iter: CharRangesOffset<'a>,
+    prev: Option<(Range<usize>, char)>,
+    /// The next [`char`], which has been decoded ahead of time.
+    next: Option<(Range<usize>, char)>,
+}
+
+impl<'a> WithContext<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>) -> Self {
+        Self {
+            iter,
+            prev: None,
+            next: None,
+        }
+    }
+
+    /// Returns the remaining substring, i.e. the text of the remaining
+    /// `current` [`char`]s.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻∈🌏";
+    ///
+    /// let mut chars = text.char_ranges().with_context();
+    /// assert_eq!(chars.as_str(), "🗻∈🌏");
+    ///
+    /// chars.next();
+    /// assert_eq!(chars.as_str(), "∈🌏");
+    /// ```
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        let chars = &self.iter.iter;
+        let start = match &self.next {
+            Some((_, c)) => chars.start - c.len_utf8(),
+            None => chars.start,
+        };
+        &chars.text[start..chars.end]
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+}
+
+impl Iterator for WithContext<'_> {
+    type Item = CharContext;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = match self.next.take() {
+            Some(current) => current,
+            None => self.iter.next()?,
+        };
+        self.next = self.iter.next();
+
+        Some(CharContext {
+            prev: self.prev.replace(current.clone()),
+            current,
+            next: self.next.clone(),
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let next = usize::from(self.next.is_some());
+        (lower + next, upper.map(|upper| upper + next))
+    }
+}
+
+impl FusedIterator for WithContext<'_> {}
+
+impl fmt::Debug for WithContext<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "WithContext(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use super::{CharContext, ContextAround};
+    use crate::CharRangesExt;
+
+    #[test]
+    fn test_with_context() {
+        let text = "Hello 🗻12∈45🌏 World ¢";
+        let chars = text.char_ranges().collect::<Vec<_>>();
+
+        let contexts = text.char_ranges().with_context().collect::<Vec<_>>();
+        assert_eq!(contexts.len(), chars.len());
+
+        for (i, ctx) in contexts.iter().enumerate() {
+            assert_eq!(ctx.current, chars[i]);
+            assert_eq!(ctx.prev, i.checked_sub(1).map(|i| chars[i].clone()));
+            assert_eq!(ctx.next, chars.get(i + 1).cloned());
+
+            // Neighbors are contiguous with the current char
+            let (r, _c) = &ctx.current;
+            if let Some((prev, _c)) = &ctx.prev {
+                assert_eq!(prev.end, r.start);
+            }
+            if let Some((next, _c)) = &ctx.next {
+                assert_eq!(r.end, next.start);
+            }
+        }
+
+        assert_eq!(contexts[0].prev, None);
+        assert_eq!(contexts[chars.len() - 1].next, None);
+    }
+
+    #[test]
+    fn test_with_context_edges() {
+        assert_eq!("".char_ranges().with_context().next(), None);
+
+        let mut chars = "🌏".char_ranges().with_context();
+        assert_eq!(
+            chars.next(),
+            Some(CharContext {
+                prev: None,
+                current: (0..4, '🌏'),
+                next: None,
+            }),
+        );
+        assert_eq!(chars.next(), None);
+
+        let mut chars = "ab".char_ranges().with_context();
+        assert_eq!(chars.size_hint(), (1, Some(2)));
+        assert_eq!(chars.next().map(|ctx| ctx.next), Some(Some((1..2, 'b'))));
+        assert_eq!(chars.as_str(), "b");
+        assert_eq!(chars.size_hint(), (1, Some(1)));
+        assert_eq!(chars.next().map(|ctx| ctx.prev), Some(Some((0..1, 'a'))));
+        assert_eq!(chars.as_str(), "");
+        assert_eq!(chars.next(), None);
+    }
+
+    #[test]
+    fn test_with_context_offset() {
+        let line = "it's 'quoted'";
+        let start = 20;
+
+        let apostrophes = line
+            .char_ranges_offset(start)
+            .with_context()
+            .filter(|ctx| ctx.current.1 == '\'')
+            .filter(|ctx| {
+                let is_alphabetic =
+                    |c: &Option<(_, char)>| c.as_ref().is_some_and(|(_, c)| c.is_alphabetic());
+                is_alphabetic(&ctx.prev) && is_alphabetic(&ctx.next)
+            })
+            .map(|ctx| ctx.current)
+            .collect::<Vec<_>>();
+        assert_eq!(apostrophes, [(22..23, '\'')]);
+
+        let mut chars = line[5..].char_ranges_offset(start + 5).with_context();
+        assert_eq!(chars.offset(), 25);
+        let ctx = chars.next().unwrap();
+        assert_eq!(ctx.prev, None);
+        assert_eq!(ctx.current, (25..26, '\''));
+        assert_eq!(ctx.next, Some((26..27, 'q')));
+    }
+
+    #[test]
+    fn test_context_around() {
+        let text = "Hello 🌏 World";
+
+        let ctx = text.context_around(6..10, 3).unwrap();
+        assert_eq!(
+            ctx,
+            ContextAround {
+                range: 3..13,
+                before: "lo ",
+                span: "🌏",
+                after: " Wo",
+                truncated_before: true,
+                truncated_after: true,
+            }
+        );
+        assert_eq!(&text[ctx.range.clone()], "lo 🌏 Wo");
+
+        // Snapped outwards to `char` boundaries
+        assert_eq!(text.context_around(7..8, 3), Some(ctx));
+
+        // n = 0
+        let ctx = text.context_around(6..10, 0).unwrap();
+        assert_eq!(
+            (ctx.range, ctx.before, ctx.span, ctx.after),
+            (6..10, "", "🌏", "")
+        );
+        assert!(ctx.truncated_before && ctx.truncated_after);
+
+        // Empty range
+        let ctx = text.context_around(5..5, 2).unwrap();
+        assert_eq!(
+            (ctx.range, ctx.before, ctx.span, ctx.after),
+            (3..10, "lo", "", " 🌏")
+        );
+
+        // Invalid ranges
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = 8..2;
+        assert_eq!(text.context_around(reversed, 3), None);
+        assert_eq!(text.context_around(100..200, 3), None);
+    }
+
+    #[test]
+    fn test_context_around_bounds() {
+        let text = "Hello 🌏 World";
+
+        // At the very start
+        let ctx = text.context_around(0..1, 3).unwrap();
+        assert_eq!(
+            (ctx.range, ctx.before, ctx.span, ctx.after),
+            (0..4, "", "H", "ell")
+        );
+        assert!(!ctx.truncated_before && ctx.truncated_after);
+
+        // At the very end
+        let ctx = text.context_around(15..16, 3).unwrap();
+        assert_eq!(
+            (ctx.range, ctx.before, ctx.span, ctx.after),
+            (12..16, "orl", "d", "")
+        );
+        assert!(ctx.truncated_before && !ctx.truncated_after);
+
+        // Context colliding with both bounds
+        let ctx = text.context_around(6..10, 100).unwrap();
+        assert_eq!(
+            (ctx.before, ctx.span, ctx.after),
+            ("Hello ", "🌏", " World")
+        );
+        assert_eq!(ctx.range, 0..text.len());
+        assert!(!ctx.truncated_before && !ctx.truncated_after);
+
+        // Exactly reaching the bounds is not truncated
+        let ctx = text.context_around(6..10, 6).unwrap();
+        assert_eq!(ctx.range, 0..text.len());
+        assert!(!ctx.truncated_before && !ctx.truncated_after);
+
+        let ctx = "".context_around(0..0, 3).unwrap();
+        assert_eq!(ctx.range, 0..0);
+        assert!(!ctx.truncated_before && !ctx.truncated_after);
+    }
+
+    #[test]
+    fn test_context_around_multi_byte_edges() {
+        // Multi-byte chars at every edge
+        let text = "🗻∈🌏¢🗻∈🌏";
+
+        for start in 0..=text.len() {
+            for end in start..=text.len() {
+                for n in 0..4 {
+                    let ctx = text.context_around(start..end, n).unwrap();
+                    assert_eq!(
+                        &text[ctx.range.clone()],
+                        [ctx.before, ctx.span, ctx.after].concat()
+                    );
+                    assert!(ctx.before.chars().count() <= n);
+                    assert!(ctx.after.chars().count() <= n);
+                    assert_eq!(ctx.truncated_before, ctx.range.start > 0);
+                    assert_eq!(ctx.truncated_after, ctx.range.end < text.len());
+                }
+            }
+        }
+
+        let ctx = text.context_around(8..9, 1).unwrap();
+        assert_eq!((ctx.before, ctx.span, ctx.after), ("∈", "🌏", "¢"));
+        assert_eq!(ctx.range, 4..13);
+    }
+}
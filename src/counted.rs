@@ -0,0 +1,234 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::CharRangesOffset;
+
+/// An iterator over [`char`]s and their start and end byte positions,
+/// which knows the exact number of remaining [`char`]s.
+///
+/// The number of [`char`]s is counted once when created, after which
+/// it is kept up to date as [`char`]s are consumed from either end.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRanges::counted()`] for more information.
+///
+/// [`CharRanges::counted()`]: crate::CharRanges::counted
+#[derive(Clone)]
+pub struct CountedCharRanges<'a> {
+    iter: CharRangesOffset<'a>,
+    /// The number of remaining [`char`]s in `iter`.
+    len: usize,
+}
+
+impl<'a> CountedCharRanges<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>) -> Self {
+        Self {
+            len: iter.remaining_char_count(),
+            iter,
+        }
+    }
+
+    /// Returns the remaining substring.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻∈🌏";
+    ///
+    /// let mut chars = text.char_ranges().counted();
+    /// assert_eq!(chars.next(), Some((0..4, '🗻')));
+    ///
+    /// assert_eq!(chars.as_str(), "∈🌏");
+    /// assert_eq!(chars.len(), 2);
+    /// ```
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+}
+
+impl Iterator for CountedCharRanges<'_> {
+    type Item = (Range<usize>, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        self.len -= 1;
+        Some(item)
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<(Range<usize>, char)> {
+        self.next_back()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len {
+            _ = self.iter.advance_by(self.len);
+            self.len = 0;
+            return None;
+        }
+
+        let item = self.iter.nth(n);
+        self.len -= n + 1;
+        item
+    }
+}
+
+impl DoubleEndedIterator for CountedCharRanges<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next_back()?;
+        self.len -= 1;
+        Some(item)
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len {
+            _ = self.iter.advance_back_by(self.len);
+            self.len = 0;
+            return None;
+        }
+
+        let item = self.iter.nth_back(n);
+        self.len -= n + 1;
+        item
+    }
+}
+
+impl ExactSizeIterator for CountedCharRanges<'_> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl FusedIterator for CountedCharRanges<'_> {}
+
+impl fmt::Debug for CountedCharRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CountedCharRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::CharRangesExt;
+
+    const TEXT: &str = "Hello 🗻12∈45🌏 World ¢";
+
+    #[test]
+    fn test_counted() {
+        let mut chars = TEXT.char_ranges().counted();
+        let mut expected = TEXT.char_ranges();
+        assert_eq!(chars.len(), 21);
+
+        loop {
+            assert_eq!(chars.len(), expected.count());
+            assert_eq!(chars.as_str(), expected.as_str());
+            match chars.next() {
+                Some(item) => assert_eq!(Some(item), expected.next()),
+                None => break,
+            }
+            assert_eq!(chars.len(), expected.count());
+            match chars.next_back() {
+                Some(item) => assert_eq!(Some(item), expected.next_back()),
+                None => break,
+            }
+        }
+
+        assert_eq!(chars.len(), 0);
+        assert_eq!(expected.next(), None);
+    }
+
+    #[test]
+    fn test_counted_nth() {
+        let count = TEXT.chars().count();
+        for n in 0..(count + 2) {
+            let mut chars = TEXT.char_ranges().counted();
+            let mut expected = TEXT.char_ranges();
+            loop {
+                let item = chars.nth(n);
+                assert_eq!(item, expected.nth(n));
+                assert_eq!(chars.len(), expected.count());
+                assert_eq!(chars.as_str(), expected.as_str());
+                if item.is_none() {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_counted_nth_back() {
+        let count = TEXT.chars().count();
+        for n in 0..(count + 2) {
+            let mut chars = TEXT.char_ranges().counted();
+            let mut expected = TEXT.char_ranges();
+            loop {
+                let item = chars.nth_back(n);
+                assert_eq!(item, expected.nth_back(n));
+                assert_eq!(chars.len(), expected.count());
+                assert_eq!(chars.as_str(), expected.as_str());
+                if item.is_none() {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_counted_mixed() {
+        let mut chars = TEXT.char_ranges().counted();
+        assert_eq!(chars.nth(2), Some((2..3, 'l')));
+        assert_eq!(chars.len(), 18);
+        assert_eq!(chars.nth_back(3), Some((25..26, 'l')));
+        assert_eq!(chars.len(), 14);
+        assert_eq!(chars.next(), Some((3..4, 'l')));
+        assert_eq!(chars.next_back(), Some((24..25, 'r')));
+        assert_eq!(chars.len(), 12);
+        assert_eq!(chars.size_hint(), (12, Some(12)));
+        assert_eq!(chars.clone().count(), 12);
+        assert_eq!(chars.nth(11), Some((23..24, 'o')));
+        assert_eq!(chars.len(), 0);
+        assert_eq!(chars.next_back(), None);
+    }
+
+    #[test]
+    fn test_counted_offset() {
+        let text = "Hello 👋 World 🌏";
+
+        let start = 11;
+        let mut chars = text[start..].char_ranges_offset(start).counted();
+        assert_eq!(chars.offset(), start);
+        assert_eq!(chars.len(), 7);
+        assert_eq!(chars.next_back(), Some((17..21, '🌏')));
+        assert_eq!(chars.len(), 6);
+    }
+}
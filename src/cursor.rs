@@ -0,0 +1,308 @@
+use core::ops::Range;
+
+use crate::{CharRanges, SeekError};
+
+/// A cursor positioned between [`char`]s of a text, which can be moved
+/// and peeked in both directions.
+///
+/// Unlike [`CharRanges`], which is consumed from both ends, a cursor
+/// represents a single position, e.g. the caret in an editor.
+///
+/// Note: Cloning the cursor is essentially a copy.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::CharCursor;
+///
+/// let text = "🗻∈🌏";
+///
+/// let mut cursor = CharCursor::new(text);
+/// assert_eq!(cursor.move_next(), Some((0..4, '🗻')));
+/// assert_eq!(cursor.move_next(), Some((4..7, '∈')));
+/// assert_eq!(cursor.pos(), 7);
+///
+/// assert_eq!(cursor.peek_prev(), Some((4..7, '∈')));
+/// assert_eq!(cursor.peek_next(), Some((7..11, '🌏')));
+///
+/// assert_eq!(cursor.move_prev(), Some((4..7, '∈')));
+/// assert_eq!(cursor.before(), "🗻");
+/// assert_eq!(cursor.after(), "∈🌏");
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct CharCursor<'a> {
+    text: &'a str,
+    /// Byte position of the cursor, always on a [`char`] boundary.
+    pos: usize,
+}
+
+impl<'a> CharCursor<'a> {
+    /// Creates a cursor positioned at the start of `text`.
+    #[inline]
+    pub fn new(text: &'a str) -> Self {
+        Self { text, pos: 0 }
+    }
+
+    /// Creates a cursor positioned at the byte position `pos` of `text`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `pos` is out of bounds of `text`, or not
+    /// on a [`char`] boundary.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{CharCursor, SeekError};
+    ///
+    /// let text = "🗻∈🌏";
+    ///
+    /// let cursor = CharCursor::with_pos(text, 4).unwrap();
+    /// assert_eq!(cursor.peek_next(), Some((4..7, '∈')));
+    ///
+    /// assert_eq!(CharCursor::with_pos(text, 5).err(), Some(SeekError::NotCharBoundary));
+    /// assert_eq!(CharCursor::with_pos(text, 12).err(), Some(SeekError::OutOfBounds));
+    /// ```
+    #[inline]
+    pub fn with_pos(text: &'a str, pos: usize) -> Result<Self, SeekError> {
+        let mut cursor = Self::new(text);
+        cursor.set_pos(pos)?;
+        Ok(cursor)
+    }
+
+    /// Returns the text this cursor was created with.
+    #[inline]
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// Returns the byte position of the cursor.
+    #[inline]
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Moves the cursor to the byte position `pos`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `pos` is out of bounds of the text, or not
+    /// on a [`char`] boundary. In which case the cursor is not moved.
+    pub fn set_pos(&mut self, pos: usize) -> Result<(), SeekError> {
+        if pos > self.text.len() {
+            Err(SeekError::OutOfBounds)
+        } else if !self.text.is_char_boundary(pos) {
+            Err(SeekError::NotCharBoundary)
+        } else {
+            self.pos = pos;
+            Ok(())
+        }
+    }
+
+    /// Returns the substring before the cursor.
+    #[inline]
+    pub fn before(&self) -> &'a str {
+        &self.text[..self.pos]
+    }
+
+    /// Returns the substring after the cursor.
+    #[inline]
+    pub fn after(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    /// Returns the [`char`] after the cursor and its start and end
+    /// byte positions, without moving the cursor.
+    #[inline]
+    pub fn peek_next(&self) -> Option<(Range<usize>, char)> {
+        let c = self.after().chars().next()?;
+        Some((self.pos..(self.pos + c.len_utf8()), c))
+    }
+
+    /// Returns the [`char`] before the cursor and its start and end
+    /// byte positions, without moving the cursor.
+    #[inline]
+    pub fn peek_prev(&self) -> Option<(Range<usize>, char)> {
+        let c = self.before().chars().next_back()?;
+        Some(((self.pos - c.len_utf8())..self.pos, c))
+    }
+
+    /// Moves the cursor forward over the next [`char`], and returns
+    /// it and its start and end byte positions.
+    ///
+    /// Returns `None` and leaves the cursor unchanged, if the cursor
+    /// is at the end of the text.
+    #[inline]
+    pub fn move_next(&mut self) -> Option<(Range<usize>, char)> {
+        let (r, c) = self.peek_next()?;
+        self.pos = r.end;
+        Some((r, c))
+    }
+
+    /// Moves the cursor backward over the previous [`char`], and returns
+    /// it and its start and end byte positions.
+    ///
+    /// Returns `None` and leaves the cursor unchanged, if the cursor
+    /// is at the start of the text.
+    #[inline]
+    pub fn move_prev(&mut self) -> Option<(Range<usize>, char)> {
+        let (r, c) = self.peek_prev()?;
+        self.pos = r.start;
+        Some((r, c))
+    }
+
+    /// Returns a [`CharRanges`] over the [`char`]s after the cursor.
+    ///
+    /// The positions are relative to the whole text, i.e. the
+    /// [original text](CharRanges::original_str) is the whole text.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharCursor;
+    ///
+    /// let text = "🗻∈🌏";
+    ///
+    /// let cursor = CharCursor::with_pos(text, 4).unwrap();
+    ///
+    /// let mut chars = cursor.ranges_after();
+    /// assert_eq!(chars.next(), Some((4..7, '∈')));
+    /// assert_eq!(chars.next(), Some((7..11, '🌏')));
+    /// assert_eq!(chars.next(), None);
+    /// ```
+    #[inline]
+    pub fn ranges_after(&self) -> CharRanges<'a> {
+        CharRanges {
+            text: self.text,
+            start: self.pos,
+            end: self.text.len(),
+        }
+    }
+
+    /// Returns a [`CharRanges`] over the [`char`]s before the cursor.
+    ///
+    /// The positions are relative to the whole text, i.e. the
+    /// [original text](CharRanges::original_str) is the whole text.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharCursor;
+    ///
+    /// let text = "🗻∈🌏";
+    ///
+    /// let cursor = CharCursor::with_pos(text, 7).unwrap();
+    ///
+    /// let mut chars = cursor.ranges_before();
+    /// assert_eq!(chars.next_back(), Some((4..7, '∈')));
+    /// assert_eq!(chars.next_back(), Some((0..4, '🗻')));
+    /// assert_eq!(chars.next_back(), None);
+    /// ```
+    #[inline]
+    pub fn ranges_before(&self) -> CharRanges<'a> {
+        CharRanges {
+            text: self.text,
+            start: 0,
+            end: self.pos,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CharCursor;
+    use crate::{CharRangesExt, SeekError};
+
+    const TEXT: &str = "Hello 🗻12∈45🌏 World ¢";
+
+    fn assert_boundary(cursor: &CharCursor<'_>) {
+        assert!(TEXT.is_char_boundary(cursor.pos()));
+        assert_eq!(cursor.before(), &TEXT[..cursor.pos()]);
+        assert_eq!(cursor.after(), &TEXT[cursor.pos()..]);
+    }
+
+    #[test]
+    fn test_cursor_walk() {
+        let mut cursor = CharCursor::new(TEXT);
+        assert_eq!(cursor.peek_prev(), None);
+        assert_eq!(cursor.move_prev(), None);
+
+        let mut expected = TEXT.char_ranges();
+        while let Some((r, c)) = cursor.move_next() {
+            assert_boundary(&cursor);
+            assert_eq!(Some((r.clone(), c)), expected.next());
+            assert_eq!(cursor.pos(), r.end);
+            assert_eq!(cursor.peek_prev(), Some((r, c)));
+        }
+        assert_eq!(expected.next(), None);
+        assert_eq!(cursor.pos(), TEXT.len());
+        assert_eq!(cursor.peek_next(), None);
+
+        let mut expected = TEXT.char_ranges();
+        while let Some((r, c)) = cursor.move_prev() {
+            assert_boundary(&cursor);
+            assert_eq!(Some((r.clone(), c)), expected.next_back());
+            assert_eq!(cursor.pos(), r.start);
+            assert_eq!(cursor.peek_next(), Some((r, c)));
+        }
+        assert_eq!(expected.next_back(), None);
+        assert_eq!(cursor.pos(), 0);
+    }
+
+    #[test]
+    fn test_cursor_seek() {
+        let mut cursor = CharCursor::new(TEXT);
+
+        // Deterministic pseudo-random positions, including
+        // positions within chars and out of bounds
+        let mut random = crate::test_util::random();
+        for _ in 0..1000 {
+            let pos = random() % (TEXT.len() + 3);
+
+            let prev = cursor.pos();
+            match cursor.set_pos(pos) {
+                Ok(()) => assert_eq!(cursor.pos(), pos),
+                Err(SeekError::OutOfBounds) => {
+                    assert!(pos > TEXT.len());
+                    assert_eq!(cursor.pos(), prev);
+                }
+                Err(SeekError::NotCharBoundary) => {
+                    assert!(!TEXT.is_char_boundary(pos));
+                    assert_eq!(cursor.pos(), prev);
+                }
+                Err(err) => panic!("unexpected error: {err}"),
+            }
+            assert_boundary(&cursor);
+
+            let next = cursor.peek_next();
+            let prev = cursor.peek_prev();
+            assert_eq!(next, cursor.ranges_after().next());
+            assert_eq!(prev, cursor.ranges_before().next_back());
+
+            let mut moved = cursor;
+            assert_eq!(moved.move_next(), next);
+            assert_boundary(&moved);
+
+            let mut moved = cursor;
+            assert_eq!(moved.move_prev(), prev);
+            assert_boundary(&moved);
+        }
+    }
+
+    #[test]
+    fn test_cursor_ranges() {
+        let cursor = CharCursor::with_pos(TEXT, 12).unwrap();
+        assert_eq!(cursor.before(), "Hello 🗻12");
+        assert_eq!(cursor.after(), "∈45🌏 World ¢");
+
+        let after = cursor.ranges_after();
+        assert_eq!(after.as_str(), cursor.after());
+        assert_eq!(after.original_str(), TEXT);
+        assert!(after.eq(TEXT.char_ranges().skip(9)));
+
+        let before = cursor.ranges_before();
+        assert_eq!(before.as_str(), cursor.before());
+        assert_eq!(before.original_str(), TEXT);
+        assert!(before.eq(TEXT.char_ranges().take(9)));
+    }
+}
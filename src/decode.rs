@@ -0,0 +1,348 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use encoding_rs::{Decoder, DecoderResult, Encoding};
+
+/// The size of the intermediate UTF-8 buffer, which is decoded into.
+const BUF_LEN: usize = 16;
+
+/// An iterator over the [`char`]s decoded from bytes in a legacy encoding,
+/// e.g. Shift_JIS or Windows-1252, and the start and end byte positions
+/// of the bytes in the source, which produced them.
+///
+/// The source bytes are fed to the decoder one at a time, such that each
+/// output [`char`] can be attributed to the bytes consumed since the
+/// previous output. If a single input sequence decodes into multiple
+/// [`char`]s, then they share the same range.
+///
+/// Malformed byte sequences produce `'\u{FFFD}'`, i.e. the replacement
+/// character, with the range of the malformed bytes.
+///
+/// A byte order mark is not sniffed nor removed, and produces `'\u{FEFF}'`
+/// like any other [`char`], if the encoding decodes it.
+///
+/// See [`decode_char_ranges()`] for more information.
+pub struct DecodeCharRanges<'a> {
+    bytes: &'a [u8],
+    /// The position of the next byte to feed to the decoder.
+    pos: usize,
+    /// The start of the bytes fed to the decoder, which have not yet
+    /// produced any output.
+    pending: usize,
+    offset: usize,
+    decoder: Decoder,
+    /// Decoded [`char`]s and their start and end byte positions, which
+    /// have not yet been returned.
+    queue: [(usize, usize, char); BUF_LEN + 1],
+    queue_start: usize,
+    queue_end: usize,
+    finished: bool,
+}
+
+impl<'a> DecodeCharRanges<'a> {
+    #[inline]
+    fn new(bytes: &'a [u8], encoding: &'static Encoding, offset: usize) -> Self {
+        Self {
+            bytes,
+            pos: 0,
+            pending: 0,
+            offset,
+            decoder: encoding.new_decoder_without_bom_handling(),
+            queue: [(0, 0, '\0'); BUF_LEN + 1],
+            queue_start: 0,
+            queue_end: 0,
+            finished: false,
+        }
+    }
+
+    /// Returns the remaining bytes, which have not yet been fed to the
+    /// decoder.
+    #[inline]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        &self.bytes[self.pos..]
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the [`Encoding`] of the decoder.
+    #[inline]
+    pub fn encoding(&self) -> &'static Encoding {
+        self.decoder.encoding()
+    }
+
+    /// Applies the offset to a position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position overflows `usize`, regardless of
+    /// whether overflow checks are enabled.
+    #[inline]
+    fn apply_offset_to(&self, pos: usize) -> usize {
+        match pos.checked_add(self.offset) {
+            Some(pos) => pos,
+            None => panic!("position {pos} with offset {} overflows usize", self.offset),
+        }
+    }
+
+    #[inline]
+    fn push(&mut self, start: usize, end: usize, c: char) {
+        self.queue[self.queue_end] = (start, end, c);
+        self.queue_end += 1;
+    }
+
+    /// Pushes all [`char`]s in `s` with the range `start..end`.
+    fn push_str(&mut self, s: &str, start: usize, end: usize) {
+        for c in s.chars() {
+            self.push(start, end, c);
+        }
+    }
+
+    /// Feeds the next byte, or flushes the decoder if all bytes have been
+    /// fed, and queues any decoded [`char`]s.
+    fn decode(&mut self) {
+        let mut buf = [0; BUF_LEN];
+
+        let last = self.pos == self.bytes.len();
+        let src = &self.bytes[self.pos..(self.pos + usize::from(!last))];
+
+        let (result, read, written) = self
+            .decoder
+            .decode_to_utf8_without_replacement(src, &mut buf, last);
+        self.pos += read;
+
+        // The decoder only ever writes whole `char`s
+        let s = core::str::from_utf8(&buf[..written]).unwrap_or_default();
+
+        match result {
+            DecoderResult::InputEmpty | DecoderResult::OutputFull => {
+                if !s.is_empty() {
+                    self.push_str(s, self.pending, self.pos);
+                    self.pending = self.pos;
+                }
+
+                if last && (result == DecoderResult::InputEmpty) {
+                    self.finished = true;
+                }
+            }
+            DecoderResult::Malformed(len, after) => {
+                let end = self.pos - usize::from(after);
+                let start = end.saturating_sub(usize::from(len)).max(self.pending);
+
+                self.push_str(s, self.pending, start);
+                self.push(start, end, char::REPLACEMENT_CHARACTER);
+                self.pending = end;
+            }
+        }
+    }
+}
+
+impl Iterator for DecodeCharRanges<'_> {
+    type Item = (Range<usize>, char);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.queue_start == self.queue_end {
+            if self.finished {
+                return None;
+            }
+
+            self.queue_start = 0;
+            self.queue_end = 0;
+            self.decode();
+        }
+
+        let (start, end, c) = self.queue[self.queue_start];
+        self.queue_start += 1;
+
+        Some((self.apply_offset_to(start)..self.apply_offset_to(end), c))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let queued = self.queue_end - self.queue_start;
+        let remaining = self.bytes.len() - self.pending;
+        if self.finished {
+            (queued, Some(queued))
+        } else {
+            // Every byte produces at most a few `char`s, e.g. Big5
+            // decodes some sequences into 2 `char`s
+            let upper = remaining
+                .checked_mul(2)
+                .and_then(|upper| upper.checked_add(queued + 1));
+            (queued, upper)
+        }
+    }
+}
+
+impl FusedIterator for DecodeCharRanges<'_> {}
+
+impl fmt::Debug for DecodeCharRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DecodeCharRanges")
+            .field("encoding", &self.encoding())
+            .field("offset", &self.offset)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Returns an iterator over the [`char`]s decoded from `bytes` in
+/// `encoding`, and the start and end byte positions of the bytes in
+/// `bytes` which produced them, with `offset` applied to all positions.
+///
+/// This allows diagnostics to point at the original bytes of a file, e.g.
+/// encoded in Shift_JIS, instead of at positions in the decoded string.
+/// The `offset` is useful when decoding a file in chunks, i.e. it should
+/// be the position of the chunk in the file.
+///
+/// See [`DecodeCharRanges`] for more information.
+///
+/// # Panics
+///
+/// If `offset` plus the length of `bytes` overflows `usize`, then the
+/// returned iterator panics when producing a position that overflows,
+/// in both debug and release builds.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::decode_char_ranges;
+/// use encoding_rs::SHIFT_JIS;
+///
+/// // "a日本" in Shift_JIS
+/// let bytes = b"a\x93\xfa\x96\x7b";
+///
+/// let mut chars = decode_char_ranges(bytes, SHIFT_JIS, 0);
+/// assert_eq!(chars.next(), Some((0..1, 'a')));
+/// assert_eq!(chars.next(), Some((1..3, '日')));
+/// assert_eq!(chars.next(), Some((3..5, '本')));
+/// assert_eq!(chars.next(), None);
+///
+/// // Truncated double-byte sequence
+/// let mut chars = decode_char_ranges(&bytes[..4], SHIFT_JIS, 100);
+/// assert_eq!(chars.next(), Some((100..101, 'a')));
+/// assert_eq!(chars.next(), Some((101..103, '日')));
+/// assert_eq!(chars.next(), Some((103..104, '\u{FFFD}')));
+/// assert_eq!(chars.next(), None);
+/// ```
+#[inline]
+pub fn decode_char_ranges<'a>(
+    bytes: &'a [u8],
+    encoding: &'static Encoding,
+    offset: usize,
+) -> DecodeCharRanges<'a> {
+    DecodeCharRanges::new(bytes, encoding, offset)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::String;
+    use std::vec::Vec;
+
+    use encoding_rs::{BIG5, SHIFT_JIS, UTF_8, WINDOWS_1252};
+
+    use super::decode_char_ranges;
+
+    #[test]
+    fn test_decode_windows_1252() {
+        // "Café €5" in Windows-1252
+        let bytes = b"Caf\xe9 \x805";
+
+        let chars = decode_char_ranges(bytes, WINDOWS_1252, 0).collect::<Vec<_>>();
+        assert_eq!(
+            chars,
+            [
+                (0..1, 'C'),
+                (1..2, 'a'),
+                (2..3, 'f'),
+                (3..4, 'é'),
+                (4..5, ' '),
+                (5..6, '€'),
+                (6..7, '5'),
+            ]
+        );
+
+        // Every byte produces exactly one `char`
+        let bytes = (0..=255).collect::<Vec<u8>>();
+        let chars = decode_char_ranges(&bytes, WINDOWS_1252, 10);
+        for (i, (r, _c)) in chars.enumerate() {
+            assert_eq!(r, (i + 10)..(i + 11));
+        }
+    }
+
+    #[test]
+    fn test_decode_shift_jis() {
+        // "ｱ日本語x" in Shift_JIS, where 'ｱ' is a single byte
+        let bytes = b"\xb1\x93\xfa\x96\x7b\x8c\xea\x78";
+
+        let chars = decode_char_ranges(bytes, SHIFT_JIS, 0).collect::<Vec<_>>();
+        assert_eq!(
+            chars,
+            [
+                (0..1, 'ｱ'),
+                (1..3, '日'),
+                (3..5, '本'),
+                (5..7, '語'),
+                (7..8, 'x'),
+            ]
+        );
+
+        let s = chars.iter().map(|(_r, c)| c).collect::<String>();
+        assert_eq!(s, "ｱ日本語x");
+    }
+
+    #[test]
+    fn test_decode_malformed() {
+        // Invalid lead byte
+        let chars = decode_char_ranges(b"a\xa0b", SHIFT_JIS, 0).collect::<Vec<_>>();
+        assert_eq!(chars, [(0..1, 'a'), (1..2, '\u{FFFD}'), (2..3, 'b')]);
+
+        // ASCII trail byte, which is not part of the malformed sequence
+        let chars = decode_char_ranges(b"\x93 b", SHIFT_JIS, 0).collect::<Vec<_>>();
+        assert_eq!(chars, [(0..1, '\u{FFFD}'), (1..2, ' '), (2..3, 'b')]);
+
+        // Truncated at the end
+        let chars = decode_char_ranges(b"a\x93", SHIFT_JIS, 0).collect::<Vec<_>>();
+        assert_eq!(chars, [(0..1, 'a'), (1..2, '\u{FFFD}')]);
+
+        // Malformed UTF-8
+        let chars = decode_char_ranges(b"\xe2\x88x\xff", UTF_8, 0).collect::<Vec<_>>();
+        assert_eq!(chars, [(0..2, '\u{FFFD}'), (2..3, 'x'), (3..4, '\u{FFFD}')]);
+
+        let s = chars.iter().map(|(_r, c)| c).collect::<String>();
+        assert_eq!(s, "\u{FFFD}x\u{FFFD}");
+    }
+
+    #[test]
+    fn test_decode_multiple_chars() {
+        // Big5 decodes 0x8862 into "Ê̄", i.e. 2 `char`s sharing the range
+        let chars = decode_char_ranges(b"a\x88\x62b", BIG5, 0).collect::<Vec<_>>();
+        assert_eq!(
+            chars,
+            [
+                (0..1, 'a'),
+                (1..3, '\u{CA}'),
+                (1..3, '\u{304}'),
+                (3..4, 'b'),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_empty() {
+        assert_eq!(decode_char_ranges(b"", SHIFT_JIS, 0).next(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "position 2 with offset 18446744073709551614 overflows usize")]
+    fn test_decode_offset_overflow() {
+        let mut chars = decode_char_ranges(b"ab", SHIFT_JIS, usize::MAX - 1);
+        assert_eq!(chars.next(), Some(((usize::MAX - 1)..usize::MAX, 'a')));
+        chars.next();
+    }
+}
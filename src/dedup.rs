@@ -0,0 +1,184 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::CharRangesOffset;
+
+/// An iterator over maximal runs of identical consecutive [`char`]s,
+/// and their start and end byte positions.
+///
+/// Produced items are `(range, char, count)`, where `range` spans all
+/// `count` occurrences of `char` in the run.
+///
+/// Runs are always maximal, also when consuming from both ends. Each run
+/// is fully consumed from the end it is produced from, so the remaining
+/// text always starts and ends between two runs, and a run is never split
+/// between the front and the back.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRanges::dedup_runs()`] for more information.
+///
+/// [`CharRanges::dedup_runs()`]: crate::CharRanges::dedup_runs
+#[derive(Clone)]
+pub struct DedupRuns<'a> {
+    iter: CharRangesOffset<'a>,
+}
+
+impl<'a> DedupRuns<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>) -> Self {
+        Self { iter }
+    }
+
+    /// Returns the remaining substring, i.e. the part of the text
+    /// which is not covered by any run returned so far.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+}
+
+impl Iterator for DedupRuns<'_> {
+    type Item = (Range<usize>, char, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let chars = &mut self.iter.iter;
+        let text = chars.as_str();
+        let c = text.chars().next()?;
+
+        let len = text.len() - text.trim_start_matches(c).len();
+        let start = chars.start;
+        chars.start += len;
+
+        let r = self.iter.apply_offset(start..(start + len));
+        Some((r, c, len / c.len_utf8()))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (lower.min(1), upper)
+    }
+}
+
+impl DoubleEndedIterator for DedupRuns<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let chars = &mut self.iter.iter;
+        let text = chars.as_str();
+        let c = text.chars().next_back()?;
+
+        let len = text.len() - text.trim_end_matches(c).len();
+        let end = chars.end;
+        chars.end -= len;
+
+        let r = self.iter.apply_offset((end - len)..end);
+        Some((r, c, len / c.len_utf8()))
+    }
+}
+
+impl FusedIterator for DedupRuns<'_> {}
+
+impl fmt::Debug for DedupRuns<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DedupRuns(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use crate::CharRangesExt;
+
+    #[test]
+    fn test_dedup_runs() {
+        let text = "==  ∈∈∈🌏🌏x";
+
+        let mut runs = text.char_ranges().dedup_runs();
+        assert_eq!(runs.next(), Some((0..2, '=', 2)));
+        assert_eq!(runs.next(), Some((2..4, ' ', 2)));
+        assert_eq!(runs.as_str(), "∈∈∈🌏🌏x");
+        assert_eq!(runs.next(), Some((4..13, '∈', 3)));
+        assert_eq!(runs.next(), Some((13..21, '🌏', 2)));
+        assert_eq!(runs.next(), Some((21..22, 'x', 1)));
+        assert_eq!(runs.next(), None);
+
+        let runs = text.char_ranges().dedup_runs().collect::<Vec<_>>();
+        let mut rev = text.char_ranges().dedup_runs().rev().collect::<Vec<_>>();
+        rev.reverse();
+        assert_eq!(runs, rev);
+    }
+
+    #[test]
+    fn test_dedup_runs_edges() {
+        assert_eq!("".char_ranges().dedup_runs().next(), None);
+        assert!("a".char_ranges().dedup_runs().eq([(0..1, 'a', 1)]));
+        assert!("🗻".char_ranges().dedup_runs().eq([(0..4, '🗻', 1)]));
+        assert!("aaaa".char_ranges().dedup_runs().eq([(0..4, 'a', 4)]));
+        assert!("🗻🗻🗻"
+            .char_ranges()
+            .dedup_runs()
+            .rev()
+            .eq([(0..12, '🗻', 3)]));
+
+        let runs = "abab".char_ranges().dedup_runs();
+        assert!(runs.eq([
+            (0..1, 'a', 1),
+            (1..2, 'b', 1),
+            (2..3, 'a', 1),
+            (3..4, 'b', 1)
+        ]));
+    }
+
+    #[test]
+    fn test_dedup_runs_double_ended() {
+        let text = "aaabbbccc";
+
+        // A run produced from the back isn't split by the front
+        let mut runs = text.char_ranges().dedup_runs();
+        assert_eq!(runs.next_back(), Some((6..9, 'c', 3)));
+        assert_eq!(runs.next(), Some((0..3, 'a', 3)));
+        assert_eq!(runs.as_str(), "bbb");
+        assert_eq!(runs.next(), Some((3..6, 'b', 3)));
+        assert_eq!(runs.next_back(), None);
+        assert_eq!(runs.next(), None);
+
+        // The last remaining run is produced whole from either end
+        let mut runs = "aabb".char_ranges().dedup_runs();
+        assert_eq!(runs.next(), Some((0..2, 'a', 2)));
+        assert_eq!(runs.next_back(), Some((2..4, 'b', 2)));
+        assert_eq!(runs.next(), None);
+
+        let mut runs = "🌏🌏🌏".char_ranges().dedup_runs();
+        assert_eq!(runs.next_back(), Some((0..12, '🌏', 3)));
+        assert_eq!(runs.next(), None);
+    }
+
+    #[test]
+    fn test_dedup_runs_offset() {
+        let text = "x--∈∈";
+
+        let mut chars = text.char_ranges();
+        chars.next();
+        let runs = chars.dedup_runs();
+        assert!(runs.eq([(1..3, '-', 2), (3..9, '∈', 2)]));
+
+        let runs = text[1..].char_ranges_offset(1).dedup_runs();
+        assert_eq!(runs.offset(), 1);
+        assert!(runs.rev().eq([(3..9, '∈', 2), (1..3, '-', 2)]));
+    }
+}
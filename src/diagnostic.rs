@@ -0,0 +1,131 @@
+use core::ops::Range;
+use std::string::String;
+
+use miette::{LabeledSpan, SourceSpan};
+
+use crate::Spanned;
+
+/// Extension trait for converting byte ranges into a [`miette::SourceSpan`].
+///
+/// Implemented for <code>[Range]&lt;usize&gt;</code>, the
+/// <code>([Range]&lt;usize&gt;, [char])</code> items produced by
+/// [`char_ranges()`](crate::CharRangesExt::char_ranges), and [`Spanned<T>`].
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::{CharRangesExt, ToSourceSpan};
+/// use miette::{Diagnostic, LabeledSpan, MietteDiagnostic};
+///
+/// let text = "let x = 1 ∈ 2;";
+///
+/// let item = text
+///     .char_ranges()
+///     .find(|(_r, c)| *c == '∈')
+///     .unwrap();
+/// assert_eq!(item.0, 10..13);
+///
+/// let diagnostic = MietteDiagnostic::new("unexpected operator")
+///     .with_label(LabeledSpan::at(item.to_source_span(), "expected `==`"));
+///
+/// let label = diagnostic.labels().unwrap().next().unwrap();
+/// assert_eq!(label.offset(), 10);
+/// assert_eq!(label.len(), 3);
+/// assert_eq!(label.label(), Some("expected `==`"));
+///
+/// // Render with the source code
+/// let report = miette::Report::new(diagnostic).with_source_code(text);
+/// ```
+pub trait ToSourceSpan {
+    /// Returns the start and end byte positions as a [`SourceSpan`].
+    fn to_source_span(&self) -> SourceSpan;
+}
+
+impl ToSourceSpan for Range<usize> {
+    #[inline]
+    fn to_source_span(&self) -> SourceSpan {
+        SourceSpan::new(self.start.into(), self.len())
+    }
+}
+
+impl ToSourceSpan for (Range<usize>, char) {
+    #[inline]
+    fn to_source_span(&self) -> SourceSpan {
+        self.0.to_source_span()
+    }
+}
+
+impl<T> ToSourceSpan for Spanned<T> {
+    #[inline]
+    fn to_source_span(&self) -> SourceSpan {
+        self.range.to_source_span()
+    }
+}
+
+impl<T> From<Spanned<T>> for SourceSpan {
+    #[inline]
+    fn from(spanned: Spanned<T>) -> Self {
+        spanned.to_source_span()
+    }
+}
+
+impl<T> From<&Spanned<T>> for SourceSpan {
+    #[inline]
+    fn from(spanned: &Spanned<T>) -> Self {
+        spanned.to_source_span()
+    }
+}
+
+impl<T> Spanned<T> {
+    /// Returns a [`LabeledSpan`] with `label`, spanning `self.range`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    /// use miette::{Diagnostic, MietteDiagnostic};
+    ///
+    /// let text = "a = 🗻";
+    ///
+    /// let mountain = text.char_ranges().spanned().last().unwrap();
+    ///
+    /// let diagnostic = MietteDiagnostic::new("mountains are not allowed")
+    ///     .with_label(mountain.labeled("this mountain"));
+    ///
+    /// let label = diagnostic.labels().unwrap().next().unwrap();
+    /// assert_eq!((label.offset(), label.len()), (4, 4));
+    /// ```
+    #[inline]
+    pub fn labeled(&self, label: impl Into<String>) -> LabeledSpan {
+        LabeledSpan::at(self.to_source_span(), label)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::SourceSpan;
+
+    use super::ToSourceSpan;
+    use crate::{CharRangesExt, Spanned};
+
+    #[test]
+    fn test_to_source_span() {
+        let text = "Hello 🗻12∈45🌏 World ¢";
+
+        for (r, c) in text.char_ranges() {
+            let span = (r.clone(), c).to_source_span();
+            assert_eq!(span.offset(), r.start);
+            assert_eq!(span.len(), r.len());
+            assert_eq!(span, r.to_source_span());
+
+            let spanned = Spanned::new(r, c);
+            assert_eq!(SourceSpan::from(&spanned), span);
+            assert_eq!(SourceSpan::from(spanned), span);
+        }
+
+        let label = Spanned::new(17..21, '🌏').labeled("globe");
+        assert_eq!(label.offset(), 17);
+        assert_eq!(label.len(), 4);
+        assert_eq!(label.label(), Some("globe"));
+    }
+}
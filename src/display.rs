@@ -0,0 +1,201 @@
+use core::fmt;
+use core::ops::Range;
+
+/// The default maximum number of [`char`]s in the excerpt of [`SpanDisplay`].
+const DEFAULT_MAX_CHARS: usize = 16;
+
+/// Displays a `range` of a `text` as a human-readable position, e.g.
+/// `12:7 ('🌏')`, instead of byte positions.
+///
+/// The start is displayed as a 1-based `line:column`, where the column
+/// counts [`char`]s. If the range spans more than a single [`char`], then
+/// the end, i.e. the position after the last [`char`], is also displayed,
+/// e.g. `1:5-2:3`. This is followed by an escaped excerpt of the text
+/// within the range, which is truncated with `…` after
+/// [`max_chars()`](Self::max_chars) [`char`]s, by default 16.
+///
+/// The range is clamped to the bounds of `text` and widened outwards to
+/// the nearest [`char`] boundaries, i.e. formatting never panics.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::SpanDisplay;
+///
+/// let text = "let x = 1;\nlet 🌏 = x;\n";
+///
+/// let span = SpanDisplay::new(text, 15..19);
+/// assert_eq!(span.to_string(), "2:5 ('🌏')");
+///
+/// let span = SpanDisplay::new(text, 4..19);
+/// assert_eq!(span.to_string(), r#"1:5-2:6 ("x = 1;\nlet 🌏")"#);
+///
+/// let span = SpanDisplay::new(text, 0..text.len()).max_chars(6);
+/// assert_eq!(span.to_string(), r#"1:1-3:1 ("let x "…)"#);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SpanDisplay<'a> {
+    text: &'a str,
+    range: Range<usize>,
+    max_chars: usize,
+}
+
+impl<'a> SpanDisplay<'a> {
+    /// Creates a display of `range` in `text`.
+    #[inline]
+    pub const fn new(text: &'a str, range: Range<usize>) -> Self {
+        Self {
+            text,
+            range,
+            max_chars: DEFAULT_MAX_CHARS,
+        }
+    }
+
+    /// Sets the maximum number of [`char`]s in the excerpt, before
+    /// it is truncated.
+    #[inline]
+    pub const fn max_chars(mut self, max_chars: usize) -> Self {
+        self.max_chars = max_chars;
+        self
+    }
+
+    /// Returns the range clamped to the bounds of the text, and
+    /// widened outwards to the nearest [`char`] boundaries.
+    fn clamped_range(&self) -> Range<usize> {
+        let text = self.text;
+        let mut start = self.range.start.min(text.len());
+        while !text.is_char_boundary(start) {
+            start -= 1;
+        }
+        let mut end = self.range.end.clamp(start, text.len());
+        while !text.is_char_boundary(end) {
+            end += 1;
+        }
+        start..end
+    }
+}
+
+impl fmt::Display for SpanDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let r = self.clamped_range();
+        let excerpt = &self.text[r.clone()];
+
+        let (line, column) = line_column(&self.text[..r.start]);
+        write!(f, "{line}:{column}")?;
+
+        let mut chars = excerpt.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return write!(f, " ({c:?})");
+        }
+
+        if !excerpt.is_empty() {
+            let (end_line, end_column) = line_column(&self.text[..r.end]);
+            write!(f, "-{end_line}:{end_column}")?;
+        }
+
+        write!(f, " (\"")?;
+        let mut chars = excerpt.chars();
+        for c in chars.by_ref().take(self.max_chars) {
+            // Single quotes are not escaped within a string
+            match c {
+                '\'' => f.write_str("'")?,
+                c => write!(f, "{}", c.escape_debug())?,
+            }
+        }
+        f.write_str("\"")?;
+        if chars.next().is_some() {
+            f.write_str("…")?;
+        }
+        f.write_str(")")
+    }
+}
+
+/// Returns the 1-based line and column of the end of `text`.
+fn line_column(text: &str) -> (usize, usize) {
+    let line_start = text.rfind('\n').map_or(0, |i| i + 1);
+    let line = text[..line_start].bytes().filter(|&b| b == b'\n').count() + 1;
+    let column = text[line_start..].chars().count() + 1;
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::ToString;
+
+    use super::SpanDisplay;
+
+    const TEXT: &str = "fn main() {\n    let 🗻 = \"∈\";\n}";
+
+    #[test]
+    fn test_span_display_single_char() {
+        assert_eq!(SpanDisplay::new(TEXT, 0..1).to_string(), "1:1 ('f')");
+        assert_eq!(SpanDisplay::new(TEXT, 20..24).to_string(), "2:9 ('🗻')");
+        assert_eq!(SpanDisplay::new(TEXT, 11..12).to_string(), r"1:12 ('\n')");
+        assert_eq!(SpanDisplay::new(TEXT, 27..28).to_string(), r#"2:13 ('"')"#);
+        assert_eq!(
+            SpanDisplay::new(TEXT, (TEXT.len() - 1)..TEXT.len()).to_string(),
+            "3:1 ('}')",
+        );
+    }
+
+    #[test]
+    fn test_span_display_multi_char() {
+        assert_eq!(
+            SpanDisplay::new(TEXT, 0..2).to_string(),
+            r#"1:1-1:3 ("fn")"#,
+        );
+        assert_eq!(
+            SpanDisplay::new(TEXT, 16..33).to_string(),
+            r#"2:5-2:17 ("let 🗻 = \"∈\";")"#,
+        );
+        assert_eq!(
+            SpanDisplay::new(TEXT, 10..17).to_string(),
+            r#"1:11-2:6 ("{\n    l")"#,
+        );
+        assert_eq!(
+            SpanDisplay::new("it's", 0..4).to_string(),
+            r#"1:1-1:5 ("it's")"#,
+        );
+    }
+
+    #[test]
+    fn test_span_display_truncated() {
+        assert_eq!(
+            SpanDisplay::new(TEXT, 0..TEXT.len()).to_string(),
+            r#"1:1-3:2 ("fn main() {\n    "…)"#,
+        );
+        assert_eq!(
+            SpanDisplay::new(TEXT, 16..33).max_chars(5).to_string(),
+            r#"2:5-2:17 ("let 🗻"…)"#,
+        );
+        assert_eq!(
+            SpanDisplay::new(TEXT, 16..33).max_chars(0).to_string(),
+            r#"2:5-2:17 (""…)"#,
+        );
+    }
+
+    #[test]
+    fn test_span_display_out_of_range() {
+        // Empty
+        assert_eq!(SpanDisplay::new(TEXT, 4..4).to_string(), r#"1:5 ("")"#);
+        assert_eq!(SpanDisplay::new("", 0..0).to_string(), r#"1:1 ("")"#);
+
+        // Out of bounds
+        assert_eq!(SpanDisplay::new(TEXT, 100..200).to_string(), r#"3:2 ("")"#);
+        assert_eq!(SpanDisplay::new(TEXT, 34..200).to_string(), "3:1 ('}')");
+
+        // Within '🗻'
+        assert_eq!(SpanDisplay::new(TEXT, 21..22).to_string(), "2:9 ('🗻')");
+        assert_eq!(
+            SpanDisplay::new(TEXT, 18..21).to_string(),
+            r#"2:7-2:10 ("t 🗻")"#,
+        );
+
+        // Reversed
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = 8..2;
+        assert_eq!(SpanDisplay::new(TEXT, reversed).to_string(), r#"1:9 ("")"#);
+    }
+}
@@ -0,0 +1,715 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::{EditsError, OverlappingEditError, SpanError};
+
+/// A text edit, in the byte positions of the old text, see [`RangeMapper`].
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Edit {
+    /// Inserts `len` bytes at the position `at`.
+    Insert { at: usize, len: usize },
+    /// Deletes the bytes within `range`.
+    Delete { range: Range<usize> },
+    /// Replaces the bytes within `range` with `new_len` bytes.
+    Replace { range: Range<usize>, new_len: usize },
+}
+
+impl Edit {
+    /// Returns the range of the old text, which is replaced.
+    #[inline]
+    pub fn range(&self) -> Range<usize> {
+        match self {
+            Self::Insert { at, .. } => *at..*at,
+            Self::Delete { range } | Self::Replace { range, .. } => range.clone(),
+        }
+    }
+
+    /// Returns the length of the new text, which replaces [`range()`](Self::range).
+    #[inline]
+    pub fn new_len(&self) -> usize {
+        match *self {
+            Self::Insert { len, .. } => len,
+            Self::Delete { .. } => 0,
+            Self::Replace { new_len, .. } => new_len,
+        }
+    }
+}
+
+/// Maps byte positions and ranges of an old text to the new text,
+/// after applying a list of [`Edit`]s.
+///
+/// All edits are in the byte positions of the old text, i.e. like a
+/// batch of edits, and must be in ascending order without overlapping.
+/// Edits may be adjacent, e.g. multiple inserts at the same position are
+/// applied in order.
+///
+/// Positions strictly inside a deleted or replaced range have no
+/// corresponding position in the new text, and map to `None`. The start
+/// and end of a deleted or replaced range map to the start and end of
+/// the new text replacing it.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::{Edit, RangeMapper};
+///
+/// let old = "let foo = bar;";
+/// let new = "let mut foo = 1;";
+///
+/// let mapper = RangeMapper::new([
+///     Edit::Insert { at: 4, len: 4 },
+///     Edit::Replace { range: 10..13, new_len: 1 },
+/// ])
+/// .unwrap();
+///
+/// // `foo` is moved
+/// assert_eq!(mapper.map_range(4..7), Some(8..11));
+/// assert_eq!(&new[8..11], &old[4..7]);
+///
+/// // `bar` is replaced
+/// assert_eq!(mapper.map_range(10..13), Some(14..15));
+/// assert_eq!(mapper.map_offset(11), None);
+///
+/// // `;` is moved
+/// assert_eq!(mapper.map_offset(13), Some(15));
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct RangeMapper {
+    edits: Vec<Edit>,
+}
+
+impl RangeMapper {
+    /// Creates a mapper from `edits`, in the byte positions of the old text.
+    ///
+    /// Returns an [`OverlappingEditError`] if an edit starts before the
+    /// end of the previous edit, i.e. if the edits overlap or are not in
+    /// ascending order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range of an edit is reversed, i.e. the start is
+    /// after the end.
+    #[track_caller]
+    pub fn new<I>(edits: I) -> Result<Self, OverlappingEditError>
+    where
+        I: IntoIterator<Item = Edit>,
+    {
+        let edits = edits.into_iter().collect::<Vec<_>>();
+
+        let mut prev_end = 0;
+        for (index, edit) in edits.iter().enumerate() {
+            let r = edit.range();
+            assert!(
+                r.start <= r.end,
+                "range start {} is after end {}",
+                r.start,
+                r.end,
+            );
+            if r.start < prev_end {
+                return Err(OverlappingEditError::new(index, r));
+            }
+            prev_end = r.end;
+        }
+
+        Ok(Self { edits })
+    }
+
+    /// Returns the edits.
+    #[inline]
+    pub fn edits(&self) -> &[Edit] {
+        &self.edits
+    }
+
+    /// Returns the position in the new text corresponding to the
+    /// position `pos` in the old text.
+    ///
+    /// A position at an insert maps to the position before the inserted
+    /// text. Returns `None` if `pos` is strictly inside a deleted or
+    /// replaced range.
+    #[inline]
+    pub fn map_offset(&self, pos: usize) -> Option<usize> {
+        self.map(pos, false)
+    }
+
+    /// Returns the range in the new text corresponding to the range
+    /// `range` in the old text.
+    ///
+    /// Text inserted at the start or end of `range` is excluded, i.e. only
+    /// text inserted strictly inside `range` is included. Text inserted at
+    /// an empty `range` is also excluded, i.e. it maps to the position
+    /// before the inserted text.
+    ///
+    /// Returns `None` if the start or end of `range` is strictly inside
+    /// a deleted or replaced range, or if `range` is reversed.
+    #[inline]
+    pub fn map_range(&self, range: Range<usize>) -> Option<Range<usize>> {
+        if range.start > range.end {
+            return None;
+        }
+        let is_empty = range.start == range.end;
+        let start = self.map(range.start, !is_empty)?;
+        let end = self.map(range.end, false)?;
+        Some(start..end)
+    }
+
+    /// Maps `pos`, where `after_inserts` determines whether `pos` maps
+    /// to after text inserted at `pos`.
+    fn map(&self, pos: usize, after_inserts: bool) -> Option<usize> {
+        let mut added = 0;
+        let mut removed = 0;
+
+        for edit in &self.edits {
+            let r = edit.range();
+            if (pos < r.start) || ((pos == r.start) && !(r.is_empty() && after_inserts)) {
+                break;
+            } else if pos < r.end {
+                return None;
+            }
+            added += edit.new_len();
+            removed += r.len();
+        }
+
+        Some(pos - removed + added)
+    }
+}
+
+/// A builder of a batch of text edits, in the byte positions of the old
+/// text, which are applied as if simultaneously.
+///
+/// Unlike [`RangeMapper`], edits can be added in any order, and they
+/// contain the new text. Edits are kept sorted by their range, where
+/// multiple inserts at the same position are applied in the order they
+/// were added, and an insert at the start of another edit is applied
+/// before it.
+///
+/// Edits must not overlap, and an insert must not be strictly inside the
+/// range of another edit, while adjacent edits are allowed. Use
+/// [`validate()`](Self::validate) to check the edits against a text.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::Edits;
+///
+/// let old = "let foo = bar;";
+///
+/// let mut edits = Edits::new();
+/// edits.replace(10..13, "1");
+/// edits.insert(4, "mut ");
+/// assert_eq!(edits.validate(old), Ok(()));
+///
+/// let new = edits.apply(old);
+/// assert_eq!(new, "let mut foo = 1;");
+///
+/// // `foo` is moved
+/// assert_eq!(edits.map_range(4..7), Some(8..11));
+/// assert_eq!(&new[8..11], &old[4..7]);
+///
+/// // `bar` is replaced
+/// assert_eq!(edits.map_range(10..13), None);
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Default, Debug)]
+pub struct Edits {
+    /// The range of the old text, and the new text replacing it,
+    /// sorted by the start and end of the range.
+    edits: Vec<(Range<usize>, String)>,
+}
+
+impl Edits {
+    /// Creates an empty batch of edits.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { edits: Vec::new() }
+    }
+
+    /// Returns the number of edits.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.edits.len()
+    }
+
+    /// Returns `true` if there are no edits.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.edits.is_empty()
+    }
+
+    /// Returns an iterator over the edits, sorted by their range,
+    /// producing the range of the old text and the new text replacing it.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (Range<usize>, &str)> + '_ {
+        self.edits.iter().map(|(r, s)| (r.clone(), s.as_str()))
+    }
+
+    /// Replaces the bytes within `range` of the old text with `s`.
+    pub fn replace(&mut self, range: Range<usize>, s: &str) -> &mut Self {
+        let i = self
+            .edits
+            .partition_point(|(r, _)| (r.start, r.end) <= (range.start, range.end));
+        self.edits.insert(i, (range, String::from(s)));
+        self
+    }
+
+    /// Inserts `s` at the position `at` of the old text.
+    #[inline]
+    pub fn insert(&mut self, at: usize, s: &str) -> &mut Self {
+        self.replace(at..at, s)
+    }
+
+    /// Deletes the bytes within `range` of the old text.
+    #[inline]
+    pub fn delete(&mut self, range: Range<usize>) -> &mut Self {
+        self.replace(range, "")
+    }
+
+    /// Checks that the edits are valid for `text`, i.e. that all ranges
+    /// are within `text` and on [`char`] boundaries, and that no edits
+    /// overlap.
+    ///
+    /// Returns the first invalid edit, or the first pair of overlapping
+    /// edits, in the order of their ranges.
+    pub fn validate(&self, text: &str) -> Result<(), EditsError> {
+        let mut prev: Option<&Range<usize>> = None;
+        for (r, _) in &self.edits {
+            let error = if r.start > r.end {
+                Some(SpanError::Reversed)
+            } else if r.end > text.len() {
+                Some(SpanError::OutOfBounds)
+            } else if !text.is_char_boundary(r.start) || !text.is_char_boundary(r.end) {
+                Some(SpanError::NotCharBoundary)
+            } else {
+                None
+            };
+            if let Some(error) = error {
+                return Err(EditsError::Span {
+                    range: r.clone(),
+                    error,
+                });
+            }
+
+            match prev {
+                Some(first) if r.start < first.end => {
+                    return Err(EditsError::Overlapping {
+                        first: first.clone(),
+                        second: r.clone(),
+                    });
+                }
+                Some(first) if r.end <= first.end => {}
+                _ => prev = Some(r),
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns `text` with all edits applied.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the edits are invalid for `text`, see
+    /// [`validate()`](Self::validate).
+    #[track_caller]
+    pub fn apply(&self, text: &str) -> String {
+        if let Err(err) = self.validate(text) {
+            panic!("{err}");
+        }
+
+        let mut new = String::with_capacity(text.len());
+        let mut pos = 0;
+        for (r, s) in &self.edits {
+            new.push_str(&text[pos..r.start]);
+            new.push_str(s);
+            pos = r.end;
+        }
+        new.push_str(&text[pos..]);
+        new
+    }
+
+    /// Returns the range in the new text corresponding to the range
+    /// `old` in the old text, i.e. the range `old` is moved by the edits
+    /// before it.
+    ///
+    /// Text inserted at the start of `old` is before the mapped range,
+    /// and text inserted at the end of `old` is after it.
+    ///
+    /// Returns `None` if `old` intersects an edit, i.e. if it overlaps
+    /// the range of an edit, or an edit is strictly inside `old`, or
+    /// `old` is strictly inside an edit. Also returns `None` if `old` is
+    /// reversed.
+    pub fn map_range(&self, old: Range<usize>) -> Option<Range<usize>> {
+        if old.start > old.end {
+            return None;
+        }
+
+        let mut added = 0;
+        let mut removed = 0;
+        for (r, s) in &self.edits {
+            if r.end > old.start {
+                if r.start < old.end {
+                    return None;
+                }
+                break;
+            }
+            added += s.len();
+            removed += r.len();
+        }
+
+        Some((old.start - removed + added)..(old.end - removed + added))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use core::ops::Range;
+    use std::string::String;
+    use std::vec::Vec;
+
+    use super::{Edit, Edits, RangeMapper};
+    use crate::{substr_range, EditsError, SpanError};
+
+    /// Applies `edits` to `text`, where inserted and replaced
+    /// text is filled with `'#'`.
+    fn apply(text: &str, edits: &[Edit]) -> String {
+        let mut new = String::new();
+        let mut pos = 0;
+        for edit in edits {
+            let r = edit.range();
+            new.push_str(&text[pos..r.start]);
+            new.extend(core::iter::repeat_n('#', edit.new_len()));
+            pos = r.end;
+        }
+        new.push_str(&text[pos..]);
+        new
+    }
+
+    /// Returns the ranges of all words in `text`.
+    fn words(text: &str) -> Vec<(Range<usize>, &str)> {
+        text.split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(|word| (substr_range(text, word).unwrap(), word))
+            .collect()
+    }
+
+    #[test]
+    fn test_range_mapper() {
+        let text = "let foo = bar + baz;";
+
+        let edits = [
+            Edit::Insert { at: 4, len: 4 },
+            Edit::Delete { range: 9..16 },
+            Edit::Replace {
+                range: 19..20,
+                new_len: 2,
+            },
+        ];
+        let mapper = RangeMapper::new(edits.clone()).unwrap();
+        assert_eq!(mapper.edits(), edits);
+
+        let new = apply(text, &edits);
+        assert_eq!(new, "let ####foo =baz##");
+
+        // The words which are not edited are found at the mapped ranges
+        let old_words = words(text);
+        let new_words = words(&new);
+        let mapped = old_words
+            .iter()
+            .filter_map(|(r, word)| Some((mapper.map_range(r.clone())?, *word)))
+            .collect::<Vec<_>>();
+        assert_eq!(mapped, new_words);
+
+        // `bar`, `=` and `+` are deleted
+        assert_eq!(mapper.map_range(10..13), None);
+        assert_eq!(mapper.map_offset(12), None);
+        // Both ends of the deleted range map to the same position
+        assert_eq!(mapper.map_range(9..16), Some(13..13));
+        // A range containing the deleted range shrinks
+        assert_eq!(mapper.map_range(4..19), Some(8..16));
+
+        // Replaced
+        assert_eq!(mapper.map_range(19..20), Some(16..18));
+        assert_eq!(mapper.map_offset(20), Some(18));
+    }
+
+    #[test]
+    fn test_range_mapper_inserts() {
+        let mapper = RangeMapper::new([
+            Edit::Insert { at: 2, len: 3 },
+            Edit::Insert { at: 2, len: 1 },
+            Edit::Insert { at: 6, len: 2 },
+        ])
+        .unwrap();
+
+        // At an insert, the position before the inserted text
+        assert_eq!(mapper.map_offset(0), Some(0));
+        assert_eq!(mapper.map_offset(2), Some(2));
+        assert_eq!(mapper.map_offset(3), Some(7));
+        assert_eq!(mapper.map_offset(6), Some(10));
+        assert_eq!(mapper.map_offset(7), Some(13));
+
+        // Text inserted at the start and end of a range is excluded
+        assert_eq!(mapper.map_range(2..6), Some(6..10));
+        assert_eq!(mapper.map_range(0..2), Some(0..2));
+        assert_eq!(mapper.map_range(6..7), Some(12..13));
+        // Text inserted strictly inside a range is included
+        assert_eq!(mapper.map_range(0..7), Some(0..13));
+        // Empty ranges
+        assert_eq!(mapper.map_range(2..2), Some(2..2));
+        assert_eq!(mapper.map_range(4..4), Some(8..8));
+
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = 4..2;
+        assert_eq!(mapper.map_range(reversed), None);
+
+        // No edits
+        let mapper = RangeMapper::new([]).unwrap();
+        assert_eq!(mapper.map_range(3..5), Some(3..5));
+    }
+
+    #[test]
+    fn test_range_mapper_overlapping() {
+        let err = RangeMapper::new([Edit::Delete { range: 2..5 }, Edit::Insert { at: 4, len: 1 }])
+            .unwrap_err();
+        assert_eq!(err.index(), 1);
+        assert_eq!(err.range(), 4..4);
+
+        // Out of order
+        let err = RangeMapper::new([
+            Edit::Insert { at: 4, len: 1 },
+            Edit::Replace {
+                range: 1..2,
+                new_len: 1,
+            },
+        ])
+        .unwrap_err();
+        assert_eq!(err.index(), 1);
+
+        // Adjacent
+        assert!(RangeMapper::new([
+            Edit::Delete { range: 2..5 },
+            Edit::Insert { at: 5, len: 1 },
+            Edit::Delete { range: 5..6 },
+        ])
+        .is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "range start 5 is after end 2")]
+    fn test_range_mapper_reversed() {
+        #[allow(clippy::reversed_empty_ranges)]
+        let range = 5..2;
+        _ = RangeMapper::new([Edit::Delete { range }]);
+    }
+
+    #[test]
+    fn test_range_mapper_random() {
+        let mut random = crate::test_util::random();
+
+        let text = "ab cd ef gh ij kl mn op qr st uv wx yz";
+        for _ in 0..200 {
+            let mut edits = Vec::new();
+            let mut pos = 0;
+            while pos < text.len() {
+                pos += random() % 8;
+                let len = random() % 4;
+                // Nothing is inserted at the end
+                if pos + len >= text.len() {
+                    break;
+                }
+                edits.push(match random() % 3 {
+                    0 => Edit::Insert { at: pos, len },
+                    1 => Edit::Delete {
+                        range: pos..(pos + len),
+                    },
+                    _ => Edit::Replace {
+                        range: pos..(pos + len),
+                        new_len: random() % 4,
+                    },
+                });
+                pos += len;
+            }
+
+            let mapper = RangeMapper::new(edits.clone()).unwrap();
+            let new = apply(text, &edits);
+
+            // Every old char that isn't deleted or replaced
+            // is found at its mapped range
+            for (i, c) in text.char_indices() {
+                let r = mapper.map_range(i..(i + 1));
+                if edits.iter().any(|edit| edit.range().contains(&i)) {
+                    continue;
+                }
+                let r = r.unwrap();
+                assert_eq!(r.len(), 1);
+                assert_eq!(new[r].chars().next(), Some(c));
+            }
+            assert_eq!(mapper.map_offset(text.len()), Some(new.len()));
+        }
+    }
+
+    #[test]
+    fn test_edits() {
+        let text = "let foo = bar + baz;";
+
+        // Added out of order
+        let mut edits = Edits::new();
+        edits.replace(16..19, "qux").delete(9..16).insert(4, "mut ");
+        assert_eq!(edits.len(), 3);
+        assert_eq!(edits.validate(text), Ok(()));
+        assert!(edits
+            .iter()
+            .eq([(4..4, "mut "), (9..16, ""), (16..19, "qux")]));
+
+        let new = edits.apply(text);
+        assert_eq!(new, "let mut foo =qux;");
+
+        // Before, between, and after edits
+        assert_eq!(edits.map_range(0..3), Some(0..3));
+        assert_eq!(edits.map_range(4..7), Some(8..11));
+        assert_eq!(&new[8..11], "foo");
+        assert_eq!(edits.map_range(7..9), Some(11..13));
+        assert_eq!(edits.map_range(19..20), Some(16..17));
+        assert_eq!(&new[16..17], ";");
+        assert_eq!(edits.map_range(20..20), Some(17..17));
+
+        // Intersecting edits
+        assert_eq!(edits.map_range(10..13), None);
+        assert_eq!(edits.map_range(8..10), None);
+        assert_eq!(edits.map_range(3..5), None);
+        assert_eq!(edits.map_range(12..12), None);
+
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = 3..0;
+        assert_eq!(edits.map_range(reversed), None);
+
+        // No edits
+        let edits = Edits::new();
+        assert!(edits.is_empty());
+        assert_eq!(edits.apply(text), text);
+        assert_eq!(edits.map_range(3..5), Some(3..5));
+    }
+
+    #[test]
+    fn test_edits_adjacent() {
+        let text = "abcdef";
+
+        let mut edits = Edits::new();
+        edits
+            .replace(2..4, "X")
+            .replace(0..2, "")
+            .replace(4..6, "YZ");
+        assert_eq!(edits.validate(text), Ok(()));
+        assert_eq!(edits.apply(text), "XYZ");
+
+        // Empty ranges at the edges of edits
+        assert_eq!(edits.map_range(0..0), Some(0..0));
+        assert_eq!(edits.map_range(2..2), Some(0..0));
+        assert_eq!(edits.map_range(6..6), Some(3..3));
+    }
+
+    #[test]
+    fn test_edits_insert_at_delete() {
+        let text = "foo bar baz";
+
+        // An insert at the start of a delete is applied before it,
+        // regardless of the order they were added
+        let mut a = Edits::new();
+        a.delete(4..8).insert(4, "qux ");
+        let mut b = Edits::new();
+        b.insert(4, "qux ").delete(4..8);
+        assert_eq!(a, b);
+        assert_eq!(a.validate(text), Ok(()));
+        assert_eq!(a.apply(text), "foo qux baz");
+
+        // Inserts at the same position are applied in the order they
+        // were added, and an insert at the end of a delete is allowed
+        let mut edits = Edits::new();
+        edits
+            .insert(8, "2")
+            .delete(4..8)
+            .insert(8, "3")
+            .insert(4, "1");
+        assert_eq!(edits.validate(text), Ok(()));
+        assert_eq!(edits.apply(text), "foo 123baz");
+
+        // Text inserted at the start and end of a range is outside it
+        assert_eq!(edits.map_range(8..11), Some(7..10));
+        assert_eq!(edits.map_range(0..4), Some(0..4));
+    }
+
+    #[test]
+    fn test_edits_overlapping() {
+        let text = "let foo = bar;";
+
+        let mut edits = Edits::new();
+        edits.replace(8..13, "1").delete(4..9);
+        assert_eq!(
+            edits.validate(text),
+            Err(EditsError::Overlapping {
+                first: 4..9,
+                second: 8..13,
+            })
+        );
+
+        // An insert strictly inside another edit
+        let mut edits = Edits::new();
+        edits.delete(0..4).insert(6, "x").insert(2, "y");
+        assert_eq!(
+            edits.validate(text),
+            Err(EditsError::Overlapping {
+                first: 0..4,
+                second: 2..2,
+            })
+        );
+
+        // An edit contained in a previous edit
+        let mut edits = Edits::new();
+        edits.delete(0..10).delete(2..3).delete(5..6);
+        assert_eq!(
+            edits.validate(text),
+            Err(EditsError::Overlapping {
+                first: 0..10,
+                second: 2..3,
+            })
+        );
+
+        // Identical ranges
+        let mut edits = Edits::new();
+        edits.replace(4..7, "a").replace(4..7, "b");
+        assert!(edits.validate(text).is_err());
+    }
+
+    #[test]
+    fn test_edits_invalid_span() {
+        let text = "a🌏b";
+
+        let mut edits = Edits::new();
+        edits.insert(2, "x");
+        assert_eq!(
+            edits.validate(text),
+            Err(EditsError::Span {
+                range: 2..2,
+                error: SpanError::NotCharBoundary,
+            })
+        );
+
+        let mut edits = Edits::new();
+        edits.delete(5..7);
+        assert_eq!(
+            edits.validate(text),
+            Err(EditsError::Span {
+                range: 5..7,
+                error: SpanError::OutOfBounds,
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "edit at 2..3 overlaps edit at 0..4")]
+    fn test_edits_apply_overlapping() {
+        let mut edits = Edits::new();
+        edits.delete(0..4).delete(2..3);
+        _ = edits.apply("abcdef");
+    }
+}
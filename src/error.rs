@@ -0,0 +1,663 @@
+use core::fmt;
+use core::ops::Range;
+
+/// An error returned by [`CharRanges::seek_to()`] and
+/// [`CharRanges::seek_back_to()`].
+///
+/// [`CharRanges::seek_to()`]: crate::CharRanges::seek_to
+/// [`CharRanges::seek_back_to()`]: crate::CharRanges::seek_back_to
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SeekError {
+    /// The position is out of bounds of the original text.
+    OutOfBounds,
+    /// The position is not on a [`char`] boundary.
+    NotCharBoundary,
+    /// The position is past the opposite end of the iterator.
+    CrossesCursor,
+}
+
+impl fmt::Display for SeekError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfBounds => write!(f, "position is out of bounds"),
+            Self::NotCharBoundary => write!(f, "position is not on a char boundary"),
+            Self::CrossesCursor => {
+                write!(f, "position is past the opposite end of the iterator")
+            }
+        }
+    }
+}
+
+impl core::error::Error for SeekError {}
+
+/// An error returned by [`CharRanges::partition_at_byte()`] and
+/// [`CharRangesOffset::partition_at_byte()`], when the position is not
+/// a [`char`] boundary within the remaining [`char`]s.
+///
+/// [`CharRanges::partition_at_byte()`]: crate::CharRanges::partition_at_byte
+/// [`CharRangesOffset::partition_at_byte()`]: crate::CharRangesOffset::partition_at_byte
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BoundaryError {
+    /// The position is outside the remaining [`char`]s.
+    OutOfBounds {
+        /// The byte position.
+        pos: usize,
+        /// The start and end byte positions of the remaining [`char`]s.
+        remaining: Range<usize>,
+    },
+    /// The position is within a [`char`].
+    NotCharBoundary {
+        /// The byte position.
+        pos: usize,
+        /// The start and end byte positions of the [`char`] around `pos`.
+        range: Range<usize>,
+        /// The [`char`] around `pos`.
+        c: char,
+    },
+}
+
+impl BoundaryError {
+    /// Returns the byte position.
+    #[inline]
+    pub const fn pos(&self) -> usize {
+        match *self {
+            Self::OutOfBounds { pos, .. } | Self::NotCharBoundary { pos, .. } => pos,
+        }
+    }
+}
+
+impl fmt::Display for BoundaryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfBounds { pos, remaining } => write!(
+                f,
+                "position {pos} is outside the remaining chars at {}..{}",
+                remaining.start, remaining.end,
+            ),
+            Self::NotCharBoundary { pos, range, c } => write!(
+                f,
+                "position {pos} is not on a char boundary, it is within {c:?} at {}..{}",
+                range.start, range.end,
+            ),
+        }
+    }
+}
+
+impl core::error::Error for BoundaryError {}
+
+/// An error returned by [`ValidSpan::new()`] and [`ValidSpan::join()`].
+///
+/// [`ValidSpan::new()`]: crate::ValidSpan::new
+/// [`ValidSpan::join()`]: crate::ValidSpan::join
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SpanError {
+    /// The start of the range is greater than its end.
+    Reversed,
+    /// The range is out of bounds of the text.
+    OutOfBounds,
+    /// The start or end of the range is not on a [`char`] boundary.
+    NotCharBoundary,
+    /// The spans were validated against different texts.
+    DifferentText,
+}
+
+impl fmt::Display for SpanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Reversed => write!(f, "range is reversed"),
+            Self::OutOfBounds => write!(f, "range is out of bounds"),
+            Self::NotCharBoundary => write!(f, "range is not on char boundaries"),
+            Self::DifferentText => write!(f, "spans are from different texts"),
+        }
+    }
+}
+
+impl core::error::Error for SpanError {}
+
+/// An error returned by [`CharRangesExt::segment_by_ranges()`] for
+/// an invalid range, along with the index of the range.
+///
+/// [`CharRangesExt::segment_by_ranges()`]: crate::CharRangesExt::segment_by_ranges
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SegmentError {
+    /// The range is invalid for the text.
+    Span {
+        /// The index of the range.
+        index: usize,
+        /// Why the range is invalid.
+        error: SpanError,
+    },
+    /// The range starts before the end of the previous range, i.e. the
+    /// ranges are not sorted, or they overlap.
+    Unsorted {
+        /// The index of the range.
+        index: usize,
+    },
+}
+
+impl SegmentError {
+    /// Returns the index of the invalid range.
+    #[inline]
+    pub const fn index(&self) -> usize {
+        match *self {
+            Self::Span { index, .. } | Self::Unsorted { index } => index,
+        }
+    }
+}
+
+impl fmt::Display for SegmentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Span { index, error } => write!(f, "{error} at index {index}"),
+            Self::Unsorted { index } => {
+                write!(f, "range at index {index} overlaps the previous range")
+            }
+        }
+    }
+}
+
+impl core::error::Error for SegmentError {}
+
+/// An error returned by [`CharRangesExt::find_balanced_range()`].
+///
+/// [`CharRangesExt::find_balanced_range()`]: crate::CharRangesExt::find_balanced_range
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum UnbalancedError {
+    /// The byte position is not the start of an opening delimiter.
+    NotOpen(usize),
+    /// The text ended with unmatched opening delimiters.
+    Unclosed {
+        /// The byte position where the text ended.
+        end: usize,
+        /// The number of unmatched opening delimiters.
+        depth: usize,
+    },
+}
+
+impl fmt::Display for UnbalancedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotOpen(pos) => write!(f, "position {pos} is not an opening delimiter"),
+            Self::Unclosed { end, depth } => {
+                write!(f, "{depth} unclosed delimiters at end of text at {end}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for UnbalancedError {}
+
+/// An error returned when an offset plus the length of the text
+/// overflows `usize`.
+///
+/// See [`CharRanges::try_offset()`] and [`CharRangesOffset::try_from_text()`].
+///
+/// [`CharRanges::try_offset()`]: crate::CharRanges::try_offset
+/// [`CharRangesOffset::try_from_text()`]: crate::CharRangesOffset::try_from_text
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct OffsetOverflowError {
+    offset: usize,
+    len: usize,
+}
+
+impl OffsetOverflowError {
+    #[inline]
+    pub(crate) const fn new(offset: usize, len: usize) -> Self {
+        Self { offset, len }
+    }
+
+    /// Returns the offset, which caused the overflow.
+    #[inline]
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the length of the text, which caused the overflow.
+    #[inline]
+    pub const fn text_len(&self) -> usize {
+        self.len
+    }
+}
+
+impl fmt::Display for OffsetOverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "offset {} plus text length {} overflows usize",
+            self.offset, self.len,
+        )
+    }
+}
+
+impl core::error::Error for OffsetOverflowError {}
+
+/// An error returned when an offset plus the length of the text
+/// overflows `u32`.
+///
+/// See [`CharRanges32::try_new()`].
+///
+/// [`CharRanges32::try_new()`]: crate::CharRanges32::try_new
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct U32OverflowError {
+    offset: u32,
+    len: usize,
+}
+
+impl U32OverflowError {
+    #[inline]
+    pub(crate) const fn new(offset: u32, len: usize) -> Self {
+        Self { offset, len }
+    }
+
+    /// Returns the offset, which caused the overflow.
+    #[inline]
+    pub const fn offset(&self) -> u32 {
+        self.offset
+    }
+
+    /// Returns the length of the text, which caused the overflow.
+    #[inline]
+    pub const fn text_len(&self) -> usize {
+        self.len
+    }
+}
+
+impl fmt::Display for U32OverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "offset {} plus text length {} overflows u32",
+            self.offset, self.len,
+        )
+    }
+}
+
+impl core::error::Error for U32OverflowError {}
+
+/// An error returned by [`RangeMapper::new()`] when an edit starts
+/// before the end of the previous edit, i.e. if the edits overlap or
+/// are not in ascending order.
+///
+/// [`RangeMapper::new()`]: crate::RangeMapper::new
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct OverlappingEditError {
+    index: usize,
+    range: Range<usize>,
+}
+
+impl OverlappingEditError {
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub(crate) const fn new(index: usize, range: Range<usize>) -> Self {
+        Self { index, range }
+    }
+
+    /// Returns the index of the edit, which overlaps the previous edit.
+    #[inline]
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the range of the edit, which overlaps the previous edit.
+    #[inline]
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+}
+
+impl fmt::Display for OverlappingEditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "edit {} at {}..{} overlaps the previous edit",
+            self.index, self.range.start, self.range.end,
+        )
+    }
+}
+
+impl core::error::Error for OverlappingEditError {}
+
+/// An error returned by [`Edits::validate()`] for an edit, which is
+/// invalid for the text, or which conflicts with another edit.
+///
+/// [`Edits::validate()`]: crate::Edits::validate
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum EditsError {
+    /// The range of an edit is invalid for the text.
+    Span {
+        /// The range of the edit.
+        range: Range<usize>,
+        /// Why the range is invalid.
+        error: SpanError,
+    },
+    /// The ranges of two edits overlap, or an insert is strictly
+    /// inside the range of another edit.
+    Overlapping {
+        /// The range of the edit, which starts first.
+        first: Range<usize>,
+        /// The range of the edit, which overlaps `first`.
+        second: Range<usize>,
+    },
+}
+
+impl fmt::Display for EditsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Span { range, error } => {
+                write!(f, "{error} for edit at {}..{}", range.start, range.end)
+            }
+            Self::Overlapping { first, second } => write!(
+                f,
+                "edit at {}..{} overlaps edit at {}..{}",
+                second.start, second.end, first.start, first.end,
+            ),
+        }
+    }
+}
+
+impl core::error::Error for EditsError {}
+
+/// An error produced by [`ByteChunks`] for a [`char`], which is larger
+/// than the maximum number of bytes per chunk.
+///
+/// [`ByteChunks`]: crate::ByteChunks
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CharTooLargeError {
+    range: Range<usize>,
+    max_bytes: usize,
+}
+
+impl CharTooLargeError {
+    #[inline]
+    pub(crate) const fn new(range: Range<usize>, max_bytes: usize) -> Self {
+        Self { range, max_bytes }
+    }
+
+    /// Returns the start and end byte positions of the [`char`].
+    #[inline]
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// Returns the maximum number of bytes per chunk.
+    #[inline]
+    pub const fn max_bytes(&self) -> usize {
+        self.max_bytes
+    }
+}
+
+impl fmt::Display for CharTooLargeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "char at {}..{} is larger than the maximum of {} bytes per chunk",
+            self.range.start, self.range.end, self.max_bytes,
+        )
+    }
+}
+
+impl core::error::Error for CharTooLargeError {}
+
+/// An error produced by [`Utf8CharRanges`] for a run of bytes, which
+/// is not valid UTF-8.
+///
+/// [`Utf8CharRanges`]: crate::Utf8CharRanges
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct InvalidUtf8Range {
+    range: Range<usize>,
+}
+
+impl InvalidUtf8Range {
+    #[inline]
+    pub(crate) const fn new(range: Range<usize>) -> Self {
+        Self { range }
+    }
+
+    /// Returns the start and end byte positions of the invalid bytes.
+    #[inline]
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+}
+
+impl fmt::Display for InvalidUtf8Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid UTF-8 sequence at bytes {}..{}",
+            self.range.start, self.range.end,
+        )
+    }
+}
+
+impl core::error::Error for InvalidUtf8Range {}
+
+/// An error produced by [`Utf16CharRanges`] for an unpaired surrogate.
+///
+/// [`Utf16CharRanges`]: crate::Utf16CharRanges
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct UnpairedSurrogate {
+    pos: usize,
+    unit: u16,
+}
+
+impl UnpairedSurrogate {
+    #[inline]
+    pub(crate) const fn new(pos: usize, unit: u16) -> Self {
+        Self { pos, unit }
+    }
+
+    /// Returns the start and end position of the unpaired surrogate,
+    /// in `u16` code units.
+    #[inline]
+    pub const fn range(&self) -> Range<usize> {
+        self.pos..(self.pos + 1)
+    }
+
+    /// Returns the unpaired surrogate code unit.
+    #[inline]
+    pub const fn unpaired_surrogate(&self) -> u16 {
+        self.unit
+    }
+}
+
+impl fmt::Display for UnpairedSurrogate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unpaired surrogate {:#06X} at position {}",
+            self.unit, self.pos,
+        )
+    }
+}
+
+impl core::error::Error for UnpairedSurrogate {}
+
+/// An error produced by [`ChunkedCharRanges`] for an invalid or
+/// incomplete UTF-8 sequence.
+///
+/// [`ChunkedCharRanges`]: crate::ChunkedCharRanges
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ChunkedUtf8Error {
+    range: Range<u64>,
+    incomplete: bool,
+}
+
+impl ChunkedUtf8Error {
+    #[inline]
+    pub(crate) const fn new(range: Range<u64>, incomplete: bool) -> Self {
+        Self { range, incomplete }
+    }
+
+    /// Returns the start and end byte positions of the invalid bytes,
+    /// relative to the start of the stream.
+    #[inline]
+    pub fn range(&self) -> Range<u64> {
+        self.range.clone()
+    }
+
+    /// Returns `true` if the stream ended in the middle of an otherwise
+    /// valid UTF-8 sequence.
+    #[inline]
+    pub const fn is_incomplete(&self) -> bool {
+        self.incomplete
+    }
+}
+
+impl fmt::Display for ChunkedUtf8Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = if self.incomplete {
+            "incomplete"
+        } else {
+            "invalid"
+        };
+        write!(
+            f,
+            "{kind} UTF-8 sequence at bytes {}..{}",
+            self.range.start, self.range.end,
+        )
+    }
+}
+
+impl core::error::Error for ChunkedUtf8Error {}
+
+/// An error produced by [`UnescapeRanges`] for an invalid escape
+/// sequence, with the start and end byte positions of the escape.
+///
+/// [`UnescapeRanges`]: crate::UnescapeRanges
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum UnescapeError {
+    /// The text ended in the middle of an escape, e.g. `\` or `\u{1F3`.
+    Unterminated(Range<usize>),
+    /// The [`char`] following `\` is not a known escape, e.g. `\q`.
+    UnknownEscape(Range<usize>),
+    /// An escape is missing hex digits, e.g. `\x4g`, `\u{}` or a
+    /// `\u` without `{` in the Rust style, or it has too many hex
+    /// digits, e.g. `\u{1234567}`.
+    InvalidHex(Range<usize>),
+    /// The value of an escape is out of range, e.g. `\xFF` in the Rust
+    /// style, or `\u{110000}`.
+    OutOfRange(Range<usize>),
+    /// The value of an escape is a surrogate, which is not a [`char`],
+    /// e.g. `\u{D800}` or an unpaired `\uD83C` in the JSON style.
+    LoneSurrogate(Range<usize>),
+}
+
+impl UnescapeError {
+    /// Returns the start and end byte positions of the escape.
+    #[inline]
+    pub fn range(&self) -> Range<usize> {
+        match self {
+            Self::Unterminated(r)
+            | Self::UnknownEscape(r)
+            | Self::InvalidHex(r)
+            | Self::OutOfRange(r)
+            | Self::LoneSurrogate(r) => r.clone(),
+        }
+    }
+}
+
+impl fmt::Display for UnescapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            Self::Unterminated(_) => "unterminated escape",
+            Self::UnknownEscape(_) => "unknown escape",
+            Self::InvalidHex(_) => "invalid hex digits in escape",
+            Self::OutOfRange(_) => "out of range escape",
+            Self::LoneSurrogate(_) => "lone surrogate escape",
+        };
+        let r = self.range();
+        write!(f, "{kind} at bytes {}..{}", r.start, r.end)
+    }
+}
+
+impl core::error::Error for UnescapeError {}
+
+/// An error produced by [`Scanner::scan_unsigned()`] and
+/// [`Scanner::scan_signed()`], when the scanned integer does not fit
+/// in the integer type, with the start and end byte positions of the
+/// consumed integer.
+///
+/// [`Scanner::scan_unsigned()`]: crate::Scanner::scan_unsigned
+/// [`Scanner::scan_signed()`]: crate::Scanner::scan_signed
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct IntOverflowError {
+    range: Range<usize>,
+}
+
+impl IntOverflowError {
+    #[inline]
+    pub(crate) const fn new(range: Range<usize>) -> Self {
+        Self { range }
+    }
+
+    /// Returns the start and end byte positions of the integer.
+    #[inline]
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+}
+
+impl fmt::Display for IntOverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "integer at bytes {}..{} overflows",
+            self.range.start, self.range.end,
+        )
+    }
+}
+
+impl core::error::Error for IntOverflowError {}
+
+/// An error produced by [`Scanner::scan_quoted()`] and
+/// [`Scanner::scan_quoted_pair()`] for a quoted string without a closing
+/// quote, with the start and end byte positions of the opening quote.
+///
+/// [`Scanner::scan_quoted()`]: crate::Scanner::scan_quoted
+/// [`Scanner::scan_quoted_pair()`]: crate::Scanner::scan_quoted_pair
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct UnterminatedString {
+    open: Range<usize>,
+}
+
+impl UnterminatedString {
+    #[inline]
+    pub(crate) const fn new(open: Range<usize>) -> Self {
+        Self { open }
+    }
+
+    /// Returns the start and end byte positions of the opening quote.
+    #[inline]
+    pub fn range(&self) -> Range<usize> {
+        self.open.clone()
+    }
+}
+
+impl fmt::Display for UnterminatedString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unterminated string starting at bytes {}..{}",
+            self.open.start, self.open.end,
+        )
+    }
+}
+
+impl core::error::Error for UnterminatedString {}
@@ -0,0 +1,165 @@
+use core::char::EscapeDebug;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::CharRangesOffset;
+
+/// An iterator over the [`char`]s produced by [`char::escape_debug()`],
+/// and the start and end byte positions of the source [`char`] they
+/// originate from.
+///
+/// When a single source [`char`] is escaped into multiple [`char`]s,
+/// e.g. `'\n'` is escaped into `"\\n"`, then all of them share the
+/// same range.
+///
+/// See [`CharRangesExt::escape_debug_ranges()`](crate::CharRangesExt::escape_debug_ranges).
+#[derive(Clone)]
+pub struct EscapeDebugRanges<'a> {
+    iter: CharRangesOffset<'a>,
+    front: Option<(Range<usize>, EscapeDebug)>,
+}
+
+impl<'a> EscapeDebugRanges<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>) -> Self {
+        Self { iter, front: None }
+    }
+
+    /// Returns the remaining substring, of source [`char`]s that
+    /// have not yet been escaped.
+    ///
+    /// Note that if a source [`char`] was escaped into multiple [`char`]s,
+    /// then it is no longer included, even if not all of them have been
+    /// returned yet.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "\ta";
+    ///
+    /// let mut chars = text.escape_debug_ranges();
+    /// assert_eq!(chars.as_str(), "\ta");
+    ///
+    /// assert_eq!(chars.next(), Some((0..1, '\\')));
+    /// assert_eq!(chars.as_str(), "a");
+    ///
+    /// assert_eq!(chars.next(), Some((0..1, 't')));
+    /// assert_eq!(chars.as_str(), "a");
+    /// ```
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+}
+
+impl Iterator for EscapeDebugRanges<'_> {
+    type Item = (Range<usize>, char);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((r, escaped)) = &mut self.front {
+                if let Some(c) = escaped.next() {
+                    return Some((r.clone(), c));
+                }
+            }
+
+            let (r, c) = self.iter.next()?;
+            self.front = Some((r, c.escape_debug()));
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Every source `char` is escaped into at least 1
+        // and at most 10 `char`s, e.g. `\u{10ffff}`
+        let (lower, upper) = self.iter.size_hint();
+        let buffered = match &self.front {
+            Some((_, escaped)) => escaped.len(),
+            None => 0,
+        };
+        let lower = lower.saturating_add(buffered);
+        let upper = upper
+            .and_then(|upper| upper.checked_mul(10))
+            .and_then(|upper| upper.checked_add(buffered));
+        (lower, upper)
+    }
+}
+
+impl FusedIterator for EscapeDebugRanges<'_> {}
+
+impl fmt::Debug for EscapeDebugRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "EscapeDebugRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::CharRangesExt;
+
+    #[test]
+    fn test_escape_debug_ranges() {
+        let text = "a\n\t\"'";
+
+        let mut chars = text.escape_debug_ranges();
+        assert_eq!(chars.next(), Some((0..1, 'a')));
+        assert_eq!(chars.next(), Some((1..2, '\\')));
+        assert_eq!(chars.next(), Some((1..2, 'n')));
+        assert_eq!(chars.next(), Some((2..3, '\\')));
+        assert_eq!(chars.next(), Some((2..3, 't')));
+        assert_eq!(chars.next(), Some((3..4, '\\')));
+        assert_eq!(chars.next(), Some((3..4, '"')));
+        assert_eq!(chars.next(), Some((4..5, '\\')));
+        assert_eq!(chars.next(), Some((4..5, '\'')));
+        assert_eq!(chars.next(), None);
+    }
+
+    #[test]
+    fn test_escape_debug_ranges_zero_width_space() {
+        let text = "a\u{200B}b";
+
+        let mut chars = text.escape_debug_ranges();
+        assert_eq!(chars.next(), Some((0..1, 'a')));
+        for expected in "\\u{200b}".chars() {
+            assert_eq!(chars.next(), Some((1..4, expected)));
+        }
+        assert_eq!(chars.next(), Some((4..5, 'b')));
+        assert_eq!(chars.next(), None);
+    }
+
+    #[test]
+    fn test_escape_debug_ranges_printable() {
+        let text = "é∈🌏";
+
+        let mut chars = text.escape_debug_ranges();
+        assert_eq!(chars.next(), Some((0..2, 'é')));
+        assert_eq!(chars.next(), Some((2..5, '∈')));
+        assert_eq!(chars.next(), Some((5..9, '🌏')));
+        assert_eq!(chars.next(), None);
+    }
+
+    #[test]
+    fn test_escape_debug_ranges_chars() {
+        let text = "Hello\r\n\t\"🌏\u{200B}\u{0}'\\ World";
+
+        let expected = text.chars().flat_map(char::escape_debug);
+        assert!(text.escape_debug_ranges().map(|(_, c)| c).eq(expected));
+
+        for (r, c) in text.escape_debug_ranges() {
+            let source = text[r].chars().next().unwrap();
+            assert!(source.escape_debug().any(|escaped| escaped == c));
+        }
+    }
+}
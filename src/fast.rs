@@ -0,0 +1,237 @@
+//! Accelerated counting and skipping of [`char`]s, enabled by the `fast`
+//! feature.
+//!
+//! The safe implementations read each `usize` by copying the bytes into
+//! an array. Instead, the bytes are split into an unaligned prefix and
+//! suffix, and the aligned `usize`s in between, which are read directly,
+//! and unchecked indexing is used where the bounds are already known.
+//!
+//! All functions produce exactly the same results as the safe
+//! implementations, which is enforced by differential tests.
+#![allow(unsafe_code)]
+
+use crate::{is_utf8_continuation_byte, non_continuation_bytes, WORD};
+
+/// Splits `bytes` into an unaligned prefix, the aligned `usize`s, and
+/// an unaligned suffix.
+#[inline]
+fn as_words(bytes: &[u8]) -> (&[u8], &[usize], &[u8]) {
+    // SAFETY: Any bit pattern is a valid `usize`, and `align_to()`
+    // ensures the middle slice is correctly aligned
+    unsafe { bytes.align_to::<usize>() }
+}
+
+/// Returns the number of bytes in `bytes` that are not UTF-8
+/// continuation bytes, a byte at a time.
+#[inline]
+fn count_bytes(bytes: &[u8]) -> usize {
+    bytes
+        .iter()
+        .filter(|&&b| !is_utf8_continuation_byte(b))
+        .count()
+}
+
+/// Returns the sum of all the bytes in `word`.
+#[inline]
+const fn sum_bytes(word: usize) -> usize {
+    const LOW_BYTES: usize = usize::MAX / 0xFFFF * 0xFF;
+    const LOW_SHORTS: usize = usize::MAX / 0xFFFF;
+
+    // Each pair of bytes summed into a `u16`, of which there are
+    // at most `WORD / 2`, so the total fits in a `u16`
+    let pairs = (word & LOW_BYTES) + ((word >> 8) & LOW_BYTES);
+    pairs.wrapping_mul(LOW_SHORTS) >> (usize::BITS - 16)
+}
+
+/// See [`count_leading_bytes()`](crate::count_leading_bytes).
+pub(crate) fn count_leading_bytes(bytes: &[u8]) -> usize {
+    // Each byte of the accumulator counts at most 1 per word,
+    // so it must be summed before it can overflow
+    const BATCH: usize = 255;
+
+    let (prefix, words, suffix) = as_words(bytes);
+
+    let mut count = count_bytes(prefix) + count_bytes(suffix);
+    for batch in words.chunks(BATCH) {
+        let mut acc = 0;
+        for &word in batch {
+            acc += non_continuation_bytes(word);
+        }
+        count += sum_bytes(acc);
+    }
+    count
+}
+
+/// See [`skip_chars()`](crate::skip_chars).
+pub(crate) fn skip_chars(bytes: &[u8], n: usize) -> Result<usize, usize> {
+    let (prefix, words, _suffix) = as_words(bytes);
+
+    let mut remaining = n;
+    for (i, &b) in prefix.iter().enumerate() {
+        if !is_utf8_continuation_byte(b) {
+            if remaining == 0 {
+                return Ok(i);
+            }
+            remaining -= 1;
+        }
+    }
+
+    let mut i = prefix.len();
+    for &word in words {
+        let count = non_continuation_bytes(word).count_ones() as usize;
+        if count > remaining {
+            break;
+        }
+        remaining -= count;
+        i += WORD;
+    }
+
+    // The position is now within the next word, or the next
+    // word contains the remaining continuation bytes
+    while i < bytes.len() {
+        // SAFETY: `i < bytes.len()`
+        let b = unsafe { *bytes.get_unchecked(i) };
+        if !is_utf8_continuation_byte(b) {
+            if remaining == 0 {
+                return Ok(i);
+            }
+            remaining -= 1;
+        }
+        i += 1;
+    }
+
+    if remaining == 0 {
+        Ok(bytes.len())
+    } else {
+        Err(n - remaining)
+    }
+}
+
+/// See [`skip_chars_back()`](crate::skip_chars_back).
+pub(crate) fn skip_chars_back(bytes: &[u8], n: usize) -> Result<usize, usize> {
+    if n == 0 {
+        return Ok(0);
+    }
+
+    let (_prefix, words, suffix) = as_words(bytes);
+
+    let mut remaining = n;
+    let mut i = bytes.len();
+    for &b in suffix.iter().rev() {
+        i -= 1;
+        if !is_utf8_continuation_byte(b) {
+            remaining -= 1;
+            if remaining == 0 {
+                return Ok(bytes.len() - i);
+            }
+        }
+    }
+
+    // Only skip words containing fewer leading bytes than remaining,
+    // as the start of the word might not be on a char boundary
+    for &word in words.iter().rev() {
+        let count = non_continuation_bytes(word).count_ones() as usize;
+        if count >= remaining {
+            break;
+        }
+        remaining -= count;
+        i -= WORD;
+    }
+
+    while i > 0 {
+        i -= 1;
+        // SAFETY: `i < bytes.len()`, as `i` only decreases from `bytes.len()`
+        let b = unsafe { *bytes.get_unchecked(i) };
+        if !is_utf8_continuation_byte(b) {
+            remaining -= 1;
+            if remaining == 0 {
+                return Ok(bytes.len() - i);
+            }
+        }
+    }
+
+    Err(n - remaining)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use super::sum_bytes;
+    use crate::test_util::random;
+
+    /// Returns random bytes, mostly valid UTF-8, but also random bytes,
+    /// as the functions must agree on any bytes.
+    fn random_bytes(random: &mut impl FnMut() -> usize) -> Vec<u8> {
+        const CHARS: [&str; 6] = ["a", "¢", "∈", "🗻", " ", "\u{FFFD}"];
+
+        let len = random() % 300;
+        let mut bytes = Vec::with_capacity(len + 4);
+        while bytes.len() < len {
+            if random().is_multiple_of(16) {
+                bytes.push(random() as u8);
+            } else {
+                bytes.extend_from_slice(CHARS[random() % CHARS.len()].as_bytes());
+            }
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_fast_sum_bytes() {
+        assert_eq!(sum_bytes(0), 0);
+        assert_eq!(sum_bytes(usize::MAX), 0xFF * crate::WORD);
+        assert_eq!(sum_bytes(crate::LOW_BITS), crate::WORD);
+        assert_eq!(sum_bytes(crate::LOW_BITS * 255), 255 * crate::WORD);
+    }
+
+    #[test]
+    fn test_fast_count_leading_bytes() {
+        let mut random = random();
+        for _ in 0..2000 {
+            let bytes = random_bytes(&mut random);
+            // Every alignment of the start and end
+            for start in 0..crate::WORD.min(bytes.len()) {
+                let end = bytes.len() - (random() % crate::WORD).min(bytes.len() - start);
+                let bytes = &bytes[start..end];
+                assert_eq!(
+                    super::count_leading_bytes(bytes),
+                    crate::count_leading_bytes_safe(bytes),
+                    "{bytes:?}"
+                );
+            }
+        }
+
+        // More than a batch of words
+        let bytes = "a🗻∈".repeat(1000);
+        assert_eq!(
+            super::count_leading_bytes(bytes.as_bytes()),
+            crate::count_leading_bytes_safe(bytes.as_bytes())
+        );
+    }
+
+    #[test]
+    fn test_fast_skip_chars() {
+        let mut random = random();
+        for _ in 0..500 {
+            let bytes = random_bytes(&mut random);
+            for start in 0..crate::WORD.min(bytes.len()) {
+                let bytes = &bytes[start..];
+                for n in (0..bytes.len() + 2).step_by(1 + (random() % 5)) {
+                    assert_eq!(
+                        super::skip_chars(bytes, n),
+                        crate::skip_chars_safe(bytes, n),
+                        "{bytes:?} {n}"
+                    );
+                    assert_eq!(
+                        super::skip_chars_back(bytes, n),
+                        crate::skip_chars_back_safe(bytes, n),
+                        "{bytes:?} {n}"
+                    );
+                }
+            }
+        }
+    }
+}
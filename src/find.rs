@@ -0,0 +1,445 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::{utf8_char_width, CharRangesOffset};
+
+/// Returns the start and end byte positions of the first occurrence
+/// of `c` in `text`.
+///
+/// This searches for the leading byte of `c` and verifies any continuation
+/// bytes, i.e. [`char`]s are not decoded along the way. With the `memchr`
+/// feature enabled, the byte search is done using [`memchr`].
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::find_char_range;
+///
+/// let text = "let s = \"🗻∈🌏\";";
+/// assert_eq!(find_char_range(text, '"'), Some(8..9));
+/// assert_eq!(find_char_range(text, '🌏'), Some(16..20));
+/// assert_eq!(find_char_range(text, '\n'), None);
+/// ```
+///
+/// [`memchr`]: https://docs.rs/memchr
+#[inline]
+pub fn find_char_range(text: &str, c: char) -> Option<Range<usize>> {
+    let mut buf = [0; 4];
+    let needle = c.encode_utf8(&mut buf).as_bytes();
+    let i = find_bytes(text.as_bytes(), needle)?;
+    Some(i..(i + needle.len()))
+}
+
+/// Returns the position of the first occurrence of the non-empty
+/// `needle` in `haystack`, by searching for its leading byte.
+#[inline]
+fn find_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    let mut start = 0;
+    while let Some(i) = memchr(needle[0], &haystack[start..]) {
+        let i = start + i;
+        if haystack[i..].starts_with(needle) {
+            return Some(i);
+        }
+        start = i + 1;
+    }
+    None
+}
+
+/// Returns the start and end byte positions of the last occurrence
+/// of `c` in `text`.
+///
+/// See [`find_char_range()`] for more information.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::rfind_char_range;
+///
+/// let text = "let s = \"🗻∈🌏\";";
+/// assert_eq!(rfind_char_range(text, '"'), Some(20..21));
+/// assert_eq!(rfind_char_range(text, '∈'), Some(13..16));
+/// ```
+#[inline]
+pub fn rfind_char_range(text: &str, c: char) -> Option<Range<usize>> {
+    let mut buf = [0; 4];
+    let needle = c.encode_utf8(&mut buf).as_bytes();
+    let bytes = text.as_bytes();
+
+    let mut end = bytes.len();
+    while let Some(i) = memrchr(needle[0], &bytes[..end]) {
+        if bytes[i..].starts_with(needle) {
+            return Some(i..(i + needle.len()));
+        }
+        end = i;
+    }
+    None
+}
+
+#[cfg(feature = "memchr")]
+#[inline]
+fn memchr(b: u8, haystack: &[u8]) -> Option<usize> {
+    memchr::memchr(b, haystack)
+}
+
+#[cfg(not(feature = "memchr"))]
+#[inline]
+fn memchr(b: u8, haystack: &[u8]) -> Option<usize> {
+    haystack.iter().position(|&x| x == b)
+}
+
+#[cfg(feature = "memchr")]
+#[inline]
+fn memrchr(b: u8, haystack: &[u8]) -> Option<usize> {
+    memchr::memrchr(b, haystack)
+}
+
+#[cfg(not(feature = "memchr"))]
+#[inline]
+fn memrchr(b: u8, haystack: &[u8]) -> Option<usize> {
+    haystack.iter().rposition(|&x| x == b)
+}
+
+/// An iterator over the occurrences of a [`char`], and their start and
+/// end byte positions.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRanges::match_char_ranges()`] for more information.
+///
+/// [`CharRanges::match_char_ranges()`]: crate::CharRanges::match_char_ranges
+#[derive(Clone)]
+pub struct MatchCharRanges<'a> {
+    iter: CharRangesOffset<'a>,
+    needle: char,
+}
+
+impl<'a> MatchCharRanges<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>, needle: char) -> Self {
+        Self { iter, needle }
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+}
+
+impl Iterator for MatchCharRanges<'_> {
+    type Item = (Range<usize>, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let chars = &mut self.iter.iter;
+        match find_char_range(chars.as_str(), self.needle) {
+            Some(r) => {
+                let r = (chars.start + r.start)..(chars.start + r.end);
+                chars.start = r.end;
+                Some((self.iter.apply_offset(r), self.needle))
+            }
+            None => {
+                chars.start = chars.end;
+                None
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.iter.iter.end - self.iter.iter.start;
+        (0, Some(len / self.needle.len_utf8()))
+    }
+}
+
+impl DoubleEndedIterator for MatchCharRanges<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let chars = &mut self.iter.iter;
+        match rfind_char_range(chars.as_str(), self.needle) {
+            Some(r) => {
+                let r = (chars.start + r.start)..(chars.start + r.end);
+                chars.end = r.start;
+                Some((self.iter.apply_offset(r), self.needle))
+            }
+            None => {
+                chars.end = chars.start;
+                None
+            }
+        }
+    }
+}
+
+impl FusedIterator for MatchCharRanges<'_> {}
+
+impl fmt::Debug for MatchCharRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MatchCharRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+/// An iterator over all occurrences of a substring, including
+/// overlapping occurrences, and their start and end byte positions.
+///
+/// After each match, the search continues from the second [`char`] of
+/// the match, i.e. it advances by one [`char`] and not one byte, such
+/// that all matches are on [`char`] boundaries. An empty needle produces
+/// no matches.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRangesExt::match_overlapping_ranges()`] for more information.
+///
+/// [`CharRangesExt::match_overlapping_ranges()`]: crate::CharRangesExt::match_overlapping_ranges
+#[derive(Clone)]
+pub struct MatchOverlappingRanges<'a, 'b> {
+    iter: CharRangesOffset<'a>,
+    needle: &'b str,
+}
+
+impl<'a, 'b> MatchOverlappingRanges<'a, 'b> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>, needle: &'b str) -> Self {
+        Self { iter, needle }
+    }
+
+    /// Returns the needle.
+    #[inline]
+    pub fn needle(&self) -> &'b str {
+        self.needle
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+}
+
+impl<'a> Iterator for MatchOverlappingRanges<'a, '_> {
+    type Item = (Range<usize>, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chars = &mut self.iter.iter;
+        let needle = self.needle.as_bytes();
+
+        let found = if needle.is_empty() {
+            None
+        } else {
+            find_bytes(chars.as_str().as_bytes(), needle)
+        };
+        let Some(i) = found else {
+            chars.start = chars.end;
+            return None;
+        };
+
+        let start = chars.start + i;
+        let end = start + needle.len();
+        // The needle starts with a leading byte, so `start`
+        // is on a char boundary
+        chars.start = start + utf8_char_width(needle[0]);
+
+        let s = &chars.text[start..end];
+        Some((self.iter.apply_offset(start..end), s))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, upper) = self.iter.size_hint();
+        (0, upper)
+    }
+}
+
+impl FusedIterator for MatchOverlappingRanges<'_, '_> {}
+
+impl fmt::Debug for MatchOverlappingRanges<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MatchOverlappingRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use core::ops::Range;
+    use std::string::String;
+    use std::vec::Vec;
+
+    use super::{find_char_range, rfind_char_range};
+    use crate::CharRangesExt;
+
+    const CHARS: [char; 10] = ['a', '"', '\n', '\u{80}', 'é', 'ɐ', '∈', '⊂', '🌏', '🗻'];
+
+    fn random_strings() -> impl Iterator<Item = String> {
+        let mut next = crate::test_util::random();
+        (0..500).map(move |_| {
+            let len = next() % 32;
+            (0..len).map(|_| CHARS[next() % CHARS.len()]).collect()
+        })
+    }
+
+    fn naive(text: &str, c: char) -> Vec<Range<usize>> {
+        text.char_ranges()
+            .filter(|&(_, c2)| c2 == c)
+            .map(|(r, _c)| r)
+            .collect()
+    }
+
+    #[test]
+    fn test_find_char_range() {
+        let text = "Hello 🗻12∈45🌏 World ¢";
+        assert_eq!(find_char_range(text, 'l'), Some(2..3));
+        assert_eq!(rfind_char_range(text, 'l'), Some(25..26));
+        assert_eq!(find_char_range(text, '∈'), Some(12..15));
+        assert_eq!(rfind_char_range(text, '¢'), Some(28..30));
+        assert_eq!(find_char_range(text, 'x'), None);
+        assert_eq!(rfind_char_range("", 'x'), None);
+
+        // Same leading byte, different continuation bytes
+        assert_eq!(find_char_range("∈⊂∈", '⊂'), Some(3..6));
+        assert_eq!(rfind_char_range("⊂∈⊂", '∈'), Some(3..6));
+        assert_eq!(find_char_range("🗻🗻", '🌏'), None);
+    }
+
+    #[test]
+    fn test_find_char_range_random() {
+        for text in random_strings() {
+            for c in CHARS.into_iter().chain(['x', '🦀']) {
+                let expected = naive(&text, c);
+                assert_eq!(find_char_range(&text, c), expected.first().cloned());
+                assert_eq!(rfind_char_range(&text, c), expected.last().cloned());
+            }
+        }
+    }
+
+    #[test]
+    fn test_match_char_ranges() {
+        let text = "a\"b\"🌏\"";
+        let mut matches = text.char_ranges().match_char_ranges('"');
+        assert_eq!(matches.next(), Some((1..2, '"')));
+        assert_eq!(matches.next_back(), Some((8..9, '"')));
+        assert_eq!(matches.as_str(), "b\"🌏");
+        assert_eq!(matches.next(), Some((3..4, '"')));
+        assert_eq!(matches.next_back(), None);
+        assert_eq!(matches.next(), None);
+
+        for text in random_strings() {
+            for c in CHARS {
+                let expected = naive(&text, c);
+                let matches = text.char_ranges().match_char_ranges(c);
+                assert!(matches.clone().map(|(r, _c)| r).eq(expected.clone()));
+                assert!(matches
+                    .rev()
+                    .map(|(r, _c)| r)
+                    .eq(expected.into_iter().rev()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_match_char_ranges_offset() {
+        let text = "Hello 👋 World 🌏";
+
+        let matches = text[4..].char_ranges_offset(4).match_char_ranges('o');
+        assert_eq!(matches.offset(), 4);
+        assert!(matches.eq([(4..5, 'o'), (12..13, 'o')]));
+
+        let mut chars = text.char_ranges();
+        chars.next_back();
+        assert!(chars
+            .match_char_ranges(' ')
+            .eq([(5..6, ' '), (10..11, ' '), (16..17, ' ')]));
+    }
+
+    #[test]
+    fn test_match_overlapping_ranges() {
+        let matches = "aaa".match_overlapping_ranges("aa").collect::<Vec<_>>();
+        assert_eq!(matches, [(0..2, "aa"), (1..3, "aa")]);
+
+        let matches = "abababa".match_overlapping_ranges("aba");
+        assert!(matches.map(|(r, _s)| r).eq([0..3, 2..5, 4..7]));
+
+        // Non-overlapping matches are also found
+        let matches = "a-a-a".match_overlapping_ranges("a");
+        assert!(matches.map(|(r, _s)| r).eq([0..1, 2..3, 4..5]));
+    }
+
+    #[test]
+    fn test_match_overlapping_ranges_multi_byte() {
+        // Self-overlapping multi-byte needles advance by a whole char
+        let text = "🌏🌏🌏∈🌏🌏";
+        let matches = text.match_overlapping_ranges("🌏🌏").collect::<Vec<_>>();
+        assert_eq!(matches, [(0..8, "🌏🌏"), (4..12, "🌏🌏"), (15..23, "🌏🌏")]);
+
+        let matches = "∈⊂∈⊂∈".match_overlapping_ranges("∈⊂∈");
+        assert!(matches.eq([(0..9, "∈⊂∈"), (6..15, "∈⊂∈")]));
+
+        // Same leading byte, different continuation bytes
+        assert_eq!("🗻🗻".match_overlapping_ranges("🌏").next(), None);
+    }
+
+    #[test]
+    fn test_match_overlapping_ranges_edge_cases() {
+        // Needles longer than the haystack
+        assert_eq!("aa".match_overlapping_ranges("aaa").next(), None);
+        assert_eq!("".match_overlapping_ranges("a").next(), None);
+        assert_eq!("🌏".match_overlapping_ranges("🌏🌏").next(), None);
+
+        // An empty needle produces no matches
+        assert_eq!("abc".match_overlapping_ranges("").next(), None);
+        assert_eq!("".match_overlapping_ranges("").next(), None);
+    }
+
+    #[test]
+    fn test_match_overlapping_ranges_random() {
+        for text in random_strings() {
+            for needle in ["a", "aa", "\"\"", "🌏🌏", "∈⊂", "é\n", "aéa"] {
+                // All char boundaries, where `needle` is a prefix
+                let expected = text
+                    .char_indices()
+                    .filter(|&(i, _c)| text[i..].starts_with(needle))
+                    .map(|(i, _c)| i..(i + needle.len()))
+                    .collect::<Vec<_>>();
+
+                let matches = text.match_overlapping_ranges(needle);
+                assert!(matches.clone().map(|(r, _s)| r).eq(expected));
+                for (r, s) in matches {
+                    assert_eq!(&text[r], s);
+                    assert_eq!(s, needle);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_match_overlapping_ranges_offset() {
+        let text = "xaaaa";
+
+        let mut chars = text[1..].char_ranges_offset(1);
+        chars.next_back();
+        let matches = chars.overlapping_matches("aa");
+        assert_eq!(matches.offset(), 1);
+        assert_eq!(matches.needle(), "aa");
+        assert!(matches.eq([(1..3, "aa"), (2..4, "aa")]));
+    }
+}
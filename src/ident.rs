@@ -0,0 +1,236 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use unicode_ident::{is_xid_continue, is_xid_start};
+
+use crate::CharRangesOffset;
+
+/// Why a [`char`] is invalid in an identifier.
+///
+/// See [`CharRangesExt::invalid_ident_char_ranges()`] for more information.
+///
+/// [`CharRangesExt::invalid_ident_char_ranges()`]: crate::CharRangesExt::invalid_ident_char_ranges
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum IdentError {
+    /// The first [`char`] is not `XID_Start`, e.g. a digit.
+    InvalidStart,
+    /// A [`char`] following the first is not `XID_Continue`, e.g. `'-'`.
+    InvalidContinue,
+}
+
+impl fmt::Display for IdentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidStart => write!(f, "char is not valid at the start of an identifier"),
+            Self::InvalidContinue => write!(f, "char is not valid in an identifier"),
+        }
+    }
+}
+
+impl core::error::Error for IdentError {}
+
+/// An iterator over the [`char`]s of a candidate identifier, which are not
+/// valid according to [UAX #31], producing their start and end byte
+/// positions, along with the [`IdentError`].
+///
+/// The first [`char`] must be `XID_Start`, and the following [`char`]s
+/// must be `XID_Continue`, as determined by the [`unicode-ident`] crate.
+///
+/// Note that `'_'` is not `XID_Start`, but it is `XID_Continue`. If
+/// `underscore_start` is `true`, then `'_'` is also valid as the first
+/// [`char`], like identifiers in e.g. Rust, C, and Python.
+///
+/// Since Unicode 15.1, U+200C ZERO WIDTH NON-JOINER and U+200D ZERO WIDTH
+/// JOINER are `XID_Continue`, i.e. they are only invalid as the first
+/// [`char`].
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRangesExt::invalid_ident_char_ranges()`] for more information.
+///
+/// [UAX #31]: https://www.unicode.org/reports/tr31/
+/// [`unicode-ident`]: https://docs.rs/unicode-ident
+/// [`CharRangesExt::invalid_ident_char_ranges()`]: crate::CharRangesExt::invalid_ident_char_ranges
+#[derive(Clone)]
+pub struct InvalidIdentCharRanges<'a> {
+    iter: CharRangesOffset<'a>,
+    /// The start byte position of the first [`char`], with the offset
+    /// applied.
+    start: usize,
+    underscore_start: bool,
+}
+
+impl<'a> InvalidIdentCharRanges<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>, underscore_start: bool) -> Self {
+        Self {
+            start: iter.offset() + iter.iter.start,
+            iter,
+            underscore_start,
+        }
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+
+    /// Returns the error for `c` at the start byte position `start`,
+    /// if `c` is invalid.
+    #[inline]
+    fn check(&self, start: usize, c: char) -> Option<IdentError> {
+        if start == self.start {
+            let valid = is_xid_start(c) || (self.underscore_start && (c == '_'));
+            (!valid).then_some(IdentError::InvalidStart)
+        } else {
+            (!is_xid_continue(c)).then_some(IdentError::InvalidContinue)
+        }
+    }
+}
+
+impl Iterator for InvalidIdentCharRanges<'_> {
+    type Item = (Range<usize>, char, IdentError);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (r, c) = self.iter.next()?;
+            if let Some(err) = self.check(r.start, c) {
+                return Some((r, c, err));
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_lower, upper) = self.iter.size_hint();
+        (0, upper)
+    }
+}
+
+impl DoubleEndedIterator for InvalidIdentCharRanges<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            let (r, c) = self.iter.next_back()?;
+            if let Some(err) = self.check(r.start, c) {
+                return Some((r, c, err));
+            }
+        }
+    }
+}
+
+impl FusedIterator for InvalidIdentCharRanges<'_> {}
+
+impl fmt::Debug for InvalidIdentCharRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "InvalidIdentCharRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use super::IdentError::{InvalidContinue, InvalidStart};
+    use crate::CharRangesExt;
+
+    #[test]
+    fn test_invalid_ident_char_ranges() {
+        let invalid = "1foo-bar".invalid_ident_char_ranges(false);
+        assert!(invalid.eq([(0..1, '1', InvalidStart), (4..5, '-', InvalidContinue)]));
+
+        assert_eq!("foo_bar2".invalid_ident_char_ranges(false).next(), None);
+        assert!("foo_bar2".is_valid_identifier(false));
+        assert!("日本語".is_valid_identifier(false));
+
+        assert!(!"2x".is_valid_identifier(false));
+        assert!(!"a b".is_valid_identifier(false));
+
+        let mut rev = "9a-🌏"
+            .invalid_ident_char_ranges(false)
+            .rev()
+            .collect::<Vec<_>>();
+        rev.reverse();
+        assert_eq!(
+            rev,
+            [
+                (0..1, '9', InvalidStart),
+                (2..3, '-', InvalidContinue),
+                (3..7, '🌏', InvalidContinue),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_ident_char_ranges_empty() {
+        // No invalid chars, but an empty identifier is not valid
+        assert_eq!("".invalid_ident_char_ranges(false).next(), None);
+        assert!(!"".is_valid_identifier(false));
+        assert!(!"".is_valid_identifier(true));
+    }
+
+    #[test]
+    fn test_invalid_ident_char_ranges_combining_marks() {
+        // U+0301 COMBINING ACUTE ACCENT is `XID_Continue`, but not `XID_Start`
+        assert!("e\u{301}".is_valid_identifier(false));
+
+        let invalid = "\u{301}e".invalid_ident_char_ranges(false);
+        assert!(invalid.eq([(0..2, '\u{301}', InvalidStart)]));
+    }
+
+    #[test]
+    fn test_invalid_ident_char_ranges_zwj() {
+        // U+200D ZERO WIDTH JOINER is not `XID_Start`, and only
+        // `XID_Continue` since Unicode 15.1
+        let text = "\u{200D}a";
+        let invalid = text.invalid_ident_char_ranges(true);
+        assert!(invalid.eq([(0..3, '\u{200D}', InvalidStart)]));
+        assert!(!text.is_valid_identifier(true));
+
+        assert!("a\u{200D}b".is_valid_identifier(false));
+    }
+
+    #[test]
+    fn test_invalid_ident_char_ranges_underscore() {
+        // '_' is not `XID_Start`
+        let invalid = "_foo".invalid_ident_char_ranges(false);
+        assert!(invalid.eq([(0..1, '_', InvalidStart)]));
+        assert!(!"__init__".is_valid_identifier(false));
+
+        assert!("_foo".is_valid_identifier(true));
+        assert!("__init__".is_valid_identifier(true));
+        assert!("_".is_valid_identifier(true));
+
+        // '_' and digits are `XID_Continue`
+        assert!("a_1".is_valid_identifier(false));
+    }
+
+    #[test]
+    fn test_invalid_ident_char_ranges_offset() {
+        let text = "let 1x = 2;";
+
+        let invalid = text[4..6].char_ranges_offset(4).invalid_ident_chars(false);
+        assert_eq!(invalid.offset(), 4);
+        assert!(invalid.eq([(4..5, '1', InvalidStart)]));
+
+        // The first char is the first char of the remaining substring
+        let mut chars = "a1".char_ranges();
+        chars.next();
+        assert!(chars
+            .invalid_ident_chars(false)
+            .eq([(1..2, '1', InvalidStart)]));
+    }
+}
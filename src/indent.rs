@@ -0,0 +1,266 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::CharRangesOffset;
+
+/// What the indentation of a line consists of.
+///
+/// Only `' '` and `'\t'` are indentation. Other whitespace, e.g. the
+/// no-break space `'\u{A0}'`, is considered content, i.e. it ends the
+/// indentation, since formatters and most languages don't treat it as
+/// indentation.
+///
+/// See [`IndentationRanges`] for more information.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum IndentKind {
+    /// The indentation is only spaces, i.e. `' '`.
+    Spaces(usize),
+    /// The indentation is only tabs, i.e. `'\t'`.
+    Tabs(usize),
+    /// The indentation is both spaces and tabs, in any order.
+    Mixed,
+    /// The line has no indentation, or is blank.
+    None,
+}
+
+impl IndentKind {
+    /// Returns the kind of the indentation `indent`, which must only
+    /// consist of `' '` and `'\t'`.
+    #[inline]
+    fn of(indent: &str) -> Self {
+        let tabs = indent.bytes().filter(|&b| b == b'\t').count();
+        match (indent.len() - tabs, tabs) {
+            (0, 0) => Self::None,
+            (spaces, 0) => Self::Spaces(spaces),
+            (0, tabs) => Self::Tabs(tabs),
+            _ => Self::Mixed,
+        }
+    }
+}
+
+/// An iterator over the lines of a string, i.e. their start and end
+/// byte positions, along with the start and end byte positions of their
+/// indentation and what it consists of.
+///
+/// Lines are split like [`str::lines()`], i.e. at `'\n'` and `"\r\n"`,
+/// which are excluded from the line ranges, and a trailing `'\n'` does
+/// not produce a final empty line.
+///
+/// The indentation is the leading `' '` and `'\t'` of a line, see
+/// [`IndentKind`]. Blank lines, i.e. lines which are empty or only
+/// contain `' '` and `'\t'`, have an empty indentation at the start of
+/// the line, along with [`IndentKind::None`].
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRanges::indentation_ranges()`] for more information.
+///
+/// [`CharRanges::indentation_ranges()`]: crate::CharRanges::indentation_ranges
+#[derive(Clone)]
+pub struct IndentationRanges<'a> {
+    iter: CharRangesOffset<'a>,
+}
+
+impl<'a> IndentationRanges<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>) -> Self {
+        Self { iter }
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+
+    /// Returns the line ranges, the indentation range, and the kind of
+    /// indentation of the line `line`, which excludes its terminator.
+    #[inline]
+    fn indentation(&self, line: Range<usize>) -> (Range<usize>, Range<usize>, IndentKind) {
+        let text = &self.iter.iter.text[line.clone()];
+        let rest = text.trim_start_matches([' ', '\t']);
+
+        let (indent, kind) = if rest.is_empty() {
+            (line.start..line.start, IndentKind::None)
+        } else {
+            let len = text.len() - rest.len();
+            let kind = IndentKind::of(&text[..len]);
+            (line.start..(line.start + len), kind)
+        };
+
+        (
+            self.iter.apply_offset(line),
+            self.iter.apply_offset(indent),
+            kind,
+        )
+    }
+}
+
+impl Iterator for IndentationRanges<'_> {
+    type Item = (Range<usize>, Range<usize>, IndentKind);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = self.iter.as_str();
+        if rest.is_empty() {
+            return None;
+        }
+
+        let start = self.iter.iter.start;
+        let (line, next) = match rest.find('\n') {
+            Some(i) => {
+                let end = start + i;
+                let line_end = if rest[..i].ends_with('\r') {
+                    end - 1
+                } else {
+                    end
+                };
+                (start..line_end, end + 1)
+            }
+            None => (start..self.iter.iter.end, self.iter.iter.end),
+        };
+        self.iter.iter.start = next;
+
+        Some(self.indentation(line))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.iter.as_str().len();
+        (usize::from(len > 0), Some(len))
+    }
+}
+
+impl DoubleEndedIterator for IndentationRanges<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let rest = self.iter.as_str();
+        if rest.is_empty() {
+            return None;
+        }
+
+        let start = self.iter.iter.start;
+        let line = match rest.strip_suffix('\n') {
+            Some(line) => line.strip_suffix('\r').unwrap_or(line),
+            None => rest,
+        };
+        let line_start = line.rfind('\n').map_or(0, |i| i + 1);
+        let line = (start + line_start)..(start + line.len());
+        self.iter.iter.end = line.start;
+
+        Some(self.indentation(line))
+    }
+}
+
+impl FusedIterator for IndentationRanges<'_> {}
+
+impl fmt::Debug for IndentationRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "IndentationRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use super::IndentKind;
+    use crate::CharRangesExt;
+
+    #[test]
+    fn test_indentation_ranges() {
+        let text = "fn main() {\r\n    let x = 1;\n\n  \n\tfoo();\r\n}";
+
+        let lines = text.char_ranges().indentation_ranges().collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                (0..11, 0..0, IndentKind::None),
+                (13..27, 13..17, IndentKind::Spaces(4)),
+                (28..28, 28..28, IndentKind::None),
+                (29..31, 29..29, IndentKind::None),
+                (32..39, 32..33, IndentKind::Tabs(1)),
+                (41..42, 41..41, IndentKind::None),
+            ]
+        );
+
+        // Same lines as `str::lines()`
+        let ranges = lines.iter().map(|(line, _, _)| &text[line.clone()]);
+        assert!(ranges.eq(text.lines()));
+
+        let mut rev = text
+            .char_ranges()
+            .indentation_ranges()
+            .rev()
+            .collect::<Vec<_>>();
+        rev.reverse();
+        assert_eq!(rev, lines);
+    }
+
+    #[test]
+    fn test_indentation_ranges_mixed() {
+        let text = "\t  tab then spaces\n  \tspaces then tab\n \t \tboth\n";
+        let mut lines = text.char_ranges().indentation_ranges();
+        assert_eq!(lines.next(), Some((0..18, 0..3, IndentKind::Mixed)));
+        assert_eq!(lines.next(), Some((19..37, 19..22, IndentKind::Mixed)));
+        assert_eq!(lines.next_back(), Some((38..46, 38..42, IndentKind::Mixed)));
+        assert_eq!(lines.next(), None);
+        assert_eq!(lines.next_back(), None);
+    }
+
+    #[test]
+    fn test_indentation_ranges_tabs() {
+        let text = "mod a {\n\tfn b() {\n\t\tc();\n\t}\n}\n";
+        let kinds = text
+            .char_ranges()
+            .indentation_ranges()
+            .map(|(_, _, kind)| kind);
+        assert!(kinds.eq([
+            IndentKind::None,
+            IndentKind::Tabs(1),
+            IndentKind::Tabs(2),
+            IndentKind::Tabs(1),
+            IndentKind::None,
+        ]));
+    }
+
+    #[test]
+    fn test_indentation_ranges_no_break_space() {
+        // U+00A0 is not indentation
+        let text = "\u{A0}\u{A0}a\n  \u{A0}b\n\u{A0}\n";
+        let mut lines = text.char_ranges().indentation_ranges();
+        assert_eq!(lines.next(), Some((0..5, 0..0, IndentKind::None)));
+        assert_eq!(lines.next(), Some((6..11, 6..8, IndentKind::Spaces(2))));
+        assert_eq!(lines.next(), Some((12..14, 12..12, IndentKind::None)));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn test_indentation_ranges_offset() {
+        let text = "a\n  🗻\n\t∈";
+
+        let lines = text[2..].char_ranges_offset(2).indentation_ranges();
+        assert_eq!(lines.offset(), 2);
+        assert!(lines.eq([
+            (2..8, 2..4, IndentKind::Spaces(2)),
+            (9..13, 9..10, IndentKind::Tabs(1)),
+        ]));
+
+        assert_eq!("".char_ranges().indentation_ranges().next(), None);
+        assert!("\n"
+            .char_ranges()
+            .indentation_ranges()
+            .eq([(0..0, 0..0, IndentKind::None)]));
+    }
+}
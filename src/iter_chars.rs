@@ -0,0 +1,144 @@
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+/// Returns an iterator over the [`char`]s of `iter` and their start and
+/// end byte positions, as if the [`char`]s were encoded as UTF-8, starting
+/// at the byte position `start_offset`.
+///
+/// Each range is the previous end plus the [`len_utf8()`] of the [`char`],
+/// i.e. ranges are contiguous regardless of where the [`char`]s came from.
+///
+/// See [`IterCharRanges`] for more information.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::iter_char_ranges;
+///
+/// let chars = "a🗻b∈".chars().filter(|c| !c.is_ascii());
+///
+/// let mut chars = iter_char_ranges(chars, 10);
+/// assert_eq!(chars.next(), Some((10..14, '🗻')));
+/// assert_eq!(chars.next(), Some((14..17, '∈')));
+/// assert_eq!(chars.next(), None);
+/// assert_eq!(chars.position(), 17);
+/// ```
+///
+/// [`len_utf8()`]: char::len_utf8
+#[inline]
+pub fn iter_char_ranges<I>(iter: I, start_offset: usize) -> IterCharRanges<I>
+where
+    I: Iterator<Item = char>,
+{
+    IterCharRanges {
+        iter,
+        pos: start_offset,
+    }
+}
+
+/// An iterator over the [`char`]s of any `Iterator<Item = char>` and
+/// their start and end byte positions, as if the [`char`]s were encoded
+/// as UTF-8.
+///
+/// This does not implement [`DoubleEndedIterator`], even if the inner
+/// iterator does, since the range of the last [`char`] depends on the
+/// encoded length of all [`char`]s before it, which is unknown without
+/// consuming the whole inner iterator.
+///
+/// # Panics
+///
+/// Iterating panics if a position overflows `usize`.
+///
+/// See [`iter_char_ranges()`] for more information.
+#[derive(Clone, Debug)]
+pub struct IterCharRanges<I> {
+    iter: I,
+    /// Byte position of the next [`char`].
+    pos: usize,
+}
+
+impl<I> IterCharRanges<I> {
+    /// Returns the byte position following the last produced [`char`],
+    /// i.e. the start of the next [`char`].
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the inner iterator.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I> Iterator for IterCharRanges<I>
+where
+    I: Iterator<Item = char>,
+{
+    type Item = (Range<usize>, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let c = self.iter.next()?;
+        let start = self.pos;
+        self.pos = start
+            .checked_add(c.len_utf8())
+            .expect("position overflows usize");
+        Some((start..self.pos, c))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I> ExactSizeIterator for IterCharRanges<I> where I: ExactSizeIterator<Item = char> {}
+
+impl<I> FusedIterator for IterCharRanges<I> where I: FusedIterator<Item = char> {}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::String;
+    use std::vec::Vec;
+
+    use super::iter_char_ranges;
+    use crate::CharRangesExt;
+
+    const TEXT: &str = "Hello 🗻12∈45🌏 World ¢";
+
+    #[test]
+    fn test_iter_char_ranges() {
+        assert!(iter_char_ranges(TEXT.chars(), 0).eq(TEXT.char_ranges()));
+        assert!(iter_char_ranges(TEXT.chars(), 7).eq(TEXT.char_ranges_offset(7)));
+
+        let mut chars = iter_char_ranges("".chars(), 3);
+        assert_eq!(chars.next(), None);
+        assert_eq!(chars.position(), 3);
+    }
+
+    #[test]
+    fn test_iter_char_ranges_filtered() {
+        let chars = TEXT.chars().filter(|c| !c.is_ascii_digit());
+        let chars = iter_char_ranges(chars, 5).collect::<Vec<_>>();
+
+        // Contiguous
+        let mut end = 5;
+        for (r, c) in &chars {
+            assert_eq!(r.start, end);
+            assert_eq!(r.len(), c.len_utf8());
+            end = r.end;
+        }
+
+        // Same as if the filtered chars were encoded
+        let filtered = TEXT
+            .chars()
+            .filter(|c| !c.is_ascii_digit())
+            .collect::<String>();
+        assert_eq!(end, 5 + filtered.len());
+        assert!(chars.into_iter().eq(filtered.char_ranges_offset(5)));
+    }
+}
@@ -112,13 +112,262 @@
 //! [`nth_back()`]: https://doc.rust-lang.org/std/iter/trait.DoubleEndedIterator.html#method.nth_back
 
 #![no_std]
-#![forbid(unsafe_code)]
+#![cfg_attr(not(feature = "fast"), forbid(unsafe_code))]
+#![cfg_attr(feature = "fast", deny(unsafe_code))]
 #![forbid(elided_lifetimes_in_paths)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
 use core::fmt;
+use core::hash::{Hash, Hasher};
 use core::iter::FusedIterator;
-use core::ops::Range;
-use core::str::CharIndices;
+use core::ops::{Bound, Range, RangeBounds};
+use core::str::{CharIndices, Chars};
+
+#[cfg(feature = "alloc")]
+pub use self::annotate::annotate;
+pub use self::annotate::render_annotation;
+pub use self::ascii::AsciiCharRanges;
+pub use self::boundaries::CharBoundaries;
+pub use self::byte_chunks::ByteChunks;
+#[cfg(feature = "bstr")]
+pub use self::byte_str::{from_bstr_char_index, to_bstr_char_index};
+pub use self::bytes::BytesWithCharRanges;
+pub use self::case::{CharRangesCaseMap, CharRangesLowercase, CharRangesUppercase};
+pub use self::caseless::CaselessMatchRanges;
+pub use self::char_index::{CharIndexRanges, EnumerateChars};
+#[cfg(feature = "alloc")]
+pub use self::char_index_map::CharIndexMap;
+pub use self::char_slice::{CharSliceIndexRanges, CharSliceRanges, CharSliceRangesExt};
+pub use self::chunk_by::ChunkBy;
+pub use self::chunked::{ChunkCharRanges, ChunkedCharRanges};
+pub use self::class::{CharClass, ClassifyRuns};
+pub use self::cluster::LegacyClusterRanges;
+pub use self::coalesce::CoalesceBy;
+#[cfg(feature = "codespan")]
+pub use self::codespan::{clamp_label, label_primary, label_secondary};
+pub use self::columns::{char_at_visual_column, column_at, VisualColumns};
+pub use self::common::{common_prefix_range, common_suffix_range, first_mismatch};
+pub use self::concat::{char_ranges_concat, ConcatCharRanges};
+pub use self::context::{CharContext, ContextAround, WithContext};
+pub use self::counted::CountedCharRanges;
+pub use self::cursor::CharCursor;
+#[cfg(feature = "encoding")]
+pub use self::decode::{decode_char_ranges, DecodeCharRanges};
+pub use self::dedup::DedupRuns;
+#[cfg(feature = "miette")]
+pub use self::diagnostic::ToSourceSpan;
+pub use self::display::SpanDisplay;
+#[cfg(feature = "alloc")]
+pub use self::edit::{Edit, Edits, RangeMapper};
+pub use self::error::{
+    BoundaryError, CharTooLargeError, ChunkedUtf8Error, EditsError, IntOverflowError,
+    InvalidUtf8Range, OffsetOverflowError, OverlappingEditError, SeekError, SegmentError,
+    SpanError, U32OverflowError, UnbalancedError, UnescapeError, UnpairedSurrogate,
+    UnterminatedString,
+};
+pub use self::escape::EscapeDebugRanges;
+pub use self::find::{find_char_range, rfind_char_range, MatchCharRanges, MatchOverlappingRanges};
+#[cfg(feature = "unicode-ident")]
+pub use self::ident::{IdentError, InvalidIdentCharRanges};
+pub use self::indent::{IndentKind, IndentationRanges};
+pub use self::iter_chars::{iter_char_ranges, IterCharRanges};
+pub use self::like::CharRangesLike;
+#[cfg(feature = "alloc")]
+pub use self::line_index::{LineIndex, LineIndexMut};
+#[cfg(feature = "nom")]
+pub use self::located::Located;
+pub use self::long_line::LongLineRanges;
+#[cfg(feature = "lsp")]
+pub use self::lsp::{from_lsp_position, from_lsp_range, to_lsp_position, to_lsp_range};
+pub use self::mark::Mark;
+#[cfg(feature = "aho-corasick")]
+pub use self::multi_match::{MultiMatchKind, MultiMatchRanges, MultiMatcher};
+pub use self::multipeek::MultiPeekCharRanges;
+pub use self::newline::{LineEnding, LineEndingRanges, NormalizedNewlines};
+#[cfg(feature = "unicode-normalization")]
+pub use self::normalize::NormalizedCharRanges;
+#[cfg(feature = "alloc")]
+pub use self::owned::{CharRangesOwned, IntoCharRanges};
+pub use self::pairs::CharPairs;
+#[cfg(feature = "rayon")]
+pub use self::par::{ParCharRanges, ParCharRuns};
+pub use self::paragraph::ParagraphRanges;
+pub use self::pattern::CharPattern;
+pub use self::peekable::PeekableCharRanges;
+#[cfg(feature = "percent")]
+pub use self::percent::{PercentDecodeError, PercentDecodeRanges};
+pub use self::position::{Base, CharPositions, ColumnUnit, LineColumn};
+#[doc(hidden)]
+pub use self::range_of::{__range_of, __range_of_nth};
+pub use self::range_of::{range_of_nth, range_of_unique};
+#[cfg(feature = "alloc")]
+pub use self::range_set::{
+    normalize_ranges, ranges_difference, ranges_intersection, ranges_union, RangeCheck,
+};
+pub use self::ranges::Ranges;
+pub use self::ranges32::CharRanges32;
+#[cfg(feature = "std")]
+pub use self::read::ReadCharRanges;
+#[cfg(feature = "regex")]
+pub use self::regex_match::{
+    capture_ranges, char_ranges_of_bytes_match, char_ranges_of_match, offset_match,
+};
+pub use self::rev::CharRangesRev;
+#[cfg(feature = "ropey")]
+pub use self::rope::{
+    rope_char_ranges, rope_slice_char_ranges, rope_slice_char_ranges_offset, RopeCharRanges,
+};
+pub use self::runs::CharRuns;
+pub use self::scanner::{QuotedRange, Scanner};
+#[cfg(feature = "unicode-script")]
+pub use self::script::ScriptRuns;
+pub use self::segment::SegmentByRanges;
+pub use self::snap::Snap;
+pub use self::span::{CharRangesAs, Span};
+#[cfg(feature = "alloc")]
+pub use self::span_builder::SpanBuilder;
+pub use self::span_lines::SpanLines;
+pub use self::spanned::{Spanned, SpannedCharRanges};
+pub use self::split::{RSplitCharRanges, RSplitStrRanges, SplitInclusiveRanges};
+pub use self::step_by::CharStepBy;
+pub use self::substr::substr_range;
+pub use self::suspicious::{CharIssue, SuspiciousCharRanges};
+pub use self::take::TakeBytes;
+#[cfg(feature = "text-size")]
+pub use self::text_range::{to_text_range, CharTextRanges};
+pub use self::tokens::{tokens, Tokens};
+pub use self::trailing::TrailingWhitespaceRanges;
+pub use self::unescape::{EscapeStyle, UnescapeRanges};
+pub use self::utf16::{Utf16CharRanges, Utf16CharRangesExt, Utf16CharRangesOffset};
+pub use self::utf8::{Utf8CharRanges, Utf8CharRangesExt, Utf8CharRangesLossy};
+pub use self::valid_span::ValidSpan;
+#[cfg(feature = "unicode-width")]
+pub use self::width::{char_at_width_column, CharWidthRanges, WidthColumns};
+pub use self::wrap::WrapRanges;
+pub use self::zip::{
+    char_ranges_zip, char_ranges_zip_longest, EitherOrBoth, ZipCharRanges, ZipLongestCharRanges,
+};
+
+mod annotate;
+mod ascii;
+mod balanced;
+mod boundaries;
+mod byte_chunks;
+#[cfg(feature = "bstr")]
+mod byte_str;
+mod bytes;
+mod caret;
+mod case;
+mod caseless;
+mod char_index;
+#[cfg(feature = "alloc")]
+mod char_index_map;
+mod char_slice;
+mod chunk_by;
+mod chunked;
+mod class;
+mod cluster;
+mod coalesce;
+#[cfg(feature = "codespan")]
+mod codespan;
+mod columns;
+mod common;
+mod concat;
+mod context;
+mod counted;
+mod cursor;
+#[cfg(feature = "encoding")]
+mod decode;
+mod dedup;
+#[cfg(feature = "miette")]
+mod diagnostic;
+mod display;
+#[cfg(feature = "alloc")]
+mod edit;
+mod error;
+mod escape;
+#[cfg(feature = "fast")]
+mod fast;
+mod find;
+#[cfg(feature = "unicode-ident")]
+mod ident;
+mod indent;
+mod iter_chars;
+mod like;
+#[cfg(feature = "alloc")]
+mod line_index;
+#[cfg(feature = "nom")]
+mod located;
+mod long_line;
+#[cfg(feature = "lsp")]
+mod lsp;
+mod mark;
+#[cfg(feature = "aho-corasick")]
+mod multi_match;
+mod multipeek;
+mod newline;
+#[cfg(feature = "unicode-normalization")]
+mod normalize;
+#[cfg(feature = "alloc")]
+mod owned;
+mod pairs;
+#[cfg(feature = "rayon")]
+mod par;
+mod paragraph;
+mod pattern;
+mod peekable;
+#[cfg(feature = "percent")]
+mod percent;
+mod position;
+mod range_of;
+#[cfg(feature = "alloc")]
+mod range_set;
+mod ranges;
+mod ranges32;
+#[cfg(feature = "std")]
+mod read;
+#[cfg(feature = "regex")]
+mod regex_match;
+mod rev;
+#[cfg(feature = "ropey")]
+mod rope;
+mod runs;
+mod scanner;
+#[cfg(feature = "unicode-script")]
+mod script;
+mod segment;
+#[cfg(feature = "serde")]
+pub mod serde_range;
+mod snap;
+mod span;
+#[cfg(feature = "alloc")]
+mod span_builder;
+mod span_lines;
+mod spanned;
+mod split;
+mod step_by;
+mod substr;
+mod suspicious;
+mod take;
+#[cfg(test)]
+mod test_util;
+#[cfg(feature = "text-size")]
+mod text_range;
+mod tokens;
+mod trailing;
+mod unescape;
+mod utf16;
+mod utf8;
+mod valid_span;
+#[cfg(feature = "unicode-width")]
+mod width;
+mod word;
+mod wrap;
+mod zip;
 
 pub trait CharRangesExt {
     /// Returns an iterator over [`char`]s and their start and end byte positions.
@@ -134,642 +383,10430 @@ pub trait CharRangesExt {
     fn char_ranges_offset(&self, offset: usize) -> CharRangesOffset<'_> {
         self.char_ranges().offset(offset)
     }
-}
 
-impl CharRangesExt for str {
+    /// Returns an iterator over [`char`]s and their start and end byte positions,
+    /// relative to `parent`, where `self` is a subslice of `parent`.
+    ///
+    /// Returns `None` if `self` does not lie within `parent`.
+    ///
+    /// See [`substr_range()`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "foo 🗻∈🌏 bar";
+    ///
+    /// let word = text.split(' ').nth(1).unwrap();
+    ///
+    /// let mut chars = word.char_ranges_within(text).unwrap();
+    /// assert_eq!(chars.next(), Some((4..8, '🗻')));
+    /// assert_eq!(chars.next(), Some((8..11, '∈')));
+    /// ```
     #[inline]
-    fn char_ranges(&self) -> CharRanges<'_> {
-        CharRanges::new(self)
+    fn char_ranges_within(&self, parent: &str) -> Option<CharRangesOffset<'_>> {
+        let chars = self.char_ranges();
+        let r = substr_range(parent, chars.as_str())?;
+        Some(chars.offset(r.start))
     }
-}
-
-/// An iterator over [`char`]s and their start and end byte positions.
-///
-/// Note: Cloning this iterator is essentially a copy.
-///
-/// See examples in the [crate root](crate).
-#[derive(Clone)]
-pub struct CharRanges<'a> {
-    iter: CharIndices<'a>,
-}
 
-impl<'a> CharRanges<'a> {
-    /// Creates an iterator over [`char`]s and their start and end byte positions.
+    /// Returns an iterator over the [`char`]s within the byte positions `range`,
+    /// and their start and end byte positions in the whole text.
     ///
-    /// Consider using <code>text.[char_ranges()]</code>, instead of
-    /// explicitly using `CharRanges::new()`.
+    /// This is the same as `text[range].char_ranges_offset(range.start)`,
+    /// i.e. `char_ranges_in(..)` yields the same items as [`char_ranges()`].
     ///
-    /// See examples in the [crate root](crate).
+    /// # Panics
     ///
-    /// [char_ranges()]: CharRangesExt::char_ranges
+    /// Panics if the start or end of `range` is out of bounds or is
+    /// not on a [`char`] boundary, in which case the message includes
+    /// the straddled [`char`]. Panics if the start is after the end.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "foo 🗻∈🌏 bar";
+    ///
+    /// let mut chars = text.char_ranges_in(4..11);
+    /// assert_eq!(chars.next(), Some((4..8, '🗻')));
+    /// assert_eq!(chars.next(), Some((8..11, '∈')));
+    /// assert_eq!(chars.next(), None);
+    ///
+    /// let mut chars = text.char_ranges_in(16..);
+    /// assert_eq!(chars.next(), Some((16..17, 'b')));
+    /// ```
+    ///
+    /// [`char_ranges()`]: Self::char_ranges
     #[inline]
-    pub fn new(text: &'a str) -> Self {
-        Self {
-            iter: text.char_indices(),
-        }
+    #[track_caller]
+    fn char_ranges_in<R>(&self, range: R) -> CharRangesOffset<'_>
+    where
+        R: RangeBounds<usize>,
+    {
+        let text = self.char_ranges().as_str();
+        let r = bounds_to_range(range, text.len());
+        assert_char_boundary(text, r.start);
+        assert_char_boundary(text, r.end);
+        assert!(
+            r.start <= r.end,
+            "range start {} is after end {}",
+            r.start,
+            r.end,
+        );
+        text[r.clone()].char_ranges_offset(r.start)
     }
 
-    /// Returns the remaining substring.
+    /// Returns an iterator over the [`char`]s from the byte position `pos`
+    /// to the end, and their start and end byte positions in the whole text.
+    ///
+    /// This is the same as [`char_ranges_in(pos..)`](Self::char_ranges_in).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pos` is out of bounds or is not on a [`char`] boundary,
+    /// in which case the message includes the straddled [`char`].
     ///
     /// # Example
     ///
     /// ```rust
     /// use char_ranges::CharRangesExt;
     ///
-    /// let text = "ABCDE";
+    /// let text = "foo 🗻∈🌏 bar";
     ///
-    /// let mut chars = text.char_ranges();
-    /// assert_eq!(chars.as_str(), "ABCDE");
+    /// let mut chars = text.char_ranges_from(8);
+    /// assert_eq!(chars.next(), Some((8..11, '∈')));
+    /// assert_eq!(chars.next_back(), Some((18..19, 'r')));
+    /// ```
+    #[inline]
+    #[track_caller]
+    fn char_ranges_from(&self, pos: usize) -> CharRangesOffset<'_> {
+        self.char_ranges_in(pos..)
+    }
+
+    /// Returns an iterator over the [`char`]s from the start up to the byte
+    /// position `pos`, and their start and end byte positions.
     ///
-    /// assert_eq!(chars.next(), Some((0..1, 'A')));
-    /// assert_eq!(chars.next_back(), Some((4..5, 'E')));
+    /// This is the same as [`char_ranges_in(..pos)`](Self::char_ranges_in).
     ///
-    /// assert_eq!(chars.as_str(), "BCD");
+    /// # Panics
+    ///
+    /// Panics if `pos` is out of bounds or is not on a [`char`] boundary,
+    /// in which case the message includes the straddled [`char`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "foo 🗻∈🌏 bar";
+    ///
+    /// let mut chars = text.char_ranges_up_to(8);
+    /// assert_eq!(chars.next_back(), Some((4..8, '🗻')));
+    /// assert_eq!(chars.as_str(), "foo ");
     /// ```
     #[inline]
-    pub fn as_str(&self) -> &'a str {
-        self.iter.as_str()
+    #[track_caller]
+    fn char_ranges_up_to(&self, pos: usize) -> CharRangesOffset<'_> {
+        self.char_ranges_in(..pos)
     }
 
-    /// Returns an iterator over the remaining [`char`]s and their start and
-    /// end byte positions, with an offset applied to all positions.
+    /// Returns the range of the leading byte order mark (BOM), i.e.
+    /// `'\u{FEFF}'`, if any, along with the remaining text after it.
     ///
-    /// See examples in the [crate root](crate).
+    /// The range is always `0..3` when present, as only a BOM at the
+    /// very start of the text is stripped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// assert_eq!("\u{FEFF}foo".strip_bom_range(), (Some(0..3), "foo"));
+    /// assert_eq!("foo".strip_bom_range(), (None, "foo"));
+    /// assert_eq!("foo\u{FEFF}".strip_bom_range(), (None, "foo\u{FEFF}"));
+    /// ```
     #[inline]
-    pub fn offset(self, offset: usize) -> CharRangesOffset<'a> {
-        CharRangesOffset { iter: self, offset }
+    fn strip_bom_range(&self) -> (Option<Range<usize>>, &str) {
+        let text = self.char_ranges().as_str();
+        match text.strip_prefix('\u{FEFF}') {
+            Some(rest) => (Some(0..BOM_LEN), rest),
+            None => (None, text),
+        }
     }
-}
-
-impl Iterator for CharRanges<'_> {
-    type Item = (Range<usize>, char);
 
+    /// Returns an iterator over [`char`]s and their start and end byte
+    /// positions, skipping the leading byte order mark (BOM), i.e.
+    /// `'\u{FEFF}'`, if any.
+    ///
+    /// The positions are still relative to the whole text, i.e. when a
+    /// BOM is present, the first [`char`] starts at `3`.
+    ///
+    /// See [`strip_bom_range()`](Self::strip_bom_range) for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "\u{FEFF}🗻∈";
+    ///
+    /// let mut chars = text.char_ranges_skip_bom();
+    /// assert_eq!(chars.next(), Some((3..7, '🗻')));
+    /// assert_eq!(chars.next(), Some((7..10, '∈')));
+    /// assert_eq!(chars.next(), None);
+    /// ```
     #[inline]
-    fn next(&mut self) -> Option<Self::Item> {
-        let (start, c) = self.iter.next()?;
-        let end = start + c.len_utf8();
-        Some((start..end, c))
+    fn char_ranges_skip_bom(&self) -> CharRangesOffset<'_> {
+        let (bom, rest) = self.strip_bom_range();
+        let start = bom.map_or(0, |bom| bom.end);
+        rest.char_ranges_offset(start)
     }
 
+    /// Returns an iterator over [`char`]s and their start and end byte
+    /// positions, where `"\r\n"` and `'\r'` are normalized to `'\n'`.
+    ///
+    /// A `"\r\n"` pair is produced as a single `'\n'` with a range
+    /// spanning both bytes, such that spans still cover the source text.
+    /// See [`NormalizedNewlines`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "a\r\nb\rc\n";
+    ///
+    /// let mut chars = text.char_ranges_normalized_newlines();
+    /// assert_eq!(chars.next(), Some((0..1, 'a')));
+    /// assert_eq!(chars.next(), Some((1..3, '\n')));
+    /// assert_eq!(chars.next(), Some((3..4, 'b')));
+    /// assert_eq!(chars.next(), Some((4..5, '\n')));
+    /// assert_eq!(chars.next(), Some((5..6, 'c')));
+    /// assert_eq!(chars.next(), Some((6..7, '\n')));
+    /// assert_eq!(chars.next(), None);
+    /// ```
     #[inline]
-    fn count(self) -> usize {
-        self.iter.count()
+    fn char_ranges_normalized_newlines(&self) -> NormalizedNewlines<'_> {
+        self.char_ranges().normalize_newlines()
     }
 
+    /// Returns the most common line terminator, or `None` if there are
+    /// no line terminators.
+    ///
+    /// Ties are resolved in the order [`LineEnding::Lf`],
+    /// [`LineEnding::CrLf`], and [`LineEnding::Cr`].
+    ///
+    /// See [`CharRanges::line_ending_ranges()`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{CharRangesExt, LineEnding};
+    ///
+    /// assert_eq!("a\r\nb\r\nc\n".detect_line_ending(), Some(LineEnding::CrLf));
+    /// assert_eq!("a\nb".detect_line_ending(), Some(LineEnding::Lf));
+    /// assert_eq!("a".detect_line_ending(), None);
+    /// ```
     #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        self.iter.size_hint()
+    fn detect_line_ending(&self) -> Option<LineEnding> {
+        newline::detect_line_ending(self.char_ranges().as_str())
     }
 
+    /// Returns `true` if there is more than one kind of line terminator,
+    /// e.g. both `'\n'` and `"\r\n"`.
+    ///
+    /// See [`CharRanges::line_ending_ranges()`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// assert!("a\r\nb\n".has_mixed_line_endings());
+    /// assert!(!"a\r\nb\r\n".has_mixed_line_endings());
+    /// assert!(!"a".has_mixed_line_endings());
+    /// ```
     #[inline]
-    fn last(mut self) -> Option<(Range<usize>, char)> {
-        self.next_back()
+    fn has_mixed_line_endings(&self) -> bool {
+        newline::has_mixed_line_endings(self.char_ranges().as_str())
     }
 
+    /// Returns an iterator over [`char`]s and their start and end byte positions,
+    /// in reverse order.
+    ///
+    /// See [`CharRanges::reversed()`] for more information.
     #[inline]
-    fn nth(&mut self, n: usize) -> Option<Self::Item> {
-        let (start, c) = self.iter.nth(n)?;
-        let end = start + c.len_utf8();
-        Some((start..end, c))
+    fn char_ranges_rev(&self) -> CharRangesRev<'_> {
+        self.char_ranges().reversed()
     }
-}
 
-impl DoubleEndedIterator for CharRanges<'_> {
+    /// Returns an iterator over [`char`]s and their start and end byte positions,
+    /// as <code>[Range]&lt;[u32]&gt;</code>.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the text overflows `u32`.
+    /// Use [`CharRanges32::try_new()`] to handle this instead.
+    ///
+    /// See [`CharRanges32`] for more information.
     #[inline]
-    fn next_back(&mut self) -> Option<Self::Item> {
-        let (start, c) = self.iter.next_back()?;
-        let end = start + c.len_utf8();
-        Some((start..end, c))
+    fn char_ranges32(&self) -> CharRanges32<'_> {
+        self.char_ranges32_offset(0)
     }
 
+    /// Returns an iterator over [`char`]s and their start and end byte positions,
+    /// as <code>[Range]&lt;[u32]&gt;</code>, with an offset applied to all positions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `offset` plus the length of the text overflows `u32`.
+    /// Use [`CharRanges32::try_new()`] to handle this instead.
+    ///
+    /// See [`CharRanges32`] for more information.
     #[inline]
-    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
-        let (start, c) = self.iter.nth_back(n)?;
-        let end = start + c.len_utf8();
-        Some((start..end, c))
+    fn char_ranges32_offset(&self, offset: u32) -> CharRanges32<'_> {
+        CharRanges32::new(offset, self.char_ranges().as_str())
     }
-}
 
-impl FusedIterator for CharRanges<'_> {}
-
-impl fmt::Debug for CharRanges<'_> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "CharRanges(")?;
-        f.debug_list().entries(self.clone()).finish()?;
-        write!(f, ")")?;
-        Ok(())
+    /// Returns an iterator over [`char`]s and their start and end byte positions,
+    /// as a [`TextRange`](text_size::TextRange).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the length of the text overflows `u32`,
+    /// instead of silently truncating positions.
+    ///
+    /// See [`CharTextRanges`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    /// use text_size::TextRange;
+    ///
+    /// let text = "a🌏";
+    ///
+    /// let mut chars = text.char_text_ranges().unwrap();
+    /// assert_eq!(chars.next(), Some((TextRange::new(0.into(), 1.into()), 'a')));
+    /// assert_eq!(chars.next(), Some((TextRange::new(1.into(), 5.into()), '🌏')));
+    /// assert_eq!(chars.next(), None);
+    /// ```
+    #[cfg(feature = "text-size")]
+    #[inline]
+    fn char_text_ranges(&self) -> Result<CharTextRanges<'_>, U32OverflowError> {
+        CharTextRanges::try_new(0.into(), self.char_ranges().as_str())
     }
-}
-
-/// An iterator over [`char`]s and their start and end byte positions,
-/// with an offset applied to all positions.
-///
-/// Note: Cloning this iterator is essentially a copy.
-///
-/// See examples in the [crate root](crate).
-#[derive(Clone)]
-pub struct CharRangesOffset<'a> {
-    iter: CharRanges<'a>,
-    offset: usize,
-}
 
-impl<'a> CharRangesOffset<'a> {
-    /// Creates an iterator over [`char`]s and their start and end byte positions,
-    /// with an offset applied to all positions.
+    /// Returns an iterator over the [`char`]s within the byte range `range`,
+    /// and their start and end byte positions in the whole text, as a
+    /// [`TextRange`](text_size::TextRange).
     ///
-    /// Consider using <code>text.[char_ranges_offset()]</code> or
-    /// <code>text.[char_ranges()].[offset()]</code>, instead of
-    /// explicitly using `CharRangesOffset::new()`.
+    /// This is the same as [`char_ranges_in()`](Self::char_ranges_in),
+    /// except for a [`TextRange`](text_size::TextRange).
     ///
-    /// See examples in the [crate root](crate).
+    /// # Panics
     ///
-    /// [char_ranges()]: CharRangesExt::char_ranges
-    /// [char_ranges_offset()]: CharRangesExt::char_ranges_offset
-    /// [offset()]: CharRanges::offset
+    /// Panics if the start or end of `range` is out of bounds or is not on
+    /// a [`char`] boundary, in which case the message includes the
+    /// straddled [`char`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    /// use text_size::TextRange;
+    ///
+    /// let text = "foo 🗻∈🌏 bar";
+    ///
+    /// let mut chars = text.char_text_ranges_in(TextRange::new(4.into(), 15.into()));
+    /// assert_eq!(chars.next(), Some((TextRange::new(4.into(), 8.into()), '🗻')));
+    /// assert_eq!(chars.as_str(), "∈🌏");
+    /// ```
+    #[cfg(feature = "text-size")]
     #[inline]
-    pub fn new(offset: usize, text: &'a str) -> Self {
-        Self {
-            iter: text.char_ranges(),
-            offset,
-        }
+    #[track_caller]
+    fn char_text_ranges_in(&self, range: text_size::TextRange) -> CharTextRanges<'_> {
+        let chars = self.char_ranges_in(Range::<usize>::from(range));
+        // The range is within a `TextRange`, so no position can overflow
+        CharTextRanges::new(range.start(), chars.as_str())
     }
 
-    /// Returns the remaining substring.
+    /// Returns an iterator over [`char`]s mapped to lowercase, and the start
+    /// and end byte positions of the source [`char`] they originate from.
+    ///
+    /// When a single [`char`] lowercases to multiple [`char`]s, then they
+    /// all share the range of the source [`char`].
     ///
     /// # Example
     ///
     /// ```rust
     /// use char_ranges::CharRangesExt;
     ///
-    /// let text = "Hello 👋 World 🌏";
+    /// let text = "Aİ";
     ///
-    /// let start = 11; // Start index of 'W'
-    /// let text = &text[start..]; // "World 🌏"
+    /// let mut chars = text.char_ranges_lowercase();
+    /// assert_eq!(chars.next(), Some((0..1, 'a')));
+    /// assert_eq!(chars.next(), Some((1..3, 'i')));
+    /// assert_eq!(chars.next(), Some((1..3, '\u{307}')));
+    /// assert_eq!(chars.next(), None);
+    /// ```
+    #[inline]
+    fn char_ranges_lowercase(&self) -> CharRangesLowercase<'_> {
+        self.char_ranges().lowercase()
+    }
+
+    /// Returns an iterator over [`char`]s mapped to uppercase, and the start
+    /// and end byte positions of the source [`char`] they originate from.
     ///
-    /// let mut chars = text.char_ranges_offset(start);
-    /// assert_eq!(chars.as_str(), "World 🌏");
+    /// When a single [`char`] uppercases to multiple [`char`]s, then they
+    /// all share the range of the source [`char`].
     ///
-    /// assert_eq!(chars.next(), Some((11..12, 'W'))); // These chars are 1 byte
-    /// assert_eq!(chars.next_back(), Some((17..21, '🌏'))); // This char is 4 bytes
+    /// # Example
     ///
-    /// assert_eq!(chars.as_str(), "orld ");
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "aß";
+    ///
+    /// let mut chars = text.char_ranges_uppercase();
+    /// assert_eq!(chars.next(), Some((0..1, 'A')));
+    /// assert_eq!(chars.next(), Some((1..3, 'S')));
+    /// assert_eq!(chars.next(), Some((1..3, 'S')));
+    /// assert_eq!(chars.next(), None);
     /// ```
     #[inline]
-    pub fn as_str(&self) -> &'a str {
-        self.iter.as_str()
+    fn char_ranges_uppercase(&self) -> CharRangesUppercase<'_> {
+        self.char_ranges().uppercase()
     }
 
-    /// Returns the `offset` this [`CharRangesOffset`] was created with.
+    /// Returns an iterator over the [`char`]s produced by [`char::escape_debug()`],
+    /// and the start and end byte positions of the source [`char`] they
+    /// originate from.
+    ///
+    /// When a single [`char`] is escaped into multiple [`char`]s, then they
+    /// all share the range of the source [`char`]. This makes it possible to
+    /// render an escaped string, while still mapping each escaped [`char`]
+    /// back to the source.
     ///
     /// # Example
     ///
     /// ```rust
     /// use char_ranges::CharRangesExt;
     ///
-    /// let text = "Hello 👋 World 🌏";
+    /// let text = "a\n🌏";
     ///
-    /// let start = 11; // Start index of 'W'
-    /// let text = &text[start..]; // "World 🌏"
+    /// let mut chars = text.escape_debug_ranges();
+    /// assert_eq!(chars.next(), Some((0..1, 'a')));
+    /// assert_eq!(chars.next(), Some((1..2, '\\')));
+    /// assert_eq!(chars.next(), Some((1..2, 'n')));
+    /// assert_eq!(chars.next(), Some((2..6, '🌏')));
+    /// assert_eq!(chars.next(), None);
+    /// ```
+    #[inline]
+    fn escape_debug_ranges(&self) -> EscapeDebugRanges<'_> {
+        self.char_ranges().escape_debug()
+    }
+
+    /// Returns an iterator over the decoded [`char`]s of the body of a
+    /// string literal, and the start and end byte positions of their source,
+    /// where escapes are decoded using the [`EscapeStyle::Rust`] style.
     ///
-    /// let mut chars = text.char_ranges_offset(start);
-    /// // Offset is `start`
-    /// assert_eq!(chars.offset(), start);
+    /// Decoded escapes are produced with the range of the whole escape.
+    /// Invalid escapes produce an [`UnescapeError`] with the range of the
+    /// escape, after which iteration continues.
     ///
-    /// assert_eq!(chars.next(), Some((11..12, 'W'))); // These chars are 1 byte
-    /// assert_eq!(chars.next_back(), Some((17..21, '🌏'))); // This char is 4 bytes
+    /// See [`UnescapeRanges`] for more information.
     ///
-    /// // Offset remains as `start` always
-    /// assert_eq!(chars.offset(), start);
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{CharRangesExt, UnescapeError};
+    ///
+    /// let text = r"a\n\u{1F30D}\q";
+    ///
+    /// let mut chars = text.unescape_ranges();
+    /// assert_eq!(chars.next(), Some(Ok((0..1, 'a'))));
+    /// assert_eq!(chars.next(), Some(Ok((1..3, '\n'))));
+    /// assert_eq!(chars.next(), Some(Ok((3..12, '🌍'))));
+    /// assert_eq!(chars.next(), Some(Err(UnescapeError::UnknownEscape(12..14))));
+    /// assert_eq!(chars.next(), None);
     /// ```
     #[inline]
-    pub fn offset(&self) -> usize {
-        self.offset
+    fn unescape_ranges(&self) -> UnescapeRanges<'_> {
+        self.char_ranges().unescape()
     }
-}
-
-impl Iterator for CharRangesOffset<'_> {
-    type Item = (Range<usize>, char);
 
+    /// Returns an iterator over the percent-decoded [`char`]s, e.g. of a
+    /// URL or a query string, and the start and end byte positions of
+    /// their source.
+    ///
+    /// Decoded escapes are produced with the range of all the `%XX` escapes
+    /// of the [`char`]. Invalid escapes produce a [`PercentDecodeError`]
+    /// with the range of the escapes, after which iteration continues.
+    ///
+    /// See [`PercentDecodeRanges`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{CharRangesExt, PercentDecodeError};
+    ///
+    /// let text = "a%E2%9C%93%G";
+    ///
+    /// let mut chars = text.percent_decode_ranges();
+    /// assert_eq!(chars.next(), Some(Ok((0..1, 'a'))));
+    /// assert_eq!(chars.next(), Some(Ok((1..10, '✓'))));
+    /// assert_eq!(chars.next(), Some(Err(PercentDecodeError::InvalidHex(10..11))));
+    /// assert_eq!(chars.next(), Some(Ok((11..12, 'G'))));
+    /// assert_eq!(chars.next(), None);
+    /// ```
+    #[cfg(feature = "percent")]
     #[inline]
-    fn next(&mut self) -> Option<Self::Item> {
-        let (r, c) = self.iter.next()?;
-        let start = r.start + self.offset;
-        let end = r.end + self.offset;
-        Some((start..end, c))
+    fn percent_decode_ranges(&self) -> PercentDecodeRanges<'_> {
+        self.char_ranges().percent_decode()
     }
 
-    #[inline]
+    /// Returns an iterator over suspicious [`char`]s, their start and end
+    /// byte positions, and their [`CharIssue`], e.g. bidirectional controls,
+    /// zero-width [`char`]s, control characters and non-ASCII [`char`]s.
+    ///
+    /// Which categories are reported can be configured, e.g. to allow plain
+    /// non-ASCII [`char`]s, while still reporting bidirectional controls.
+    /// See [`SuspiciousCharRanges`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{CharIssue, CharRangesExt};
+    ///
+    /// let text = "if é\u{202E} {\u{200B}\t}";
+    ///
+    /// let mut chars = text.suspicious_char_ranges();
+    /// assert_eq!(chars.next(), Some((3..5, 'é', CharIssue::NonAscii)));
+    /// assert_eq!(chars.next(), Some((5..8, '\u{202E}', CharIssue::BidiControl)));
+    /// assert_eq!(chars.next(), Some((10..13, '\u{200B}', CharIssue::ZeroWidth)));
+    /// assert_eq!(chars.next(), None);
+    ///
+    /// let mut chars = text
+    ///     .suspicious_char_ranges()
+    ///     .report(CharIssue::NonAscii, false)
+    ///     .allow_tab(false);
+    /// assert_eq!(chars.next(), Some((5..8, '\u{202E}', CharIssue::BidiControl)));
+    /// assert_eq!(chars.next(), Some((10..13, '\u{200B}', CharIssue::ZeroWidth)));
+    /// assert_eq!(chars.next(), Some((13..14, '\t', CharIssue::Control)));
+    /// assert_eq!(chars.next(), None);
+    /// ```
+    #[inline]
+    fn suspicious_char_ranges(&self) -> SuspiciousCharRanges<'_> {
+        self.char_ranges().suspicious()
+    }
+
+    /// Returns an iterator over the non-overlapping case-insensitive
+    /// matches of `needle`, and their start and end byte positions.
+    ///
+    /// Matches are found left to right, and [`char`]s are compared using
+    /// simple case folding, i.e. their [`to_lowercase()`]. The byte length
+    /// of a match may differ from the byte length of `needle`. An empty
+    /// `needle` produces no matches.
+    ///
+    /// Note: Full case folding is not supported, e.g. `"ß"` does not match
+    /// `"ss"`. See [`CaselessMatchRanges`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "Warning: WARNING, warning";
+    ///
+    /// let mut matches = text.caseless_match_ranges("warning");
+    /// assert_eq!(matches.next(), Some((0..7, "Warning")));
+    /// assert_eq!(matches.next(), Some((9..16, "WARNING")));
+    /// assert_eq!(matches.next(), Some((18..25, "warning")));
+    /// assert_eq!(matches.next(), None);
+    ///
+    /// // The 3 byte KELVIN SIGN lowercases to the 1 byte 'k'
+    /// let mut matches = "\u{212A}elvin".caseless_match_ranges("kelvin");
+    /// assert_eq!(matches.next(), Some((0..8, "\u{212A}elvin")));
+    /// ```
+    ///
+    /// [`to_lowercase()`]: char::to_lowercase
+    #[inline]
+    fn caseless_match_ranges<'b>(&self, needle: &'b str) -> CaselessMatchRanges<'_, 'b> {
+        self.char_ranges().caseless_matches(needle)
+    }
+
+    /// Returns an iterator over all matches of `needle`, including
+    /// overlapping matches, and their start and end byte positions.
+    ///
+    /// Unlike [`str::match_indices()`], which only produces non-overlapping
+    /// matches, the search continues one [`char`] after the start of the
+    /// previous match, i.e. all matches are on [`char`] boundaries. An
+    /// empty `needle` produces no matches.
+    ///
+    /// See [`MatchOverlappingRanges`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let mut matches = "aaa".match_overlapping_ranges("aa");
+    /// assert_eq!(matches.next(), Some((0..2, "aa")));
+    /// assert_eq!(matches.next(), Some((1..3, "aa")));
+    /// assert_eq!(matches.next(), None);
+    ///
+    /// let mut matches = "🌏🌏🌏".match_overlapping_ranges("🌏🌏");
+    /// assert_eq!(matches.next(), Some((0..8, "🌏🌏")));
+    /// assert_eq!(matches.next(), Some((4..12, "🌏🌏")));
+    /// assert_eq!(matches.next(), None);
+    /// ```
+    #[inline]
+    fn match_overlapping_ranges<'b>(&self, needle: &'b str) -> MatchOverlappingRanges<'_, 'b> {
+        self.char_ranges().overlapping_matches(needle)
+    }
+
+    /// Returns an iterator over [`char`]s in Normalization Form C (NFC), and
+    /// the start and end byte positions of the source [`char`]s they were
+    /// derived from.
+    ///
+    /// When multiple source [`char`]s compose into a single [`char`], then its
+    /// range spans all of them. When a single source [`char`] decomposes into
+    /// multiple [`char`]s, then they all share the range of the source [`char`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "Cafe\u{301}";
+    ///
+    /// let mut chars = text.nfc_char_ranges();
+    /// assert_eq!(chars.next(), Some((0..1, 'C')));
+    /// assert_eq!(chars.next(), Some((1..2, 'a')));
+    /// assert_eq!(chars.next(), Some((2..3, 'f')));
+    /// assert_eq!(chars.next(), Some((3..6, 'é')));
+    /// assert_eq!(chars.next(), None);
+    /// ```
+    #[cfg(feature = "unicode-normalization")]
+    #[inline]
+    fn nfc_char_ranges(&self) -> NormalizedCharRanges<'_> {
+        self.char_ranges().nfc()
+    }
+
+    /// Returns an iterator over [`char`]s in Normalization Form D (NFD), and
+    /// the start and end byte positions of the source [`char`]s they were
+    /// derived from.
+    ///
+    /// When a single source [`char`] decomposes into multiple [`char`]s, then
+    /// they all share the range of the source [`char`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "Café";
+    ///
+    /// let mut chars = text.nfd_char_ranges();
+    /// assert_eq!(chars.next(), Some((0..1, 'C')));
+    /// assert_eq!(chars.next(), Some((1..2, 'a')));
+    /// assert_eq!(chars.next(), Some((2..3, 'f')));
+    /// assert_eq!(chars.next(), Some((3..5, 'e')));
+    /// assert_eq!(chars.next(), Some((3..5, '\u{301}')));
+    /// assert_eq!(chars.next(), None);
+    /// ```
+    #[cfg(feature = "unicode-normalization")]
+    #[inline]
+    fn nfd_char_ranges(&self) -> NormalizedCharRanges<'_> {
+        self.char_ranges().nfd()
+    }
+
+    /// Returns an iterator over [`char`]s, their start and end byte positions,
+    /// and the number of terminal cells they occupy.
+    ///
+    /// Widths are determined by the [`unicode-width`] crate, e.g. `'中'` and
+    /// `'🌏'` are `2` cells wide, and combining marks are `0` cells wide.
+    /// Control characters, e.g. `'\t'` and `'\n'`, for which [`unicode-width`]
+    /// returns `None`, are `0` cells wide.
+    ///
+    /// See [`CharRanges::width_columns()`] to also track the column.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "a中e\u{301}🌏";
+    ///
+    /// let mut chars = text.char_width_ranges();
+    /// assert_eq!(chars.next(), Some((0..1, 'a', 1)));
+    /// assert_eq!(chars.next(), Some((1..4, '中', 2)));
+    /// assert_eq!(chars.next(), Some((4..5, 'e', 1)));
+    /// assert_eq!(chars.next(), Some((5..7, '\u{301}', 0)));
+    /// assert_eq!(chars.next(), Some((7..11, '🌏', 2)));
+    /// assert_eq!(chars.next(), None);
+    /// ```
+    ///
+    /// [`unicode-width`]: https://docs.rs/unicode-width
+    #[cfg(feature = "unicode-width")]
+    #[inline]
+    fn char_width_ranges(&self) -> CharWidthRanges<'_> {
+        self.char_ranges().widths()
+    }
+
+    /// Returns the number of terminal cells occupied by the [`char`]s
+    /// within the byte range `range`.
+    ///
+    /// This is the sum of the widths produced by
+    /// [`char_width_ranges()`](Self::char_width_ranges).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start or end of `range` is out of bounds or is not
+    /// on a [`char`] boundary, or if the start is after the end.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "a中e\u{301}🌏";
+    /// assert_eq!(text.width_of_range(..), 6);
+    /// assert_eq!(text.width_of_range(1..7), 3);
+    /// ```
+    #[cfg(feature = "unicode-width")]
+    #[inline]
+    #[track_caller]
+    fn width_of_range<R>(&self, range: R) -> usize
+    where
+        R: RangeBounds<usize>,
+    {
+        self.char_ranges_in(range)
+            .map(|(_r, c)| width::char_width(c))
+            .sum()
+    }
+
+    /// Returns the byte range of the longest prefix, which fits within
+    /// `max_cells` terminal cells.
+    ///
+    /// A [`char`] is never split, so if a wide [`char`] doesn't fit in the
+    /// remaining cells, then the prefix ends before it. Zero-width [`char`]s,
+    /// e.g. combining marks, immediately following the prefix are included.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "a中e\u{301}🌏";
+    /// assert_eq!(text.range_for_width(2), 0..1);
+    /// assert_eq!(text.range_for_width(4), 0..7);
+    /// assert_eq!(text.range_for_width(6), 0..11);
+    /// ```
+    #[cfg(feature = "unicode-width")]
+    #[inline]
+    fn range_for_width(&self, max_cells: usize) -> Range<usize> {
+        let mut cells = 0;
+        let mut end = 0;
+        for (r, _c, width) in self.char_width_ranges() {
+            cells += width;
+            if cells > max_cells {
+                break;
+            }
+            end = r.end;
+        }
+        0..end
+    }
+
+    /// Returns the byte range of the prefix to keep, when truncating the
+    /// text to at most `max_cells` terminal cells including a single cell
+    /// ellipsis, e.g. `"…"`, along with whether the text was truncated.
+    ///
+    /// If the text fits within `max_cells`, then the whole text is kept
+    /// and `false` is returned. Otherwise, the longest prefix fitting within
+    /// `max_cells - 1` cells is kept, see [`range_for_width()`].
+    ///
+    /// See [`truncate_with_ellipsis()`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "a中文🌏";
+    /// assert_eq!(text.truncate_with_ellipsis_width(7), (0..11, false));
+    /// // '文' doesn't fit in the remaining cell
+    /// assert_eq!(text.truncate_with_ellipsis_width(5), (0..4, true));
+    /// ```
+    ///
+    /// [`range_for_width()`]: Self::range_for_width
+    /// [`truncate_with_ellipsis()`]: Self::truncate_with_ellipsis
+    #[cfg(feature = "unicode-width")]
+    #[inline]
+    fn truncate_with_ellipsis_width(&self, max_cells: usize) -> (Range<usize>, bool) {
+        let len = self.char_ranges().as_str().len();
+        if self.width_of_range(..) <= max_cells {
+            (0..len, false)
+        } else {
+            (self.range_for_width(max_cells.saturating_sub(1)), true)
+        }
+    }
+
+    /// Returns an iterator over maximal runs of [`char`]s sharing the same
+    /// [`Script`], producing the start and end byte positions of each run,
+    /// the script, and the substring.
+    ///
+    /// [`char`]s of [`Script::Common`], e.g. spaces, punctuation, and
+    /// emoji, and [`Script::Inherited`], e.g. combining marks, are merged
+    /// into the preceding run, or into the following run at the start.
+    /// See [`ScriptRuns`] for the exact merging rules.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    /// use unicode_script::Script;
+    ///
+    /// let text = "Hello, Мир! 🌏";
+    ///
+    /// let mut runs = text.script_runs();
+    /// assert_eq!(runs.next(), Some((0..7, Script::Latin, "Hello, ")));
+    /// assert_eq!(runs.next(), Some((7..19, Script::Cyrillic, "Мир! 🌏")));
+    /// assert_eq!(runs.next(), None);
+    /// ```
+    ///
+    /// [`Script`]: unicode_script::Script
+    /// [`Script::Common`]: unicode_script::Script::Common
+    /// [`Script::Inherited`]: unicode_script::Script::Inherited
+    #[cfg(feature = "unicode-script")]
+    #[inline]
+    fn script_runs(&self) -> ScriptRuns<'_> {
+        self.char_ranges().script_runs()
+    }
+
+    /// Returns the [`Script`] of the [`char`]s within the byte `range`,
+    /// or `None` if they have different scripts.
+    ///
+    /// [`char`]s of [`Script::Common`] and [`Script::Inherited`] are
+    /// compatible with any script, see [`script_runs()`]. If `range` only
+    /// contains such [`char`]s, or is empty, then [`Script::Common`] is
+    /// returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start or end of `range` is out of bounds or is not
+    /// on a [`char`] boundary, or if the start is after the end.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    /// use unicode_script::Script;
+    ///
+    /// let text = "Hello, Мир!";
+    /// assert_eq!(text.script_of_range(0..7), Some(Script::Latin));
+    /// assert_eq!(text.script_of_range(5..), Some(Script::Cyrillic));
+    /// assert_eq!(text.script_of_range(..), None);
+    /// assert_eq!(text.script_of_range(5..7), Some(Script::Common));
+    /// ```
+    ///
+    /// [`Script`]: unicode_script::Script
+    /// [`Script::Common`]: unicode_script::Script::Common
+    /// [`Script::Inherited`]: unicode_script::Script::Inherited
+    /// [`script_runs()`]: Self::script_runs
+    #[cfg(feature = "unicode-script")]
+    #[inline]
+    #[track_caller]
+    fn script_of_range<R>(&self, range: R) -> Option<unicode_script::Script>
+    where
+        R: RangeBounds<usize>,
+    {
+        let mut script = None;
+        for (_r, c) in self.char_ranges_in(range) {
+            match (script::specific_script(c), script) {
+                (Some(next), Some(script)) if next != script => return None,
+                (Some(next), None) => script = Some(next),
+                _ => {}
+            }
+        }
+        Some(script.unwrap_or(unicode_script::Script::Common))
+    }
+
+    /// Returns an iterator over the [`char`]s, which are not valid in an
+    /// identifier according to [UAX #31], producing their start and end
+    /// byte positions, along with why they are invalid, e.g. for error
+    /// carets in a lexer.
+    ///
+    /// The first [`char`] must be `XID_Start`, and the following [`char`]s
+    /// must be `XID_Continue`. Since `'_'` is not `XID_Start`, it is only
+    /// valid as the first [`char`] if `underscore_start` is `true`.
+    ///
+    /// See [`InvalidIdentCharRanges`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{CharRangesExt, IdentError};
+    ///
+    /// let mut invalid = "1st-🌏".invalid_ident_char_ranges(false);
+    /// assert_eq!(invalid.next(), Some((0..1, '1', IdentError::InvalidStart)));
+    /// assert_eq!(invalid.next(), Some((3..4, '-', IdentError::InvalidContinue)));
+    /// assert_eq!(invalid.next(), Some((4..8, '🌏', IdentError::InvalidContinue)));
+    /// assert_eq!(invalid.next(), None);
+    ///
+    /// let mut invalid = "_foo".invalid_ident_char_ranges(false);
+    /// assert_eq!(invalid.next(), Some((0..1, '_', IdentError::InvalidStart)));
+    ///
+    /// let mut invalid = "_foo".invalid_ident_char_ranges(true);
+    /// assert_eq!(invalid.next(), None);
+    /// ```
+    ///
+    /// [UAX #31]: https://www.unicode.org/reports/tr31/
+    #[cfg(feature = "unicode-ident")]
+    #[inline]
+    fn invalid_ident_char_ranges(&self, underscore_start: bool) -> InvalidIdentCharRanges<'_> {
+        self.char_ranges().invalid_ident_chars(underscore_start)
+    }
+
+    /// Returns `true` if the text is a valid identifier according to
+    /// [UAX #31], i.e. if it is not empty and
+    /// [`invalid_ident_char_ranges()`] produces nothing.
+    ///
+    /// Note that this does not check for keywords, e.g. Rust does not
+    /// allow `_` alone as an identifier, even though it is valid if
+    /// `underscore_start` is `true`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// assert!("café".is_valid_identifier(false));
+    /// assert!("_private".is_valid_identifier(true));
+    /// assert!(!"_private".is_valid_identifier(false));
+    /// assert!(!"2fast".is_valid_identifier(true));
+    /// assert!(!"".is_valid_identifier(true));
+    /// ```
+    ///
+    /// [UAX #31]: https://www.unicode.org/reports/tr31/
+    /// [`invalid_ident_char_ranges()`]: Self::invalid_ident_char_ranges
+    #[cfg(feature = "unicode-ident")]
+    #[inline]
+    fn is_valid_identifier(&self, underscore_start: bool) -> bool {
+        let mut invalid = self.invalid_ident_char_ranges(underscore_start);
+        !invalid.as_str().is_empty() && invalid.next().is_none()
+    }
+
+    /// Returns a parallel iterator over [`char`]s and their start and end
+    /// byte positions.
+    ///
+    /// The text is recursively split at the [`char`] boundary nearest its
+    /// midpoint, such that no [`char`] is ever split across tasks. Items are
+    /// produced in whatever order rayon produces them.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    /// use rayon::prelude::*;
+    ///
+    /// let text = "Hello 🗻∈🌏 World";
+    ///
+    /// let mut chars = text
+    ///     .par_char_ranges()
+    ///     .filter(|(_r, c)| !c.is_ascii())
+    ///     .collect::<Vec<_>>();
+    /// chars.sort_by_key(|(r, _c)| r.start);
+    ///
+    /// assert_eq!(chars, [(6..10, '🗻'), (10..13, '∈'), (13..17, '🌏')]);
+    /// ```
+    #[cfg(feature = "rayon")]
+    #[inline]
+    fn par_char_ranges(&self) -> ParCharRanges<'_> {
+        ParCharRanges::new(self.char_ranges_offset(0))
+    }
+
+    /// Returns a parallel iterator over maximal runs of [`char`]s, where
+    /// each run either entirely matches `pred` or entirely doesn't.
+    ///
+    /// The text is only split at the run boundary nearest its midpoint,
+    /// such that the produced runs are the same as for
+    /// [`CharRanges::char_runs()`]. Finding a run boundary scans from the
+    /// midpoint, so texts with very long runs split less evenly. Items are
+    /// produced in whatever order rayon produces them.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    /// use rayon::prelude::*;
+    ///
+    /// let text = "foo 123  bar";
+    ///
+    /// let mut runs = text
+    ///     .par_char_runs(char::is_whitespace)
+    ///     .filter(|(_r, _run, matched)| !matched)
+    ///     .collect::<Vec<_>>();
+    /// runs.sort_by_key(|(r, _run, _matched)| r.start);
+    ///
+    /// assert_eq!(runs, [(0..3, "foo", false), (4..7, "123", false), (9..12, "bar", false)]);
+    /// ```
+    #[cfg(feature = "rayon")]
+    #[inline]
+    fn par_char_runs<P>(&self, pred: P) -> ParCharRuns<'_, P>
+    where
+        P: Fn(char) -> bool + Sync + Send,
+    {
+        ParCharRuns::new(self.char_ranges_offset(0), pred)
+    }
+
+    /// Returns the [`char`] containing the byte position `idx`, along
+    /// with its start and end byte positions.
+    ///
+    /// `idx` can be either the start of the [`char`] or any of its
+    /// continuation bytes. Returns `None` if `idx >= len`.
+    ///
+    /// This is _O(1)_, i.e. the text is not scanned from the start,
+    /// only at most 3 bytes before `idx` are checked.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "a🌏b";
+    /// assert_eq!(text.char_range_at(0), Some((0..1, 'a')));
+    /// assert_eq!(text.char_range_at(1), Some((1..5, '🌏')));
+    /// assert_eq!(text.char_range_at(3), Some((1..5, '🌏')));
+    /// assert_eq!(text.char_range_at(5), Some((5..6, 'b')));
+    /// assert_eq!(text.char_range_at(6), None);
+    /// ```
+    #[inline]
+    fn char_range_at(&self, idx: usize) -> Option<(Range<usize>, char)> {
+        let text = self.char_ranges().as_str();
+        if idx >= text.len() {
+            return None;
+        }
+
+        let mut start = idx;
+        while !text.is_char_boundary(start) {
+            start -= 1;
+        }
+
+        let c = text[start..].chars().next()?;
+        Some((start..(start + c.len_utf8()), c))
+    }
+
+    /// Returns the [`char`] immediately before the byte position `idx`,
+    /// along with its start and end byte positions.
+    ///
+    /// If `idx` is a [`char`] boundary, then this is the [`char`] ending
+    /// at `idx`. Otherwise, `idx` is within a [`char`], and this is the
+    /// [`char`] containing `idx - 1`, i.e. the [`char`] containing `idx`.
+    ///
+    /// Returns `None` if `idx` is `0` or `idx > len`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "a🌏b";
+    /// assert_eq!(text.char_before(0), None);
+    /// assert_eq!(text.char_before(1), Some((0..1, 'a')));
+    /// assert_eq!(text.char_before(5), Some((1..5, '🌏')));
+    /// assert_eq!(text.char_before(6), Some((5..6, 'b')));
+    ///
+    /// // Within '🌏'
+    /// assert_eq!(text.char_before(3), Some((1..5, '🌏')));
+    /// ```
+    #[inline]
+    fn char_before(&self, idx: usize) -> Option<(Range<usize>, char)> {
+        let text = self.char_ranges().as_str();
+        if (idx == 0) || (idx > text.len()) {
+            return None;
+        }
+        text.char_range_at(idx - 1)
+    }
+
+    /// Returns the [`char`] immediately after the byte position `idx`,
+    /// along with its start and end byte positions.
+    ///
+    /// If `idx` is a [`char`] boundary, then this is the [`char`] starting
+    /// at `idx`. Otherwise, `idx` is within a [`char`], and this is the
+    /// [`char`] following it, i.e. the first [`char`] starting after `idx`.
+    ///
+    /// Returns `None` if there is no such [`char`], i.e. if `idx >= len`
+    /// or if `idx` is within the last [`char`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "a🌏b";
+    /// assert_eq!(text.char_after(0), Some((0..1, 'a')));
+    /// assert_eq!(text.char_after(1), Some((1..5, '🌏')));
+    /// assert_eq!(text.char_after(5), Some((5..6, 'b')));
+    /// assert_eq!(text.char_after(6), None);
+    ///
+    /// // Within '🌏'
+    /// assert_eq!(text.char_after(3), Some((5..6, 'b')));
+    /// ```
+    #[inline]
+    fn char_after(&self, idx: usize) -> Option<(Range<usize>, char)> {
+        let text = self.char_ranges().as_str();
+        if idx >= text.len() {
+            return None;
+        }
+
+        let mut start = idx;
+        while !text.is_char_boundary(start) {
+            start += 1;
+        }
+
+        let c = text[start..].chars().next()?;
+        Some((start..(start + c.len_utf8()), c))
+    }
+
+    /// Returns the closest [`char`] boundary at or before the byte
+    /// position `idx`.
+    ///
+    /// If `idx >= len`, then `len` is returned.
+    ///
+    /// This is the same as [`str::floor_char_boundary()`], which is only
+    /// stable since Rust 1.91. Note that for `str` and `String` the inherent
+    /// method takes precedence when using method call syntax.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "a🌏b";
+    /// assert_eq!(CharRangesExt::floor_char_boundary(text, 1), 1);
+    /// assert_eq!(CharRangesExt::floor_char_boundary(text, 3), 1);
+    /// assert_eq!(CharRangesExt::floor_char_boundary(text, 5), 5);
+    /// assert_eq!(CharRangesExt::floor_char_boundary(text, 100), 6);
+    /// ```
+    #[inline]
+    fn floor_char_boundary(&self, idx: usize) -> usize {
+        self.floor_char_boundary_range(idx).0
+    }
+
+    /// Returns the closest [`char`] boundary at or after the byte
+    /// position `idx`.
+    ///
+    /// If `idx > len`, then `len` is returned.
+    ///
+    /// This is the same as [`str::ceil_char_boundary()`], which is only
+    /// stable since Rust 1.91. Note that for `str` and `String` the inherent
+    /// method takes precedence when using method call syntax.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "a🌏b";
+    /// assert_eq!(CharRangesExt::ceil_char_boundary(text, 1), 1);
+    /// assert_eq!(CharRangesExt::ceil_char_boundary(text, 3), 5);
+    /// assert_eq!(CharRangesExt::ceil_char_boundary(text, 5), 5);
+    /// assert_eq!(CharRangesExt::ceil_char_boundary(text, 100), 6);
+    /// ```
+    #[inline]
+    fn ceil_char_boundary(&self, idx: usize) -> usize {
+        self.ceil_char_boundary_range(idx).0
+    }
+
+    /// Returns the closest [`char`] boundary at or before the byte
+    /// position `idx`, along with the [`char`] and its start and end byte
+    /// positions, if `idx` is within the [`char`], i.e. if `idx` is not
+    /// a [`char`] boundary.
+    ///
+    /// See [`floor_char_boundary()`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "a🌏b";
+    /// assert_eq!(text.floor_char_boundary_range(1), (1, None));
+    /// assert_eq!(text.floor_char_boundary_range(3), (1, Some((1..5, '🌏'))));
+    /// assert_eq!(text.floor_char_boundary_range(100), (6, None));
+    /// ```
+    ///
+    /// [`floor_char_boundary()`]: CharRangesExt::floor_char_boundary
+    #[inline]
+    fn floor_char_boundary_range(&self, idx: usize) -> (usize, Option<(Range<usize>, char)>) {
+        let text = self.char_ranges().as_str();
+        if text.is_char_boundary(idx) {
+            (idx, None)
+        } else if idx > text.len() {
+            (text.len(), None)
+        } else {
+            let (r, c) = text.char_range_at(idx).unwrap();
+            (r.start, Some((r, c)))
+        }
+    }
+
+    /// Returns the closest [`char`] boundary at or after the byte
+    /// position `idx`, along with the [`char`] and its start and end byte
+    /// positions, if `idx` is within the [`char`], i.e. if `idx` is not
+    /// a [`char`] boundary.
+    ///
+    /// See [`ceil_char_boundary()`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "a🌏b";
+    /// assert_eq!(text.ceil_char_boundary_range(1), (1, None));
+    /// assert_eq!(text.ceil_char_boundary_range(3), (5, Some((1..5, '🌏'))));
+    /// assert_eq!(text.ceil_char_boundary_range(100), (6, None));
+    /// ```
+    ///
+    /// [`ceil_char_boundary()`]: CharRangesExt::ceil_char_boundary
+    #[inline]
+    fn ceil_char_boundary_range(&self, idx: usize) -> (usize, Option<(Range<usize>, char)>) {
+        let text = self.char_ranges().as_str();
+        if text.is_char_boundary(idx) {
+            (idx, None)
+        } else if idx > text.len() {
+            (text.len(), None)
+        } else {
+            let (r, c) = text.char_range_at(idx).unwrap();
+            (r.end, Some((r, c)))
+        }
+    }
+
+    /// Returns `true` if `range` is within bounds, not reversed, and
+    /// both its start and end are on [`char`] boundaries, i.e. if slicing
+    /// the text with `range` would not panic.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "a🌏b";
+    /// assert!(text.is_boundary_range(&(1..5)));
+    /// assert!(text.is_boundary_range(&(6..6)));
+    ///
+    /// assert!(!text.is_boundary_range(&(1..3)));
+    /// assert!(!text.is_boundary_range(&(5..7)));
+    /// ```
+    #[inline]
+    fn is_boundary_range(&self, range: &Range<usize>) -> bool {
+        let text = self.char_ranges().as_str();
+        (range.start <= range.end)
+            && text.is_char_boundary(range.start)
+            && text.is_char_boundary(range.end)
+    }
+
+    /// Returns `range` aligned to [`char`] boundaries, where `mode`
+    /// determines whether the range shrinks or grows, see [`Snap`].
+    ///
+    /// An end past the end of the text is clamped to the end of the text.
+    ///
+    /// With [`Snap::Inner`], a range strictly inside a single [`char`]
+    /// collapses to an empty range at the start of the [`char`].
+    ///
+    /// Returns `None` if `range` is reversed, or if the start of `range`
+    /// is out of bounds, i.e. `range` is entirely out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{CharRangesExt, Snap};
+    ///
+    /// let text = "a🌏b∈";
+    /// assert_eq!(text.snap_range(2..6, Snap::Inner), Some(5..6));
+    /// assert_eq!(text.snap_range(2..6, Snap::Outer), Some(1..6));
+    ///
+    /// assert_eq!(text.snap_range(2..3, Snap::Inner), Some(1..1));
+    /// assert_eq!(text.snap_range(2..3, Snap::Outer), Some(1..5));
+    ///
+    /// assert_eq!(text.snap_range(7..100, Snap::Outer), Some(6..9));
+    /// assert_eq!(text.snap_range(10..100, Snap::Outer), None);
+    /// ```
+    #[inline]
+    fn snap_range(&self, range: Range<usize>, mode: Snap) -> Option<Range<usize>> {
+        let text = self.char_ranges().as_str();
+        if (range.start > range.end) || (range.start > text.len()) {
+            return None;
+        }
+
+        let end = range.end.min(text.len());
+        match mode {
+            Snap::Inner => {
+                let start = CharRangesExt::ceil_char_boundary(text, range.start);
+                let end = CharRangesExt::floor_char_boundary(text, end);
+                if start <= end {
+                    Some(start..end)
+                } else {
+                    Some(end..end)
+                }
+            }
+            Snap::Outer => {
+                let start = CharRangesExt::floor_char_boundary(text, range.start);
+                let end = CharRangesExt::ceil_char_boundary(text, end);
+                Some(start..end)
+            }
+        }
+    }
+
+    /// Returns the [`char`] index of the byte position `byte`, i.e. the
+    /// number of [`char`]s before it.
+    ///
+    /// Returns `None` if `byte` is not a [`char`] boundary or `byte > len`.
+    /// The end of the text, i.e. `byte == len`, is the number of [`char`]s.
+    ///
+    /// This is _O(n)_, but only counts the leading bytes of [`char`]s,
+    /// i.e. [`char`]s are not decoded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻∈🌏";
+    /// assert_eq!(text.byte_to_char_index(0), Some(0));
+    /// assert_eq!(text.byte_to_char_index(4), Some(1));
+    /// assert_eq!(text.byte_to_char_index(7), Some(2));
+    /// assert_eq!(text.byte_to_char_index(11), Some(3));
+    ///
+    /// // Not a char boundary
+    /// assert_eq!(text.byte_to_char_index(5), None);
+    /// // Out of bounds
+    /// assert_eq!(text.byte_to_char_index(12), None);
+    /// ```
+    #[inline]
+    fn byte_to_char_index(&self, byte: usize) -> Option<usize> {
+        let text = self.char_ranges().as_str();
+        if text.is_char_boundary(byte) {
+            Some(count_chars(&text[..byte]))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the number of [`char`]s within the byte `range`.
+    ///
+    /// Returns `None` if `range` is out of bounds, reversed, or if its
+    /// start or end is not a [`char`] boundary, i.e. if slicing the text
+    /// with `range` would panic.
+    ///
+    /// This is the same as <code>text\[range\].[chars()].[count()]</code>,
+    /// except that only the leading bytes of [`char`]s are counted, i.e.
+    /// [`char`]s are not decoded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻∈🌏 World";
+    /// assert_eq!(text.count_chars_in_range(4..11), Some(2));
+    /// assert_eq!(text.count_chars_in_range(..7), Some(2));
+    /// assert_eq!(text.count_chars_in_range(11..), Some(6));
+    /// assert_eq!(text.count_chars_in_range(..), Some(9));
+    ///
+    /// // Not a char boundary
+    /// assert_eq!(text.count_chars_in_range(5..11), None);
+    /// // Out of bounds
+    /// assert_eq!(text.count_chars_in_range(11..=17), None);
+    /// ```
+    ///
+    /// [chars()]: str::chars
+    /// [count()]: Iterator::count
+    #[inline]
+    fn count_chars_in_range<R>(&self, range: R) -> Option<usize>
+    where
+        R: RangeBounds<usize>,
+    {
+        let text = self.char_ranges().as_str();
+        let range = bounds_to_range(range, text.len());
+        text.get(range).map(count_chars)
+    }
+
+    /// Returns an iterator over the [`char`] boundaries of the text, i.e.
+    /// the byte positions where [`char`]s start, including `0` and the
+    /// length of the text.
+    ///
+    /// Both `0` and the length are produced exactly once, i.e. an empty
+    /// text produces the single position `0`. Pairing adjacent boundaries
+    /// produces the ranges of [`char_ranges()`](Self::char_ranges).
+    ///
+    /// This only scans the leading bytes of [`char`]s, i.e. [`char`]s are
+    /// not decoded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "a🗻b∈";
+    ///
+    /// let mut boundaries = text.char_boundaries();
+    /// assert_eq!(boundaries.next(), Some(0));
+    /// assert_eq!(boundaries.next(), Some(1));
+    /// assert_eq!(boundaries.next_back(), Some(9));
+    /// assert_eq!(boundaries.next_back(), Some(6));
+    /// assert_eq!(boundaries.next(), Some(5));
+    /// assert_eq!(boundaries.next(), None);
+    ///
+    /// assert!("".char_boundaries().eq([0]));
+    /// ```
+    #[inline]
+    fn char_boundaries(&self) -> CharBoundaries<'_> {
+        self.char_ranges().boundaries()
+    }
+
+    /// Returns an iterator over the bytes, producing the byte position of
+    /// each byte, the byte, and the start and end byte positions of the
+    /// [`char`] it belongs to.
+    ///
+    /// This is useful for byte-by-byte scanners, which occasionally need
+    /// the whole [`char`] a byte belongs to. All bytes of a multi-byte
+    /// [`char`] share the same range, which is computed once per [`char`].
+    ///
+    /// See [`BytesWithCharRanges`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "a∈";
+    ///
+    /// let mut bytes = text.bytes_with_char_ranges();
+    /// assert_eq!(bytes.next(), Some((0, b'a', 0..1)));
+    /// assert_eq!(bytes.next(), Some((1, 0xE2, 1..4)));
+    /// assert_eq!(bytes.next(), Some((2, 0x88, 1..4)));
+    /// assert_eq!(bytes.next(), Some((3, 0x88, 1..4)));
+    /// assert_eq!(bytes.next(), None);
+    /// ```
+    #[inline]
+    fn bytes_with_char_ranges(&self) -> BytesWithCharRanges<'_> {
+        self.char_ranges().bytes_with_char_ranges()
+    }
+
+    /// Returns the number of [`char`] boundaries of the text, i.e. the
+    /// number of [`char`]s plus one.
+    ///
+    /// This is the same as <code>[char_boundaries()].[count()]</code>,
+    /// except that only the leading bytes of [`char`]s are counted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// assert_eq!("a🗻b∈".boundary_count(), 5);
+    /// assert_eq!("".boundary_count(), 1);
+    /// ```
+    ///
+    /// [char_boundaries()]: Self::char_boundaries
+    /// [count()]: Iterator::count
+    #[inline]
+    fn boundary_count(&self) -> usize {
+        count_chars(self.char_ranges().as_str()) + 1
+    }
+
+    /// Returns the [`char`] at [`char`] index `n` and its start and end
+    /// byte positions, i.e. the same as <code>[char_ranges()].[nth(n)]</code>.
+    ///
+    /// Returns `None` if there are `n` or fewer [`char`]s.
+    ///
+    /// This is _O(n)_, but only counts the leading bytes of the skipped
+    /// [`char`]s, i.e. they are not decoded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻∈🌏";
+    /// assert_eq!(text.char_index_to_byte_range(0), Some((0..4, '🗻')));
+    /// assert_eq!(text.char_index_to_byte_range(2), Some((7..11, '🌏')));
+    /// assert_eq!(text.char_index_to_byte_range(3), None);
+    /// ```
+    ///
+    /// [char_ranges()]: CharRangesExt::char_ranges
+    /// [nth(n)]: Iterator::nth
+    #[inline]
+    fn char_index_to_byte_range(&self, n: usize) -> Option<(Range<usize>, char)> {
+        let text = self.char_ranges().as_str();
+        let start = skip_chars(text.as_bytes(), n).ok()?;
+        let c = text[start..].chars().next()?;
+        Some((start..(start + c.len_utf8()), c))
+    }
+
+    /// Returns the byte range and substring of the longest prefix, which
+    /// is at most `max_bytes` long, without splitting a [`char`].
+    ///
+    /// The range is always `0..k`, where `k <= max_bytes`. If the text
+    /// fits, i.e. `max_bytes >= len`, then the whole text is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "ab🌏c";
+    /// assert_eq!(text.truncate_to_boundary(2), (0..2, "ab"));
+    /// assert_eq!(text.truncate_to_boundary(5), (0..2, "ab"));
+    /// assert_eq!(text.truncate_to_boundary(6), (0..6, "ab🌏"));
+    /// assert_eq!(text.truncate_to_boundary(100), (0..7, "ab🌏c"));
+    /// ```
+    #[inline]
+    fn truncate_to_boundary(&self, max_bytes: usize) -> (Range<usize>, &str) {
+        let text = self.char_ranges().as_str();
+        let (end, _) = text.floor_char_boundary_range(max_bytes);
+        (0..end, &text[..end])
+    }
+
+    /// Returns the byte range and substring of the longest suffix, which
+    /// is at most `max_bytes` long, without splitting a [`char`].
+    ///
+    /// The range is always `k..len`, where `len - k <= max_bytes`. If the
+    /// text fits, i.e. `max_bytes >= len`, then the whole text is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "a🌏bc";
+    /// assert_eq!(text.truncate_back_to_boundary(2), (5..7, "bc"));
+    /// assert_eq!(text.truncate_back_to_boundary(5), (5..7, "bc"));
+    /// assert_eq!(text.truncate_back_to_boundary(6), (1..7, "🌏bc"));
+    /// assert_eq!(text.truncate_back_to_boundary(100), (0..7, "a🌏bc"));
+    /// ```
+    #[inline]
+    fn truncate_back_to_boundary(&self, max_bytes: usize) -> (Range<usize>, &str) {
+        let text = self.char_ranges().as_str();
+        let start = text.len().saturating_sub(max_bytes);
+        let (start, _) = text.ceil_char_boundary_range(start);
+        (start..text.len(), &text[start..])
+    }
+
+    /// Returns the byte range of the prefix to keep, when truncating the
+    /// text to at most `max_chars` [`char`]s including an ellipsis, e.g.
+    /// `"…"`, along with whether the text was truncated.
+    ///
+    /// If the text contains at most `max_chars` [`char`]s, then the whole
+    /// text is kept and `false` is returned. Otherwise, the first
+    /// `max_chars - 1` [`char`]s are kept, leaving room for the ellipsis,
+    /// i.e. at least 2 [`char`]s are dropped, so the ellipsis always
+    /// replaces text. If `max_chars` is `0`, then nothing is kept.
+    ///
+    /// The range is always `0..k`, where `k` is a [`char`] boundary, and
+    /// `k..len` is the truncated text, e.g. for showing it in a tooltip.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "Hello 🌏";
+    /// assert_eq!(text.truncate_with_ellipsis(7), (0..10, false));
+    ///
+    /// let (r, truncated) = text.truncate_with_ellipsis(6);
+    /// assert_eq!((r.clone(), truncated), (0..5, true));
+    /// assert_eq!(format!("{}…", &text[r]), "Hello…");
+    /// ```
+    #[inline]
+    fn truncate_with_ellipsis(&self, max_chars: usize) -> (Range<usize>, bool) {
+        let text = self.char_ranges().as_str().as_bytes();
+        match skip_chars(text, max_chars) {
+            Ok(len) if len == text.len() => (0..len, false),
+            Err(_) => (0..text.len(), false),
+            Ok(_) => {
+                let len = skip_chars(text, max_chars.saturating_sub(1)).unwrap_or(0);
+                (0..len, true)
+            }
+        }
+    }
+
+    /// Returns the byte ranges of the head and tail to keep, when
+    /// truncating the middle of the text to at most `max_chars` [`char`]s
+    /// including an ellipsis, e.g. `"…"`, along with whether the text was
+    /// truncated, e.g. for displaying a long file path.
+    ///
+    /// If the text contains at most `max_chars` [`char`]s, then the head
+    /// is the whole text, the tail is empty at the end, and `false` is
+    /// returned. Otherwise, `max_chars - 1` [`char`]s are kept, where the
+    /// tail gets the extra [`char`] if they can't be split evenly.
+    ///
+    /// The head is always `0..h`, the tail `t..len`, and `h..t` is the
+    /// truncated text, where `h` and `t` are [`char`] boundaries.
+    ///
+    /// See [`truncate_with_ellipsis()`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let path = "/home/🗻/src/lib.rs";
+    ///
+    /// let (head, tail, truncated) = path.truncate_middle_with_ellipsis(12);
+    /// assert_eq!((head.clone(), tail.clone(), truncated), (0..5, 15..21, true));
+    /// assert_eq!(format!("{}…{}", &path[head], &path[tail]), "/home…lib.rs");
+    ///
+    /// assert_eq!(path.truncate_middle_with_ellipsis(18), (0..21, 21..21, false));
+    /// ```
+    ///
+    /// [`truncate_with_ellipsis()`]: Self::truncate_with_ellipsis
+    fn truncate_middle_with_ellipsis(
+        &self,
+        max_chars: usize,
+    ) -> (Range<usize>, Range<usize>, bool) {
+        let text = self.char_ranges().as_str().as_bytes();
+        let (r, truncated) = self.truncate_with_ellipsis(max_chars);
+        if !truncated {
+            return (r, text.len()..text.len(), false);
+        }
+
+        let keep = max_chars.saturating_sub(1);
+        let head = skip_chars(text, keep / 2).unwrap_or(0);
+        let tail = skip_chars_back(text, keep - (keep / 2)).unwrap_or(0);
+        (0..head, (text.len() - tail)..text.len(), true)
+    }
+
+    /// Returns the byte range and substring of the last `n` [`char`]s.
+    ///
+    /// If the text contains fewer than `n` [`char`]s, then the whole
+    /// text is returned.
+    ///
+    /// Only the last `n` [`char`]s are scanned, backwards from the end,
+    /// e.g. for showing the context preceding an error at the end
+    /// of a large text.
+    ///
+    /// See [`CharRanges::last_n()`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "Hello 🗻∈🌏";
+    /// assert_eq!(text.last_n(2), (10..17, "∈🌏"));
+    /// assert_eq!(text.last_n(4), (5..17, " 🗻∈🌏"));
+    /// assert_eq!(text.last_n(0), (17..17, ""));
+    /// assert_eq!(text.last_n(100), (0..17, "Hello 🗻∈🌏"));
+    /// ```
+    #[inline]
+    fn last_n(&self, n: usize) -> (Range<usize>, &str) {
+        self.char_ranges().last_n(n)
+    }
+
+    /// Returns an iterator over the last `n` [`char`]s and their start
+    /// and end byte positions, in order from front to back.
+    ///
+    /// If the text contains fewer than `n` [`char`]s, then the iterator
+    /// produces all of them.
+    ///
+    /// See [`CharRanges::last_n_ranges()`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "Hello 🗻∈🌏";
+    ///
+    /// let mut chars = text.last_n_ranges(2);
+    /// assert_eq!(chars.next(), Some((10..13, '∈')));
+    /// assert_eq!(chars.next(), Some((13..17, '🌏')));
+    /// assert_eq!(chars.next(), None);
+    /// ```
+    #[inline]
+    fn last_n_ranges(&self, n: usize) -> CharRanges<'_> {
+        self.char_ranges().last_n_ranges(n)
+    }
+
+    /// Returns the text within `range`, along with at most `n` [`char`]s
+    /// of context before and after it, e.g. for displaying an error as
+    /// `…lo [🌏] Wo…`.
+    ///
+    /// The `range` is snapped outwards to the nearest [`char`] boundaries,
+    /// and the context never splits a [`char`]. Whether the context was
+    /// truncated on either side is also reported, see [`ContextAround`].
+    ///
+    /// Returns `None` if `range` is reversed, or starts out of bounds.
+    /// An end out of bounds is clamped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "Hello 🌏 World";
+    ///
+    /// let ctx = text.context_around(6..10, 3).unwrap();
+    /// assert_eq!(ctx.range, 3..13);
+    /// assert_eq!((ctx.before, ctx.span, ctx.after), ("lo ", "🌏", " Wo"));
+    /// assert!(ctx.truncated_before && ctx.truncated_after);
+    ///
+    /// let ctx = text.context_around(0..5, 3).unwrap();
+    /// assert_eq!((ctx.before, ctx.span, ctx.after), ("", "Hello", " 🌏 "));
+    /// assert!(!ctx.truncated_before && ctx.truncated_after);
+    /// ```
+    #[inline]
+    fn context_around(&self, range: Range<usize>, n: usize) -> Option<ContextAround<'_>> {
+        let range = self.snap_range(range, Snap::Outer)?;
+        let text = self.char_ranges().as_str();
+        Some(ContextAround::new(text, range, n))
+    }
+
+    /// Returns the byte range and substring remaining after removing
+    /// leading and trailing whitespace, as defined by [`char::is_whitespace()`].
+    ///
+    /// If the text is empty or only contains whitespace, then the
+    /// range is empty and positioned at the end of the text, i.e.
+    /// `len..len`, the same as [`trim_start_range()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "  Hello 🌏\u{3000}";
+    /// assert_eq!(text.trim_range(), (2..12, "Hello 🌏"));
+    ///
+    /// let text = "   ";
+    /// assert_eq!(text.trim_range(), (3..3, ""));
+    /// ```
+    ///
+    /// [`trim_start_range()`]: CharRangesExt::trim_start_range
+    #[inline]
+    fn trim_range(&self) -> (Range<usize>, &str) {
+        let (r, text) = self.trim_start_range();
+        let text = text.trim_end();
+        (r.start..(r.start + text.len()), text)
+    }
+
+    /// Returns the byte range and substring remaining after removing
+    /// leading whitespace, as defined by [`char::is_whitespace()`].
+    ///
+    /// If the text is empty or only contains whitespace, then the
+    /// range is empty and positioned at the end of the text, i.e.
+    /// `len..len`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "\u{A0} Hello ";
+    /// assert_eq!(text.trim_start_range(), (3..9, "Hello "));
+    /// ```
+    #[inline]
+    fn trim_start_range(&self) -> (Range<usize>, &str) {
+        let text = self.char_ranges().as_str();
+        let trimmed = text.trim_start();
+        ((text.len() - trimmed.len())..text.len(), trimmed)
+    }
+
+    /// Returns the byte range and substring remaining after removing
+    /// trailing whitespace, as defined by [`char::is_whitespace()`].
+    ///
+    /// If the text is empty or only contains whitespace, then the
+    /// range is empty and positioned at the start of the text, i.e.
+    /// `0..0`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = " Hello \u{A0}";
+    /// assert_eq!(text.trim_end_range(), (0..6, " Hello"));
+    /// ```
+    #[inline]
+    fn trim_end_range(&self) -> (Range<usize>, &str) {
+        let trimmed = self.char_ranges().as_str().trim_end();
+        (0..trimmed.len(), trimmed)
+    }
+
+    /// Returns the byte range and substring remaining after repeatedly
+    /// removing leading and trailing matches of `pat`.
+    ///
+    /// See [`CharPattern`] for the kinds of patterns, e.g. a [`char`],
+    /// a `&str`, or a predicate.
+    ///
+    /// If the whole text is matched, then the range is empty and
+    /// positioned at the end of the text, i.e. `len..len`, the same
+    /// as [`trim_range()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "“Hello”";
+    /// assert_eq!(text.trim_matches_range(|c| c == '“' || c == '”'), (3..8, "Hello"));
+    /// assert_eq!(text.trim_matches_range(['“', '”']), (3..8, "Hello"));
+    ///
+    /// let text = "[[[]]]";
+    /// assert_eq!(text.trim_matches_range(|c| c == '[' || c == ']'), (6..6, ""));
+    /// ```
+    ///
+    /// [`trim_range()`]: CharRangesExt::trim_range
+    #[inline]
+    fn trim_matches_range<P>(&self, mut pat: P) -> (Range<usize>, &str)
+    where
+        P: CharPattern,
+    {
+        let text = self.char_ranges().as_str();
+        let start = pattern::trim_start_len(text, &mut pat);
+        let end = text.len() - pattern::trim_end_len(&text[start..], &mut pat);
+        (start..end, &text[start..end])
+    }
+
+    /// Returns the byte range and substring remaining after repeatedly
+    /// removing leading matches of `pat`.
+    ///
+    /// If the whole text is matched, then the range is empty and
+    /// positioned at the end of the text, i.e. `len..len`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "// Comment";
+    /// assert_eq!(text.trim_start_matches_range(|c| c == '/'), (2..10, " Comment"));
+    /// assert_eq!(text.trim_start_matches_range("//"), (2..10, " Comment"));
+    /// ```
+    #[inline]
+    fn trim_start_matches_range<P>(&self, mut pat: P) -> (Range<usize>, &str)
+    where
+        P: CharPattern,
+    {
+        let text = self.char_ranges().as_str();
+        let start = pattern::trim_start_len(text, &mut pat);
+        (start..text.len(), &text[start..])
+    }
+
+    /// Returns the byte range and substring remaining after repeatedly
+    /// removing trailing matches of `pat`.
+    ///
+    /// If the whole text is matched, then the range is empty and
+    /// positioned at the start of the text, i.e. `0..0`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "Hello!!!";
+    /// assert_eq!(text.trim_end_matches_range(|c| c == '!'), (0..5, "Hello"));
+    /// assert_eq!(text.trim_end_matches_range('!'), (0..5, "Hello"));
+    /// ```
+    #[inline]
+    fn trim_end_matches_range<P>(&self, mut pat: P) -> (Range<usize>, &str)
+    where
+        P: CharPattern,
+    {
+        let text = self.char_ranges().as_str();
+        let end = text.len() - pattern::trim_end_len(text, &mut pat);
+        (0..end, &text[..end])
+    }
+
+    /// Returns the byte range from the `open` delimiter at the byte
+    /// position `from` to its matching `close` delimiter, including both.
+    ///
+    /// Nested pairs of `open` and `close` are skipped over. If `open` and
+    /// `close` are the same, then the next occurrence is the match.
+    ///
+    /// Returns [`UnbalancedError::NotOpen`] if `from` is not the start of
+    /// an `open` delimiter, and [`UnbalancedError::Unclosed`] if the text
+    /// ends before the matching `close` delimiter.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{CharRangesExt, UnbalancedError};
+    ///
+    /// let text = "f(a, (b), c) + (d";
+    /// assert_eq!(text.find_balanced_range('(', ')', 1), Ok(1..12));
+    /// assert_eq!(text.find_balanced_range('(', ')', 5), Ok(5..8));
+    ///
+    /// assert_eq!(
+    ///     text.find_balanced_range('(', ')', 15),
+    ///     Err(UnbalancedError::Unclosed { end: 17, depth: 1 }),
+    /// );
+    /// assert_eq!(
+    ///     text.find_balanced_range('(', ')', 0),
+    ///     Err(UnbalancedError::NotOpen(0)),
+    /// );
+    /// ```
+    #[inline]
+    fn find_balanced_range(
+        &self,
+        open: char,
+        close: char,
+        from: usize,
+    ) -> Result<Range<usize>, UnbalancedError> {
+        balanced::find_balanced_range(self.char_ranges().as_str(), open, close, from)
+    }
+
+    /// Returns an iterator over the segments of the text partitioned by
+    /// `ranges`, i.e. their start and end byte positions, substrings,
+    /// and whether they are inside one of the `ranges`.
+    ///
+    /// The segments alternate between the `ranges` and the text between
+    /// them, and together tile the whole text in order. Empty ranges are
+    /// skipped, i.e. they don't produce a segment, but are still validated.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `ranges` are not sorted, overlap, are out of
+    /// bounds, or are not on [`char`] boundaries, along with the index of
+    /// the first invalid range.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "The 🌏 is round, the 🌏 is blue";
+    ///
+    /// let matches = text
+    ///     .match_indices("🌏")
+    ///     .map(|(i, s)| i..(i + s.len()))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// let mut highlighted = String::new();
+    /// for (_r, s, inside) in text.segment_by_ranges(&matches).unwrap() {
+    ///     if inside {
+    ///         highlighted.push_str(&format!("[{s}]"));
+    ///     } else {
+    ///         highlighted.push_str(s);
+    ///     }
+    /// }
+    /// assert_eq!(highlighted, "The [🌏] is round, the [🌏] is blue");
+    /// ```
+    #[inline]
+    fn segment_by_ranges<'b>(
+        &self,
+        ranges: &'b [Range<usize>],
+    ) -> Result<SegmentByRanges<'_, 'b>, SegmentError> {
+        SegmentByRanges::new(self.char_ranges().as_str(), ranges)
+    }
+
+    /// Returns the UTF-16 code unit position of the byte position `byte`,
+    /// i.e. the number of UTF-16 code units before it.
+    ///
+    /// Returns `None` if `byte` is not a [`char`] boundary or `byte > len`.
+    ///
+    /// This is _O(n)_, but only looks at the leading bytes of [`char`]s,
+    /// i.e. [`char`]s are not decoded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "a🌏b";
+    /// assert_eq!(text.utf16_offset(1), Some(1));
+    /// assert_eq!(text.utf16_offset(5), Some(3));
+    /// assert_eq!(text.utf16_offset(6), Some(4));
+    ///
+    /// // Within '🌏'
+    /// assert_eq!(text.utf16_offset(2), None);
+    /// ```
+    #[inline]
+    fn utf16_offset(&self, byte: usize) -> Option<usize> {
+        let text = self.char_ranges().as_str();
+        let prefix = text.get(..byte)?;
+        Some(utf16::utf16_len(prefix))
+    }
+
+    /// Returns the byte position of the UTF-16 code unit position `units`.
+    ///
+    /// Returns `None` if `units` is between the two code units of a
+    /// surrogate pair, or `units` is past the UTF-16 length of the text.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "a🌏b";
+    /// assert_eq!(text.byte_offset_from_utf16(1), Some(1));
+    /// assert_eq!(text.byte_offset_from_utf16(3), Some(5));
+    /// assert_eq!(text.byte_offset_from_utf16(4), Some(6));
+    ///
+    /// // Within the surrogate pair of '🌏'
+    /// assert_eq!(text.byte_offset_from_utf16(2), None);
+    /// assert_eq!(text.byte_offset_from_utf16(5), None);
+    /// ```
+    #[inline]
+    fn byte_offset_from_utf16(&self, units: usize) -> Option<usize> {
+        utf16::utf16_to_byte_offset(self.char_ranges().as_str(), units)
+    }
+
+    /// Returns the length in UTF-16 code units of the byte range `range`.
+    ///
+    /// Returns `None` if `range` is out of bounds, reversed, or if its
+    /// start or end is not a [`char`] boundary.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "a🌏b";
+    /// assert_eq!(text.utf16_len_of_range(0..6), Some(4));
+    /// assert_eq!(text.utf16_len_of_range(1..5), Some(2));
+    /// assert_eq!(text.utf16_len_of_range(1..3), None);
+    /// ```
+    #[inline]
+    fn utf16_len_of_range(&self, range: Range<usize>) -> Option<usize> {
+        let text = self.char_ranges().as_str();
+        let text = text.get(range)?;
+        Some(utf16::utf16_len(text))
+    }
+
+    /// Returns the position after the next word at or after the byte
+    /// position `from`, along with the range of the word skipped over.
+    ///
+    /// Whitespace before the word is skipped. If only whitespace remains,
+    /// then `len` is returned along with an empty range. Returns `None`
+    /// if `from >= len`. If `from` is within a [`char`], then it is moved
+    /// back to the start of the [`char`], see [`floor_char_boundary()`].
+    ///
+    /// A word is a run of alphanumeric [`char`]s and `'_'`, or a run of
+    /// other non-whitespace [`char`]s. With the `unicode-segmentation`
+    /// feature enabled, the [Unicode word boundaries] are used instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "let π = 3;";
+    /// assert_eq!(text.next_word_boundary(0), Some((3, 0..3)));
+    /// assert_eq!(text.next_word_boundary(3), Some((6, 4..6)));
+    /// assert_eq!(text.next_word_boundary(6), Some((8, 7..8)));
+    /// ```
+    ///
+    /// [`floor_char_boundary()`]: Self::floor_char_boundary
+    /// [Unicode word boundaries]: https://www.unicode.org/reports/tr29/#Word_Boundaries
+    #[inline]
+    fn next_word_boundary(&self, from: usize) -> Option<(usize, Range<usize>)> {
+        let from = self.floor_char_boundary(from);
+        word::next_word_boundary(self.char_ranges().as_str(), from)
+    }
+
+    /// Returns the start of the previous word before the byte position
+    /// `from`, along with the range of the word skipped over.
+    ///
+    /// Whitespace after the word is skipped. If only whitespace precedes,
+    /// then `0` is returned along with an empty range. Returns `None`
+    /// if `from == 0`.
+    ///
+    /// See [`next_word_boundary()`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "let π = 3;";
+    /// assert_eq!(text.prev_word_boundary(text.len()), Some((10, 10..11)));
+    /// assert_eq!(text.prev_word_boundary(10), Some((9, 9..10)));
+    /// assert_eq!(text.prev_word_boundary(7), Some((4, 4..6)));
+    /// ```
+    ///
+    /// [`next_word_boundary()`]: Self::next_word_boundary
+    #[inline]
+    fn prev_word_boundary(&self, from: usize) -> Option<(usize, Range<usize>)> {
+        let from = self.floor_char_boundary(from);
+        word::prev_word_boundary(self.char_ranges().as_str(), from)
+    }
+
+    /// Returns `range` grown outward to the start and end of the words
+    /// it touches, e.g. for selecting words by double-clicking.
+    ///
+    /// Words are the same as for [`next_word_boundary()`], except runs
+    /// of whitespace are also grown over. An empty `range`, i.e. a caret,
+    /// grows to the word under it, which is the word after the caret,
+    /// unless the caret is at the end of a word and followed by whitespace.
+    ///
+    /// `range` is first snapped outward to [`char`] boundaries, see
+    /// [`snap_range()`]. Returns `None` if `range` is reversed, or if the
+    /// start of `range` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "let wörld = 42;";
+    ///
+    /// // Caret within "wörld"
+    /// assert_eq!(text.expand_to_word(6..6), Some(4..10));
+    /// // Caret at the end of "wörld"
+    /// assert_eq!(text.expand_to_word(10..10), Some(4..10));
+    ///
+    /// assert_eq!(text.expand_to_word(1..5), Some(0..10));
+    /// ```
+    ///
+    /// [`next_word_boundary()`]: Self::next_word_boundary
+    /// [`snap_range()`]: Self::snap_range
+    #[inline]
+    fn expand_to_word(&self, range: Range<usize>) -> Option<Range<usize>> {
+        let range = self.snap_range(range, Snap::Outer)?;
+        Some(word::expand_to_word(self.char_ranges().as_str(), range))
+    }
+
+    /// Returns the byte range and substring of the identifier at the byte
+    /// position `pos`, i.e. the run of [`char`]s around `pos` for which
+    /// `is_ident_char` returns `true`, e.g. for the word under the cursor.
+    ///
+    /// If `pos` is between two identifiers, i.e. the [`char`] before `pos`
+    /// matches but the [`char`] after it doesn't, then the preceding
+    /// identifier is returned, since a caret at the end of an identifier,
+    /// e.g. right after typing it, usually refers to it.
+    ///
+    /// If `pos` is not on a [`char`] boundary, then it is snapped down to
+    /// the start of the [`char`] containing it.
+    ///
+    /// Returns `None` if neither the [`char`] before nor after `pos`
+    /// matches, or if `pos` is out of bounds.
+    ///
+    /// Use [`identifier_at_default()`] for alphanumeric [`char`]s and `'_'`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "(foo-bär + baz)";
+    ///
+    /// let is_ident_char = |c: char| c.is_alphanumeric() || (c == '-');
+    /// assert_eq!(text.identifier_at(6, is_ident_char), Some((1..9, "foo-bär")));
+    ///
+    /// // At the end of "foo-bär"
+    /// assert_eq!(text.identifier_at(9, is_ident_char), Some((1..9, "foo-bär")));
+    /// assert_eq!(text.identifier_at(10, is_ident_char), None);
+    /// ```
+    ///
+    /// [`identifier_at_default()`]: Self::identifier_at_default
+    #[inline]
+    fn identifier_at<F>(&self, pos: usize, is_ident_char: F) -> Option<(Range<usize>, &str)>
+    where
+        F: Fn(char) -> bool,
+    {
+        let text = self.char_ranges().as_str();
+        if pos > text.len() {
+            return None;
+        }
+        let pos = CharRangesExt::floor_char_boundary(text, pos);
+        let r = word::identifier_at(text, pos, is_ident_char)?;
+        Some((r.clone(), &text[r]))
+    }
+
+    /// Returns the byte range and substring of the identifier at the
+    /// byte position `pos`, where identifiers consist of alphanumeric
+    /// [`char`]s and `'_'`.
+    ///
+    /// See [`identifier_at()`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "let wörld = foo_bar;";
+    /// assert_eq!(text.identifier_at_default(6), Some((4..10, "wörld")));
+    /// assert_eq!(text.identifier_at_default(14), Some((13..20, "foo_bar")));
+    /// assert_eq!(text.identifier_at_default(11), None);
+    /// ```
+    ///
+    /// [`identifier_at()`]: Self::identifier_at
+    #[inline]
+    fn identifier_at_default(&self, pos: usize) -> Option<(Range<usize>, &str)> {
+        self.identifier_at(pos, word::is_word_char)
+    }
+
+    /// Returns the byte range, which a single backspace at the caret
+    /// position `pos` should delete.
+    ///
+    /// This is the [`char`] before `pos`, where `"\r\n"` is deleted as a
+    /// whole. With the `unicode-segmentation` feature enabled, it is the
+    /// [extended grapheme cluster] before `pos` instead, e.g. an emoji
+    /// sequence joined by U+200D ZERO WIDTH JOINER is deleted as a whole.
+    ///
+    /// If `pos` is not on a [`char`] boundary, then it is moved back to
+    /// the start of the [`char`], see [`floor_char_boundary()`].
+    ///
+    /// Returns `None` if `pos == 0` or `pos > len`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "a🌏\r\nb";
+    /// assert_eq!(text.backspace_range(5), Some(1..5));
+    /// assert_eq!(text.backspace_range(7), Some(5..7));
+    /// assert_eq!(text.backspace_range(0), None);
+    /// ```
+    ///
+    /// [extended grapheme cluster]: https://www.unicode.org/reports/tr29/#Grapheme_Cluster_Boundaries
+    /// [`floor_char_boundary()`]: Self::floor_char_boundary
+    #[inline]
+    fn backspace_range(&self, pos: usize) -> Option<Range<usize>> {
+        let text = self.char_ranges().as_str();
+        if pos > text.len() {
+            return None;
+        }
+        let pos = CharRangesExt::floor_char_boundary(text, pos);
+        caret::backspace_range(text, pos)
+    }
+
+    /// Returns the byte range, which a single delete at the caret
+    /// position `pos` should delete, i.e. the same as
+    /// [`backspace_range()`] but forward.
+    ///
+    /// Returns `None` if `pos >= len`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "a🌏\r\nb";
+    /// assert_eq!(text.delete_forward_range(1), Some(1..5));
+    /// assert_eq!(text.delete_forward_range(5), Some(5..7));
+    /// assert_eq!(text.delete_forward_range(8), None);
+    /// ```
+    ///
+    /// [`backspace_range()`]: Self::backspace_range
+    #[inline]
+    fn delete_forward_range(&self, pos: usize) -> Option<Range<usize>> {
+        let text = self.char_ranges().as_str();
+        if pos > text.len() {
+            return None;
+        }
+        let pos = CharRangesExt::floor_char_boundary(text, pos);
+        caret::delete_forward_range(text, pos)
+    }
+
+    /// Returns the byte range of the line containing the byte position
+    /// `idx`, along with the zero-based line number, and the zero-based
+    /// column of `idx` within the line, counted in [`char`]s.
+    ///
+    /// The range excludes the line terminator, i.e. `'\n'` or `"\r\n"`.
+    /// If `idx` is on the terminator, then the line it terminates is
+    /// returned, and the column is past the end of the line. The position
+    /// at the end of the text is on the last line, which is empty if the
+    /// text ends with a `'\n'`.
+    ///
+    /// If `idx` is not on a [`char`] boundary, then it is snapped down to
+    /// the start of the [`char`] containing it, i.e. [`floor_char_boundary()`].
+    ///
+    /// Returns `None` if `idx` is out of bounds.
+    ///
+    /// This scans the text for every call. Use `LineIndex` (requires
+    /// the `alloc` feature) when converting many positions.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "foo\r\n🗻∈🌏\nbar";
+    ///
+    /// assert_eq!(text.line_range_at(1), Some((0..3, 0, 1)));
+    /// assert_eq!(text.line_range_at(9), Some((5..16, 1, 1)));
+    /// assert_eq!(text.line_range_at(17), Some((17..20, 2, 0)));
+    ///
+    /// // Within '🗻'
+    /// assert_eq!(text.line_range_at(6), Some((5..16, 1, 0)));
+    /// ```
+    ///
+    /// [`floor_char_boundary()`]: Self::floor_char_boundary
+    #[inline]
+    fn line_range_at(&self, idx: usize) -> Option<(Range<usize>, usize, usize)> {
+        let text = self.char_ranges().as_str();
+        if idx > text.len() {
+            return None;
+        }
+        let idx = self.floor_char_boundary(idx);
+
+        let start = text[..idx].rfind('\n').map_or(0, |i| i + 1);
+        let end = match text[idx..].find('\n') {
+            Some(i) => {
+                let end = idx + i;
+                if text[start..end].ends_with('\r') {
+                    end - 1
+                } else {
+                    end
+                }
+            }
+            None => text.len(),
+        };
+
+        let line = text[..start].bytes().filter(|&b| b == b'\n').count();
+        let column = count_chars(&text[start..idx]);
+        Some((start..end, line, column))
+    }
+
+    /// Returns `range` grown outward to the start of the first line and
+    /// the end of the last line it touches, excluding the line terminator,
+    /// i.e. `'\n'` or `"\r\n"`, e.g. for selecting lines by triple-clicking.
+    ///
+    /// A `range` ending exactly at the start of a line does not touch
+    /// that line, while an empty `range`, i.e. a caret, grows to the line
+    /// it is on. The range never shrinks, i.e. if `range` already ends
+    /// within or after the terminator of its last line, then its end is
+    /// kept. Use [`expand_to_line_with_terminator()`] to always include
+    /// the terminator.
+    ///
+    /// `range` is first snapped outward to [`char`] boundaries, see
+    /// [`snap_range()`]. Returns `None` if `range` is reversed, or if the
+    /// start of `range` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "foo\r\nbar baz\nqux";
+    ///
+    /// // Caret within "bar baz"
+    /// assert_eq!(text.expand_to_line(9..9), Some(5..12));
+    /// assert_eq!(text.expand_to_line(1..9), Some(0..12));
+    ///
+    /// // Ends at the start of "bar baz"
+    /// assert_eq!(text.expand_to_line(1..5), Some(0..5));
+    /// ```
+    ///
+    /// [`expand_to_line_with_terminator()`]: Self::expand_to_line_with_terminator
+    /// [`snap_range()`]: Self::snap_range
+    #[inline]
+    fn expand_to_line(&self, range: Range<usize>) -> Option<Range<usize>> {
+        let range = self.snap_range(range, Snap::Outer)?;
+        let (start, (end, _terminator_end)) = expand_to_line(self.char_ranges().as_str(), &range);
+        Some(start..end.max(range.end))
+    }
+
+    /// Returns `range` grown outward to the start of the first line and
+    /// the end of the last line it touches, including the line terminator,
+    /// i.e. `'\n'` or `"\r\n"`.
+    ///
+    /// See [`expand_to_line()`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "foo\r\nbar baz\nqux";
+    ///
+    /// assert_eq!(text.expand_to_line_with_terminator(1..1), Some(0..5));
+    /// assert_eq!(text.expand_to_line_with_terminator(1..9), Some(0..13));
+    /// assert_eq!(text.expand_to_line_with_terminator(14..14), Some(13..16));
+    /// ```
+    ///
+    /// [`expand_to_line()`]: Self::expand_to_line
+    #[inline]
+    fn expand_to_line_with_terminator(&self, range: Range<usize>) -> Option<Range<usize>> {
+        let range = self.snap_range(range, Snap::Outer)?;
+        let (start, (_end, terminator_end)) = expand_to_line(self.char_ranges().as_str(), &range);
+        Some(start..terminator_end)
+    }
+
+    /// Returns an iterator over the lines touched by `range`, producing
+    /// the zero-based line number, the byte range of the line, and the
+    /// byte range of the intersection of `range` with the line, e.g.
+    /// for highlighting a range which spans multiple lines.
+    ///
+    /// Line ranges and intersections exclude the line terminator,
+    /// i.e. `'\n'` or `"\r\n"`. An empty range touches exactly one line.
+    ///
+    /// The `range` is snapped outwards to the nearest [`char`] boundaries.
+    /// Returns `None` if `range` is reversed, or starts out of bounds.
+    /// An end out of bounds is clamped.
+    ///
+    /// See [`SpanLines`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "foo\r\n🗻∈🌏\nbar";
+    ///
+    /// let mut lines = text.span_lines(1..19).unwrap();
+    /// assert_eq!(lines.next(), Some((0, 0..3, 1..3)));
+    /// assert_eq!(lines.next(), Some((1, 5..16, 5..16)));
+    /// assert_eq!(lines.next(), Some((2, 17..20, 17..19)));
+    /// assert_eq!(lines.next(), None);
+    ///
+    /// // Empty range
+    /// let mut lines = text.span_lines(9..9).unwrap();
+    /// assert_eq!(lines.next(), Some((1, 5..16, 9..9)));
+    /// assert_eq!(lines.next(), None);
+    /// ```
+    #[inline]
+    fn span_lines(&self, range: Range<usize>) -> Option<SpanLines<'_>> {
+        let range = self.snap_range(range, Snap::Outer)?;
+        Some(SpanLines::new(self.char_ranges().as_str(), range))
+    }
+
+    /// Returns the byte range of the prefix matching `prefix`, along with
+    /// the remaining substring after it, or `None` if the text doesn't
+    /// start with `prefix`.
+    ///
+    /// This is the same as [`str::strip_prefix()`], i.e. `prefix` is
+    /// removed at most once, except the range of the prefix is also
+    /// returned. See [`CharPattern`] for the kinds of patterns, e.g. a
+    /// `&str` or a predicate.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "// 🌏 comment";
+    /// assert_eq!(text.strip_prefix_range("// "), Some((0..3, "🌏 comment")));
+    /// assert_eq!(text.strip_prefix_range('/'), Some((0..1, "/ 🌏 comment")));
+    /// assert_eq!(text.strip_prefix_range("#"), None);
+    ///
+    /// // Iterate the remainder with positions in the whole text
+    /// let mut chars = text
+    ///     .strip_prefix_range("// ")
+    ///     .map(|(r, rest)| rest.char_ranges_offset(r.end))
+    ///     .unwrap();
+    /// assert_eq!(chars.next(), Some((3..7, '🌏')));
+    /// assert_eq!(chars.next(), Some((7..8, ' ')));
+    /// ```
+    #[inline]
+    fn strip_prefix_range<P>(&self, mut prefix: P) -> Option<(Range<usize>, &str)>
+    where
+        P: CharPattern,
+    {
+        let text = self.char_ranges().as_str();
+        let len = prefix.prefix_len(text)?;
+        Some((0..len, &text[len..]))
+    }
+
+    /// Returns the byte range of the suffix matching `suffix`, along with
+    /// the remaining substring before it, or `None` if the text doesn't
+    /// end with `suffix`.
+    ///
+    /// This is the same as [`str::strip_suffix()`], i.e. `suffix` is
+    /// removed at most once, except the range of the suffix is also
+    /// returned. See [`CharPattern`] for the kinds of patterns, e.g. a
+    /// `&str` or a predicate.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "Hello 🌏!!";
+    /// assert_eq!(text.strip_suffix_range("!"), Some((11..12, "Hello 🌏!")));
+    /// assert_eq!(text.strip_suffix_range(|c| c == '!'), Some((11..12, "Hello 🌏!")));
+    /// assert_eq!(text.strip_suffix_range("🌏!!"), Some((6..12, "Hello ")));
+    /// assert_eq!(text.strip_suffix_range("?"), None);
+    /// ```
+    #[inline]
+    fn strip_suffix_range<P>(&self, mut suffix: P) -> Option<(Range<usize>, &str)>
+    where
+        P: CharPattern,
+    {
+        let text = self.char_ranges().as_str();
+        let len = suffix.suffix_len(text)?;
+        let start = text.len() - len;
+        Some((start..text.len(), &text[..start]))
+    }
+
+    /// Returns an iterator over the substrings separated by `delim`, along
+    /// with their start and end byte positions, in reverse order, i.e.
+    /// from right to left.
+    ///
+    /// This produces the same substrings as [`str::rsplit()`], i.e. the
+    /// same as a forward split in reverse.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻/∈/🌏";
+    ///
+    /// let mut pieces = text.rsplit_char_ranges('/');
+    /// assert_eq!(pieces.next(), Some((9..13, "🌏")));
+    /// assert_eq!(pieces.next(), Some((5..8, "∈")));
+    /// assert_eq!(pieces.next(), Some((0..4, "🗻")));
+    /// assert_eq!(pieces.next(), None);
+    /// ```
+    #[inline]
+    fn rsplit_char_ranges(&self, delim: char) -> RSplitCharRanges<'_> {
+        RSplitCharRanges::new(self.char_ranges().as_str(), delim)
+    }
+
+    /// Returns an iterator over the substrings separated by `delim`, along
+    /// with their start and end byte positions, in reverse order, i.e.
+    /// from right to left.
+    ///
+    /// This produces the same substrings as [`str::rsplit()`], i.e. the
+    /// same as a forward split in reverse, unless matches of `delim` can
+    /// overlap, e.g. `"::"` in `"a:::b"`, in which case matches are
+    /// found from the right.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "a=🗻, b=∈";
+    ///
+    /// let mut pieces = text.rsplit_str_ranges(", ");
+    /// assert_eq!(pieces.next(), Some((8..13, "b=∈")));
+    /// assert_eq!(pieces.next(), Some((0..6, "a=🗻")));
+    /// assert_eq!(pieces.next(), None);
+    /// ```
+    #[inline]
+    fn rsplit_str_ranges<'b>(&self, delim: &'b str) -> RSplitStrRanges<'_, 'b> {
+        RSplitStrRanges::new(self.char_ranges().as_str(), delim)
+    }
+
+    /// Returns an iterator over the substrings separated by matches of
+    /// `delim`, where each substring includes the match ending it, along
+    /// with their start and end byte positions.
+    ///
+    /// This produces the same substrings as [`str::split_inclusive()`],
+    /// i.e. nothing is lost, as the substrings tile the text exactly, and
+    /// a match at the very end doesn't produce a final empty substring.
+    /// See [`CharPattern`] for the kinds of patterns, e.g. a [`char`],
+    /// a `&str` or a predicate.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "let 🗻 = 1;\nlet ∈ = 2;\nend";
+    ///
+    /// let mut lines = text.split_inclusive_ranges('\n');
+    /// assert_eq!(lines.next(), Some((0..14, "let 🗻 = 1;\n")));
+    /// assert_eq!(lines.next(), Some((14..27, "let ∈ = 2;\n")));
+    /// assert_eq!(lines.next(), Some((27..30, "end")));
+    /// assert_eq!(lines.next(), None);
+    /// ```
+    #[inline]
+    fn split_inclusive_ranges<P>(&self, delim: P) -> SplitInclusiveRanges<'_, P>
+    where
+        P: CharPattern,
+    {
+        SplitInclusiveRanges::new(self.char_ranges().as_str(), delim)
+    }
+
+    /// Returns the byte ranges and substrings before and after the last
+    /// match of `delim`, or `None` if `delim` doesn't match.
+    ///
+    /// This is the same as [`str::rsplit_once()`], except the ranges are
+    /// also returned. The match itself is between the two ranges. See
+    /// [`CharPattern`] for the kinds of patterns, e.g. a `&str` or
+    /// a predicate.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻.tar.gz";
+    /// assert_eq!(
+    ///     text.rsplit_once_range('.'),
+    ///     Some(((0..8, "🗻.tar"), (9..11, "gz"))),
+    /// );
+    /// assert_eq!(text.rsplit_once_range('/'), None);
+    /// ```
+    #[inline]
+    #[allow(clippy::type_complexity)]
+    fn rsplit_once_range<P>(
+        &self,
+        mut delim: P,
+    ) -> Option<((Range<usize>, &str), (Range<usize>, &str))>
+    where
+        P: CharPattern,
+    {
+        let text = self.char_ranges().as_str();
+        let r = delim.rfind_in(text)?;
+        Some((
+            (0..r.start, &text[..r.start]),
+            (r.end..text.len(), &text[r.end..]),
+        ))
+    }
+}
+
+/// Implemented for all types which are <code>[AsRef]&lt;[str]&gt;</code>,
+/// e.g. [`str`], `String`, `Box<str>`, `Rc<str>`, `Arc<str>`, and
+/// `Cow<'_, str>`, such that generic code bounded by `T: CharRangesExt`
+/// accepts both borrowed and owned text.
+///
+/// # Example
+///
+/// ```rust
+/// use std::borrow::Cow;
+///
+/// use char_ranges::CharRangesExt;
+///
+/// fn first_char<T>(text: &T) -> Option<(std::ops::Range<usize>, char)>
+/// where
+///     T: CharRangesExt + ?Sized,
+/// {
+///     text.char_ranges().next()
+/// }
+///
+/// assert_eq!(first_char("🗻∈🌏"), Some((0..4, '🗻')));
+/// assert_eq!(first_char(&String::from("∈🌏")), Some((0..3, '∈')));
+/// assert_eq!(first_char(&Cow::Borrowed("🌏")), Some((0..4, '🌏')));
+/// ```
+impl<T> CharRangesExt for T
+where
+    T: AsRef<str> + ?Sized,
+{
+    #[inline]
+    fn char_ranges(&self) -> CharRanges<'_> {
+        CharRanges::new(self.as_ref())
+    }
+}
+
+/// An iterator over [`char`]s and their start and end byte positions.
+///
+/// Note: This iterator is [`Copy`], i.e. it can be stored and restored
+/// freely, e.g. for backtracking, without explicitly cloning it.
+///
+/// See examples in the [crate root](crate).
+#[derive(Clone, Copy)]
+pub struct CharRanges<'a> {
+    /// The text this iterator was created with.
+    text: &'a str,
+    /// Start byte position of the remaining text.
+    start: usize,
+    /// End byte position of the remaining text.
+    end: usize,
+}
+
+impl<'a> CharRanges<'a> {
+    /// Creates an iterator over [`char`]s and their start and end byte positions.
+    ///
+    /// Consider using <code>text.[char_ranges()]</code>, instead of
+    /// explicitly using `CharRanges::new()`.
+    ///
+    /// See examples in the [crate root](crate).
+    ///
+    /// [char_ranges()]: CharRangesExt::char_ranges
+    #[inline]
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            start: 0,
+            end: text.len(),
+        }
+    }
+
+    /// Returns the remaining substring.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "ABCDE";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.as_str(), "ABCDE");
+    ///
+    /// assert_eq!(chars.next(), Some((0..1, 'A')));
+    /// assert_eq!(chars.next_back(), Some((4..5, 'E')));
+    ///
+    /// assert_eq!(chars.as_str(), "BCD");
+    /// ```
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        &self.text[self.start..self.end]
+    }
+
+    /// Returns the original text, this iterator was created with,
+    /// regardless of how much has been consumed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "ABCDE";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.next(), Some((0..1, 'A')));
+    /// assert_eq!(chars.next_back(), Some((4..5, 'E')));
+    ///
+    /// assert_eq!(chars.original_str(), "ABCDE");
+    /// ```
+    #[inline]
+    pub fn original_str(&self) -> &'a str {
+        self.text
+    }
+
+    /// Returns the substring, which has been consumed from the front.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "ABCDE";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.consumed_prefix(), "");
+    ///
+    /// assert_eq!(chars.nth(1), Some((1..2, 'B')));
+    /// assert_eq!(chars.consumed_prefix(), "AB");
+    /// ```
+    #[inline]
+    pub fn consumed_prefix(&self) -> &'a str {
+        &self.text[..self.start]
+    }
+
+    /// Returns the substring, which has been consumed from the back.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "ABCDE";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.consumed_suffix(), "");
+    ///
+    /// assert_eq!(chars.nth_back(1), Some((3..4, 'D')));
+    /// assert_eq!(chars.consumed_suffix(), "DE");
+    /// ```
+    #[inline]
+    pub fn consumed_suffix(&self) -> &'a str {
+        &self.text[self.end..]
+    }
+
+    /// Returns a [`ValidSpan`] of the byte range `r` within the
+    /// [`original_str()`](Self::original_str), e.g. of a range produced
+    /// by this iterator.
+    ///
+    /// Returns an error if `r` is reversed, out of bounds, or if its start
+    /// or end is not a [`char`] boundary. This is _O(1)_.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "a🌏b";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// chars.next();
+    ///
+    /// let (r, _c) = chars.next().unwrap();
+    /// let span = chars.valid_span(r).unwrap();
+    /// assert_eq!(span.as_str(), "🌏");
+    /// assert_eq!(span.text(), "a🌏b");
+    /// ```
+    #[inline]
+    pub fn valid_span(&self, r: Range<usize>) -> Result<ValidSpan<'a>, SpanError> {
+        ValidSpan::new(self.text, r)
+    }
+
+    /// Converts this iterator into a [`CharIndices`] over the remaining
+    /// substring, i.e. [`as_str()`](Self::as_str).
+    ///
+    /// Note that the positions produced by the returned [`CharIndices`]
+    /// restart at `0`, i.e. they are relative to the remaining substring.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻∈🌏";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.next(), Some((0..4, '🗻')));
+    ///
+    /// let mut chars = chars.into_char_indices();
+    /// assert_eq!(chars.next(), Some((0, '∈')));
+    /// assert_eq!(chars.next(), Some((3, '🌏')));
+    /// assert_eq!(chars.next(), None);
+    /// ```
+    #[inline]
+    pub fn into_char_indices(self) -> CharIndices<'a> {
+        self.as_str().char_indices()
+    }
+
+    /// Restores both ends of the iterator, such that the remaining
+    /// substring is the [original text](Self::original_str) again.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "ABCDE";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.next(), Some((0..1, 'A')));
+    /// assert_eq!(chars.next_back(), Some((4..5, 'E')));
+    /// assert_eq!(chars.as_str(), "BCD");
+    ///
+    /// chars.reset();
+    /// assert_eq!(chars.as_str(), "ABCDE");
+    /// assert_eq!(chars.next(), Some((0..1, 'A')));
+    /// ```
+    #[inline]
+    pub fn reset(&mut self) {
+        self.reset_front();
+        self.reset_back();
+    }
+
+    /// Restores the front of the iterator, such that the
+    /// [consumed prefix](Self::consumed_prefix) is empty again.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "ABCDE";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.next(), Some((0..1, 'A')));
+    /// assert_eq!(chars.next_back(), Some((4..5, 'E')));
+    ///
+    /// chars.reset_front();
+    /// assert_eq!(chars.as_str(), "ABCD");
+    /// ```
+    #[inline]
+    pub fn reset_front(&mut self) {
+        self.start = 0;
+    }
+
+    /// Restores the back of the iterator, such that the
+    /// [consumed suffix](Self::consumed_suffix) is empty again.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "ABCDE";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.next(), Some((0..1, 'A')));
+    /// assert_eq!(chars.next_back(), Some((4..5, 'E')));
+    ///
+    /// chars.reset_back();
+    /// assert_eq!(chars.as_str(), "BCDE");
+    /// ```
+    #[inline]
+    pub fn reset_back(&mut self) {
+        self.end = self.text.len();
+    }
+
+    /// Moves the front of the iterator back by one [`char`], and returns
+    /// that [`char`] and its start and end byte positions, i.e. the
+    /// inverse of [`next()`].
+    ///
+    /// Returns `None` if the front is at the start of the
+    /// [original text](Self::original_str).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻∈🌏";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.prev(), None);
+    ///
+    /// assert_eq!(chars.next(), Some((0..4, '🗻')));
+    /// assert_eq!(chars.next(), Some((4..7, '∈')));
+    /// assert_eq!(chars.as_str(), "🌏");
+    ///
+    /// assert_eq!(chars.prev(), Some((4..7, '∈')));
+    /// assert_eq!(chars.as_str(), "∈🌏");
+    /// ```
+    ///
+    /// [`next()`]: Iterator::next
+    #[inline]
+    pub fn prev(&mut self) -> Option<(Range<usize>, char)> {
+        let c = self.consumed_prefix().chars().next_back()?;
+        let end = self.start;
+        self.start -= c.len_utf8();
+        Some((self.start..end, c))
+    }
+
+    /// Moves the back of the iterator forward by one [`char`], and returns
+    /// that [`char`] and its start and end byte positions, i.e. the
+    /// inverse of [`next_back()`].
+    ///
+    /// Returns `None` if the back is at the end of the
+    /// [original text](Self::original_str).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻∈🌏";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.prev_back(), None);
+    ///
+    /// assert_eq!(chars.next_back(), Some((7..11, '🌏')));
+    /// assert_eq!(chars.next_back(), Some((4..7, '∈')));
+    /// assert_eq!(chars.as_str(), "🗻");
+    ///
+    /// assert_eq!(chars.prev_back(), Some((4..7, '∈')));
+    /// assert_eq!(chars.as_str(), "🗻∈");
+    /// ```
+    ///
+    /// [`next_back()`]: DoubleEndedIterator::next_back
+    #[inline]
+    pub fn prev_back(&mut self) -> Option<(Range<usize>, char)> {
+        let c = self.consumed_suffix().chars().next()?;
+        let start = self.end;
+        self.end += c.len_utf8();
+        Some((start..self.end, c))
+    }
+
+    /// Moves the front of the iterator to the byte position `pos`,
+    /// such that [`next()`] produces the [`char`] starting at `pos`.
+    ///
+    /// The front can be moved both forwards and backwards, within the
+    /// [original text](Self::original_str), up until the back of the
+    /// iterator.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `pos` is out of bounds of the original text,
+    /// not on a [`char`] boundary, or past the back of the iterator.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{CharRangesExt, SeekError};
+    ///
+    /// let text = "🗻∈🌏";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.seek_to(7), Ok(()));
+    /// assert_eq!(chars.as_str(), "🌏");
+    /// assert_eq!(chars.next(), Some((7..11, '🌏')));
+    ///
+    /// assert_eq!(chars.seek_to(5), Err(SeekError::NotCharBoundary));
+    /// assert_eq!(chars.seek_to(12), Err(SeekError::OutOfBounds));
+    ///
+    /// assert_eq!(chars.seek_to(4), Ok(()));
+    /// assert_eq!(chars.next(), Some((4..7, '∈')));
+    /// ```
+    ///
+    /// [`next()`]: Iterator::next
+    pub fn seek_to(&mut self, pos: usize) -> Result<(), SeekError> {
+        self.check_seek(pos)?;
+        if pos > self.end {
+            return Err(SeekError::CrossesCursor);
+        }
+        self.start = pos;
+        Ok(())
+    }
+
+    /// Moves the back of the iterator to the byte position `pos`,
+    /// such that [`next_back()`] produces the [`char`] ending at `pos`.
+    ///
+    /// The back can be moved both forwards and backwards, within the
+    /// [original text](Self::original_str), down until the front of the
+    /// iterator.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `pos` is out of bounds of the original text,
+    /// not on a [`char`] boundary, or before the front of the iterator.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{CharRangesExt, SeekError};
+    ///
+    /// let text = "🗻∈🌏";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.seek_back_to(4), Ok(()));
+    /// assert_eq!(chars.as_str(), "🗻");
+    /// assert_eq!(chars.next_back(), Some((0..4, '🗻')));
+    ///
+    /// assert_eq!(chars.seek_back_to(2), Err(SeekError::NotCharBoundary));
+    ///
+    /// assert_eq!(chars.seek_back_to(11), Ok(()));
+    /// assert_eq!(chars.as_str(), "🗻∈🌏");
+    /// ```
+    ///
+    /// [`next_back()`]: DoubleEndedIterator::next_back
+    pub fn seek_back_to(&mut self, pos: usize) -> Result<(), SeekError> {
+        self.check_seek(pos)?;
+        if pos < self.start {
+            return Err(SeekError::CrossesCursor);
+        }
+        self.end = pos;
+        Ok(())
+    }
+
+    /// Returns an iterator over the [`char`]s of the [original text]
+    /// from the byte position `pos` to the end of the original text,
+    /// regardless of how much has been consumed.
+    ///
+    /// See [`seek_to()`] to move the front without also resetting the back.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pos` is out of bounds of the original text or is not on
+    /// a [`char`] boundary, in which case the message includes the
+    /// straddled [`char`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻∈🌏";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.next_back(), Some((7..11, '🌏')));
+    ///
+    /// let mut chars = chars.char_ranges_from(4);
+    /// assert_eq!(chars.as_str(), "∈🌏");
+    /// assert_eq!(chars.next(), Some((4..7, '∈')));
+    /// ```
+    ///
+    /// [original text]: Self::original_str
+    /// [`seek_to()`]: Self::seek_to
+    #[inline]
+    #[track_caller]
+    pub fn char_ranges_from(mut self, pos: usize) -> Self {
+        assert_char_boundary(self.text, pos);
+        self.start = pos;
+        self.end = self.text.len();
+        self
+    }
+
+    /// Returns an iterator over the [`char`]s of the [original text]
+    /// from the start of the original text up to the byte position `pos`,
+    /// regardless of how much has been consumed.
+    ///
+    /// See [`seek_back_to()`] to move the back without also resetting
+    /// the front.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pos` is out of bounds of the original text or is not on
+    /// a [`char`] boundary, in which case the message includes the
+    /// straddled [`char`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻∈🌏";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.next(), Some((0..4, '🗻')));
+    ///
+    /// let mut chars = chars.char_ranges_up_to(7);
+    /// assert_eq!(chars.as_str(), "🗻∈");
+    /// assert_eq!(chars.next_back(), Some((4..7, '∈')));
+    /// ```
+    ///
+    /// [original text]: Self::original_str
+    /// [`seek_back_to()`]: Self::seek_back_to
+    #[inline]
+    #[track_caller]
+    pub fn char_ranges_up_to(mut self, pos: usize) -> Self {
+        assert_char_boundary(self.text, pos);
+        self.start = 0;
+        self.end = pos;
+        self
+    }
+
+    fn check_seek(&self, pos: usize) -> Result<(), SeekError> {
+        if pos > self.text.len() {
+            Err(SeekError::OutOfBounds)
+        } else if !self.text.is_char_boundary(pos) {
+            Err(SeekError::NotCharBoundary)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns the next [`char`] and its start and end byte positions,
+    /// without consuming it, i.e. the same as [`next()`] would return.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻∈🌏";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.peek(), Some((0..4, '🗻')));
+    /// assert_eq!(chars.peek(), Some((0..4, '🗻')));
+    /// assert_eq!(chars.next(), Some((0..4, '🗻')));
+    ///
+    /// assert_eq!(chars.peek(), Some((4..7, '∈')));
+    /// ```
+    ///
+    /// [`next()`]: Iterator::next
+    #[inline]
+    pub fn peek(&self) -> Option<(Range<usize>, char)> {
+        self.clone().next()
+    }
+
+    /// Returns the last [`char`] and its start and end byte positions,
+    /// without consuming it, i.e. the same as [`next_back()`] would return.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻∈🌏";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.peek_back(), Some((7..11, '🌏')));
+    /// assert_eq!(chars.peek_back(), Some((7..11, '🌏')));
+    /// assert_eq!(chars.next_back(), Some((7..11, '🌏')));
+    ///
+    /// assert_eq!(chars.peek_back(), Some((4..7, '∈')));
+    /// ```
+    ///
+    /// [`next_back()`]: DoubleEndedIterator::next_back
+    #[inline]
+    pub fn peek_back(&self) -> Option<(Range<usize>, char)> {
+        self.clone().next_back()
+    }
+
+    /// Returns the start byte position of the next [`char`], i.e. the
+    /// start of the range [`next()`] would return.
+    ///
+    /// If the iterator is exhausted, then this is the same as [`back_offset()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻∈🌏";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.front_offset(), 0);
+    ///
+    /// assert_eq!(chars.next(), Some((0..4, '🗻')));
+    /// assert_eq!(chars.front_offset(), 4);
+    /// ```
+    ///
+    /// [`next()`]: Iterator::next
+    /// [`back_offset()`]: Self::back_offset
+    #[inline]
+    pub fn front_offset(&self) -> usize {
+        self.start
+    }
+
+    /// Returns the end byte position of the last [`char`], i.e. the
+    /// end of the range [`next_back()`] would return.
+    ///
+    /// If the iterator is exhausted, then this is the same as [`front_offset()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻∈🌏";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.back_offset(), 11);
+    ///
+    /// assert_eq!(chars.next_back(), Some((7..11, '🌏')));
+    /// assert_eq!(chars.back_offset(), 7);
+    /// ```
+    ///
+    /// [`next_back()`]: DoubleEndedIterator::next_back
+    /// [`front_offset()`]: Self::front_offset
+    #[inline]
+    pub fn back_offset(&self) -> usize {
+        self.end
+    }
+
+    /// Returns a [`Mark`] of the current front position, for later
+    /// extracting everything consumed since then with [`slice_since()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "let 🗻 = 1;";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// chars.advance_by(4).unwrap();
+    ///
+    /// let start = chars.mark();
+    /// chars.next_if_eq('🗻').unwrap();
+    /// assert_eq!(chars.slice_since(start), (4..8, "🗻"));
+    ///
+    /// let end = chars.mark();
+    /// chars.next();
+    /// assert_eq!(chars.slice_between(start, end), (4..8, "🗻"));
+    /// ```
+    ///
+    /// [`slice_since()`]: Self::slice_since
+    #[inline]
+    pub fn mark(&self) -> Mark {
+        Mark::new(self.text, self.start)
+    }
+
+    /// Returns the range and substring consumed from the front since
+    /// `mark` was taken.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mark` was taken from an iterator over a different
+    /// text, or if `mark` is after the current front position, e.g. if
+    /// the iterator was [`reset_front()`](Self::reset_front) since.
+    #[inline]
+    #[track_caller]
+    pub fn slice_since(&self, mark: Mark) -> (Range<usize>, &'a str) {
+        self.slice_between(mark, self.mark())
+    }
+
+    /// Returns the range and substring between the marks `a` and `b`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either mark was taken from an iterator over a different
+    /// text, or if `a` is after `b`.
+    #[inline]
+    #[track_caller]
+    pub fn slice_between(&self, a: Mark, b: Mark) -> (Range<usize>, &'a str) {
+        a.assert_source(self.text);
+        b.assert_source(self.text);
+        if a.pos > b.pos {
+            panic!("mark at {} is after mark at {}", a.pos, b.pos);
+        }
+        let r = a.pos..b.pos;
+        (r.clone(), &self.text[r])
+    }
+
+    /// Returns the range of the next [`char`], without decoding it.
+    ///
+    /// This is the same as <code>[next()].map(|(r, _)| r)</code>, except that
+    /// only the leading byte is inspected, to determine the length of
+    /// the [`char`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻∈🌏";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.next_range(), Some(0..4));
+    /// assert_eq!(chars.next(), Some((4..7, '∈')));
+    /// assert_eq!(chars.next_range(), Some(7..11));
+    /// assert_eq!(chars.next_range(), None);
+    /// ```
+    ///
+    /// [next()]: Iterator::next
+    #[inline]
+    pub fn next_range(&mut self) -> Option<Range<usize>> {
+        let &b = self.as_str().as_bytes().first()?;
+        let start = self.start;
+        self.start += utf8_char_width(b);
+        Some(start..self.start)
+    }
+
+    /// Returns the range of the last [`char`], without decoding it.
+    ///
+    /// This is the same as <code>[next_back()].map(|(r, _)| r)</code>, except
+    /// that only continuation bytes are skipped, to determine the length
+    /// of the [`char`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻∈🌏";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.next_back_range(), Some(7..11));
+    /// assert_eq!(chars.next_back(), Some((4..7, '∈')));
+    /// assert_eq!(chars.next_back_range(), Some(0..4));
+    /// assert_eq!(chars.next_back_range(), None);
+    /// ```
+    ///
+    /// [next_back()]: DoubleEndedIterator::next_back
+    #[inline]
+    pub fn next_back_range(&mut self) -> Option<Range<usize>> {
+        let bytes = self.as_str().as_bytes();
+        let len = 1 + bytes
+            .iter()
+            .rev()
+            .position(|&b| !is_utf8_continuation_byte(b))?;
+        let end = self.end;
+        self.end -= len;
+        Some(self.end..end)
+    }
+
+    /// Returns the number of remaining [`char`]s, without consuming
+    /// the iterator.
+    ///
+    /// This is the same as <code>[clone()].[count()]</code>, except that
+    /// only the leading bytes of [`char`]s are counted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻∈🌏";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.remaining_char_count(), 3);
+    ///
+    /// assert_eq!(chars.next(), Some((0..4, '🗻')));
+    /// assert_eq!(chars.remaining_char_count(), 2);
+    /// ```
+    ///
+    /// [clone()]: Clone::clone
+    /// [count()]: Iterator::count
+    #[inline]
+    pub fn remaining_char_count(&self) -> usize {
+        count_chars(self.as_str())
+    }
+
+    /// Advances the iterator by `n` [`char`]s, without decoding them.
+    ///
+    /// Returns `Ok(())` if `n` [`char`]s were skipped, otherwise
+    /// <code>Err(k)</code> where `k` is the number of [`char`]s that
+    /// were skipped before the iterator was exhausted.
+    ///
+    /// This is a stable alternative to the unstable [`Iterator::advance_by()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻∈🌏";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.advance_by(2), Ok(()));
+    /// assert_eq!(chars.as_str(), "🌏");
+    ///
+    /// assert_eq!(chars.advance_by(5), Err(1));
+    /// assert_eq!(chars.as_str(), "");
+    /// ```
+    ///
+    /// [`Iterator::advance_by()`]: https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.advance_by
+    #[inline]
+    pub fn advance_by(&mut self, n: usize) -> Result<(), usize> {
+        match skip_chars(self.as_str().as_bytes(), n) {
+            Ok(len) => {
+                self.start += len;
+                Ok(())
+            }
+            Err(k) => {
+                self.start = self.end;
+                Err(k)
+            }
+        }
+    }
+
+    /// Advances the iterator from the back by `n` [`char`]s, without
+    /// decoding them.
+    ///
+    /// Returns `Ok(())` if `n` [`char`]s were skipped, otherwise
+    /// <code>Err(k)</code> where `k` is the number of [`char`]s that
+    /// were skipped before the iterator was exhausted.
+    ///
+    /// This is a stable alternative to the unstable
+    /// [`DoubleEndedIterator::advance_back_by()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻∈🌏";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.advance_back_by(2), Ok(()));
+    /// assert_eq!(chars.as_str(), "🗻");
+    ///
+    /// assert_eq!(chars.advance_back_by(5), Err(1));
+    /// assert_eq!(chars.as_str(), "");
+    /// ```
+    ///
+    /// [`DoubleEndedIterator::advance_back_by()`]: https://doc.rust-lang.org/std/iter/trait.DoubleEndedIterator.html#method.advance_back_by
+    #[inline]
+    pub fn advance_back_by(&mut self, n: usize) -> Result<(), usize> {
+        match skip_chars_back(self.as_str().as_bytes(), n) {
+            Ok(len) => {
+                self.end -= len;
+                Ok(())
+            }
+            Err(k) => {
+                self.end = self.start;
+                Err(k)
+            }
+        }
+    }
+
+    /// Splits the remaining [`char`]s into two iterators, where the first
+    /// produces the next `n` [`char`]s, and the second produces the rest.
+    ///
+    /// If fewer than `n` [`char`]s remain, then the second iterator is empty.
+    ///
+    /// Both iterators produce positions relative to the same text as this
+    /// iterator, i.e. the positions of the second continue where the first ends.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻∈🌏";
+    ///
+    /// let (mut left, mut right) = text.char_ranges().split_at_char(1);
+    /// assert_eq!(left.as_str(), "🗻");
+    /// assert_eq!(right.as_str(), "∈🌏");
+    ///
+    /// assert_eq!(left.next(), Some((0..4, '🗻')));
+    /// assert_eq!(left.next(), None);
+    ///
+    /// assert_eq!(right.next(), Some((4..7, '∈')));
+    /// assert_eq!(right.next(), Some((7..11, '🌏')));
+    /// assert_eq!(right.next(), None);
+    /// ```
+    #[inline]
+    pub fn split_at_char(self, n: usize) -> (CharRangesOffset<'a>, CharRangesOffset<'a>) {
+        self.offset(0).split_at_char(n)
+    }
+
+    /// Splits the remaining [`char`]s into two iterators at the byte
+    /// position `pos`, where the first produces the [`char`]s before `pos`,
+    /// and the second produces the [`char`]s from `pos`.
+    ///
+    /// Unlike [`split_at_char()`](Self::split_at_char), this splits at a
+    /// byte position in the [original text](Self::original_str), instead
+    /// of a number of [`char`]s. Both iterators produce positions relative
+    /// to the same text as this iterator.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `pos` is before the front or after the back of
+    /// the iterator, or if `pos` is not on a [`char`] boundary, along with
+    /// the [`char`] around `pos`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{BoundaryError, CharRangesExt};
+    ///
+    /// let text = "🗻∈🌏";
+    ///
+    /// let (mut left, mut right) = text.char_ranges().partition_at_byte(4).unwrap();
+    /// assert_eq!(left.as_str(), "🗻");
+    /// assert_eq!(right.as_str(), "∈🌏");
+    /// assert_eq!(left.next(), Some((0..4, '🗻')));
+    /// assert_eq!(right.next(), Some((4..7, '∈')));
+    ///
+    /// let err = text.char_ranges().partition_at_byte(9).unwrap_err();
+    /// assert_eq!(
+    ///     err,
+    ///     BoundaryError::NotCharBoundary {
+    ///         pos: 9,
+    ///         range: 7..11,
+    ///         c: '🌏',
+    ///     }
+    /// );
+    /// ```
+    #[inline]
+    pub fn partition_at_byte(
+        self,
+        pos: usize,
+    ) -> Result<(CharRangesOffset<'a>, CharRangesOffset<'a>), BoundaryError> {
+        self.offset(0).partition_at_byte(pos)
+    }
+
+    /// Returns an iterator over the next `n` [`char`]s, i.e. the remaining
+    /// [`char`]s are bounded to a window of at most `n` [`char`]s.
+    ///
+    /// Unlike <code>[take]\(n)</code>, the returned iterator is still a
+    /// [`CharRanges`], i.e. [`as_str()`] returns the remaining substring of
+    /// the window, and consuming from the back starts at the end of the window.
+    ///
+    /// If fewer than `n` [`char`]s remain, then the window is all of them.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻∈🌏!";
+    ///
+    /// let mut chars = text.char_ranges().take_chars(3);
+    /// assert_eq!(chars.as_str(), "🗻∈🌏");
+    ///
+    /// assert_eq!(chars.next_back(), Some((7..11, '🌏')));
+    /// assert_eq!(chars.next(), Some((0..4, '🗻')));
+    /// assert_eq!(chars.as_str(), "∈");
+    /// ```
+    ///
+    /// [take]: Iterator::take
+    /// [`as_str()`]: Self::as_str
+    #[inline]
+    pub fn take_chars(mut self, n: usize) -> Self {
+        self.end = match skip_chars(self.as_str().as_bytes(), n) {
+            Ok(len) => self.start + len,
+            Err(_) => self.end,
+        };
+        self
+    }
+
+    /// Advances the iterator by `n` [`char`]s, and returns it.
+    ///
+    /// This is the same as [`advance_by()`], except that it can be chained.
+    /// If fewer than `n` [`char`]s remain, then the returned iterator is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻∈🌏!";
+    ///
+    /// let mut chars = text.char_ranges().skip_chars(1).take_chars(2);
+    /// assert_eq!(chars.as_str(), "∈🌏");
+    /// assert_eq!(chars.next(), Some((4..7, '∈')));
+    /// ```
+    ///
+    /// [`advance_by()`]: Self::advance_by
+    #[inline]
+    pub fn skip_chars(mut self, n: usize) -> Self {
+        _ = self.advance_by(n);
+        self
+    }
+
+    /// Returns the byte range and substring of the last `n` remaining
+    /// [`char`]s, without consuming the iterator.
+    ///
+    /// If fewer than `n` [`char`]s remain, then the whole remaining
+    /// substring is returned.
+    ///
+    /// Only the last `n` [`char`]s are scanned, by inspecting the bytes
+    /// backwards from the end, instead of scanning the whole remaining
+    /// substring.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "Hello 🗻∈🌏";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.last_n(3), (6..17, "🗻∈🌏"));
+    ///
+    /// chars.next_back();
+    /// assert_eq!(chars.last_n(3), (5..13, " 🗻∈"));
+    /// ```
+    #[inline]
+    pub fn last_n(&self, n: usize) -> (Range<usize>, &'a str) {
+        let chars = (*self).last_n_ranges(n);
+        (chars.start..chars.end, chars.as_str())
+    }
+
+    /// Returns an iterator over the last `n` remaining [`char`]s, i.e. the
+    /// remaining [`char`]s are bounded to a window of at most `n` [`char`]s
+    /// at the back.
+    ///
+    /// This is the back equivalent of [`take_chars()`], and unlike
+    /// <code>[rev]\().[take]\(n)</code>, the [`char`]s are still produced
+    /// in order from front to back.
+    ///
+    /// If fewer than `n` [`char`]s remain, then the window is all of them.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻∈🌏!";
+    ///
+    /// let mut chars = text.char_ranges().last_n_ranges(2);
+    /// assert_eq!(chars.as_str(), "🌏!");
+    ///
+    /// assert_eq!(chars.next(), Some((7..11, '🌏')));
+    /// assert_eq!(chars.next(), Some((11..12, '!')));
+    /// assert_eq!(chars.next(), None);
+    /// ```
+    ///
+    /// [`take_chars()`]: Self::take_chars
+    /// [rev]: Iterator::rev
+    /// [take]: Iterator::take
+    #[inline]
+    pub fn last_n_ranges(mut self, n: usize) -> Self {
+        self.start = match skip_chars_back(self.as_str().as_bytes(), n) {
+            Ok(len) => self.end - len,
+            Err(_) => self.start,
+        };
+        self
+    }
+
+    /// Returns an iterator over the remaining [`char`]s within a budget
+    /// of `max` bytes, counted from the current position.
+    ///
+    /// Iteration stops before the first [`char`] whose end would exceed the
+    /// budget, i.e. a [`char`] is never split. Use [`TakeBytes::stopped_at()`]
+    /// to get the byte position where iteration stops.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻∈🌏";
+    ///
+    /// // Only 2 bytes remain in the budget for the 4 byte '🌏'
+    /// let mut chars = text.char_ranges().take_bytes(9);
+    /// assert_eq!(chars.as_str(), "🗻∈");
+    /// assert_eq!(chars.stopped_at(), 7);
+    ///
+    /// assert_eq!(chars.next(), Some((0..4, '🗻')));
+    /// assert_eq!(chars.next(), Some((4..7, '∈')));
+    /// assert_eq!(chars.next(), None);
+    /// ```
+    #[inline]
+    pub fn take_bytes(self, max: usize) -> TakeBytes<'a> {
+        self.offset(0).take_bytes(max)
+    }
+
+    /// Returns an iterator over chunks of the remaining substring and their
+    /// start and end byte positions, where each chunk is as large as possible
+    /// without exceeding `max_bytes` bytes, and without splitting a [`char`].
+    ///
+    /// The chunks tile the remaining substring, i.e. concatenating them
+    /// produces the remaining substring.
+    ///
+    /// A [`char`] larger than `max_bytes`, which is only possible when
+    /// `max_bytes` is less than `4`, produces a [`CharTooLargeError`]
+    /// with the range of the [`char`], after which iteration continues.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "Hello 🌏 World";
+    ///
+    /// let mut chunks = text.char_ranges().chunk_by_bytes(8);
+    /// assert_eq!(chunks.next(), Some(Ok((0..6, "Hello "))));
+    /// assert_eq!(chunks.next(), Some(Ok((6..14, "🌏 Wor"))));
+    /// assert_eq!(chunks.next(), Some(Ok((14..16, "ld"))));
+    /// assert_eq!(chunks.next(), None);
+    ///
+    /// let mut chunks = "a🌏".char_ranges().chunk_by_bytes(2);
+    /// assert_eq!(chunks.next(), Some(Ok((0..1, "a"))));
+    /// assert_eq!(chunks.next().unwrap().unwrap_err().range(), 1..5);
+    /// assert_eq!(chunks.next(), None);
+    /// ```
+    #[inline]
+    pub fn chunk_by_bytes(self, max_bytes: usize) -> ByteChunks<'a> {
+        self.offset(0).chunk_by_bytes(max_bytes)
+    }
+
+    /// Returns an iterator over the [`char`] boundaries of the remaining
+    /// substring, including its start and end.
+    ///
+    /// See [`CharRangesExt::char_boundaries()`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let mut chars = "a🗻b∈".char_ranges();
+    /// chars.next();
+    ///
+    /// let boundaries = chars.boundaries();
+    /// assert!(boundaries.eq([1, 5, 6, 9]));
+    /// ```
+    #[inline]
+    pub fn boundaries(self) -> CharBoundaries<'a> {
+        self.offset(0).boundaries()
+    }
+
+    /// Returns an iterator over the remaining bytes, producing the byte
+    /// position of each byte, the byte, and the start and end byte
+    /// positions of the [`char`] it belongs to.
+    ///
+    /// See [`CharRangesExt::bytes_with_char_ranges()`] for more information.
+    #[inline]
+    pub fn bytes_with_char_ranges(self) -> BytesWithCharRanges<'a> {
+        self.offset(0).bytes_with_char_ranges()
+    }
+
+    /// Consumes and returns the next [`char`] and its start and end byte
+    /// positions, only if `pred` returns `true` for the [`char`].
+    ///
+    /// Otherwise, the iterator is left untouched and `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "1a";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.next_if(|c| c.is_ascii_digit()), Some((0..1, '1')));
+    /// assert_eq!(chars.next_if(|c| c.is_ascii_digit()), None);
+    /// assert_eq!(chars.as_str(), "a");
+    /// ```
+    #[inline]
+    pub fn next_if<P>(&mut self, pred: P) -> Option<(Range<usize>, char)>
+    where
+        P: FnOnce(char) -> bool,
+    {
+        let mut iter = *self;
+        let (r, c) = iter.next()?;
+        if pred(c) {
+            *self = iter;
+            Some((r, c))
+        } else {
+            None
+        }
+    }
+
+    /// Consumes and returns the last [`char`] and its start and end byte
+    /// positions, only if `pred` returns `true` for the [`char`].
+    ///
+    /// Otherwise, the iterator is left untouched and `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "a1";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.next_back_if(|c| c.is_ascii_digit()), Some((1..2, '1')));
+    /// assert_eq!(chars.next_back_if(|c| c.is_ascii_digit()), None);
+    /// assert_eq!(chars.as_str(), "a");
+    /// ```
+    #[inline]
+    pub fn next_back_if<P>(&mut self, pred: P) -> Option<(Range<usize>, char)>
+    where
+        P: FnOnce(char) -> bool,
+    {
+        let mut iter = *self;
+        let (r, c) = iter.next_back()?;
+        if pred(c) {
+            *self = iter;
+            Some((r, c))
+        } else {
+            None
+        }
+    }
+
+    /// Consumes and returns the next [`char`] and its start and end byte
+    /// positions, only if it is equal to `expected`.
+    ///
+    /// Otherwise, the iterator is left untouched and `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "<=";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.next_if_eq('<'), Some((0..1, '<')));
+    /// assert_eq!(chars.next_if_eq('<'), None);
+    /// assert_eq!(chars.next_if_eq('='), Some((1..2, '=')));
+    /// ```
+    #[inline]
+    pub fn next_if_eq(&mut self, expected: char) -> Option<(Range<usize>, char)> {
+        self.next_if(|c| c == expected)
+    }
+
+    /// Consumes the longest prefix of [`char`]s, for which `pred` returns
+    /// `true`, and returns its byte range and substring.
+    ///
+    /// If `pred` returns `false` for the next [`char`], then nothing is
+    /// consumed and an empty range at the current position is returned.
+    ///
+    /// Afterwards, the iterator resumes at the first [`char`] for which
+    /// `pred` returned `false`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "foo_bar = 123";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// let (r, ident) = chars.take_while_range(|c| c.is_alphanumeric() || (c == '_'));
+    /// assert_eq!(r, 0..7);
+    /// assert_eq!(ident, "foo_bar");
+    ///
+    /// assert_eq!(chars.as_str(), " = 123");
+    /// assert_eq!(chars.take_while_range(char::is_alphanumeric), (7..7, ""));
+    /// ```
+    pub fn take_while_range<P>(&mut self, mut pred: P) -> (Range<usize>, &'a str)
+    where
+        P: FnMut(char) -> bool,
+    {
+        let start = self.start;
+        while self.next_if(&mut pred).is_some() {}
+        (start..self.start, &self.text[start..self.start])
+    }
+
+    /// Consumes the next [`char`] only if it is equal to `c`, and returns
+    /// its byte range.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "∈a";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.consume_char('a'), None);
+    /// assert_eq!(chars.consume_char('∈'), Some(0..3));
+    /// assert_eq!(chars.as_str(), "a");
+    /// ```
+    #[inline]
+    pub fn consume_char(&mut self, c: char) -> Option<Range<usize>> {
+        self.next_if_eq(c).map(|(r, _c)| r)
+    }
+
+    /// Consumes `s` only if the remaining substring starts with `s`,
+    /// and returns its byte range.
+    ///
+    /// If the remaining substring does not start with all of `s`, then
+    /// nothing is consumed, i.e. a partially matching prefix is never
+    /// consumed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "a🗻b";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.consume_str("a🌏"), None);
+    /// assert_eq!(chars.as_str(), "a🗻b");
+    ///
+    /// assert_eq!(chars.consume_str("a🗻"), Some(0..5));
+    /// assert_eq!(chars.as_str(), "b");
+    /// ```
+    #[inline]
+    pub fn consume_str(&mut self, s: &str) -> Option<Range<usize>> {
+        if !self.as_str().starts_with(s) {
+            return None;
+        }
+        let start = self.start;
+        self.start += s.len();
+        Some(start..self.start)
+    }
+
+    /// Consumes the longest prefix of [`char`]s, for which `pred` returns
+    /// `true`, and returns its byte range.
+    ///
+    /// If `pred` returns `false` for the next [`char`], then nothing is
+    /// consumed and an empty range at the current position is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "123abc";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.consume_while(|c| c.is_ascii_digit()), 0..3);
+    /// assert_eq!(chars.consume_while(|c| c.is_ascii_digit()), 3..3);
+    /// assert_eq!(chars.as_str(), "abc");
+    /// ```
+    #[inline]
+    pub fn consume_while<P>(&mut self, pred: P) -> Range<usize>
+    where
+        P: FnMut(char) -> bool,
+    {
+        let (r, _s) = self.take_while_range(pred);
+        r
+    }
+
+    /// Skips the longest prefix of [`char`]s, for which `pred` returns
+    /// `true`, and returns the byte range of the skipped [`char`]s.
+    ///
+    /// If `pred` returns `false` for the next [`char`], then nothing is
+    /// skipped and an empty range at the current position is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "  \t foo";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.skip_while_range(char::is_whitespace), 0..4);
+    /// assert_eq!(chars.as_str(), "foo");
+    ///
+    /// assert_eq!(chars.skip_while_range(char::is_whitespace), 4..4);
+    /// assert_eq!(chars.as_str(), "foo");
+    /// ```
+    #[inline]
+    pub fn skip_while_range<P>(&mut self, pred: P) -> Range<usize>
+    where
+        P: FnMut(char) -> bool,
+    {
+        self.take_while_range(pred).0
+    }
+
+    /// Skips the longest suffix of [`char`]s, for which `pred` returns
+    /// `true`, and returns the byte range of the skipped [`char`]s.
+    ///
+    /// If `pred` returns `false` for the last [`char`], then nothing is
+    /// skipped and an empty range at the current end position is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "foo \n";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.skip_back_while_range(char::is_whitespace), 3..5);
+    /// assert_eq!(chars.as_str(), "foo");
+    ///
+    /// assert_eq!(chars.skip_back_while_range(char::is_whitespace), 3..3);
+    /// assert_eq!(chars.as_str(), "foo");
+    /// ```
+    pub fn skip_back_while_range<P>(&mut self, mut pred: P) -> Range<usize>
+    where
+        P: FnMut(char) -> bool,
+    {
+        let end = self.end;
+        while self.next_back_if(&mut pred).is_some() {}
+        self.end..end
+    }
+
+    /// Searches for the next match of `pat`, and returns the first [`char`]
+    /// of the match along with the start and end byte positions of the match.
+    ///
+    /// See [`CharPattern`] for the kinds of patterns, e.g. a [`char`],
+    /// a `&str`, or a predicate. For a `&str` the range spans the whole
+    /// substring, and an empty `&str` never matches, as it has no [`char`].
+    ///
+    /// This is similar to <code>[find()]</code>, except that ranges are
+    /// not computed for the skipped [`char`]s. The iterator is advanced
+    /// just past the match, or exhausted if none was found.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "foo/bar/baz";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.find_range(|c| c == '/'), Some((3..4, '/')));
+    /// assert_eq!(chars.as_str(), "bar/baz");
+    ///
+    /// assert_eq!(chars.find_range('/'), Some((7..8, '/')));
+    /// assert_eq!(chars.find_range('/'), None);
+    /// assert_eq!(chars.as_str(), "");
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.find_range("ba"), Some((4..6, 'b')));
+    /// assert_eq!(chars.as_str(), "r/baz");
+    /// ```
+    ///
+    /// [find()]: Iterator::find
+    pub fn find_range<P>(&mut self, mut pat: P) -> Option<(Range<usize>, char)>
+    where
+        P: CharPattern,
+    {
+        match pattern::find_char(self.as_str(), &mut pat) {
+            Some((r, c)) => {
+                let start = self.start + r.start;
+                self.start += r.end;
+                Some((start..self.start, c))
+            }
+            None => {
+                self.start = self.end;
+                None
+            }
+        }
+    }
+
+    /// Searches for the last match of `pat`, and returns the first [`char`]
+    /// of the match along with the start and end byte positions of the match.
+    ///
+    /// See [`find_range()`](Self::find_range) for more information about
+    /// patterns.
+    ///
+    /// This is similar to <code>[rev()].[find()]</code>, except that ranges are
+    /// not computed for the skipped [`char`]s. The iterator is advanced from
+    /// the back just past the match, or exhausted if none was found.
+    /// The front is left untouched.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "foo/bar/baz";
+    ///
+    /// let mut chars = text.char_ranges();
+    /// assert_eq!(chars.rfind_range(|c| c == '/'), Some((7..8, '/')));
+    /// assert_eq!(chars.as_str(), "foo/bar");
+    ///
+    /// assert_eq!(chars.rfind_range('/'), Some((3..4, '/')));
+    /// assert_eq!(chars.rfind_range('/'), None);
+    /// assert_eq!(chars.as_str(), "");
+    /// ```
+    ///
+    /// [rev()]: Iterator::rev
+    /// [find()]: Iterator::find
+    pub fn rfind_range<P>(&mut self, mut pat: P) -> Option<(Range<usize>, char)>
+    where
+        P: CharPattern,
+    {
+        match pattern::rfind_char(self.as_str(), &mut pat) {
+            Some((r, c)) => {
+                let end = self.start + r.end;
+                self.end = self.start + r.start;
+                Some((self.end..end, c))
+            }
+            None => {
+                self.end = self.start;
+                None
+            }
+        }
+    }
+
+    /// Returns an iterator over the remaining [`char`]s and their start and
+    /// end byte positions, with an offset applied to all positions.
+    ///
+    /// # Panics
+    ///
+    /// If `offset` plus the length of the [original text](Self::original_str)
+    /// overflows `usize`, then the returned iterator panics when producing
+    /// a position that overflows, in both debug and release builds.
+    /// Use [`try_offset()`] to detect this up front.
+    ///
+    /// See examples in the [crate root](crate).
+    ///
+    /// [`try_offset()`]: Self::try_offset
+    #[inline]
+    pub fn offset(self, offset: usize) -> CharRangesOffset<'a> {
+        CharRangesOffset { iter: self, offset }
+    }
+
+    /// Returns an iterator over the remaining [`char`]s and their start and
+    /// end byte positions, with an offset applied to all positions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `offset` plus the length of the
+    /// [original text](Self::original_str) overflows `usize`,
+    /// i.e. if any position could overflow.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "abc";
+    ///
+    /// let mut chars = text.char_ranges().try_offset(10).unwrap();
+    /// assert_eq!(chars.next(), Some((10..11, 'a')));
+    ///
+    /// assert!(text.char_ranges().try_offset(usize::MAX - 3).is_ok());
+    /// assert!(text.char_ranges().try_offset(usize::MAX - 2).is_err());
+    /// ```
+    #[inline]
+    pub fn try_offset(self, offset: usize) -> Result<CharRangesOffset<'a>, OffsetOverflowError> {
+        let len = self.text.len();
+        match offset.checked_add(len) {
+            Some(_) => Ok(self.offset(offset)),
+            None => Err(OffsetOverflowError::new(offset, len)),
+        }
+    }
+
+    /// Returns an iterator over the remaining [`char`]s and their start
+    /// and end byte positions, in reverse order.
+    ///
+    /// Unlike <code>.[rev()]</code>, the returned [`CharRangesRev`] retains
+    /// [`as_str()`](CharRangesRev::as_str) and [`offset()`](CharRangesRev::offset).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻∈🌏";
+    ///
+    /// let mut chars = text.char_ranges().reversed();
+    /// assert_eq!(chars.next(), Some((7..11, '🌏')));
+    /// assert_eq!(chars.as_str(), "🗻∈");
+    ///
+    /// assert_eq!(chars.next(), Some((4..7, '∈')));
+    /// assert_eq!(chars.as_str(), "🗻");
+    /// ```
+    ///
+    /// [rev()]: Iterator::rev
+    #[inline]
+    pub fn reversed(self) -> CharRangesRev<'a> {
+        self.offset(0).reversed()
+    }
+
+    /// Returns an iterator over the start and end byte positions of the
+    /// remaining [`char`]s.
+    ///
+    /// Unlike <code>.[map]\(|(r, _)| r)</code>, the returned [`Ranges`]
+    /// retains [`as_str()`](Ranges::as_str) and [`offset()`](Ranges::offset),
+    /// and only inspects the leading bytes of [`char`]s.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻∈🌏";
+    ///
+    /// let mut ranges = text.char_ranges().ranges();
+    /// assert_eq!(ranges.next(), Some(0..4));
+    /// assert_eq!(ranges.as_str(), "∈🌏");
+    ///
+    /// assert_eq!(ranges.next(), Some(4..7));
+    /// assert_eq!(ranges.next(), Some(7..11));
+    /// assert_eq!(ranges.next(), None);
+    /// ```
+    ///
+    /// [map]: Iterator::map
+    #[inline]
+    pub fn ranges(self) -> Ranges<'a> {
+        self.offset(0).ranges()
+    }
+
+    /// Returns an iterator over the remaining [`char`]s and their start
+    /// and end byte positions, which implements [`ExactSizeIterator`].
+    ///
+    /// The remaining [`char`]s are counted once, i.e. this is _O(n)_.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻∈🌏";
+    ///
+    /// let mut chars = text.char_ranges().counted();
+    /// assert_eq!(chars.len(), 3);
+    ///
+    /// assert_eq!(chars.next(), Some((0..4, '🗻')));
+    /// assert_eq!(chars.len(), 2);
+    ///
+    /// assert_eq!(chars.next_back(), Some((7..11, '🌏')));
+    /// assert_eq!(chars.len(), 1);
+    /// ```
+    #[inline]
+    pub fn counted(self) -> CountedCharRanges<'a> {
+        self.offset(0).counted()
+    }
+
+    /// Returns an iterator over the remaining [`char`]s as [`Spanned<char>`],
+    /// instead of <code>([Range]&lt;usize&gt;, [char])</code> tuples.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{CharRangesExt, Spanned};
+    ///
+    /// let text = "🗻∈🌏";
+    ///
+    /// let mut chars = text.char_ranges().spanned();
+    /// assert_eq!(chars.next(), Some(Spanned::new(0..4, '🗻')));
+    /// assert_eq!(chars.next_back(), Some(Spanned::new(7..11, '🌏')));
+    /// assert_eq!(chars.next(), Some(Spanned::new(4..7, '∈')));
+    /// assert_eq!(chars.next(), None);
+    /// ```
+    #[inline]
+    pub fn spanned(self) -> SpannedCharRanges<'a> {
+        self.offset(0).spanned()
+    }
+
+    /// Returns an iterator over the remaining [`char`]s and their start
+    /// and end byte positions converted into the [`Span`] `S`.
+    ///
+    /// For [`CharRangesOffset`], the offset is applied before converting.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a range cannot be converted into `S`, e.g. if a position
+    /// overflows <code>[Range]&lt;[u32]&gt;</code>. See the [`Span`] impls.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻∈🌏";
+    ///
+    /// let mut chars = text.char_ranges().map_span::<(usize, usize)>();
+    /// assert_eq!(chars.next(), Some(((0, 4), '🗻')));
+    /// assert_eq!(chars.next_back(), Some(((7, 11), '🌏')));
+    /// ```
+    #[inline]
+    pub fn map_span<S>(self) -> CharRangesAs<'a, S>
+    where
+        S: Span,
+    {
+        self.offset(0).map_span()
+    }
+
+    /// Returns a [`Scanner`] over the remaining [`char`]s, for grouping
+    /// [`char`]s into tokens.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "foo = 12";
+    ///
+    /// let mut scanner = text.char_ranges().scanner();
+    ///
+    /// scanner.accept_while(char::is_alphabetic);
+    /// assert_eq!(scanner.finish_token(), (0..3, "foo"));
+    ///
+    /// scanner.accept_while(char::is_whitespace);
+    /// scanner.finish_token();
+    ///
+    /// assert!(scanner.accept('='));
+    /// assert_eq!(scanner.finish_token(), (4..5, "="));
+    /// ```
+    #[inline]
+    pub fn scanner(self) -> Scanner<'a> {
+        self.offset(0).scanner()
+    }
+
+    /// Returns an iterator over the remaining [`char`]s, their start and
+    /// end byte positions, and their [`LineColumn`] position.
+    ///
+    /// The [`LineColumn`] is zero-based and relative to the current
+    /// position, i.e. the next [`char`] is at line `0` and column `0`.
+    /// Use [`CharPositions::with_start()`] to resume mid-file.
+    ///
+    /// Only `'\n'` starts a new line, and columns count [`char`]s,
+    /// not bytes. See [`LineColumn`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{CharRangesExt, LineColumn};
+    ///
+    /// let text = "🗻\n∈🌏";
+    ///
+    /// let mut chars = text.char_ranges().char_positions();
+    /// assert_eq!(chars.next(), Some((0..4, LineColumn::new(0, 0), '🗻')));
+    /// assert_eq!(chars.next(), Some((4..5, LineColumn::new(0, 1), '\n')));
+    /// assert_eq!(chars.next(), Some((5..8, LineColumn::new(1, 0), '∈')));
+    /// assert_eq!(chars.next(), Some((8..12, LineColumn::new(1, 1), '🌏')));
+    /// assert_eq!(chars.next(), None);
+    /// ```
+    #[inline]
+    pub fn char_positions(self) -> CharPositions<'a> {
+        self.offset(0).char_positions()
+    }
+
+    /// Returns an iterator over the remaining [`char`]s, their start and
+    /// end byte positions, and their start and end visual columns.
+    ///
+    /// A `'\t'` advances to the next multiple of `tab_width`, a `'\n'`
+    /// resets the column, and every other [`char`] advances by a single
+    /// column. Columns are zero-based and relative to the current position.
+    /// See [`column_at()`] to get the column of a single position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tab_width` is `0`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻\t∈\n\t🌏";
+    ///
+    /// let mut chars = text.char_ranges().visual_columns(4);
+    /// assert_eq!(chars.next(), Some((0..4, '🗻', 0, 1)));
+    /// assert_eq!(chars.next(), Some((4..5, '\t', 1, 4)));
+    /// assert_eq!(chars.next(), Some((5..8, '∈', 4, 5)));
+    /// assert_eq!(chars.next(), Some((8..9, '\n', 5, 6)));
+    /// assert_eq!(chars.next(), Some((9..10, '\t', 0, 4)));
+    /// assert_eq!(chars.next(), Some((10..14, '🌏', 4, 5)));
+    /// assert_eq!(chars.next(), None);
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn visual_columns(self, tab_width: usize) -> VisualColumns<'a> {
+        self.offset(0).visual_columns(tab_width)
+    }
+
+    /// Returns an iterator over the remaining substring soft wrapped into
+    /// lines no wider than `max_width`, and their start and end byte
+    /// positions.
+    ///
+    /// Lines are broken at whitespace when possible, otherwise mid-word,
+    /// but never within a [`char`]. Every `'\n'` forces a break. With the
+    /// `unicode-width` feature, widths are measured in terminal cells,
+    /// otherwise in [`char`]s.
+    ///
+    /// The whitespace at a break is dropped, see [`WrapRanges`] for
+    /// exactly which [`char`]s are dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_width` is `0`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "foo bar bazqux\nquux";
+    ///
+    /// let mut lines = text.char_ranges().wrap_ranges(5);
+    /// assert_eq!(lines.next(), Some((0..3, "foo")));
+    /// assert_eq!(lines.next(), Some((4..7, "bar")));
+    /// assert_eq!(lines.next(), Some((8..13, "bazqu")));
+    /// assert_eq!(lines.next(), Some((13..14, "x")));
+    /// assert_eq!(lines.next(), Some((15..19, "quux")));
+    /// assert_eq!(lines.next(), None);
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn wrap_ranges(self, max_width: usize) -> WrapRanges<'a> {
+        self.offset(0).wrap_ranges(max_width)
+    }
+
+    /// Returns an iterator over the remaining [`char`]s and their start
+    /// and end byte positions, where `"\r\n"` and `'\r'` are normalized
+    /// to `'\n'`.
+    ///
+    /// See [`CharRangesExt::char_ranges_normalized_newlines()`] for more information.
+    #[inline]
+    pub fn normalize_newlines(self) -> NormalizedNewlines<'a> {
+        self.offset(0).normalize_newlines()
+    }
+
+    /// Returns an iterator over the remaining line terminators, producing
+    /// the start and end byte positions of each terminator, along with
+    /// its kind.
+    ///
+    /// A `'\r'` immediately followed by `'\n'` is a single
+    /// [`LineEnding::CrLf`], never [`LineEnding::Cr`] followed by
+    /// [`LineEnding::Lf`].
+    ///
+    /// See [`LineEndingRanges`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{CharRangesExt, LineEnding};
+    ///
+    /// let text = "a\r\nb\rc\n";
+    ///
+    /// let mut endings = text.char_ranges().line_ending_ranges();
+    /// assert_eq!(endings.next(), Some((1..3, LineEnding::CrLf)));
+    /// assert_eq!(endings.next(), Some((4..5, LineEnding::Cr)));
+    /// assert_eq!(endings.next(), Some((6..7, LineEnding::Lf)));
+    /// assert_eq!(endings.next(), None);
+    /// ```
+    #[inline]
+    pub fn line_ending_ranges(self) -> LineEndingRanges<'a> {
+        self.offset(0).line_ending_ranges()
+    }
+
+    /// Returns an iterator over the remaining [`char`]s, their start and
+    /// end [`char`] indices, and their start and end byte positions.
+    ///
+    /// This is useful when interfacing with APIs indexing strings by
+    /// [`char`] (code point) instead of by byte.
+    ///
+    /// The [`char`] indices are relative to the current position, i.e. the
+    /// next [`char`] is at index `0`. Use
+    /// [`char_index_ranges_offset()`](Self::char_index_ranges_offset)
+    /// to offset the [`char`] indices independently of byte positions.
+    ///
+    /// Iterating from the back requires the [`char`] index of the last
+    /// [`char`], so the first call to [`next_back()`], [`nth_back()`],
+    /// or [`last()`] counts the remaining [`char`]s, i.e. is _O(n)_.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "aØb";
+    ///
+    /// let mut chars = text.char_ranges().char_index_ranges();
+    /// assert_eq!(chars.next(), Some((0..1, 0..1, 'a')));
+    /// assert_eq!(chars.next(), Some((1..2, 1..3, 'Ø')));
+    /// assert_eq!(chars.next(), Some((2..3, 3..4, 'b')));
+    /// assert_eq!(chars.next(), None);
+    /// ```
+    ///
+    /// [`next_back()`]: DoubleEndedIterator::next_back
+    /// [`nth_back()`]: DoubleEndedIterator::nth_back
+    /// [`last()`]: Iterator::last
+    #[inline]
+    pub fn char_index_ranges(self) -> CharIndexRanges<'a> {
+        self.offset(0).char_index_ranges()
+    }
+
+    /// Returns an iterator over the remaining [`char`]s, their start and
+    /// end [`char`] indices with `char_offset` applied, and their start
+    /// and end byte positions.
+    ///
+    /// See [`char_index_ranges()`](Self::char_index_ranges) for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "aØb🌏";
+    ///
+    /// // Start at 'b', which is char index 2 and byte position 3
+    /// let mut chars = text[3..].char_ranges_offset(3).char_index_ranges_offset(2);
+    /// assert_eq!(chars.next(), Some((2..3, 3..4, 'b')));
+    /// assert_eq!(chars.next(), Some((3..4, 4..8, '🌏')));
+    /// ```
+    #[inline]
+    pub fn char_index_ranges_offset(self, char_offset: usize) -> CharIndexRanges<'a> {
+        self.offset(0).char_index_ranges_offset(char_offset)
+    }
+
+    /// Returns an iterator over the remaining [`char`]s, their [`char`]
+    /// index, and their start and end byte positions.
+    ///
+    /// Unlike <code>.[enumerate()]</code>, the [`char`] index remains
+    /// correct when skipping using [`nth()`] and when iterating from
+    /// the back. See [`char_index_ranges()`](Self::char_index_ranges)
+    /// for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻∈🌏 World";
+    ///
+    /// let mut chars = text.char_ranges().enumerate_chars();
+    /// assert_eq!(chars.nth(1), Some((1, 4..7, '∈')));
+    /// assert_eq!(chars.next_back(), Some((8, 16..17, 'd')));
+    /// assert_eq!(chars.next(), Some((2, 7..11, '🌏')));
+    /// assert_eq!(chars.nth_back(1), Some((6, 14..15, 'r')));
+    /// assert_eq!(chars.as_str(), " Wo");
+    /// ```
+    ///
+    /// [enumerate()]: Iterator::enumerate
+    /// [`nth()`]: Iterator::nth
+    #[inline]
+    pub fn enumerate_chars(self) -> EnumerateChars<'a> {
+        self.offset(0).enumerate_chars()
+    }
+
+    /// Returns an iterator over the remaining [`char`]s and their start
+    /// and end byte positions, if the remaining text is entirely ASCII,
+    /// otherwise `None`.
+    ///
+    /// The returned [`AsciiCharRanges`] yields the same items, but as
+    /// every [`char`] is a single byte, it implements [`ExactSizeIterator`],
+    /// and [`nth()`] and [`nth_back()`] are _O(1)_.
+    ///
+    /// Checking the text is _O(n)_, so this pays off when the iterator
+    /// is used for counting or skipping, or when the text is long.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "Hello World";
+    ///
+    /// let mut chars = text.char_ranges().ascii().unwrap();
+    /// assert_eq!(chars.len(), 11);
+    /// assert_eq!(chars.nth(6), Some((6..7, 'W')));
+    /// assert_eq!(chars.len(), 4);
+    ///
+    /// assert!("Hello 🌏".char_ranges().ascii().is_none());
+    /// ```
+    ///
+    /// [`nth()`]: Iterator::nth
+    /// [`nth_back()`]: DoubleEndedIterator::nth_back
+    #[inline]
+    pub fn ascii(self) -> Option<AsciiCharRanges<'a>> {
+        self.offset(0).ascii()
+    }
+
+    /// Returns an iterator over the occurrences of `c` in the remaining
+    /// text, and their start and end byte positions.
+    ///
+    /// This is similar to <code>.[filter()]</code>, except that the
+    /// [`char`]s in between are not decoded. See [`find_char_range()`]
+    /// for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻\n∈\n🌏";
+    ///
+    /// let mut lines = text.char_ranges().match_char_ranges('\n');
+    /// assert_eq!(lines.next(), Some((4..5, '\n')));
+    /// assert_eq!(lines.next(), Some((8..9, '\n')));
+    /// assert_eq!(lines.next(), None);
+    /// ```
+    ///
+    /// [filter()]: Iterator::filter
+    #[inline]
+    pub fn match_char_ranges(self, c: char) -> MatchCharRanges<'a> {
+        self.offset(0).match_char_ranges(c)
+    }
+
+    /// Returns an iterator over maximal runs of identical consecutive
+    /// [`char`]s in the remaining text, along with the start and end byte
+    /// positions of the whole run, and the number of [`char`]s in it.
+    ///
+    /// See [`DedupRuns`] for how runs are produced from both ends.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "a ===== 🌏🌏";
+    ///
+    /// let mut runs = text.char_ranges().dedup_runs();
+    /// assert_eq!(runs.next(), Some((0..1, 'a', 1)));
+    /// assert_eq!(runs.next(), Some((1..2, ' ', 1)));
+    /// assert_eq!(runs.next(), Some((2..7, '=', 5)));
+    /// assert_eq!(runs.next_back(), Some((8..16, '🌏', 2)));
+    /// assert_eq!(runs.next(), Some((7..8, ' ', 1)));
+    /// assert_eq!(runs.next(), None);
+    /// ```
+    #[inline]
+    pub fn dedup_runs(self) -> DedupRuns<'a> {
+        self.offset(0).dedup_runs()
+    }
+
+    /// Returns an iterator over the remaining [`char`]s and their start
+    /// and end byte positions, which can peek the next item from either end.
+    ///
+    /// Unlike <code>.[peekable()]</code>, the returned [`PeekableCharRanges`]
+    /// retains [`as_str()`](PeekableCharRanges::as_str) and
+    /// [`offset()`](PeekableCharRanges::offset), and is double-ended.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻∈🌏";
+    ///
+    /// let mut chars = text.char_ranges().peekable_ranges();
+    /// assert_eq!(chars.peek(), Some(&(0..4, '🗻')));
+    /// assert_eq!(chars.peek_back(), Some(&(7..11, '🌏')));
+    ///
+    /// // Peeked chars are still included
+    /// assert_eq!(chars.as_str(), "🗻∈🌏");
+    /// ```
+    ///
+    /// [peekable()]: Iterator::peekable
+    #[inline]
+    pub fn peekable_ranges(self) -> PeekableCharRanges<'a> {
+        self.offset(0).peekable_ranges()
+    }
+
+    /// Returns an iterator over the remaining [`char`]s and their start
+    /// and end byte positions, which can peek up to `N` items ahead.
+    ///
+    /// Peeked items are buffered in a fixed-size buffer, i.e. no allocation
+    /// is required. [`as_str()`](MultiPeekCharRanges::as_str) still includes
+    /// peeked items, until they are returned by [`next()`](Iterator::next).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "<<= 1";
+    ///
+    /// let mut chars = text.char_ranges().multipeek::<3>();
+    /// assert_eq!(chars.peek_nth(0), Some(&(0..1, '<')));
+    /// assert_eq!(chars.peek_nth(1), Some(&(1..2, '<')));
+    /// assert_eq!(chars.peek_nth(2), Some(&(2..3, '=')));
+    /// assert_eq!(chars.as_str(), "<<= 1");
+    ///
+    /// assert_eq!(chars.next(), Some((0..1, '<')));
+    /// assert_eq!(chars.as_str(), "<= 1");
+    /// ```
+    #[inline]
+    pub fn multipeek<const N: usize>(self) -> MultiPeekCharRanges<'a, N> {
+        self.offset(0).multipeek()
+    }
+
+    /// Returns an iterator over overlapping pairs of adjacent [`char`]s
+    /// in the remaining text, and their start and end byte positions.
+    ///
+    /// For `n` [`char`]s, `n - 1` pairs are produced, i.e. nothing is
+    /// produced for fewer than 2 [`char`]s.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "x:=🌏";
+    ///
+    /// let mut pairs = text.char_ranges().char_pairs();
+    /// assert_eq!(pairs.next(), Some(((0..1, 'x'), (1..2, ':'))));
+    /// assert_eq!(pairs.next(), Some(((1..2, ':'), (2..3, '='))));
+    /// assert_eq!(pairs.next(), Some(((2..3, '='), (3..7, '🌏'))));
+    /// assert_eq!(pairs.next(), None);
+    /// ```
+    #[inline]
+    pub fn char_pairs(self) -> CharPairs<'a> {
+        self.offset(0).char_pairs()
+    }
+
+    /// Returns an iterator over the remaining [`char`]s and their start
+    /// and end byte positions, along with the previous and next [`char`]s.
+    ///
+    /// The previous [`char`] is `None` for the first remaining [`char`],
+    /// and the next [`char`] is `None` for the last remaining [`char`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{CharContext, CharRangesExt};
+    ///
+    /// let text = "it's";
+    ///
+    /// let mut chars = text.char_ranges().with_context();
+    /// assert_eq!(
+    ///     chars.nth(2),
+    ///     Some(CharContext {
+    ///         prev: Some((1..2, 't')),
+    ///         current: (2..3, '\''),
+    ///         next: Some((3..4, 's')),
+    ///     }),
+    /// );
+    /// assert_eq!(chars.next().unwrap().next, None);
+    /// ```
+    #[inline]
+    pub fn with_context(self) -> WithContext<'a> {
+        self.offset(0).with_context()
+    }
+
+    /// Returns an iterator over the remaining [`char`]s mapped to lowercase,
+    /// and the start and end byte positions of the source [`char`] they
+    /// originate from.
+    ///
+    /// See [`CharRangesExt::char_ranges_lowercase()`] for more information.
+    #[inline]
+    pub fn lowercase(self) -> CharRangesLowercase<'a> {
+        self.offset(0).lowercase()
+    }
+
+    /// Returns an iterator over the remaining [`char`]s mapped to uppercase,
+    /// and the start and end byte positions of the source [`char`] they
+    /// originate from.
+    ///
+    /// See [`CharRangesExt::char_ranges_uppercase()`] for more information.
+    #[inline]
+    pub fn uppercase(self) -> CharRangesUppercase<'a> {
+        self.offset(0).uppercase()
+    }
+
+    /// Returns an iterator over the [`char`]s produced by [`char::escape_debug()`]
+    /// for the remaining [`char`]s, and the start and end byte positions of the
+    /// source [`char`] they originate from.
+    ///
+    /// See [`CharRangesExt::escape_debug_ranges()`] for more information.
+    #[inline]
+    pub fn escape_debug(self) -> EscapeDebugRanges<'a> {
+        self.offset(0).escape_debug()
+    }
+
+    /// Returns an iterator over the decoded [`char`]s of the remaining
+    /// substring, as the body of a string literal, and the start and end
+    /// byte positions of their source.
+    ///
+    /// See [`CharRangesExt::unescape_ranges()`] for more information.
+    #[inline]
+    pub fn unescape(self) -> UnescapeRanges<'a> {
+        self.offset(0).unescape()
+    }
+
+    /// Returns an iterator over the percent-decoded [`char`]s of the
+    /// remaining substring, and the start and end byte positions of
+    /// their source.
+    ///
+    /// See [`CharRangesExt::percent_decode_ranges()`] for more information.
+    #[cfg(feature = "percent")]
+    #[inline]
+    pub fn percent_decode(self) -> PercentDecodeRanges<'a> {
+        self.offset(0).percent_decode()
+    }
+
+    /// Returns an iterator over the remaining suspicious [`char`]s, their
+    /// start and end byte positions, and their [`CharIssue`].
+    ///
+    /// See [`CharRangesExt::suspicious_char_ranges()`] for more information.
+    #[inline]
+    pub fn suspicious(self) -> SuspiciousCharRanges<'a> {
+        self.offset(0).suspicious()
+    }
+
+    /// Returns an iterator over the non-overlapping case-insensitive
+    /// matches of `needle` in the remaining substring, and their start
+    /// and end byte positions.
+    ///
+    /// See [`CharRangesExt::caseless_match_ranges()`] for more information.
+    #[inline]
+    pub fn caseless_matches<'b>(self, needle: &'b str) -> CaselessMatchRanges<'a, 'b> {
+        self.offset(0).caseless_matches(needle)
+    }
+
+    /// Returns an iterator over all matches of `needle` in the remaining
+    /// substring, including overlapping matches, and their start and end
+    /// byte positions.
+    ///
+    /// See [`CharRangesExt::match_overlapping_ranges()`] for more information.
+    #[inline]
+    pub fn overlapping_matches<'b>(self, needle: &'b str) -> MatchOverlappingRanges<'a, 'b> {
+        self.offset(0).overlapping_matches(needle)
+    }
+
+    /// Returns an iterator over the remaining [`char`]s in Normalization
+    /// Form C (NFC), and the start and end byte positions of the source
+    /// [`char`]s they were derived from.
+    ///
+    /// See [`CharRangesExt::nfc_char_ranges()`] for more information.
+    #[cfg(feature = "unicode-normalization")]
+    #[inline]
+    pub fn nfc(self) -> NormalizedCharRanges<'a> {
+        self.offset(0).nfc()
+    }
+
+    /// Returns an iterator over the remaining [`char`]s in Normalization
+    /// Form D (NFD), and the start and end byte positions of the source
+    /// [`char`]s they were derived from.
+    ///
+    /// See [`CharRangesExt::nfd_char_ranges()`] for more information.
+    #[cfg(feature = "unicode-normalization")]
+    #[inline]
+    pub fn nfd(self) -> NormalizedCharRanges<'a> {
+        self.offset(0).nfd()
+    }
+
+    /// Returns an iterator over the remaining [`char`]s, their start and
+    /// end byte positions, and the number of terminal cells they occupy.
+    ///
+    /// See [`CharRangesExt::char_width_ranges()`] for more information.
+    #[cfg(feature = "unicode-width")]
+    #[inline]
+    pub fn widths(self) -> CharWidthRanges<'a> {
+        self.offset(0).widths()
+    }
+
+    /// Returns an iterator over maximal runs of the remaining [`char`]s
+    /// sharing the same script, producing the start and end byte
+    /// positions of each run, the script, and the substring.
+    ///
+    /// See [`CharRangesExt::script_runs()`] for more information.
+    #[cfg(feature = "unicode-script")]
+    #[inline]
+    pub fn script_runs(self) -> ScriptRuns<'a> {
+        self.offset(0).script_runs()
+    }
+
+    /// Returns an iterator over the remaining [`char`]s, which are not
+    /// valid in an identifier, producing their start and end byte
+    /// positions, along with why they are invalid.
+    ///
+    /// The next [`char`] is the first [`char`] of the identifier.
+    ///
+    /// See [`CharRangesExt::invalid_ident_char_ranges()`] for more information.
+    #[cfg(feature = "unicode-ident")]
+    #[inline]
+    pub fn invalid_ident_chars(self, underscore_start: bool) -> InvalidIdentCharRanges<'a> {
+        self.offset(0).invalid_ident_chars(underscore_start)
+    }
+
+    /// Returns an iterator over the remaining [`char`]s, their start and
+    /// end byte positions, and the start and end terminal cell columns
+    /// they occupy.
+    ///
+    /// Each [`char`] advances the column by its width, see
+    /// [`CharRangesExt::char_width_ranges()`]. A `'\n'` resets the column.
+    /// Columns are zero-based and relative to the current position.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "中e\u{301}\n🌏";
+    ///
+    /// let mut chars = text.char_ranges().width_columns();
+    /// assert_eq!(chars.next(), Some((0..3, '中', 0, 2)));
+    /// assert_eq!(chars.next(), Some((3..4, 'e', 2, 3)));
+    /// assert_eq!(chars.next(), Some((4..6, '\u{301}', 3, 3)));
+    /// assert_eq!(chars.next(), Some((6..7, '\n', 3, 3)));
+    /// assert_eq!(chars.next(), Some((7..11, '🌏', 0, 2)));
+    /// assert_eq!(chars.next(), None);
+    /// ```
+    #[cfg(feature = "unicode-width")]
+    #[inline]
+    pub fn width_columns(self) -> WidthColumns<'a> {
+        self.offset(0).width_columns()
+    }
+
+    /// Returns an iterator over maximal runs of the remaining [`char`]s,
+    /// where each run either entirely matches `pred` or entirely doesn't.
+    ///
+    /// The runs alternate between matching and not matching, and together
+    /// they cover the whole remaining substring.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "foo 123  bar";
+    ///
+    /// let mut runs = text.char_ranges().char_runs(char::is_whitespace);
+    /// assert_eq!(runs.next(), Some((0..3, "foo", false)));
+    /// assert_eq!(runs.next(), Some((3..4, " ", true)));
+    /// assert_eq!(runs.next(), Some((4..7, "123", false)));
+    /// assert_eq!(runs.next(), Some((7..9, "  ", true)));
+    /// assert_eq!(runs.next(), Some((9..12, "bar", false)));
+    /// assert_eq!(runs.next(), None);
+    /// ```
+    #[inline]
+    pub fn char_runs<P>(self, pred: P) -> CharRuns<'a, P>
+    where
+        P: FnMut(char) -> bool,
+    {
+        self.offset(0).char_runs(pred)
+    }
+
+    /// Returns an iterator over maximal runs of the remaining [`char`]s
+    /// of the same [`CharClass`], e.g. alphabetic or whitespace.
+    ///
+    /// The runs together cover the whole remaining substring. See
+    /// [`CharClass::of()`] for the exact rules, and [`classify_runs_by()`]
+    /// to customize the classification.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{CharClass, CharRangesExt};
+    ///
+    /// let text = "x = 42; // 🌏";
+    ///
+    /// let mut runs = text.char_ranges().classify_runs();
+    /// assert_eq!(runs.next(), Some((0..1, "x", CharClass::Alphabetic)));
+    /// assert_eq!(runs.next(), Some((1..2, " ", CharClass::Whitespace)));
+    /// assert_eq!(runs.next(), Some((2..3, "=", CharClass::Punctuation)));
+    /// assert_eq!(runs.next(), Some((3..4, " ", CharClass::Whitespace)));
+    /// assert_eq!(runs.next(), Some((4..6, "42", CharClass::Numeric)));
+    /// assert_eq!(runs.next(), Some((6..7, ";", CharClass::Punctuation)));
+    /// assert_eq!(runs.next(), Some((7..8, " ", CharClass::Whitespace)));
+    /// assert_eq!(runs.next(), Some((8..10, "//", CharClass::Punctuation)));
+    /// assert_eq!(runs.next(), Some((10..11, " ", CharClass::Whitespace)));
+    /// assert_eq!(runs.next(), Some((11..15, "🌏", CharClass::Other)));
+    /// assert_eq!(runs.next(), None);
+    /// ```
+    ///
+    /// [`classify_runs_by()`]: Self::classify_runs_by
+    #[inline]
+    pub fn classify_runs(self) -> ClassifyRuns<'a, fn(char) -> CharClass> {
+        self.offset(0).classify_runs()
+    }
+
+    /// Returns an iterator over maximal runs of the remaining [`char`]s
+    /// of the same [`CharClass`], where `classify` decides the class of
+    /// each [`char`].
+    ///
+    /// See [`classify_runs()`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{CharClass, CharRangesExt};
+    ///
+    /// let text = "foo_bar";
+    ///
+    /// // Treat '_' as part of words
+    /// let mut runs = text.char_ranges().classify_runs_by(|c| match c {
+    ///     '_' => CharClass::Alphabetic,
+    ///     c => CharClass::of(c),
+    /// });
+    /// assert_eq!(runs.next(), Some((0..7, "foo_bar", CharClass::Alphabetic)));
+    /// assert_eq!(runs.next(), None);
+    /// ```
+    ///
+    /// [`classify_runs()`]: Self::classify_runs
+    #[inline]
+    pub fn classify_runs_by<F>(self, classify: F) -> ClassifyRuns<'a, F>
+    where
+        F: FnMut(char) -> CharClass,
+    {
+        self.offset(0).classify_runs_by(classify)
+    }
+
+    /// Returns an iterator over the byte ranges of maximal runs of the
+    /// remaining [`char`]s matching `pred`, where [`char`]s not matching
+    /// `pred` are skipped.
+    ///
+    /// Unlike [`char_runs()`](Self::char_runs), only the matching runs
+    /// are produced.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "foo 123  bar";
+    ///
+    /// let mut ranges = text.char_ranges().coalesce_by(char::is_whitespace);
+    /// assert_eq!(ranges.next(), Some(3..4));
+    /// assert_eq!(ranges.next(), Some(7..9));
+    /// assert_eq!(ranges.next(), None);
+    /// ```
+    #[inline]
+    pub fn coalesce_by<P>(self, pred: P) -> CoalesceBy<'a, P>
+    where
+        P: FnMut(char) -> bool,
+    {
+        self.offset(0).coalesce_by(pred)
+    }
+
+    /// Returns an iterator over groups of adjacent remaining [`char`]s,
+    /// where `same_group(a, b)` decides whether the [`char`] `b` continues
+    /// the group of the preceding [`char`] `a`, i.e. the same as
+    /// [`slice::chunk_by()`] for [`char`]s.
+    ///
+    /// Unlike [`char_runs()`](Self::char_runs), which classifies each
+    /// [`char`] independently, this compares adjacent [`char`]s. The groups
+    /// together cover the whole remaining substring.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "camelCaseWörds";
+    ///
+    /// // Lowercase followed by uppercase starts a new group
+    /// let mut words = text
+    ///     .char_ranges()
+    ///     .chunk_by(|a, b| !(a.is_lowercase() && b.is_uppercase()));
+    /// assert_eq!(words.next(), Some((0..5, "camel")));
+    /// assert_eq!(words.next(), Some((5..9, "Case")));
+    /// assert_eq!(words.next(), Some((9..15, "Wörds")));
+    /// assert_eq!(words.next(), None);
+    /// ```
+    #[inline]
+    pub fn chunk_by<F>(self, same_group: F) -> ChunkBy<'a, F>
+    where
+        F: FnMut(char, char) -> bool,
+    {
+        self.offset(0).chunk_by(same_group)
+    }
+
+    /// Returns an iterator over the remaining lines, i.e. their start and
+    /// end byte positions, along with the start and end byte positions
+    /// of their indentation, and what it consists of.
+    ///
+    /// See [`IndentationRanges`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{CharRangesExt, IndentKind};
+    ///
+    /// let text = "if x {\r\n    y();\n\n\t z();\n}";
+    ///
+    /// let mut lines = text.char_ranges().indentation_ranges();
+    /// assert_eq!(lines.next(), Some((0..6, 0..0, IndentKind::None)));
+    /// assert_eq!(lines.next(), Some((8..16, 8..12, IndentKind::Spaces(4))));
+    /// // Blank line
+    /// assert_eq!(lines.next(), Some((17..17, 17..17, IndentKind::None)));
+    /// assert_eq!(lines.next(), Some((18..24, 18..20, IndentKind::Mixed)));
+    /// assert_eq!(lines.next(), Some((25..26, 25..25, IndentKind::None)));
+    /// assert_eq!(lines.next(), None);
+    /// ```
+    #[inline]
+    pub fn indentation_ranges(self) -> IndentationRanges<'a> {
+        self.offset(0).indentation_ranges()
+    }
+
+    /// Returns an iterator over the remaining lines, which end with
+    /// whitespace, producing the zero-based line number and the start
+    /// and end byte positions of the trailing whitespace, e.g. for
+    /// linting and removing it.
+    ///
+    /// The `'\r'` of a `"\r\n"` terminator is never included, and lines
+    /// which only contain whitespace produce their whole content.
+    ///
+    /// See [`TrailingWhitespaceRanges`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "foo  \r\nbar\n\t\nbaz\u{A0}";
+    ///
+    /// let mut ranges = text.char_ranges().trailing_whitespace_ranges();
+    /// assert_eq!(ranges.next(), Some((0, 3..5)));
+    /// assert_eq!(ranges.next(), Some((2, 11..12)));
+    /// assert_eq!(ranges.next(), Some((3, 16..18)));
+    /// assert_eq!(ranges.next(), None);
+    /// ```
+    #[inline]
+    pub fn trailing_whitespace_ranges(self) -> TrailingWhitespaceRanges<'a> {
+        self.offset(0).trailing_whitespace_ranges()
+    }
+
+    /// Returns an iterator over the remaining lines, which are longer than
+    /// `max_chars` [`char`]s, producing the zero-based line number, the
+    /// start and end byte positions of the line, the start and end byte
+    /// positions of the overflow, and the number of [`char`]s in the line.
+    ///
+    /// The overflow starts after the `max_chars`-th [`char`], e.g. for
+    /// only underlining the excess. Lines of exactly `max_chars` [`char`]s
+    /// are not produced, and `"\r\n"` terminators are never included.
+    ///
+    /// Lengths are counted in [`char`]s, i.e. a `'\t'` counts as a single
+    /// [`char`]. See [`LongLineRanges`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "fn main() {\r\n    println!(\"🌏\");\r\n}";
+    ///
+    /// let mut ranges = text.char_ranges().long_line_ranges(16);
+    /// assert_eq!(ranges.next(), Some((1, 13..34, 32..34, 18)));
+    /// assert_eq!(&text[32..34], ");");
+    /// assert_eq!(ranges.next(), None);
+    /// ```
+    #[inline]
+    pub fn long_line_ranges(self, max_chars: usize) -> LongLineRanges<'a> {
+        self.offset(0).long_line_ranges(max_chars)
+    }
+
+    /// Returns an iterator over the remaining paragraphs, i.e. maximal
+    /// runs of non-blank lines, producing their start and end byte
+    /// positions, along with the substring.
+    ///
+    /// An empty line is always blank. If `whitespace_is_blank` is `true`,
+    /// then lines only containing whitespace are also blank.
+    ///
+    /// See [`ParagraphRanges`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "\nfoo\r\nbar\n  \n\nbaz\n";
+    ///
+    /// let mut paragraphs = text.char_ranges().paragraph_ranges(true);
+    /// assert_eq!(paragraphs.next(), Some((1..9, "foo\r\nbar")));
+    /// assert_eq!(paragraphs.next(), Some((14..17, "baz")));
+    /// assert_eq!(paragraphs.next(), None);
+    ///
+    /// let mut paragraphs = text.char_ranges().paragraph_ranges(false);
+    /// assert_eq!(paragraphs.next(), Some((1..12, "foo\r\nbar\n  ")));
+    /// assert_eq!(paragraphs.next(), Some((14..17, "baz")));
+    /// assert_eq!(paragraphs.next(), None);
+    /// ```
+    #[inline]
+    pub fn paragraph_ranges(self, whitespace_is_blank: bool) -> ParagraphRanges<'a> {
+        self.offset(0).paragraph_ranges(whitespace_is_blank)
+    }
+
+    /// Returns an iterator over the remaining clusters of a base [`char`]
+    /// followed by any combining marks, e.g. `"e\u{301}"`, producing their
+    /// start and end byte positions, along with the substring.
+    ///
+    /// This is a dependency-free approximation of [extended grapheme
+    /// clusters], which e.g. does not keep emoji sequences together.
+    ///
+    /// See [`LegacyClusterRanges`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "e\u{301}\u{323}🌏";
+    ///
+    /// let mut clusters = text.char_ranges().legacy_cluster_ranges();
+    /// assert_eq!(clusters.next(), Some((0..5, "e\u{301}\u{323}")));
+    /// assert_eq!(clusters.next(), Some((5..9, "🌏")));
+    /// assert_eq!(clusters.next(), None);
+    /// ```
+    ///
+    /// [extended grapheme clusters]: https://www.unicode.org/reports/tr29/#Grapheme_Cluster_Boundaries
+    #[inline]
+    pub fn legacy_cluster_ranges(self) -> LegacyClusterRanges<'a> {
+        self.offset(0).legacy_cluster_ranges()
+    }
+
+    /// Returns an iterator over every `n`th remaining [`char`] and its
+    /// start and end byte positions, starting with the next [`char`].
+    ///
+    /// This is like [`Iterator::step_by()`], except that the returned
+    /// iterator is double-ended and retains `as_str()`. Iterating from the
+    /// back produces the items of the forward sequence in reverse order,
+    /// i.e. the first item from the back is not necessarily the last
+    /// [`char`].
+    ///
+    /// See [`CharStepBy`] for more information.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is `0`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "a🗻b∈cd";
+    ///
+    /// let mut chars = text.char_ranges().char_step_by(2);
+    /// assert_eq!(chars.next(), Some((0..1, 'a')));
+    /// assert_eq!(chars.next(), Some((5..6, 'b')));
+    /// assert_eq!(chars.next(), Some((9..10, 'c')));
+    /// assert_eq!(chars.next(), None);
+    ///
+    /// // The last item is 'c' and not 'd'
+    /// let mut chars = text.char_ranges().char_step_by(2);
+    /// assert_eq!(chars.next_back(), Some((9..10, 'c')));
+    /// assert_eq!(chars.next_back(), Some((5..6, 'b')));
+    /// assert_eq!(chars.as_str(), "a🗻");
+    /// ```
+    #[inline]
+    pub fn char_step_by(self, n: usize) -> CharStepBy<'a> {
+        self.offset(0).char_step_by(n)
+    }
+}
+
+/// The length in bytes of the byte order mark `'\u{FEFF}'`.
+const BOM_LEN: usize = '\u{FEFF}'.len_utf8();
+
+/// Returns the start of the first line touched by `range`, along with
+/// the end of the last line touched by `range`, excluding and including
+/// its terminator. `range` must be on [`char`] boundaries.
+///
+/// A non-empty `range` ending at the start of a line does not touch it.
+fn expand_to_line(text: &str, range: &Range<usize>) -> (usize, (usize, usize)) {
+    let start = text[..range.start].rfind('\n').map_or(0, |i| i + 1);
+
+    // The start of the last `char` within the range
+    let last = match text[..range.end].chars().next_back() {
+        Some(c) if !range.is_empty() => range.end - c.len_utf8(),
+        _ => range.end,
+    };
+    let end = match text[last..].find('\n') {
+        Some(i) => {
+            let end = last + i;
+            if text[..end].ends_with('\r') && (end > start) {
+                (end - 1, end + 1)
+            } else {
+                (end, end + 1)
+            }
+        }
+        None => (text.len(), text.len()),
+    };
+
+    (start, end)
+}
+
+/// Returns the start and end positions of `bounds`, where an unbounded
+/// end is `len`.
+#[inline]
+fn bounds_to_range<R>(bounds: R, len: usize) -> Range<usize>
+where
+    R: RangeBounds<usize>,
+{
+    let start = match bounds.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.saturating_add(1),
+        Bound::Unbounded => 0,
+    };
+    let end = match bounds.end_bound() {
+        Bound::Included(&end) => end.saturating_add(1),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    start..end
+}
+
+/// Panics if `idx` is out of bounds of `text` or is not on a [`char`]
+/// boundary, in which case the message includes the straddled [`char`].
+#[inline]
+#[track_caller]
+fn assert_char_boundary(text: &str, idx: usize) {
+    if idx > text.len() {
+        panic!(
+            "byte index {idx} is out of bounds of text of length {}",
+            text.len(),
+        );
+    }
+    if let (_, Some((r, c))) = text.floor_char_boundary_range(idx) {
+        panic!(
+            "byte index {idx} is not a char boundary; it is inside {c:?} (bytes {}..{})",
+            r.start, r.end,
+        );
+    }
+}
+
+/// Returns the length of a UTF-8 encoded [`char`], given its leading byte.
+#[inline]
+const fn utf8_char_width(b: u8) -> usize {
+    if b < 0x80 {
+        1
+    } else if b < 0xE0 {
+        2
+    } else if b < 0xF0 {
+        3
+    } else {
+        4
+    }
+}
+
+/// Returns `true` if `b` is a UTF-8 continuation byte, i.e. `0b10xx_xxxx`.
+#[inline]
+const fn is_utf8_continuation_byte(b: u8) -> bool {
+    (b & 0xC0) == 0x80
+}
+
+const WORD: usize = core::mem::size_of::<usize>();
+const LOW_BITS: usize = usize::from_ne_bytes([0x01; WORD]);
+
+/// Returns a word with the low bit of each byte set, if the byte in
+/// `word` is not a UTF-8 continuation byte, i.e. if its highest bit
+/// is not set or its second highest bit is set.
+#[inline]
+const fn non_continuation_bytes(word: usize) -> usize {
+    ((!word >> 7) | (word >> 6)) & LOW_BITS
+}
+
+#[cfg(any(not(feature = "fast"), test))]
+#[inline]
+fn read_word(bytes: &[u8], i: usize) -> usize {
+    let mut word = [0; WORD];
+    word.copy_from_slice(&bytes[i..(i + WORD)]);
+    usize::from_ne_bytes(word)
+}
+
+/// Returns the number of bytes occupied by the first `n` [`char`]s
+/// of the UTF-8 encoded `bytes`, by only counting leading bytes.
+///
+/// Returns <code>Err(k)</code> if `bytes` only contains `k < n` [`char`]s.
+#[inline]
+fn skip_chars(bytes: &[u8], n: usize) -> Result<usize, usize> {
+    #[cfg(feature = "fast")]
+    return fast::skip_chars(bytes, n);
+    #[cfg(not(feature = "fast"))]
+    return skip_chars_safe(bytes, n);
+}
+
+/// Returns the number of bytes occupied by the first `n` [`char`]s
+/// of the UTF-8 encoded `bytes`, by only counting leading bytes,
+/// a `usize` at a time.
+///
+/// Returns <code>Err(k)</code> if `bytes` only contains `k < n` [`char`]s.
+#[cfg(any(not(feature = "fast"), test))]
+fn skip_chars_safe(bytes: &[u8], n: usize) -> Result<usize, usize> {
+    let mut remaining = n;
+    let mut i = 0;
+
+    while i + WORD <= bytes.len() {
+        let count = non_continuation_bytes(read_word(bytes, i)).count_ones() as usize;
+        if count > remaining {
+            break;
+        }
+        remaining -= count;
+        i += WORD;
+    }
+
+    // The position is now within the next word, or the next
+    // word contains the remaining continuation bytes
+    for (j, &b) in bytes[i..].iter().enumerate() {
+        if !is_utf8_continuation_byte(b) {
+            if remaining == 0 {
+                return Ok(i + j);
+            }
+            remaining -= 1;
+        }
+    }
+
+    if remaining == 0 {
+        Ok(bytes.len())
+    } else {
+        Err(n - remaining)
+    }
+}
+
+/// Returns the number of bytes occupied by the last `n` [`char`]s
+/// of the UTF-8 encoded `bytes`, by only counting leading bytes.
+///
+/// Returns <code>Err(k)</code> if `bytes` only contains `k < n` [`char`]s.
+#[inline]
+fn skip_chars_back(bytes: &[u8], n: usize) -> Result<usize, usize> {
+    #[cfg(feature = "fast")]
+    return fast::skip_chars_back(bytes, n);
+    #[cfg(not(feature = "fast"))]
+    return skip_chars_back_safe(bytes, n);
+}
+
+/// Returns the number of bytes occupied by the last `n` [`char`]s
+/// of the UTF-8 encoded `bytes`, by only counting leading bytes,
+/// a `usize` at a time.
+///
+/// Returns <code>Err(k)</code> if `bytes` only contains `k < n` [`char`]s.
+#[cfg(any(not(feature = "fast"), test))]
+fn skip_chars_back_safe(bytes: &[u8], n: usize) -> Result<usize, usize> {
+    if n == 0 {
+        return Ok(0);
+    }
+
+    let mut remaining = n;
+    let mut i = bytes.len();
+
+    // Only skip words containing fewer leading bytes than remaining,
+    // as the start of the word might not be on a char boundary
+    while i >= WORD {
+        let count = non_continuation_bytes(read_word(bytes, i - WORD)).count_ones() as usize;
+        if count >= remaining {
+            break;
+        }
+        remaining -= count;
+        i -= WORD;
+    }
+
+    while i > 0 {
+        i -= 1;
+        if !is_utf8_continuation_byte(bytes[i]) {
+            remaining -= 1;
+            if remaining == 0 {
+                return Ok(bytes.len() - i);
+            }
+        }
+    }
+
+    Err(n - remaining)
+}
+
+/// Returns the number of [`char`]s in `text`, by counting the bytes
+/// that are not UTF-8 continuation bytes, a `usize` at a time.
+#[inline]
+fn count_chars(text: &str) -> usize {
+    count_leading_bytes(text.as_bytes())
+}
+
+/// Returns the number of bytes in `bytes` that are not UTF-8 continuation
+/// bytes, i.e. the number of [`char`]s starting within `bytes`, even if
+/// `bytes` is not on [`char`] boundaries.
+#[inline]
+fn count_leading_bytes(bytes: &[u8]) -> usize {
+    #[cfg(feature = "fast")]
+    return fast::count_leading_bytes(bytes);
+    #[cfg(not(feature = "fast"))]
+    return count_leading_bytes_safe(bytes);
+}
+
+/// Returns the number of bytes in `bytes` that are not UTF-8 continuation
+/// bytes, a `usize` at a time, i.e. the number of [`char`]s starting
+/// within `bytes`, even if `bytes` is not on [`char`] boundaries.
+#[cfg(any(not(feature = "fast"), test))]
+fn count_leading_bytes_safe(bytes: &[u8]) -> usize {
+    // Each byte of the accumulator counts at most 1 per word,
+    // so it must be summed before it can overflow
+    const BATCH: usize = 255;
+
+    let mut chunks = bytes.chunks_exact(WORD);
+
+    let mut count = 0;
+    loop {
+        let mut acc = 0;
+        let mut n = 0;
+        for chunk in chunks.by_ref().take(BATCH) {
+            let mut word = [0; WORD];
+            word.copy_from_slice(chunk);
+            acc += non_continuation_bytes(usize::from_ne_bytes(word));
+            n += 1;
+        }
+        if n == 0 {
+            break;
+        }
+        count += acc.to_ne_bytes().iter().map(|&b| b as usize).sum::<usize>();
+    }
+
+    count
+        + chunks
+            .remainder()
+            .iter()
+            .filter(|&&b| !is_utf8_continuation_byte(b))
+            .count()
+}
+
+impl Iterator for CharRanges<'_> {
+    type Item = (Range<usize>, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let c = self.as_str().chars().next()?;
+        let start = self.start;
+        self.start += c.len_utf8();
+        Some((start..self.start, c))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.remaining_char_count()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.as_str().chars().size_hint()
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<(Range<usize>, char)> {
+        self.next_back()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.advance_by(n).ok()?;
+        self.next()
+    }
+
+    // `try_fold()` cannot be specialized on stable, as `Try` is unstable
+
+    #[inline]
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let start = self.start;
+        self.as_str().char_indices().fold(init, |acc, (i, c)| {
+            let i = start + i;
+            f(acc, (i..(i + c.len_utf8()), c))
+        })
+    }
+
+    #[inline]
+    fn for_each<F>(self, mut f: F)
+    where
+        F: FnMut(Self::Item),
+    {
+        self.fold((), |(), item| f(item));
+    }
+}
+
+impl DoubleEndedIterator for CharRanges<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let c = self.as_str().chars().next_back()?;
+        let end = self.end;
+        self.end -= c.len_utf8();
+        Some((self.end..end, c))
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.advance_back_by(n).ok()?;
+        self.next_back()
+    }
+
+    #[inline]
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let start = self.start;
+        self.as_str().char_indices().rfold(init, |acc, (i, c)| {
+            let i = start + i;
+            f(acc, (i..(i + c.len_utf8()), c))
+        })
+    }
+}
+
+impl FusedIterator for CharRanges<'_> {}
+
+/// Converts the remaining substring of a [`CharIndices`] into a
+/// [`CharRanges`].
+///
+/// Note that the positions produced by the returned [`CharRanges`]
+/// restart at `0`, i.e. they are relative to the remaining substring.
+/// Convert into a [`CharRangesOffset`] instead, to preserve the positions.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::CharRanges;
+///
+/// let text = "🗻∈🌏";
+///
+/// let mut chars = text.char_indices();
+/// assert_eq!(chars.next(), Some((0, '🗻')));
+///
+/// let mut chars = CharRanges::from(chars);
+/// assert_eq!(chars.next(), Some((0..3, '∈')));
+/// ```
+impl<'a> From<CharIndices<'a>> for CharRanges<'a> {
+    #[inline]
+    fn from(iter: CharIndices<'a>) -> Self {
+        Self::new(iter.as_str())
+    }
+}
+
+/// Converts the remaining substring of a [`Chars`] into a [`CharRanges`].
+///
+/// Note that the positions produced by the returned [`CharRanges`]
+/// are relative to the remaining substring.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::CharRanges;
+///
+/// let text = "🗻∈🌏";
+///
+/// let mut chars = text.chars();
+/// assert_eq!(chars.next(), Some('🗻'));
+///
+/// let mut chars = CharRanges::from(chars);
+/// assert_eq!(chars.next(), Some((0..3, '∈')));
+/// ```
+impl<'a> From<Chars<'a>> for CharRanges<'a> {
+    #[inline]
+    fn from(iter: Chars<'a>) -> Self {
+        Self::new(iter.as_str())
+    }
+}
+
+/// Returns an empty iterator, i.e. the same as `CharRanges::new("")`.
+impl Default for CharRanges<'_> {
+    #[inline]
+    fn default() -> Self {
+        Self::new("")
+    }
+}
+
+/// Two iterators are equal if they produce the same items, i.e. if their
+/// remaining substrings are equal and start at the same byte position.
+///
+/// Only the contents matter, not the address of the text, i.e. iterators
+/// over different allocations with identical remaining text are equal.
+/// The consumed parts of the texts are ignored, except for their length,
+/// since that decides the positions produced.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::CharRangesExt;
+///
+/// let a = String::from("foo bar");
+/// let b = String::from("xyz bar");
+///
+/// let mut a = a.char_ranges();
+/// let mut b = b.char_ranges();
+/// assert_ne!(a, b);
+///
+/// a.nth(2);
+/// b.nth(2);
+/// assert_eq!(a, b);
+///
+/// // Different positions
+/// assert_ne!(a, " bar".char_ranges());
+/// ```
+impl PartialEq for CharRanges<'_> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        (self.start == other.start) && (self.as_str() == other.as_str())
+    }
+}
+
+impl Eq for CharRanges<'_> {}
+
+/// Hashes the remaining substring and its start byte position, which
+/// is consistent with [`PartialEq`].
+impl Hash for CharRanges<'_> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.start.hash(state);
+        self.as_str().hash(state);
+    }
+}
+
+/// By default a compact summary is printed, i.e. the remaining byte range
+/// and length, along with a preview of the remaining text, truncated to
+/// the first and last few [`char`]s.
+///
+/// The alternate form (`{:#?}`) instead prints the remaining items,
+/// truncated to the first 64.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::CharRangesExt;
+///
+/// let mut chars = "Hello 🌏".char_ranges();
+/// chars.next();
+///
+/// assert_eq!(
+///     format!("{chars:?}"),
+///     r#"CharRanges { range: 1..10, len: 9, text: "ello 🌏" }"#,
+/// );
+///
+/// let text = "The quick brown fox jumps over the lazy dog";
+/// assert_eq!(
+///     format!("{:?}", text.char_ranges()),
+///     r#"CharRanges { range: 0..43, len: 43, text: "The quick brown "…"ver the lazy dog" }"#,
+/// );
+/// ```
+impl fmt::Debug for CharRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            write!(f, "CharRanges(")?;
+            debug_entries(f, *self)?;
+            write!(f, ")")
+        } else {
+            f.debug_struct("CharRanges")
+                .field("range", &(self.start..self.end))
+                .field("len", &(self.end - self.start))
+                .field("text", &DebugPreview(self.as_str()))
+                .finish()
+        }
+    }
+}
+
+/// The maximum number of items printed by the alternate [`Debug`] form.
+const DEBUG_MAX_ENTRIES: usize = 64;
+
+/// The number of [`char`]s at the start and end of [`DebugPreview`].
+const DEBUG_PREVIEW_CHARS: usize = 16;
+
+/// Prints at most [`DEBUG_MAX_ENTRIES`] items of `iter`.
+fn debug_entries<I>(f: &mut fmt::Formatter<'_>, mut iter: I) -> fmt::Result
+where
+    I: Iterator,
+    I::Item: fmt::Debug,
+{
+    let mut list = f.debug_list();
+    list.entries(iter.by_ref().take(DEBUG_MAX_ENTRIES));
+    if iter.next().is_some() {
+        list.finish_non_exhaustive()
+    } else {
+        list.finish()
+    }
+}
+
+/// Prints a string like [`str`]'s [`Debug`], except strings longer than
+/// `2 * DEBUG_PREVIEW_CHARS` [`char`]s are truncated in the middle,
+/// e.g. `"Hello"…"World"`.
+struct DebugPreview<'a>(&'a str);
+
+impl fmt::Debug for DebugPreview<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = self.0;
+        let head = match text.char_indices().nth(DEBUG_PREVIEW_CHARS) {
+            Some((i, _c)) => i,
+            None => text.len(),
+        };
+        let tail = match text.char_indices().nth_back(DEBUG_PREVIEW_CHARS - 1) {
+            Some((i, _c)) => i,
+            None => 0,
+        };
+        if head >= tail {
+            fmt::Debug::fmt(text, f)
+        } else {
+            write!(f, "{:?}…{:?}", &text[..head], &text[tail..])
+        }
+    }
+}
+
+/// An iterator over [`char`]s and their start and end byte positions,
+/// with an offset applied to all positions.
+///
+/// Note: This iterator is [`Copy`], i.e. it can be stored and restored
+/// freely, e.g. for backtracking, without explicitly cloning it.
+///
+/// See examples in the [crate root](crate).
+#[derive(Clone, Copy)]
+pub struct CharRangesOffset<'a> {
+    iter: CharRanges<'a>,
+    offset: usize,
+}
+
+impl<'a> CharRangesOffset<'a> {
+    /// Creates an iterator over [`char`]s and their start and end byte positions,
+    /// with an offset applied to all positions.
+    ///
+    /// Consider using <code>text.[char_ranges_offset()]</code> or
+    /// <code>text.[char_ranges()].[offset()]</code>, instead of
+    /// explicitly using `CharRangesOffset::from_text()`.
+    ///
+    /// See examples in the [crate root](crate).
+    ///
+    /// [char_ranges()]: CharRangesExt::char_ranges
+    /// [char_ranges_offset()]: CharRangesExt::char_ranges_offset
+    /// [offset()]: CharRanges::offset
+    #[inline]
+    pub fn from_text(text: &'a str, offset: usize) -> Self {
+        Self {
+            iter: text.char_ranges(),
+            offset,
+        }
+    }
+
+    /// Creates an iterator over [`char`]s and their start and end byte positions,
+    /// with an offset applied to all positions.
+    ///
+    /// This takes the offset before the text, unlike everything else,
+    /// see [`from_text()`](Self::from_text).
+    #[deprecated(
+        since = "0.1.3",
+        note = "use `CharRangesOffset::from_text(text, offset)`"
+    )]
+    #[inline]
+    pub fn new(offset: usize, text: &'a str) -> Self {
+        Self::from_text(text, offset)
+    }
+
+    /// Creates an iterator over [`char`]s and their start and end byte positions,
+    /// with an offset applied to all positions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `offset + text.len()` overflows `usize`,
+    /// i.e. if any position could overflow.
+    ///
+    /// See [`CharRanges::try_offset()`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesOffset;
+    ///
+    /// assert!(CharRangesOffset::try_from_text("abc", usize::MAX - 3).is_ok());
+    /// assert!(CharRangesOffset::try_from_text("abc", usize::MAX - 2).is_err());
+    /// ```
+    #[inline]
+    pub fn try_from_text(text: &'a str, offset: usize) -> Result<Self, OffsetOverflowError> {
+        text.char_ranges().try_offset(offset)
+    }
+
+    /// Returns the remaining substring.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "Hello 👋 World 🌏";
+    ///
+    /// let start = 11; // Start index of 'W'
+    /// let text = &text[start..]; // "World 🌏"
+    ///
+    /// let mut chars = text.char_ranges_offset(start);
+    /// assert_eq!(chars.as_str(), "World 🌏");
+    ///
+    /// assert_eq!(chars.next(), Some((11..12, 'W'))); // These chars are 1 byte
+    /// assert_eq!(chars.next_back(), Some((17..21, '🌏'))); // This char is 4 bytes
+    ///
+    /// assert_eq!(chars.as_str(), "orld ");
+    /// ```
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the original text, this iterator was created with,
+    /// regardless of how much has been consumed.
+    ///
+    /// See [`CharRanges::original_str()`] for more information.
+    #[inline]
+    pub fn original_str(&self) -> &'a str {
+        self.iter.original_str()
+    }
+
+    /// Returns the substring, which has been consumed from the front.
+    ///
+    /// See [`CharRanges::consumed_prefix()`] for more information.
+    #[inline]
+    pub fn consumed_prefix(&self) -> &'a str {
+        self.iter.consumed_prefix()
+    }
+
+    /// Returns the substring, which has been consumed from the back.
+    ///
+    /// See [`CharRanges::consumed_suffix()`] for more information.
+    #[inline]
+    pub fn consumed_suffix(&self) -> &'a str {
+        self.iter.consumed_suffix()
+    }
+
+    /// Returns a [`ValidSpan`] of the byte range `r` within the
+    /// [`original_str()`](Self::original_str), where `r` has the offset
+    /// applied, e.g. a range produced by this iterator.
+    ///
+    /// The offset is removed from the range of the returned [`ValidSpan`],
+    /// i.e. it is relative to the [`original_str()`](Self::original_str).
+    /// A range starting before the offset is out of bounds.
+    ///
+    /// See [`CharRanges::valid_span()`] for more information.
+    #[inline]
+    pub fn valid_span(&self, r: Range<usize>) -> Result<ValidSpan<'a>, SpanError> {
+        let start = r.start.checked_sub(self.offset);
+        let end = r.end.checked_sub(self.offset);
+        match (start, end) {
+            (Some(start), Some(end)) => self.iter.valid_span(start..end),
+            _ => Err(SpanError::OutOfBounds),
+        }
+    }
+
+    /// Converts this iterator into a [`CharIndices`] over the remaining
+    /// substring, i.e. [`as_str()`](Self::as_str).
+    ///
+    /// Note that the offset is not applied, i.e. the positions produced
+    /// by the returned [`CharIndices`] restart at `0`.
+    ///
+    /// See [`CharRanges::into_char_indices()`] for more information.
+    #[inline]
+    pub fn into_char_indices(self) -> CharIndices<'a> {
+        self.iter.into_char_indices()
+    }
+
+    /// Restores both ends of the iterator, such that the remaining
+    /// substring is the [original text](Self::original_str) again.
+    /// The offset is preserved.
+    ///
+    /// See [`CharRanges::reset()`] for more information.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.iter.reset();
+    }
+
+    /// Restores the front of the iterator, such that the
+    /// [consumed prefix](Self::consumed_prefix) is empty again.
+    ///
+    /// See [`CharRanges::reset_front()`] for more information.
+    #[inline]
+    pub fn reset_front(&mut self) {
+        self.iter.reset_front();
+    }
+
+    /// Restores the back of the iterator, such that the
+    /// [consumed suffix](Self::consumed_suffix) is empty again.
+    ///
+    /// See [`CharRanges::reset_back()`] for more information.
+    #[inline]
+    pub fn reset_back(&mut self) {
+        self.iter.reset_back();
+    }
+
+    /// Moves the front of the iterator back by one [`char`], and returns
+    /// that [`char`] and its start and end byte positions, with the
+    /// offset applied.
+    ///
+    /// See [`CharRanges::prev()`] for more information.
+    #[inline]
+    pub fn prev(&mut self) -> Option<(Range<usize>, char)> {
+        let (r, c) = self.iter.prev()?;
+        Some((self.apply_offset(r), c))
+    }
+
+    /// Moves the back of the iterator forward by one [`char`], and returns
+    /// that [`char`] and its start and end byte positions, with the
+    /// offset applied.
+    ///
+    /// See [`CharRanges::prev_back()`] for more information.
+    #[inline]
+    pub fn prev_back(&mut self) -> Option<(Range<usize>, char)> {
+        let (r, c) = self.iter.prev_back()?;
+        Some((self.apply_offset(r), c))
+    }
+
+    /// Moves the front of the iterator to the byte position `pos`,
+    /// where `pos` includes the offset.
+    ///
+    /// See [`CharRanges::seek_to()`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{CharRangesExt, SeekError};
+    ///
+    /// let text = "Hello 👋 World 🌏";
+    ///
+    /// let start = 6;
+    /// let mut chars = text[start..].char_ranges_offset(start);
+    /// assert_eq!(chars.seek_to(11), Ok(()));
+    /// assert_eq!(chars.next(), Some((11..12, 'W')));
+    ///
+    /// assert_eq!(chars.seek_to(5), Err(SeekError::OutOfBounds));
+    /// ```
+    #[inline]
+    pub fn seek_to(&mut self, pos: usize) -> Result<(), SeekError> {
+        let pos = pos.checked_sub(self.offset).ok_or(SeekError::OutOfBounds)?;
+        self.iter.seek_to(pos)
+    }
+
+    /// Moves the back of the iterator to the byte position `pos`,
+    /// where `pos` includes the offset.
+    ///
+    /// See [`CharRanges::seek_back_to()`] for more information.
+    #[inline]
+    pub fn seek_back_to(&mut self, pos: usize) -> Result<(), SeekError> {
+        let pos = pos.checked_sub(self.offset).ok_or(SeekError::OutOfBounds)?;
+        self.iter.seek_back_to(pos)
+    }
+
+    /// Returns the `offset` this [`CharRangesOffset`] was created with.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "Hello 👋 World 🌏";
+    ///
+    /// let start = 11; // Start index of 'W'
+    /// let text = &text[start..]; // "World 🌏"
+    ///
+    /// let mut chars = text.char_ranges_offset(start);
+    /// // Offset is `start`
+    /// assert_eq!(chars.offset(), start);
+    ///
+    /// assert_eq!(chars.next(), Some((11..12, 'W'))); // These chars are 1 byte
+    /// assert_eq!(chars.next_back(), Some((17..21, '🌏'))); // This char is 4 bytes
+    ///
+    /// // Offset remains as `start` always
+    /// assert_eq!(chars.offset(), start);
+    /// ```
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns an iterator over the remaining [`char`]s and their start and
+    /// end byte positions, with `extra` added to the current offset.
+    ///
+    /// This allows stacking offsets, e.g. for a token inside a line
+    /// inside a file.
+    ///
+    /// Note that [`offset()`](Self::offset) returns the current offset,
+    /// so this is the equivalent of [`CharRanges::offset()`] for
+    /// [`CharRangesOffset`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let file = "foo\nlet x = 1;";
+    ///
+    /// let line_start = 4;
+    /// let line = &file[line_start..]; // "let x = 1;"
+    ///
+    /// let token_start = 4;
+    /// let token = &line[token_start..(token_start + 1)]; // "x"
+    ///
+    /// let mut chars = token.char_ranges_offset(token_start).add_offset(line_start);
+    /// assert_eq!(chars.offset(), 8);
+    /// assert_eq!(chars.next(), Some((8..9, 'x')));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sum of the offsets overflows `usize`.
+    #[inline]
+    pub fn add_offset(self, extra: usize) -> CharRangesOffset<'a> {
+        let offset = self.offset.checked_add(extra).expect("offset overflow");
+        self.with_offset(offset)
+    }
+
+    /// Returns an iterator over the remaining [`char`]s and their start and
+    /// end byte positions, with the offset replaced by `offset`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "Hello 👋 World 🌏";
+    ///
+    /// let start = 11; // Start index of 'W'
+    /// let text = &text[start..]; // "World 🌏"
+    ///
+    /// let mut chars = text.char_ranges_offset(start).with_offset(0);
+    /// assert_eq!(chars.offset(), 0);
+    /// assert_eq!(chars.next(), Some((0..1, 'W')));
+    /// ```
+    #[inline]
+    pub fn with_offset(self, offset: usize) -> CharRangesOffset<'a> {
+        CharRangesOffset {
+            iter: self.iter,
+            offset,
+        }
+    }
+
+    /// Returns an iterator over the remaining [`char`]s and their start and
+    /// end byte positions, with the offset replaced by the result of calling
+    /// `f` with the current offset.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "Hello 👋 World 🌏";
+    ///
+    /// let start = 11; // Start index of 'W'
+    /// let text = &text[start..]; // "World 🌏"
+    ///
+    /// let mut chars = text.char_ranges_offset(start).map_offset(|offset| offset * 2);
+    /// assert_eq!(chars.offset(), 22);
+    /// assert_eq!(chars.next(), Some((22..23, 'W')));
+    /// ```
+    #[inline]
+    pub fn map_offset(self, f: impl FnOnce(usize) -> usize) -> CharRangesOffset<'a> {
+        let offset = f(self.offset);
+        self.with_offset(offset)
+    }
+
+    /// Returns the underlying [`CharRanges`] without the offset, along
+    /// with the offset, i.e. the inverse of [`CharRanges::offset()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "Hello 👋 World 🌏";
+    ///
+    /// let start = 11; // Start index of 'W'
+    /// let text = &text[start..]; // "World 🌏"
+    ///
+    /// let mut chars = text.char_ranges_offset(start);
+    /// assert_eq!(chars.next(), Some((11..12, 'W')));
+    ///
+    /// let (mut chars, offset) = chars.into_unoffset();
+    /// assert_eq!(offset, start);
+    /// assert_eq!(chars.next(), Some((1..2, 'o')));
+    /// ```
+    #[inline]
+    pub fn into_unoffset(self) -> (CharRanges<'a>, usize) {
+        (self.iter, self.offset)
+    }
+
+    /// Returns the substring of the [original text](Self::original_str)
+    /// at the absolute `range`, i.e. a range which includes the offset,
+    /// such as the ranges produced by this iterator.
+    ///
+    /// Returns `None` if `range` precedes the offset, is out of bounds,
+    /// or does not start and end on [`char`] boundaries.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "Hello 👋 World 🌏";
+    ///
+    /// let start = 11; // Start index of 'W'
+    /// let text = &text[start..]; // "World 🌏"
+    ///
+    /// let mut chars = text.char_ranges_offset(start);
+    /// let (r, _c) = chars.next_back().unwrap();
+    /// assert_eq!(r, 17..21);
+    /// assert_eq!(chars.get(r), Some("🌏"));
+    ///
+    /// assert_eq!(chars.get(11..16), Some("World"));
+    /// assert_eq!(chars.get(0..5), None);
+    /// assert_eq!(chars.get(17..19), None);
+    /// ```
+    #[inline]
+    pub fn get(&self, range: Range<usize>) -> Option<&'a str> {
+        let start = range.start.checked_sub(self.offset)?;
+        let end = range.end.checked_sub(self.offset)?;
+        self.original_str().get(start..end)
+    }
+
+    /// Returns `true` if the absolute position `pos`, i.e. a position
+    /// which includes the offset, is within the [original text](Self::original_str).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "Hello 👋 World 🌏";
+    ///
+    /// let start = 11; // Start index of 'W'
+    /// let text = &text[start..]; // "World 🌏"
+    ///
+    /// let chars = text.char_ranges_offset(start);
+    /// assert!(!chars.contains(10));
+    /// assert!(chars.contains(11));
+    /// assert!(chars.contains(20));
+    /// assert!(!chars.contains(21));
+    /// ```
+    #[inline]
+    pub fn contains(&self, pos: usize) -> bool {
+        match pos.checked_sub(self.offset) {
+            Some(pos) => pos < self.original_str().len(),
+            None => false,
+        }
+    }
+
+    /// Applies the offset to a range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position overflows `usize`, regardless of
+    /// whether overflow checks are enabled.
+    #[inline]
+    fn apply_offset(&self, r: Range<usize>) -> Range<usize> {
+        self.apply_offset_to(r.start)..self.apply_offset_to(r.end)
+    }
+
+    /// Applies the offset to a position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position overflows `usize`, regardless of
+    /// whether overflow checks are enabled.
+    #[inline]
+    fn apply_offset_to(&self, pos: usize) -> usize {
+        match pos.checked_add(self.offset) {
+            Some(pos) => pos,
+            None => panic!("position {pos} with offset {} overflows usize", self.offset),
+        }
+    }
+
+    /// Returns the next [`char`] and its start and end byte positions,
+    /// without consuming it, i.e. the same as [`next()`] would return.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "Hello 👋 World 🌏";
+    ///
+    /// let start = 11; // Start index of 'W'
+    /// let text = &text[start..]; // "World 🌏"
+    ///
+    /// let mut chars = text.char_ranges_offset(start);
+    /// assert_eq!(chars.peek(), Some((11..12, 'W')));
+    /// assert_eq!(chars.next(), Some((11..12, 'W')));
+    /// ```
+    ///
+    /// [`next()`]: Iterator::next
+    #[inline]
+    pub fn peek(&self) -> Option<(Range<usize>, char)> {
+        self.clone().next()
+    }
+
+    /// Returns the last [`char`] and its start and end byte positions,
+    /// without consuming it, i.e. the same as [`next_back()`] would return.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "Hello 👋 World 🌏";
+    ///
+    /// let start = 11; // Start index of 'W'
+    /// let text = &text[start..]; // "World 🌏"
+    ///
+    /// let mut chars = text.char_ranges_offset(start);
+    /// assert_eq!(chars.peek_back(), Some((17..21, '🌏')));
+    /// assert_eq!(chars.next_back(), Some((17..21, '🌏')));
+    /// ```
+    ///
+    /// [`next_back()`]: DoubleEndedIterator::next_back
+    #[inline]
+    pub fn peek_back(&self) -> Option<(Range<usize>, char)> {
+        self.clone().next_back()
+    }
+
+    /// Returns the start byte position of the next [`char`], with the
+    /// offset applied.
+    ///
+    /// See [`CharRanges::front_offset()`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "Hello 👋 World 🌏";
+    ///
+    /// let start = 11; // Start index of 'W'
+    /// let text = &text[start..]; // "World 🌏"
+    ///
+    /// let mut chars = text.char_ranges_offset(start);
+    /// assert_eq!(chars.front_offset(), 11);
+    ///
+    /// assert_eq!(chars.next(), Some((11..12, 'W')));
+    /// assert_eq!(chars.front_offset(), 12);
+    /// ```
+    #[inline]
+    pub fn front_offset(&self) -> usize {
+        self.apply_offset_to(self.iter.front_offset())
+    }
+
+    /// Returns the end byte position of the last [`char`], with the
+    /// offset applied.
+    ///
+    /// See [`CharRanges::back_offset()`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "Hello 👋 World 🌏";
+    ///
+    /// let start = 11; // Start index of 'W'
+    /// let text = &text[start..]; // "World 🌏"
+    ///
+    /// let mut chars = text.char_ranges_offset(start);
+    /// assert_eq!(chars.back_offset(), 21);
+    ///
+    /// assert_eq!(chars.next_back(), Some((17..21, '🌏')));
+    /// assert_eq!(chars.back_offset(), 17);
+    /// ```
+    #[inline]
+    pub fn back_offset(&self) -> usize {
+        self.apply_offset_to(self.iter.back_offset())
+    }
+
+    /// Returns a [`Mark`] of the current front position.
+    ///
+    /// See [`CharRanges::mark()`] for more information.
+    #[inline]
+    pub fn mark(&self) -> Mark {
+        self.iter.mark()
+    }
+
+    /// Returns the range and substring consumed from the front since
+    /// `mark` was taken. With the offset applied to the range.
+    ///
+    /// See [`CharRanges::slice_since()`] for more information.
+    #[inline]
+    #[track_caller]
+    pub fn slice_since(&self, mark: Mark) -> (Range<usize>, &'a str) {
+        let (r, s) = self.iter.slice_since(mark);
+        (self.apply_offset(r), s)
+    }
+
+    /// Returns the range and substring between the marks `a` and `b`.
+    /// With the offset applied to the range.
+    ///
+    /// See [`CharRanges::slice_between()`] for more information.
+    #[inline]
+    #[track_caller]
+    pub fn slice_between(&self, a: Mark, b: Mark) -> (Range<usize>, &'a str) {
+        let (r, s) = self.iter.slice_between(a, b);
+        (self.apply_offset(r), s)
+    }
+
+    /// Returns the range of the next [`char`], without decoding it,
+    /// with the offset applied.
+    ///
+    /// See [`CharRanges::next_range()`] for more information.
+    #[inline]
+    pub fn next_range(&mut self) -> Option<Range<usize>> {
+        let r = self.iter.next_range()?;
+        Some(self.apply_offset(r))
+    }
+
+    /// Returns the range of the last [`char`], without decoding it,
+    /// with the offset applied.
+    ///
+    /// See [`CharRanges::next_back_range()`] for more information.
+    #[inline]
+    pub fn next_back_range(&mut self) -> Option<Range<usize>> {
+        let r = self.iter.next_back_range()?;
+        Some(self.apply_offset(r))
+    }
+
+    /// Returns the number of remaining [`char`]s, without consuming
+    /// the iterator.
+    ///
+    /// See [`CharRanges::remaining_char_count()`] for more information.
+    #[inline]
+    pub fn remaining_char_count(&self) -> usize {
+        self.iter.remaining_char_count()
+    }
+
+    /// Advances the iterator by `n` [`char`]s, without decoding them.
+    ///
+    /// See [`CharRanges::advance_by()`] for more information.
+    #[inline]
+    pub fn advance_by(&mut self, n: usize) -> Result<(), usize> {
+        self.iter.advance_by(n)
+    }
+
+    /// Advances the iterator from the back by `n` [`char`]s, without
+    /// decoding them.
+    ///
+    /// See [`CharRanges::advance_back_by()`] for more information.
+    #[inline]
+    pub fn advance_back_by(&mut self, n: usize) -> Result<(), usize> {
+        self.iter.advance_back_by(n)
+    }
+
+    /// Splits the remaining [`char`]s into two iterators, where the first
+    /// produces the next `n` [`char`]s, and the second produces the rest.
+    /// Both with the offset applied to all positions.
+    ///
+    /// See [`CharRanges::split_at_char()`] for more information.
+    pub fn split_at_char(self, n: usize) -> (CharRangesOffset<'a>, CharRangesOffset<'a>) {
+        let mut right = self;
+        _ = right.advance_by(n);
+
+        let mut left = self;
+        left.iter.end = right.iter.start;
+
+        (left, right)
+    }
+
+    /// Splits the remaining [`char`]s into two iterators at the byte
+    /// position `pos`, where `pos` includes the offset. Both with the
+    /// offset applied to all positions.
+    ///
+    /// See [`CharRanges::partition_at_byte()`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{BoundaryError, CharRangesExt};
+    ///
+    /// let text = "Hello 👋 World 🌏";
+    ///
+    /// let chars = text[6..].char_ranges_offset(6);
+    /// let (left, right) = chars.partition_at_byte(11).unwrap();
+    /// assert_eq!(left.as_str(), "👋 ");
+    /// assert_eq!(right.as_str(), "World 🌏");
+    /// assert_eq!(right.offset(), 6);
+    ///
+    /// let err = chars.partition_at_byte(5).unwrap_err();
+    /// assert_eq!(
+    ///     err,
+    ///     BoundaryError::OutOfBounds {
+    ///         pos: 5,
+    ///         remaining: 6..21,
+    ///     }
+    /// );
+    /// ```
+    pub fn partition_at_byte(
+        self,
+        pos: usize,
+    ) -> Result<(CharRangesOffset<'a>, CharRangesOffset<'a>), BoundaryError> {
+        let remaining = self.apply_offset(self.iter.start..self.iter.end);
+        if (pos < remaining.start) || (pos > remaining.end) {
+            return Err(BoundaryError::OutOfBounds { pos, remaining });
+        }
+
+        let i = pos - self.offset;
+        let text = self.iter.text;
+        if !text.is_char_boundary(i) {
+            let mut start = i;
+            while !text.is_char_boundary(start) {
+                start -= 1;
+            }
+            let c = text[start..].chars().next().unwrap();
+            let range = self.apply_offset(start..(start + c.len_utf8()));
+            return Err(BoundaryError::NotCharBoundary { pos, range, c });
+        }
+
+        let mut left = self;
+        left.iter.end = i;
+        let mut right = self;
+        right.iter.start = i;
+
+        Ok((left, right))
+    }
+
+    /// Returns an iterator over the next `n` [`char`]s.
+    /// With the offset applied to all positions.
+    ///
+    /// See [`CharRanges::take_chars()`] for more information.
+    #[inline]
+    pub fn take_chars(mut self, n: usize) -> CharRangesOffset<'a> {
+        self.iter = self.iter.take_chars(n);
+        self
+    }
+
+    /// Advances the iterator by `n` [`char`]s, and returns it.
+    ///
+    /// See [`CharRanges::skip_chars()`] for more information.
+    #[inline]
+    pub fn skip_chars(mut self, n: usize) -> CharRangesOffset<'a> {
+        _ = self.advance_by(n);
+        self
+    }
+
+    /// Returns the byte range, with the offset applied, and substring
+    /// of the last `n` remaining [`char`]s, without consuming the iterator.
+    ///
+    /// See [`CharRanges::last_n()`] for more information.
+    #[inline]
+    pub fn last_n(&self, n: usize) -> (Range<usize>, &'a str) {
+        let (r, s) = self.iter.last_n(n);
+        (self.apply_offset(r), s)
+    }
+
+    /// Returns an iterator over the last `n` remaining [`char`]s.
+    /// With the offset applied to all positions.
+    ///
+    /// See [`CharRanges::last_n_ranges()`] for more information.
+    #[inline]
+    pub fn last_n_ranges(mut self, n: usize) -> CharRangesOffset<'a> {
+        self.iter = self.iter.last_n_ranges(n);
+        self
+    }
+
+    /// Returns an iterator over the remaining [`char`]s within a budget
+    /// of `max` bytes. With the offset applied to all positions.
+    ///
+    /// See [`CharRanges::take_bytes()`] for more information.
+    #[inline]
+    pub fn take_bytes(self, max: usize) -> TakeBytes<'a> {
+        TakeBytes::new(self, max)
+    }
+
+    /// Returns an iterator over chunks of the remaining substring of at
+    /// most `max_bytes` bytes. With the offset applied to all positions.
+    ///
+    /// See [`CharRanges::chunk_by_bytes()`] for more information.
+    #[inline]
+    pub fn chunk_by_bytes(self, max_bytes: usize) -> ByteChunks<'a> {
+        ByteChunks::new(self, max_bytes)
+    }
+
+    /// Returns an iterator over the [`char`] boundaries of the remaining
+    /// substring, including its start and end. With the offset applied
+    /// to all positions.
+    ///
+    /// See [`CharRangesExt::char_boundaries()`] for more information.
+    #[inline]
+    pub fn boundaries(self) -> CharBoundaries<'a> {
+        CharBoundaries::new(self)
+    }
+
+    /// Returns an iterator over the remaining bytes, producing the byte
+    /// position of each byte, the byte, and the start and end byte
+    /// positions of the [`char`] it belongs to. With the offset applied
+    /// to all positions.
+    ///
+    /// See [`CharRangesExt::bytes_with_char_ranges()`] for more information.
+    #[inline]
+    pub fn bytes_with_char_ranges(self) -> BytesWithCharRanges<'a> {
+        BytesWithCharRanges::new(self)
+    }
+
+    /// Consumes and returns the next [`char`] and its start and end byte
+    /// positions, only if `pred` returns `true` for the [`char`].
+    /// With the offset applied.
+    ///
+    /// See [`CharRanges::next_if()`] for more information.
+    #[inline]
+    pub fn next_if<P>(&mut self, pred: P) -> Option<(Range<usize>, char)>
+    where
+        P: FnOnce(char) -> bool,
+    {
+        let (r, c) = self.iter.next_if(pred)?;
+        Some((self.apply_offset(r), c))
+    }
+
+    /// Consumes and returns the last [`char`] and its start and end byte
+    /// positions, only if `pred` returns `true` for the [`char`].
+    /// With the offset applied.
+    ///
+    /// See [`CharRanges::next_back_if()`] for more information.
+    #[inline]
+    pub fn next_back_if<P>(&mut self, pred: P) -> Option<(Range<usize>, char)>
+    where
+        P: FnOnce(char) -> bool,
+    {
+        let (r, c) = self.iter.next_back_if(pred)?;
+        Some((self.apply_offset(r), c))
+    }
+
+    /// Consumes and returns the next [`char`] and its start and end byte
+    /// positions, only if it is equal to `expected`. With the offset applied.
+    ///
+    /// See [`CharRanges::next_if_eq()`] for more information.
+    #[inline]
+    pub fn next_if_eq(&mut self, expected: char) -> Option<(Range<usize>, char)> {
+        self.next_if(|c| c == expected)
+    }
+
+    /// Consumes the longest prefix of [`char`]s, for which `pred` returns
+    /// `true`, and returns its byte range with the offset applied, and
+    /// its substring.
+    ///
+    /// See [`CharRanges::take_while_range()`] for more information.
+    #[inline]
+    pub fn take_while_range<P>(&mut self, pred: P) -> (Range<usize>, &'a str)
+    where
+        P: FnMut(char) -> bool,
+    {
+        let (r, s) = self.iter.take_while_range(pred);
+        (self.apply_offset(r), s)
+    }
+
+    /// Consumes the next [`char`] only if it is equal to `c`, and returns
+    /// its byte range, with the offset applied.
+    ///
+    /// See [`CharRanges::consume_char()`] for more information.
+    #[inline]
+    pub fn consume_char(&mut self, c: char) -> Option<Range<usize>> {
+        let r = self.iter.consume_char(c)?;
+        Some(self.apply_offset(r))
+    }
+
+    /// Consumes `s` only if the remaining substring starts with `s`,
+    /// and returns its byte range, with the offset applied.
+    ///
+    /// See [`CharRanges::consume_str()`] for more information.
+    #[inline]
+    pub fn consume_str(&mut self, s: &str) -> Option<Range<usize>> {
+        let r = self.iter.consume_str(s)?;
+        Some(self.apply_offset(r))
+    }
+
+    /// Consumes the longest prefix of [`char`]s, for which `pred` returns
+    /// `true`, and returns its byte range, with the offset applied.
+    ///
+    /// See [`CharRanges::consume_while()`] for more information.
+    #[inline]
+    pub fn consume_while<P>(&mut self, pred: P) -> Range<usize>
+    where
+        P: FnMut(char) -> bool,
+    {
+        let r = self.iter.consume_while(pred);
+        self.apply_offset(r)
+    }
+
+    /// Skips the longest prefix of [`char`]s, for which `pred` returns
+    /// `true`, and returns the byte range of the skipped [`char`]s,
+    /// with the offset applied.
+    ///
+    /// See [`CharRanges::skip_while_range()`] for more information.
+    #[inline]
+    pub fn skip_while_range<P>(&mut self, pred: P) -> Range<usize>
+    where
+        P: FnMut(char) -> bool,
+    {
+        let r = self.iter.skip_while_range(pred);
+        self.apply_offset(r)
+    }
+
+    /// Skips the longest suffix of [`char`]s, for which `pred` returns
+    /// `true`, and returns the byte range of the skipped [`char`]s,
+    /// with the offset applied.
+    ///
+    /// See [`CharRanges::skip_back_while_range()`] for more information.
+    #[inline]
+    pub fn skip_back_while_range<P>(&mut self, pred: P) -> Range<usize>
+    where
+        P: FnMut(char) -> bool,
+    {
+        let r = self.iter.skip_back_while_range(pred);
+        self.apply_offset(r)
+    }
+
+    /// Searches for the next match of `pat`, and returns the first [`char`]
+    /// of the match along with the start and end byte positions of the match,
+    /// with the offset applied.
+    ///
+    /// See [`CharRanges::find_range()`] for more information.
+    #[inline]
+    pub fn find_range<P>(&mut self, pat: P) -> Option<(Range<usize>, char)>
+    where
+        P: CharPattern,
+    {
+        let (r, c) = self.iter.find_range(pat)?;
+        Some((self.apply_offset(r), c))
+    }
+
+    /// Searches for the last match of `pat`, and returns the first [`char`]
+    /// of the match along with the start and end byte positions of the match,
+    /// with the offset applied.
+    ///
+    /// See [`CharRanges::rfind_range()`] for more information.
+    #[inline]
+    pub fn rfind_range<P>(&mut self, pat: P) -> Option<(Range<usize>, char)>
+    where
+        P: CharPattern,
+    {
+        let (r, c) = self.iter.rfind_range(pat)?;
+        Some((self.apply_offset(r), c))
+    }
+
+    /// Returns an iterator over the remaining [`char`]s and their start
+    /// and end byte positions, in reverse order, with the offset applied
+    /// to all positions.
+    ///
+    /// See [`CharRanges::reversed()`] for more information.
+    #[inline]
+    pub fn reversed(self) -> CharRangesRev<'a> {
+        CharRangesRev::new(self)
+    }
+
+    /// Returns an iterator over the start and end byte positions of the
+    /// remaining [`char`]s, with the offset applied to all positions.
+    ///
+    /// See [`CharRanges::ranges()`] for more information.
+    #[inline]
+    pub fn ranges(self) -> Ranges<'a> {
+        Ranges::new(self)
+    }
+
+    /// Returns an iterator over the remaining [`char`]s and their start
+    /// and end byte positions, which implements [`ExactSizeIterator`],
+    /// with the offset applied to all positions.
+    ///
+    /// See [`CharRanges::counted()`] for more information.
+    #[inline]
+    pub fn counted(self) -> CountedCharRanges<'a> {
+        CountedCharRanges::new(self)
+    }
+
+    /// Returns an iterator over the remaining [`char`]s as [`Spanned<char>`],
+    /// with the offset applied to all positions.
+    ///
+    /// See [`CharRanges::spanned()`] for more information.
+    #[inline]
+    pub fn spanned(self) -> SpannedCharRanges<'a> {
+        SpannedCharRanges::new(self)
+    }
+
+    /// Returns an iterator over the remaining [`char`]s and their start
+    /// and end byte positions, with the offset applied, converted into
+    /// the [`Span`] `S`.
+    ///
+    /// See [`CharRanges::map_span()`] for more information.
+    #[inline]
+    pub fn map_span<S>(self) -> CharRangesAs<'a, S>
+    where
+        S: Span,
+    {
+        CharRangesAs::new(self)
+    }
+
+    /// Returns a [`Scanner`] over the remaining [`char`]s, for grouping
+    /// [`char`]s into tokens, with the offset applied to all positions.
+    ///
+    /// See [`CharRanges::scanner()`] for more information.
+    #[inline]
+    pub fn scanner(self) -> Scanner<'a> {
+        Scanner::new(self)
+    }
+
+    /// Returns an iterator over the remaining [`char`]s, their start and
+    /// end byte positions, with the offset applied, and their
+    /// [`LineColumn`] position.
+    ///
+    /// See [`CharRanges::char_positions()`] for more information.
+    #[inline]
+    pub fn char_positions(self) -> CharPositions<'a> {
+        CharPositions::new(self)
+    }
+
+    /// Returns an iterator over the remaining [`char`]s, their start and
+    /// end byte positions, with the offset applied, and their start and
+    /// end visual columns.
+    ///
+    /// See [`CharRanges::visual_columns()`] for more information.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tab_width` is `0`.
+    #[inline]
+    #[track_caller]
+    pub fn visual_columns(self, tab_width: usize) -> VisualColumns<'a> {
+        VisualColumns::new(self, tab_width)
+    }
+
+    /// Returns an iterator over the remaining substring soft wrapped into
+    /// lines no wider than `max_width`, and their start and end byte
+    /// positions, with the offset applied.
+    ///
+    /// See [`CharRanges::wrap_ranges()`] for more information.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_width` is `0`.
+    #[inline]
+    #[track_caller]
+    pub fn wrap_ranges(self, max_width: usize) -> WrapRanges<'a> {
+        WrapRanges::new(self, max_width)
+    }
+
+    /// Returns an iterator over the remaining [`char`]s and their start
+    /// and end byte positions, with the offset applied, where `"\r\n"`
+    /// and `'\r'` are normalized to `'\n'`.
+    ///
+    /// See [`CharRangesExt::char_ranges_normalized_newlines()`] for more information.
+    #[inline]
+    pub fn normalize_newlines(self) -> NormalizedNewlines<'a> {
+        NormalizedNewlines::new(self)
+    }
+
+    /// Returns an iterator over the remaining line terminators, producing
+    /// the start and end byte positions of each terminator, along with
+    /// its kind, with the offset applied to all ranges.
+    ///
+    /// See [`CharRanges::line_ending_ranges()`] for more information.
+    #[inline]
+    pub fn line_ending_ranges(self) -> LineEndingRanges<'a> {
+        LineEndingRanges::new(self)
+    }
+
+    /// Returns an iterator over the remaining [`char`]s, their start and
+    /// end [`char`] indices, and their start and end byte positions,
+    /// with the offset applied to all byte positions.
+    ///
+    /// See [`CharRanges::char_index_ranges()`] for more information.
+    #[inline]
+    pub fn char_index_ranges(self) -> CharIndexRanges<'a> {
+        self.char_index_ranges_offset(0)
+    }
+
+    /// Returns an iterator over the remaining [`char`]s, their start and
+    /// end [`char`] indices with `char_offset` applied, and their start
+    /// and end byte positions with the offset applied.
+    ///
+    /// See [`CharRanges::char_index_ranges_offset()`] for more information.
+    #[inline]
+    pub fn char_index_ranges_offset(self, char_offset: usize) -> CharIndexRanges<'a> {
+        CharIndexRanges::new(self, char_offset)
+    }
+
+    /// Returns an iterator over the remaining [`char`]s, their [`char`]
+    /// index, and their start and end byte positions, with the offset
+    /// applied to all byte positions.
+    ///
+    /// See [`CharRanges::enumerate_chars()`] for more information.
+    #[inline]
+    pub fn enumerate_chars(self) -> EnumerateChars<'a> {
+        EnumerateChars::new(self)
+    }
+
+    /// Returns an iterator over the remaining [`char`]s and their start
+    /// and end byte positions, with the offset applied to all positions,
+    /// if the remaining text is entirely ASCII, otherwise `None`.
+    ///
+    /// See [`CharRanges::ascii()`] for more information.
+    #[inline]
+    pub fn ascii(self) -> Option<AsciiCharRanges<'a>> {
+        AsciiCharRanges::new(self)
+    }
+
+    /// Returns an iterator over the occurrences of `c` in the remaining
+    /// text, and their start and end byte positions, with the offset
+    /// applied to all positions.
+    ///
+    /// See [`CharRanges::match_char_ranges()`] for more information.
+    #[inline]
+    pub fn match_char_ranges(self, c: char) -> MatchCharRanges<'a> {
+        MatchCharRanges::new(self, c)
+    }
+
+    /// Returns an iterator over maximal runs of identical consecutive
+    /// [`char`]s in the remaining text, along with the start and end byte
+    /// positions of the whole run, with the offset applied, and the number
+    /// of [`char`]s in it.
+    ///
+    /// See [`CharRanges::dedup_runs()`] for more information.
+    #[inline]
+    pub fn dedup_runs(self) -> DedupRuns<'a> {
+        DedupRuns::new(self)
+    }
+
+    /// Returns an iterator over the remaining [`char`]s and their start
+    /// and end byte positions, which can peek the next item from either end,
+    /// with the offset applied to all positions.
+    ///
+    /// See [`CharRanges::peekable_ranges()`] for more information.
+    #[inline]
+    pub fn peekable_ranges(self) -> PeekableCharRanges<'a> {
+        PeekableCharRanges::new(self)
+    }
+
+    /// Returns an iterator over the remaining [`char`]s and their start
+    /// and end byte positions, which can peek up to `N` items ahead,
+    /// with the offset applied to all positions.
+    ///
+    /// See [`CharRanges::multipeek()`] for more information.
+    #[inline]
+    pub fn multipeek<const N: usize>(self) -> MultiPeekCharRanges<'a, N> {
+        MultiPeekCharRanges::new(self)
+    }
+
+    /// Returns an iterator over overlapping pairs of adjacent [`char`]s
+    /// in the remaining text, and their start and end byte positions,
+    /// with the offset applied to all positions.
+    ///
+    /// See [`CharRanges::char_pairs()`] for more information.
+    #[inline]
+    pub fn char_pairs(self) -> CharPairs<'a> {
+        CharPairs::new(self)
+    }
+
+    /// Returns an iterator over the remaining [`char`]s and their start
+    /// and end byte positions, along with the previous and next [`char`]s,
+    /// with the offset applied to all positions.
+    ///
+    /// See [`CharRanges::with_context()`] for more information.
+    #[inline]
+    pub fn with_context(self) -> WithContext<'a> {
+        WithContext::new(self)
+    }
+
+    /// Returns an iterator over the remaining [`char`]s mapped to lowercase,
+    /// and the start and end byte positions of the source [`char`] they
+    /// originate from, with the offset applied to all positions.
+    ///
+    /// See [`CharRangesExt::char_ranges_lowercase()`] for more information.
+    #[inline]
+    pub fn lowercase(self) -> CharRangesLowercase<'a> {
+        CharRangesCaseMap::new(self, char::to_lowercase)
+    }
+
+    /// Returns an iterator over the remaining [`char`]s mapped to uppercase,
+    /// and the start and end byte positions of the source [`char`] they
+    /// originate from, with the offset applied to all positions.
+    ///
+    /// See [`CharRangesExt::char_ranges_uppercase()`] for more information.
+    #[inline]
+    pub fn uppercase(self) -> CharRangesUppercase<'a> {
+        CharRangesCaseMap::new(self, char::to_uppercase)
+    }
+
+    /// Returns an iterator over the [`char`]s produced by [`char::escape_debug()`]
+    /// for the remaining [`char`]s, and the start and end byte positions of the
+    /// source [`char`] they originate from, with the offset applied to all
+    /// positions.
+    ///
+    /// See [`CharRangesExt::escape_debug_ranges()`] for more information.
+    #[inline]
+    pub fn escape_debug(self) -> EscapeDebugRanges<'a> {
+        EscapeDebugRanges::new(self)
+    }
+
+    /// Returns an iterator over the decoded [`char`]s of the remaining
+    /// substring, as the body of a string literal, and the start and end
+    /// byte positions of their source, with the offset applied to all
+    /// positions.
+    ///
+    /// See [`CharRangesExt::unescape_ranges()`] for more information.
+    #[inline]
+    pub fn unescape(self) -> UnescapeRanges<'a> {
+        UnescapeRanges::new(self)
+    }
+
+    /// Returns an iterator over the percent-decoded [`char`]s of the
+    /// remaining substring, and the start and end byte positions of
+    /// their source, with the offset applied to all positions.
+    ///
+    /// See [`CharRangesExt::percent_decode_ranges()`] for more information.
+    #[cfg(feature = "percent")]
+    #[inline]
+    pub fn percent_decode(self) -> PercentDecodeRanges<'a> {
+        PercentDecodeRanges::new(self)
+    }
+
+    /// Returns an iterator over the remaining suspicious [`char`]s, their
+    /// start and end byte positions, with the offset applied, and their
+    /// [`CharIssue`].
+    ///
+    /// See [`CharRangesExt::suspicious_char_ranges()`] for more information.
+    #[inline]
+    pub fn suspicious(self) -> SuspiciousCharRanges<'a> {
+        SuspiciousCharRanges::new(self)
+    }
+
+    /// Returns an iterator over the non-overlapping case-insensitive
+    /// matches of `needle` in the remaining substring, and their start
+    /// and end byte positions, with the offset applied to all positions.
+    ///
+    /// See [`CharRangesExt::caseless_match_ranges()`] for more information.
+    #[inline]
+    pub fn caseless_matches<'b>(self, needle: &'b str) -> CaselessMatchRanges<'a, 'b> {
+        CaselessMatchRanges::new(self, needle)
+    }
+
+    /// Returns an iterator over all matches of `needle` in the remaining
+    /// substring, including overlapping matches, and their start and end
+    /// byte positions. With the offset applied to all positions.
+    ///
+    /// See [`CharRangesExt::match_overlapping_ranges()`] for more information.
+    #[inline]
+    pub fn overlapping_matches<'b>(self, needle: &'b str) -> MatchOverlappingRanges<'a, 'b> {
+        MatchOverlappingRanges::new(self, needle)
+    }
+
+    /// Returns an iterator over the remaining [`char`]s in Normalization
+    /// Form C (NFC), and the start and end byte positions of the source
+    /// [`char`]s they were derived from, with the offset applied to all
+    /// positions.
+    ///
+    /// See [`CharRangesExt::nfc_char_ranges()`] for more information.
+    #[cfg(feature = "unicode-normalization")]
+    #[inline]
+    pub fn nfc(self) -> NormalizedCharRanges<'a> {
+        NormalizedCharRanges::nfc(self)
+    }
+
+    /// Returns an iterator over the remaining [`char`]s in Normalization
+    /// Form D (NFD), and the start and end byte positions of the source
+    /// [`char`]s they were derived from, with the offset applied to all
+    /// positions.
+    ///
+    /// See [`CharRangesExt::nfd_char_ranges()`] for more information.
+    #[cfg(feature = "unicode-normalization")]
+    #[inline]
+    pub fn nfd(self) -> NormalizedCharRanges<'a> {
+        NormalizedCharRanges::nfd(self)
+    }
+
+    /// Returns an iterator over the remaining [`char`]s, their start and
+    /// end byte positions, with the offset applied, and the number of
+    /// terminal cells they occupy.
+    ///
+    /// See [`CharRangesExt::char_width_ranges()`] for more information.
+    #[cfg(feature = "unicode-width")]
+    #[inline]
+    pub fn widths(self) -> CharWidthRanges<'a> {
+        CharWidthRanges::new(self)
+    }
+
+    /// Returns an iterator over maximal runs of the remaining [`char`]s
+    /// sharing the same script, producing the start and end byte
+    /// positions of each run, with the offset applied, the script, and
+    /// the substring.
+    ///
+    /// See [`CharRangesExt::script_runs()`] for more information.
+    #[cfg(feature = "unicode-script")]
+    #[inline]
+    pub fn script_runs(self) -> ScriptRuns<'a> {
+        ScriptRuns::new(self)
+    }
+
+    /// Returns an iterator over the remaining [`char`]s, which are not
+    /// valid in an identifier, producing their start and end byte
+    /// positions, with the offset applied, along with why they are
+    /// invalid.
+    ///
+    /// See [`CharRangesExt::invalid_ident_char_ranges()`] for more information.
+    #[cfg(feature = "unicode-ident")]
+    #[inline]
+    pub fn invalid_ident_chars(self, underscore_start: bool) -> InvalidIdentCharRanges<'a> {
+        InvalidIdentCharRanges::new(self, underscore_start)
+    }
+
+    /// Returns an iterator over the remaining [`char`]s, their start and
+    /// end byte positions, with the offset applied, and the start and end
+    /// terminal cell columns they occupy.
+    ///
+    /// See [`CharRanges::width_columns()`] for more information.
+    #[cfg(feature = "unicode-width")]
+    #[inline]
+    pub fn width_columns(self) -> WidthColumns<'a> {
+        WidthColumns::new(self)
+    }
+
+    /// Returns an iterator over maximal runs of the remaining [`char`]s,
+    /// where each run either entirely matches `pred` or entirely doesn't,
+    /// with the offset applied to all ranges.
+    ///
+    /// See [`CharRanges::char_runs()`] for more information.
+    #[inline]
+    pub fn char_runs<P>(self, pred: P) -> CharRuns<'a, P>
+    where
+        P: FnMut(char) -> bool,
+    {
+        CharRuns::new(self, pred)
+    }
+
+    /// Returns an iterator over maximal runs of the remaining [`char`]s
+    /// of the same [`CharClass`], with the offset applied to all ranges.
+    ///
+    /// See [`CharRanges::classify_runs()`] for more information.
+    #[inline]
+    pub fn classify_runs(self) -> ClassifyRuns<'a, fn(char) -> CharClass> {
+        ClassifyRuns::new(self, CharClass::of)
+    }
+
+    /// Returns an iterator over maximal runs of the remaining [`char`]s
+    /// of the same [`CharClass`], where `classify` decides the class of
+    /// each [`char`], with the offset applied to all ranges.
+    ///
+    /// See [`CharRanges::classify_runs_by()`] for more information.
+    #[inline]
+    pub fn classify_runs_by<F>(self, classify: F) -> ClassifyRuns<'a, F>
+    where
+        F: FnMut(char) -> CharClass,
+    {
+        ClassifyRuns::new(self, classify)
+    }
+
+    /// Returns an iterator over the byte ranges of maximal runs of the
+    /// remaining [`char`]s matching `pred`, with the offset applied to
+    /// all ranges.
+    ///
+    /// See [`CharRanges::coalesce_by()`] for more information.
+    #[inline]
+    pub fn coalesce_by<P>(self, pred: P) -> CoalesceBy<'a, P>
+    where
+        P: FnMut(char) -> bool,
+    {
+        CoalesceBy::new(self, pred)
+    }
+
+    /// Returns an iterator over groups of adjacent remaining [`char`]s,
+    /// where `same_group(a, b)` decides whether the [`char`] `b` continues
+    /// the group of the preceding [`char`] `a`, with the offset applied to
+    /// all ranges.
+    ///
+    /// See [`CharRanges::chunk_by()`] for more information.
+    #[inline]
+    pub fn chunk_by<F>(self, same_group: F) -> ChunkBy<'a, F>
+    where
+        F: FnMut(char, char) -> bool,
+    {
+        ChunkBy::new(self, same_group)
+    }
+
+    /// Returns an iterator over the remaining lines, i.e. their start and
+    /// end byte positions, along with the start and end byte positions
+    /// of their indentation, and what it consists of, with the offset
+    /// applied to all ranges.
+    ///
+    /// See [`CharRanges::indentation_ranges()`] for more information.
+    #[inline]
+    pub fn indentation_ranges(self) -> IndentationRanges<'a> {
+        IndentationRanges::new(self)
+    }
+
+    /// Returns an iterator over the remaining lines, which end with
+    /// whitespace, producing the zero-based line number and the start
+    /// and end byte positions of the trailing whitespace, with the offset
+    /// applied to all ranges.
+    ///
+    /// See [`CharRanges::trailing_whitespace_ranges()`] for more information.
+    #[inline]
+    pub fn trailing_whitespace_ranges(self) -> TrailingWhitespaceRanges<'a> {
+        TrailingWhitespaceRanges::new(self)
+    }
+
+    /// Returns an iterator over the remaining lines, which are longer than
+    /// `max_chars` [`char`]s, producing the zero-based line number, the
+    /// start and end byte positions of the line, the start and end byte
+    /// positions of the overflow, and the number of [`char`]s in the line,
+    /// with the offset applied to all ranges.
+    ///
+    /// See [`CharRanges::long_line_ranges()`] for more information.
+    #[inline]
+    pub fn long_line_ranges(self, max_chars: usize) -> LongLineRanges<'a> {
+        LongLineRanges::new(self, max_chars)
+    }
+
+    /// Returns an iterator over the remaining paragraphs, i.e. maximal
+    /// runs of non-blank lines, producing their start and end byte
+    /// positions, along with the substring, with the offset applied to
+    /// all ranges.
+    ///
+    /// See [`CharRanges::paragraph_ranges()`] for more information.
+    #[inline]
+    pub fn paragraph_ranges(self, whitespace_is_blank: bool) -> ParagraphRanges<'a> {
+        ParagraphRanges::new(self, whitespace_is_blank)
+    }
+
+    /// Returns an iterator over the remaining clusters of a base [`char`]
+    /// followed by any combining marks, producing their start and end
+    /// byte positions, along with the substring, with the offset applied
+    /// to all ranges.
+    ///
+    /// See [`CharRanges::legacy_cluster_ranges()`] for more information.
+    #[inline]
+    pub fn legacy_cluster_ranges(self) -> LegacyClusterRanges<'a> {
+        LegacyClusterRanges::new(self)
+    }
+
+    /// Returns an iterator over every `n`th remaining [`char`] and its
+    /// start and end byte positions, starting with the next [`char`],
+    /// with the offset applied to all ranges.
+    ///
+    /// See [`CharRanges::char_step_by()`] for more information.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is `0`.
+    #[inline]
+    pub fn char_step_by(self, n: usize) -> CharStepBy<'a> {
+        CharStepBy::new(self, n)
+    }
+}
+
+impl Iterator for CharRangesOffset<'_> {
+    type Item = (Range<usize>, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (r, c) = self.iter.next()?;
+        Some((self.apply_offset(r), c))
+    }
+
+    #[inline]
     fn count(self) -> usize {
         self.iter.count()
     }
 
-    #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        self.iter.size_hint()
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<(Range<usize>, char)> {
+        self.next_back()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let (r, c) = self.iter.nth(n)?;
+        Some((self.apply_offset(r), c))
+    }
+
+    #[inline]
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.iter
+            .fold(init, |acc, (r, c)| f(acc, (self.apply_offset(r), c)))
+    }
+
+    #[inline]
+    fn for_each<F>(self, mut f: F)
+    where
+        F: FnMut(Self::Item),
+    {
+        self.fold((), |(), item| f(item));
+    }
+}
+
+impl DoubleEndedIterator for CharRangesOffset<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (r, c) = self.iter.next_back()?;
+        Some((self.apply_offset(r), c))
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        let (r, c) = self.iter.nth_back(n)?;
+        Some((self.apply_offset(r), c))
+    }
+
+    #[inline]
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.iter
+            .rfold(init, |acc, (r, c)| f(acc, (self.apply_offset(r), c)))
+    }
+}
+
+impl FusedIterator for CharRangesOffset<'_> {}
+
+/// Converts the remaining substring of a [`CharIndices`] into a
+/// [`CharRangesOffset`], where the offset is the position of the next
+/// [`char`] in the [`CharIndices`].
+///
+/// This preserves the positions, i.e. they remain relative to the
+/// text the [`CharIndices`] was created from.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::CharRangesOffset;
+///
+/// let text = "🗻∈🌏";
+///
+/// let mut chars = text.char_indices();
+/// assert_eq!(chars.next(), Some((0, '🗻')));
+///
+/// let mut chars = CharRangesOffset::from(chars);
+/// assert_eq!(chars.next(), Some((4..7, '∈')));
+/// ```
+impl<'a> From<CharIndices<'a>> for CharRangesOffset<'a> {
+    #[inline]
+    fn from(iter: CharIndices<'a>) -> Self {
+        Self::from_text(iter.as_str(), iter.offset())
+    }
+}
+
+/// Same as <code>iter.[offset(offset)]</code>.
+///
+/// [offset(offset)]: CharRanges::offset
+impl<'a> From<(usize, CharRanges<'a>)> for CharRangesOffset<'a> {
+    #[inline]
+    fn from((offset, iter): (usize, CharRanges<'a>)) -> Self {
+        iter.offset(offset)
+    }
+}
+
+/// Same as <code>iter.[offset(offset)]</code>.
+///
+/// [offset(offset)]: CharRanges::offset
+impl<'a> From<(CharRanges<'a>, usize)> for CharRangesOffset<'a> {
+    #[inline]
+    fn from((iter, offset): (CharRanges<'a>, usize)) -> Self {
+        iter.offset(offset)
+    }
+}
+
+/// Returns an empty iterator with an offset of `0`.
+impl Default for CharRangesOffset<'_> {
+    #[inline]
+    fn default() -> Self {
+        Self::from_text("", 0)
+    }
+}
+
+/// Two iterators are equal if their [`CharRanges`] are equal, and their
+/// offsets are equal.
+///
+/// Like for [`CharRanges`], only the contents of the remaining text
+/// matter, not its address. Note that iterators producing the same items
+/// can still be unequal, e.g. `"ab"` with an offset of `1` after consuming
+/// `'a'`, and `"b"` with an offset of `2`, since their offsets differ.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::CharRangesExt;
+///
+/// let mut a = "ab".char_ranges_offset(1);
+/// a.next();
+/// assert_eq!(a, "xb".char_ranges_offset(1).skip_chars(1));
+///
+/// // Same items, but different offsets
+/// let b = "b".char_ranges_offset(2);
+/// assert!(Iterator::eq(a, b));
+/// assert_ne!(a, b);
+/// ```
+impl PartialEq for CharRangesOffset<'_> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        (self.offset == other.offset) && (self.iter == other.iter)
+    }
+}
+
+impl Eq for CharRangesOffset<'_> {}
+
+/// Hashes the offset and the [`CharRanges`], which is consistent with
+/// [`PartialEq`].
+impl Hash for CharRangesOffset<'_> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.offset.hash(state);
+        self.iter.hash(state);
+    }
+}
+
+/// By default a compact summary is printed, i.e. the `offset`, and the
+/// remaining byte range, with the offset applied, and length, along with
+/// a preview of the remaining text.
+///
+/// The alternate form (`{:#?}`) instead prints the remaining items,
+/// truncated to the first 64.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::CharRangesExt;
+///
+/// let chars = "Hello 🌏".char_ranges_offset(10);
+///
+/// assert_eq!(
+///     format!("{chars:?}"),
+///     r#"CharRangesOffset { offset: 10, range: 10..20, len: 10, text: "Hello 🌏" }"#,
+/// );
+/// ```
+impl fmt::Debug for CharRangesOffset<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            write!(f, "CharRangesOffset(")?;
+            debug_entries(f, *self)?;
+            write!(f, ")")
+        } else {
+            let start = self.iter.start.saturating_add(self.offset);
+            let end = self.iter.end.saturating_add(self.offset);
+            f.debug_struct("CharRangesOffset")
+                .field("offset", &self.offset)
+                .field("range", &(start..end))
+                .field("len", &(self.iter.end - self.iter.start))
+                .field("text", &DebugPreview(self.as_str()))
+                .finish()
+        }
+    }
+}
+
+/// Formats the remaining range and its length, without the text.
+#[cfg(feature = "defmt")]
+impl defmt::Format for CharRanges<'_> {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(
+            f,
+            "CharRanges {{ range: {=usize}..{=usize}, len: {=usize} }}",
+            self.start,
+            self.end,
+            self.end - self.start,
+        );
+    }
+}
+
+/// Formats the offset, the remaining range with the offset applied,
+/// and its length, without the text.
+#[cfg(feature = "defmt")]
+impl defmt::Format for CharRangesOffset<'_> {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(
+            f,
+            "CharRangesOffset {{ offset: {=usize}, range: {=usize}..{=usize}, len: {=usize} }}",
+            self.offset,
+            self.iter.start.saturating_add(self.offset),
+            self.iter.end.saturating_add(self.offset),
+            self.iter.end - self.iter.start,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use core::iter;
+    use std::format;
+    use std::string::String;
+    use std::vec::Vec;
+
+    use super::{
+        BoundaryError, CharRanges, CharRangesExt, CharRangesOffset, OffsetOverflowError, SeekError,
+        Snap,
+    };
+
+    #[test]
+    fn test_empty() {
+        let text = "";
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.next(), None);
+        assert_eq!(chars.next_back(), None);
+        assert_eq!(chars.as_str(), "");
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.next_back(), None);
+        assert_eq!(chars.next(), None);
+        assert_eq!(chars.as_str(), "");
+    }
+
+    #[test]
+    fn test_empty_single_char() {
+        let text = "a";
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.as_str(), "a");
+        assert_eq!(chars.next(), Some((0..1, 'a')));
+        assert_eq!(chars.next_back(), None);
+        assert_eq!(chars.as_str(), "");
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.as_str(), "a");
+        assert_eq!(chars.next_back(), Some((0..1, 'a')));
+        assert_eq!(chars.next(), None);
+        assert_eq!(chars.as_str(), "");
+    }
+
+    #[test]
+    fn test_empty_single_char_multi_byte() {
+        let text = "🌏";
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.as_str(), "🌏");
+        assert_eq!(chars.next(), Some((0..4, '🌏')));
+        assert_eq!(chars.next_back(), None);
+        assert_eq!(chars.as_str(), "");
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.as_str(), "🌏");
+        assert_eq!(chars.next_back(), Some((0..4, '🌏')));
+        assert_eq!(chars.next(), None);
+        assert_eq!(chars.as_str(), "");
+    }
+
+    #[test]
+    fn test_simple() {
+        let text = "Foo";
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.as_str(), "Foo");
+
+        assert_eq!(chars.next(), Some((0..1, 'F')));
+        assert_eq!(chars.as_str(), "oo");
+
+        assert_eq!(chars.next(), Some((1..2, 'o')));
+        assert_eq!(chars.as_str(), "o");
+
+        assert_eq!(chars.next(), Some((2..3, 'o')));
+        assert_eq!(chars.as_str(), "");
+
+        assert_eq!(chars.next(), None);
+        assert_eq!(chars.as_str(), "");
+    }
+
+    #[test]
+    fn test_simple_multi_byte() {
+        let text = "🗻∈🌏";
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.as_str(), "🗻∈🌏");
+
+        assert_eq!(chars.next(), Some((0..4, '🗻')));
+        assert_eq!(chars.next(), Some((4..7, '∈')));
+        assert_eq!(chars.next(), Some((7..11, '🌏')));
+        assert_eq!(chars.next(), None);
+    }
+
+    #[test]
+    fn test_simple_mixed_multi_byte() {
+        let text = "🗻12∈45🌏";
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.as_str(), "🗻12∈45🌏");
+
+        assert_eq!(chars.next(), Some((0..4, '🗻')));
+        assert_eq!(chars.as_str(), "12∈45🌏");
+
+        assert_eq!(chars.next(), Some((4..5, '1')));
+        assert_eq!(chars.as_str(), "2∈45🌏");
+
+        assert_eq!(chars.next(), Some((5..6, '2')));
+        assert_eq!(chars.as_str(), "∈45🌏");
+
+        assert_eq!(chars.next(), Some((6..9, '∈')));
+        assert_eq!(chars.as_str(), "45🌏");
+
+        assert_eq!(chars.next(), Some((9..10, '4')));
+        assert_eq!(chars.as_str(), "5🌏");
+
+        assert_eq!(chars.next(), Some((10..11, '5')));
+        assert_eq!(chars.as_str(), "🌏");
+
+        assert_eq!(chars.next(), Some((11..15, '🌏')));
+        assert_eq!(chars.as_str(), "");
+
+        assert_eq!(chars.next(), None);
+        assert_eq!(chars.as_str(), "");
+    }
+
+    #[test]
+    fn test_simple_next_back() {
+        let text = "Foo";
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.as_str(), "Foo");
+
+        assert_eq!(chars.next_back(), Some((2..3, 'o')));
+        assert_eq!(chars.as_str(), "Fo");
+
+        assert_eq!(chars.next_back(), Some((1..2, 'o')));
+        assert_eq!(chars.as_str(), "F");
+
+        assert_eq!(chars.next_back(), Some((0..1, 'F')));
+        assert_eq!(chars.as_str(), "");
+
+        assert_eq!(chars.next_back(), None);
+        assert_eq!(chars.as_str(), "");
+    }
+
+    #[test]
+    fn test_simple_next_back_multi_byte() {
+        let text = "🗻12∈45🌏";
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.as_str(), "🗻12∈45🌏");
+
+        assert_eq!(chars.next_back(), Some((11..15, '🌏')));
+        assert_eq!(chars.as_str(), "🗻12∈45");
+
+        assert_eq!(chars.next_back(), Some((10..11, '5')));
+        assert_eq!(chars.as_str(), "🗻12∈4");
+
+        assert_eq!(chars.next_back(), Some((9..10, '4')));
+        assert_eq!(chars.as_str(), "🗻12∈");
+
+        assert_eq!(chars.next_back(), Some((6..9, '∈')));
+        assert_eq!(chars.as_str(), "🗻12");
+
+        assert_eq!(chars.next_back(), Some((5..6, '2')));
+        assert_eq!(chars.as_str(), "🗻1");
+
+        assert_eq!(chars.next_back(), Some((4..5, '1')));
+        assert_eq!(chars.as_str(), "🗻");
+
+        assert_eq!(chars.next_back(), Some((0..4, '🗻')));
+        assert_eq!(chars.as_str(), "");
+
+        assert_eq!(chars.next_back(), None);
+        assert_eq!(chars.as_str(), "");
+    }
+
+    #[test]
+    fn test_simple_next_and_next_back() {
+        let text = "Foo Bar";
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.as_str(), "Foo Bar");
+
+        assert_eq!(chars.next_back(), Some((6..7, 'r')));
+        assert_eq!(chars.as_str(), "Foo Ba");
+
+        assert_eq!(chars.next_back(), Some((5..6, 'a')));
+        assert_eq!(chars.as_str(), "Foo B");
+
+        assert_eq!(chars.next(), Some((0..1, 'F')));
+        assert_eq!(chars.as_str(), "oo B");
+
+        assert_eq!(chars.next(), Some((1..2, 'o')));
+        assert_eq!(chars.as_str(), "o B");
+
+        assert_eq!(chars.next_back(), Some((4..5, 'B')));
+        assert_eq!(chars.as_str(), "o ");
+
+        assert_eq!(chars.next(), Some((2..3, 'o')));
+        assert_eq!(chars.as_str(), " ");
+
+        assert_eq!(chars.next(), Some((3..4, ' ')));
+        assert_eq!(chars.as_str(), "");
+
+        assert_eq!(chars.next(), None);
+        assert_eq!(chars.as_str(), "");
+    }
+
+    #[test]
+    fn test_simple_next_and_next_back_multi_byte() {
+        let text = "🗻12∈45🌏";
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.as_str(), "🗻12∈45🌏");
+
+        assert_eq!(chars.next_back(), Some((11..15, '🌏')));
+        assert_eq!(chars.as_str(), "🗻12∈45");
+
+        assert_eq!(chars.next_back(), Some((10..11, '5')));
+        assert_eq!(chars.as_str(), "🗻12∈4");
+
+        assert_eq!(chars.next(), Some((0..4, '🗻')));
+        assert_eq!(chars.as_str(), "12∈4");
+
+        assert_eq!(chars.next(), Some((4..5, '1')));
+        assert_eq!(chars.as_str(), "2∈4");
+
+        assert_eq!(chars.next_back(), Some((9..10, '4')));
+        assert_eq!(chars.as_str(), "2∈");
+
+        assert_eq!(chars.next(), Some((5..6, '2')));
+        assert_eq!(chars.as_str(), "∈");
+
+        assert_eq!(chars.next(), Some((6..9, '∈')));
+        assert_eq!(chars.as_str(), "");
+
+        assert_eq!(chars.next(), None);
+        assert_eq!(chars.as_str(), "");
+    }
+
+    #[test]
+    fn test_last() {
+        let cases = [
+            ("Hello World", 10..11, 'd'),
+            ("Hello 👋 World 🌏", 17..21, '🌏'),
+            ("🗻12∈45🌏", 11..15, '🌏'),
+            ("Hello 🗻12∈45🌏 World", 26..27, 'd'),
+        ];
+        for (text, r, c) in cases {
+            let actual = text.char_ranges().last().unwrap();
+
+            assert_eq!(actual.0, r);
+            assert_eq!(actual.1, c);
+            assert!(text[r].chars().eq([c]));
+        }
+    }
+
+    #[test]
+    #[allow(clippy::iter_nth_zero)]
+    fn test_nth() {
+        let cases = [
+            "Hello World",
+            "Hello 👋 World 🌏",
+            "🗻12∈45🌏",
+            "Hello 🗻12∈45🌏 World",
+        ];
+        for text in cases {
+            // Since `nth()` doesn't use `next()` internally,
+            // then they are be compared
+            let char_ranges1 = text.char_ranges();
+            let char_ranges2 = iter::from_fn({
+                let mut char_ranges = text.char_ranges();
+                move || char_ranges.nth(0)
+            });
+            assert!(char_ranges1.eq(char_ranges2));
+        }
+    }
+
+    #[test]
+    fn test_nth_back() {
+        let cases = [
+            "Hello World",
+            "Hello 👋 World 🌏",
+            "🗻12∈45🌏",
+            "Hello 🗻12∈45🌏 World",
+        ];
+        for text in cases {
+            // Since `nth_back()` doesn't use `next_back()` internally,
+            // then they are be compared
+            let char_ranges1 = text.char_ranges().rev();
+            let char_ranges2 = iter::from_fn({
+                let mut char_ranges = text.char_ranges();
+                move || char_ranges.nth_back(0)
+            });
+            assert!(char_ranges1.eq(char_ranges2));
+        }
+    }
+
+    #[test]
+    fn test_nth_char_indices() {
+        let cases = [
+            "",
+            "Hello World",
+            "Hello 👋 World 🌏",
+            "🗻12∈45🌏",
+            "Hello 🗻12∈45🌏 World ¢",
+            "🗻🌏👋",
+        ];
+        for text in cases {
+            let count = text.chars().count();
+            for n in 0..=(count + 1) {
+                let mut chars = text.char_ranges();
+                let mut expected = text.char_indices();
+                loop {
+                    let item = chars.nth(n);
+                    let expected_item = expected.nth(n).map(|(i, c)| (i..(i + c.len_utf8()), c));
+                    assert_eq!(item, expected_item);
+                    assert_eq!(chars.as_str(), expected.as_str());
+                    if item.is_none() {
+                        break;
+                    }
+                }
+
+                let mut chars = text.char_ranges();
+                let mut expected = text.char_indices();
+                loop {
+                    let item = chars.nth_back(n);
+                    let expected_item = expected
+                        .nth_back(n)
+                        .map(|(i, c)| (i..(i + c.len_utf8()), c));
+                    assert_eq!(item, expected_item);
+                    assert_eq!(chars.as_str(), expected.as_str());
+                    if item.is_none() {
+                        break;
+                    }
+                }
+
+                let mut chars = text.char_ranges();
+                let mut expected = text.char_ranges();
+                let skipped = (0..n).take_while(|_| expected.next().is_some()).count();
+                let result = if skipped == n { Ok(()) } else { Err(skipped) };
+                assert_eq!(chars.advance_by(n), result);
+                assert_eq!(chars.as_str(), expected.as_str());
+
+                let mut chars = text.char_ranges();
+                let mut expected = text.char_ranges();
+                let skipped = (0..n)
+                    .take_while(|_| expected.next_back().is_some())
+                    .count();
+                let result = if skipped == n { Ok(()) } else { Err(skipped) };
+                assert_eq!(chars.advance_back_by(n), result);
+                assert_eq!(chars.as_str(), expected.as_str());
+            }
+        }
+    }
+
+    #[test]
+    fn test_char_ranges() {
+        let text = "Hello World";
+        for (r, c) in text.char_ranges() {
+            let mut chars = text[r].chars();
+            assert_eq!(chars.next(), Some(c));
+            assert_eq!(chars.next(), None);
+        }
+
+        let text = "🗻12∈45🌏";
+        for (r, c) in text.char_ranges() {
+            let mut chars = text[r].chars();
+            assert_eq!(chars.next(), Some(c));
+            assert_eq!(chars.next(), None);
+        }
+    }
+
+    #[test]
+    fn test_char_ranges_start() {
+        let text = "Hello 🗻12∈45🌏 World";
+        let mut chars = text.char_ranges();
+        while let Some((r, _c)) = chars.next_back() {
+            assert_eq!(chars.as_str(), &text[..r.start]);
+        }
+    }
+
+    #[test]
+    fn test_char_ranges_end() {
+        let text = "Hello 🗻12∈45🌏 World";
+        let mut chars = text.char_ranges();
+        while let Some((r, _c)) = chars.next() {
+            assert_eq!(chars.as_str(), &text[r.end..]);
+        }
+    }
+
+    #[test]
+    fn test_full_range() {
+        let text = "Hello 🗻12∈45🌏 World\n";
+        let mut chars = text.char_ranges();
+        while let Some((first, _)) = chars.next() {
+            let (last, _) = chars.next_back().unwrap();
+            assert_eq!(chars.as_str(), &text[first.end..last.start]);
+        }
+    }
+
+    #[test]
+    fn test_offset() {
+        let text = "Hello 👋 World 🌏";
+        let mut chars = text.char_ranges();
+
+        let emoji_end = {
+            assert_eq!(chars.next(), Some((0..1, 'H')));
+            assert_eq!(chars.next(), Some((1..2, 'e')));
+            assert_eq!(chars.next(), Some((2..3, 'l')));
+            assert_eq!(chars.next(), Some((3..4, 'l')));
+            assert_eq!(chars.next(), Some((4..5, 'o')));
+            assert_eq!(chars.next(), Some((5..6, ' ')));
+
+            let emoji_waving_hand = chars.next();
+            assert_eq!(emoji_waving_hand, Some((6..10, '👋')));
+
+            emoji_waving_hand.unwrap().0.end
+        };
+
+        let offset_chars = text[emoji_end..].char_ranges().offset(emoji_end);
+        assert_eq!(chars.as_str(), offset_chars.as_str());
+
+        for offset_char in offset_chars {
+            assert_eq!(chars.next(), Some(offset_char));
+        }
+
+        assert_eq!(chars.next(), None);
+    }
+
+    #[test]
+    fn test_peek() {
+        let text = "Hello 🗻12∈45🌏 World";
+
+        let mut chars = text.char_ranges();
+        loop {
+            let peeked = chars.peek();
+            assert_eq!(chars.peek(), peeked);
+            assert_eq!(
+                chars.as_str(),
+                &text[peeked.as_ref().map_or(text.len(), |(r, _)| r.start)..]
+            );
+
+            assert_eq!(chars.next(), peeked);
+            if peeked.is_none() {
+                break;
+            }
+        }
+        assert_eq!(chars.peek(), None);
+        assert_eq!(chars.peek_back(), None);
+    }
+
+    #[test]
+    fn test_peek_back() {
+        let text = "Hello 🗻12∈45🌏 World";
+
+        let mut chars = text.char_ranges();
+        loop {
+            let peeked = chars.peek_back();
+            assert_eq!(chars.peek_back(), peeked);
+
+            assert_eq!(chars.next_back(), peeked);
+            if peeked.is_none() {
+                break;
+            }
+        }
+        assert_eq!(chars.peek(), None);
+        assert_eq!(chars.peek_back(), None);
+    }
+
+    #[test]
+    fn test_peek_both_ends() {
+        let text = "🗻∈🌏";
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.peek(), Some((0..4, '🗻')));
+        assert_eq!(chars.peek_back(), Some((7..11, '🌏')));
+        assert_eq!(chars.as_str(), "🗻∈🌏");
+
+        assert_eq!(chars.next(), Some((0..4, '🗻')));
+        assert_eq!(chars.next_back(), Some((7..11, '🌏')));
+
+        assert_eq!(chars.peek(), Some((4..7, '∈')));
+        assert_eq!(chars.peek_back(), Some((4..7, '∈')));
+
+        assert_eq!(chars.next(), Some((4..7, '∈')));
+        assert_eq!(chars.peek(), None);
+        assert_eq!(chars.peek_back(), None);
+    }
+
+    #[test]
+    fn test_peek_offset() {
+        let text = "Hello 👋 World 🌏";
+
+        let start = 6;
+        let mut chars = text[start..].char_ranges_offset(start);
+        while let Some(peeked) = chars.peek() {
+            assert_eq!(chars.peek(), Some(peeked.clone()));
+            assert_eq!(chars.next(), Some(peeked));
+        }
+        assert_eq!(chars.peek(), None);
+
+        let chars = text[start..].char_ranges_offset(start);
+        assert_eq!(chars.peek(), Some((6..10, '👋')));
+        assert_eq!(chars.peek_back(), Some((17..21, '🌏')));
+        assert!(chars.eq(text.char_ranges().skip(6)));
+    }
+
+    #[test]
+    fn test_front_back_offset() {
+        let text = "Hello 🗻12∈45🌏 World";
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.front_offset(), 0);
+        assert_eq!(chars.back_offset(), text.len());
+
+        loop {
+            let front = chars.front_offset();
+            let back = chars.back_offset();
+            assert_eq!(&text[front..back], chars.as_str());
+            assert_eq!(
+                chars.peek().map(|(r, _)| r.start),
+                (front != back).then_some(front)
+            );
+            assert_eq!(
+                chars.peek_back().map(|(r, _)| r.end),
+                (front != back).then_some(back)
+            );
+
+            match (chars.next(), chars.next_back()) {
+                (Some((r, _)), Some((r_back, _))) => {
+                    assert_eq!(chars.front_offset(), r.end);
+                    assert_eq!(chars.back_offset(), r_back.start);
+                }
+                _ => break,
+            }
+        }
+
+        assert_eq!(chars.front_offset(), chars.back_offset());
+    }
+
+    #[test]
+    fn test_front_back_offset_mixed() {
+        let text = "🗻12∈45🌏";
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.nth(1), Some((4..5, '1')));
+        assert_eq!(chars.nth_back(1), Some((10..11, '5')));
+        assert_eq!(chars.front_offset(), 5);
+        assert_eq!(chars.back_offset(), 10);
+
+        assert_eq!(chars.next(), Some((5..6, '2')));
+        assert_eq!(chars.next_back(), Some((9..10, '4')));
+        assert_eq!(chars.front_offset(), 6);
+        assert_eq!(chars.back_offset(), 9);
+
+        assert_eq!(chars.next_back(), Some((6..9, '∈')));
+        assert_eq!(chars.front_offset(), 6);
+        assert_eq!(chars.back_offset(), 6);
+
+        assert_eq!(chars.next(), None);
+        assert_eq!(chars.front_offset(), 6);
+        assert_eq!(chars.back_offset(), 6);
+    }
+
+    #[test]
+    fn test_front_back_offset_offset() {
+        let text = "Hello 👋 World 🌏";
+
+        let start = 11;
+        let mut chars = text[start..].char_ranges_offset(start);
+        assert_eq!(chars.front_offset(), 11);
+        assert_eq!(chars.back_offset(), 21);
+
+        assert_eq!(chars.next_back(), Some((17..21, '🌏')));
+        assert_eq!(chars.next(), Some((11..12, 'W')));
+        assert_eq!(chars.front_offset(), 12);
+        assert_eq!(chars.back_offset(), 17);
+
+        assert_eq!(chars.by_ref().count(), 5);
+        assert_eq!(chars.front_offset(), chars.back_offset());
+        assert_eq!(chars.front_offset(), 17);
+    }
+
+    #[test]
+    fn test_mark() {
+        let text = "Hello 👋 World 🌏";
+
+        let mut chars = text.char_ranges();
+        let start = chars.mark();
+        assert_eq!(chars.slice_since(start), (0..0, ""));
+
+        chars.advance_by(6).unwrap();
+        assert_eq!(chars.slice_since(start), (0..6, "Hello "));
+
+        // Consuming from the back does not affect the front
+        let world = chars.mark();
+        assert_eq!(chars.next_back(), Some((17..21, '🌏')));
+        assert_eq!(chars.slice_since(world), (6..6, ""));
+
+        assert_eq!(chars.next(), Some((6..10, '👋')));
+        assert_eq!(chars.slice_since(world), (6..10, "👋"));
+        assert_eq!(chars.slice_since(start), (0..10, "Hello 👋"));
+
+        let mid = chars.mark();
+        assert_eq!(chars.slice_between(start, mid), (0..10, "Hello 👋"));
+        assert_eq!(chars.slice_between(world, mid), (6..10, "👋"));
+        assert_eq!(chars.slice_between(mid, mid), (10..10, ""));
+
+        // Mark at EOF
+        chars.by_ref().for_each(drop);
+        let eof = chars.mark();
+        assert_eq!(chars.slice_since(eof), (17..17, ""));
+        assert_eq!(chars.slice_between(mid, eof), (10..17, " World "));
+
+        // Marks are valid for other iterators over the same text
+        let chars = text.char_ranges();
+        assert_eq!(chars.slice_between(world, eof), (6..17, "👋 World "));
+    }
+
+    #[test]
+    fn test_mark_offset() {
+        let text = "Hello 👋 World 🌏";
+
+        let start = 6;
+        let mut chars = text[start..].char_ranges_offset(start);
+        let mark = chars.mark();
+        chars.next_back();
+        assert_eq!(chars.nth(1), Some((10..11, ' ')));
+        assert_eq!(chars.slice_since(mark), (6..11, "👋 "));
+
+        let end = chars.mark();
+        chars.by_ref().for_each(drop);
+        assert_eq!(chars.slice_between(mark, end), (6..11, "👋 "));
+        assert_eq!(chars.slice_since(end), (11..17, "World "));
+    }
+
+    #[test]
+    #[should_panic(expected = "mark was taken from an iterator over a different text")]
+    fn test_mark_different_text() {
+        let text = String::from("Hello World");
+        let other = text.clone();
+
+        let mark = text.char_ranges().mark();
+        other.char_ranges().slice_since(mark);
+    }
+
+    #[test]
+    #[should_panic(expected = "mark was taken from an iterator over a different text")]
+    fn test_mark_substring() {
+        let text = "Hello World";
+
+        let mark = text.char_ranges().mark();
+        text[..5].char_ranges().slice_since(mark);
+    }
+
+    #[test]
+    #[should_panic(expected = "mark at 5 is after mark at 0")]
+    fn test_mark_after_front() {
+        let text = "Hello World";
+
+        let mut chars = text.char_ranges();
+        chars.advance_by(5).unwrap();
+        let mark = chars.mark();
+        chars.reset_front();
+        chars.slice_since(mark);
+    }
+
+    #[test]
+    fn test_consumed_prefix_suffix() {
+        let text = "Hello 🗻12∈45🌏 World";
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.original_str(), text);
+        assert_eq!(chars.consumed_prefix(), "");
+        assert_eq!(chars.consumed_suffix(), "");
+
+        assert_eq!(chars.next(), Some((0..1, 'H')));
+        assert_eq!(chars.next_back(), Some((26..27, 'd')));
+        assert_eq!(chars.consumed_prefix(), "H");
+        assert_eq!(chars.consumed_suffix(), "d");
+
+        assert_eq!(chars.nth(5), Some((6..10, '🗻')));
+        assert_eq!(chars.consumed_prefix(), "Hello 🗻");
+        assert_eq!(chars.as_str(), "12∈45🌏 Worl");
+
+        assert_eq!(chars.nth_back(5), Some((17..21, '🌏')));
+        assert_eq!(chars.consumed_suffix(), "🌏 World");
+        assert_eq!(chars.as_str(), "12∈45");
+
+        assert_eq!(chars.next_back(), Some((16..17, '5')));
+        assert_eq!(chars.next(), Some((10..11, '1')));
+        assert_eq!(chars.consumed_prefix(), "Hello 🗻1");
+        assert_eq!(chars.consumed_suffix(), "5🌏 World");
+        assert_eq!(chars.original_str(), text);
+
+        let prefix = chars.consumed_prefix();
+        let suffix = chars.consumed_suffix();
+        assert_eq!(
+            prefix.len() + chars.as_str().len() + suffix.len(),
+            text.len()
+        );
+
+        assert_eq!(chars.nth(10), None);
+        assert_eq!(chars.consumed_prefix(), "Hello 🗻12∈4");
+        assert_eq!(chars.consumed_suffix(), "5🌏 World");
+        assert_eq!(chars.as_str(), "");
+    }
+
+    #[test]
+    fn test_consumed_prefix_suffix_exhausted_back() {
+        let text = "🗻∈🌏";
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.next(), Some((0..4, '🗻')));
+        assert_eq!(chars.nth_back(5), None);
+        assert_eq!(chars.consumed_prefix(), "🗻");
+        assert_eq!(chars.consumed_suffix(), "∈🌏");
+        assert_eq!(chars.front_offset(), chars.back_offset());
+    }
+
+    #[test]
+    fn test_consumed_prefix_suffix_offset() {
+        let text = "Hello 👋 World 🌏";
+
+        let start = 11;
+        let mut chars = text[start..].char_ranges_offset(start);
+        assert_eq!(chars.original_str(), "World 🌏");
+
+        assert_eq!(chars.next(), Some((11..12, 'W')));
+        assert_eq!(chars.next_back(), Some((17..21, '🌏')));
+        assert_eq!(chars.consumed_prefix(), "W");
+        assert_eq!(chars.consumed_suffix(), "🌏");
+        assert_eq!(chars.as_str(), "orld ");
+    }
+
+    #[test]
+    fn test_next_range() {
+        let text = "a¢∈🌏b";
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.next_range(), Some(0..1));
+        assert_eq!(chars.next_range(), Some(1..3));
+        assert_eq!(chars.next_range(), Some(3..6));
+        assert_eq!(chars.next_range(), Some(6..10));
+        assert_eq!(chars.next_range(), Some(10..11));
+        assert_eq!(chars.next_range(), None);
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.next_back_range(), Some(10..11));
+        assert_eq!(chars.next_back_range(), Some(6..10));
+        assert_eq!(chars.next_back_range(), Some(3..6));
+        assert_eq!(chars.next_back_range(), Some(1..3));
+        assert_eq!(chars.next_back_range(), Some(0..1));
+        assert_eq!(chars.next_back_range(), None);
+    }
+
+    #[test]
+    fn test_next_range_mixed() {
+        let text = "Hello 🗻12∈45🌏 World ¢¢";
+
+        // Every combination of calls, selected by the bits of `pattern`
+        for pattern in 0u32..256 {
+            let mut chars = text.char_ranges();
+            let mut expected = text.char_ranges();
+            for i in 0.. {
+                let item = match (pattern >> (i % 8)) & 0b11 {
+                    0 => chars.next().map(|(r, _)| r),
+                    1 => chars.next_range(),
+                    2 => chars.next_back().map(|(r, _)| r),
+                    _ => chars.next_back_range(),
+                };
+                let want = match (pattern >> (i % 8)) & 0b11 {
+                    0 | 1 => expected.next(),
+                    _ => expected.next_back(),
+                };
+                assert_eq!(item, want.map(|(r, _)| r));
+                assert_eq!(
+                    chars.as_str(),
+                    &text[chars.front_offset()..chars.back_offset()]
+                );
+                if item.is_none() {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_next_range_offset() {
+        let text = "Hello 👋 World 🌏";
+
+        let start = 11;
+        let mut chars = text[start..].char_ranges_offset(start);
+        assert_eq!(chars.next_range(), Some(11..12));
+        assert_eq!(chars.next_back_range(), Some(17..21));
+        assert_eq!(chars.as_str(), "orld ");
+    }
+
+    #[test]
+    fn test_from_char_indices() {
+        let text = "Hello 🗻12∈45🌏 World";
+
+        for n in 0..=text.chars().count() {
+            let mut chars = text.char_indices();
+            chars.by_ref().take(n).for_each(drop);
+            let front = chars.offset();
+
+            let expected = text.char_ranges().skip(n);
+
+            let ranges = CharRangesOffset::from(chars.clone());
+            assert_eq!(ranges.as_str(), &text[front..]);
+            assert!(ranges.eq(expected.clone()));
+
+            let ranges = CharRanges::from(chars.clone());
+            assert_eq!(ranges.as_str(), &text[front..]);
+            assert!(ranges
+                .map(|(r, c)| ((r.start + front)..(r.end + front), c))
+                .eq(expected));
+        }
+    }
+
+    #[test]
+    fn test_from_chars() {
+        let text = "🗻∈🌏";
+
+        let mut chars = text.chars();
+        assert_eq!(chars.next(), Some('🗻'));
+        assert_eq!(chars.next_back(), Some('🌏'));
+
+        let mut chars = CharRanges::from(chars);
+        assert_eq!(chars.as_str(), "∈");
+        assert_eq!(chars.next(), Some((0..3, '∈')));
+        assert_eq!(chars.next(), None);
+    }
+
+    #[test]
+    fn test_into_char_indices() {
+        let text = "🗻12∈45🌏";
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.next(), Some((0..4, '🗻')));
+        assert_eq!(chars.next_back(), Some((11..15, '🌏')));
+
+        let chars = chars.into_char_indices();
+        assert_eq!(chars.as_str(), "12∈45");
+        assert!(chars.eq("12∈45".char_indices()));
+
+        // Roundtrip
+        let mut chars = text.char_indices();
+        assert_eq!(chars.nth(3), Some((6, '∈')));
+        let mut chars = CharRangesOffset::from(chars);
+        assert_eq!(chars.next(), Some((9..10, '4')));
+        let chars = chars.into_char_indices();
+        assert_eq!(chars.as_str(), "5🌏");
+    }
+
+    #[test]
+    fn test_advance_by() {
+        let text = "a🗻b🌏c";
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.advance_by(0), Ok(()));
+        assert_eq!(chars.as_str(), text);
+
+        assert_eq!(chars.advance_by(2), Ok(()));
+        assert_eq!(chars.as_str(), "b🌏c");
+        assert_eq!(chars.next(), Some((5..6, 'b')));
+
+        assert_eq!(chars.advance_by(1), Ok(()));
+        assert_eq!(chars.next(), Some((10..11, 'c')));
+        assert_eq!(chars.advance_by(0), Ok(()));
+        assert_eq!(chars.advance_by(1), Err(0));
+
+        // Exactly to the end
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.advance_by(5), Ok(()));
+        assert_eq!(chars.as_str(), "");
+        assert_eq!(chars.next(), None);
+
+        // Past the end
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.advance_by(100), Err(5));
+        assert_eq!(chars.as_str(), "");
+        assert_eq!(chars.front_offset(), text.len());
+    }
+
+    #[test]
+    fn test_advance_back_by() {
+        let text = "a🗻b🌏c";
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.advance_back_by(0), Ok(()));
+        assert_eq!(chars.as_str(), text);
+
+        assert_eq!(chars.advance_back_by(2), Ok(()));
+        assert_eq!(chars.as_str(), "a🗻b");
+        assert_eq!(chars.next_back(), Some((5..6, 'b')));
+
+        assert_eq!(chars.advance_back_by(1), Ok(()));
+        assert_eq!(chars.next_back(), Some((0..1, 'a')));
+        assert_eq!(chars.advance_back_by(1), Err(0));
+
+        // Exactly to the end
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.advance_back_by(5), Ok(()));
+        assert_eq!(chars.as_str(), "");
+
+        // Past the end, meeting the front
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.next(), Some((0..1, 'a')));
+        assert_eq!(chars.advance_back_by(100), Err(4));
+        assert_eq!(chars.as_str(), "");
+        assert_eq!(chars.back_offset(), 1);
+    }
+
+    #[test]
+    fn test_advance_by_offset() {
+        let text = "Hello 👋 World 🌏";
+
+        let start = 6;
+        let mut chars = text[start..].char_ranges_offset(start);
+        assert_eq!(chars.advance_by(2), Ok(()));
+        assert_eq!(chars.advance_back_by(2), Ok(()));
+        assert_eq!(chars.as_str(), "World");
+        assert_eq!(chars.next(), Some((11..12, 'W')));
+        assert_eq!(chars.next_back(), Some((15..16, 'd')));
+    }
+
+    #[test]
+    fn test_split_at_char() {
+        let text = "Hello 🗻12∈45🌏 World";
+
+        let count = text.chars().count();
+        for n in 0..(count + 2) {
+            let (left, right) = text.char_ranges().split_at_char(n);
+            assert_eq!(left.count(), n.min(count));
+            assert_eq!(left.back_offset(), right.front_offset());
+            assert_eq!(left.as_str().len() + right.as_str().len(), text.len());
+            assert!(left.chain(right).eq(text.char_ranges()));
+        }
+
+        let (left, mut right) = text.char_ranges().split_at_char(count + 1);
+        assert_eq!(left.as_str(), text);
+        assert_eq!(right.as_str(), "");
+        assert_eq!(right.next(), None);
+    }
+
+    #[test]
+    fn test_split_at_char_offset() {
+        let text = "Hello 👋 World 🌏";
+
+        let start = 6;
+        let mut chars = text[start..].char_ranges_offset(start);
+        assert_eq!(chars.next(), Some((6..10, '👋')));
+        assert_eq!(chars.next_back(), Some((17..21, '🌏')));
+
+        let (mut left, mut right) = chars.split_at_char(3);
+        assert_eq!(left.as_str(), " Wo");
+        assert_eq!(right.as_str(), "rld ");
+        assert_eq!(left.next_back(), Some((12..13, 'o')));
+        assert_eq!(right.next(), Some((13..14, 'r')));
+        assert_eq!(right.next_back(), Some((16..17, ' ')));
+    }
+
+    #[test]
+    fn test_partition_at_byte() {
+        let text = "a🗻∈🌏b";
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.next(), Some((0..1, 'a')));
+        assert_eq!(chars.next_back(), Some((12..13, 'b')));
+
+        // Every char boundary within the remaining chars
+        for pos in (1..=12).filter(|&pos| text.is_char_boundary(pos)) {
+            let (left, right) = chars.partition_at_byte(pos).unwrap();
+            assert_eq!(left.back_offset(), pos);
+            assert_eq!(right.front_offset(), pos);
+            assert_eq!(left.as_str(), &text[1..pos]);
+            assert_eq!(right.as_str(), &text[pos..12]);
+            assert!(left.chain(right).eq(chars));
+        }
+
+        // At the front and at the back
+        let (left, right) = chars.partition_at_byte(1).unwrap();
+        assert_eq!(left.as_str(), "");
+        assert_eq!(right.as_str(), "🗻∈🌏");
+        let (left, right) = chars.partition_at_byte(12).unwrap();
+        assert_eq!(left.as_str(), "🗻∈🌏");
+        assert_eq!(right.as_str(), "");
+    }
+
+    #[test]
+    fn test_partition_at_byte_errors() {
+        let text = "a🗻∈🌏b";
+
+        let mut chars = text.char_ranges();
+        chars.next();
+        chars.next_back();
+
+        // Within an emoji
+        for pos in 9..12 {
+            let err = chars.partition_at_byte(pos).unwrap_err();
+            assert_eq!(
+                err,
+                BoundaryError::NotCharBoundary {
+                    pos,
+                    range: 8..12,
+                    c: '🌏',
+                }
+            );
+        }
+        let err = chars.partition_at_byte(6).unwrap_err();
+        assert_eq!(err.pos(), 6);
+        assert_eq!(
+            format!("{err}"),
+            "position 6 is not on a char boundary, it is within '∈' at 5..8"
+        );
+
+        // Outside the remaining chars
+        for pos in [0, 13, 14, usize::MAX] {
+            let err = chars.partition_at_byte(pos).unwrap_err();
+            assert_eq!(
+                err,
+                BoundaryError::OutOfBounds {
+                    pos,
+                    remaining: 1..12,
+                }
+            );
+        }
+        assert_eq!(
+            format!("{}", chars.partition_at_byte(0).unwrap_err()),
+            "position 0 is outside the remaining chars at 1..12"
+        );
+
+        // Offset
+        let chars = text[1..].char_ranges_offset(1);
+        assert_eq!(
+            chars.partition_at_byte(0),
+            Err(BoundaryError::OutOfBounds {
+                pos: 0,
+                remaining: 1..13,
+            })
+        );
+        let (left, right) = chars.partition_at_byte(5).unwrap();
+        assert!(left.eq([(1..5, '🗻')]));
+        assert!(right.eq([(5..8, '∈'), (8..12, '🌏'), (12..13, 'b')]));
+    }
+
+    #[test]
+    fn test_take_chars() {
+        let text = "Hello 🗻12∈45🌏 World";
+
+        // `as_str()` at every step of the window
+        let mut chars = text.char_ranges().skip_chars(5).take_chars(6);
+        let window = " 🗻12∈4";
+        assert_eq!(chars.as_str(), window);
+        for (r, c) in text[5..].char_ranges_offset(5).take(6) {
+            assert_eq!(chars.next(), Some((r.clone(), c)));
+            assert_eq!(chars.as_str(), &text[r.end..(5 + window.len())]);
+        }
+        assert_eq!(chars.next(), None);
+        assert_eq!(chars.as_str(), "");
+
+        // Double-ended over the window
+        let chars = text.char_ranges().skip_chars(5).take_chars(6);
+        assert!(chars.rev().eq(text.char_ranges_in(5..16).rev()));
+
+        let mut chars = text.char_ranges().take_chars(3);
+        assert_eq!(chars.next_back(), Some((2..3, 'l')));
+        assert_eq!(chars.as_str(), "He");
+
+        // Fewer than `n` remaining
+        let chars = text.char_ranges().take_chars(100);
+        assert_eq!(chars.as_str(), text);
+        assert_eq!(text.char_ranges().take_chars(0).next(), None);
+
+        // Offset is preserved
+        let mut chars = text[6..].char_ranges_offset(6).take_chars(2);
+        assert_eq!(chars.offset(), 6);
+        assert_eq!(chars.next_back(), Some((10..11, '1')));
+        assert_eq!(chars.as_str(), "🗻");
+    }
+
+    #[test]
+    fn test_skip_chars() {
+        let text = "Hello 🗻12∈45🌏 World";
+
+        let count = text.chars().count();
+        for n in 1..(count + 2) {
+            let mut expected = text.char_ranges();
+            _ = expected.nth(n - 1);
+
+            let chars = text.char_ranges().skip_chars(n);
+            assert_eq!(chars.as_str(), expected.as_str());
+            assert!(chars.eq(expected));
+
+            let mut expected = text.char_ranges_offset(3);
+            _ = expected.nth(n - 1);
+            assert!(text.char_ranges_offset(3).skip_chars(n).eq(expected));
+        }
+
+        assert_eq!(text.char_ranges().skip_chars(0).as_str(), text);
+    }
+
+    #[test]
+    fn test_last_n() {
+        let text = "Hello 🗻12∈45🌏 World";
+
+        let count = text.chars().count();
+        for n in 0..(count + 2) {
+            let mut expected = text.char_ranges().rev().take(n).collect::<Vec<_>>();
+            expected.reverse();
+
+            let start = expected.first().map_or(text.len(), |(r, _c)| r.start);
+            assert_eq!(text.last_n(n), (start..text.len(), &text[start..]));
+            assert!(text.last_n_ranges(n).eq(expected.iter().cloned()));
+            assert!(text
+                .last_n_ranges(n)
+                .rev()
+                .eq(expected.iter().rev().cloned()));
+        }
+
+        // n = 0 is empty at the end
+        assert_eq!(text.last_n(0), (text.len()..text.len(), ""));
+        assert_eq!(text.last_n_ranges(0).next(), None);
+
+        // n >= char count is the whole text
+        assert_eq!(text.last_n(count), (0..text.len(), text));
+        assert_eq!(text.last_n(usize::MAX), (0..text.len(), text));
+        assert_eq!("".last_n(3), (0..0, ""));
+
+        // Emoji at the boundary
+        assert_eq!("a🌏".last_n(1), (1..5, "🌏"));
+        assert_eq!("🌏a".last_n(2), (0..5, "🌏a"));
+        assert_eq!("🗻🌏".last_n(1), (4..8, "🌏"));
+    }
+
+    #[test]
+    fn test_last_n_remaining() {
+        let text = "Hello 🗻12∈45🌏 World";
+
+        // Only the remaining chars are considered
+        let mut chars = text.char_ranges();
+        chars.advance_by(6).unwrap();
+        chars.advance_back_by(6).unwrap();
+        assert_eq!(chars.as_str(), "🗻12∈45🌏");
+        assert_eq!(chars.last_n(2), (16..21, "5🌏"));
+        assert_eq!(chars.last_n(100), (6..21, "🗻12∈45🌏"));
+        assert!(chars.last_n_ranges(100).eq(chars));
+
+        // Offset is applied
+        let chars = text[6..].char_ranges_offset(6);
+        assert_eq!(chars.last_n(7), (17..27, "🌏 World"));
+        let mut chars = chars.last_n_ranges(7);
+        assert_eq!(chars.offset(), 6);
+        assert_eq!(chars.next(), Some((17..21, '🌏')));
+    }
+
+    #[test]
+    fn test_next_if() {
+        let text = "🗻12∈";
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.next_if(|c| c.is_ascii_digit()), None);
+        assert_eq!(chars.as_str(), text);
+        assert_eq!(chars.front_offset(), 0);
+
+        assert_eq!(chars.next_if(|c| c == '🗻'), Some((0..4, '🗻')));
+        assert_eq!(chars.next_if(|c| c.is_ascii_digit()), Some((4..5, '1')));
+        assert_eq!(chars.next_if(|c| c.is_ascii_digit()), Some((5..6, '2')));
+        assert_eq!(chars.next_if(|c| c.is_ascii_digit()), None);
+        assert_eq!(chars.as_str(), "∈");
+
+        assert_eq!(chars.next_if_eq('x'), None);
+        assert_eq!(chars.as_str(), "∈");
+        assert_eq!(chars.next_if_eq('∈'), Some((6..9, '∈')));
+        assert_eq!(chars.next_if_eq('∈'), None);
+        assert_eq!(chars.next_if(|_| true), None);
+    }
+
+    #[test]
+    fn test_next_back_if() {
+        let text = "🗻12∈";
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.next_back_if(|c| c.is_ascii_digit()), None);
+        assert_eq!(chars.as_str(), text);
+        assert_eq!(chars.back_offset(), text.len());
+
+        assert_eq!(chars.next_back_if(|c| c == '∈'), Some((6..9, '∈')));
+        assert_eq!(
+            chars.next_back_if(|c| c.is_ascii_digit()),
+            Some((5..6, '2'))
+        );
+        assert_eq!(
+            chars.next_back_if(|c| c.is_ascii_digit()),
+            Some((4..5, '1'))
+        );
+        assert_eq!(chars.next_back_if(|c| c.is_ascii_digit()), None);
+        assert_eq!(chars.as_str(), "🗻");
+    }
+
+    #[test]
+    fn test_next_if_offset() {
+        let text = "Hello 👋 World 🌏";
+
+        let start = 6;
+        let mut chars = text[start..].char_ranges_offset(start);
+        assert_eq!(chars.next_if_eq(' '), None);
+        assert_eq!(chars.as_str(), "👋 World 🌏");
+
+        assert_eq!(chars.next_if_eq('👋'), Some((6..10, '👋')));
+        assert_eq!(chars.next_if(char::is_whitespace), Some((10..11, ' ')));
+        assert_eq!(chars.next_back_if(char::is_whitespace), None);
+        assert_eq!(chars.next_back_if(|c| c == '🌏'), Some((17..21, '🌏')));
+        assert_eq!(chars.next_back_if(char::is_whitespace), Some((16..17, ' ')));
+        assert_eq!(chars.as_str(), "World");
+    }
+
+    #[test]
+    fn test_take_while_range() {
+        let text = "🗻∈🌏 abc";
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.take_while_range(char::is_whitespace), (0..0, ""));
+        assert_eq!(chars.as_str(), text);
+
+        let (r, s) = chars.take_while_range(|c| !c.is_whitespace());
+        assert_eq!(r, 0..11);
+        assert_eq!(s, "🗻∈🌏");
+        assert_eq!(&text[r], s);
+        assert_eq!(chars.next(), Some((11..12, ' ')));
+
+        let (r, s) = chars.take_while_range(char::is_alphabetic);
+        assert_eq!(r, 12..15);
+        assert_eq!(s, "abc");
+        assert_eq!(chars.next(), None);
+        assert_eq!(chars.take_while_range(|_| true), (15..15, ""));
+    }
+
+    #[test]
+    fn test_take_while_range_offset() {
+        let text = "Hello 👋 World 🌏";
+
+        let start = 6;
+        let mut chars = text[start..].char_ranges_offset(start);
+        assert_eq!(chars.take_while_range(char::is_alphabetic), (6..6, ""));
+        assert_eq!(chars.take_while_range(|c| c != 'W'), (6..11, "👋 "));
+        assert_eq!(
+            chars.take_while_range(char::is_alphabetic),
+            (11..16, "World")
+        );
+        assert_eq!(chars.next(), Some((16..17, ' ')));
+        assert_eq!(chars.take_while_range(|_| true), (17..21, "🌏"));
+        assert_eq!(chars.take_while_range(|_| true), (21..21, ""));
+    }
+
+    #[test]
+    fn test_skip_while_range() {
+        let text = " \u{3000}\tfoo \u{3000}";
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.skip_while_range(char::is_alphabetic), 0..0);
+        assert_eq!(chars.as_str(), text);
+
+        assert_eq!(chars.skip_while_range(char::is_whitespace), 0..5);
+        assert_eq!(chars.as_str(), "foo \u{3000}");
+
+        assert_eq!(chars.skip_back_while_range(char::is_alphabetic), 12..12);
+        assert_eq!(chars.skip_back_while_range(char::is_whitespace), 8..12);
+        assert_eq!(chars.as_str(), "foo");
+
+        assert_eq!(chars.skip_while_range(|_| true), 5..8);
+        assert_eq!(chars.as_str(), "");
+        assert_eq!(chars.skip_while_range(|_| true), 8..8);
+        assert_eq!(chars.skip_back_while_range(|_| true), 8..8);
+        assert_eq!(chars.next(), None);
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.skip_back_while_range(|_| true), 0..12);
+        assert_eq!(chars.as_str(), "");
+    }
+
+    #[test]
+    fn test_skip_while_range_offset() {
+        let text = "Hello 👋 World 🌏";
+
+        let start = 5;
+        let mut chars = text[start..].char_ranges_offset(start);
+        assert_eq!(chars.skip_while_range(char::is_whitespace), 5..6);
+        assert_eq!(chars.skip_back_while_range(|c| !c.is_alphabetic()), 16..21);
+        assert_eq!(chars.as_str(), "👋 World");
+        assert_eq!(chars.skip_while_range(|c| !c.is_alphabetic()), 6..11);
+        assert_eq!(chars.as_str(), "World");
+    }
+
+    #[test]
+    fn test_find_range() {
+        let text = "Hello 🗻12∈45🌏 World";
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.find_range(|c| c == 'H'), Some((0..1, 'H')));
+        assert_eq!(chars.as_str(), "ello 🗻12∈45🌏 World");
+
+        assert_eq!(
+            chars.find_range(|c: char| !c.is_ascii()),
+            Some((6..10, '🗻'))
+        );
+        assert_eq!(chars.as_str(), "12∈45🌏 World");
+
+        assert_eq!(
+            chars.find_range(|c: char| !c.is_ascii()),
+            Some((12..15, '∈'))
+        );
+        assert_eq!(chars.next(), Some((15..16, '4')));
+
+        assert_eq!(chars.find_range(|c| c == 'd'), Some((26..27, 'd')));
+        assert_eq!(chars.as_str(), "");
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.next_back(), Some((26..27, 'd')));
+        assert_eq!(chars.find_range(|c| c == 'd'), None);
+        assert_eq!(chars.as_str(), "");
+        assert_eq!(chars.front_offset(), 26);
+        assert_eq!(chars.next(), None);
+
+        for (r, c) in text.char_ranges() {
+            let mut chars = text.char_ranges();
+            let expected = text.char_ranges().find(|&(_, c2)| c2 == c);
+            assert_eq!(chars.find_range(|c2| c2 == c), expected);
+            assert!(r.start >= expected.unwrap().0.start);
+        }
+    }
+
+    #[test]
+    fn test_find_range_offset() {
+        let text = "Hello 👋 World 🌏";
+
+        let start = 6;
+        let mut chars = text[start..].char_ranges_offset(start);
+        assert_eq!(chars.find_range(char::is_whitespace), Some((10..11, ' ')));
+        assert_eq!(
+            chars.find_range(|c: char| !c.is_ascii()),
+            Some((17..21, '🌏'))
+        );
+        assert_eq!(chars.find_range(|_| true), None);
+    }
+
+    #[test]
+    fn test_find_range_pattern() {
+        let text = "Hello 🗻12∈45🌏 World ¢";
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.find_range("∈4"), Some((12..16, '∈')));
+        assert_eq!(chars.find_range(['🌏', 'W']), Some((17..21, '🌏')));
+        assert_eq!(chars.find_range(&['o', 'l'][..]), Some((23..24, 'o')));
+        assert_eq!(chars.rfind_range("l"), Some((25..26, 'l')));
+        assert_eq!(chars.as_str(), "r");
+
+        // Empty `&str` never matches
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.find_range(""), None);
+        assert_eq!(chars.as_str(), "");
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.rfind_range(""), None);
+        assert_eq!(chars.as_str(), "");
+
+        for (i, _) in text.char_indices() {
+            for needle in [&text[i..], &text[..i], "l", "o W", "🌏"] {
+                let expected = text.find(needle).filter(|_| !needle.is_empty());
+                let actual = text.char_ranges().find_range(needle);
+                assert_eq!(actual.map(|(r, _c)| r.start), expected);
+
+                let expected = text.rfind(needle).filter(|_| !needle.is_empty());
+                let actual = text.char_ranges().rfind_range(needle);
+                assert_eq!(actual.map(|(r, _c)| r.start), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rfind_range() {
+        let text = "Hello 🗻12∈45🌏 World";
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.rfind_range(|c| c == 'd'), Some((26..27, 'd')));
+        assert_eq!(chars.as_str(), "Hello 🗻12∈45🌏 Worl");
+
+        assert_eq!(
+            chars.rfind_range(|c: char| !c.is_ascii()),
+            Some((17..21, '🌏'))
+        );
+        assert_eq!(chars.as_str(), "Hello 🗻12∈45");
+
+        assert_eq!(chars.next(), Some((0..1, 'H')));
+        assert_eq!(chars.rfind_range(|c| c == 'H'), None);
+        assert_eq!(chars.as_str(), "");
+        assert_eq!(chars.front_offset(), 1);
+        assert_eq!(chars.back_offset(), 1);
+        assert_eq!(chars.next_back(), None);
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.next(), Some((0..1, 'H')));
+        assert_eq!(chars.next(), Some((1..2, 'e')));
+        assert_eq!(chars.rfind_range(|c| c == 'l'), Some((25..26, 'l')));
+        assert_eq!(chars.rfind_range(|c| c == 'l'), Some((3..4, 'l')));
+        assert_eq!(chars.rfind_range(|c| c == 'l'), Some((2..3, 'l')));
+        assert_eq!(chars.as_str(), "");
+        assert_eq!(chars.rfind_range(|c| c == 'l'), None);
+    }
+
+    #[test]
+    fn test_rfind_range_offset() {
+        let text = "Hello 👋 World 🌏";
+
+        let start = 6;
+        let mut chars = text[start..].char_ranges_offset(start);
+        assert_eq!(chars.rfind_range(char::is_whitespace), Some((16..17, ' ')));
+        assert_eq!(
+            chars.rfind_range(|c: char| !c.is_ascii()),
+            Some((6..10, '👋'))
+        );
+        assert_eq!(chars.rfind_range(|_| true), None);
+    }
+
+    #[test]
+    fn test_reset() {
+        let text = "Hello 🗻12∈45🌏 World";
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.nth(8), Some((11..12, '2')));
+        assert_eq!(chars.nth_back(6), Some((17..21, '🌏')));
+        assert_eq!(chars.as_str(), "∈45");
+
+        chars.reset();
+        assert_eq!(chars.as_str(), text);
+        assert_eq!(chars.consumed_prefix(), "");
+        assert_eq!(chars.consumed_suffix(), "");
+        assert!(chars.eq(text.char_ranges()));
+        assert!(chars.rev().eq(text.char_ranges().rev()));
+
+        let mut chars = text.char_ranges();
+        chars.by_ref().for_each(drop);
+        chars.reset();
+        assert!(chars.eq(text.char_ranges()));
+    }
+
+    #[test]
+    fn test_reset_front_back() {
+        let text = "🗻∈🌏";
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.next(), Some((0..4, '🗻')));
+        assert_eq!(chars.next_back(), Some((7..11, '🌏')));
+
+        chars.reset_front();
+        assert_eq!(chars.as_str(), "🗻∈");
+        assert_eq!(chars.next_back(), Some((4..7, '∈')));
+
+        chars.reset_back();
+        assert_eq!(chars.as_str(), "🗻∈🌏");
+        assert_eq!(chars.next(), Some((0..4, '🗻')));
+
+        chars.reset_back();
+        assert_eq!(chars.as_str(), "∈🌏");
+    }
+
+    #[test]
+    fn test_reset_offset() {
+        let text = "Hello 👋 World 🌏";
+
+        let start = 11;
+        let mut chars = text[start..].char_ranges_offset(start);
+        assert_eq!(chars.next(), Some((11..12, 'W')));
+        assert_eq!(chars.next_back(), Some((17..21, '🌏')));
+
+        chars.reset();
+        assert_eq!(chars.offset(), start);
+        assert_eq!(chars.as_str(), "World 🌏");
+        assert!(chars.eq(text[start..].char_ranges_offset(start)));
+    }
+
+    #[test]
+    fn test_prev() {
+        let text = "a🗻∈¢🌏";
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.prev(), None);
+
+        let mut passed = iter::from_fn(|| chars.next()).collect::<Vec<_>>();
+        assert_eq!(passed.len(), 5);
+        assert_eq!(chars.as_str(), "");
+
+        while let Some(expected) = passed.pop() {
+            assert_eq!(chars.prev(), Some(expected.clone()));
+            assert_eq!(chars.as_str(), &text[expected.0.start..]);
+
+            // Ping-pong
+            assert_eq!(chars.next(), Some(expected.clone()));
+            assert_eq!(chars.prev(), Some(expected));
+        }
+
+        assert_eq!(chars.prev(), None);
+        assert_eq!(chars.as_str(), text);
+        assert!(chars.eq(text.char_ranges()));
+    }
+
+    #[test]
+    fn test_prev_back() {
+        let text = "a🗻∈¢🌏";
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.prev_back(), None);
+
+        let mut passed = iter::from_fn(|| chars.next_back()).collect::<Vec<_>>();
+        assert_eq!(passed.len(), 5);
+        assert_eq!(chars.as_str(), "");
+
+        while let Some(expected) = passed.pop() {
+            assert_eq!(chars.prev_back(), Some(expected.clone()));
+            assert_eq!(chars.as_str(), &text[..expected.0.end]);
+
+            // Ping-pong
+            assert_eq!(chars.next_back(), Some(expected.clone()));
+            assert_eq!(chars.prev_back(), Some(expected));
+        }
+
+        assert_eq!(chars.prev_back(), None);
+        assert_eq!(chars.as_str(), text);
+    }
+
+    #[test]
+    fn test_prev_offset() {
+        let text = "Hello 👋 World 🌏";
+
+        let start = 6;
+        let mut chars = text[start..].char_ranges_offset(start);
+        assert_eq!(chars.prev(), None);
+        assert_eq!(chars.prev_back(), None);
+
+        assert_eq!(chars.next(), Some((6..10, '👋')));
+        assert_eq!(chars.next_back(), Some((17..21, '🌏')));
+        assert_eq!(chars.prev(), Some((6..10, '👋')));
+        assert_eq!(chars.prev_back(), Some((17..21, '🌏')));
+        assert_eq!(chars.as_str(), "👋 World 🌏");
+    }
+
+    #[test]
+    fn test_seek_to() {
+        let text = "a🗻∈🌏b";
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.seek_to(0), Ok(()));
+        assert_eq!(chars.as_str(), text);
+
+        assert_eq!(chars.seek_to(5), Ok(()));
+        assert_eq!(chars.as_str(), "∈🌏b");
+        assert_eq!(chars.seek_to(5), Ok(()));
+        assert_eq!(chars.as_str(), "∈🌏b");
+
+        assert_eq!(chars.seek_to(8), Ok(()));
+        assert_eq!(chars.next(), Some((8..12, '🌏')));
+
+        for pos in [2, 3, 4, 9, 10, 11] {
+            assert_eq!(chars.seek_to(pos), Err(SeekError::NotCharBoundary));
+            assert_eq!(chars.as_str(), "b");
+        }
+        assert_eq!(chars.seek_to(14), Err(SeekError::OutOfBounds));
+        assert_eq!(chars.seek_to(usize::MAX), Err(SeekError::OutOfBounds));
+
+        assert_eq!(chars.seek_to(1), Ok(()));
+        assert_eq!(chars.next(), Some((1..5, '🗻')));
+
+        assert_eq!(chars.seek_to(13), Ok(()));
+        assert_eq!(chars.next(), None);
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.next_back(), Some((12..13, 'b')));
+        assert_eq!(chars.seek_to(13), Err(SeekError::CrossesCursor));
+        assert_eq!(chars.seek_to(12), Ok(()));
+        assert_eq!(chars.as_str(), "");
+    }
+
+    #[test]
+    fn test_seek_back_to() {
+        let text = "a🗻∈🌏b";
+
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.seek_back_to(13), Ok(()));
+        assert_eq!(chars.as_str(), text);
+
+        assert_eq!(chars.seek_back_to(8), Ok(()));
+        assert_eq!(chars.as_str(), "a🗻∈");
+        assert_eq!(chars.next_back(), Some((5..8, '∈')));
+
+        assert_eq!(chars.seek_back_to(10), Err(SeekError::NotCharBoundary));
+        assert_eq!(chars.seek_back_to(14), Err(SeekError::OutOfBounds));
+        assert_eq!(chars.as_str(), "a🗻");
+
+        assert_eq!(chars.next(), Some((0..1, 'a')));
+        assert_eq!(chars.seek_back_to(0), Err(SeekError::CrossesCursor));
+        assert_eq!(chars.seek_back_to(1), Ok(()));
+        assert_eq!(chars.as_str(), "");
+        assert_eq!(chars.next_back(), None);
+    }
+
+    #[test]
+    fn test_seek_to_offset() {
+        let text = "Hello 👋 World 🌏";
+
+        let start = 6;
+        let mut chars = text[start..].char_ranges_offset(start);
+        assert_eq!(chars.seek_to(5), Err(SeekError::OutOfBounds));
+        assert_eq!(chars.seek_to(7), Err(SeekError::NotCharBoundary));
+        assert_eq!(chars.seek_to(10), Ok(()));
+        assert_eq!(chars.next(), Some((10..11, ' ')));
+
+        assert_eq!(chars.seek_back_to(22), Err(SeekError::OutOfBounds));
+        assert_eq!(chars.seek_back_to(16), Ok(()));
+        assert_eq!(chars.as_str(), "World");
+        assert_eq!(chars.next_back(), Some((15..16, 'd')));
     }
 
-    #[inline]
-    fn last(mut self) -> Option<(Range<usize>, char)> {
-        self.next_back()
+    #[test]
+    fn test_add_offset() {
+        let text = "Hello 🗻12∈45🌏 World";
+
+        let chars = text.char_ranges_offset(3).add_offset(4).add_offset(5);
+        assert_eq!(chars.offset(), 12);
+        assert!(chars.eq(text.char_ranges_offset(12)));
+        assert!(chars.eq(text.char_ranges().offset(12)));
+
+        let mut chars = text.char_ranges_offset(3);
+        assert_eq!(chars.next(), Some((3..4, 'H')));
+        let mut chars = chars.add_offset(10);
+        assert_eq!(chars.offset(), 13);
+        assert_eq!(chars.as_str(), "ello 🗻12∈45🌏 World");
+        assert_eq!(chars.next(), Some((14..15, 'e')));
+        assert_eq!(chars.front_offset(), 15);
+
+        let mut chars = chars.with_offset(0);
+        assert_eq!(chars.offset(), 0);
+        assert_eq!(chars.next(), Some((2..3, 'l')));
     }
 
-    #[inline]
-    fn nth(&mut self, n: usize) -> Option<Self::Item> {
-        let (r, c) = self.iter.nth(n)?;
-        let start = r.start + self.offset;
-        let end = r.end + self.offset;
-        Some((start..end, c))
+    #[test]
+    fn test_try_offset() {
+        let text = "a🗻";
+
+        for offset in [0, 1, usize::MAX / 2, usize::MAX - 6, usize::MAX - 5] {
+            let mut chars = text.char_ranges().try_offset(offset).unwrap();
+            assert_eq!(chars.next(), Some((offset..(offset + 1), 'a')));
+            assert_eq!(chars.next(), Some(((offset + 1)..(offset + 5), '🗻')));
+            assert!(CharRangesOffset::try_from_text(text, offset).is_ok());
+        }
+
+        for offset in [usize::MAX - 4, usize::MAX - 1, usize::MAX] {
+            let err = text.char_ranges().try_offset(offset).unwrap_err();
+            assert_eq!(err, OffsetOverflowError::new(offset, text.len()));
+            assert!(CharRangesOffset::try_from_text(text, offset).is_err());
+        }
+
+        // The original text is validated, not the remaining text
+        let mut chars = text.char_ranges();
+        assert_eq!(chars.next_back(), Some((1..5, '🗻')));
+        assert!(chars.try_offset(usize::MAX - 1).is_err());
+        assert!(chars.try_offset(usize::MAX - 5).is_ok());
+
+        assert!("".char_ranges().try_offset(usize::MAX).is_ok());
     }
-}
 
-impl DoubleEndedIterator for CharRangesOffset<'_> {
-    #[inline]
-    fn next_back(&mut self) -> Option<Self::Item> {
-        let (r, c) = self.iter.next_back()?;
-        let start = r.start + self.offset;
-        let end = r.end + self.offset;
-        Some((start..end, c))
+    #[test]
+    fn test_char_ranges_offset_constructors() {
+        let text = "Hello 🗻12∈45🌏 World ¢";
+        let expected = text.char_ranges_offset(7).collect::<Vec<_>>();
+
+        #[allow(deprecated)]
+        let deprecated = CharRangesOffset::new(7, text);
+        let iters = [
+            deprecated,
+            CharRangesOffset::from_text(text, 7),
+            CharRangesOffset::try_from_text(text, 7).unwrap(),
+            text.char_ranges().offset(7),
+            CharRangesOffset::from((7, text.char_ranges())),
+            CharRangesOffset::from((text.char_ranges(), 7)),
+            (text.char_ranges(), 7).into(),
+            text.char_ranges_offset(0).map_offset(|offset| offset + 7),
+            text.char_ranges_offset(3).with_offset(7),
+        ];
+        for (i, chars) in iters.into_iter().enumerate() {
+            assert_eq!(chars.offset(), 7, "{i}");
+            assert!(chars.eq(expected.iter().cloned()), "{i}");
+            assert!(chars.rev().eq(expected.iter().rev().cloned()), "{i}");
+        }
     }
 
-    #[inline]
-    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
-        let (r, c) = self.iter.nth_back(n)?;
-        let start = r.start + self.offset;
-        let end = r.end + self.offset;
-        Some((start..end, c))
+    #[test]
+    fn test_char_ranges_offset_into_unoffset() {
+        let text = "Hello 🗻12∈45🌏 World ¢";
+
+        let mut chars = text.char_ranges_offset(7);
+        chars.next();
+        chars.next_back();
+
+        let (iter, offset) = chars.into_unoffset();
+        assert_eq!(offset, 7);
+        assert_eq!(iter.as_str(), chars.as_str());
+        assert!(iter
+            .map(|(r, c)| ((r.start + offset)..(r.end + offset), c))
+            .eq(chars));
+
+        // Round trips
+        assert!(CharRangesOffset::from((iter, offset)).eq(chars));
     }
-}
 
-impl FusedIterator for CharRangesOffset<'_> {}
+    #[test]
+    fn test_char_ranges_copy() {
+        fn assert_copy<T: Copy>(_: T) {}
 
-impl fmt::Debug for CharRangesOffset<'_> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "CharRangesOffset(")?;
-        f.debug_list().entries(self.clone()).finish()?;
-        write!(f, ")")?;
-        Ok(())
+        let text = "a🗻b";
+        assert_copy(text.char_ranges());
+        assert_copy(text.char_ranges_offset(3));
+
+        // Backtracking by storing and restoring the state
+        let mut chars = text.char_ranges_offset(3);
+        let saved = chars;
+        assert_eq!(chars.next(), Some((3..4, 'a')));
+        assert_eq!(chars.next(), Some((4..8, '🗻')));
+        chars = saved;
+        assert_eq!(chars.next(), Some((3..4, 'a')));
+        assert_eq!(saved.as_str(), text);
+
+        // `Debug` output is unchanged
+        assert_eq!(
+            format!("{:?}", saved.into_unoffset().0),
+            r#"CharRanges { range: 0..6, len: 6, text: "a🗻b" }"#
+        );
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use core::iter;
+    #[test]
+    fn test_char_ranges_eq_hash() {
+        use core::hash::{Hash, Hasher};
+        use std::collections::hash_map::DefaultHasher;
+        use std::string::ToString;
+
+        fn hash<T: Hash>(value: &T) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        assert_eq!(CharRanges::default(), "".char_ranges());
+        assert_eq!(CharRanges::default().next(), None);
+        assert_eq!(CharRangesOffset::default(), "".char_ranges_offset(0));
+        assert_eq!(CharRangesOffset::default().next(), None);
 
-    use super::CharRangesExt;
+        // Different allocations with identical text
+        let a = "foo 🗻 bar".to_string();
+        let b = "foo 🗻 bar".to_string();
+        assert_ne!(a.as_ptr(), b.as_ptr());
+        assert_eq!(a.char_ranges(), b.char_ranges());
+        assert_eq!(hash(&a.char_ranges()), hash(&b.char_ranges()));
+
+        // Partially consumed, with different consumed texts
+        let c = "xyz 🗻 bar".to_string();
+        let (mut a, mut c) = (a.char_ranges(), c.char_ranges());
+        assert_ne!(a, c);
+        a.nth(2);
+        c.nth(2);
+        assert_eq!(a, c);
+        assert_eq!(hash(&a), hash(&c));
+        a.next_back();
+        assert_ne!(a, c);
+        c.next_back();
+        assert_eq!(a, c);
+
+        // Same remaining text at different positions
+        let d = " 🗻 ba".char_ranges();
+        assert_eq!(a.as_str(), d.as_str());
+        assert_ne!(a, d);
+
+        // Offsets
+        let (a, c) = (a.offset(3), c.offset(3));
+        assert_eq!(a, c);
+        assert_eq!(hash(&a), hash(&c));
+        assert_ne!(a, c.into_unoffset().0.offset(4));
+
+        // Equal iterators have equal hashes
+        let texts = ["", "a", "ab", "b", "ba", "🗻"];
+        let mut iters = Vec::new();
+        for text in texts {
+            for n in 0..=2 {
+                for offset in 0..=1 {
+                    iters.push(text.char_ranges().skip_chars(n).offset(offset));
+                }
+            }
+        }
+        for a in &iters {
+            for b in &iters {
+                if a == b {
+                    assert_eq!(hash(a), hash(b));
+                    assert!(Iterator::eq(*a, *b));
+                }
+                assert_eq!(a == b, a.into_unoffset() == b.into_unoffset());
+            }
+        }
+    }
 
     #[test]
-    fn test_empty() {
-        let text = "";
+    fn test_char_ranges_char_indices() {
+        let texts = ["", "a", "Hello 🗻12∈45🌏 World ¢", "\u{80}é∈🌏\r\n"];
 
-        let mut chars = text.char_ranges();
-        assert_eq!(chars.next(), None);
-        assert_eq!(chars.next_back(), None);
-        assert_eq!(chars.as_str(), "");
+        for text in texts {
+            let expected = text
+                .char_indices()
+                .map(|(i, c)| (i..(i + c.len_utf8()), c))
+                .collect::<Vec<_>>();
+            let len = expected.len();
+
+            assert_eq!(text.char_ranges().count(), text.char_indices().count());
+            assert_eq!(text.char_ranges().last(), expected.last().cloned());
+
+            for n in 0..=(len + 1) {
+                assert_eq!(text.char_ranges().nth(n), expected.get(n).cloned());
+                assert_eq!(
+                    text.char_ranges().nth_back(n),
+                    len.checked_sub(n + 1).map(|i| expected[i].clone()),
+                );
+
+                // After skipping from both ends
+                let mut chars = text.char_ranges();
+                let mut indices = text.char_indices();
+                for _ in 0..n.min(len / 2) {
+                    chars.next();
+                    chars.next_back();
+                    indices.next();
+                    indices.next_back();
+                }
+                let to_range = |(i, c): (usize, char)| (i..(i + c.len_utf8()), c);
+                assert_eq!(chars.count(), indices.clone().count());
+                assert_eq!(chars.last(), indices.clone().last().map(to_range));
+                assert_eq!(chars.nth(1), indices.nth(1).map(to_range));
+                assert_eq!(chars.nth_back(1), indices.nth_back(1).map(to_range));
+            }
+        }
+    }
+
+    // "ab" with this offset, only overflows for the end of 'b'
+    const OVERFLOW_OFFSET: usize = usize::MAX - 1;
+
+    #[test]
+    #[allow(clippy::iter_nth_zero)]
+    fn test_offset_overflow_partial() {
+        let mut chars = "ab".char_ranges().offset(OVERFLOW_OFFSET);
+        assert_eq!(chars.next(), Some(((usize::MAX - 1)..usize::MAX, 'a')));
+
+        let mut chars = "ab".char_ranges().offset(OVERFLOW_OFFSET);
+        assert_eq!(chars.nth(0), Some(((usize::MAX - 1)..usize::MAX, 'a')));
+
+        let mut chars = "ab".char_ranges().offset(OVERFLOW_OFFSET);
+        assert_eq!(chars.nth_back(1), Some(((usize::MAX - 1)..usize::MAX, 'a')));
+
+        let mut chars = "ab".char_ranges().offset(OVERFLOW_OFFSET);
+        assert_eq!(chars.advance_back_by(1), Ok(()));
+        assert_eq!(chars.next_back(), Some(((usize::MAX - 1)..usize::MAX, 'a')));
+    }
+
+    #[test]
+    #[should_panic(expected = "position 2 with offset 18446744073709551614 overflows usize")]
+    #[cfg(target_pointer_width = "64")]
+    fn test_offset_overflow_next() {
+        let mut chars = "ab".char_ranges().offset(OVERFLOW_OFFSET);
+        _ = chars.next();
+        _ = chars.next();
+    }
+
+    #[test]
+    #[should_panic(expected = "position 2 with offset 18446744073709551614 overflows usize")]
+    #[cfg(target_pointer_width = "64")]
+    fn test_offset_overflow_nth() {
+        let mut chars = "ab".char_ranges().offset(OVERFLOW_OFFSET);
+        _ = chars.nth(1);
+    }
+
+    #[test]
+    #[should_panic(expected = "position 2 with offset 18446744073709551614 overflows usize")]
+    #[cfg(target_pointer_width = "64")]
+    fn test_offset_overflow_next_back() {
+        let mut chars = "ab".char_ranges().offset(OVERFLOW_OFFSET);
+        _ = chars.next_back();
+    }
+
+    #[test]
+    #[should_panic(expected = "position 2 with offset 18446744073709551614 overflows usize")]
+    #[cfg(target_pointer_width = "64")]
+    fn test_offset_overflow_nth_back() {
+        let mut chars = "ab".char_ranges().offset(OVERFLOW_OFFSET);
+        _ = chars.nth_back(0);
+    }
+
+    #[test]
+    fn test_get() {
+        let text = "Hello 🗻12∈45🌏 World";
+
+        for start in [0, 6, 10, 12, 17] {
+            let chars = text[start..].char_ranges_offset(start);
+            for (r, c) in chars {
+                let mut expected = [0; 4];
+                assert_eq!(chars.get(r.clone()), Some(&*c.encode_utf8(&mut expected)));
+                assert_eq!(chars.get(r.clone()), Some(&text[r.clone()]));
+                assert!(chars.contains(r.start));
+                assert!(chars.contains(r.end - 1));
+            }
+
+            assert_eq!(chars.get(start..text.len()), Some(&text[start..]));
+            assert_eq!(chars.get(start..start), Some(""));
+            assert!(!chars.contains(text.len()));
+        }
+
+        let chars = text[6..].char_ranges_offset(6);
+        // Precedes the offset
+        assert_eq!(chars.get(0..1), None);
+        assert_eq!(chars.get(5..6), None);
+        assert!(!chars.contains(5));
+        // Out of bounds
+        assert_eq!(chars.get(27..28), None);
+        assert_eq!(chars.get(6..100), None);
+        assert_eq!(chars.get(usize::MAX..usize::MAX), None);
+        // Not on char boundaries
+        assert_eq!(chars.get(7..10), None);
+        assert_eq!(chars.get(6..9), None);
+        // Reversed
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = 10..6;
+        assert_eq!(chars.get(reversed), None);
+
+        // Stale ranges from another string
+        let other = "🌏🌏";
+        for (r, _c) in other.char_ranges_offset(6) {
+            assert_eq!(chars.get(r.clone()), text.get(r));
+        }
+        for (r, _c) in other.char_ranges() {
+            assert_eq!(chars.get(r), None);
+        }
+    }
+
+    #[test]
+    fn test_remaining_char_count() {
+        let texts = [
+            "",
+            "a",
+            "Hello World",
+            "¢",
+            "∈∈∈∈∈∈∈∈∈∈∈",
+            "🌏🌏🌏🌏🌏🌏🌏🌏🌏🌏🌏🌏🌏🌏🌏🌏🌏",
+            "Hello 🗻12∈45🌏 World, ¢ naïve café 🗻12∈45🌏",
+        ];
+        for text in texts {
+            let mut chars = text.char_ranges();
+            loop {
+                assert_eq!(chars.remaining_char_count(), chars.count());
+                assert_eq!(chars.remaining_char_count(), chars.as_str().chars().count());
+                if chars.next().is_none() {
+                    break;
+                }
+                if chars.next_back().is_none() {
+                    break;
+                }
+            }
+        }
+
+        let text = "Hello 👋 World 🌏";
+        let mut chars = text[6..].char_ranges_offset(6);
+        assert_eq!(chars.remaining_char_count(), 9);
+        assert_eq!(chars.nth(2), Some((11..12, 'W')));
+        assert_eq!(chars.remaining_char_count(), 6);
+    }
+
+    #[test]
+    fn test_consume_char() {
+        let text = "a🗻b";
 
         let mut chars = text.char_ranges();
-        assert_eq!(chars.next_back(), None);
-        assert_eq!(chars.next(), None);
-        assert_eq!(chars.as_str(), "");
+        assert_eq!(chars.consume_char('b'), None);
+        assert_eq!(chars.consume_char('a'), Some(0..1));
+        assert_eq!(chars.consume_char('🌏'), None);
+        assert_eq!(chars.consume_char('🗻'), Some(1..5));
+        assert_eq!(chars.as_str(), "b");
+        assert_eq!(chars.consume_char('b'), Some(5..6));
+        assert_eq!(chars.consume_char('b'), None);
+
+        let mut chars = text[1..].char_ranges_offset(1);
+        assert_eq!(chars.consume_char('🗻'), Some(1..5));
     }
 
     #[test]
-    fn test_empty_single_char() {
-        let text = "a";
+    fn test_consume_str() {
+        let text = "foo🗻∈🌏bar";
 
         let mut chars = text.char_ranges();
-        assert_eq!(chars.as_str(), "a");
-        assert_eq!(chars.next(), Some((0..1, 'a')));
-        assert_eq!(chars.next_back(), None);
+        assert_eq!(chars.consume_str(""), Some(0..0));
+        assert_eq!(chars.consume_str("foo"), Some(0..3));
+
+        // Prefixes which match partially, then diverge on a multi-byte char
+        for prefix in ["🗻∈🌎", "🗻∋", "🗻🌏", "🗻∈🌏barr", "∈"] {
+            assert_eq!(chars.consume_str(prefix), None, "{prefix:?}");
+            assert_eq!(chars.as_str(), "🗻∈🌏bar");
+            assert_eq!(chars.front_offset(), 3);
+            assert_eq!(chars.next(), Some((3..7, '🗻')));
+            chars.seek_to(3).unwrap();
+        }
+
+        // Diverging on the last byte of a multi-byte char, i.e. 🌏 is
+        // F0 9F 8C 8F and 🌎 is F0 9F 8C 8E
+        assert_eq!(chars.consume_str("🗻∈🌎"), None);
+        assert_eq!(chars.as_str(), "🗻∈🌏bar");
+
+        assert_eq!(chars.consume_str("🗻∈"), Some(3..10));
+        assert_eq!(chars.consume_str("🌏bar"), Some(10..17));
         assert_eq!(chars.as_str(), "");
+        assert_eq!(chars.consume_str("a"), None);
+        assert_eq!(chars.consume_str(""), Some(17..17));
 
         let mut chars = text.char_ranges();
-        assert_eq!(chars.as_str(), "a");
-        assert_eq!(chars.next_back(), Some((0..1, 'a')));
-        assert_eq!(chars.next(), None);
-        assert_eq!(chars.as_str(), "");
+        chars.next_back();
+        assert_eq!(chars.consume_str("foo🗻∈🌏bar"), None);
+        assert_eq!(chars.as_str(), "foo🗻∈🌏ba");
+
+        let mut chars = text[3..].char_ranges_offset(3);
+        assert_eq!(chars.consume_str("🗻∈🌎"), None);
+        assert_eq!(chars.front_offset(), 3);
+        assert_eq!(chars.consume_str("🗻∈"), Some(3..10));
     }
 
     #[test]
-    fn test_empty_single_char_multi_byte() {
-        let text = "🌏";
+    fn test_consume_while() {
+        let text = "12∈45🌏";
 
         let mut chars = text.char_ranges();
-        assert_eq!(chars.as_str(), "🌏");
-        assert_eq!(chars.next(), Some((0..4, '🌏')));
-        assert_eq!(chars.next_back(), None);
-        assert_eq!(chars.as_str(), "");
+        assert_eq!(chars.consume_while(|c| c.is_ascii_digit()), 0..2);
+        assert_eq!(chars.consume_while(|c| c.is_ascii_digit()), 2..2);
+        assert_eq!(chars.consume_while(|c| c != '🌏'), 2..7);
+        assert_eq!(chars.consume_while(|_| true), 7..11);
+        assert_eq!(chars.consume_while(|_| true), 11..11);
+
+        let mut chars = text[2..].char_ranges_offset(2);
+        assert_eq!(chars.consume_while(|c| !c.is_ascii()), 2..5);
+    }
+
+    #[test]
+    fn test_char_ranges_ext_owned() {
+        use core::ops::Range;
+        use std::borrow::Cow;
+        use std::boxed::Box;
+        use std::rc::Rc;
+        use std::sync::Arc;
+
+        fn collect<T>(text: &T) -> Vec<(Range<usize>, char)>
+        where
+            T: CharRangesExt + ?Sized,
+        {
+            text.char_ranges().collect()
+        }
+
+        let text = "Hello 🗻12∈45🌏 World ¢";
+        let expected = collect(text);
+        assert_eq!(expected.len(), 21);
+
+        assert_eq!(collect(&String::from(text)), expected);
+        assert_eq!(collect(&Box::<str>::from(text)), expected);
+        assert_eq!(collect(&Rc::<str>::from(text)), expected);
+        assert_eq!(collect(&Arc::<str>::from(text)), expected);
+        assert_eq!(collect(&Cow::Borrowed(text)), expected);
+        assert_eq!(collect(&Cow::<str>::Owned(String::from(text))), expected);
+        assert_eq!(collect(&text), expected);
+
+        // Trait objects
+        let owned = String::from(text);
+        let objects: [&dyn AsRef<str>; 3] = [&text, &owned, &Box::<str>::from(text)];
+        for object in objects {
+            assert_eq!(collect(object), expected);
+        }
+
+        // The iterators borrow from the owned value
+        let owned = String::from(text);
+        let mut chars = owned.char_ranges_offset(3);
+        assert_eq!(chars.next(), Some((3..4, 'H')));
+        assert_eq!(chars.next_back(), Some((31..33, '¢')));
+        assert!(core::ptr::eq(chars.as_str(), &owned[1..28]));
+        assert_eq!(owned.trim_range(), (0..30, text));
+    }
+
+    #[test]
+    fn test_fold() {
+        let text = "Hello 🗻12∈45🌏 World ¢";
+
+        fn collect_next<I: Iterator>(iter: I) -> Vec<I::Item> {
+            let mut items = Vec::new();
+            for item in iter {
+                items.push(item);
+            }
+            items
+        }
+
+        fn collect_next_back<I: DoubleEndedIterator>(mut iter: I) -> Vec<I::Item> {
+            let mut items = Vec::new();
+            while let Some(item) = iter.next_back() {
+                items.push(item);
+            }
+            items
+        }
+
+        fn push<T>(mut items: Vec<T>, item: T) -> Vec<T> {
+            items.push(item);
+            items
+        }
+
+        for (i, _) in text.char_indices() {
+            for j in (i..=text.len()).filter(|&j| text.is_char_boundary(j)) {
+                let mut chars = text.char_ranges();
+                text[..i].chars().for_each(|_| _ = chars.next());
+                text[j..].chars().for_each(|_| _ = chars.next_back());
+
+                let expected = collect_next(chars);
+                assert_eq!(chars.fold(Vec::new(), push), expected);
+                let mut actual = Vec::new();
+                chars.for_each(|item| actual.push(item));
+                assert_eq!(actual, expected);
+
+                let expected = collect_next_back(chars);
+                assert_eq!(chars.rfold(Vec::new(), push), expected);
+
+                let chars = chars.offset(100);
+                let expected = collect_next(chars);
+                assert_eq!(chars.fold(Vec::new(), push), expected);
+                let mut actual = Vec::new();
+                chars.for_each(|item| actual.push(item));
+                assert_eq!(actual, expected);
+
+                let expected = collect_next_back(chars);
+                assert_eq!(chars.rfold(Vec::new(), push), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_try_fold() {
+        let text = "Hello 🗻12∈45🌏 World ¢";
+
+        let mut chars = text.char_ranges_offset(10);
+        let sum = chars.try_fold(0, |sum, (r, c)| {
+            if c == '∈' {
+                Err(r)
+            } else {
+                Ok(sum + r.len())
+            }
+        });
+        assert_eq!(sum, Err(22..25));
+        assert_eq!(chars.as_str(), "45🌏 World ¢");
 
         let mut chars = text.char_ranges();
-        assert_eq!(chars.as_str(), "🌏");
-        assert_eq!(chars.next_back(), Some((0..4, '🌏')));
-        assert_eq!(chars.next(), None);
-        assert_eq!(chars.as_str(), "");
+        let sum = chars.try_rfold(0, |sum, (r, c)| {
+            if c == '∈' {
+                Err(r)
+            } else {
+                Ok(sum + r.len())
+            }
+        });
+        assert_eq!(sum, Err(12..15));
+        assert_eq!(chars.as_str(), "Hello 🗻12");
+    }
+
+    #[test]
+    fn test_debug() {
+        let text = "Hello 🗻12∈45🌏 World ¢";
+
+        let mut chars = text.char_ranges();
+        chars.next();
+        chars.next_back();
+        assert_eq!(
+            format!("{chars:?}"),
+            r#"CharRanges { range: 1..28, len: 27, text: "ello 🗻12∈45🌏 World " }"#,
+        );
+        assert_eq!(
+            format!("{:?}", chars.offset(3)),
+            r#"CharRangesOffset { offset: 3, range: 4..31, len: 27, text: "ello 🗻12∈45🌏 World " }"#,
+        );
+        assert_eq!(
+            format!("{:?}", "\"\n".char_ranges()),
+            r#"CharRanges { range: 0..2, len: 2, text: "\"\n" }"#,
+        );
+        assert_eq!(
+            format!("{:?}", "🌏".char_ranges_offset(usize::MAX)),
+            format!(
+                r#"CharRangesOffset {{ offset: {max}, range: {max}..{max}, len: 4, text: "🌏" }}"#,
+                max = usize::MAX,
+            ),
+        );
+
+        // Truncated on char boundaries
+        let text = "🗻".repeat(20) + "∈" + &"🌏".repeat(20);
+        assert_eq!(
+            format!("{:?}", text.char_ranges()),
+            format!(
+                "CharRanges {{ range: 0..163, len: 163, text: {:?}…{:?} }}",
+                "🗻".repeat(16),
+                "🌏".repeat(16),
+            ),
+        );
+
+        let mut chars = "Hi🌏".char_ranges_offset(10);
+        chars.next();
+        assert_eq!(
+            format!("{chars:#?}"),
+            "CharRangesOffset([\n    (\n        11..12,\n        'i',\n    ),\n    (\n        12..16,\n        '🌏',\n    ),\n])",
+        );
+        assert_eq!(format!("{:#?}", "".char_ranges()), "CharRanges([])");
+    }
+
+    #[test]
+    fn test_debug_bounded() {
+        let text = "Hello 🗻12∈45🌏 World ¢".repeat(100_000);
+
+        let debug = format!("{:?}", text.char_ranges());
+        assert!(debug.len() < 256, "{debug}");
+        let debug = format!("{:?}", text.char_ranges_offset(1));
+        assert!(debug.len() < 256, "{debug}");
+
+        let debug = format!("{:#?}", text.char_ranges());
+        assert!(debug.len() < 64 * 64, "{debug}");
+        assert!(debug.ends_with("    ..\n])"), "{debug}");
+        let debug = format!("{:#?}", text[..64].char_ranges());
+        assert!(debug.ends_with("    ),\n])"), "{debug}");
+    }
+
+    #[test]
+    fn test_char_range_at() {
+        let text = "Hello 🗻12∈45🌏 World ¢";
+
+        for idx in 0..text.len() {
+            let (r, c) = text.char_range_at(idx).unwrap();
+            assert!(r.contains(&idx), "{idx}");
+            assert_eq!(r.len(), c.len_utf8(), "{idx}");
+            assert_eq!(text[r.clone()].chars().next(), Some(c), "{idx}");
+            assert_eq!(
+                text.char_ranges().find(|(r, _c)| r.contains(&idx)),
+                Some((r, c))
+            );
+        }
+
+        assert_eq!(text.char_range_at(text.len()), None);
+        assert_eq!(text.char_range_at(usize::MAX), None);
+        assert_eq!("".char_range_at(0), None);
+    }
+
+    #[test]
+    fn test_char_before_after() {
+        let text = "a🌏∈¢b";
+
+        #[rustfmt::skip]
+        let expected = [
+            //  idx  before               after
+            (0,  None,               Some((0..1, 'a'))),
+            (1,  Some((0..1, 'a')),  Some((1..5, '🌏'))),
+            (2,  Some((1..5, '🌏')), Some((5..8, '∈'))),
+            (3,  Some((1..5, '🌏')), Some((5..8, '∈'))),
+            (4,  Some((1..5, '🌏')), Some((5..8, '∈'))),
+            (5,  Some((1..5, '🌏')), Some((5..8, '∈'))),
+            (6,  Some((5..8, '∈')),  Some((8..10, '¢'))),
+            (7,  Some((5..8, '∈')),  Some((8..10, '¢'))),
+            (8,  Some((5..8, '∈')),  Some((8..10, '¢'))),
+            (9,  Some((8..10, '¢')), Some((10..11, 'b'))),
+            (10, Some((8..10, '¢')), Some((10..11, 'b'))),
+            (11, Some((10..11, 'b')), None),
+            (12, None,               None),
+        ];
+        for (idx, before, after) in expected {
+            assert_eq!(text.char_before(idx), before, "{idx}");
+            assert_eq!(text.char_after(idx), after, "{idx}");
+        }
+
+        assert_eq!(text.char_before(usize::MAX), None);
+        assert_eq!(text.char_after(usize::MAX), None);
+        assert_eq!("".char_before(0), None);
+        assert_eq!("".char_after(0), None);
+
+        // Within the last char
+        assert_eq!("a🌏".char_after(2), None);
+    }
+
+    #[test]
+    fn test_floor_ceil_char_boundary() {
+        let text = "a¢∈🌏b";
+
+        // Captured from `str::floor_char_boundary()`
+        // and `str::ceil_char_boundary()`
+        #[rustfmt::skip]
+        let expected = [
+            // idx, floor, ceil
+            (0, 0, 0),
+            (1, 1, 1),
+            (2, 1, 3),
+            (3, 3, 3),
+            (4, 3, 6),
+            (5, 3, 6),
+            (6, 6, 6),
+            (7, 6, 10),
+            (8, 6, 10),
+            (9, 6, 10),
+            (10, 10, 10),
+            (11, 11, 11),
+            (12, 11, 11),
+            (100, 11, 11),
+            (usize::MAX, 11, 11),
+        ];
+        for (idx, floor, ceil) in expected {
+            assert_eq!(
+                CharRangesExt::floor_char_boundary(text, idx),
+                floor,
+                "{idx}"
+            );
+            assert_eq!(CharRangesExt::ceil_char_boundary(text, idx), ceil, "{idx}");
+            assert_eq!(str::floor_char_boundary(text, idx), floor, "{idx}");
+            assert_eq!(str::ceil_char_boundary(text, idx), ceil, "{idx}");
+
+            let straddling = text
+                .char_ranges()
+                .find(|(r, _c)| (r.start < idx) && (idx < r.end));
+            assert_eq!(
+                text.floor_char_boundary_range(idx),
+                (floor, straddling.clone())
+            );
+            assert_eq!(text.ceil_char_boundary_range(idx), (ceil, straddling));
+        }
+
+        assert_eq!(CharRangesExt::floor_char_boundary("", 0), 0);
+        assert_eq!(CharRangesExt::ceil_char_boundary("", 1), 0);
+    }
+
+    #[test]
+    fn test_snap_range() {
+        let text = "🗻a∈🌏";
+
+        let boundaries = (0..=text.len())
+            .filter(|&i| text.is_char_boundary(i))
+            .collect::<Vec<_>>();
+
+        for start in 0..(text.len() + 3) {
+            for end in 0..(text.len() + 3) {
+                let range = start..end;
+                assert_eq!(
+                    text.is_boundary_range(&range),
+                    text.get(range.clone()).is_some(),
+                    "{range:?}"
+                );
+
+                if (start > end) || (start > text.len()) {
+                    assert_eq!(text.snap_range(range.clone(), Snap::Inner), None);
+                    assert_eq!(text.snap_range(range, Snap::Outer), None);
+                    continue;
+                }
+                let end = end.min(text.len());
+
+                // Largest subrange on boundaries
+                let inner = boundaries
+                    .iter()
+                    .flat_map(|&a| boundaries.iter().map(move |&b| a..b))
+                    .filter(|r| (start <= r.start) && (r.start <= r.end) && (r.end <= end))
+                    .max_by_key(|r| r.len())
+                    .unwrap_or_else(|| {
+                        let floor = CharRangesExt::floor_char_boundary(text, start);
+                        floor..floor
+                    });
+                let snapped = text.snap_range(range.clone(), Snap::Inner).unwrap();
+                assert_eq!(snapped, inner, "{range:?}");
+                assert!(text.is_boundary_range(&snapped));
+
+                // Smallest superrange on boundaries
+                let outer = boundaries
+                    .iter()
+                    .flat_map(|&a| boundaries.iter().map(move |&b| a..b))
+                    .filter(|r| (r.start <= start) && (end <= r.end))
+                    .min_by_key(|r| r.len())
+                    .unwrap();
+                let snapped = text.snap_range(range.clone(), Snap::Outer).unwrap();
+                assert_eq!(snapped, outer, "{range:?}");
+                assert!(text.is_boundary_range(&snapped));
+            }
+        }
+
+        // Already on boundaries
+        assert_eq!(text.snap_range(4..5, Snap::Inner), Some(4..5));
+        assert_eq!(text.snap_range(4..5, Snap::Outer), Some(4..5));
+        // Touching `len()`
+        assert_eq!(text.snap_range(12..12, Snap::Inner), Some(12..12));
+        assert_eq!(text.snap_range(12..20, Snap::Outer), Some(12..12));
+        assert_eq!(text.snap_range(10..12, Snap::Inner), Some(12..12));
+        // Collapsed within a single char
+        assert_eq!(text.snap_range(1..3, Snap::Inner), Some(0..0));
+        assert_eq!(text.snap_range(9..9, Snap::Inner), Some(8..8));
+
+        assert_eq!("".snap_range(0..1, Snap::Outer), Some(0..0));
+        assert_eq!("".snap_range(1..1, Snap::Outer), None);
+    }
+
+    #[test]
+    fn test_byte_to_char_index() {
+        let text = "Hello 🗻12∈45🌏 World ¢";
+        let count = text.chars().count();
+
+        assert_eq!(text.byte_to_char_index(0), Some(0));
+        assert_eq!(text.byte_to_char_index(28), Some(count - 1));
+        assert_eq!(text.byte_to_char_index(text.len()), Some(count));
+        assert_eq!(text.byte_to_char_index(text.len() + 1), None);
+        assert_eq!(text.byte_to_char_index(7), None);
+
+        assert_eq!(text.char_index_to_byte_range(0), Some((0..1, 'H')));
+        assert_eq!(
+            text.char_index_to_byte_range(count - 1),
+            Some((28..30, '¢'))
+        );
+        assert_eq!(text.char_index_to_byte_range(count), None);
+        assert_eq!(text.char_index_to_byte_range(usize::MAX), None);
+
+        assert_eq!("".byte_to_char_index(0), Some(0));
+        assert_eq!("".char_index_to_byte_range(0), None);
+    }
+
+    #[test]
+    fn test_byte_to_char_index_random() {
+        const CHARS: [char; 6] = ['a', 'é', '∈', '🌏', ' ', '¢'];
+
+        let mut next = crate::test_util::random();
+
+        for _ in 0..200 {
+            let len = next() % 48;
+            let text = (0..len)
+                .map(|_| CHARS[next() % CHARS.len()])
+                .collect::<String>();
+
+            for (i, (r, c)) in text.char_ranges().enumerate() {
+                assert_eq!(text.byte_to_char_index(r.start), Some(i));
+                assert_eq!(text.char_index_to_byte_range(i), Some((r.clone(), c)));
+                for byte in (r.start + 1)..r.end {
+                    assert_eq!(text.byte_to_char_index(byte), None);
+                }
+            }
+
+            assert_eq!(text.byte_to_char_index(text.len()), Some(len));
+            assert_eq!(text.char_index_to_byte_range(len), None);
+        }
+    }
+
+    #[test]
+    fn test_count_chars_in_range() {
+        const CHARS: [char; 6] = ['a', 'é', '∈', '🌏', ' ', '¢'];
+
+        let mut next = crate::test_util::random();
+
+        for _ in 0..200 {
+            let len = next() % 64;
+            let text = (0..len)
+                .map(|_| CHARS[next() % CHARS.len()])
+                .collect::<String>();
+
+            for _ in 0..20 {
+                let start = next() % (text.len() + 2);
+                let end = next() % (text.len() + 2);
+
+                let expected = text.get(start..end).map(|s| s.chars().count());
+                assert_eq!(text.count_chars_in_range(start..end), expected);
+
+                let expected = text.get(..end).map(|s| s.chars().count());
+                assert_eq!(text.count_chars_in_range(..end), expected);
+
+                let expected = text.get(start..).map(|s| s.chars().count());
+                assert_eq!(text.count_chars_in_range(start..), expected);
+            }
+
+            assert_eq!(text.count_chars_in_range(..), Some(len));
+        }
+
+        let text = "a🌏b";
+        assert_eq!(text.count_chars_in_range(1..=3), None);
+        assert_eq!(text.count_chars_in_range(1..=5), Some(2));
+        assert_eq!(text.count_chars_in_range(2..), None);
+        assert_eq!(text.count_chars_in_range(..3), None);
+        assert_eq!(text.count_chars_in_range(6..6), Some(0));
+        assert_eq!(text.count_chars_in_range(7..7), None);
+        assert_eq!(text.count_chars_in_range(..=usize::MAX), None);
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = 5..1;
+        assert_eq!(text.count_chars_in_range(reversed), None);
+    }
+
+    #[test]
+    fn test_truncate_to_boundary() {
+        let text = "Hello 🗻12∈45🌏 World ¢";
+
+        for max_bytes in 0..=(text.len() + 1) {
+            let (r, prefix) = text.truncate_to_boundary(max_bytes);
+            assert_eq!(r.start, 0);
+            assert_eq!(&text[r.clone()], prefix);
+            assert!(prefix.len() <= max_bytes);
+            // Maximal, i.e. the next char doesn't fit
+            if let Some(c) = text[r.end..].chars().next() {
+                assert!(r.end + c.len_utf8() > max_bytes, "{max_bytes}");
+            }
+
+            let (r, suffix) = text.truncate_back_to_boundary(max_bytes);
+            assert_eq!(r.end, text.len());
+            assert_eq!(&text[r.clone()], suffix);
+            assert!(suffix.len() <= max_bytes);
+            if let Some(c) = text[..r.start].chars().next_back() {
+                assert!(suffix.len() + c.len_utf8() > max_bytes, "{max_bytes}");
+            }
+        }
+
+        assert_eq!("🌏".truncate_to_boundary(3), (0..0, ""));
+        assert_eq!("🌏".truncate_back_to_boundary(3), (4..4, ""));
+        assert_eq!("".truncate_to_boundary(0), (0..0, ""));
+        assert_eq!("".truncate_back_to_boundary(usize::MAX), (0..0, ""));
     }
 
     #[test]
-    fn test_simple() {
-        let text = "Foo";
-        let mut chars = text.char_ranges();
-        assert_eq!(chars.as_str(), "Foo");
+    fn test_truncate_with_ellipsis() {
+        let text = "Hello 🗻12∈45🌏 World ¢";
+        let count = text.chars().count();
 
-        assert_eq!(chars.next(), Some((0..1, 'F')));
-        assert_eq!(chars.as_str(), "oo");
+        // Shorter than and exactly at the limit
+        assert_eq!(
+            text.truncate_with_ellipsis(count + 1),
+            (0..text.len(), false)
+        );
+        assert_eq!(text.truncate_with_ellipsis(count), (0..text.len(), false));
+        assert_eq!(
+            text.truncate_with_ellipsis(usize::MAX),
+            (0..text.len(), false)
+        );
 
-        assert_eq!(chars.next(), Some((1..2, 'o')));
-        assert_eq!(chars.as_str(), "o");
+        for max_chars in 0..count {
+            let (r, truncated) = text.truncate_with_ellipsis(max_chars);
+            assert!(truncated);
+            assert_eq!(r.start, 0);
+            assert!(text.is_char_boundary(r.end));
+            assert_eq!(text[r.clone()].chars().count(), max_chars.saturating_sub(1));
+            // At least one char is dropped, besides the ellipsis
+            assert!(text[r.end..].chars().count() >= 2);
+        }
 
-        assert_eq!(chars.next(), Some((2..3, 'o')));
-        assert_eq!(chars.as_str(), "");
+        // Cuts adjacent to 4 byte chars
+        let text = "🗻🌏🗻";
+        assert_eq!(text.truncate_with_ellipsis(2), (0..4, true));
+        assert_eq!(text.truncate_with_ellipsis(1), (0..0, true));
+        assert_eq!(text.truncate_with_ellipsis(3), (0..12, false));
+        assert_eq!("a🌏b".truncate_with_ellipsis(2), (0..1, true));
+        assert_eq!("ab🌏".truncate_with_ellipsis(2), (0..1, true));
 
-        assert_eq!(chars.next(), None);
-        assert_eq!(chars.as_str(), "");
+        assert_eq!(text.truncate_with_ellipsis(0), (0..0, true));
+        assert_eq!("".truncate_with_ellipsis(0), (0..0, false));
+        assert_eq!("a".truncate_with_ellipsis(0), (0..0, true));
     }
 
     #[test]
-    fn test_simple_multi_byte() {
-        let text = "🗻∈🌏";
+    fn test_truncate_middle_with_ellipsis() {
+        let text = "Hello 🗻12∈45🌏 World ¢";
+        let count = text.chars().count();
 
-        let mut chars = text.char_ranges();
-        assert_eq!(chars.as_str(), "🗻∈🌏");
+        assert_eq!(
+            text.truncate_middle_with_ellipsis(count),
+            (0..text.len(), text.len()..text.len(), false)
+        );
 
-        assert_eq!(chars.next(), Some((0..4, '🗻')));
-        assert_eq!(chars.next(), Some((4..7, '∈')));
-        assert_eq!(chars.next(), Some((7..11, '🌏')));
-        assert_eq!(chars.next(), None);
+        for max_chars in 0..count {
+            let (head, tail, truncated) = text.truncate_middle_with_ellipsis(max_chars);
+            assert!(truncated);
+            assert_eq!((head.start, tail.end), (0, text.len()));
+            assert!(head.end < tail.start);
+
+            let head = text[head].chars().count();
+            let tail = text[tail].chars().count();
+            assert_eq!(head + tail, max_chars.saturating_sub(1));
+            assert!(tail == head || tail == head + 1);
+        }
+
+        // Cuts adjacent to 4 byte chars
+        let text = "🗻a🌏b🗻";
+        assert_eq!(text.truncate_middle_with_ellipsis(4), (0..4, 9..14, true));
+        assert_eq!(text.truncate_middle_with_ellipsis(3), (0..4, 10..14, true));
+        assert_eq!(text.truncate_middle_with_ellipsis(2), (0..0, 10..14, true));
+        assert_eq!(text.truncate_middle_with_ellipsis(1), (0..0, 14..14, true));
+        assert_eq!("".truncate_middle_with_ellipsis(0), (0..0, 0..0, false));
     }
 
     #[test]
-    fn test_simple_mixed_multi_byte() {
-        let text = "🗻12∈45🌏";
-        let mut chars = text.char_ranges();
-        assert_eq!(chars.as_str(), "🗻12∈45🌏");
+    fn test_strip_prefix_suffix_range() {
+        let text = "🗻∈🌏 Hello 🗻∈🌏";
 
-        assert_eq!(chars.next(), Some((0..4, '🗻')));
-        assert_eq!(chars.as_str(), "12∈45🌏");
+        assert_eq!(text.strip_prefix_range(""), Some((0..0, text)));
+        assert_eq!(text.strip_suffix_range(""), Some((29..29, text)));
+        assert_eq!(text.strip_prefix_range(text), Some((0..29, "")));
+        assert_eq!(text.strip_suffix_range(text), Some((0..29, "")));
 
-        assert_eq!(chars.next(), Some((4..5, '1')));
-        assert_eq!(chars.as_str(), "2∈45🌏");
+        assert_eq!(
+            text.strip_prefix_range("🗻∈"),
+            Some((0..7, "🌏 Hello 🗻∈🌏"))
+        );
+        assert_eq!(
+            text.strip_suffix_range("∈🌏"),
+            Some((22..29, "🗻∈🌏 Hello 🗻"))
+        );
+        assert_eq!(
+            text.strip_prefix_range('🗻'),
+            Some((0..4, "∈🌏 Hello 🗻∈🌏"))
+        );
+        assert_eq!(
+            text.strip_suffix_range(['🗻', '🌏']),
+            Some((25..29, "🗻∈🌏 Hello 🗻∈"))
+        );
+        assert_eq!(
+            text.strip_prefix_range(|c: char| !c.is_ascii()),
+            Some((0..4, "∈🌏 Hello 🗻∈🌏")),
+        );
 
-        assert_eq!(chars.next(), Some((5..6, '2')));
-        assert_eq!(chars.as_str(), "∈45🌏");
+        assert_eq!(text.strip_prefix_range("∈"), None);
+        assert_eq!(text.strip_suffix_range("🗻"), None);
+        assert_eq!(text.strip_prefix_range(char::is_whitespace), None);
+        assert_eq!("".strip_prefix_range('a'), None);
+        assert_eq!("".strip_prefix_range(""), Some((0..0, "")));
 
-        assert_eq!(chars.next(), Some((6..9, '∈')));
-        assert_eq!(chars.as_str(), "45🌏");
+        for prefix in ["", "🗻", "🗻∈🌏 H", "x", "🌏", text] {
+            let expected = text.strip_prefix(prefix);
+            let actual = text.strip_prefix_range(prefix);
+            assert_eq!(actual.clone().map(|(_r, rest)| rest), expected);
+            if let Some((r, rest)) = actual {
+                assert_eq!(&text[r.clone()], prefix);
+                assert_eq!(&text[r.end..], rest);
+            }
 
-        assert_eq!(chars.next(), Some((9..10, '4')));
-        assert_eq!(chars.as_str(), "5🌏");
+            let expected = text.strip_suffix(prefix);
+            let actual = text.strip_suffix_range(prefix);
+            assert_eq!(actual.map(|(_r, rest)| rest), expected);
+        }
+    }
 
-        assert_eq!(chars.next(), Some((10..11, '5')));
-        assert_eq!(chars.as_str(), "🌏");
+    #[test]
+    fn test_utf16_offset() {
+        let text = "Hello 🗻12∈45🌏 World ¢";
+        let units = text.encode_utf16().count();
 
-        assert_eq!(chars.next(), Some((11..15, '🌏')));
-        assert_eq!(chars.as_str(), "");
+        assert_eq!(text.utf16_offset(0), Some(0));
+        assert_eq!(text.utf16_offset(text.len()), Some(units));
+        assert_eq!(text.utf16_offset(text.len() + 1), None);
+        assert_eq!(text.byte_offset_from_utf16(0), Some(0));
+        assert_eq!(text.byte_offset_from_utf16(units), Some(text.len()));
+        assert_eq!(text.byte_offset_from_utf16(units + 1), None);
+        assert_eq!(text.utf16_len_of_range(0..text.len()), Some(units));
+        assert_eq!(text.utf16_len_of_range(6..10), Some(2));
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = 10..6;
+        assert_eq!(text.utf16_len_of_range(reversed), None);
+        assert_eq!(text.utf16_len_of_range(0..(text.len() + 1)), None);
 
-        assert_eq!(chars.next(), None);
-        assert_eq!(chars.as_str(), "");
+        assert_eq!("".utf16_offset(0), Some(0));
+        assert_eq!("".byte_offset_from_utf16(0), Some(0));
+        assert_eq!("".byte_offset_from_utf16(1), None);
     }
 
     #[test]
-    fn test_simple_next_back() {
-        let text = "Foo";
-        let mut chars = text.char_ranges();
-        assert_eq!(chars.as_str(), "Foo");
+    fn test_utf16_offset_random() {
+        const CHARS: [char; 6] = ['a', 'é', '∈', '🌏', '🗻', '\u{FFFF}'];
 
-        assert_eq!(chars.next_back(), Some((2..3, 'o')));
-        assert_eq!(chars.as_str(), "Fo");
+        let mut next = crate::test_util::random();
 
-        assert_eq!(chars.next_back(), Some((1..2, 'o')));
-        assert_eq!(chars.as_str(), "F");
+        for _ in 0..200 {
+            let len = next() % 48;
+            let text = (0..len)
+                .map(|_| CHARS[next() % CHARS.len()])
+                .collect::<String>();
 
-        assert_eq!(chars.next_back(), Some((0..1, 'F')));
-        assert_eq!(chars.as_str(), "");
+            let mut units = 0;
+            for (r, c) in text.char_ranges() {
+                assert_eq!(text.utf16_offset(r.start), Some(units));
+                assert_eq!(text.byte_offset_from_utf16(units), Some(r.start));
+                assert_eq!(text.utf16_len_of_range(r.clone()), Some(c.len_utf16()));
+                for byte in (r.start + 1)..r.end {
+                    assert_eq!(text.utf16_offset(byte), None);
+                }
+                if c.len_utf16() == 2 {
+                    assert_eq!(text.byte_offset_from_utf16(units + 1), None);
+                }
+                units += c.len_utf16();
+            }
 
-        assert_eq!(chars.next_back(), None);
-        assert_eq!(chars.as_str(), "");
+            assert_eq!(text.utf16_offset(text.len()), Some(units));
+            assert_eq!(text.byte_offset_from_utf16(units), Some(text.len()));
+            assert_eq!(text.byte_offset_from_utf16(units + 1), None);
+        }
     }
 
     #[test]
-    fn test_simple_next_back_multi_byte() {
-        let text = "🗻12∈45🌏";
-        let mut chars = text.char_ranges();
-        assert_eq!(chars.as_str(), "🗻12∈45🌏");
-
-        assert_eq!(chars.next_back(), Some((11..15, '🌏')));
-        assert_eq!(chars.as_str(), "🗻12∈45");
+    fn test_char_ranges_in() {
+        let text = "Hello 🗻12∈45🌏 World ¢";
 
-        assert_eq!(chars.next_back(), Some((10..11, '5')));
-        assert_eq!(chars.as_str(), "🗻12∈4");
+        assert!(text.char_ranges_in(..).eq(text.char_ranges()));
+        assert!(text.char_ranges_in(0..text.len()).eq(text.char_ranges()));
+        assert!(text
+            .char_ranges_in(0..text.len())
+            .rev()
+            .eq(text.char_ranges().rev()));
 
-        assert_eq!(chars.next_back(), Some((9..10, '4')));
-        assert_eq!(chars.as_str(), "🗻12∈");
+        assert_eq!(text.char_ranges_in(6..6).next(), None);
+        assert_eq!(text.char_ranges_in(30..).next(), None);
+        assert!(text.char_ranges_in(6..10).eq([(6..10, '🗻')]));
+        assert!(text.char_ranges_in(17..=20).eq([(17..21, '🌏')]));
+        assert!(text.char_ranges_in(28..).eq([(28..30, '¢')]));
 
-        assert_eq!(chars.next_back(), Some((6..9, '∈')));
-        assert_eq!(chars.as_str(), "🗻12");
+        let chars = text.char_ranges_in(10..15);
+        assert_eq!(chars.offset(), 10);
+        assert_eq!(chars.as_str(), "12∈");
+        assert!(chars.eq([(10..11, '1'), (11..12, '2'), (12..15, '∈')]));
+    }
 
-        assert_eq!(chars.next_back(), Some((5..6, '2')));
-        assert_eq!(chars.as_str(), "🗻1");
+    #[test]
+    #[should_panic(
+        expected = "byte index 8 is not a char boundary; it is inside '🗻' (bytes 6..10)"
+    )]
+    fn test_char_ranges_in_start_not_boundary() {
+        _ = "Hello 🗻12∈45🌏 World ¢".char_ranges_in(8..);
+    }
 
-        assert_eq!(chars.next_back(), Some((4..5, '1')));
-        assert_eq!(chars.as_str(), "🗻");
+    #[test]
+    #[should_panic(
+        expected = "byte index 29 is not a char boundary; it is inside '¢' (bytes 28..30)"
+    )]
+    fn test_char_ranges_in_end_not_boundary() {
+        _ = "Hello 🗻12∈45🌏 World ¢".char_ranges_in(..29);
+    }
 
-        assert_eq!(chars.next_back(), Some((0..4, '🗻')));
-        assert_eq!(chars.as_str(), "");
+    #[test]
+    #[should_panic(expected = "byte index 31 is out of bounds of text of length 30")]
+    fn test_char_ranges_in_out_of_bounds() {
+        _ = "Hello 🗻12∈45🌏 World ¢".char_ranges_in(0..=30);
+    }
 
-        assert_eq!(chars.next_back(), None);
-        assert_eq!(chars.as_str(), "");
+    #[test]
+    #[should_panic(expected = "range start 10 is after end 6")]
+    fn test_char_ranges_in_reversed() {
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = 10..6;
+        _ = "Hello 🗻12∈45🌏 World ¢".char_ranges_in(reversed);
     }
 
     #[test]
-    fn test_simple_next_and_next_back() {
-        let text = "Foo Bar";
+    fn test_char_ranges_from() {
+        let text = "Hello 🗻12∈45🌏 World ¢";
+        let expected = text.char_ranges().collect::<Vec<_>>();
 
-        let mut chars = text.char_ranges();
-        assert_eq!(chars.as_str(), "Foo Bar");
+        for (i, (r, _c)) in expected.iter().enumerate() {
+            assert!(text
+                .char_ranges_from(r.start)
+                .eq(expected[i..].iter().cloned()));
+            assert!(text
+                .char_ranges_up_to(r.start)
+                .eq(expected[..i].iter().cloned()));
 
-        assert_eq!(chars.next_back(), Some((6..7, 'r')));
-        assert_eq!(chars.as_str(), "Foo Ba");
+            let mut chars = text.char_ranges();
+            chars.next();
+            chars.next_back();
+            let chars = chars.char_ranges_from(r.start);
+            assert!(chars.eq(expected[i..].iter().cloned()));
 
-        assert_eq!(chars.next_back(), Some((5..6, 'a')));
-        assert_eq!(chars.as_str(), "Foo B");
+            let mut chars = text.char_ranges();
+            chars.next();
+            chars.next_back();
+            let chars = chars.char_ranges_up_to(r.start);
+            assert!(chars.eq(expected[..i].iter().cloned()));
+        }
 
-        assert_eq!(chars.next(), Some((0..1, 'F')));
-        assert_eq!(chars.as_str(), "oo B");
+        assert_eq!(text.char_ranges_from(text.len()).next(), None);
+        assert!(text
+            .char_ranges_up_to(text.len())
+            .eq(expected.iter().cloned()));
+        assert_eq!(text.char_ranges().char_ranges_from(text.len()).next(), None);
+    }
 
-        assert_eq!(chars.next(), Some((1..2, 'o')));
-        assert_eq!(chars.as_str(), "o B");
+    #[test]
+    #[should_panic(
+        expected = "byte index 18 is not a char boundary; it is inside '🌏' (bytes 17..21)"
+    )]
+    fn test_char_ranges_from_not_boundary() {
+        _ = "Hello 🗻12∈45🌏 World ¢".char_ranges_from(18);
+    }
 
-        assert_eq!(chars.next_back(), Some((4..5, 'B')));
-        assert_eq!(chars.as_str(), "o ");
+    #[test]
+    #[should_panic(
+        expected = "byte index 13 is not a char boundary; it is inside '∈' (bytes 12..15)"
+    )]
+    fn test_char_ranges_up_to_not_boundary() {
+        _ = "Hello 🗻12∈45🌏 World ¢"
+            .char_ranges()
+            .char_ranges_up_to(13);
+    }
 
-        assert_eq!(chars.next(), Some((2..3, 'o')));
-        assert_eq!(chars.as_str(), " ");
+    #[test]
+    #[should_panic(expected = "byte index 31 is out of bounds of text of length 30")]
+    fn test_char_ranges_from_out_of_bounds() {
+        _ = "Hello 🗻12∈45🌏 World ¢".char_ranges().char_ranges_from(31);
+    }
 
-        assert_eq!(chars.next(), Some((3..4, ' ')));
-        assert_eq!(chars.as_str(), "");
+    #[test]
+    fn test_strip_bom_range() {
+        // BOM only
+        assert_eq!("\u{FEFF}".strip_bom_range(), (Some(0..3), ""));
+        assert_eq!("\u{FEFF}".char_ranges_skip_bom().next(), None);
 
-        assert_eq!(chars.next(), None);
-        assert_eq!(chars.as_str(), "");
+        // BOM followed by text
+        let text = "\u{FEFF}Hello 🗻";
+        assert_eq!(text.strip_bom_range(), (Some(0..3), "Hello 🗻"));
+        let mut chars = text.char_ranges_skip_bom();
+        assert_eq!(chars.as_str(), "Hello 🗻");
+        assert_eq!(chars.next(), Some((3..4, 'H')));
+        assert_eq!(chars.next_back(), Some((9..13, '🗻')));
+
+        // Only a single BOM is stripped
+        let text = "\u{FEFF}\u{FEFF}a";
+        assert_eq!(text.strip_bom_range(), (Some(0..3), "\u{FEFF}a"));
+        assert_eq!(text.char_ranges_skip_bom().next(), Some((3..6, '\u{FEFF}')));
+
+        // No BOM
+        assert_eq!("".strip_bom_range(), (None, ""));
+        assert_eq!("Hello".strip_bom_range(), (None, "Hello"));
+        assert!("🗻∈".char_ranges_skip_bom().eq("🗻∈".char_ranges()));
+
+        // BOM in the middle of the text
+        let text = "a\u{FEFF}b";
+        assert_eq!(text.strip_bom_range(), (None, text));
+        assert_eq!(
+            text.char_ranges_skip_bom().collect::<Vec<_>>(),
+            [(0..1, 'a'), (1..4, '\u{FEFF}'), (4..5, 'b')],
+        );
     }
 
     #[test]
-    fn test_simple_next_and_next_back_multi_byte() {
-        let text = "🗻12∈45🌏";
-        let mut chars = text.char_ranges();
-        assert_eq!(chars.as_str(), "🗻12∈45🌏");
+    fn test_line_range_at() {
+        let text = "foo\nbar\r\n\n🗻∈🌏";
 
-        assert_eq!(chars.next_back(), Some((11..15, '🌏')));
-        assert_eq!(chars.as_str(), "🗻12∈45");
+        // Index 0
+        assert_eq!(text.line_range_at(0), Some((0..3, 0, 0)));
+        assert_eq!(text.line_range_at(2), Some((0..3, 0, 2)));
 
-        assert_eq!(chars.next_back(), Some((10..11, '5')));
-        assert_eq!(chars.as_str(), "🗻12∈4");
+        // Exactly on a newline
+        assert_eq!(text.line_range_at(3), Some((0..3, 0, 3)));
 
-        assert_eq!(chars.next(), Some((0..4, '🗻')));
-        assert_eq!(chars.as_str(), "12∈4");
+        // CRLF, where the '\r' belongs to the terminator
+        assert_eq!(text.line_range_at(4), Some((4..7, 1, 0)));
+        assert_eq!(text.line_range_at(7), Some((4..7, 1, 3)));
+        assert_eq!(text.line_range_at(8), Some((4..7, 1, 4)));
 
-        assert_eq!(chars.next(), Some((4..5, '1')));
-        assert_eq!(chars.as_str(), "2∈4");
+        // Empty line
+        assert_eq!(text.line_range_at(9), Some((9..9, 2, 0)));
 
-        assert_eq!(chars.next_back(), Some((9..10, '4')));
-        assert_eq!(chars.as_str(), "2∈");
+        // Last line without a newline
+        assert_eq!(text.line_range_at(10), Some((10..21, 3, 0)));
+        assert_eq!(text.line_range_at(14), Some((10..21, 3, 1)));
+        assert_eq!(text.line_range_at(21), Some((10..21, 3, 3)));
+        assert_eq!(text.line_range_at(22), None);
 
-        assert_eq!(chars.next(), Some((5..6, '2')));
-        assert_eq!(chars.as_str(), "∈");
+        // Snapped down to the containing char
+        assert_eq!(text.line_range_at(13), Some((10..21, 3, 0)));
+        assert_eq!(text.line_range_at(16), Some((10..21, 3, 1)));
+        assert_eq!(text.line_range_at(20), Some((10..21, 3, 2)));
 
-        assert_eq!(chars.next(), Some((6..9, '∈')));
-        assert_eq!(chars.as_str(), "");
+        // Trailing newline
+        assert_eq!("a\n".line_range_at(2), Some((2..2, 1, 0)));
+        assert_eq!("a\r\n".line_range_at(3), Some((3..3, 1, 0)));
+        assert_eq!("".line_range_at(0), Some((0..0, 0, 0)));
 
-        assert_eq!(chars.next(), None);
-        assert_eq!(chars.as_str(), "");
+        // A lone '\r' is part of the line
+        assert_eq!("a\rb".line_range_at(3), Some((0..3, 0, 3)));
+        assert_eq!("a\r".line_range_at(0), Some((0..2, 0, 0)));
     }
 
     #[test]
-    fn test_last() {
-        let cases = [
-            ("Hello World", 10..11, 'd'),
-            ("Hello 👋 World 🌏", 17..21, '🌏'),
-            ("🗻12∈45🌏", 11..15, '🌏'),
-            ("Hello 🗻12∈45🌏 World", 26..27, 'd'),
-        ];
-        for (text, r, c) in cases {
-            let actual = text.char_ranges().last().unwrap();
+    fn test_expand_to_line() {
+        let text = "foo\nbar\r\n\n🗻∈🌏";
 
-            assert_eq!(actual.0, r);
-            assert_eq!(actual.1, c);
-            assert!(text[r].chars().eq([c]));
-        }
+        // Caret
+        assert_eq!(text.expand_to_line(1..1), Some(0..3));
+        assert_eq!(text.expand_to_line(4..4), Some(4..7));
+        assert_eq!(text.expand_to_line(9..9), Some(9..9));
+        assert_eq!(text.expand_to_line(21..21), Some(10..21));
+        // Within '∈'
+        assert_eq!(text.expand_to_line(15..15), Some(10..21));
+
+        // Spanning multiple lines
+        assert_eq!(text.expand_to_line(1..5), Some(0..7));
+        assert_eq!(text.expand_to_line(5..12), Some(4..21));
+
+        // Touching a line boundary exactly
+        assert_eq!(text.expand_to_line(0..3), Some(0..3));
+        assert_eq!(text.expand_to_line(1..4), Some(0..4));
+        assert_eq!(text.expand_to_line(5..10), Some(4..10));
+        assert_eq!(text.expand_to_line(4..7), Some(4..7));
+
+        // CRLF, where the range never shrinks
+        assert_eq!(text.expand_to_line(5..8), Some(4..8));
+        assert_eq!(text.expand_to_line(5..9), Some(4..9));
+
+        assert_eq!(text.expand_to_line(0..100), Some(0..21));
+        assert_eq!(text.expand_to_line(22..22), None);
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = text.expand_to_line(5..4);
+        assert_eq!(reversed, None);
+        assert_eq!("".expand_to_line(0..0), Some(0..0));
     }
 
     #[test]
-    fn test_nth() {
-        let cases = [
-            "Hello World",
-            "Hello 👋 World 🌏",
-            "🗻12∈45🌏",
-            "Hello 🗻12∈45🌏 World",
-        ];
-        for text in cases {
-            // Since `nth()` doesn't use `next()` internally,
-            // then they are be compared
-            let char_ranges1 = text.char_ranges();
-            let char_ranges2 = iter::from_fn({
-                let mut char_ranges = text.char_ranges();
-                move || char_ranges.nth(0)
-            });
-            assert!(char_ranges1.eq(char_ranges2));
-        }
+    fn test_expand_to_line_with_terminator() {
+        let text = "foo\nbar\r\n\n🗻∈🌏";
+
+        assert_eq!(text.expand_to_line_with_terminator(1..1), Some(0..4));
+        assert_eq!(text.expand_to_line_with_terminator(5..5), Some(4..9));
+        assert_eq!(text.expand_to_line_with_terminator(9..9), Some(9..10));
+        assert_eq!(text.expand_to_line_with_terminator(12..12), Some(10..21));
+
+        // Touching a line boundary exactly
+        assert_eq!(text.expand_to_line_with_terminator(0..4), Some(0..4));
+        assert_eq!(text.expand_to_line_with_terminator(1..5), Some(0..9));
+        assert_eq!(text.expand_to_line_with_terminator(4..7), Some(4..9));
+
+        // Within CRLF
+        assert_eq!(text.expand_to_line_with_terminator(7..7), Some(4..9));
+        assert_eq!(text.expand_to_line_with_terminator(8..8), Some(4..9));
+        assert_eq!("a\r\n".expand_to_line_with_terminator(3..3), Some(3..3));
     }
 
     #[test]
-    fn test_nth_back() {
+    fn test_trim_range() {
         let cases = [
-            "Hello World",
-            "Hello 👋 World 🌏",
-            "🗻12∈45🌏",
-            "Hello 🗻12∈45🌏 World",
+            "",
+            "   ",
+            "Hello",
+            "  Hello World  ",
+            "\t\nHello\r\n",
+            "\u{A0}Hello\u{A0}",
+            "\u{3000}🗻∈🌏\u{3000}",
+            "\u{3000} \u{A0}",
         ];
         for text in cases {
-            // Since `nth_back()` doesn't use `next_back()` internally,
-            // then they are be compared
-            let char_ranges1 = text.char_ranges().rev();
-            let char_ranges2 = iter::from_fn({
-                let mut char_ranges = text.char_ranges();
-                move || char_ranges.nth_back(0)
-            });
-            assert!(char_ranges1.eq(char_ranges2));
-        }
-    }
+            let (r, trimmed) = text.trim_range();
+            assert_eq!(trimmed, text.trim());
+            assert_eq!(&text[r], trimmed);
 
-    #[test]
-    fn test_char_ranges() {
-        let text = "Hello World";
-        for (r, c) in text.char_ranges() {
-            let mut chars = text[r].chars();
-            assert_eq!(chars.next(), Some(c));
-            assert_eq!(chars.next(), None);
-        }
+            let (r, trimmed) = text.trim_start_range();
+            assert_eq!(trimmed, text.trim_start());
+            assert_eq!(&text[r.clone()], trimmed);
+            assert_eq!(r.end, text.len());
 
-        let text = "🗻12∈45🌏";
-        for (r, c) in text.char_ranges() {
-            let mut chars = text[r].chars();
-            assert_eq!(chars.next(), Some(c));
-            assert_eq!(chars.next(), None);
+            let (r, trimmed) = text.trim_end_range();
+            assert_eq!(trimmed, text.trim_end());
+            assert_eq!(&text[r.clone()], trimmed);
+            assert_eq!(r.start, 0);
         }
     }
 
     #[test]
-    fn test_char_ranges_start() {
-        let text = "Hello 🗻12∈45🌏 World";
-        let mut chars = text.char_ranges();
-        while let Some((r, _c)) = chars.next_back() {
-            assert_eq!(chars.as_str(), &text[..r.start]);
-        }
+    fn test_trim_range_multi_byte_whitespace() {
+        let text = "\u{3000}\u{A0}🌏\u{A0}\u{3000}";
+        assert_eq!(text.trim_range(), (5..9, "🌏"));
+        assert_eq!(text.trim_start_range(), (5..14, "🌏\u{A0}\u{3000}"));
+        assert_eq!(text.trim_end_range(), (0..9, "\u{3000}\u{A0}🌏"));
     }
 
     #[test]
-    fn test_char_ranges_end() {
-        let text = "Hello 🗻12∈45🌏 World";
-        let mut chars = text.char_ranges();
-        while let Some((r, _c)) = chars.next() {
-            assert_eq!(chars.as_str(), &text[r.end..]);
-        }
-    }
+    fn test_trim_range_empty() {
+        assert_eq!("".trim_range(), (0..0, ""));
+        assert_eq!("".trim_start_range(), (0..0, ""));
+        assert_eq!("".trim_end_range(), (0..0, ""));
 
-    #[test]
-    fn test_full_range() {
-        let text = "Hello 🗻12∈45🌏 World\n";
-        let mut chars = text.char_ranges();
-        while let Some((first, _)) = chars.next() {
-            let (last, _) = chars.next_back().unwrap();
-            assert_eq!(chars.as_str(), &text[first.end..last.start]);
-        }
+        let text = " \u{3000}\u{A0}";
+        assert_eq!(text.trim_range(), (6..6, ""));
+        assert_eq!(text.trim_start_range(), (6..6, ""));
+        assert_eq!(text.trim_end_range(), (0..0, ""));
     }
 
     #[test]
-    fn test_offset() {
-        let text = "Hello 👋 World 🌏";
-        let mut chars = text.char_ranges();
-
-        let emoji_end = {
-            assert_eq!(chars.next(), Some((0..1, 'H')));
-            assert_eq!(chars.next(), Some((1..2, 'e')));
-            assert_eq!(chars.next(), Some((2..3, 'l')));
-            assert_eq!(chars.next(), Some((3..4, 'l')));
-            assert_eq!(chars.next(), Some((4..5, 'o')));
-            assert_eq!(chars.next(), Some((5..6, ' ')));
+    fn test_trim_matches_range() {
+        let is_quote = |c| matches!(c, '"' | '“' | '”');
+        let cases = [
+            "",
+            "\"\"",
+            "Hello",
+            "\"Hello\"",
+            "“Hello”",
+            "““🌏””",
+            "“Hello \" World”",
+            "\"“”\"",
+        ];
+        for text in cases {
+            let (r, trimmed) = text.trim_matches_range(is_quote);
+            assert_eq!(trimmed, text.trim_matches(is_quote));
+            assert_eq!(&text[r], trimmed);
 
-            let emoji_waving_hand = chars.next();
-            assert_eq!(emoji_waving_hand, Some((6..10, '👋')));
+            let (r, trimmed) = text.trim_start_matches_range(is_quote);
+            assert_eq!(trimmed, text.trim_start_matches(is_quote));
+            assert_eq!(&text[r.clone()], trimmed);
+            assert_eq!(r.end, text.len());
 
-            emoji_waving_hand.unwrap().0.end
-        };
+            let (r, trimmed) = text.trim_end_matches_range(is_quote);
+            assert_eq!(trimmed, text.trim_end_matches(is_quote));
+            assert_eq!(&text[r.clone()], trimmed);
+            assert_eq!(r.start, 0);
+        }
+    }
 
-        let offset_chars = text[emoji_end..].char_ranges().offset(emoji_end);
-        assert_eq!(chars.as_str(), offset_chars.as_str());
+    #[test]
+    fn test_trim_matches_range_edge_cases() {
+        let text = "““🌏””";
+        assert_eq!(text.trim_matches_range(|_| true), (16..16, ""));
+        assert_eq!(text.trim_start_matches_range(|_| true), (16..16, ""));
+        assert_eq!(text.trim_end_matches_range(|_| true), (0..0, ""));
 
-        for offset_char in offset_chars {
-            assert_eq!(chars.next(), Some(offset_char));
-        }
+        assert_eq!(text.trim_matches_range(|_| false), (0..16, text));
+        assert_eq!(text.trim_start_matches_range(|_| false), (0..16, text));
+        assert_eq!(text.trim_end_matches_range(|_| false), (0..16, text));
 
-        assert_eq!(chars.next(), None);
+        let is_quote = |c| c == '“' || c == '”';
+        assert_eq!(text.trim_matches_range(is_quote), (6..10, "🌏"));
+        assert_eq!(text.trim_start_matches_range(is_quote), (6..16, "🌏””"));
+        assert_eq!(text.trim_end_matches_range(is_quote), (0..10, "““🌏"));
     }
 }
@@ -0,0 +1,112 @@
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::{CharRanges, CharRangesOffset};
+
+/// A trait implemented by both [`CharRanges`] and [`CharRangesOffset`],
+/// allowing code to be generic over either of them.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::{CharRangesExt, CharRangesLike};
+///
+/// fn first_word<'a>(chars: impl CharRangesLike<'a>) -> (usize, &'a str) {
+///     let mut chars = chars.add_offset(0);
+///     let (r, word) = chars.take_while_range(|c| !c.is_whitespace());
+///     (r.start, word)
+/// }
+///
+/// let text = "Hello World";
+/// assert_eq!(first_word(text.char_ranges()), (0, "Hello"));
+/// assert_eq!(first_word(text[6..].char_ranges_offset(6)), (6, "World"));
+/// ```
+pub trait CharRangesLike<'a>:
+    DoubleEndedIterator<Item = (Range<usize>, char)> + FusedIterator + Clone
+{
+    /// Returns the remaining substring.
+    fn as_str(&self) -> &'a str;
+
+    /// Returns the start byte position of the next [`char`],
+    /// with any offset applied.
+    fn front_offset(&self) -> usize;
+
+    /// Returns the end byte position of the last [`char`],
+    /// with any offset applied.
+    fn back_offset(&self) -> usize;
+
+    /// Returns a [`CharRangesOffset`], with `extra` added to any
+    /// current offset.
+    fn add_offset(self, extra: usize) -> CharRangesOffset<'a>;
+}
+
+impl<'a> CharRangesLike<'a> for CharRanges<'a> {
+    #[inline]
+    fn as_str(&self) -> &'a str {
+        CharRanges::as_str(self)
+    }
+
+    #[inline]
+    fn front_offset(&self) -> usize {
+        CharRanges::front_offset(self)
+    }
+
+    #[inline]
+    fn back_offset(&self) -> usize {
+        CharRanges::back_offset(self)
+    }
+
+    #[inline]
+    fn add_offset(self, extra: usize) -> CharRangesOffset<'a> {
+        self.offset(extra)
+    }
+}
+
+impl<'a> CharRangesLike<'a> for CharRangesOffset<'a> {
+    #[inline]
+    fn as_str(&self) -> &'a str {
+        CharRangesOffset::as_str(self)
+    }
+
+    #[inline]
+    fn front_offset(&self) -> usize {
+        CharRangesOffset::front_offset(self)
+    }
+
+    #[inline]
+    fn back_offset(&self) -> usize {
+        CharRangesOffset::back_offset(self)
+    }
+
+    #[inline]
+    fn add_offset(self, extra: usize) -> CharRangesOffset<'a> {
+        CharRangesOffset::add_offset(self, extra)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::ops::Range;
+
+    use crate::{CharRangesExt, CharRangesLike};
+
+    fn ranges<'a>(chars: impl CharRangesLike<'a>) -> impl Iterator<Item = Range<usize>> {
+        chars.map(|(r, _c)| r)
+    }
+
+    #[test]
+    fn test_char_ranges_like() {
+        let text = "Hello 🗻12∈45🌏 World";
+
+        assert!(ranges(text.char_ranges()).eq(ranges(text.char_ranges_offset(0))));
+
+        let chars = text.char_ranges_offset(3);
+        assert_eq!(CharRangesLike::front_offset(&chars), 3);
+        assert_eq!(CharRangesLike::back_offset(&chars), 3 + text.len());
+        assert_eq!(CharRangesLike::as_str(&chars), text);
+
+        let stacked = chars.add_offset(4).add_offset(5);
+        assert_eq!(stacked.offset(), 12);
+        assert!(stacked.eq(text.char_ranges().add_offset(12)));
+    }
+}
@@ -0,0 +1,744 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::{Base, ColumnUnit, LineColumn};
+
+/// An index of the start byte positions of all lines in a text, for
+/// repeatedly converting between byte positions and [`LineColumn`]s.
+///
+/// Building the index is a single pass over the text, after which
+/// looking up a line is a binary search over the line starts.
+///
+/// Lines and columns follow the same rules as [`CharRanges::char_positions()`],
+/// i.e. only `'\n'` starts a new line, and by default lines and columns are
+/// zero-based and columns count [`char`]s, see [`with_base()`] and
+/// [`with_column_unit()`]. A text ending with `'\n'` ends with an empty line.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::{LineColumn, LineIndex};
+///
+/// let text = "foo\n🗻∈🌏\nbar";
+///
+/// let index = LineIndex::new(text);
+/// assert_eq!(index.line_count(), 3);
+///
+/// assert_eq!(index.line_col(8), Some(LineColumn::new(1, 1)));
+/// assert_eq!(index.offset(1, 1), Some(8));
+///
+/// assert_eq!(index.line_range(1), Some(4..16));
+/// ```
+///
+/// [`CharRanges::char_positions()`]: crate::CharRanges::char_positions
+/// [`with_base()`]: Self::with_base
+/// [`with_column_unit()`]: Self::with_column_unit
+#[derive(Clone, Debug)]
+pub struct LineIndex<'a> {
+    text: &'a str,
+    /// Start byte position of each line, where the first is always `0`.
+    line_starts: Vec<usize>,
+    base: Base,
+    unit: ColumnUnit,
+}
+
+impl<'a> LineIndex<'a> {
+    /// Builds the index of all lines in `text`.
+    pub fn new(text: &'a str) -> Self {
+        let line_starts = core::iter::once(0).chain(line_ends(text)).collect();
+        Self {
+            text,
+            line_starts,
+            base: Base::Zero,
+            unit: ColumnUnit::Chars,
+        }
+    }
+
+    /// Sets whether lines and columns are zero-based or one-based,
+    /// for all lookups.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{Base, ColumnUnit, LineColumn, LineIndex};
+    ///
+    /// let text = "foo\n🗻bar";
+    ///
+    /// // clangd-style one-based byte columns
+    /// let index = LineIndex::new(text)
+    ///     .with_base(Base::One)
+    ///     .with_column_unit(ColumnUnit::Bytes);
+    /// assert_eq!(index.line_col(8), Some(LineColumn::new(2, 5)));
+    /// assert_eq!(index.offset(2, 5), Some(8));
+    /// ```
+    #[inline]
+    pub fn with_base(mut self, base: Base) -> Self {
+        self.base = base;
+        self
+    }
+
+    /// Sets what columns count, for all lookups.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{ColumnUnit, LineColumn, LineIndex};
+    ///
+    /// let text = "foo\n🗻bar";
+    ///
+    /// // LSP-style zero-based UTF-16 columns
+    /// let index = LineIndex::new(text).with_column_unit(ColumnUnit::Utf16);
+    /// assert_eq!(index.line_col(8), Some(LineColumn::new(1, 2)));
+    /// assert_eq!(index.offset(1, 2), Some(8));
+    ///
+    /// // Within '🗻'
+    /// assert_eq!(index.offset(1, 1), None);
+    /// ```
+    #[inline]
+    pub fn with_column_unit(mut self, unit: ColumnUnit) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    /// Returns whether lines and columns are zero-based or one-based.
+    #[inline]
+    pub fn base(&self) -> Base {
+        self.base
+    }
+
+    /// Returns what columns count.
+    #[inline]
+    pub fn column_unit(&self) -> ColumnUnit {
+        self.unit
+    }
+
+    /// Returns the text this index was built from.
+    #[inline]
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// Returns the number of lines, which is always at least `1`.
+    #[inline]
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Returns the byte range of `line`, including its trailing `'\n'`
+    /// if any, or `None` if `line` is out of bounds.
+    #[inline]
+    pub fn line_range(&self, line: usize) -> Option<Range<usize>> {
+        line_range(self.text, &self.line_starts, line)
+    }
+
+    /// Returns the [`LineColumn`] of the byte position `pos`.
+    ///
+    /// The position at the end of the text is valid, and is the
+    /// position after the last [`char`].
+    ///
+    /// Returns `None` if `pos` is out of bounds, or not on a [`char`]
+    /// boundary, i.e. positions within a [`char`] are not rounded down.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{LineColumn, LineIndex};
+    ///
+    /// let text = "a\n🗻b";
+    ///
+    /// let index = LineIndex::new(text);
+    /// assert_eq!(index.line_col(6), Some(LineColumn::new(1, 1)));
+    /// assert_eq!(index.line_col(7), Some(LineColumn::new(1, 2)));
+    ///
+    /// // Within '🗻'
+    /// assert_eq!(index.line_col(3), None);
+    /// // Out of bounds
+    /// assert_eq!(index.line_col(8), None);
+    /// ```
+    #[inline]
+    pub fn line_col(&self, pos: usize) -> Option<LineColumn> {
+        line_col(self.text, &self.line_starts, pos, self.base, self.unit)
+    }
+
+    /// Returns the byte position of the [`char`] at `line` and `column`.
+    ///
+    /// The column after the last [`char`] of the last line is valid,
+    /// and is the end of the text.
+    ///
+    /// Returns `None` if `line` is out of bounds, or if `column` is past
+    /// the end of the line, i.e. the inverse of [`line_col()`].
+    ///
+    /// [`line_col()`]: Self::line_col
+    #[inline]
+    pub fn offset(&self, line: usize, column: usize) -> Option<usize> {
+        offset(
+            self.text,
+            &self.line_starts,
+            line,
+            column,
+            self.base,
+            self.unit,
+        )
+    }
+
+    /// Converts `pos` from the [`Base`] and [`ColumnUnit`] of this index,
+    /// to `base` and `unit`, for the same position in the text.
+    ///
+    /// Returns `None` if `pos` is not a valid position, i.e. if
+    /// [`offset()`](Self::offset) returns `None`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{Base, ColumnUnit, LineColumn, LineIndex};
+    ///
+    /// let text = "foo\n🗻bar";
+    ///
+    /// let index = LineIndex::new(text).with_base(Base::One);
+    ///
+    /// let pos = LineColumn::new(2, 3);
+    /// let lsp = index.convert(pos, Base::Zero, ColumnUnit::Utf16);
+    /// assert_eq!(lsp, Some(LineColumn::new(1, 3)));
+    /// ```
+    #[inline]
+    pub fn convert(&self, pos: LineColumn, base: Base, unit: ColumnUnit) -> Option<LineColumn> {
+        let offset = self.offset(pos.line, pos.column)?;
+        line_col(self.text, &self.line_starts, offset, base, unit)
+    }
+}
+
+/// An index of the start byte positions of all lines in an owned text,
+/// which is updated incrementally when the text is edited.
+///
+/// This is the same as [`LineIndex`], except that [`apply_edit()`]
+/// replaces a range of the text, and only updates the line starts within
+/// the edited range, instead of rebuilding the whole index.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::{LineColumn, LineIndexMut};
+///
+/// let mut index = LineIndexMut::new("foo\nbar\nbaz");
+/// assert_eq!(index.line_count(), 3);
+///
+/// // Merges the first two lines
+/// index.apply_edit(2..5, "🗻\n\n");
+/// assert_eq!(index.text(), "fo🗻\n\nar\nbaz");
+/// assert_eq!(index.line_count(), 4);
+///
+/// assert_eq!(index.line_col(12), Some(LineColumn::new(3, 1)));
+/// assert_eq!(index.offset(3, 1), Some(12));
+/// ```
+///
+/// [`apply_edit()`]: Self::apply_edit
+#[derive(Clone, Debug)]
+pub struct LineIndexMut {
+    text: String,
+    /// Start byte position of each line, where the first is always `0`.
+    line_starts: Vec<usize>,
+    base: Base,
+    unit: ColumnUnit,
+}
+
+impl LineIndexMut {
+    /// Builds the index of all lines in `text`.
+    pub fn new(text: impl Into<String>) -> Self {
+        let text = text.into();
+        let line_starts = core::iter::once(0).chain(line_ends(&text)).collect();
+        Self {
+            text,
+            line_starts,
+            base: Base::Zero,
+            unit: ColumnUnit::Chars,
+        }
+    }
+
+    /// Sets whether lines and columns are zero-based or one-based,
+    /// for all lookups.
+    ///
+    /// See [`LineIndex::with_base()`] for more information.
+    #[inline]
+    pub fn with_base(mut self, base: Base) -> Self {
+        self.base = base;
+        self
+    }
+
+    /// Sets what columns count, for all lookups.
+    ///
+    /// See [`LineIndex::with_column_unit()`] for more information.
+    #[inline]
+    pub fn with_column_unit(mut self, unit: ColumnUnit) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    /// Returns whether lines and columns are zero-based or one-based.
+    #[inline]
+    pub fn base(&self) -> Base {
+        self.base
+    }
+
+    /// Returns what columns count.
+    #[inline]
+    pub fn column_unit(&self) -> ColumnUnit {
+        self.unit
+    }
+
+    /// Returns the current text.
+    #[inline]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the current text, consuming the index.
+    #[inline]
+    pub fn into_string(self) -> String {
+        self.text
+    }
+
+    /// Returns a [`LineIndex`] of the current text.
+    ///
+    /// This copies the line starts, but does not rescan the text.
+    #[inline]
+    pub fn to_line_index(&self) -> LineIndex<'_> {
+        LineIndex {
+            text: &self.text,
+            line_starts: self.line_starts.clone(),
+            base: self.base,
+            unit: self.unit,
+        }
+    }
+
+    /// Returns the number of lines, which is always at least `1`.
+    #[inline]
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Returns the byte range of `line`, including its trailing `'\n'`
+    /// if any, or `None` if `line` is out of bounds.
+    #[inline]
+    pub fn line_range(&self, line: usize) -> Option<Range<usize>> {
+        line_range(&self.text, &self.line_starts, line)
+    }
+
+    /// Returns the [`LineColumn`] of the byte position `pos`.
+    ///
+    /// See [`LineIndex::line_col()`] for more information.
+    #[inline]
+    pub fn line_col(&self, pos: usize) -> Option<LineColumn> {
+        line_col(&self.text, &self.line_starts, pos, self.base, self.unit)
+    }
+
+    /// Returns the byte position of the [`char`] at `line` and `column`.
+    ///
+    /// See [`LineIndex::offset()`] for more information.
+    #[inline]
+    pub fn offset(&self, line: usize, column: usize) -> Option<usize> {
+        offset(
+            &self.text,
+            &self.line_starts,
+            line,
+            column,
+            self.base,
+            self.unit,
+        )
+    }
+
+    /// Converts `pos` from the [`Base`] and [`ColumnUnit`] of this index,
+    /// to `base` and `unit`, for the same position in the text.
+    ///
+    /// See [`LineIndex::convert()`] for more information.
+    #[inline]
+    pub fn convert(&self, pos: LineColumn, base: Base, unit: ColumnUnit) -> Option<LineColumn> {
+        let offset = self.offset(pos.line, pos.column)?;
+        line_col(&self.text, &self.line_starts, offset, base, unit)
+    }
+
+    /// Replaces the byte `range` of the text with `replacement`, and
+    /// updates the index.
+    ///
+    /// Only the line starts within `range` are replaced by the line starts
+    /// within `replacement`, and the line starts after `range` are shifted
+    /// by the difference in length. Since only `'\n'` starts a new line,
+    /// a `'\r'` and `'\n'` being joined or split by the edit requires no
+    /// special handling.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds, reversed, or if its start or
+    /// end is not a [`char`] boundary, i.e. the same as
+    /// [`String::replace_range()`].
+    pub fn apply_edit(&mut self, range: Range<usize>, replacement: &str) {
+        // Panics before the line starts are modified
+        self.text.replace_range(range.clone(), replacement);
+
+        // Line starts following a '\n' within the range
+        let first = self
+            .line_starts
+            .partition_point(|&start| start <= range.start);
+        let last = self
+            .line_starts
+            .partition_point(|&start| start <= range.end);
+
+        let inserted = line_ends(replacement).map(|end| range.start + end);
+        let after = first + replacement.bytes().filter(|&b| b == b'\n').count();
+        self.line_starts.splice(first..last, inserted);
+
+        for start in &mut self.line_starts[after..] {
+            *start = *start - range.len() + replacement.len();
+        }
+    }
+}
+
+/// Returns the end byte positions of all lines ending with `'\n'`,
+/// i.e. the start byte positions of all lines except the first.
+#[inline]
+fn line_ends(text: &str) -> impl Iterator<Item = usize> + '_ {
+    text.bytes()
+        .enumerate()
+        .filter(|&(_, b)| b == b'\n')
+        .map(|(i, _)| i + 1)
+}
+
+fn line_range(text: &str, line_starts: &[usize], line: usize) -> Option<Range<usize>> {
+    let start = *line_starts.get(line)?;
+    let end = line_starts.get(line + 1).copied().unwrap_or(text.len());
+    Some(start..end)
+}
+
+fn line_col(
+    text: &str,
+    line_starts: &[usize],
+    pos: usize,
+    base: Base,
+    unit: ColumnUnit,
+) -> Option<LineColumn> {
+    if (pos > text.len()) || !text.is_char_boundary(pos) {
+        return None;
+    }
+
+    let line = line_starts.partition_point(|&start| start <= pos) - 1;
+    let start = line_starts[line];
+    let column = column_len(&text[start..pos], unit);
+    Some(LineColumn::new(line + base.first(), column + base.first()))
+}
+
+fn offset(
+    text: &str,
+    line_starts: &[usize],
+    line: usize,
+    column: usize,
+    base: Base,
+    unit: ColumnUnit,
+) -> Option<usize> {
+    let line = line.checked_sub(base.first())?;
+    let column = column.checked_sub(base.first())?;
+
+    let r = line_range(text, line_starts, line)?;
+    let line_text = &text[r.clone()];
+
+    let i = byte_of_column(line_text, column, unit)?;
+    if i < line_text.len() {
+        Some(r.start + i)
+    } else {
+        // Only the last line has a column after its last `char`,
+        // as every other line ends with '\n'
+        let is_last_line = line + 1 == line_starts.len();
+        is_last_line.then_some(r.end)
+    }
+}
+
+/// Returns the number of columns occupied by `text`.
+pub(crate) fn column_len(text: &str, unit: ColumnUnit) -> usize {
+    match unit {
+        ColumnUnit::Chars => crate::count_chars(text),
+        ColumnUnit::Bytes => text.len(),
+        ColumnUnit::Utf16 => text.chars().map(char::len_utf16).sum(),
+    }
+}
+
+/// Returns the byte position in `text` after `column` columns, or
+/// `None` if `column` is within a [`char`] or past the end of `text`.
+pub(crate) fn byte_of_column(text: &str, column: usize, unit: ColumnUnit) -> Option<usize> {
+    match unit {
+        ColumnUnit::Chars => match text.char_indices().nth(column) {
+            Some((i, _c)) => Some(i),
+            None => (crate::count_chars(text) == column).then_some(text.len()),
+        },
+        ColumnUnit::Bytes => text.is_char_boundary(column).then_some(column),
+        ColumnUnit::Utf16 => {
+            let mut n = 0;
+            for (i, c) in text.char_indices() {
+                if n >= column {
+                    return (n == column).then_some(i);
+                }
+                n += c.len_utf16();
+            }
+            (n == column).then_some(text.len())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::String;
+
+    use super::{LineIndex, LineIndexMut};
+    use crate::{Base, CharRangesExt, ColumnUnit, LineColumn};
+
+    const CORPUS: &[&str] = &[
+        "",
+        "\n",
+        "\n\n",
+        "foo",
+        "foo\n",
+        "foo\nbar",
+        "foo\r\nbar\r\n",
+        "\r\n\r\n",
+        "a\n\n\nb",
+        "🗻∈🌏\n\n¢a\r\nHello 👋 World 🌏\n",
+    ];
+
+    #[test]
+    fn test_line_index_char_positions() {
+        for text in CORPUS {
+            let index = LineIndex::new(text);
+
+            let mut chars = text.char_ranges().char_positions();
+            for (r, pos, c) in chars.by_ref() {
+                assert_eq!(index.line_col(r.start), Some(pos), "{text:?} {c:?}");
+                assert_eq!(index.offset(pos.line, pos.column), Some(r.start));
+
+                let line = index.line_range(pos.line).unwrap();
+                assert!(line.contains(&r.start));
+                assert!(r.end <= line.end);
+            }
+
+            // The position after the last char
+            let end = chars.line_column();
+            assert_eq!(index.line_col(text.len()), Some(end));
+            assert_eq!(index.offset(end.line, end.column), Some(text.len()));
+            assert_eq!(index.line_count(), end.line + 1);
+        }
+    }
+
+    #[test]
+    fn test_line_index_mid_char() {
+        let text = "🗻∈\n🌏";
+
+        let index = LineIndex::new(text);
+        for pos in 0..=(text.len() + 2) {
+            let expected = text
+                .char_ranges()
+                .char_positions()
+                .find(|(r, _, _)| r.start == pos)
+                .map(|(_, pos, _)| pos);
+            if pos == text.len() {
+                assert_eq!(index.line_col(pos), Some(LineColumn::new(1, 1)));
+            } else {
+                assert_eq!(index.line_col(pos), expected, "{pos}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_line_index_lines() {
+        let text = "foo\r\n\nbar\n";
+
+        let index = LineIndex::new(text);
+        assert_eq!(index.line_count(), 4);
+        assert_eq!(index.line_range(0), Some(0..5));
+        assert_eq!(index.line_range(1), Some(5..6));
+        assert_eq!(index.line_range(2), Some(6..10));
+        assert_eq!(index.line_range(3), Some(10..10));
+        assert_eq!(index.line_range(4), None);
+
+        assert_eq!(index.offset(0, 3), Some(3));
+        assert_eq!(index.offset(0, 4), Some(4));
+        // Past the end of a line
+        assert_eq!(index.offset(0, 5), None);
+        assert_eq!(index.offset(1, 1), None);
+        assert_eq!(index.offset(3, 0), Some(10));
+        assert_eq!(index.offset(3, 1), None);
+        assert_eq!(index.offset(4, 0), None);
+
+        let index = LineIndex::new("");
+        assert_eq!(index.line_count(), 1);
+        assert_eq!(index.line_range(0), Some(0..0));
+        assert_eq!(index.line_col(0), Some(LineColumn::new(0, 0)));
+        assert_eq!(index.offset(0, 0), Some(0));
+    }
+
+    fn assert_same_index(index: &LineIndexMut) {
+        let expected = LineIndex::new(index.text());
+        assert_eq!(
+            index.line_starts,
+            expected.line_starts,
+            "{:?}",
+            index.text()
+        );
+        assert_eq!(index.to_line_index().line_starts, expected.line_starts);
+    }
+
+    #[test]
+    fn test_line_index_mut_edits() {
+        let mut index = LineIndexMut::new("foo\r\nbar\nbaz");
+
+        // Multi-line insertion
+        index.apply_edit(3..3, "\n1\n2");
+        assert_eq!(index.text(), "foo\n1\n2\r\nbar\nbaz");
+        assert_same_index(&index);
+
+        // Deletion merging lines
+        index.apply_edit(3..9, "");
+        assert_eq!(index.text(), "foobar\nbaz");
+        assert_same_index(&index);
+
+        // Forming CRLF across the edit boundary
+        index.apply_edit(6..6, "\r");
+        assert_eq!(index.text(), "foobar\r\nbaz");
+        assert_same_index(&index);
+
+        // Splitting CRLF
+        index.apply_edit(7..7, "🗻");
+        assert_eq!(index.text(), "foobar\r🗻\nbaz");
+        assert_same_index(&index);
+
+        // At EOF
+        index.apply_edit(15..15, "\n");
+        assert_eq!(index.text(), "foobar\r🗻\nbaz\n");
+        assert_eq!(index.line_count(), 3);
+        assert_same_index(&index);
+
+        index.apply_edit(12..16, "");
+        assert_eq!(index.text(), "foobar\r🗻\n");
+        assert_eq!(index.line_count(), 2);
+        assert_same_index(&index);
+
+        // Replacing everything
+        index.apply_edit(0..12, "");
+        assert_eq!(index.line_count(), 1);
+        assert_eq!(index.line_col(0), Some(LineColumn::new(0, 0)));
+        assert_same_index(&index);
+    }
+
+    #[test]
+    fn test_line_index_mut_random_edits() {
+        let mut random = crate::test_util::random();
+
+        const FRAGMENTS: &[&str] = &["", "a", "\n", "\r", "\r\n", "🗻", "\n\n", "b\nc", "∈\r"];
+
+        for _ in 0..50 {
+            let mut index = LineIndexMut::new("");
+            for _ in 0..100 {
+                let text = index.text();
+                let start = random() % (text.len() + 1);
+                let start = CharRangesExt::floor_char_boundary(text, start);
+                let end = (start + random() % 4).min(text.len());
+                let end = CharRangesExt::ceil_char_boundary(text, end);
+
+                let mut replacement = String::new();
+                for _ in 0..(random() % 3) {
+                    replacement.push_str(FRAGMENTS[random() % FRAGMENTS.len()]);
+                }
+
+                let mut expected = String::from(text);
+                expected.replace_range(start..end, &replacement);
+
+                index.apply_edit(start..end, &replacement);
+                assert_eq!(index.text(), expected);
+                assert_same_index(&index);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_line_index_mut_edit_out_of_bounds() {
+        let mut index = LineIndexMut::new("foo\n");
+        index.apply_edit(2..5, "");
+    }
+
+    #[test]
+    fn test_line_index_units() {
+        // Columns after a 4 byte emoji
+        let text = "🗻a\n🌏";
+
+        #[rustfmt::skip]
+        let expected = [
+            (Base::Zero, ColumnUnit::Chars, (0, 1), (1, 1)),
+            (Base::Zero, ColumnUnit::Bytes, (0, 4), (1, 4)),
+            (Base::Zero, ColumnUnit::Utf16, (0, 2), (1, 2)),
+            (Base::One,  ColumnUnit::Chars, (1, 2), (2, 2)),
+            (Base::One,  ColumnUnit::Bytes, (1, 5), (2, 5)),
+            (Base::One,  ColumnUnit::Utf16, (1, 3), (2, 3)),
+        ];
+
+        for (base, unit, (line, column), (end_line, end_column)) in expected {
+            let index = LineIndex::new(text).with_base(base).with_column_unit(unit);
+            assert_eq!(index.base(), base);
+            assert_eq!(index.column_unit(), unit);
+
+            assert_eq!(index.line_col(4), Some(LineColumn::new(line, column)));
+            assert_eq!(index.offset(line, column), Some(4));
+            let end = LineColumn::new(end_line, end_column);
+            assert_eq!(index.line_col(text.len()), Some(end));
+            assert_eq!(index.offset(end_line, end_column), Some(text.len()));
+
+            // Within '🗻'
+            if unit != ColumnUnit::Chars {
+                assert_eq!(index.offset(line, column - 1), None);
+            }
+            // Before the first line and column
+            if base == Base::One {
+                assert_eq!(index.offset(0, 1), None);
+                assert_eq!(index.offset(1, 0), None);
+            }
+
+            // Same as `char_positions()`
+            let chars = text
+                .char_ranges()
+                .char_positions()
+                .with_base(base)
+                .with_column_unit(unit);
+            for (r, pos, _c) in chars {
+                assert_eq!(index.line_col(r.start), Some(pos));
+                assert_eq!(index.offset(pos.line, pos.column), Some(r.start));
+            }
+        }
+    }
+
+    #[test]
+    fn test_line_index_convert() {
+        let text = "🗻∈🌏\n\n¢a\r\nHello 👋 World 🌏\n";
+
+        let configs = [Base::Zero, Base::One].into_iter().flat_map(|base| {
+            [ColumnUnit::Chars, ColumnUnit::Bytes, ColumnUnit::Utf16].map(|unit| (base, unit))
+        });
+        for (from_base, from_unit) in configs.clone() {
+            let from = LineIndex::new(text)
+                .with_base(from_base)
+                .with_column_unit(from_unit);
+            for (to_base, to_unit) in configs.clone() {
+                let to = LineIndex::new(text)
+                    .with_base(to_base)
+                    .with_column_unit(to_unit);
+                for (r, _c) in text.char_ranges() {
+                    let pos = from.line_col(r.start).unwrap();
+                    assert_eq!(from.convert(pos, to_base, to_unit), to.line_col(r.start));
+                }
+            }
+        }
+
+        let index = LineIndex::new(text);
+        assert_eq!(
+            index.convert(LineColumn::new(9, 0), Base::One, ColumnUnit::Bytes),
+            None
+        );
+    }
+}
@@ -0,0 +1,342 @@
+use core::ops::Range;
+use core::str::{CharIndices, Chars};
+
+use nom::{Compare, CompareResult, FindSubstring, Input, Needed, Offset};
+
+use crate::{CharRanges, CharRangesExt, CharRangesOffset};
+
+/// A [`nom`] input, which is a `&str` along with the `offset` of its first
+/// byte in the whole text.
+///
+/// Slicing keeps the `offset` consistent, such that the span of any input
+/// can be recovered as byte positions in the whole text, e.g. using
+/// [`range()`](Self::range) or [`span_since()`](Self::span_since).
+///
+/// The items are [`char`]s, same as `&str`, so all of nom's character
+/// parsers can be used.
+///
+/// # Example
+///
+/// ```rust
+/// use std::ops::Range;
+///
+/// use char_ranges::Located;
+/// use nom::branch::alt;
+/// use nom::bytes::complete::{tag, take_while1};
+/// use nom::character::complete::{digit1, multispace0};
+/// use nom::combinator::value;
+/// use nom::{IResult, Parser};
+///
+/// #[derive(Clone, Copy, PartialEq, Debug)]
+/// enum Token {
+///     Ident,
+///     Number,
+///     Eq,
+///     Semi,
+/// }
+///
+/// fn token(input: Located<'_>) -> IResult<Located<'_>, (Token, Range<usize>)> {
+///     let (input, _) = multispace0(input)?;
+///     let start = input;
+///     let (input, token) = alt((
+///         value(Token::Ident, take_while1(char::is_alphabetic)),
+///         value(Token::Number, digit1),
+///         value(Token::Eq, tag("=")),
+///         value(Token::Semi, tag(";")),
+///     ))
+///     .parse(input)?;
+///     Ok((input, (token, input.span_since(&start))))
+/// }
+///
+/// let text = "let π = 314;";
+///
+/// let mut input = Located::new(text);
+/// let mut tokens = Vec::new();
+/// while let Ok((rest, token)) = token(input) {
+///     tokens.push(token);
+///     input = rest;
+/// }
+///
+/// assert_eq!(
+///     tokens,
+///     [
+///         (Token::Ident, 0..3),
+///         (Token::Ident, 4..6),
+///         (Token::Eq, 7..8),
+///         (Token::Number, 9..12),
+///         (Token::Semi, 12..13),
+///     ],
+/// );
+/// assert_eq!(&text[4..6], "π");
+/// ```
+///
+/// [`nom`]: https://docs.rs/nom
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Located<'a> {
+    text: &'a str,
+    offset: usize,
+}
+
+impl<'a> Located<'a> {
+    /// Creates an input where `text` is the whole text.
+    #[inline]
+    pub const fn new(text: &'a str) -> Self {
+        Self::with_offset(text, 0)
+    }
+
+    /// Creates an input where `text` starts at the byte position `offset`
+    /// in the whole text.
+    #[inline]
+    pub const fn with_offset(text: &'a str, offset: usize) -> Self {
+        Self { text, offset }
+    }
+
+    /// Returns the remaining text.
+    #[inline]
+    pub const fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    /// Returns the byte position of the start of the remaining text,
+    /// in the whole text.
+    #[inline]
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the start and end byte positions of the remaining text,
+    /// in the whole text.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `offset` plus the length of the remaining text
+    /// overflows `usize`.
+    #[inline]
+    pub fn range(&self) -> Range<usize> {
+        self.offset..self.apply_offset_to(self.text.len())
+    }
+
+    /// Returns the start and end byte positions spanning from `start` up
+    /// to `self`, i.e. the part of the text consumed since `start`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` is after `self`.
+    #[inline]
+    pub fn span_since(&self, start: &Located<'a>) -> Range<usize> {
+        assert!(
+            start.offset <= self.offset,
+            "start {} is after {}",
+            start.offset,
+            self.offset,
+        );
+        start.offset..self.offset
+    }
+
+    /// Returns an iterator over the remaining [`char`]s and their start
+    /// and end byte positions in the whole text.
+    #[inline]
+    pub fn char_ranges(&self) -> CharRangesOffset<'a> {
+        self.text.char_ranges_offset(self.offset)
+    }
+
+    /// Applies the offset to a position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position overflows `usize`, regardless of
+    /// whether overflow checks are enabled.
+    #[inline]
+    fn apply_offset_to(&self, pos: usize) -> usize {
+        match pos.checked_add(self.offset) {
+            Some(pos) => pos,
+            None => panic!("position {pos} with offset {} overflows usize", self.offset),
+        }
+    }
+
+    #[inline]
+    fn slice(&self, r: Range<usize>) -> Self {
+        Self::with_offset(&self.text[r.clone()], self.apply_offset_to(r.start))
+    }
+}
+
+impl<'a> From<&'a str> for Located<'a> {
+    #[inline]
+    fn from(text: &'a str) -> Self {
+        Self::new(text)
+    }
+}
+
+impl<'a> From<CharRanges<'a>> for Located<'a> {
+    /// Creates an input of the remaining text, where the `offset` is the
+    /// start byte position of the remaining text.
+    #[inline]
+    fn from(iter: CharRanges<'a>) -> Self {
+        Self::with_offset(iter.as_str(), iter.start)
+    }
+}
+
+impl<'a> From<CharRangesOffset<'a>> for Located<'a> {
+    /// Creates an input of the remaining text, where the `offset` is the
+    /// start byte position of the remaining text, with the offset applied.
+    #[inline]
+    fn from(iter: CharRangesOffset<'a>) -> Self {
+        let offset = iter.apply_offset_to(iter.iter.start);
+        Self::with_offset(iter.as_str(), offset)
+    }
+}
+
+impl<'a> Input for Located<'a> {
+    type Item = char;
+    type Iter = Chars<'a>;
+    type IterIndices = CharIndices<'a>;
+
+    #[inline]
+    fn input_len(&self) -> usize {
+        self.text.len()
+    }
+
+    #[inline]
+    fn take(&self, index: usize) -> Self {
+        self.slice(0..index)
+    }
+
+    #[inline]
+    fn take_from(&self, index: usize) -> Self {
+        self.slice(index..self.text.len())
+    }
+
+    #[inline]
+    fn take_split(&self, index: usize) -> (Self, Self) {
+        (self.take_from(index), self.take(index))
+    }
+
+    #[inline]
+    fn position<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: Fn(Self::Item) -> bool,
+    {
+        self.text.find(predicate)
+    }
+
+    #[inline]
+    fn iter_elements(&self) -> Self::Iter {
+        self.text.chars()
+    }
+
+    #[inline]
+    fn iter_indices(&self) -> Self::IterIndices {
+        self.text.char_indices()
+    }
+
+    #[inline]
+    fn slice_index(&self, count: usize) -> Result<usize, Needed> {
+        self.text.slice_index(count)
+    }
+}
+
+impl Offset for Located<'_> {
+    #[inline]
+    fn offset(&self, second: &Self) -> usize {
+        second.offset - self.offset
+    }
+}
+
+impl<'b> Compare<&'b str> for Located<'_> {
+    #[inline]
+    fn compare(&self, t: &'b str) -> CompareResult {
+        self.text.compare(t)
+    }
+
+    #[inline]
+    fn compare_no_case(&self, t: &'b str) -> CompareResult {
+        self.text.compare_no_case(t)
+    }
+}
+
+impl<'b> FindSubstring<&'b str> for Located<'_> {
+    #[inline]
+    fn find_substring(&self, substr: &'b str) -> Option<usize> {
+        self.text.find_substring(substr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::bytes::complete::{tag, take_until};
+    use nom::character::complete::{alpha1, char, multispace1};
+    use nom::combinator::recognize;
+    use nom::sequence::{delimited, pair};
+    use nom::{IResult, Input, Offset, Parser};
+
+    use super::Located;
+    use crate::CharRangesExt;
+
+    #[test]
+    fn test_located_slicing() {
+        let input = Located::with_offset("🗻∈🌏", 10);
+        assert_eq!(input.range(), 10..21);
+
+        let (rest, first) = input.take_split(4);
+        assert_eq!(first.as_str(), "🗻");
+        assert_eq!(first.range(), 10..14);
+        assert_eq!(rest.as_str(), "∈🌏");
+        assert_eq!(rest.range(), 14..21);
+        assert_eq!(rest.span_since(&input), 10..14);
+        assert_eq!(Offset::offset(&input, &rest), 4);
+
+        assert_eq!(input.slice_index(2), Ok(7));
+        assert_eq!(rest.take(3).range(), 14..17);
+
+        // Ranges of chars are in the whole text
+        assert!(rest.char_ranges().eq([(14..17, '∈'), (17..21, '🌏')]));
+    }
+
+    #[test]
+    #[should_panic(expected = "position 2 with offset 18446744073709551614 overflows usize")]
+    fn test_located_offset_overflow() {
+        let input = Located::with_offset("ab", usize::MAX - 1);
+        input.range();
+    }
+
+    #[test]
+    fn test_located_parse() {
+        fn quoted(input: Located<'_>) -> IResult<Located<'_>, Located<'_>> {
+            delimited(char('"'), take_until("\""), char('"')).parse(input)
+        }
+
+        let text = "say \"🗻 ∈ 🌏\" to world";
+        let input = Located::new(text);
+
+        let result: IResult<_, _> = recognize(pair(alpha1, multispace1)).parse(input);
+        let (input, word) = result.unwrap();
+        assert_eq!(word.range(), 0..4);
+
+        let (input, quote) = quoted(input).unwrap();
+        assert_eq!(quote.as_str(), "🗻 ∈ 🌏");
+        assert_eq!(quote.range(), 5..18);
+        assert_eq!(&text[quote.range()], quote.as_str());
+
+        let result: IResult<_, _> = tag(" to ").parse(input);
+        let (input, _) = result.unwrap();
+        assert_eq!(input.range(), 23..28);
+        assert_eq!(input.as_str(), "world");
+    }
+
+    #[test]
+    fn test_located_from_char_ranges() {
+        let text = "Hello 👋 World 🌏";
+
+        let mut chars = text.char_ranges_offset(100);
+        chars.nth(6);
+        let input = Located::from(chars);
+        assert_eq!(input.range(), 110..(100 + text.len()));
+        assert_eq!(input.as_str(), " World 🌏");
+
+        let mut chars = text.char_ranges();
+        chars.next_back();
+        chars.nth(6);
+        let input = Located::from(chars);
+        assert_eq!(input.range(), 10..17);
+    }
+}
@@ -0,0 +1,200 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::CharRangesOffset;
+
+/// An iterator over the lines of a string, which are longer than a
+/// maximum number of [`char`]s, producing the zero-based line number,
+/// the start and end byte positions of the line, the start and end byte
+/// positions of the overflow, and the number of [`char`]s in the line.
+///
+/// Lines are split like [`str::lines()`], i.e. at `'\n'` and `"\r\n"`,
+/// where the terminator is never included in the ranges or the count.
+/// The overflow starts after the `max_chars`-th [`char`], and ends at
+/// the end of the line. Lines of exactly `max_chars` [`char`]s are not
+/// produced.
+///
+/// Lengths are counted in [`char`]s, not in columns, i.e. a `'\t'` counts
+/// as a single [`char`], as does a wide or zero-width [`char`].
+///
+/// Line numbers are relative to the start of the remaining substring,
+/// when the iterator is created.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRanges::long_line_ranges()`] for more information.
+///
+/// [`CharRanges::long_line_ranges()`]: crate::CharRanges::long_line_ranges
+#[derive(Clone)]
+pub struct LongLineRanges<'a> {
+    iter: CharRangesOffset<'a>,
+    max_chars: usize,
+    /// The line number of the next line.
+    line: usize,
+}
+
+impl<'a> LongLineRanges<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>, max_chars: usize) -> Self {
+        Self {
+            iter,
+            max_chars,
+            line: 0,
+        }
+    }
+
+    /// Returns the maximum number of [`char`]s per line.
+    #[inline]
+    pub fn max_chars(&self) -> usize {
+        self.max_chars
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+}
+
+impl Iterator for LongLineRanges<'_> {
+    type Item = (usize, Range<usize>, Range<usize>, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let rest = self.iter.as_str();
+            if rest.is_empty() {
+                return None;
+            }
+
+            let start = self.iter.iter.start;
+            let (line, next) = match rest.find('\n') {
+                Some(i) => {
+                    let line = &rest[..i];
+                    (line.strip_suffix('\r').unwrap_or(line), start + i + 1)
+                }
+                None => (rest, self.iter.iter.end),
+            };
+            self.iter.iter.start = next;
+
+            let n = self.line;
+            self.line += 1;
+
+            if let Some((i, _c)) = line.char_indices().nth(self.max_chars) {
+                let count = self.max_chars + line[i..].chars().count();
+                let end = start + line.len();
+                let r = self.iter.apply_offset(start..end);
+                let overflow = self.iter.apply_offset((start + i)..end);
+                return Some((n, r, overflow, count));
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.iter.as_str().len()))
+    }
+}
+
+impl FusedIterator for LongLineRanges<'_> {}
+
+impl fmt::Debug for LongLineRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "LongLineRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use crate::CharRangesExt;
+
+    #[test]
+    fn test_long_line_ranges() {
+        let text = "short\nthis is too long\nexactly10\n";
+
+        let ranges = text.char_ranges().long_line_ranges(10).collect::<Vec<_>>();
+        assert_eq!(ranges, [(1, 6..22, 16..22, 16)]);
+        assert_eq!(&text[16..22], "o long");
+
+        // Lines at exactly the limit are not reported
+        let text = "0123456789\n01234567890";
+        let ranges = text.char_ranges().long_line_ranges(10);
+        assert!(ranges.eq([(1, 11..22, 21..22, 11)]));
+
+        assert_eq!("".char_ranges().long_line_ranges(0).next(), None);
+        assert!("a\n\nb"
+            .char_ranges()
+            .long_line_ranges(0)
+            .eq([(0, 0..1, 0..1, 1), (2, 3..4, 3..4, 1)]));
+    }
+
+    #[test]
+    fn test_long_line_ranges_multi_byte() {
+        // The limit falls immediately before a 4 byte char
+        let text = "abc🌏d";
+
+        let ranges = text.char_ranges().long_line_ranges(3).collect::<Vec<_>>();
+        assert_eq!(ranges, [(0, 0..8, 3..8, 5)]);
+        assert_eq!(&text[3..8], "🌏d");
+
+        let ranges = text.char_ranges().long_line_ranges(4).collect::<Vec<_>>();
+        assert_eq!(ranges, [(0, 0..8, 7..8, 5)]);
+
+        assert_eq!(text.char_ranges().long_line_ranges(5).next(), None);
+    }
+
+    #[test]
+    fn test_long_line_ranges_tabs() {
+        // A tab counts as a single char
+        let text = "\t\tfoo\n\t\t\tfoo";
+
+        let ranges = text.char_ranges().long_line_ranges(5).collect::<Vec<_>>();
+        assert_eq!(ranges, [(1, 6..12, 11..12, 6)]);
+    }
+
+    #[test]
+    fn test_long_line_ranges_crlf() {
+        // The terminator is never included
+        let text = "abcd\r\nabc\r\nabcde\r\n";
+
+        let ranges = text.char_ranges().long_line_ranges(3).collect::<Vec<_>>();
+        assert_eq!(ranges, [(0, 0..4, 3..4, 4), (2, 11..16, 14..16, 5)]);
+
+        // A lone '\r' is not a terminator
+        let text = "ab\rc";
+        let ranges = text.char_ranges().long_line_ranges(3);
+        assert!(ranges.eq([(0, 0..4, 3..4, 4)]));
+    }
+
+    #[test]
+    fn test_long_line_ranges_unterminated() {
+        let text = "ok\nthe final line";
+
+        let ranges = text.char_ranges().long_line_ranges(4).collect::<Vec<_>>();
+        assert_eq!(ranges, [(1, 3..17, 7..17, 14)]);
+        assert_eq!(&text[7..17], "final line");
+    }
+
+    #[test]
+    fn test_long_line_ranges_offset() {
+        let text = "xx\nyyy\nzzzz";
+
+        let ranges = text[3..].char_ranges_offset(3).long_line_ranges(2);
+        assert_eq!(ranges.offset(), 3);
+        assert_eq!(ranges.max_chars(), 2);
+        assert!(ranges.eq([(0, 3..6, 5..6, 3), (1, 7..11, 9..11, 4)]));
+    }
+}
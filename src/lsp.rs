@@ -0,0 +1,181 @@
+use core::ops::Range;
+
+use lsp_types::Position;
+
+use crate::line_index::{byte_of_column, column_len};
+use crate::ColumnUnit;
+
+/// Returns the LSP [`Position`] of the byte position `pos` in `text`,
+/// i.e. its zero-based line, and zero-based column in UTF-16 code units.
+///
+/// Only `'\n'` starts a new line, i.e. the same as [`LineIndex`], so in
+/// `"\r\n"` the `'\r'` is the last column of its line.
+///
+/// Returns `None` if `pos` is out of bounds or not on a [`char`] boundary,
+/// or if the line or column overflows `u32`.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::to_lsp_position;
+/// use lsp_types::Position;
+///
+/// let text = "foo\n🗻bar";
+///
+/// assert_eq!(to_lsp_position(text, 8), Some(Position::new(1, 2)));
+/// assert_eq!(to_lsp_position(text, 11), Some(Position::new(1, 5)));
+///
+/// // Within '🗻'
+/// assert_eq!(to_lsp_position(text, 5), None);
+/// ```
+///
+/// [`LineIndex`]: crate::LineIndex
+pub fn to_lsp_position(text: &str, pos: usize) -> Option<Position> {
+    if !text.is_char_boundary(pos) {
+        return None;
+    }
+
+    let before = &text[..pos];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let line = before[..line_start].bytes().filter(|&b| b == b'\n').count();
+    let column = column_len(&before[line_start..], ColumnUnit::Utf16);
+
+    Some(Position::new(
+        u32::try_from(line).ok()?,
+        u32::try_from(column).ok()?,
+    ))
+}
+
+/// Returns the byte position in `text` of the LSP [`Position`] `pos`,
+/// i.e. the inverse of [`to_lsp_position()`].
+///
+/// Out of bounds positions are clamped, as specified by the Language
+/// Server Protocol:
+///
+/// - A column past the end of its line is the end of the line, i.e.
+///   the position of its `'\n'`, or the end of the text for the last line.
+/// - A line past the last line is the end of the text.
+///
+/// Returns `None` if the column is within a [`char`], i.e. between the
+/// two UTF-16 code units of a surrogate pair.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::from_lsp_position;
+/// use lsp_types::Position;
+///
+/// let text = "foo\n🗻bar";
+///
+/// assert_eq!(from_lsp_position(text, Position::new(1, 2)), Some(8));
+///
+/// // Past the end of the first line
+/// assert_eq!(from_lsp_position(text, Position::new(0, 10)), Some(3));
+/// // Past the last line
+/// assert_eq!(from_lsp_position(text, Position::new(5, 0)), Some(11));
+///
+/// // Within '🗻'
+/// assert_eq!(from_lsp_position(text, Position::new(1, 1)), None);
+/// ```
+pub fn from_lsp_position(text: &str, pos: Position) -> Option<usize> {
+    let line_start = match pos.line {
+        0 => 0,
+        line => match text.match_indices('\n').nth(line as usize - 1) {
+            Some((i, _)) => i + 1,
+            None => return Some(text.len()),
+        },
+    };
+    let line_end = text[line_start..]
+        .find('\n')
+        .map_or(text.len(), |i| line_start + i);
+    let line = &text[line_start..line_end];
+
+    let column = pos.character as usize;
+    if column >= column_len(line, ColumnUnit::Utf16) {
+        return Some(line_end);
+    }
+    byte_of_column(line, column, ColumnUnit::Utf16).map(|i| line_start + i)
+}
+
+/// Returns the LSP [`Range`](lsp_types::Range) of the byte range `range`
+/// in `text`.
+///
+/// Returns `None` if either position is invalid, see [`to_lsp_position()`].
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::to_lsp_range;
+/// use lsp_types::{Position, Range};
+///
+/// let text = "foo\n🗻bar";
+///
+/// let range = to_lsp_range(text, 2..8).unwrap();
+/// assert_eq!(range, Range::new(Position::new(0, 2), Position::new(1, 2)));
+/// ```
+#[inline]
+pub fn to_lsp_range(text: &str, range: Range<usize>) -> Option<lsp_types::Range> {
+    let start = to_lsp_position(text, range.start)?;
+    let end = to_lsp_position(text, range.end)?;
+    Some(lsp_types::Range::new(start, end))
+}
+
+/// Returns the byte range in `text` of the LSP [`Range`](lsp_types::Range)
+/// `range`, with both positions clamped as specified by the Language Server
+/// Protocol, see [`from_lsp_position()`].
+///
+/// Returns `None` if either position is within a [`char`], or if the
+/// start is after the end.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::from_lsp_range;
+/// use lsp_types::{Position, Range};
+///
+/// let text = "foo\n🗻bar";
+///
+/// let range = Range::new(Position::new(0, 2), Position::new(1, 2));
+/// assert_eq!(from_lsp_range(text, range), Some(2..8));
+///
+/// // Past the end of the text
+/// let range = Range::new(Position::new(1, 3), Position::new(9, 9));
+/// assert_eq!(from_lsp_range(text, range), Some(9..11));
+/// ```
+#[inline]
+pub fn from_lsp_range(text: &str, range: lsp_types::Range) -> Option<Range<usize>> {
+    let start = from_lsp_position(text, range.start)?;
+    let end = from_lsp_position(text, range.end)?;
+    (start <= end).then_some(start..end)
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_types::Position;
+
+    use super::{from_lsp_position, to_lsp_position};
+
+    #[test]
+    fn test_lsp_position_clamping() {
+        let text = "a🗻\r\n\nbc";
+
+        // Past the end of a line is the position of its '\n'
+        assert_eq!(from_lsp_position(text, Position::new(0, 3)), Some(5));
+        assert_eq!(from_lsp_position(text, Position::new(0, 4)), Some(6));
+        assert_eq!(from_lsp_position(text, Position::new(0, 100)), Some(6));
+        assert_eq!(from_lsp_position(text, Position::new(1, 1)), Some(7));
+        assert_eq!(from_lsp_position(text, Position::new(2, 3)), Some(10));
+        assert_eq!(from_lsp_position(text, Position::new(3, 0)), Some(10));
+        assert_eq!(
+            from_lsp_position(text, Position::new(u32::MAX, u32::MAX)),
+            Some(10)
+        );
+
+        assert_eq!(from_lsp_position(text, Position::new(0, 2)), None);
+
+        assert_eq!(from_lsp_position("", Position::new(0, 0)), Some(0));
+        assert_eq!(from_lsp_position("", Position::new(1, 1)), Some(0));
+        assert_eq!(to_lsp_position("", 0), Some(Position::new(0, 0)));
+        assert_eq!(to_lsp_position("", 1), None);
+    }
+}
@@ -0,0 +1,42 @@
+/// A saved front position of a [`CharRanges`], for extracting the text
+/// consumed since then, e.g. the text of a token in a lexer.
+///
+/// A [`Mark`] remembers the text it was taken from, by address and
+/// length, and can only be used with iterators over the same text.
+///
+/// See [`CharRanges::mark()`] for more information.
+///
+/// [`CharRanges`]: crate::CharRanges
+/// [`CharRanges::mark()`]: crate::CharRanges::mark
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Mark {
+    /// Front byte position, without any offset applied.
+    pub(crate) pos: usize,
+    /// Address and length of the original text.
+    source: (usize, usize),
+}
+
+impl Mark {
+    #[inline]
+    pub(crate) fn new(text: &str, pos: usize) -> Self {
+        Self {
+            pos,
+            source: source_of(text),
+        }
+    }
+
+    /// Panics if this mark was not taken from an iterator over `text`.
+    #[inline]
+    #[track_caller]
+    pub(crate) fn assert_source(&self, text: &str) {
+        if self.source != source_of(text) {
+            panic!("mark was taken from an iterator over a different text");
+        }
+    }
+}
+
+#[inline]
+fn source_of(text: &str) -> (usize, usize) {
+    (text.as_ptr() as usize, text.len())
+}
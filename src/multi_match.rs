@@ -0,0 +1,373 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use aho_corasick::{AhoCorasick, BuildError, FindIter, MatchKind};
+
+/// How [`MultiMatcher`] chooses between patterns matching at the same
+/// position.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum MultiMatchKind {
+    /// The pattern which comes first in the list of patterns is preferred,
+    /// i.e. like alternations in a regex.
+    #[default]
+    LeftmostFirst,
+    /// The longest pattern is preferred.
+    LeftmostLongest,
+}
+
+impl MultiMatchKind {
+    #[inline]
+    fn to_match_kind(self) -> MatchKind {
+        match self {
+            Self::LeftmostFirst => MatchKind::LeftmostFirst,
+            Self::LeftmostLongest => MatchKind::LeftmostLongest,
+        }
+    }
+}
+
+/// A matcher for multiple patterns at once, which finds all
+/// non-overlapping matches in a single pass over the text, using
+/// the [`aho-corasick`] crate.
+///
+/// Matches are found left to right, where patterns matching at the same
+/// position are chosen according to the [`MultiMatchKind`], which
+/// defaults to [`MultiMatchKind::LeftmostFirst`].
+///
+/// Since both the patterns and the text are valid UTF-8, all matches of
+/// non-empty patterns are on [`char`] boundaries. Empty patterns match
+/// at every byte position, where matches within a [`char`] are skipped,
+/// i.e. they only match at [`char`] boundaries.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::{MultiMatchKind, MultiMatcher};
+///
+/// let text = "let mut foo = let_foo;";
+///
+/// let matcher = MultiMatcher::new(&["let", "mut", "let_foo"]).unwrap();
+/// let mut matches = matcher.find_ranges(text);
+/// assert_eq!(matches.next(), Some((0, 0..3, "let")));
+/// assert_eq!(matches.next(), Some((1, 4..7, "mut")));
+/// assert_eq!(matches.next(), Some((0, 14..17, "let")));
+/// assert_eq!(matches.next(), None);
+///
+/// let matcher =
+///     MultiMatcher::with_match_kind(&["let", "mut", "let_foo"], MultiMatchKind::LeftmostLongest)
+///         .unwrap();
+/// let mut matches = matcher.find_ranges(text).skip(2);
+/// assert_eq!(matches.next(), Some((2, 14..21, "let_foo")));
+/// ```
+///
+/// [`aho-corasick`]: https://docs.rs/aho-corasick
+#[derive(Clone, Debug)]
+pub struct MultiMatcher {
+    ac: AhoCorasick,
+    kind: MultiMatchKind,
+}
+
+impl MultiMatcher {
+    /// Creates a matcher for `patterns`, using
+    /// [`MultiMatchKind::LeftmostFirst`].
+    ///
+    /// Returns an error if the automaton is too large, e.g. for
+    /// an excessive number of patterns.
+    #[inline]
+    pub fn new(patterns: &[&str]) -> Result<Self, BuildError> {
+        Self::with_match_kind(patterns, MultiMatchKind::default())
+    }
+
+    /// Creates a matcher for `patterns`, using the match `kind`.
+    ///
+    /// Returns an error if the automaton is too large, e.g. for
+    /// an excessive number of patterns.
+    pub fn with_match_kind(patterns: &[&str], kind: MultiMatchKind) -> Result<Self, BuildError> {
+        let ac = AhoCorasick::builder()
+            .match_kind(kind.to_match_kind())
+            .build(patterns)?;
+        Ok(Self { ac, kind })
+    }
+
+    /// Returns the match kind.
+    #[inline]
+    pub fn match_kind(&self) -> MultiMatchKind {
+        self.kind
+    }
+
+    /// Returns the number of patterns.
+    #[inline]
+    pub fn pattern_count(&self) -> usize {
+        self.ac.patterns_len()
+    }
+
+    /// Returns an iterator over the non-overlapping matches in `text`,
+    /// producing the index of the matching pattern, the start and end
+    /// byte positions of the match, and the matched substring.
+    #[inline]
+    pub fn find_ranges<'a>(&self, text: &'a str) -> MultiMatchRanges<'a, '_> {
+        self.find_ranges_offset(text, 0)
+    }
+
+    /// Returns an iterator over the non-overlapping matches in `text`,
+    /// producing the index of the matching pattern, the start and end
+    /// byte positions of the match, with `offset` applied, and the
+    /// matched substring.
+    ///
+    /// # Panics
+    ///
+    /// If `offset` plus the length of `text` overflows `usize`, then the
+    /// returned iterator panics when producing a position that overflows,
+    /// in both debug and release builds.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::MultiMatcher;
+    ///
+    /// let text = "fn 🌏() { 🌏 }";
+    ///
+    /// let matcher = MultiMatcher::new(&["🌏", "fn"]).unwrap();
+    /// let start = 9;
+    /// let mut matches = matcher.find_ranges_offset(&text[start..], start);
+    /// assert_eq!(matches.next(), Some((0, 12..16, "🌏")));
+    /// assert_eq!(&text[12..16], "🌏");
+    /// assert_eq!(matches.next(), None);
+    /// ```
+    #[inline]
+    pub fn find_ranges_offset<'a>(&self, text: &'a str, offset: usize) -> MultiMatchRanges<'a, '_> {
+        MultiMatchRanges {
+            iter: self.ac.find_iter(text),
+            text,
+            offset,
+        }
+    }
+}
+
+/// An iterator over the non-overlapping matches of a [`MultiMatcher`],
+/// producing the index of the matching pattern, the start and end byte
+/// positions of the match, and the matched substring.
+///
+/// See [`MultiMatcher::find_ranges()`] for more information.
+pub struct MultiMatchRanges<'a, 'b> {
+    iter: FindIter<'b, 'a>,
+    text: &'a str,
+    offset: usize,
+}
+
+impl<'a> MultiMatchRanges<'a, '_> {
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Applies the offset to a position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position overflows `usize`, regardless of
+    /// whether overflow checks are enabled.
+    #[inline]
+    fn apply_offset_to(&self, pos: usize) -> usize {
+        match pos.checked_add(self.offset) {
+            Some(pos) => pos,
+            None => panic!("position {pos} with offset {} overflows usize", self.offset),
+        }
+    }
+}
+
+impl<'a> Iterator for MultiMatchRanges<'a, '_> {
+    type Item = (usize, Range<usize>, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let m = self.iter.next()?;
+            // Only empty matches can be within a char
+            if !self.text.is_char_boundary(m.start()) {
+                continue;
+            }
+
+            let s = &self.text[m.range()];
+            let r = self.apply_offset_to(m.start())..self.apply_offset_to(m.end());
+            return Some((m.pattern().as_usize(), r, s));
+        }
+    }
+}
+
+impl FusedIterator for MultiMatchRanges<'_, '_> {}
+
+impl fmt::Debug for MultiMatchRanges<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MultiMatchRanges")
+            .field("text", &self.text)
+            .field("offset", &self.offset)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::String;
+    use std::vec::Vec;
+
+    use super::{MultiMatchKind, MultiMatcher};
+    use crate::CharRangesExt;
+
+    #[test]
+    fn test_multi_matcher() {
+        let text = "if x { return y; } else { return z; }";
+
+        let matcher = MultiMatcher::new(&["if", "else", "return"]).unwrap();
+        assert_eq!(matcher.pattern_count(), 3);
+        assert_eq!(matcher.match_kind(), MultiMatchKind::LeftmostFirst);
+
+        let matches = matcher.find_ranges(text).collect::<Vec<_>>();
+        assert_eq!(
+            matches,
+            [
+                (0, 0..2, "if"),
+                (2, 7..13, "return"),
+                (1, 19..23, "else"),
+                (2, 26..32, "return"),
+            ]
+        );
+
+        assert_eq!(matcher.find_ranges("").next(), None);
+        assert_eq!(
+            matcher.find_ranges("iff eels").next(),
+            Some((0, 0..2, "if"))
+        );
+    }
+
+    #[test]
+    fn test_multi_matcher_overlapping() {
+        // Overlapping matches are not produced
+        let text = "abcd";
+        let patterns = ["bcd", "abc", "cd"];
+
+        let matcher = MultiMatcher::new(&patterns).unwrap();
+        assert!(matcher.find_ranges(text).eq([(1, 0..3, "abc")]));
+
+        let text = "abcdcd";
+        assert!(matcher
+            .find_ranges(text)
+            .eq([(1, 0..3, "abc"), (2, 4..6, "cd")]));
+    }
+
+    #[test]
+    fn test_multi_matcher_prefixes() {
+        let text = "foo foobar foob";
+        let patterns = ["foo", "foobar"];
+
+        let first = MultiMatcher::new(&patterns).unwrap();
+        let matches = first.find_ranges(text).collect::<Vec<_>>();
+        assert_eq!(
+            matches,
+            [(0, 0..3, "foo"), (0, 4..7, "foo"), (0, 11..14, "foo")]
+        );
+
+        let longest =
+            MultiMatcher::with_match_kind(&patterns, MultiMatchKind::LeftmostLongest).unwrap();
+        assert_eq!(longest.match_kind(), MultiMatchKind::LeftmostLongest);
+        let matches = longest.find_ranges(text).collect::<Vec<_>>();
+        assert_eq!(
+            matches,
+            [(0, 0..3, "foo"), (1, 4..10, "foobar"), (0, 11..14, "foo")]
+        );
+    }
+
+    #[test]
+    fn test_multi_matcher_multi_byte() {
+        let text = "🗻∈🌏 ⊂ 🌏∈";
+        let patterns = ["∈🌏", "🌏", "⊂"];
+
+        let matcher = MultiMatcher::new(&patterns).unwrap();
+        let matches = matcher.find_ranges(text).collect::<Vec<_>>();
+        assert_eq!(
+            matches,
+            [(0, 4..11, "∈🌏"), (2, 12..15, "⊂"), (1, 16..20, "🌏"),]
+        );
+        for (i, r, s) in matches {
+            assert_eq!(&text[r], s);
+            assert_eq!(patterns[i], s);
+        }
+
+        // Same leading bytes, different continuation bytes
+        let matcher = MultiMatcher::new(&["🌎"]).unwrap();
+        assert_eq!(matcher.find_ranges(text).next(), None);
+    }
+
+    #[test]
+    fn test_multi_matcher_empty_pattern() {
+        // Empty matches within a char are skipped
+        let text = "a🌏b";
+
+        let matcher = MultiMatcher::new(&[""]).unwrap();
+        let matches = matcher.find_ranges(text).map(|(_, r, _)| r);
+        assert!(matches.eq([0..0, 1..1, 5..5, 6..6]));
+
+        let matcher = MultiMatcher::new(&["🌏", ""]).unwrap();
+        let matches = matcher.find_ranges(text).map(|(i, r, _)| (i, r));
+        // An empty match directly after a match is not produced
+        assert!(matches.eq([(1, 0..0), (0, 1..5), (1, 6..6)]));
+    }
+
+    #[test]
+    fn test_multi_matcher_offset() {
+        let text = "xx ab ab";
+
+        let matcher = MultiMatcher::new(&["ab"]).unwrap();
+        let matches = matcher.find_ranges_offset(&text[4..], 4);
+        assert_eq!(matches.offset(), 4);
+        assert!(matches.eq([(0, 6..8, "ab")]));
+    }
+
+    #[test]
+    #[should_panic(expected = "position 2 with offset 18446744073709551614 overflows usize")]
+    fn test_multi_matcher_offset_overflow() {
+        let matcher = MultiMatcher::new(&["ab"]).unwrap();
+        matcher
+            .find_ranges_offset("ab", usize::MAX - 1)
+            .for_each(drop);
+    }
+
+    #[test]
+    fn test_multi_matcher_agrees_with_match_indices() {
+        const CHARS: [char; 8] = ['a', 'b', '\u{80}', 'é', '∈', '⊂', '🌏', '🗻'];
+        let patterns = ["a", "ab", "∈⊂", "🌏🌏", "é", "b🗻a"];
+
+        let mut next = crate::test_util::random();
+
+        for _ in 0..300 {
+            let len = next() % 32;
+            let text = (0..len)
+                .map(|_| CHARS[next() % CHARS.len()])
+                .collect::<String>();
+
+            // A single pattern agrees with `str::match_indices()`
+            for (i, pattern) in patterns.iter().enumerate() {
+                let matcher = MultiMatcher::new(&[*pattern]).unwrap();
+                let expected = text
+                    .match_indices(pattern)
+                    .map(|(start, s)| (0, start..(start + s.len()), s));
+                assert!(matcher.find_ranges(&text).eq(expected), "{i} {text:?}");
+            }
+
+            // All patterns, where each match is on char boundaries
+            // and is a non-overlapping occurrence of its pattern
+            let matcher = MultiMatcher::new(&patterns).unwrap();
+            let mut end = 0;
+            for (i, r, s) in matcher.find_ranges(&text) {
+                assert!(r.start >= end);
+                assert_eq!(&text[r.clone()], s);
+                assert_eq!(patterns[i], s);
+                assert!(text.match_overlapping_ranges(s).any(|(r2, _s)| r2 == r));
+                end = r.end;
+            }
+        }
+    }
+}
@@ -0,0 +1,273 @@
+use core::array;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::CharRangesOffset;
+
+/// An iterator over [`char`]s and their start and end byte positions,
+/// which can peek up to `N` items ahead.
+///
+/// Peeked items are buffered in a fixed-size buffer, i.e. no allocation
+/// is required.
+///
+/// See [`CharRanges::multipeek()`] for more information.
+///
+/// [`CharRanges::multipeek()`]: crate::CharRanges::multipeek
+#[derive(Clone)]
+pub struct MultiPeekCharRanges<'a, const N: usize> {
+    /// The remaining text, when this iterator was created.
+    text: &'a str,
+    /// Byte index in `text` of the next [`char`] to be returned.
+    start: usize,
+    iter: CharRangesOffset<'a>,
+    /// Ring buffer of peeked items.
+    buf: [(Range<usize>, char); N],
+    /// Index of the first peeked item in `buf`.
+    head: usize,
+    /// Number of peeked items in `buf`.
+    len: usize,
+}
+
+impl<'a, const N: usize> MultiPeekCharRanges<'a, N> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>) -> Self {
+        Self {
+            text: iter.as_str(),
+            start: 0,
+            iter,
+            buf: array::from_fn(|_| (0..0, '\0')),
+            head: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns the remaining substring, i.e. the text which has not
+    /// yet been returned, including any [`char`]s which have been
+    /// peeked but not yet consumed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "a🌏b";
+    ///
+    /// let mut chars = text.char_ranges().multipeek::<2>();
+    /// assert_eq!(chars.peek_nth(1), Some(&(1..5, '🌏')));
+    /// assert_eq!(chars.as_str(), "a🌏b");
+    ///
+    /// assert_eq!(chars.next(), Some((0..1, 'a')));
+    /// assert_eq!(chars.as_str(), "🌏b");
+    /// ```
+    pub fn as_str(&self) -> &'a str {
+        // Buffered items always directly precede the remaining
+        // text of `iter`, if any
+        let buffered = (0..self.len)
+            .map(|i| self.buf[(self.head + i) % N].1.len_utf8())
+            .sum::<usize>();
+        let end = self.start + buffered + self.iter.as_str().len();
+
+        &self.text[self.start..end]
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+
+    /// Returns a reference to the next [`char`] and its start and end
+    /// byte positions, without consuming it.
+    ///
+    /// This is the same as <code>[peek_nth]\(0)</code>.
+    ///
+    /// [peek_nth]: Self::peek_nth
+    #[inline]
+    pub fn peek(&mut self) -> Option<&(Range<usize>, char)> {
+        self.peek_nth(0)
+    }
+
+    /// Returns a reference to the `i`th next [`char`] and its start and
+    /// end byte positions, without consuming anything.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i >= N`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "<<=";
+    ///
+    /// let mut chars = text.char_ranges().multipeek::<3>();
+    /// assert_eq!(chars.peek_nth(2), Some(&(2..3, '=')));
+    /// assert_eq!(chars.peek_nth(0), Some(&(0..1, '<')));
+    ///
+    /// assert_eq!(chars.next(), Some((0..1, '<')));
+    /// assert_eq!(chars.peek_nth(2), None);
+    /// ```
+    pub fn peek_nth(&mut self, i: usize) -> Option<&(Range<usize>, char)> {
+        assert!(i < N, "peek index (is {i}) should be < N (is {N})");
+
+        while self.len <= i {
+            let item = self.iter.next()?;
+            self.buf[(self.head + self.len) % N] = item;
+            self.len += 1;
+        }
+
+        Some(&self.buf[(self.head + i) % N])
+    }
+}
+
+impl<const N: usize> Iterator for MultiPeekCharRanges<'_, N> {
+    type Item = (Range<usize>, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = if self.len == 0 {
+            self.iter.next()?
+        } else {
+            let item = self.buf[self.head].clone();
+            self.head = (self.head + 1) % N;
+            self.len -= 1;
+            item
+        };
+        self.start += item.1.len_utf8();
+        Some(item)
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.iter.count() + self.len
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (
+            lower.saturating_add(self.len),
+            upper.and_then(|upper| upper.checked_add(self.len)),
+        )
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<(Range<usize>, char)> {
+        self.next_back()
+    }
+}
+
+impl<const N: usize> DoubleEndedIterator for MultiPeekCharRanges<'_, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if let Some(item) = self.iter.next_back() {
+            return Some(item);
+        }
+
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+        Some(self.buf[(self.head + self.len) % N].clone())
+    }
+}
+
+impl<const N: usize> FusedIterator for MultiPeekCharRanges<'_, N> {}
+
+impl<const N: usize> fmt::Debug for MultiPeekCharRanges<'_, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MultiPeekCharRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::CharRangesExt;
+
+    #[test]
+    fn test_multipeek() {
+        let text = "🗻12∈45🌏";
+
+        let mut chars = text.char_ranges().multipeek::<3>();
+        assert_eq!(chars.peek_nth(2), Some(&(5..6, '2')));
+        assert_eq!(chars.peek_nth(0), Some(&(0..4, '🗻')));
+        assert_eq!(chars.peek_nth(1), Some(&(4..5, '1')));
+        assert_eq!(chars.as_str(), "🗻12∈45🌏");
+
+        assert_eq!(chars.next(), Some((0..4, '🗻')));
+        assert_eq!(chars.as_str(), "12∈45🌏");
+
+        assert_eq!(chars.peek_nth(2), Some(&(6..9, '∈')));
+        assert_eq!(chars.as_str(), "12∈45🌏");
+
+        assert_eq!(chars.next(), Some((4..5, '1')));
+        assert_eq!(chars.next(), Some((5..6, '2')));
+        assert_eq!(chars.as_str(), "∈45🌏");
+
+        assert_eq!(chars.peek(), Some(&(6..9, '∈')));
+        assert_eq!(chars.peek_nth(2), Some(&(10..11, '5')));
+        assert_eq!(chars.next(), Some((6..9, '∈')));
+        assert_eq!(chars.next(), Some((9..10, '4')));
+
+        assert_eq!(chars.peek_nth(1), Some(&(11..15, '🌏')));
+        assert_eq!(chars.peek_nth(2), None);
+        assert_eq!(chars.as_str(), "5🌏");
+
+        assert_eq!(chars.next(), Some((10..11, '5')));
+        assert_eq!(chars.next(), Some((11..15, '🌏')));
+        assert_eq!(chars.as_str(), "");
+        assert_eq!(chars.peek(), None);
+        assert_eq!(chars.next(), None);
+    }
+
+    #[test]
+    fn test_multipeek_equivalence() {
+        let text = "Hello 🗻12∈45🌏 World";
+
+        for depth in 0..4 {
+            let mut chars = text.char_ranges().multipeek::<4>();
+            let mut expected = text.char_ranges();
+            loop {
+                for i in 0..=depth {
+                    assert_eq!(chars.peek_nth(i).cloned(), expected.clone().nth(i));
+                }
+                assert_eq!(chars.as_str(), expected.as_str());
+
+                let item = chars.next();
+                assert_eq!(item, expected.next());
+                if item.is_none() {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_multipeek_next_back() {
+        let text = "🗻∈🌏";
+
+        let mut chars = text.char_ranges().multipeek::<3>();
+        assert_eq!(chars.peek_nth(1), Some(&(4..7, '∈')));
+        assert_eq!(chars.next_back(), Some((7..11, '🌏')));
+        assert_eq!(chars.as_str(), "🗻∈");
+
+        // Only buffered items remain
+        assert_eq!(chars.next_back(), Some((4..7, '∈')));
+        assert_eq!(chars.as_str(), "🗻");
+        assert_eq!(chars.next(), Some((0..4, '🗻')));
+        assert_eq!(chars.next_back(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_multipeek_out_of_bounds() {
+        let mut chars = "abc".char_ranges().multipeek::<2>();
+        chars.peek_nth(2);
+    }
+}
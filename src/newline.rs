@@ -0,0 +1,430 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::{CharRangesExt, CharRangesOffset};
+
+/// An iterator over [`char`]s and their start and end byte positions,
+/// where newlines are normalized to `'\n'`.
+///
+/// - A `"\r\n"` pair is produced as a single `'\n'`, with a range
+///   spanning both bytes.
+/// - A lone `'\r'` is also produced as `'\n'`, with a range spanning
+///   its single byte, i.e. classic Mac OS line endings are normalized.
+/// - All other [`char`]s are produced unchanged.
+///
+/// A `"\r\n"` pair is never split, also when consuming from both ends.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRangesExt::char_ranges_normalized_newlines()`] for more information.
+///
+/// [`CharRangesExt::char_ranges_normalized_newlines()`]: crate::CharRangesExt::char_ranges_normalized_newlines
+#[derive(Clone)]
+pub struct NormalizedNewlines<'a> {
+    iter: CharRangesOffset<'a>,
+}
+
+impl<'a> NormalizedNewlines<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>) -> Self {
+        Self { iter }
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+}
+
+impl Iterator for NormalizedNewlines<'_> {
+    type Item = (Range<usize>, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (mut r, c) = self.iter.next()?;
+        if c != '\r' {
+            return Some((r, c));
+        }
+
+        let chars = &mut self.iter.iter;
+        if chars.as_str().starts_with('\n') {
+            chars.start += 1;
+            r.end += 1;
+        }
+        Some((r, '\n'))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // A `"\r\n"` pair is shorter than the longest `char`,
+        // so the lower bound is unchanged
+        self.iter.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for NormalizedNewlines<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (mut r, c) = self.iter.next_back()?;
+        match c {
+            '\r' => Some((r, '\n')),
+            '\n' => {
+                let chars = &mut self.iter.iter;
+                if chars.as_str().ends_with('\r') {
+                    chars.end -= 1;
+                    r.start -= 1;
+                }
+                Some((r, '\n'))
+            }
+            _ => Some((r, c)),
+        }
+    }
+}
+
+impl FusedIterator for NormalizedNewlines<'_> {}
+
+impl fmt::Debug for NormalizedNewlines<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "NormalizedNewlines(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+/// A line terminator.
+///
+/// See [`CharRanges::line_ending_ranges()`] for more information.
+///
+/// [`CharRanges::line_ending_ranges()`]: crate::CharRanges::line_ending_ranges
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum LineEnding {
+    /// `'\n'`, i.e. Unix line endings.
+    Lf,
+    /// `"\r\n"`, i.e. Windows line endings.
+    CrLf,
+    /// A lone `'\r'`, i.e. classic Mac OS line endings.
+    Cr,
+}
+
+impl LineEnding {
+    /// Returns the terminator as a string, e.g. `"\r\n"` for
+    /// [`LineEnding::CrLf`].
+    #[inline]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Lf => "\n",
+            Self::CrLf => "\r\n",
+            Self::Cr => "\r",
+        }
+    }
+}
+
+/// An iterator over the line terminators of a string, producing the start
+/// and end byte positions of each terminator, along with its kind.
+///
+/// A `'\r'` immediately followed by `'\n'` is a single
+/// [`LineEnding::CrLf`], also when consuming from both ends, while any
+/// other `'\r'` is a [`LineEnding::Cr`].
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRanges::line_ending_ranges()`] for more information.
+///
+/// [`CharRanges::line_ending_ranges()`]: crate::CharRanges::line_ending_ranges
+#[derive(Clone)]
+pub struct LineEndingRanges<'a> {
+    iter: CharRangesOffset<'a>,
+}
+
+impl<'a> LineEndingRanges<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>) -> Self {
+        Self { iter }
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+}
+
+impl Iterator for LineEndingRanges<'_> {
+    type Item = (Range<usize>, LineEnding);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = self.iter.as_str();
+        let i = rest.find(['\r', '\n'])?;
+
+        let start = self.iter.iter.start + i;
+        let (len, ending) = match &rest.as_bytes()[i..] {
+            [b'\r', b'\n', ..] => (2, LineEnding::CrLf),
+            [b'\r', ..] => (1, LineEnding::Cr),
+            _ => (1, LineEnding::Lf),
+        };
+        self.iter.iter.start = start + len;
+
+        Some((self.iter.apply_offset(start..(start + len)), ending))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.iter.as_str().len()))
+    }
+}
+
+impl DoubleEndedIterator for LineEndingRanges<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let rest = self.iter.as_str();
+        let i = rest.rfind(['\r', '\n'])?;
+
+        let start = self.iter.iter.start + i;
+        let (start, ending) = match &rest.as_bytes()[..=i] {
+            [.., b'\r', b'\n'] => (start - 1, LineEnding::CrLf),
+            [.., b'\n'] => (start, LineEnding::Lf),
+            _ => (start, LineEnding::Cr),
+        };
+        let end = self.iter.iter.start + i + 1;
+        self.iter.iter.end = start;
+
+        Some((self.iter.apply_offset(start..end), ending))
+    }
+}
+
+impl FusedIterator for LineEndingRanges<'_> {}
+
+impl fmt::Debug for LineEndingRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "LineEndingRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+/// Returns the most common [`LineEnding`] in `text`, where ties are
+/// resolved in the order [`LineEnding::Lf`], [`LineEnding::CrLf`], and
+/// [`LineEnding::Cr`].
+pub(crate) fn detect_line_ending(text: &str) -> Option<LineEnding> {
+    let (mut lf, mut crlf, mut cr) = (0_usize, 0_usize, 0_usize);
+    for (_r, ending) in LineEndingRanges::new(text.char_ranges_offset(0)) {
+        match ending {
+            LineEnding::Lf => lf += 1,
+            LineEnding::CrLf => crlf += 1,
+            LineEnding::Cr => cr += 1,
+        }
+    }
+
+    if (lf | crlf | cr) == 0 {
+        None
+    } else if (lf >= crlf) && (lf >= cr) {
+        Some(LineEnding::Lf)
+    } else if crlf >= cr {
+        Some(LineEnding::CrLf)
+    } else {
+        Some(LineEnding::Cr)
+    }
+}
+
+/// Returns `true` if `text` contains more than one kind of [`LineEnding`].
+pub(crate) fn has_mixed_line_endings(text: &str) -> bool {
+    let mut endings = LineEndingRanges::new(text.char_ranges_offset(0)).map(|(_r, e)| e);
+    match endings.next() {
+        Some(first) => endings.any(|ending| ending != first),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use crate::CharRangesExt;
+
+    #[test]
+    fn test_normalized_newlines() {
+        let text = "a\r\nb\nc\rd";
+        let expected = [
+            (0..1, 'a'),
+            (1..3, '\n'),
+            (3..4, 'b'),
+            (4..5, '\n'),
+            (5..6, 'c'),
+            (6..7, '\n'),
+            (7..8, 'd'),
+        ];
+
+        let chars = text.char_ranges_normalized_newlines().collect::<Vec<_>>();
+        assert_eq!(chars, expected);
+
+        let mut rev = text
+            .char_ranges_normalized_newlines()
+            .rev()
+            .collect::<Vec<_>>();
+        rev.reverse();
+        assert_eq!(rev, expected);
+
+        let chars = text.char_ranges_offset(10).normalize_newlines();
+        assert_eq!(chars.offset(), 10);
+        assert_eq!(chars.map(|(r, _c)| r.start).nth(2), Some(13));
+    }
+
+    #[test]
+    fn test_normalized_newlines_edges() {
+        // CRLF at the very start and end
+        let text = "\r\n🗻\r\n";
+        let mut chars = text.char_ranges_normalized_newlines();
+        assert_eq!(chars.next(), Some((0..2, '\n')));
+        assert_eq!(chars.next_back(), Some((6..8, '\n')));
+        assert_eq!(chars.as_str(), "🗻");
+        assert_eq!(chars.next(), Some((2..6, '🗻')));
+        assert_eq!(chars.next(), None);
+
+        // CRCRLF
+        let text = "\r\r\n";
+        assert!(text
+            .char_ranges_normalized_newlines()
+            .eq([(0..1, '\n'), (1..3, '\n')]));
+        assert!(text
+            .char_ranges_normalized_newlines()
+            .rev()
+            .eq([(1..3, '\n'), (0..1, '\n')]));
+
+        // Lone CR
+        assert!("\r".char_ranges_normalized_newlines().eq([(0..1, '\n')]));
+        assert!("\n\r"
+            .char_ranges_normalized_newlines()
+            .rev()
+            .eq([(1..2, '\n'), (0..1, '\n')]));
+
+        assert_eq!("".char_ranges_normalized_newlines().next(), None);
+    }
+
+    #[test]
+    fn test_normalized_newlines_both_ends() {
+        // The front stops between the '\r' and '\n'
+        let text = "a\r\n";
+        let mut chars = text.char_ranges_normalized_newlines();
+        assert_eq!(chars.next(), Some((0..1, 'a')));
+        assert_eq!(chars.next_back(), Some((1..3, '\n')));
+        assert_eq!(chars.next(), None);
+        assert_eq!(chars.next_back(), None);
+
+        let mut chars = text.char_ranges_normalized_newlines();
+        assert_eq!(chars.next(), Some((0..1, 'a')));
+        assert_eq!(chars.next(), Some((1..3, '\n')));
+        assert_eq!(chars.next_back(), None);
+
+        // Interleaved
+        let text = "\r\n\r\n∈\r\r\n";
+        let mut chars = text.char_ranges_normalized_newlines();
+        assert_eq!(chars.next_back(), Some((8..10, '\n')));
+        assert_eq!(chars.next(), Some((0..2, '\n')));
+        assert_eq!(chars.next_back(), Some((7..8, '\n')));
+        assert_eq!(chars.next(), Some((2..4, '\n')));
+        assert_eq!(chars.next_back(), Some((4..7, '∈')));
+        assert_eq!(chars.next(), None);
+        assert_eq!(chars.next_back(), None);
+    }
+
+    #[test]
+    fn test_line_ending_ranges() {
+        use super::LineEnding::*;
+
+        // Pure LF
+        let text = "a\nb\n\nc";
+        let endings = text.char_ranges().line_ending_ranges().collect::<Vec<_>>();
+        assert_eq!(endings, [(1..2, Lf), (3..4, Lf), (4..5, Lf)]);
+        assert_eq!(text.detect_line_ending(), Some(Lf));
+        assert!(!text.has_mixed_line_endings());
+
+        // Pure CRLF
+        let text = "a\r\n🗻\r\n";
+        let endings = text.char_ranges().line_ending_ranges().collect::<Vec<_>>();
+        assert_eq!(endings, [(1..3, CrLf), (7..9, CrLf)]);
+        assert_eq!(text.detect_line_ending(), Some(CrLf));
+        assert!(!text.has_mixed_line_endings());
+
+        for (r, ending) in endings {
+            assert_eq!(&text[r], ending.as_str());
+        }
+    }
+
+    #[test]
+    fn test_line_ending_ranges_lone_cr() {
+        use super::LineEnding::*;
+
+        // A single lone CR in an LF file
+        let text = "a\nb\rc\nd\n";
+        let endings = text.char_ranges().line_ending_ranges().collect::<Vec<_>>();
+        assert_eq!(endings, [(1..2, Lf), (3..4, Cr), (5..6, Lf), (7..8, Lf)]);
+        assert_eq!(text.detect_line_ending(), Some(Lf));
+        assert!(text.has_mixed_line_endings());
+
+        // CR at EOF, and CRCRLF
+        let text = "a\r\r\nb\r";
+        let endings = text.char_ranges().line_ending_ranges().collect::<Vec<_>>();
+        assert_eq!(endings, [(1..2, Cr), (2..4, CrLf), (5..6, Cr)]);
+        assert_eq!(text.detect_line_ending(), Some(Cr));
+        assert!(text.has_mixed_line_endings());
+
+        let mut rev = text
+            .char_ranges()
+            .line_ending_ranges()
+            .rev()
+            .collect::<Vec<_>>();
+        rev.reverse();
+        assert_eq!(rev, endings);
+
+        // Ties are resolved as LF, CRLF, then CR
+        assert_eq!("\r\r\n".detect_line_ending(), Some(CrLf));
+        assert_eq!("\r\n\n".detect_line_ending(), Some(Lf));
+    }
+
+    #[test]
+    fn test_line_ending_ranges_both_ends() {
+        use super::LineEnding::*;
+
+        // The CRLF is never split
+        let text = "\r\n";
+        let mut endings = text.char_ranges().line_ending_ranges();
+        assert_eq!(endings.next_back(), Some((0..2, CrLf)));
+        assert_eq!(endings.next(), None);
+
+        let text = "x\r\n\r∈\n";
+        let mut endings = text.char_ranges_offset(10).line_ending_ranges();
+        assert_eq!(endings.next(), Some((11..13, CrLf)));
+        assert_eq!(endings.next_back(), Some((17..18, Lf)));
+        assert_eq!(endings.as_str(), "\r∈");
+        assert_eq!(endings.next_back(), Some((13..14, Cr)));
+        assert_eq!(endings.next(), None);
+    }
+
+    #[test]
+    fn test_line_ending_ranges_empty() {
+        assert_eq!("".char_ranges().line_ending_ranges().next(), None);
+        assert_eq!("".detect_line_ending(), None);
+        assert!(!"".has_mixed_line_endings());
+
+        assert_eq!("foo".char_ranges().line_ending_ranges().next_back(), None);
+        assert_eq!("foo".detect_line_ending(), None);
+    }
+}
@@ -0,0 +1,353 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::iter::{self, FusedIterator};
+use core::ops::Range;
+
+use unicode_normalization::char::{canonical_combining_class, compose, decompose_canonical};
+use unicode_normalization::{is_nfc_quick, IsNormalized};
+
+use crate::CharRangesOffset;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Form {
+    Nfc,
+    Nfd,
+}
+
+/// An iterator over normalized [`char`]s, and the start and end byte
+/// positions of the source [`char`]s they were derived from.
+///
+/// - When multiple source [`char`]s compose into a single [`char`], then
+///   its range spans all of the source [`char`]s.
+/// - When a single source [`char`] decomposes into multiple [`char`]s, then
+///   they all share the range of the source [`char`].
+///
+/// See [`CharRangesExt::nfc_char_ranges()`] and [`CharRangesExt::nfd_char_ranges()`].
+///
+/// [`CharRangesExt::nfc_char_ranges()`]: crate::CharRangesExt::nfc_char_ranges
+/// [`CharRangesExt::nfd_char_ranges()`]: crate::CharRangesExt::nfd_char_ranges
+#[derive(Clone)]
+pub struct NormalizedCharRanges<'a> {
+    iter: CharRangesOffset<'a>,
+    form: Form,
+    /// Normalized [`char`]s of the current segment.
+    buf: Vec<(Range<usize>, char)>,
+    /// Index of the next [`char`] in `buf` to return.
+    pos: usize,
+}
+
+impl<'a> NormalizedCharRanges<'a> {
+    #[inline]
+    pub(crate) fn nfc(iter: CharRangesOffset<'a>) -> Self {
+        Self::new(iter, Form::Nfc)
+    }
+
+    #[inline]
+    pub(crate) fn nfd(iter: CharRangesOffset<'a>) -> Self {
+        Self::new(iter, Form::Nfd)
+    }
+
+    #[inline]
+    fn new(iter: CharRangesOffset<'a>, form: Form) -> Self {
+        Self {
+            iter,
+            form,
+            buf: Vec::new(),
+            pos: 0,
+        }
+    }
+
+    /// Returns the remaining substring, of source [`char`]s that
+    /// have not yet been normalized.
+    ///
+    /// Normalization is performed in segments, so source [`char`]s
+    /// can be excluded, even if not all normalized [`char`]s derived
+    /// from them have been returned yet.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "e\u{301}a";
+    ///
+    /// let mut chars = text.nfc_char_ranges();
+    /// assert_eq!(chars.as_str(), "e\u{301}a");
+    ///
+    /// assert_eq!(chars.next(), Some((0..3, 'é')));
+    /// assert_eq!(chars.as_str(), "a");
+    /// ```
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+
+    /// Returns `true` if nothing before `c` can interact with `c`
+    /// or anything after it, during normalization.
+    fn is_segment_start(&self, c: char) -> bool {
+        match self.form {
+            Form::Nfc => {
+                (canonical_combining_class(c) == 0)
+                    && (is_nfc_quick(iter::once(c)) == IsNormalized::Yes)
+            }
+            Form::Nfd => {
+                let mut first = None;
+                decompose_canonical(c, |c| {
+                    first.get_or_insert(c);
+                });
+                first.is_none_or(|c| canonical_combining_class(c) == 0)
+            }
+        }
+    }
+
+    /// Reads, decomposes, reorders, and (if NFC) composes the next
+    /// segment into `buf`.
+    fn fill(&mut self) {
+        self.buf.clear();
+        self.pos = 0;
+
+        let Some((r, c)) = self.iter.next() else {
+            return;
+        };
+        decompose_canonical(c, |c| self.buf.push((r.clone(), c)));
+
+        loop {
+            let mut iter = self.iter;
+            match iter.next() {
+                Some((r, c)) if !self.is_segment_start(c) => {
+                    self.iter = iter;
+                    decompose_canonical(c, |c| self.buf.push((r.clone(), c)));
+                }
+                _ => break,
+            }
+        }
+
+        self.reorder();
+
+        if self.form == Form::Nfc {
+            self.compose();
+        }
+    }
+
+    /// Canonical ordering, i.e. stable sort all runs of non-starters
+    /// by their canonical combining class.
+    fn reorder(&mut self) {
+        let mut i = 0;
+        while i < self.buf.len() {
+            if canonical_combining_class(self.buf[i].1) == 0 {
+                i += 1;
+                continue;
+            }
+
+            let start = i;
+            while (i < self.buf.len()) && (canonical_combining_class(self.buf[i].1) != 0) {
+                i += 1;
+            }
+
+            self.buf[start..i].sort_by_key(|&(_, c)| canonical_combining_class(c));
+        }
+    }
+
+    /// Canonical composition, where composed [`char`]s are assigned
+    /// the union of the ranges of the [`char`]s they were composed from.
+    fn compose(&mut self) {
+        let mut starter: Option<usize> = None;
+        // The combining class of the last `char` after `starter`, if any
+        let mut last_class: Option<u8> = None;
+
+        let mut len = 0;
+        for i in 0..self.buf.len() {
+            let (r, c) = self.buf[i].clone();
+            let class = canonical_combining_class(c);
+
+            if let Some(starter) = starter {
+                let blocked = match last_class {
+                    Some(last_class) => (last_class == 0) || (last_class >= class),
+                    None => false,
+                };
+
+                if !blocked {
+                    let (starter_r, starter_c) = &mut self.buf[starter];
+                    if let Some(composed) = compose(*starter_c, c) {
+                        *starter_c = composed;
+                        *starter_r = starter_r.start.min(r.start)..starter_r.end.max(r.end);
+                        continue;
+                    }
+                }
+            }
+
+            if class == 0 {
+                starter = Some(len);
+                last_class = None;
+            } else {
+                last_class = Some(class);
+            }
+
+            self.buf[len] = (r, c);
+            len += 1;
+        }
+
+        self.buf.truncate(len);
+    }
+}
+
+impl Iterator for NormalizedCharRanges<'_> {
+    type Item = (Range<usize>, char);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos == self.buf.len() {
+            self.fill();
+        }
+
+        let item = self.buf.get(self.pos)?.clone();
+        self.pos += 1;
+        Some(item)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let buffered = self.buf.len() - self.pos;
+        if self.iter.as_str().is_empty() {
+            (buffered, Some(buffered))
+        } else {
+            (buffered + 1, None)
+        }
+    }
+}
+
+impl FusedIterator for NormalizedCharRanges<'_> {}
+
+impl fmt::Debug for NormalizedCharRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "NormalizedCharRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use unicode_normalization::UnicodeNormalization;
+
+    use crate::CharRangesExt;
+
+    const CASES: &[&str] = &[
+        "",
+        "Hello World",
+        "e\u{301}",
+        "é",
+        "Cafe\u{301} café",
+        "a\u{301}\u{323}",
+        "a\u{323}\u{301}",
+        "\u{301}e",
+        "\u{1100}\u{1161}\u{11A8}",
+        "각",
+        "\u{212B}",
+        "e\u{301}\u{301}\u{301}",
+        "Hello 🗻12∈45🌏 World",
+    ];
+
+    #[test]
+    fn test_nfc_chars() {
+        for &text in CASES {
+            assert!(text.nfc_char_ranges().map(|(_, c)| c).eq(text.nfc()));
+            for (r, _c) in text.nfc_char_ranges() {
+                assert!(text.is_char_boundary(r.start));
+                assert!(text.is_char_boundary(r.end));
+            }
+        }
+    }
+
+    #[test]
+    fn test_nfd_chars() {
+        for &text in CASES {
+            assert!(text.nfd_char_ranges().map(|(_, c)| c).eq(text.nfd()));
+            for (r, _c) in text.nfd_char_ranges() {
+                assert!(text.is_char_boundary(r.start));
+                assert!(text.is_char_boundary(r.end));
+            }
+        }
+    }
+
+    #[test]
+    fn test_nfc_combining_acute() {
+        let text = "Cafe\u{301}!";
+
+        let mut chars = text.nfc_char_ranges();
+        assert_eq!(chars.next(), Some((0..1, 'C')));
+        assert_eq!(chars.next(), Some((1..2, 'a')));
+        assert_eq!(chars.next(), Some((2..3, 'f')));
+        assert_eq!(chars.next(), Some((3..6, 'é')));
+        assert_eq!(chars.as_str(), "!");
+        assert_eq!(chars.next(), Some((6..7, '!')));
+        assert_eq!(chars.next(), None);
+    }
+
+    #[test]
+    fn test_nfd_precomposed() {
+        let text = "é!";
+
+        let mut chars = text.nfd_char_ranges();
+        assert_eq!(chars.next(), Some((0..2, 'e')));
+        assert_eq!(chars.next(), Some((0..2, '\u{301}')));
+        assert_eq!(chars.as_str(), "!");
+        assert_eq!(chars.next(), Some((2..3, '!')));
+        assert_eq!(chars.next(), None);
+
+        let mut chars = text.nfc_char_ranges();
+        assert_eq!(chars.next(), Some((0..2, 'é')));
+        assert_eq!(chars.next(), Some((2..3, '!')));
+        assert_eq!(chars.next(), None);
+    }
+
+    #[test]
+    fn test_hangul() {
+        let text = "\u{1100}\u{1161}\u{11A8}";
+
+        let mut chars = text.nfc_char_ranges();
+        assert_eq!(chars.next(), Some((0..9, '각')));
+        assert_eq!(chars.next(), None);
+
+        let text = "각";
+
+        let mut chars = text.nfd_char_ranges();
+        assert_eq!(chars.next(), Some((0..3, '\u{1100}')));
+        assert_eq!(chars.next(), Some((0..3, '\u{1161}')));
+        assert_eq!(chars.next(), Some((0..3, '\u{11A8}')));
+        assert_eq!(chars.next(), None);
+    }
+
+    #[test]
+    fn test_reorder() {
+        // U+0323 (ccc 220) is ordered before U+0301 (ccc 230)
+        let text = "a\u{301}\u{323}";
+
+        let mut chars = text.nfd_char_ranges();
+        assert_eq!(chars.next(), Some((0..1, 'a')));
+        assert_eq!(chars.next(), Some((3..5, '\u{323}')));
+        assert_eq!(chars.next(), Some((1..3, '\u{301}')));
+        assert_eq!(chars.next(), None);
+
+        let mut chars = text.nfc_char_ranges();
+        assert_eq!(chars.next(), Some((0..5, 'ạ')));
+        assert_eq!(chars.next(), Some((1..3, '\u{301}')));
+        assert_eq!(chars.next(), None);
+    }
+
+    #[test]
+    fn test_normalized_offset() {
+        let text = "xe\u{301}";
+
+        let mut chars = text[1..].char_ranges_offset(1).nfc();
+        assert_eq!(chars.offset(), 1);
+        assert_eq!(chars.next(), Some((1..4, 'é')));
+        assert_eq!(chars.next(), None);
+    }
+}
@@ -0,0 +1,326 @@
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::sync::Arc;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::{CharRanges, CharRangesOffset};
+
+/// Converts an owned string into an iterator over its [`char`]s and
+/// their start and end byte positions, which owns the string.
+///
+/// See [`CharRangesOwned`] for more information.
+pub trait IntoCharRanges: Sized + AsRef<str> {
+    /// Returns an iterator over the [`char`]s of `self` and their start
+    /// and end byte positions, which takes ownership of `self`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{CharRangesOwned, IntoCharRanges};
+    ///
+    /// fn chars() -> CharRangesOwned {
+    ///     let text = "Hello 🌏".repeat(2);
+    ///     text.into_char_ranges()
+    /// }
+    ///
+    /// let mut chars = chars();
+    /// assert_eq!(chars.next(), Some((0..1, 'H')));
+    /// assert_eq!(chars.next_back(), Some((16..20, '🌏')));
+    /// assert_eq!(chars.as_str(), "ello 🌏Hello ");
+    /// assert_eq!(chars.text(), "Hello 🌏Hello 🌏");
+    /// ```
+    #[inline]
+    fn into_char_ranges(self) -> CharRangesOwned<Self> {
+        CharRangesOwned::new(self, 0)
+    }
+
+    /// Returns an iterator over the [`char`]s of `self` and their start
+    /// and end byte positions, which takes ownership of `self`. With
+    /// `offset` applied to all positions.
+    ///
+    /// See [`CharRangesExt::char_ranges_offset()`] for more information.
+    ///
+    /// [`CharRangesExt::char_ranges_offset()`]: crate::CharRangesExt::char_ranges_offset
+    #[inline]
+    fn into_char_ranges_offset(self, offset: usize) -> CharRangesOwned<Self> {
+        CharRangesOwned::new(self, offset)
+    }
+}
+
+impl IntoCharRanges for String {}
+
+impl IntoCharRanges for Box<str> {}
+
+impl IntoCharRanges for Arc<str> {}
+
+/// An iterator over [`char`]s and their start and end byte positions,
+/// which owns its text.
+///
+/// This is the same as [`CharRanges`] and [`CharRangesOffset`], except
+/// that the text is owned, e.g. allowing the iterator to be returned from
+/// a function, which creates the text. Only the front and back byte
+/// positions are tracked alongside the text, so the iterator is not
+/// self-referential, and can be moved freely, including across threads
+/// when the text is [`Send`].
+///
+/// See [`IntoCharRanges::into_char_ranges()`] for more information.
+#[derive(Clone)]
+pub struct CharRangesOwned<T = String> {
+    text: T,
+    /// Start byte position of the remaining text.
+    start: usize,
+    /// End byte position of the remaining text.
+    end: usize,
+    offset: usize,
+}
+
+impl<T> CharRangesOwned<T>
+where
+    T: AsRef<str>,
+{
+    #[inline]
+    fn new(text: T, offset: usize) -> Self {
+        let end = text.as_ref().len();
+        Self {
+            text,
+            start: 0,
+            end,
+            offset,
+        }
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.text()[self.start..self.end]
+    }
+
+    /// Returns the whole owned text, including the [`char`]s that have
+    /// already been produced.
+    #[inline]
+    pub fn text(&self) -> &str {
+        self.text.as_ref()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the owned text, consuming the iterator.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.text
+    }
+
+    /// Returns a borrowed iterator over the remaining [`char`]s.
+    #[inline]
+    pub fn char_ranges(&self) -> CharRangesOffset<'_> {
+        CharRanges {
+            text: self.text.as_ref(),
+            start: self.start,
+            end: self.end,
+        }
+        .offset(self.offset)
+    }
+
+    /// Calls `f` with a borrowed iterator over the remaining [`char`]s,
+    /// and afterwards updates the byte positions to those of the
+    /// borrowed iterator.
+    #[inline]
+    fn advance_with<R>(&mut self, f: impl FnOnce(&mut CharRangesOffset<'_>) -> R) -> R {
+        let mut iter = self.char_ranges();
+        let ret = f(&mut iter);
+        (self.start, self.end) = (iter.iter.start, iter.iter.end);
+        ret
+    }
+}
+
+impl<T> Iterator for CharRangesOwned<T>
+where
+    T: AsRef<str>,
+{
+    type Item = (Range<usize>, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.advance_with(|iter| iter.next())
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.char_ranges().count()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.char_ranges().size_hint()
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<(Range<usize>, char)> {
+        self.next_back()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.advance_with(|iter| iter.nth(n))
+    }
+
+    #[inline]
+    fn fold<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.char_ranges().fold(init, f)
+    }
+
+    #[inline]
+    fn for_each<F>(self, mut f: F)
+    where
+        F: FnMut(Self::Item),
+    {
+        self.fold((), |(), item| f(item));
+    }
+}
+
+impl<T> DoubleEndedIterator for CharRangesOwned<T>
+where
+    T: AsRef<str>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.advance_with(|iter| iter.next_back())
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.advance_with(|iter| iter.nth_back(n))
+    }
+
+    #[inline]
+    fn rfold<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.char_ranges().rfold(init, f)
+    }
+}
+
+impl<T> FusedIterator for CharRangesOwned<T> where T: AsRef<str> {}
+
+impl<T> fmt::Debug for CharRangesOwned<T>
+where
+    T: AsRef<str>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CharRangesOwned(")?;
+        f.debug_list().entries(self.char_ranges()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::boxed::Box;
+    use std::string::String;
+    use std::sync::Arc;
+    use std::thread;
+    use std::vec::Vec;
+
+    use super::{CharRangesOwned, IntoCharRanges};
+    use crate::CharRangesExt;
+
+    const TEXT: &str = "Hello 🗻12∈45🌏 World ¢";
+
+    fn owned_chars(n: usize) -> CharRangesOwned {
+        let text = TEXT.repeat(n);
+        text.into_char_ranges()
+    }
+
+    #[test]
+    fn test_char_ranges_owned() {
+        assert!(String::from(TEXT).into_char_ranges().eq(TEXT.char_ranges()));
+        assert!(Box::<str>::from(TEXT)
+            .into_char_ranges()
+            .eq(TEXT.char_ranges()));
+        assert!(Arc::<str>::from(TEXT)
+            .into_char_ranges()
+            .rev()
+            .eq(TEXT.char_ranges().rev()));
+
+        let chars = String::from(TEXT).into_char_ranges_offset(7);
+        assert_eq!(chars.offset(), 7);
+        assert!(chars.eq(TEXT.char_ranges_offset(7)));
+
+        assert_eq!(String::new().into_char_ranges().next(), None);
+    }
+
+    #[test]
+    fn test_char_ranges_owned_specializations() {
+        let chars = owned_chars(3);
+        let expected = TEXT.repeat(3);
+        let expected = expected.char_ranges_offset(0);
+
+        assert_eq!(chars.clone().count(), expected.count());
+        assert_eq!(chars.size_hint(), expected.size_hint());
+        assert_eq!(chars.clone().last(), expected.last());
+        for n in [0, 1, 5, 20, 62, 63, 64, 100] {
+            assert_eq!(chars.clone().nth(n), expected.clone().nth(n), "{n}");
+            assert_eq!(
+                chars.clone().nth_back(n),
+                expected.clone().nth_back(n),
+                "{n}"
+            );
+        }
+
+        let folded = chars.clone().fold(Vec::new(), |mut v, item| {
+            v.push(item);
+            v
+        });
+        assert!(folded.into_iter().eq(expected));
+        let rfolded = chars.clone().rfold(Vec::new(), |mut v, item| {
+            v.push(item);
+            v
+        });
+        assert!(rfolded.into_iter().eq(expected.rev()));
+    }
+
+    #[test]
+    fn test_char_ranges_owned_both_ends() {
+        let mut chars = owned_chars(1);
+        let mut expected = TEXT.char_ranges();
+        loop {
+            assert_eq!(chars.as_str(), expected.as_str());
+            assert_eq!(chars.next(), expected.next());
+            let back = chars.next_back();
+            assert_eq!(back, expected.next_back());
+            if back.is_none() {
+                break;
+            }
+        }
+        assert_eq!(chars.text(), TEXT);
+        assert_eq!(chars.into_inner(), TEXT);
+    }
+
+    #[test]
+    fn test_char_ranges_owned_send() {
+        fn assert_send<T: Send>(_: &T) {}
+
+        let mut chars = owned_chars(2);
+        chars.next();
+        assert_send(&chars);
+
+        let handle = thread::spawn(move || chars.collect::<Vec<_>>());
+        let chars = handle.join().unwrap();
+
+        let text = TEXT.repeat(2);
+        assert!(chars.into_iter().eq(text.char_ranges().skip(1)));
+    }
+}
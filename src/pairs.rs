@@ -0,0 +1,238 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::CharRangesOffset;
+
+type CharPair = ((Range<usize>, char), (Range<usize>, char));
+
+/// An iterator over overlapping pairs of adjacent [`char`]s, and their
+/// start and end byte positions.
+///
+/// For `n` [`char`]s, `n - 1` pairs are produced, where the second
+/// [`char`] of a pair is the first [`char`] of the next pair.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRanges::char_pairs()`] for more information.
+///
+/// [`CharRanges::char_pairs()`]: crate::CharRanges::char_pairs
+#[derive(Clone)]
+pub struct CharPairs<'a> {
+    iter: CharRangesOffset<'a>,
+    /// The first [`char`] of the next pair from the front, which was
+    /// the second [`char`] of the previous pair.
+    front: Option<(Range<usize>, char)>,
+    /// The second [`char`] of the next pair from the back, which was
+    /// the first [`char`] of the previous pair.
+    back: Option<(Range<usize>, char)>,
+}
+
+impl<'a> CharPairs<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>) -> Self {
+        Self {
+            iter,
+            front: None,
+            back: None,
+        }
+    }
+
+    /// Returns the remaining substring, excluding any [`char`] which has
+    /// already been produced as part of a pair.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "a:=b";
+    ///
+    /// let mut pairs = text.char_ranges().char_pairs();
+    /// assert_eq!(pairs.next(), Some(((0..1, 'a'), (1..2, ':'))));
+    /// assert_eq!(pairs.as_str(), "=b");
+    /// ```
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+
+    /// Returns the start byte position of the first [`char`] and the
+    /// end byte position of the second [`char`] of `pair`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{CharPairs, CharRangesExt};
+    ///
+    /// let text = "a\r\nb";
+    ///
+    /// let crlf = text
+    ///     .char_ranges()
+    ///     .char_pairs()
+    ///     .find(|((_, a), (_, b))| (*a == '\r') && (*b == '\n'))
+    ///     .unwrap();
+    /// assert_eq!(CharPairs::joined_range(&crlf), 1..3);
+    /// ```
+    #[inline]
+    pub fn joined_range(pair: &CharPair) -> Range<usize> {
+        let ((first, _), (second, _)) = pair;
+        first.start..second.end
+    }
+
+    #[inline]
+    fn slots(&self) -> usize {
+        usize::from(self.front.is_some()) + usize::from(self.back.is_some())
+    }
+}
+
+impl Iterator for CharPairs<'_> {
+    type Item = CharPair;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = match self.front.take() {
+            Some(first) => first,
+            None => self.iter.next()?,
+        };
+
+        if let Some(second) = self.iter.next() {
+            self.front = Some(second.clone());
+            Some((first, second))
+        } else if let Some(second) = self.back.take() {
+            // The last pair, which was also the next pair from the back
+            Some((first, second))
+        } else {
+            self.front = Some(first);
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let slots = self.slots();
+        (
+            (lower + slots).saturating_sub(1),
+            upper.map(|upper| (upper + slots).saturating_sub(1)),
+        )
+    }
+}
+
+impl DoubleEndedIterator for CharPairs<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let second = match self.back.take() {
+            Some(second) => second,
+            None => self.iter.next_back()?,
+        };
+
+        if let Some(first) = self.iter.next_back() {
+            self.back = Some(first.clone());
+            Some((first, second))
+        } else if let Some(first) = self.front.take() {
+            // The last pair, which was also the next pair from the front
+            Some((first, second))
+        } else {
+            self.back = Some(second);
+            None
+        }
+    }
+}
+
+impl FusedIterator for CharPairs<'_> {}
+
+impl fmt::Debug for CharPairs<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CharPairs(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use super::CharPairs;
+    use crate::CharRangesExt;
+
+    const TEXT: &str = "Hello 🗻12∈45🌏 World ¢";
+
+    #[test]
+    fn test_char_pairs() {
+        let chars = TEXT.char_ranges().collect::<Vec<_>>();
+        let pairs = TEXT.char_ranges().char_pairs().collect::<Vec<_>>();
+        assert_eq!(pairs.len(), chars.len() - 1);
+
+        for (i, (first, second)) in pairs.iter().enumerate() {
+            assert_eq!(first, &chars[i]);
+            assert_eq!(second, &chars[i + 1]);
+            assert_eq!(first.0.end, second.0.start);
+        }
+        for w in pairs.windows(2) {
+            assert_eq!(w[0].1, w[1].0);
+        }
+
+        let mut rev = TEXT.char_ranges().char_pairs().rev().collect::<Vec<_>>();
+        rev.reverse();
+        assert_eq!(pairs, rev);
+    }
+
+    #[test]
+    fn test_char_pairs_edges() {
+        assert_eq!("".char_ranges().char_pairs().next(), None);
+        assert_eq!("".char_ranges().char_pairs().next_back(), None);
+        assert_eq!("🌏".char_ranges().char_pairs().next(), None);
+        assert_eq!("🌏".char_ranges().char_pairs().next_back(), None);
+        assert_eq!("🌏".char_ranges().char_pairs().size_hint(), (0, Some(3)));
+
+        let mut pairs = "🗻∈".char_ranges().char_pairs();
+        assert_eq!(pairs.next(), Some(((0..4, '🗻'), (4..7, '∈'))));
+        assert_eq!(pairs.next(), None);
+        assert_eq!(pairs.next_back(), None);
+    }
+
+    #[test]
+    fn test_char_pairs_double_ended() {
+        let mut pairs = "a:=🌏b".char_ranges().char_pairs();
+        assert_eq!(pairs.size_hint(), (1, Some(7)));
+        assert_eq!(pairs.next(), Some(((0..1, 'a'), (1..2, ':'))));
+        assert_eq!(pairs.next_back(), Some(((3..7, '🌏'), (7..8, 'b'))));
+        assert_eq!(pairs.as_str(), "=");
+        assert_eq!(pairs.size_hint(), (2, Some(2)));
+        assert_eq!(pairs.next_back(), Some(((2..3, '='), (3..7, '🌏'))));
+        assert_eq!(pairs.as_str(), "");
+        assert_eq!(pairs.next(), Some(((1..2, ':'), (2..3, '='))));
+        assert_eq!(pairs.next(), None);
+        assert_eq!(pairs.next_back(), None);
+
+        // The last pair from the front, after the back
+        let mut pairs = "abc".char_ranges().char_pairs();
+        assert_eq!(pairs.next_back(), Some(((1..2, 'b'), (2..3, 'c'))));
+        assert_eq!(pairs.next(), Some(((0..1, 'a'), (1..2, 'b'))));
+        assert_eq!(pairs.next(), None);
+        assert_eq!(pairs.next_back(), None);
+    }
+
+    #[test]
+    fn test_char_pairs_offset() {
+        let mut pairs = "x\r\n".char_ranges_offset(10).char_pairs();
+        assert_eq!(pairs.offset(), 10);
+        assert_eq!(pairs.next(), Some(((10..11, 'x'), (11..12, '\r'))));
+
+        let pair = pairs.next().unwrap();
+        assert_eq!(pair, ((11..12, '\r'), (12..13, '\n')));
+        assert_eq!(CharPairs::joined_range(&pair), 11..13);
+        assert_eq!(pairs.next(), None);
+    }
+}
@@ -0,0 +1,406 @@
+use core::fmt;
+use core::ops::Range;
+
+use rayon::iter::plumbing::{bridge_unindexed, Folder, UnindexedConsumer, UnindexedProducer};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use crate::{CharRanges, CharRangesExt, CharRangesOffset};
+
+/// A parallel iterator over [`char`]s and their start and end byte positions.
+///
+/// The text is split at the [`char`] boundary nearest its midpoint, such
+/// that no [`char`] is ever split across tasks. Items are produced in
+/// whatever order the tasks run.
+///
+/// See [`CharRangesExt::par_char_ranges()`] for more information.
+#[derive(Clone)]
+pub struct ParCharRanges<'a> {
+    iter: CharRangesOffset<'a>,
+    min_len: usize,
+}
+
+impl<'a> ParCharRanges<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>) -> Self {
+        Self { iter, min_len: 1 }
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+
+    /// Sets the minimum length in bytes of the substrings processed by
+    /// each task. Substrings shorter than `2 * min_len` are not split.
+    ///
+    /// Defaults to `1`, i.e. rayon decides how far to split.
+    #[inline]
+    pub fn with_min_len(mut self, min_len: usize) -> Self {
+        self.min_len = min_len.max(1);
+        self
+    }
+}
+
+impl<'a> ParallelIterator for ParCharRanges<'a> {
+    type Item = (Range<usize>, char);
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        let producer = CharRangesProducer {
+            text: self.iter.as_str(),
+            offset: self.iter.apply_offset_to(self.iter.iter.start),
+            min_len: self.min_len,
+        };
+        bridge_unindexed(producer, consumer)
+    }
+}
+
+impl fmt::Debug for ParCharRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParCharRanges")
+            .field("iter", &self.iter)
+            .field("min_len", &self.min_len)
+            .finish()
+    }
+}
+
+impl<'a> IntoParallelIterator for CharRanges<'a> {
+    type Iter = ParCharRanges<'a>;
+    type Item = (Range<usize>, char);
+
+    #[inline]
+    fn into_par_iter(self) -> Self::Iter {
+        ParCharRanges::new(self.offset(0))
+    }
+}
+
+impl<'a> IntoParallelIterator for CharRangesOffset<'a> {
+    type Iter = ParCharRanges<'a>;
+    type Item = (Range<usize>, char);
+
+    #[inline]
+    fn into_par_iter(self) -> Self::Iter {
+        ParCharRanges::new(self)
+    }
+}
+
+/// Returns the [`char`] boundary nearest the midpoint of `text`, or
+/// `None` if `text` is shorter than `2 * min_len` or a single [`char`].
+fn split_midpoint(text: &str, min_len: usize) -> Option<usize> {
+    let mid = text.len() / 2;
+    if mid < min_len {
+        return None;
+    }
+
+    let floor = (0..=mid).rev().find(|&i| text.is_char_boundary(i))?;
+    if floor > 0 {
+        return Some(floor);
+    }
+
+    let ceil = (mid..=text.len()).find(|&i| text.is_char_boundary(i))?;
+    (ceil < text.len()).then_some(ceil)
+}
+
+/// Applies `offset` to the position `pos`.
+///
+/// # Panics
+///
+/// Panics if the position overflows `usize`, regardless of
+/// whether overflow checks are enabled.
+#[inline]
+fn apply_offset_to(offset: usize, pos: usize) -> usize {
+    match pos.checked_add(offset) {
+        Some(pos) => pos,
+        None => panic!("position {pos} with offset {offset} overflows usize"),
+    }
+}
+
+struct CharRangesProducer<'a> {
+    text: &'a str,
+    offset: usize,
+    min_len: usize,
+}
+
+impl<'a> UnindexedProducer for CharRangesProducer<'a> {
+    type Item = (Range<usize>, char);
+
+    fn split(self) -> (Self, Option<Self>) {
+        let Some(mid) = split_midpoint(self.text, self.min_len) else {
+            return (self, None);
+        };
+
+        let (left, right) = self.text.split_at(mid);
+        let right = Self {
+            text: right,
+            offset: apply_offset_to(self.offset, mid),
+            min_len: self.min_len,
+        };
+        let left = Self { text: left, ..self };
+        (left, Some(right))
+    }
+
+    fn fold_with<F>(self, folder: F) -> F
+    where
+        F: Folder<Self::Item>,
+    {
+        folder.consume_iter(self.text.char_ranges_offset(self.offset))
+    }
+}
+
+/// A parallel iterator over maximal runs of [`char`]s, where each run
+/// either entirely matches a predicate or entirely doesn't.
+///
+/// The text is only ever split between two [`char`]s where the result
+/// of the predicate changes, i.e. at the boundary between two runs, such
+/// that the produced runs are the same as [`CharRanges::char_runs()`].
+/// Items are produced in whatever order the tasks run.
+///
+/// See [`CharRangesExt::par_char_runs()`] for more information.
+#[derive(Clone)]
+pub struct ParCharRuns<'a, P> {
+    iter: CharRangesOffset<'a>,
+    pred: P,
+    min_len: usize,
+}
+
+impl<'a, P> ParCharRuns<'a, P>
+where
+    P: Fn(char) -> bool + Sync + Send,
+{
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>, pred: P) -> Self {
+        Self {
+            iter,
+            pred,
+            min_len: 1,
+        }
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all produced ranges.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+
+    /// Sets the minimum length in bytes of the substrings processed by
+    /// each task. Substrings shorter than `2 * min_len` are not split.
+    ///
+    /// Defaults to `1`, i.e. rayon decides how far to split.
+    #[inline]
+    pub fn with_min_len(mut self, min_len: usize) -> Self {
+        self.min_len = min_len.max(1);
+        self
+    }
+}
+
+impl<'a, P> ParallelIterator for ParCharRuns<'a, P>
+where
+    P: Fn(char) -> bool + Sync + Send,
+{
+    type Item = (Range<usize>, &'a str, bool);
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        let producer = CharRunsProducer {
+            text: self.iter.as_str(),
+            offset: self.iter.apply_offset_to(self.iter.iter.start),
+            pred: &self.pred,
+            min_len: self.min_len,
+        };
+        bridge_unindexed(producer, consumer)
+    }
+}
+
+impl<P> fmt::Debug for ParCharRuns<'_, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParCharRuns")
+            .field("iter", &self.iter)
+            .field("min_len", &self.min_len)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Returns the run boundary nearest after the midpoint of `text`, or
+/// otherwise nearest before it, or `None` if `text` is a single run.
+fn split_run<P>(text: &str, min_len: usize, pred: &P) -> Option<usize>
+where
+    P: Fn(char) -> bool,
+{
+    let mid = split_midpoint(text, min_len)?;
+    let (left, right) = text.split_at(mid);
+
+    let before = pred(left.chars().next_back()?);
+    if let Some((i, _c)) = right.char_indices().find(|&(_i, c)| pred(c) != before) {
+        return Some(mid + i);
+    }
+
+    let after = pred(right.chars().next()?);
+    left.char_indices()
+        .rev()
+        .find(|&(_i, c)| pred(c) != after)
+        .map(|(i, c)| i + c.len_utf8())
+}
+
+struct CharRunsProducer<'a, 'p, P> {
+    text: &'a str,
+    offset: usize,
+    pred: &'p P,
+    min_len: usize,
+}
+
+impl<'a, P> UnindexedProducer for CharRunsProducer<'a, '_, P>
+where
+    P: Fn(char) -> bool + Sync + Send,
+{
+    type Item = (Range<usize>, &'a str, bool);
+
+    fn split(self) -> (Self, Option<Self>) {
+        let Some(mid) = split_run(self.text, self.min_len, self.pred) else {
+            return (self, None);
+        };
+
+        let (left, right) = self.text.split_at(mid);
+        let right = Self {
+            text: right,
+            offset: apply_offset_to(self.offset, mid),
+            ..self
+        };
+        let left = Self { text: left, ..self };
+        (left, Some(right))
+    }
+
+    fn fold_with<F>(self, folder: F) -> F
+    where
+        F: Folder<Self::Item>,
+    {
+        let runs = self
+            .text
+            .char_ranges_offset(self.offset)
+            .char_runs(self.pred);
+        folder.consume_iter(runs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::String;
+    use std::vec::Vec;
+
+    use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+    use super::ParCharRuns;
+    use crate::CharRangesExt;
+
+    const TEXT: &str = "Hello 🗻12∈45🌏 World ¢";
+
+    fn text() -> String {
+        TEXT.repeat(200)
+    }
+
+    #[test]
+    fn test_par_char_ranges() {
+        let text = text();
+
+        let mut actual = text.par_char_ranges().collect::<Vec<_>>();
+        actual.sort_by_key(|(r, _c)| r.start);
+        assert!(actual.into_iter().eq(text.char_ranges()));
+
+        let mut actual = text[6..]
+            .char_ranges_offset(6)
+            .into_par_iter()
+            .collect::<Vec<_>>();
+        actual.sort_by_key(|(r, _c)| r.start);
+        assert!(actual.into_iter().eq(text[6..].char_ranges_offset(6)));
+
+        // A partially consumed iterator continues at its position
+        let mut chars = text.char_ranges();
+        chars.next();
+        let mut actual = chars.into_par_iter().collect::<Vec<_>>();
+        actual.sort_by_key(|(r, _c)| r.start);
+        assert!(actual.into_iter().eq(chars));
+
+        let mut chars = text.char_ranges_offset(6);
+        chars.next();
+        chars.next_back();
+        let mut actual = chars.into_par_iter().collect::<Vec<_>>();
+        actual.sort_by_key(|(r, _c)| r.start);
+        assert!(actual.into_iter().eq(chars));
+
+        let mut chars = "abc".char_ranges();
+        chars.next();
+        let actual = chars.into_par_iter().with_min_len(8).collect::<Vec<_>>();
+        assert_eq!(actual, [(1..2, 'b'), (2..3, 'c')]);
+
+        assert_eq!("".par_char_ranges().count(), 0);
+        assert_eq!("🗻".par_char_ranges().count(), 1);
+    }
+
+    #[test]
+    fn test_par_char_runs() {
+        let text = text();
+        let pred = |c: char| c.is_alphanumeric();
+
+        let mut actual = text.par_char_runs(pred).collect::<Vec<_>>();
+        actual.sort_by_key(|(r, _run, _matched)| r.start);
+        assert!(actual.into_iter().eq(text.char_ranges().char_runs(pred)));
+
+        // A partially consumed iterator continues at its position
+        let mut chars = text.char_ranges_offset(6);
+        chars.next();
+        let mut actual = ParCharRuns::new(chars, pred).collect::<Vec<_>>();
+        actual.sort_by_key(|(r, _run, _matched)| r.start);
+        assert!(actual.into_iter().eq(chars.char_runs(pred)));
+
+        // A single run is never split
+        let text = "🗻".repeat(100);
+        let runs = text.par_char_runs(|_| true).collect::<Vec<_>>();
+        assert_eq!(runs, [(0..400, text.as_str(), true)]);
+    }
+
+    #[test]
+    fn test_par_random_min_len() {
+        let text = text();
+        let pred = |c: char| !c.is_ascii();
+
+        let mut random = crate::test_util::random();
+        for _ in 0..50 {
+            let min_len = 1 + random() % 64;
+
+            let mut actual = text
+                .par_char_ranges()
+                .with_min_len(min_len)
+                .collect::<Vec<_>>();
+            actual.sort_by_key(|(r, _c)| r.start);
+            assert!(actual.into_iter().eq(text.char_ranges()), "{min_len}");
+
+            let mut actual = text
+                .par_char_runs(pred)
+                .with_min_len(min_len)
+                .collect::<Vec<_>>();
+            actual.sort_by_key(|(r, _run, _matched)| r.start);
+            assert!(
+                actual.into_iter().eq(text.char_ranges().char_runs(pred)),
+                "{min_len}",
+            );
+        }
+    }
+}
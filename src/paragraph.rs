@@ -0,0 +1,207 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::CharRangesOffset;
+
+/// An iterator over the paragraphs of a string, i.e. maximal runs of
+/// non-blank lines, producing the start and end byte positions of each
+/// paragraph, along with the substring.
+///
+/// Lines are split like [`str::lines()`], i.e. at `'\n'` and `"\r\n"`.
+/// A paragraph range starts at its first line and ends at the end of its
+/// last line, excluding the terminator of the last line, while the
+/// terminators of its interior lines are included.
+///
+/// Blank lines separate paragraphs and are never part of one, so leading
+/// and trailing blank lines, as well as multiple consecutive blank lines,
+/// do not produce empty paragraphs. An empty line is always blank, while
+/// a line only containing [whitespace] is blank if `whitespace_is_blank`
+/// is `true`.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRanges::paragraph_ranges()`] for more information.
+///
+/// [whitespace]: char::is_whitespace
+/// [`CharRanges::paragraph_ranges()`]: crate::CharRanges::paragraph_ranges
+#[derive(Clone)]
+pub struct ParagraphRanges<'a> {
+    iter: CharRangesOffset<'a>,
+    whitespace_is_blank: bool,
+}
+
+impl<'a> ParagraphRanges<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>, whitespace_is_blank: bool) -> Self {
+        Self {
+            iter,
+            whitespace_is_blank,
+        }
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+
+    /// Returns the range of the line starting at `start`, excluding its
+    /// terminator, along with the start of the following line.
+    fn line_at(&self, start: usize) -> (Range<usize>, usize) {
+        let end = self.iter.iter.end;
+        let rest = &self.iter.iter.text[start..end];
+        match rest.find('\n') {
+            Some(i) => {
+                let newline = start + i;
+                if rest[..i].ends_with('\r') {
+                    (start..(newline - 1), newline + 1)
+                } else {
+                    (start..newline, newline + 1)
+                }
+            }
+            None => (start..end, end),
+        }
+    }
+
+    #[inline]
+    fn is_blank(&self, line: Range<usize>) -> bool {
+        let line = &self.iter.iter.text[line];
+        if self.whitespace_is_blank {
+            line.trim_start().is_empty()
+        } else {
+            line.is_empty()
+        }
+    }
+}
+
+impl<'a> Iterator for ParagraphRanges<'a> {
+    type Item = (Range<usize>, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let end = self.iter.iter.end;
+
+        // Skip blank lines
+        let mut start = self.iter.iter.start;
+        let mut paragraph = loop {
+            if start >= end {
+                self.iter.iter.start = end;
+                return None;
+            }
+
+            let (line, next) = self.line_at(start);
+            start = next;
+            if !self.is_blank(line.clone()) {
+                break line;
+            }
+        };
+
+        // Extend over non-blank lines
+        while start < end {
+            let (line, next) = self.line_at(start);
+            start = next;
+            if self.is_blank(line.clone()) {
+                break;
+            }
+            paragraph.end = line.end;
+        }
+        self.iter.iter.start = start;
+
+        let text = &self.iter.iter.text[paragraph.clone()];
+        Some((self.iter.apply_offset(paragraph), text))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.iter.as_str().len();
+        (0, Some(len.div_ceil(2)))
+    }
+}
+
+impl FusedIterator for ParagraphRanges<'_> {}
+
+impl fmt::Debug for ParagraphRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ParagraphRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use crate::CharRangesExt;
+
+    #[test]
+    fn test_paragraph_ranges() {
+        let text = "foo\nbar\n\nbaz\n";
+
+        let paragraphs = text.char_ranges().paragraph_ranges(true);
+        assert!(paragraphs.eq([(0..7, "foo\nbar"), (9..12, "baz")]));
+
+        // No trailing newline
+        let paragraphs = "foo 🗻\nbar".char_ranges().paragraph_ranges(true);
+        assert!(paragraphs.eq([(0..12, "foo 🗻\nbar")]));
+
+        // No paragraphs
+        for text in ["", "\n", "\n\n\n", "\r\n\r\n"] {
+            let mut paragraphs = text.char_ranges().paragraph_ranges(false);
+            assert_eq!(paragraphs.next(), None, "{text:?}");
+        }
+    }
+
+    #[test]
+    fn test_paragraph_ranges_blank_lines() {
+        // Leading, consecutive, and trailing blank lines
+        let text = "\n\nfoo\n\n\n\nbar\nbaz\n\n\n";
+
+        let paragraphs = text.char_ranges().paragraph_ranges(false);
+        assert!(paragraphs.eq([(2..5, "foo"), (9..16, "bar\nbaz")]));
+    }
+
+    #[test]
+    fn test_paragraph_ranges_whitespace() {
+        let text = "  \nfoo\n \t \nbar\n\u{A0}\n";
+
+        let paragraphs = text.char_ranges().paragraph_ranges(true);
+        assert!(paragraphs.eq([(3..6, "foo"), (11..14, "bar")]));
+
+        let paragraphs = text.char_ranges().paragraph_ranges(false);
+        assert!(paragraphs.eq([(0..17, "  \nfoo\n \t \nbar\n\u{A0}")]));
+    }
+
+    #[test]
+    fn test_paragraph_ranges_crlf() {
+        let text = "foo\r\nbar\r\n\r\n\r\nbaz\r\n";
+
+        let paragraphs = text.char_ranges().paragraph_ranges(false);
+        assert!(paragraphs.eq([(0..8, "foo\r\nbar"), (14..17, "baz")]));
+
+        // A lone '\r' is not a terminator
+        let paragraphs = "foo\r\rbar".char_ranges().paragraph_ranges(false);
+        assert!(paragraphs.eq([(0..8, "foo\r\rbar")]));
+    }
+
+    #[test]
+    fn test_paragraph_ranges_offset() {
+        let text = "\nfoo\n\nbar";
+
+        let paragraphs = text
+            .char_ranges()
+            .offset(10)
+            .paragraph_ranges(false)
+            .collect::<Vec<_>>();
+        assert_eq!(paragraphs, [(11..14, "foo"), (16..19, "bar")]);
+    }
+}
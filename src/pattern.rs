@@ -0,0 +1,384 @@
+use core::ops::Range;
+
+/// A pattern which can be searched for in text, e.g. by
+/// [`find_range()`] and [`trim_matches_range()`].
+///
+/// This is a stable substitute for the unstable [`core::str::pattern::Pattern`],
+/// and is implemented for the same types, with the same semantics:
+///
+/// | Pattern type               | Matches                                   |
+/// |----------------------------|-------------------------------------------|
+/// | [`char`]                   | that [`char`]                             |
+/// | `&str`                     | that substring                            |
+/// | `&[char]`                  | any of the [`char`]s in the slice         |
+/// | `[char; N]`                | any of the [`char`]s in the array         |
+/// | `F: FnMut(char) -> bool`   | any [`char`] for which `F` returns `true` |
+///
+/// This trait is sealed and cannot be implemented outside this crate.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::CharRangesExt;
+///
+/// let text = "[[🗻∈🌏]]";
+///
+/// assert_eq!(text.trim_matches_range('['), (2..15, "🗻∈🌏]]"));
+/// assert_eq!(text.trim_matches_range(['[', ']']), (2..13, "🗻∈🌏"));
+/// assert_eq!(text.trim_matches_range(&['[', ']'][..]), (2..13, "🗻∈🌏"));
+/// assert_eq!(text.trim_matches_range("[["), (2..15, "🗻∈🌏]]"));
+/// assert_eq!(text.trim_matches_range(|c: char| c.is_ascii()), (2..13, "🗻∈🌏"));
+/// ```
+///
+/// [`find_range()`]: crate::CharRanges::find_range
+/// [`trim_matches_range()`]: crate::CharRangesExt::trim_matches_range
+pub trait CharPattern: sealed::Sealed {}
+
+impl CharPattern for char {}
+
+impl CharPattern for &str {}
+
+impl CharPattern for &[char] {}
+
+impl<const N: usize> CharPattern for [char; N] {}
+
+impl<F> CharPattern for F where F: FnMut(char) -> bool {}
+
+pub(crate) mod sealed {
+    use core::ops::Range;
+
+    /// The matching machinery of [`CharPattern`](super::CharPattern).
+    ///
+    /// All positions are byte positions relative to `haystack`, and
+    /// always lie on [`char`] boundaries.
+    pub trait Sealed {
+        /// Returns the range of the first match in `haystack`.
+        fn find_in(&mut self, haystack: &str) -> Option<Range<usize>>;
+
+        /// Returns the range of the last match in `haystack`.
+        fn rfind_in(&mut self, haystack: &str) -> Option<Range<usize>>;
+
+        /// Returns the length of the match at the start of `haystack`.
+        fn prefix_len(&mut self, haystack: &str) -> Option<usize>;
+
+        /// Returns the length of the match at the end of `haystack`.
+        fn suffix_len(&mut self, haystack: &str) -> Option<usize>;
+    }
+
+    /// Patterns matching a single [`char`] at a time.
+    pub trait CharEq {
+        fn matches(&mut self, c: char) -> bool;
+    }
+
+    impl CharEq for char {
+        #[inline]
+        fn matches(&mut self, c: char) -> bool {
+            *self == c
+        }
+    }
+
+    impl CharEq for &[char] {
+        #[inline]
+        fn matches(&mut self, c: char) -> bool {
+            self.contains(&c)
+        }
+    }
+
+    impl<const N: usize> CharEq for [char; N] {
+        #[inline]
+        fn matches(&mut self, c: char) -> bool {
+            self.contains(&c)
+        }
+    }
+
+    impl<F> CharEq for F
+    where
+        F: FnMut(char) -> bool,
+    {
+        #[inline]
+        fn matches(&mut self, c: char) -> bool {
+            self(c)
+        }
+    }
+
+    impl<P> Sealed for P
+    where
+        P: CharEq,
+    {
+        #[inline]
+        fn find_in(&mut self, haystack: &str) -> Option<Range<usize>> {
+            haystack
+                .char_indices()
+                .find(|&(_i, c)| self.matches(c))
+                .map(|(i, c)| i..(i + c.len_utf8()))
+        }
+
+        #[inline]
+        fn rfind_in(&mut self, haystack: &str) -> Option<Range<usize>> {
+            haystack
+                .char_indices()
+                .rev()
+                .find(|&(_i, c)| self.matches(c))
+                .map(|(i, c)| i..(i + c.len_utf8()))
+        }
+
+        #[inline]
+        fn prefix_len(&mut self, haystack: &str) -> Option<usize> {
+            let c = haystack.chars().next()?;
+            self.matches(c).then(|| c.len_utf8())
+        }
+
+        #[inline]
+        fn suffix_len(&mut self, haystack: &str) -> Option<usize> {
+            let c = haystack.chars().next_back()?;
+            self.matches(c).then(|| c.len_utf8())
+        }
+    }
+
+    impl Sealed for &str {
+        #[inline]
+        fn find_in(&mut self, haystack: &str) -> Option<Range<usize>> {
+            let i = haystack.find(*self)?;
+            Some(i..(i + self.len()))
+        }
+
+        #[inline]
+        fn rfind_in(&mut self, haystack: &str) -> Option<Range<usize>> {
+            let i = haystack.rfind(*self)?;
+            Some(i..(i + self.len()))
+        }
+
+        #[inline]
+        fn prefix_len(&mut self, haystack: &str) -> Option<usize> {
+            haystack.starts_with(*self).then_some(self.len())
+        }
+
+        #[inline]
+        fn suffix_len(&mut self, haystack: &str) -> Option<usize> {
+            haystack.ends_with(*self).then_some(self.len())
+        }
+    }
+}
+
+/// Returns the byte length of the leading matches of `pat` in `text`,
+/// i.e. what [`str::trim_start_matches()`] removes.
+pub(crate) fn trim_start_len<P>(text: &str, pat: &mut P) -> usize
+where
+    P: CharPattern,
+{
+    let mut len = 0;
+    while let Some(n) = pat.prefix_len(&text[len..]) {
+        if n == 0 {
+            break;
+        }
+        len += n;
+    }
+    len
+}
+
+/// Returns the byte length of the trailing matches of `pat` in `text`,
+/// i.e. what [`str::trim_end_matches()`] removes.
+pub(crate) fn trim_end_len<P>(text: &str, pat: &mut P) -> usize
+where
+    P: CharPattern,
+{
+    let mut end = text.len();
+    while let Some(n) = pat.suffix_len(&text[..end]) {
+        if n == 0 {
+            break;
+        }
+        end -= n;
+    }
+    text.len() - end
+}
+
+/// Returns the range of the first non-empty match of `pat` in `text`,
+/// along with the first [`char`] of the match.
+pub(crate) fn find_char<P>(text: &str, pat: &mut P) -> Option<(Range<usize>, char)>
+where
+    P: CharPattern,
+{
+    let r = pat.find_in(text)?;
+    let c = text[r.clone()].chars().next()?;
+    Some((r, c))
+}
+
+/// Returns the range of the last non-empty match of `pat` in `text`,
+/// along with the first [`char`] of the match.
+pub(crate) fn rfind_char<P>(text: &str, pat: &mut P) -> Option<(Range<usize>, char)>
+where
+    P: CharPattern,
+{
+    let r = pat.rfind_in(text)?;
+    let c = text[r.clone()].chars().next()?;
+    Some((r, c))
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use super::sealed::Sealed;
+    use super::{trim_end_len, trim_start_len, CharPattern};
+
+    const TEXT: &str = "Hello 🗻12∈45🌏 World ¢";
+
+    /// Returns all ranges of non-overlapping matches, like [`str::match_indices()`].
+    fn match_ranges<P: CharPattern>(text: &str, mut pat: P) -> Vec<(usize, &str)> {
+        let mut matches = Vec::new();
+        let mut start = 0;
+        while let Some(r) = pat.find_in(&text[start..]) {
+            let r = (start + r.start)..(start + r.end);
+            matches.push((r.start, &text[r.clone()]));
+            start = match text[r.end..].chars().next() {
+                _ if !r.is_empty() => r.end,
+                Some(c) => r.end + c.len_utf8(),
+                None => break,
+            };
+        }
+        matches
+    }
+
+    /// Returns all ranges of non-overlapping matches, like [`str::rmatch_indices()`].
+    fn rmatch_ranges<P: CharPattern>(text: &str, mut pat: P) -> Vec<(usize, &str)> {
+        let mut matches = Vec::new();
+        let mut end = text.len();
+        while let Some(r) = pat.rfind_in(&text[..end]) {
+            matches.push((r.start, &text[r.clone()]));
+            end = match text[..r.start].chars().next_back() {
+                _ if !r.is_empty() => r.start,
+                Some(c) => r.start - c.len_utf8(),
+                None => break,
+            };
+        }
+        matches
+    }
+
+    macro_rules! assert_std_eq {
+        ($text:expr, $pat:expr) => {{
+            let text: &str = $text;
+            assert_eq!(
+                match_ranges(text, $pat),
+                text.match_indices($pat).collect::<Vec<_>>(),
+                "{text:?}",
+            );
+            assert_eq!(
+                rmatch_ranges(text, $pat),
+                text.rmatch_indices($pat).collect::<Vec<_>>(),
+                "{text:?}",
+            );
+
+            let start = trim_start_len(text, &mut $pat);
+            assert_eq!(&text[start..], text.trim_start_matches($pat), "{text:?}");
+            let end = text.len() - trim_end_len(text, &mut $pat);
+            assert_eq!(&text[..end], text.trim_end_matches($pat), "{text:?}");
+        }};
+    }
+
+    #[test]
+    fn test_char_pattern_char() {
+        let mut pat = 'l';
+        assert_eq!(pat.find_in(TEXT), Some(2..3));
+        assert_eq!(pat.rfind_in(TEXT), Some(25..26));
+        assert_eq!(pat.prefix_len("llama"), Some(1));
+        assert_eq!(pat.suffix_len("llama"), None);
+
+        let mut pat = '🌏';
+        assert_eq!(pat.find_in(TEXT), Some(17..21));
+        assert_eq!(pat.prefix_len("🌏🌏"), Some(4));
+        assert_eq!(pat.suffix_len("🌏🌏"), Some(4));
+        assert_eq!(trim_start_len("🌏🌏a🌏", &mut pat), 8);
+        assert_eq!(trim_end_len("🌏🌏a🌏", &mut pat), 4);
+
+        for c in ['l', '🗻', '∈', ' ', '¢', 'x'] {
+            let mut pat = c;
+            assert_std_eq!(TEXT, pat);
+            assert_std_eq!("", pat);
+        }
+    }
+
+    #[test]
+    fn test_char_pattern_str() {
+        let mut pat = "l";
+        assert_eq!(pat.find_in(TEXT), Some(2..3));
+
+        let mut pat = "∈45";
+        assert_eq!(pat.find_in(TEXT), Some(12..17));
+        assert_eq!(pat.rfind_in(TEXT), Some(12..17));
+        assert_eq!(pat.prefix_len("∈45∈"), Some(5));
+        assert_eq!(pat.suffix_len("∈45∈"), None);
+
+        // Empty matches everywhere, but is never trimmed
+        let mut pat = "";
+        assert_eq!(pat.find_in(TEXT), Some(0..0));
+        assert_eq!(pat.rfind_in(TEXT), Some(TEXT.len()..TEXT.len()));
+        assert_eq!(trim_start_len(TEXT, &mut pat), 0);
+        assert_eq!(trim_end_len(TEXT, &mut pat), 0);
+
+        for mut pat in ["l", "ll", "🗻1", "🌏 W", "¢", "", "x", "aa"] {
+            assert_std_eq!(TEXT, pat);
+            assert_std_eq!("", pat);
+            assert_std_eq!("aaaaa", pat);
+            assert_std_eq!("ll🗻1🗻1ll", pat);
+        }
+    }
+
+    #[test]
+    fn test_char_pattern_slice() {
+        let mut pat: &[char] = &['🌏', 'l'];
+        assert_eq!(pat.find_in(TEXT), Some(2..3));
+        assert_eq!(pat.rfind_in(TEXT), Some(25..26));
+        assert_eq!(pat.prefix_len("🌏x"), Some(4));
+        assert_eq!(pat.suffix_len("🌏x"), None);
+
+        let mut pat: &[char] = &[];
+        assert_eq!(pat.find_in(TEXT), None);
+
+        let chars = TEXT.chars().collect::<Vec<_>>();
+        for n in 0..chars.len() {
+            let mut pat = &chars[n..];
+            assert_std_eq!(TEXT, pat);
+            let mut pat = &chars[..n];
+            assert_std_eq!(TEXT, pat);
+        }
+    }
+
+    #[test]
+    fn test_char_pattern_array() {
+        let mut pat = ['🌏', 'l'];
+        assert_eq!(pat.find_in(TEXT), Some(2..3));
+        assert_eq!(pat.rfind_in(TEXT), Some(25..26));
+        assert_eq!(pat.prefix_len("l🌏"), Some(1));
+        assert_eq!(pat.suffix_len("l🌏"), Some(4));
+
+        let mut pat = ['H', '¢', ' '];
+        assert_std_eq!(TEXT, pat);
+        let mut pat = ['x'; 0];
+        assert_std_eq!(TEXT, pat);
+    }
+
+    #[test]
+    fn test_char_pattern_fn() {
+        let mut pat = |c: char| !c.is_ascii();
+        assert_eq!(pat.find_in(TEXT), Some(6..10));
+        assert_eq!(pat.rfind_in(TEXT), Some(28..30));
+        assert_eq!(pat.prefix_len("∈a"), Some(3));
+        assert_eq!(pat.suffix_len("∈a"), None);
+        assert_std_eq!(TEXT, pat);
+
+        let mut pat = char::is_alphanumeric;
+        assert_std_eq!(TEXT, pat);
+        assert_std_eq!("", pat);
+
+        // Stateful predicates are called once per char
+        let mut calls = 0;
+        let mut pat = |_c: char| {
+            calls += 1;
+            calls == 3
+        };
+        assert_eq!(pat.find_in(TEXT), Some(2..3));
+        assert_eq!(calls, 3);
+    }
+}
@@ -0,0 +1,289 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::CharRangesOffset;
+
+/// An iterator over [`char`]s and their start and end byte positions,
+/// which can [`peek()`] and [`peek_back()`] the next item from either end.
+///
+/// Unlike <code>[Peekable]<[CharRanges]></code>, this iterator retains
+/// [`as_str()`] and [`offset()`]. At most one item is buffered at each end.
+///
+/// See [`CharRanges::peekable_ranges()`] for more information.
+///
+/// [Peekable]: core::iter::Peekable
+/// [CharRanges]: crate::CharRanges
+/// [`peek()`]: PeekableCharRanges::peek
+/// [`peek_back()`]: PeekableCharRanges::peek_back
+/// [`as_str()`]: PeekableCharRanges::as_str
+/// [`offset()`]: PeekableCharRanges::offset
+/// [`CharRanges::peekable_ranges()`]: crate::CharRanges::peekable_ranges
+#[derive(Clone)]
+pub struct PeekableCharRanges<'a> {
+    /// The remaining text, when this iterator was created.
+    text: &'a str,
+    iter: CharRangesOffset<'a>,
+    front: Option<(Range<usize>, char)>,
+    back: Option<(Range<usize>, char)>,
+}
+
+impl<'a> PeekableCharRanges<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>) -> Self {
+        Self {
+            text: iter.as_str(),
+            iter,
+            front: None,
+            back: None,
+        }
+    }
+
+    /// Returns the remaining substring, including any [`char`]s
+    /// which have been peeked but not yet consumed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "🗻∈🌏";
+    ///
+    /// let mut chars = text.char_ranges().peekable_ranges();
+    /// assert_eq!(chars.as_str(), "🗻∈🌏");
+    ///
+    /// assert_eq!(chars.peek(), Some(&(0..4, '🗻')));
+    /// assert_eq!(chars.peek_back(), Some(&(7..11, '🌏')));
+    /// assert_eq!(chars.as_str(), "🗻∈🌏");
+    ///
+    /// assert_eq!(chars.next(), Some((0..4, '🗻')));
+    /// assert_eq!(chars.as_str(), "∈🌏");
+    /// ```
+    pub fn as_str(&self) -> &'a str {
+        let rest = self.iter.as_str();
+        // `rest` is always a subslice of `text`
+        let rest_start = (rest.as_ptr() as usize) - (self.text.as_ptr() as usize);
+
+        let start = match &self.front {
+            Some((_, c)) => rest_start - c.len_utf8(),
+            None => rest_start,
+        };
+        let end = match &self.back {
+            Some((_, c)) => rest_start + rest.len() + c.len_utf8(),
+            None => rest_start + rest.len(),
+        };
+
+        &self.text[start..end]
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+
+    /// Returns a reference to the next [`char`] and its start and end
+    /// byte positions, without consuming it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "ab";
+    ///
+    /// let mut chars = text.char_ranges().peekable_ranges();
+    /// assert_eq!(chars.peek(), Some(&(0..1, 'a')));
+    /// assert_eq!(chars.next(), Some((0..1, 'a')));
+    /// assert_eq!(chars.peek(), Some(&(1..2, 'b')));
+    /// assert_eq!(chars.next(), Some((1..2, 'b')));
+    /// assert_eq!(chars.peek(), None);
+    /// ```
+    pub fn peek(&mut self) -> Option<&(Range<usize>, char)> {
+        if self.front.is_none() {
+            self.front = self.iter.next();
+            if self.front.is_none() {
+                return self.back.as_ref();
+            }
+        }
+        self.front.as_ref()
+    }
+
+    /// Returns a reference to the last [`char`] and its start and end
+    /// byte positions, without consuming it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "ab";
+    ///
+    /// let mut chars = text.char_ranges().peekable_ranges();
+    /// assert_eq!(chars.peek_back(), Some(&(1..2, 'b')));
+    /// assert_eq!(chars.next_back(), Some((1..2, 'b')));
+    /// assert_eq!(chars.peek_back(), Some(&(0..1, 'a')));
+    /// assert_eq!(chars.next_back(), Some((0..1, 'a')));
+    /// assert_eq!(chars.peek_back(), None);
+    /// ```
+    pub fn peek_back(&mut self) -> Option<&(Range<usize>, char)> {
+        if self.back.is_none() {
+            self.back = self.iter.next_back();
+            if self.back.is_none() {
+                return self.front.as_ref();
+            }
+        }
+        self.back.as_ref()
+    }
+}
+
+impl Iterator for PeekableCharRanges<'_> {
+    type Item = (Range<usize>, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.front
+            .take()
+            .or_else(|| self.iter.next())
+            .or_else(|| self.back.take())
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        let buffered = (self.front.is_some() as usize) + (self.back.is_some() as usize);
+        self.iter.count() + buffered
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let buffered = (self.front.is_some() as usize) + (self.back.is_some() as usize);
+        let (lower, upper) = self.iter.size_hint();
+        (
+            lower.saturating_add(buffered),
+            upper.and_then(|upper| upper.checked_add(buffered)),
+        )
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<(Range<usize>, char)> {
+        self.next_back()
+    }
+}
+
+impl DoubleEndedIterator for PeekableCharRanges<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.back
+            .take()
+            .or_else(|| self.iter.next_back())
+            .or_else(|| self.front.take())
+    }
+}
+
+impl FusedIterator for PeekableCharRanges<'_> {}
+
+impl fmt::Debug for PeekableCharRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PeekableCharRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::CharRangesExt;
+
+    #[test]
+    fn test_peekable() {
+        let text = "Hello 🗻12∈45🌏 World";
+
+        let mut chars = text.char_ranges().peekable_ranges();
+        let mut expected = text.char_ranges();
+        loop {
+            assert_eq!(chars.as_str(), expected.as_str());
+            let peeked = chars.peek().cloned();
+            assert_eq!(peeked, expected.peek());
+            assert_eq!(chars.as_str(), expected.as_str());
+
+            assert_eq!(chars.next(), expected.next());
+            if peeked.is_none() {
+                break;
+            }
+        }
+    }
+
+    #[test]
+    fn test_peekable_back() {
+        let text = "Hello 🗻12∈45🌏 World";
+
+        let mut chars = text.char_ranges().peekable_ranges();
+        let mut expected = text.char_ranges();
+        loop {
+            assert_eq!(chars.as_str(), expected.as_str());
+            let peeked = chars.peek_back().cloned();
+            assert_eq!(peeked, expected.peek_back());
+            assert_eq!(chars.as_str(), expected.as_str());
+
+            assert_eq!(chars.next_back(), expected.next_back());
+            if peeked.is_none() {
+                break;
+            }
+        }
+    }
+
+    #[test]
+    fn test_peekable_both_ends() {
+        let text = "🗻∈🌏";
+
+        let mut chars = text.char_ranges().peekable_ranges();
+        assert_eq!(chars.peek(), Some(&(0..4, '🗻')));
+        assert_eq!(chars.peek_back(), Some(&(7..11, '🌏')));
+        assert_eq!(chars.as_str(), "🗻∈🌏");
+        assert_eq!(chars.size_hint().0, 3);
+
+        assert_eq!(chars.next(), Some((0..4, '🗻')));
+        assert_eq!(chars.as_str(), "∈🌏");
+
+        assert_eq!(chars.peek(), Some(&(4..7, '∈')));
+        assert_eq!(chars.as_str(), "∈🌏");
+
+        // Only the peeked items remain
+        assert_eq!(chars.peek_back(), Some(&(7..11, '🌏')));
+        assert_eq!(chars.as_str(), "∈🌏");
+        assert_eq!(chars.clone().count(), 2);
+
+        assert_eq!(chars.next_back(), Some((7..11, '🌏')));
+        assert_eq!(chars.as_str(), "∈");
+
+        // The last item is buffered at the front,
+        // so it is what the back sees as well
+        assert_eq!(chars.peek_back(), Some(&(4..7, '∈')));
+        assert_eq!(chars.as_str(), "∈");
+
+        assert_eq!(chars.next_back(), Some((4..7, '∈')));
+        assert_eq!(chars.as_str(), "");
+
+        assert_eq!(chars.peek(), None);
+        assert_eq!(chars.peek_back(), None);
+        assert_eq!(chars.next(), None);
+        assert_eq!(chars.next_back(), None);
+    }
+
+    #[test]
+    fn test_peekable_offset() {
+        let text = "Hello 👋 World 🌏";
+
+        let start = 11;
+        let mut chars = text[start..].char_ranges_offset(start).peekable_ranges();
+        assert_eq!(chars.offset(), start);
+
+        assert_eq!(chars.peek(), Some(&(11..12, 'W')));
+        assert_eq!(chars.peek_back(), Some(&(17..21, '🌏')));
+        assert_eq!(chars.as_str(), "World 🌏");
+
+        assert_eq!(chars.next_back(), Some((17..21, '🌏')));
+        assert_eq!(chars.as_str(), "World ");
+    }
+}
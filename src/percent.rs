@@ -0,0 +1,388 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::{is_utf8_continuation_byte, utf8_char_width, CharRanges, CharRangesOffset};
+
+/// An error produced by [`PercentDecodeRanges`] for an invalid percent
+/// escape, with the start and end byte positions of the escapes.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PercentDecodeError {
+    /// The text ended in the middle of an escape, e.g. `%` or `%E`.
+    Truncated(Range<usize>),
+    /// A [`char`] of an escape is not a hex digit, e.g. `%G1`.
+    InvalidHex(Range<usize>),
+    /// A run of escapes decodes to invalid UTF-8, e.g. `%FF`, `%C0%80`,
+    /// or `%E2%9C` followed by anything but a continuation byte escape.
+    InvalidUtf8(Range<usize>),
+}
+
+impl PercentDecodeError {
+    /// Returns the start and end byte positions of the escapes.
+    #[inline]
+    pub fn range(&self) -> Range<usize> {
+        match self {
+            Self::Truncated(r) | Self::InvalidHex(r) | Self::InvalidUtf8(r) => r.clone(),
+        }
+    }
+}
+
+impl fmt::Display for PercentDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            Self::Truncated(_) => "truncated percent escape",
+            Self::InvalidHex(_) => "invalid percent escape",
+            Self::InvalidUtf8(_) => "percent escapes of invalid UTF-8",
+        };
+        let r = self.range();
+        write!(f, "{kind} at bytes {}..{}", r.start, r.end)
+    }
+}
+
+impl core::error::Error for PercentDecodeError {}
+
+/// The kind of a [`PercentDecodeError`], before the range of the
+/// escapes is known.
+#[derive(Clone, Copy)]
+enum ErrorKind {
+    Truncated,
+    InvalidHex,
+    InvalidUtf8,
+}
+
+impl ErrorKind {
+    #[inline]
+    fn with_range(self, r: Range<usize>) -> PercentDecodeError {
+        match self {
+            Self::Truncated => PercentDecodeError::Truncated(r),
+            Self::InvalidHex => PercentDecodeError::InvalidHex(r),
+            Self::InvalidUtf8 => PercentDecodeError::InvalidUtf8(r),
+        }
+    }
+}
+
+/// An iterator over the percent-decoded [`char`]s of a string, e.g. of a
+/// URL or a query string, and the start and end byte positions of their
+/// source.
+///
+/// Plain [`char`]s are produced with their own range, while `%XX` escapes
+/// are produced with the range of all the escapes of the [`char`], e.g.
+/// `%E2%9C%93` decodes to `'✓'` with a range of 9 bytes. If enabled with
+/// [`plus_as_space()`](Self::plus_as_space), then `'+'` is decoded to
+/// `' '`, like in `application/x-www-form-urlencoded`.
+///
+/// Invalid escapes produce a [`PercentDecodeError`] with the range of the
+/// escapes, after which iteration continues. The range of an invalid
+/// escape ends before the first [`char`] that made it invalid, which is
+/// then produced as is, or starts the next escape.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRangesExt::percent_decode_ranges()`] for more information.
+///
+/// [`CharRangesExt::percent_decode_ranges()`]: crate::CharRangesExt::percent_decode_ranges
+#[derive(Clone)]
+pub struct PercentDecodeRanges<'a> {
+    iter: CharRangesOffset<'a>,
+    plus_as_space: bool,
+}
+
+impl<'a> PercentDecodeRanges<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>) -> Self {
+        Self {
+            iter,
+            plus_as_space: false,
+        }
+    }
+
+    /// Sets whether `'+'` is decoded to `' '`, which defaults to `false`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "a+b%2B";
+    ///
+    /// let mut chars = text.percent_decode_ranges().plus_as_space(true);
+    /// assert_eq!(chars.next(), Some(Ok((0..1, 'a'))));
+    /// assert_eq!(chars.next(), Some(Ok((1..2, ' '))));
+    /// assert_eq!(chars.next(), Some(Ok((2..3, 'b'))));
+    /// assert_eq!(chars.next(), Some(Ok((3..6, '+'))));
+    /// assert_eq!(chars.next(), None);
+    /// ```
+    #[inline]
+    pub fn plus_as_space(mut self, plus_as_space: bool) -> Self {
+        self.plus_as_space = plus_as_space;
+        self
+    }
+
+    /// Returns `true` if `'+'` is decoded to `' '`.
+    #[inline]
+    pub fn is_plus_as_space(&self) -> bool {
+        self.plus_as_space
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+
+    /// Decodes the escapes of a [`char`], starting with the `%` of
+    /// the first escape.
+    fn decode(&mut self) -> Result<char, ErrorKind> {
+        let chars = &mut self.iter.iter;
+
+        let first = escape(chars)?;
+        if first < 0x80 {
+            return Ok(char::from(first));
+        } else if is_utf8_continuation_byte(first) || (first >= 0xF8) {
+            return Err(ErrorKind::InvalidUtf8);
+        }
+
+        let mut buf = [first, 0, 0, 0];
+        let width = utf8_char_width(first);
+        for b in &mut buf[1..width] {
+            *b = continuation_escape(chars).ok_or(ErrorKind::InvalidUtf8)?;
+        }
+
+        let s = core::str::from_utf8(&buf[..width]).map_err(|_| ErrorKind::InvalidUtf8)?;
+        s.chars().next().ok_or(ErrorKind::InvalidUtf8)
+    }
+}
+
+/// Consumes a `%XX` escape.
+fn escape(chars: &mut CharRanges<'_>) -> Result<u8, ErrorKind> {
+    chars.next_if_eq('%').ok_or(ErrorKind::InvalidHex)?;
+    let hi = hex_digit(chars)?;
+    let lo = hex_digit(chars)?;
+    Ok((hi << 4) | lo)
+}
+
+/// Consumes a single hex digit, or nothing if the next [`char`] is not one.
+fn hex_digit(chars: &mut CharRanges<'_>) -> Result<u8, ErrorKind> {
+    let (_r, c) = chars.peek().ok_or(ErrorKind::Truncated)?;
+    let digit = c.to_digit(16).ok_or(ErrorKind::InvalidHex)?;
+    chars.next();
+    Ok(digit as u8)
+}
+
+/// Consumes a `%XX` escape of a UTF-8 continuation byte, or nothing if
+/// the next [`char`]s are not one.
+fn continuation_escape(chars: &mut CharRanges<'_>) -> Option<u8> {
+    let mut iter = *chars;
+    let b = escape(&mut iter).ok()?;
+    if is_utf8_continuation_byte(b) {
+        *chars = iter;
+        Some(b)
+    } else {
+        None
+    }
+}
+
+impl Iterator for PercentDecodeRanges<'_> {
+    type Item = Result<(Range<usize>, char), PercentDecodeError>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (r, c) = self.iter.iter.peek()?;
+        if c != '%' {
+            self.iter.iter.next();
+            let c = if self.plus_as_space && (c == '+') {
+                ' '
+            } else {
+                c
+            };
+            return Some(Ok((self.iter.apply_offset(r), c)));
+        }
+
+        let res = self.decode();
+        let r = self.iter.apply_offset(r.start..self.iter.iter.start);
+        Some(match res {
+            Ok(c) => Ok((r, c)),
+            Err(kind) => Err(kind.with_range(r)),
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, upper) = self.iter.size_hint();
+        (usize::from(!self.as_str().is_empty()), upper)
+    }
+}
+
+impl FusedIterator for PercentDecodeRanges<'_> {}
+
+impl fmt::Debug for PercentDecodeRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PercentDecodeRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::String;
+    use std::vec::Vec;
+
+    use percent_encoding::{percent_decode_str, utf8_percent_encode, NON_ALPHANUMERIC};
+
+    use super::PercentDecodeError::{self, InvalidHex, InvalidUtf8, Truncated};
+    use crate::CharRangesExt;
+
+    const CHARS: [char; 12] = [
+        'a', '%', '+', ' ', '\n', '\u{80}', 'é', '∈', '⊂', '✓', '🌏', '🗻',
+    ];
+
+    fn decode(text: &str) -> Vec<Result<(core::ops::Range<usize>, char), PercentDecodeError>> {
+        text.percent_decode_ranges().collect()
+    }
+
+    #[test]
+    fn test_percent_decode_ranges() {
+        let text = "a%20b%E2%9C%93%F0%9F%8C%8F+";
+        assert_eq!(
+            decode(text),
+            [
+                Ok((0..1, 'a')),
+                Ok((1..4, ' ')),
+                Ok((4..5, 'b')),
+                Ok((5..14, '✓')),
+                Ok((14..26, '🌏')),
+                Ok((26..27, '+')),
+            ]
+        );
+
+        // Lowercase hex digits and literal multi-byte chars
+        let text = "%c3%a9🌏%7e";
+        assert_eq!(
+            decode(text),
+            [Ok((0..6, 'é')), Ok((6..10, '🌏')), Ok((10..13, '~'))]
+        );
+
+        assert_eq!(decode(""), []);
+    }
+
+    #[test]
+    fn test_percent_decode_ranges_plus_as_space() {
+        let text = "q=a+b%2B";
+
+        let chars = text.percent_decode_ranges().plus_as_space(true);
+        assert!(chars.is_plus_as_space());
+        let s = chars.map(|res| res.unwrap().1).collect::<String>();
+        assert_eq!(s, "q=a b+");
+
+        let chars = text.percent_decode_ranges();
+        assert!(!chars.is_plus_as_space());
+        let s = chars.map(|res| res.unwrap().1).collect::<String>();
+        assert_eq!(s, "q=a+b+");
+    }
+
+    #[test]
+    fn test_percent_decode_ranges_errors() {
+        #[rustfmt::skip]
+        let cases = [
+            ("%G1", Vec::from([Err(InvalidHex(0..1)), Ok((1..2, 'G')), Ok((2..3, '1'))])),
+            ("%4g", Vec::from([Err(InvalidHex(0..2)), Ok((2..3, 'g'))])),
+            ("%%41", Vec::from([Err(InvalidHex(0..1)), Ok((1..4, 'A'))])),
+            ("%4🌏", Vec::from([Err(InvalidHex(0..2)), Ok((2..6, '🌏'))])),
+            ("%FF", Vec::from([Err(InvalidUtf8(0..3))])),
+            ("%80a", Vec::from([Err(InvalidUtf8(0..3)), Ok((3..4, 'a'))])),
+            // Overlong and surrogate
+            ("%C0%80", Vec::from([Err(InvalidUtf8(0..6))])),
+            ("%ED%A0%80", Vec::from([Err(InvalidUtf8(0..9))])),
+            // Missing continuation bytes
+            ("%E2%9Cx", Vec::from([Err(InvalidUtf8(0..6)), Ok((6..7, 'x'))])),
+            ("%E2%41", Vec::from([Err(InvalidUtf8(0..3)), Ok((3..6, 'A'))])),
+            ("%E2%9G", Vec::from([Err(InvalidUtf8(0..3)), Err(InvalidHex(3..5)), Ok((5..6, 'G'))])),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(decode(text), expected, "{text:?}");
+        }
+    }
+
+    #[test]
+    fn test_percent_decode_ranges_end_of_input() {
+        assert_eq!(decode("%"), [Err(Truncated(0..1))]);
+        assert_eq!(decode("a%4"), [Ok((0..1, 'a')), Err(Truncated(1..3))]);
+        assert_eq!(
+            decode("%E2%9C%9"),
+            [Err(InvalidUtf8(0..6)), Err(Truncated(6..8))]
+        );
+        assert_eq!(decode("%E2%9C"), [Err(InvalidUtf8(0..6))]);
+        assert_eq!(decode("%41%"), [Ok((0..3, 'A')), Err(Truncated(3..4))]);
+
+        let err = decode("abc%E2%9C").pop().unwrap().unwrap_err();
+        assert_eq!(
+            std::format!("{err}"),
+            "percent escapes of invalid UTF-8 at bytes 3..9"
+        );
+    }
+
+    #[test]
+    fn test_percent_decode_ranges_offset() {
+        let text = "?q=%E2%88%88";
+
+        let chars = text[3..].char_ranges_offset(3).percent_decode();
+        assert_eq!(chars.offset(), 3);
+        assert!(chars.eq([Ok((3..12, '∈'))]));
+    }
+
+    #[test]
+    fn test_percent_decode_ranges_random() {
+        let mut next = crate::test_util::random();
+
+        for _ in 0..500 {
+            let len = next() % 24;
+            let decoded = (0..len)
+                .map(|_| CHARS[next() % CHARS.len()])
+                .collect::<String>();
+
+            // Randomly encode some chars
+            let mut text = String::new();
+            for c in decoded.chars() {
+                let mut buf = [0; 4];
+                let s = c.encode_utf8(&mut buf);
+                if (c == '%') || next().is_multiple_of(2) {
+                    text.extend(utf8_percent_encode(s, NON_ALPHANUMERIC));
+                } else {
+                    text.push(c);
+                }
+            }
+
+            let reference = percent_decode_str(&text).decode_utf8().unwrap();
+            assert_eq!(reference, decoded);
+
+            let chars = text
+                .percent_decode_ranges()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            assert!(chars.iter().map(|&(_, c)| c).eq(decoded.chars()));
+
+            // The ranges tile the source, and each decodes on its own
+            let mut end = 0;
+            for (r, c) in chars {
+                assert_eq!(r.start, end);
+                end = r.end;
+
+                let mut source = text[r].percent_decode_ranges();
+                assert_eq!(source.next().unwrap().unwrap().1, c);
+                assert_eq!(source.next(), None);
+            }
+            assert_eq!(end, text.len());
+        }
+    }
+}
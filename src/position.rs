@@ -0,0 +1,407 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::CharRangesOffset;
+
+/// A line and column position in a text.
+///
+/// By default, both are zero-based and the `column` counts [`char`]s, not
+/// bytes, i.e. multi-byte [`char`]s like `'🗻'` still only count as a
+/// single column. The [`Base`] and [`ColumnUnit`] are configurable, e.g.
+/// with [`CharPositions::with_base()`] and [`CharPositions::with_column_unit()`].
+///
+/// Only `'\n'` starts a new line, so in `"\r\n"` the `'\r'` is the last
+/// column of its line.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LineColumn {
+    /// Line number, zero-based by default.
+    pub line: usize,
+    /// Column, zero-based and counted in [`char`]s by default.
+    pub column: usize,
+}
+
+impl LineColumn {
+    #[inline]
+    pub const fn new(line: usize, column: usize) -> Self {
+        Self { line, column }
+    }
+}
+
+/// Whether the lines and columns of a [`LineColumn`] start at `0` or `1`.
+///
+/// E.g. the Language Server Protocol uses zero-based lines and columns,
+/// while most compilers report one-based lines and columns.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Base {
+    /// The first line and column is `0`.
+    #[default]
+    Zero,
+    /// The first line and column is `1`.
+    One,
+}
+
+impl Base {
+    /// Returns the first line and column, i.e. `0` or `1`.
+    #[inline]
+    pub const fn first(self) -> usize {
+        match self {
+            Self::Zero => 0,
+            Self::One => 1,
+        }
+    }
+}
+
+/// What the column of a [`LineColumn`] counts.
+///
+/// E.g. the Language Server Protocol defaults to UTF-16 code units, while
+/// clangd reports UTF-8 bytes.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::ColumnUnit;
+///
+/// assert_eq!(ColumnUnit::Chars.len_of('🗻'), 1);
+/// assert_eq!(ColumnUnit::Bytes.len_of('🗻'), 4);
+/// assert_eq!(ColumnUnit::Utf16.len_of('🗻'), 2);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ColumnUnit {
+    /// Columns count [`char`]s.
+    #[default]
+    Chars,
+    /// Columns count UTF-8 bytes.
+    Bytes,
+    /// Columns count UTF-16 code units.
+    Utf16,
+}
+
+impl ColumnUnit {
+    /// Returns the number of columns occupied by `c`.
+    #[inline]
+    pub const fn len_of(self, c: char) -> usize {
+        match self {
+            Self::Chars => 1,
+            Self::Bytes => c.len_utf8(),
+            Self::Utf16 => c.len_utf16(),
+        }
+    }
+}
+
+/// An iterator over [`char`]s, their start and end byte positions,
+/// and their [`LineColumn`] position.
+///
+/// The [`LineColumn`] is tracked incrementally, so this iterator is
+/// forward-only, i.e. it does not implement [`DoubleEndedIterator`],
+/// as the position of the last [`char`] isn't known without scanning.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRanges::char_positions()`] for more information.
+///
+/// [`CharRanges::char_positions()`]: crate::CharRanges::char_positions
+#[derive(Clone)]
+pub struct CharPositions<'a> {
+    iter: CharRangesOffset<'a>,
+    /// The position of the next [`char`].
+    pos: LineColumn,
+    base: Base,
+    unit: ColumnUnit,
+}
+
+impl<'a> CharPositions<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>) -> Self {
+        Self::with_start(iter, LineColumn::default())
+    }
+
+    /// Creates an iterator over [`char`]s in `iter`, where the first
+    /// [`char`] is at the [`LineColumn`] `start`.
+    ///
+    /// This allows resuming mid-file, where `iter` is e.g. created with
+    /// <code>text[pos..].[char_ranges_offset]\(pos)</code>.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{CharPositions, CharRangesExt, LineColumn};
+    ///
+    /// let text = "foo\nbar baz";
+    ///
+    /// let start = 8;
+    /// let iter = text[start..].char_ranges_offset(start);
+    ///
+    /// let mut chars = CharPositions::with_start(iter, LineColumn::new(1, 4));
+    /// assert_eq!(chars.next(), Some((8..9, LineColumn::new(1, 4), 'b')));
+    /// assert_eq!(chars.next(), Some((9..10, LineColumn::new(1, 5), 'a')));
+    /// ```
+    ///
+    /// [char_ranges_offset]: crate::CharRangesExt::char_ranges_offset
+    #[inline]
+    pub fn with_start(iter: CharRangesOffset<'a>, start: LineColumn) -> Self {
+        Self {
+            iter,
+            pos: start,
+            base: Base::Zero,
+            unit: ColumnUnit::Chars,
+        }
+    }
+
+    /// Sets whether lines and columns are zero-based or one-based.
+    ///
+    /// The current position is converted from the previous [`Base`],
+    /// i.e. a zero-based start of `(1, 4)` becomes `(2, 5)`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{Base, CharRangesExt, LineColumn};
+    ///
+    /// let mut chars = "a\nb".char_ranges().char_positions().with_base(Base::One);
+    /// assert_eq!(chars.next(), Some((0..1, LineColumn::new(1, 1), 'a')));
+    /// assert_eq!(chars.next(), Some((1..2, LineColumn::new(1, 2), '\n')));
+    /// assert_eq!(chars.next(), Some((2..3, LineColumn::new(2, 1), 'b')));
+    /// ```
+    #[inline]
+    pub fn with_base(mut self, base: Base) -> Self {
+        self.pos.line = self.pos.line - self.base.first() + base.first();
+        self.pos.column = self.pos.column - self.base.first() + base.first();
+        self.base = base;
+        self
+    }
+
+    /// Sets what columns count.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{CharRangesExt, ColumnUnit, LineColumn};
+    ///
+    /// let mut chars = "🗻a".char_ranges().char_positions().with_column_unit(ColumnUnit::Utf16);
+    /// assert_eq!(chars.next(), Some((0..4, LineColumn::new(0, 0), '🗻')));
+    /// assert_eq!(chars.next(), Some((4..5, LineColumn::new(0, 2), 'a')));
+    /// ```
+    #[inline]
+    pub fn with_column_unit(mut self, unit: ColumnUnit) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    /// Returns whether lines and columns are zero-based or one-based.
+    #[inline]
+    pub fn base(&self) -> Base {
+        self.base
+    }
+
+    /// Returns what columns count.
+    #[inline]
+    pub fn column_unit(&self) -> ColumnUnit {
+        self.unit
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+
+    /// Returns the [`LineColumn`] of the next [`char`], or the position
+    /// after the last [`char`] if the iterator is exhausted.
+    #[inline]
+    pub fn line_column(&self) -> LineColumn {
+        self.pos
+    }
+}
+
+impl Iterator for CharPositions<'_> {
+    type Item = (Range<usize>, LineColumn, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (r, c) = self.iter.next()?;
+        let pos = self.pos;
+        if c == '\n' {
+            self.pos.line += 1;
+            self.pos.column = self.base.first();
+        } else {
+            self.pos.column += self.unit.len_of(c);
+        }
+        Some((r, pos, c))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.iter.count()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl FusedIterator for CharPositions<'_> {}
+
+impl fmt::Debug for CharPositions<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CharPositions(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use super::{Base, CharPositions, ColumnUnit, LineColumn};
+    use crate::CharRangesExt;
+
+    fn positions(text: &str) -> Vec<(usize, usize, char)> {
+        text.char_ranges()
+            .char_positions()
+            .map(|(_r, pos, c)| (pos.line, pos.column, c))
+            .collect()
+    }
+
+    #[test]
+    fn test_char_positions() {
+        let text = "ab\ncd\n";
+        assert_eq!(
+            positions(text),
+            [
+                (0, 0, 'a'),
+                (0, 1, 'b'),
+                (0, 2, '\n'),
+                (1, 0, 'c'),
+                (1, 1, 'd'),
+                (1, 2, '\n'),
+            ]
+        );
+
+        let mut chars = text.char_ranges().char_positions();
+        chars.by_ref().for_each(drop);
+        assert_eq!(chars.line_column(), LineColumn::new(2, 0));
+    }
+
+    #[test]
+    fn test_char_positions_crlf() {
+        let text = "a\r\nb\r\n";
+        assert_eq!(
+            positions(text),
+            [
+                (0, 0, 'a'),
+                (0, 1, '\r'),
+                (0, 2, '\n'),
+                (1, 0, 'b'),
+                (1, 1, '\r'),
+                (1, 2, '\n'),
+            ]
+        );
+
+        // A lone `'\r'` does not start a new line
+        assert_eq!(positions("a\rb"), [(0, 0, 'a'), (0, 1, '\r'), (0, 2, 'b')]);
+    }
+
+    #[test]
+    fn test_char_positions_no_final_newline() {
+        let text = "foo\nbar";
+
+        let mut chars = text.char_ranges().char_positions();
+        let last = chars.by_ref().last();
+        assert_eq!(last, Some((6..7, LineColumn::new(1, 2), 'r')));
+        assert_eq!(chars.line_column(), LineColumn::new(1, 3));
+        assert_eq!(chars.as_str(), "");
+    }
+
+    #[test]
+    fn test_char_positions_multi_byte() {
+        let text = "🗻∈🌏\n¢a";
+
+        let chars = text.char_ranges().char_positions().collect::<Vec<_>>();
+        assert_eq!(
+            chars,
+            [
+                (0..4, LineColumn::new(0, 0), '🗻'),
+                (4..7, LineColumn::new(0, 1), '∈'),
+                (7..11, LineColumn::new(0, 2), '🌏'),
+                (11..12, LineColumn::new(0, 3), '\n'),
+                (12..14, LineColumn::new(1, 0), '¢'),
+                (14..15, LineColumn::new(1, 1), 'a'),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_char_positions_resume() {
+        let text = "foo\n🗻∈🌏\nbar";
+
+        let mut chars = text.char_ranges().char_positions();
+        let (r, pos, _c) = chars.nth(5).unwrap();
+        assert_eq!((r.clone(), pos), (8..11, LineColumn::new(1, 1)));
+
+        // Resuming from the middle, produces the same positions
+        let iter = text[r.start..].char_ranges_offset(r.start);
+        let resumed = CharPositions::with_start(iter, pos);
+        assert_eq!(resumed.as_str(), "∈🌏\nbar");
+        assert_eq!(resumed.offset(), 8);
+
+        let expected = text.char_ranges().char_positions().skip(5);
+        assert!(resumed.eq(expected));
+    }
+
+    #[test]
+    fn test_char_positions_units() {
+        // Columns after a 4 byte emoji
+        let text = "🗻a\n🌏";
+
+        #[rustfmt::skip]
+        let expected = [
+            (Base::Zero, ColumnUnit::Chars, (0, 1), (1, 1)),
+            (Base::Zero, ColumnUnit::Bytes, (0, 4), (1, 4)),
+            (Base::Zero, ColumnUnit::Utf16, (0, 2), (1, 2)),
+            (Base::One,  ColumnUnit::Chars, (1, 2), (2, 2)),
+            (Base::One,  ColumnUnit::Bytes, (1, 5), (2, 5)),
+            (Base::One,  ColumnUnit::Utf16, (1, 3), (2, 3)),
+        ];
+
+        for (base, unit, after_first, end) in expected {
+            let mut chars = text
+                .char_ranges()
+                .char_positions()
+                .with_base(base)
+                .with_column_unit(unit);
+            assert_eq!(chars.base(), base);
+            assert_eq!(chars.column_unit(), unit);
+
+            let first = LineColumn::new(base.first(), base.first());
+            assert_eq!(chars.next(), Some((0..4, first, '🗻')));
+
+            let (line, column) = after_first;
+            assert_eq!(
+                chars.next(),
+                Some((4..5, LineColumn::new(line, column), 'a'))
+            );
+
+            chars.by_ref().for_each(drop);
+            let (line, column) = end;
+            assert_eq!(chars.line_column(), LineColumn::new(line, column));
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use core::iter;
+use core::ops::Range;
+
+/// Returns the byte range of `needle` in `haystack`, if `needle` occurs
+/// exactly once.
+///
+/// Overlapping occurrences are counted, e.g. `"aa"` occurs twice in
+/// `"aaa"`. Returns `None` if `needle` occurs zero or multiple times.
+///
+/// See [`range_of!`](crate::range_of!) for a panicking alternative
+/// intended for tests.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::range_of_unique;
+///
+/// let text = "let 🗻 = foo(🌏, 🌏);";
+/// assert_eq!(range_of_unique(text, "🗻"), Some(4..8));
+/// assert_eq!(range_of_unique(text, "foo"), Some(11..14));
+///
+/// // Occurs twice
+/// assert_eq!(range_of_unique(text, "🌏"), None);
+/// assert_eq!(range_of_unique(text, "bar"), None);
+/// ```
+pub fn range_of_unique(haystack: &str, needle: &str) -> Option<Range<usize>> {
+    let mut occurrences = occurrences(haystack, needle);
+    match (occurrences.next(), occurrences.next()) {
+        (Some(r), None) => Some(r),
+        _ => None,
+    }
+}
+
+/// Returns the byte range of the `n`th occurrence of `needle` in
+/// `haystack`, where `n` is zero-based.
+///
+/// Overlapping occurrences are counted, e.g. `"aa"` occurs twice in
+/// `"aaa"`. Returns `None` if `needle` occurs `n` times or less.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::range_of_nth;
+///
+/// let text = "foo(🌏, 🌏)";
+/// assert_eq!(range_of_nth(text, "🌏", 0), Some(4..8));
+/// assert_eq!(range_of_nth(text, "🌏", 1), Some(10..14));
+/// assert_eq!(range_of_nth(text, "🌏", 2), None);
+/// ```
+pub fn range_of_nth(haystack: &str, needle: &str, n: usize) -> Option<Range<usize>> {
+    occurrences(haystack, needle).nth(n)
+}
+
+/// Returns an iterator over the byte ranges of all occurrences of
+/// `needle` in `haystack`, including overlapping occurrences.
+fn occurrences<'a>(haystack: &'a str, needle: &'a str) -> impl Iterator<Item = Range<usize>> + 'a {
+    let mut pos = 0;
+    iter::from_fn(move || {
+        let start = pos + haystack.get(pos..)?.find(needle)?;
+        // The next occurrence can start at the next `char`
+        pos = start + haystack[start..].chars().next().map_or(1, char::len_utf8);
+        Some(start..(start + needle.len()))
+    })
+}
+
+/// Implementation of [`range_of!`] with 2 arguments.
+#[doc(hidden)]
+#[track_caller]
+pub fn __range_of(haystack: &str, needle: &str) -> Range<usize> {
+    let mut occurrences = occurrences(haystack, needle);
+    match (occurrences.next(), occurrences.next()) {
+        (Some(r), None) => r,
+        (None, _) => panic!("{needle:?} does not occur in {haystack:?}"),
+        (Some(_), Some(_)) => {
+            let count = occurrences.count() + 2;
+            panic!(
+                "{needle:?} occurs {count} times in {haystack:?}, \
+                select an occurrence with `range_of!(haystack, needle, n)`"
+            )
+        }
+    }
+}
+
+/// Implementation of [`range_of!`] with 3 arguments.
+#[doc(hidden)]
+#[track_caller]
+pub fn __range_of_nth(haystack: &str, needle: &str, n: usize) -> Range<usize> {
+    match range_of_nth(haystack, needle, n) {
+        Some(r) => r,
+        None => {
+            let count = occurrences(haystack, needle).count();
+            panic!("occurrence {n} of {needle:?} does not exist, it occurs {count} times in {haystack:?}")
+        }
+    }
+}
+
+/// Returns the byte range of a literal substring, for writing range
+/// assertions in tests without counting bytes.
+///
+/// - `range_of!(haystack, needle)` returns the range of `needle`, and
+///   panics if `needle` occurs zero or multiple times in `haystack`. So
+///   an assertion can never silently use the wrong occurrence.
+/// - `range_of!(haystack, needle, n)` returns the range of the `n`th
+///   occurrence of `needle`, where `n` is zero-based, and panics if it
+///   doesn't exist.
+///
+/// Overlapping occurrences are counted, e.g. `"aa"` occurs twice in
+/// `"aaa"`. This only depends on `core`, so it works in `no_std` tests.
+///
+/// See [`range_of_unique()`] and [`range_of_nth()`] for non-panicking
+/// alternatives.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::{range_of, CharRangesExt};
+///
+/// let text = "let 🗻 = foo(🌏, 🌏);";
+///
+/// let mut chars = text.char_ranges();
+/// assert_eq!(chars.nth(4), Some((range_of!(text, "🗻"), '🗻')));
+///
+/// assert_eq!(range_of!(text, "foo"), 11..14);
+/// assert_eq!(range_of!(text, "🌏", 1), 21..25);
+/// ```
+///
+/// ```rust,should_panic
+/// # use char_ranges::range_of;
+/// let text = "let 🗻 = foo(🌏, 🌏);";
+///
+/// // Panics, as '🌏' occurs twice
+/// range_of!(text, "🌏");
+/// ```
+#[macro_export]
+macro_rules! range_of {
+    ($haystack:expr, $needle:expr $(,)?) => {
+        $crate::__range_of($haystack, $needle)
+    };
+    ($haystack:expr, $needle:expr, $n:expr $(,)?) => {
+        $crate::__range_of_nth($haystack, $needle, $n)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{range_of_nth, range_of_unique};
+
+    const TEXT: &str = "Hello 🗻12∈45🌏 World ¢";
+
+    #[test]
+    fn test_range_of() {
+        assert_eq!(range_of!(TEXT, "Hello"), 0..5);
+        assert_eq!(range_of!(TEXT, "🗻"), 6..10);
+        assert_eq!(range_of!(TEXT, "∈"), 12..15);
+        assert_eq!(range_of!(TEXT, "🌏 World"), 17..27);
+        assert_eq!(range_of!(TEXT, "¢"), 28..30);
+        assert_eq!(&TEXT[range_of!(TEXT, "12∈45")], "12∈45");
+
+        assert_eq!(range_of!(TEXT, "l", 0), 2..3);
+        assert_eq!(range_of!(TEXT, "l", 1), 3..4);
+        assert_eq!(range_of!(TEXT, "l", 2), 25..26);
+        assert_eq!(range_of!(TEXT, "🗻", 0), 6..10);
+
+        assert_eq!(range_of!("", ""), 0..0);
+    }
+
+    #[test]
+    fn test_range_of_unique() {
+        assert_eq!(range_of_unique(TEXT, "🌏"), Some(17..21));
+        assert_eq!(range_of_unique(TEXT, "o"), None);
+        assert_eq!(range_of_unique(TEXT, "foo"), None);
+        assert_eq!(range_of_unique(TEXT, ""), None);
+
+        // Overlapping occurrences
+        assert_eq!(range_of_unique("aaa", "aa"), None);
+        assert_eq!(range_of_nth("aaa", "aa", 1), Some(1..3));
+        assert_eq!(range_of_nth("🌏🌏🌏", "🌏🌏", 1), Some(4..12));
+        assert_eq!(range_of_nth("ab", "", 2), Some(2..2));
+        assert_eq!(range_of_nth("ab", "", 3), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "\"l\" occurs 3 times in \"Hello 🗻12∈45🌏 World ¢\"")]
+    fn test_range_of_ambiguous() {
+        range_of!(TEXT, "l");
+    }
+
+    #[test]
+    #[should_panic(expected = "\"aa\" occurs 2 times in \"aaa\"")]
+    fn test_range_of_ambiguous_overlapping() {
+        range_of!("aaa", "aa");
+    }
+
+    #[test]
+    #[should_panic(expected = "\"foo\" does not occur in")]
+    fn test_range_of_missing() {
+        range_of!(TEXT, "foo");
+    }
+
+    #[test]
+    #[should_panic(expected = "occurrence 3 of \"l\" does not exist, it occurs 3 times")]
+    fn test_range_of_nth_missing() {
+        range_of!(TEXT, "l", 3);
+    }
+}
@@ -0,0 +1,377 @@
+use alloc::vec::Vec;
+use core::iter::Peekable;
+use core::ops::Range;
+use core::slice;
+
+use crate::{CharRangesExt, Snap, SpanError};
+
+/// How [`normalize_ranges()`] handles reversed and out of bounds ranges.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RangeCheck {
+    /// Clamps the start and end of ranges to the end of the text, and
+    /// drops reversed ranges.
+    Clamp,
+    /// Rejects reversed and out of bounds ranges with an error.
+    Reject,
+}
+
+/// Normalizes `ranges` into the canonical sequence of byte ranges of
+/// `text`, i.e. ranges on [`char`] boundaries, sorted by their start,
+/// without any overlapping, adjacent or empty ranges.
+///
+/// Each range is first aligned to [`char`] boundaries according to
+/// `snap`, see [`CharRangesExt::snap_range()`]. Afterwards, the ranges
+/// are sorted, overlapping and adjacent ranges are merged, and empty
+/// ranges are dropped.
+///
+/// The result is the input expected by [`ranges_union()`],
+/// [`ranges_intersection()`], and [`ranges_difference()`].
+///
+/// # Errors
+///
+/// With [`RangeCheck::Reject`], returns an error if any range is reversed
+/// or out of bounds, in which case `ranges` is left unchanged.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::{normalize_ranges, RangeCheck, Snap};
+///
+/// let text = "let 🗻 = 42;";
+///
+/// let mut ranges = vec![9..11, 0..3, 2..4, 5..6, 11..12, 12..12];
+/// normalize_ranges(text, &mut ranges, Snap::Outer, RangeCheck::Reject).unwrap();
+/// // Adjacent ranges are merged
+/// assert_eq!(ranges, [0..8, 9..12]);
+///
+/// let mut ranges = vec![5..6, 9..100];
+/// normalize_ranges(text, &mut ranges, Snap::Inner, RangeCheck::Clamp).unwrap();
+/// assert_eq!(ranges, [9..14]);
+/// ```
+pub fn normalize_ranges(
+    text: &str,
+    ranges: &mut Vec<Range<usize>>,
+    snap: Snap,
+    check: RangeCheck,
+) -> Result<(), SpanError> {
+    if check == RangeCheck::Reject {
+        for r in ranges.iter() {
+            if r.start > r.end {
+                return Err(SpanError::Reversed);
+            } else if r.end > text.len() {
+                return Err(SpanError::OutOfBounds);
+            }
+        }
+    }
+
+    ranges.retain_mut(|r| {
+        let start = r.start.min(text.len());
+        match text.snap_range(start..r.end, snap) {
+            Some(snapped) if !snapped.is_empty() => {
+                *r = snapped;
+                true
+            }
+            _ => false,
+        }
+    });
+
+    ranges.sort_unstable_by_key(|r| r.start);
+    ranges.dedup_by(|next, prev| {
+        if next.start <= prev.end {
+            prev.end = prev.end.max(next.end);
+            true
+        } else {
+            false
+        }
+    });
+
+    Ok(())
+}
+
+/// Returns the union of the normalized ranges `a` and `b`, i.e. the
+/// ranges covering the bytes covered by either.
+///
+/// Both `a` and `b` must be normalized, see [`normalize_ranges()`],
+/// as are the returned ranges.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::ranges_union;
+///
+/// let a = [0..3, 6..9];
+/// let b = [2..4, 9..10, 12..14];
+/// assert_eq!(ranges_union(&a, &b), [0..4, 6..10, 12..14]);
+/// ```
+pub fn ranges_union(a: &[Range<usize>], b: &[Range<usize>]) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = Vec::with_capacity(a.len() + b.len());
+    for r in MergeByStart::new(a, b) {
+        match ranges.last_mut() {
+            Some(last) if r.start <= last.end => last.end = last.end.max(r.end),
+            _ => ranges.push(r.clone()),
+        }
+    }
+    ranges
+}
+
+/// Returns the intersection of the normalized ranges `a` and `b`, i.e.
+/// the ranges covering the bytes covered by both.
+///
+/// Both `a` and `b` must be normalized, see [`normalize_ranges()`],
+/// as are the returned ranges.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::ranges_intersection;
+///
+/// let a = [0..3, 6..9];
+/// let b = [2..7, 8..10];
+/// assert_eq!(ranges_intersection(&a, &b), [2..3, 6..7, 8..9]);
+/// ```
+pub fn ranges_intersection(a: &[Range<usize>], b: &[Range<usize>]) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while (i < a.len()) && (j < b.len()) {
+        let start = a[i].start.max(b[j].start);
+        let end = a[i].end.min(b[j].end);
+        if start < end {
+            ranges.push(start..end);
+        }
+
+        if a[i].end < b[j].end {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    ranges
+}
+
+/// Returns the difference of the normalized ranges `a` and `b`, i.e.
+/// the ranges covering the bytes covered by `a` but not by `b`.
+///
+/// Both `a` and `b` must be normalized, see [`normalize_ranges()`],
+/// as are the returned ranges.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::ranges_difference;
+///
+/// let a = [0..5, 6..9];
+/// let b = [1..2, 4..7];
+/// assert_eq!(ranges_difference(&a, &b), [0..1, 2..4, 7..9]);
+/// ```
+pub fn ranges_difference(a: &[Range<usize>], b: &[Range<usize>]) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut j = 0;
+    for r in a {
+        // Skip the ranges of `b` ending before this range
+        while (j < b.len()) && (b[j].end <= r.start) {
+            j += 1;
+        }
+
+        // The ranges of `b` after `j` may overlap later ranges of `a`,
+        // so only skip them for this range
+        let mut start = r.start;
+        for sub in b[j..].iter().take_while(|sub| sub.start < r.end) {
+            if sub.start > start {
+                ranges.push(start..sub.start);
+            }
+            start = start.max(sub.end);
+        }
+        if start < r.end {
+            ranges.push(start..r.end);
+        }
+    }
+    ranges
+}
+
+/// Produces the ranges of two sorted slices of ranges, sorted by start.
+struct MergeByStart<'a> {
+    a: Peekable<slice::Iter<'a, Range<usize>>>,
+    b: Peekable<slice::Iter<'a, Range<usize>>>,
+}
+
+impl<'a> MergeByStart<'a> {
+    #[inline]
+    fn new(a: &'a [Range<usize>], b: &'a [Range<usize>]) -> Self {
+        Self {
+            a: a.iter().peekable(),
+            b: b.iter().peekable(),
+        }
+    }
+}
+
+impl<'a> Iterator for MergeByStart<'a> {
+    type Item = &'a Range<usize>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match (self.a.peek(), self.b.peek()) {
+            (Some(a), Some(b)) if b.start < a.start => self.b.next(),
+            (Some(_), _) => self.a.next(),
+            (None, _) => self.b.next(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use core::ops::Range;
+    use std::vec;
+    use std::vec::Vec;
+
+    use super::{
+        normalize_ranges, ranges_difference, ranges_intersection, ranges_union, RangeCheck,
+    };
+    use crate::test_util::random;
+    use crate::{CharRangesExt, Snap, SpanError};
+
+    const LEN: usize = 48;
+
+    /// Returns random ranges within `0..(len + 4)`, including reversed
+    /// and empty ranges.
+    fn random_ranges(random: &mut impl FnMut() -> usize, len: usize) -> Vec<Range<usize>> {
+        (0..(random() % 8))
+            .map(|_| {
+                let start = random() % (len + 4);
+                let end = start + (random() % 12);
+                if random().is_multiple_of(8) {
+                    end..start
+                } else {
+                    start..end
+                }
+            })
+            .collect()
+    }
+
+    fn to_bitmap(ranges: &[Range<usize>]) -> [bool; LEN + 16] {
+        let mut bitmap = [false; LEN + 16];
+        for r in ranges {
+            for covered in &mut bitmap[r.clone()] {
+                *covered = true;
+            }
+        }
+        bitmap
+    }
+
+    fn from_bitmap(bitmap: &[bool]) -> Vec<Range<usize>> {
+        let mut ranges = Vec::<Range<usize>>::new();
+        for (i, _) in bitmap.iter().enumerate().filter(|(_, &covered)| covered) {
+            match ranges.last_mut() {
+                Some(last) if last.end == i => last.end += 1,
+                _ => ranges.push(i..(i + 1)),
+            }
+        }
+        ranges
+    }
+
+    #[test]
+    fn test_normalize_ranges() {
+        let text = "let 🗻 = 42; // ∈ ¢ 🌏 done";
+        let mut random = random();
+        for _ in 0..2000 {
+            let input = random_ranges(&mut random, text.len());
+            for snap in [Snap::Inner, Snap::Outer] {
+                // Per range snapped and clamped
+                let snapped = input
+                    .iter()
+                    .filter_map(|r| text.snap_range(r.start.min(text.len())..r.end, snap))
+                    .collect::<Vec<_>>();
+                let expected = from_bitmap(&to_bitmap(&snapped));
+
+                let mut ranges = input.clone();
+                normalize_ranges(text, &mut ranges, snap, RangeCheck::Clamp).unwrap();
+                assert_eq!(ranges, expected, "{input:?} {snap:?}");
+                assert!(ranges.iter().all(|r| text.is_boundary_range(r)));
+
+                let mut ranges = input.clone();
+                match normalize_ranges(text, &mut ranges, snap, RangeCheck::Reject) {
+                    Ok(()) => assert_eq!(ranges, expected),
+                    Err(_) => {
+                        assert_eq!(ranges, input);
+                        assert!(input
+                            .iter()
+                            .any(|r| (r.start > r.end) || (r.end > text.len())));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges, clippy::single_range_in_vec_init)]
+    fn test_normalize_ranges_reject() {
+        let text = "a🌏b";
+
+        let mut ranges = vec![0..1, 4..2];
+        let err = normalize_ranges(text, &mut ranges, Snap::Outer, RangeCheck::Reject);
+        assert_eq!(err, Err(SpanError::Reversed));
+        assert_eq!(ranges, [0..1, 4..2]);
+
+        let mut ranges = vec![0..1, 5..7];
+        let err = normalize_ranges(text, &mut ranges, Snap::Outer, RangeCheck::Reject);
+        assert_eq!(err, Err(SpanError::OutOfBounds));
+
+        let mut ranges = vec![0..1, 5..7, 4..2, 2..3];
+        normalize_ranges(text, &mut ranges, Snap::Outer, RangeCheck::Clamp).unwrap();
+        assert_eq!(ranges, [0..6]);
+
+        let mut ranges = vec![2..3, 7..9];
+        normalize_ranges(text, &mut ranges, Snap::Inner, RangeCheck::Clamp).unwrap();
+        assert_eq!(ranges, [] as [Range<usize>; 0]);
+    }
+
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn test_ranges_set_operations() {
+        let mut random = random();
+        for _ in 0..2000 {
+            let mut a = random_ranges(&mut random, LEN);
+            let mut b = random_ranges(&mut random, LEN);
+            let text = "x".repeat(LEN);
+            normalize_ranges(&text, &mut a, Snap::Outer, RangeCheck::Clamp).unwrap();
+            normalize_ranges(&text, &mut b, Snap::Outer, RangeCheck::Clamp).unwrap();
+
+            let (bits_a, bits_b) = (to_bitmap(&a), to_bitmap(&b));
+            let combine = |f: fn(bool, bool) -> bool| {
+                let bitmap = bits_a
+                    .iter()
+                    .zip(&bits_b)
+                    .map(|(&a, &b)| f(a, b))
+                    .collect::<Vec<_>>();
+                from_bitmap(&bitmap)
+            };
+
+            assert_eq!(ranges_union(&a, &b), combine(|a, b| a || b), "{a:?} {b:?}");
+            assert_eq!(
+                ranges_intersection(&a, &b),
+                combine(|a, b| a && b),
+                "{a:?} {b:?}"
+            );
+            assert_eq!(
+                ranges_difference(&a, &b),
+                combine(|a, b| a && !b),
+                "{a:?} {b:?}"
+            );
+            assert_eq!(
+                ranges_difference(&b, &a),
+                combine(|a, b| b && !a),
+                "{a:?} {b:?}"
+            );
+        }
+
+        assert_eq!(ranges_union(&[], &[]), [] as [Range<usize>; 0]);
+        assert_eq!(ranges_intersection(&[0..5], &[]), [] as [Range<usize>; 0]);
+        assert_eq!(ranges_difference(&[0..5], &[]), [0..5]);
+        assert_eq!(
+            ranges_difference(&[0..5], &[0..10]),
+            [] as [Range<usize>; 0]
+        );
+    }
+}
@@ -0,0 +1,178 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::CharRangesOffset;
+
+/// An iterator over the start and end byte positions of [`char`]s.
+///
+/// Unlike <code>[CharRanges].map(|(r, _)| r)</code>, this iterator retains
+/// [`as_str()`] and [`offset()`], along with the specialized [`nth()`],
+/// [`nth_back()`], and [`last()`].
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRanges::ranges()`] for more information.
+///
+/// [CharRanges]: crate::CharRanges
+/// [`as_str()`]: Ranges::as_str
+/// [`offset()`]: Ranges::offset
+/// [`nth()`]: Iterator::nth
+/// [`nth_back()`]: DoubleEndedIterator::nth_back
+/// [`last()`]: Iterator::last
+/// [`CharRanges::ranges()`]: crate::CharRanges::ranges
+#[derive(Clone)]
+pub struct Ranges<'a> {
+    iter: CharRangesOffset<'a>,
+}
+
+impl<'a> Ranges<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>) -> Self {
+        Self { iter }
+    }
+
+    /// Returns the remaining substring.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "ABCDE";
+    ///
+    /// let mut ranges = text.char_ranges().ranges();
+    /// assert_eq!(ranges.as_str(), "ABCDE");
+    ///
+    /// assert_eq!(ranges.next(), Some(0..1));
+    /// assert_eq!(ranges.next_back(), Some(4..5));
+    ///
+    /// assert_eq!(ranges.as_str(), "BCD");
+    /// ```
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+}
+
+impl Iterator for Ranges<'_> {
+    type Item = Range<usize>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next_range()
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.iter.count()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth(n).map(|(r, _c)| r)
+    }
+}
+
+impl DoubleEndedIterator for Ranges<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back_range()
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth_back(n).map(|(r, _c)| r)
+    }
+}
+
+impl FusedIterator for Ranges<'_> {}
+
+impl fmt::Debug for Ranges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Ranges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::CharRangesExt;
+
+    const TEXT: &str = "Hello 🗻12∈45🌏 World ¢";
+
+    #[test]
+    fn test_ranges() {
+        let expected = TEXT.char_ranges().map(|(r, _c)| r);
+        assert!(TEXT.char_ranges().ranges().eq(expected));
+
+        let expected = TEXT.char_ranges().rev().map(|(r, _c)| r);
+        assert!(TEXT.char_ranges().ranges().rev().eq(expected));
+
+        assert_eq!(
+            TEXT.char_ranges().ranges().count(),
+            TEXT.char_ranges().count()
+        );
+        assert_eq!(TEXT.char_ranges().ranges().last(), Some(28..30));
+        assert_eq!("".char_ranges().ranges().last(), None);
+    }
+
+    #[test]
+    fn test_ranges_nth() {
+        let count = TEXT.char_ranges().count();
+        for n in 0..=count {
+            let mut ranges = TEXT.char_ranges().ranges();
+            let mut expected = TEXT.char_ranges();
+            loop {
+                let r = ranges.nth(n);
+                assert_eq!(r, expected.nth(n).map(|(r, _c)| r));
+                assert_eq!(ranges.as_str(), expected.as_str());
+                if r.is_none() {
+                    break;
+                }
+            }
+
+            let mut ranges = TEXT.char_ranges().ranges();
+            let mut expected = TEXT.char_ranges();
+            loop {
+                let r = ranges.nth_back(n);
+                assert_eq!(r, expected.nth_back(n).map(|(r, _c)| r));
+                assert_eq!(ranges.as_str(), expected.as_str());
+                if r.is_none() {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_ranges_offset() {
+        let text = "Hello 👋 World 🌏";
+
+        let start = 11;
+        let mut ranges = text[start..].char_ranges_offset(start).ranges();
+        assert_eq!(ranges.offset(), start);
+        assert_eq!(ranges.next(), Some(11..12));
+        assert_eq!(ranges.next_back(), Some(17..21));
+        assert_eq!(ranges.as_str(), "orld ");
+        assert_eq!(ranges.nth(1), Some(13..14));
+    }
+}
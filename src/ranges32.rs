@@ -0,0 +1,254 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::{CharRanges, CharRangesExt, U32OverflowError};
+
+/// An iterator over [`char`]s and their start and end byte positions,
+/// as <code>[Range]&lt;[u32]&gt;</code> instead of <code>[Range]&lt;[usize]&gt;</code>.
+///
+/// This halves the size of the ranges on 64-bit targets, e.g. when
+/// storing many of them, for texts where `offset + text.len()` fits
+/// in a `u32`, which is checked when the iterator is created.
+///
+/// Note: This iterator is [`Copy`], i.e. it can be stored and restored
+/// freely, e.g. for backtracking, without explicitly cloning it.
+///
+/// See [`CharRangesExt::char_ranges32()`] for more information.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::CharRangesExt;
+///
+/// let text = "🗻∈🌏";
+///
+/// let mut chars = text.char_ranges32();
+/// assert_eq!(chars.next(), Some((0..4, '🗻')));
+/// assert_eq!(chars.next_back(), Some((7..11, '🌏')));
+/// assert_eq!(chars.as_str(), "∈");
+/// ```
+#[derive(Clone, Copy)]
+pub struct CharRanges32<'a> {
+    iter: CharRanges<'a>,
+    offset: u32,
+}
+
+impl<'a> CharRanges32<'a> {
+    /// Creates an iterator over [`char`]s and their start and end byte positions,
+    /// with an offset applied to all positions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `offset + text.len()` overflows `u32`.
+    /// Use [`try_new()`](Self::try_new) to handle this instead.
+    #[inline]
+    pub fn new(offset: u32, text: &'a str) -> Self {
+        match Self::try_new(offset, text) {
+            Ok(chars) => chars,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Creates an iterator over [`char`]s and their start and end byte positions,
+    /// with an offset applied to all positions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `offset + text.len()` overflows `u32`,
+    /// i.e. if any position could overflow.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRanges32;
+    ///
+    /// assert!(CharRanges32::try_new(u32::MAX - 3, "abc").is_ok());
+    /// assert!(CharRanges32::try_new(u32::MAX - 2, "abc").is_err());
+    /// ```
+    #[inline]
+    pub fn try_new(offset: u32, text: &'a str) -> Result<Self, U32OverflowError> {
+        let fits = u32::try_from(text.len())
+            .ok()
+            .and_then(|len| offset.checked_add(len))
+            .is_some();
+        if fits {
+            Ok(Self {
+                iter: text.char_ranges(),
+                offset,
+            })
+        } else {
+            Err(U32OverflowError::new(offset, text.len()))
+        }
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+
+    #[inline]
+    fn apply_offset(&self, r: Range<usize>) -> Range<u32> {
+        // `offset + text.len()` was checked to fit in a `u32`
+        // when created, so no position can overflow
+        (self.offset + (r.start as u32))..(self.offset + (r.end as u32))
+    }
+}
+
+impl Iterator for CharRanges32<'_> {
+    type Item = (Range<u32>, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (r, c) = self.iter.next()?;
+        Some((self.apply_offset(r), c))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.iter.count()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<(Range<u32>, char)> {
+        self.next_back()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let (r, c) = self.iter.nth(n)?;
+        Some((self.apply_offset(r), c))
+    }
+}
+
+impl DoubleEndedIterator for CharRanges32<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (r, c) = self.iter.next_back()?;
+        Some((self.apply_offset(r), c))
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        let (r, c) = self.iter.nth_back(n)?;
+        Some((self.apply_offset(r), c))
+    }
+}
+
+impl FusedIterator for CharRanges32<'_> {}
+
+impl fmt::Debug for CharRanges32<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CharRanges32(")?;
+        f.debug_list().entries(*self).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::ops::Range;
+
+    use super::CharRanges32;
+    use crate::{CharRangesExt, U32OverflowError};
+
+    const TEXT: &str = "Hello 🗻12∈45🌏 World ¢";
+
+    fn widen((r, c): (Range<u32>, char)) -> (Range<usize>, char) {
+        ((r.start as usize)..(r.end as usize), c)
+    }
+
+    #[test]
+    fn test_char_ranges32() {
+        assert!(TEXT.char_ranges32().map(widen).eq(TEXT.char_ranges()));
+        assert!(TEXT
+            .char_ranges32()
+            .rev()
+            .map(widen)
+            .eq(TEXT.char_ranges().rev()));
+        assert_eq!(TEXT.char_ranges32().count(), TEXT.char_ranges().count());
+        assert_eq!(TEXT.char_ranges32().last(), Some((28..30, '¢')));
+        assert_eq!("".char_ranges32().last(), None);
+
+        let mut chars = TEXT.char_ranges32();
+        chars.next();
+        chars.next_back();
+        assert_eq!(chars.as_str(), &TEXT[1..28]);
+    }
+
+    #[test]
+    fn test_char_ranges32_nth() {
+        let count = TEXT.chars().count();
+        for n in 0..=count {
+            let mut chars = TEXT.char_ranges32();
+            let mut expected = TEXT.char_ranges();
+            loop {
+                let item = chars.nth(n);
+                assert_eq!(item.clone().map(widen), expected.nth(n));
+                assert_eq!(chars.as_str(), expected.as_str());
+                if item.is_none() {
+                    break;
+                }
+            }
+
+            let mut chars = TEXT.char_ranges32();
+            let mut expected = TEXT.char_ranges();
+            loop {
+                let item = chars.nth_back(n);
+                assert_eq!(item.clone().map(widen), expected.nth_back(n));
+                assert_eq!(chars.as_str(), expected.as_str());
+                if item.is_none() {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_char_ranges32_offset() {
+        let text = "Hello 👋 World 🌏";
+
+        let start = 11;
+        let chars = text[start..].char_ranges32_offset(start as u32);
+        assert_eq!(chars.offset(), 11);
+        assert!(chars.map(widen).eq(text[start..].char_ranges_offset(start)));
+    }
+
+    #[test]
+    fn test_char_ranges32_large_offset() {
+        let text = "a🌏";
+
+        let max = u32::MAX;
+        let mut chars = CharRanges32::try_new(max - 5, text).unwrap();
+        assert_eq!(chars.next(), Some(((max - 5)..(max - 4), 'a')));
+        assert_eq!(chars.next(), Some(((max - 4)..max, '🌏')));
+        assert_eq!(chars.next(), None);
+
+        let err = CharRanges32::try_new(max - 4, text).unwrap_err();
+        assert_eq!(err, U32OverflowError::new(max - 4, 5));
+        assert_eq!(err.offset(), max - 4);
+        assert_eq!(err.text_len(), 5);
+
+        assert!(CharRanges32::try_new(max, "").is_ok());
+        assert!(CharRanges32::try_new(max, "a").is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "overflows u32")]
+    fn test_char_ranges32_overflow_panic() {
+        let _ = CharRanges32::new(u32::MAX, "a");
+    }
+}
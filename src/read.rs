@@ -0,0 +1,302 @@
+use alloc::boxed::Box;
+use alloc::vec;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+use core::str;
+use std::io::{self, Read};
+
+use crate::chunked::{ChunkStep, ChunkedCharRanges};
+use crate::utf8_char_width;
+
+const DEFAULT_CAPACITY: usize = 8 * 1024;
+
+/// An iterator over the [`char`]s read from a [`Read`]er and their start
+/// and end byte positions, relative to the start of the reader.
+///
+/// The reader is read through an internal buffer, and [`char`]s split
+/// across refills of the buffer are handled transparently.
+///
+/// Invalid UTF-8 produces an [`io::Error`] of kind [`InvalidData`],
+/// wrapping a [`ChunkedUtf8Error`] with the range of the invalid bytes,
+/// after which iteration continues. I/O errors from the reader are
+/// passed through as is.
+///
+/// # Example
+///
+/// ```rust
+/// use std::io::Cursor;
+///
+/// use char_ranges::ReadCharRanges;
+///
+/// let reader = Cursor::new("a🌏b");
+///
+/// let mut chars = ReadCharRanges::new(reader);
+/// assert_eq!(chars.next().unwrap().unwrap(), (0..1, 'a'));
+/// assert_eq!(chars.next().unwrap().unwrap(), (1..5, '🌏'));
+/// assert_eq!(chars.next().unwrap().unwrap(), (5..6, 'b'));
+/// assert!(chars.next().is_none());
+/// ```
+///
+/// [`InvalidData`]: io::ErrorKind::InvalidData
+/// [`ChunkedUtf8Error`]: crate::ChunkedUtf8Error
+pub struct ReadCharRanges<R> {
+    reader: R,
+    buf: Box<[u8]>,
+    /// Start of the remaining bytes of the valid UTF-8 sequence in `buf`.
+    start: usize,
+    /// End of the valid UTF-8 sequence in `buf`, and the start
+    /// of the bytes not yet decoded.
+    valid_end: usize,
+    /// End of the bytes read into `buf`.
+    end: usize,
+    /// Byte position of `buf[start]`.
+    base: u64,
+    state: ChunkedCharRanges,
+    eof: bool,
+}
+
+impl<R> ReadCharRanges<R>
+where
+    R: Read,
+{
+    /// Creates an iterator over the [`char`]s read from `reader`,
+    /// with a default buffer capacity (currently 8 KiB).
+    #[inline]
+    pub fn new(reader: R) -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY, reader)
+    }
+
+    /// Creates an iterator over the [`char`]s read from `reader`,
+    /// with a buffer of `capacity` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is `0`.
+    #[inline]
+    pub fn with_capacity(capacity: usize, reader: R) -> Self {
+        assert!(capacity > 0, "capacity must be greater than 0");
+        Self {
+            reader,
+            buf: vec![0; capacity].into_boxed_slice(),
+            start: 0,
+            valid_end: 0,
+            end: 0,
+            base: 0,
+            state: ChunkedCharRanges::new(),
+            eof: false,
+        }
+    }
+}
+
+impl<R> ReadCharRanges<R> {
+    /// Returns a reference to the underlying reader.
+    #[inline]
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// Reading directly from the underlying reader will cause
+    /// those bytes to be skipped, without affecting positions.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Returns the underlying reader.
+    ///
+    /// Any bytes in the internal buffer not yet produced are lost.
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Returns the capacity of the internal buffer.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+}
+
+impl<R> Iterator for ReadCharRanges<R>
+where
+    R: Read,
+{
+    type Item = io::Result<(Range<u64>, char)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.start < self.valid_end {
+                let len = utf8_char_width(self.buf[self.start]);
+                let bytes = &self.buf[self.start..(self.start + len)];
+                let c = str::from_utf8(bytes).unwrap().chars().next().unwrap();
+
+                let r = self.base..(self.base + (len as u64));
+                self.start += len;
+                self.base = r.end;
+                return Some(Ok((r, c)));
+            }
+
+            if self.eof {
+                let err = self.state.take_incomplete()?;
+                return Some(Err(io::Error::new(io::ErrorKind::InvalidData, err)));
+            }
+
+            let mut bytes = &self.buf[self.valid_end..self.end];
+            let step = self.state.step(&mut bytes);
+            let pos = self.end - bytes.len();
+            match step {
+                Some(ChunkStep::Valid(base, s)) => {
+                    self.start = pos - s.len();
+                    self.valid_end = pos;
+                    self.base = base;
+                }
+                Some(ChunkStep::Item(item)) => {
+                    self.start = pos;
+                    self.valid_end = pos;
+                    return Some(
+                        item.map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err)),
+                    );
+                }
+                None => match self.reader.read(&mut self.buf) {
+                    Ok(n) => {
+                        self.start = 0;
+                        self.valid_end = 0;
+                        self.end = n;
+                        self.eof = n == 0;
+                    }
+                    Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                    Err(err) => {
+                        self.start = 0;
+                        self.valid_end = 0;
+                        self.end = 0;
+                        return Some(Err(err));
+                    }
+                },
+            }
+        }
+    }
+}
+
+impl<R> FusedIterator for ReadCharRanges<R> where R: Read {}
+
+impl<R> fmt::Debug for ReadCharRanges<R>
+where
+    R: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReadCharRanges")
+            .field("reader", &self.reader)
+            .field("capacity", &self.buf.len())
+            .field("state", &self.state)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::ops::Range;
+    use std::io::{self, Cursor, Read};
+    use std::vec::Vec;
+
+    use super::ReadCharRanges;
+    use crate::{ChunkedUtf8Error, Utf8CharRangesExt};
+
+    type Item = Result<(Range<u64>, char), Range<u64>>;
+
+    fn expected(bytes: &[u8]) -> Vec<Item> {
+        bytes
+            .utf8_char_ranges()
+            .map(|item| match item {
+                Ok((r, c)) => Ok(((r.start as u64)..(r.end as u64), c)),
+                Err(err) => Err((err.range().start as u64)..(err.range().end as u64)),
+            })
+            .collect()
+    }
+
+    fn read_all(capacity: usize, bytes: &[u8]) -> Vec<Item> {
+        ReadCharRanges::with_capacity(capacity, Cursor::new(bytes.to_vec()))
+            .map(|item| {
+                item.map_err(|err| {
+                    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+                    let err = err.into_inner().unwrap();
+                    err.downcast::<ChunkedUtf8Error>().unwrap().range()
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_read_char_ranges() {
+        let inputs: [&[u8]; 4] = [
+            "Hello 🗻12∈45🌏 World ¢".as_bytes(),
+            "🗻🌏🗻🌏".as_bytes(),
+            b"a\xF0\x9F\x97\xBB\xFFb\xE2\x88c\xF0\x9F",
+            b"",
+        ];
+        for bytes in inputs {
+            let expected = expected(bytes);
+            for capacity in [1, 2, 3, 4, 5, 7, 8192] {
+                assert_eq!(read_all(capacity, bytes), expected, "capacity {capacity}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_read_char_ranges_incomplete() {
+        let mut chars = ReadCharRanges::with_capacity(3, Cursor::new(b"ab\xF0\x9F\x97"));
+        assert_eq!(chars.next().unwrap().unwrap(), (0..1, 'a'));
+        assert_eq!(chars.next().unwrap().unwrap(), (1..2, 'b'));
+
+        let err = chars.next().unwrap().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let err = err.into_inner().unwrap();
+        let err = err.downcast_ref::<ChunkedUtf8Error>().unwrap();
+        assert_eq!(err.range(), 2..5);
+        assert!(err.is_incomplete());
+
+        assert!(chars.next().is_none());
+        assert!(chars.next().is_none());
+    }
+
+    #[test]
+    fn test_read_char_ranges_io_error() {
+        struct FailOnce {
+            inner: Cursor<&'static [u8]>,
+            failed: bool,
+        }
+
+        impl Read for FailOnce {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                if (self.inner.position() == 2) && !self.failed {
+                    self.failed = true;
+                    return Err(io::Error::other("fail"));
+                }
+                self.inner.read(buf)
+            }
+        }
+
+        let reader = FailOnce {
+            inner: Cursor::new("a∈b".as_bytes()),
+            failed: false,
+        };
+        let mut chars = ReadCharRanges::with_capacity(2, reader);
+        assert_eq!(chars.next().unwrap().unwrap(), (0..1, 'a'));
+
+        let err = chars.next().unwrap().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Other);
+
+        // The partially read char is completed after the error
+        assert_eq!(chars.next().unwrap().unwrap(), (1..4, '∈'));
+        assert_eq!(chars.next().unwrap().unwrap(), (4..5, 'b'));
+        assert!(chars.next().is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity must be greater than 0")]
+    fn test_read_char_ranges_zero_capacity() {
+        let _ = ReadCharRanges::with_capacity(0, Cursor::new(b""));
+    }
+}
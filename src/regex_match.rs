@@ -0,0 +1,290 @@
+use core::ops::Range;
+
+use alloc::vec::Vec;
+
+use regex::{Captures, Match, Regex};
+
+use crate::{CharRangesOffset, OffsetOverflowError};
+
+/// Returns the start and end byte positions of the [`Match`] `m`, with
+/// `offset` applied, e.g. when the regex was run on a substring starting
+/// at `offset` of a larger document.
+///
+/// # Errors
+///
+/// Returns an error if `offset` plus the end of `m` overflows `usize`.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::offset_match;
+/// use regex::Regex;
+///
+/// let doc = "foo 🗻 bar 123";
+/// let start = 4;
+///
+/// let re = Regex::new(r"[0-9]+").unwrap();
+/// let m = re.find(&doc[start..]).unwrap();
+/// assert_eq!(m.range(), 9..12);
+///
+/// let r = offset_match(&m, start).unwrap();
+/// assert_eq!(r, 13..16);
+/// assert_eq!(&doc[r], "123");
+///
+/// assert!(offset_match(&m, usize::MAX - 11).is_err());
+/// ```
+#[inline]
+pub fn offset_match(m: &Match<'_>, offset: usize) -> Result<Range<usize>, OffsetOverflowError> {
+    match offset.checked_add(m.end()) {
+        Some(end) => Ok((offset + m.start())..end),
+        None => Err(OffsetOverflowError::new(offset, m.end())),
+    }
+}
+
+/// Returns an iterator over the [`char`]s of the [`Match`] `m` in `text`,
+/// i.e. the haystack `m` was found in, and their start and end byte
+/// positions, with `offset` applied to all positions.
+///
+/// The positions are relative to `text`, not to the match, i.e. the first
+/// position is `offset + m.start()`.
+///
+/// Returns `None` if `m` is not within `text` or not on [`char`]
+/// boundaries, e.g. if `text` is not the haystack, or if `offset` plus
+/// the end of `m` overflows `usize`.
+///
+/// # Example
+///
+/// Highlighting all matches of a pattern in a section of a document,
+/// in document coordinates:
+///
+/// ```rust
+/// use char_ranges::{char_ranges_of_match, offset_match};
+/// use regex::Regex;
+///
+/// let doc = "# Title\nfoo ∈ bar, foo 🗻 baz";
+///
+/// // Only search the body
+/// let start = doc.find('\n').unwrap() + 1;
+/// let body = &doc[start..];
+///
+/// let re = Regex::new(r"foo \S").unwrap();
+///
+/// let matches = re
+///     .find_iter(body)
+///     .map(|m| offset_match(&m, start).unwrap())
+///     .collect::<Vec<_>>();
+/// assert_eq!(matches, [8..15, 21..29]);
+///
+/// // Per-char ranges, e.g. to highlight each char individually
+/// let m = re.find_iter(body).nth(1).unwrap();
+/// let mut chars = char_ranges_of_match(body, &m, start).unwrap();
+/// assert_eq!(chars.next(), Some((21..22, 'f')));
+/// assert_eq!(chars.next_back(), Some((25..29, '🗻')));
+/// assert_eq!(chars.next_back(), Some((24..25, ' ')));
+///
+/// for (r, c) in char_ranges_of_match(body, &m, start).unwrap() {
+///     assert_eq!(doc[r].chars().next(), Some(c));
+/// }
+/// ```
+#[inline]
+pub fn char_ranges_of_match<'a>(
+    text: &'a str,
+    m: &Match<'_>,
+    offset: usize,
+) -> Option<CharRangesOffset<'a>> {
+    char_ranges_of_range(text, m.range(), offset)
+}
+
+/// Returns an iterator over the [`char`]s of the [`bytes::Match`] `m`
+/// in `text`, and their start and end byte positions, with `offset`
+/// applied to all positions.
+///
+/// Since a [`bytes::Regex`] can match arbitrary bytes, e.g. `(?-u:.)`
+/// matches a single byte of a multi-byte [`char`], `m` is validated to
+/// be on [`char`] boundaries.
+///
+/// Returns `None` if `m` is not within `text` or not on [`char`]
+/// boundaries, or if `offset` plus the end of `m` overflows `usize`.
+///
+/// See [`char_ranges_of_match()`] for more information.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::char_ranges_of_bytes_match;
+/// use regex::bytes::Regex;
+///
+/// let text = "a🌏b";
+///
+/// let re = Regex::new(r"a\S").unwrap();
+/// let m = re.find(text.as_bytes()).unwrap();
+///
+/// let mut chars = char_ranges_of_bytes_match(text, &m, 10).unwrap();
+/// assert_eq!(chars.next(), Some((10..11, 'a')));
+/// assert_eq!(chars.next(), Some((11..15, '🌏')));
+/// assert_eq!(chars.next(), None);
+///
+/// // Matches within the encoding of '🌏'
+/// let re = Regex::new(r"(?-u)a.").unwrap();
+/// let m = re.find(text.as_bytes()).unwrap();
+/// assert_eq!(m.range(), 0..2);
+/// assert!(char_ranges_of_bytes_match(text, &m, 10).is_none());
+/// ```
+///
+/// [`bytes::Match`]: regex::bytes::Match
+/// [`bytes::Regex`]: regex::bytes::Regex
+#[inline]
+pub fn char_ranges_of_bytes_match<'a>(
+    text: &'a str,
+    m: &regex::bytes::Match<'_>,
+    offset: usize,
+) -> Option<CharRangesOffset<'a>> {
+    char_ranges_of_range(text, m.range(), offset)
+}
+
+#[inline]
+fn char_ranges_of_range(
+    text: &str,
+    range: Range<usize>,
+    offset: usize,
+) -> Option<CharRangesOffset<'_>> {
+    let start = range.start;
+    let s = text.get(range)?;
+    let offset = offset.checked_add(start)?;
+    CharRangesOffset::try_from_text(s, offset).ok()
+}
+
+/// Returns the names and the start and end byte positions of the named
+/// capture groups in `caps`, with `offset` applied, where `caps` was
+/// produced by `re`.
+///
+/// Groups are returned in order of their index. Unnamed groups, including
+/// the implicit group of the whole match, and groups which did not
+/// participate in the match are skipped.
+///
+/// # Errors
+///
+/// Returns an error if `offset` plus the end of a group overflows `usize`.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::capture_ranges;
+/// use regex::Regex;
+///
+/// let doc = "x: 🗻=12, y: a=3";
+/// let start = 9;
+///
+/// let re = Regex::new(r"(?<key>\w+)=(?<value>\d+)(?<unit>px)?").unwrap();
+/// let caps = re.captures(&doc[start..]).unwrap();
+///
+/// let groups = capture_ranges(&re, &caps, start).unwrap();
+/// assert_eq!(groups, [("key", 15..16), ("value", 17..18)]);
+/// assert_eq!(&doc[groups[0].1.clone()], "a");
+/// ```
+pub fn capture_ranges<'r>(
+    re: &'r Regex,
+    caps: &Captures<'_>,
+    offset: usize,
+) -> Result<Vec<(&'r str, Range<usize>)>, OffsetOverflowError> {
+    let mut groups = Vec::new();
+    for (name, m) in re.capture_names().zip(caps.iter()) {
+        if let (Some(name), Some(m)) = (name, m) {
+            groups.push((name, offset_match(&m, offset)?));
+        }
+    }
+    Ok(groups)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use regex::Regex;
+
+    use super::{capture_ranges, char_ranges_of_bytes_match, char_ranges_of_match, offset_match};
+
+    #[test]
+    fn test_offset_match() {
+        let re = Regex::new("b+").unwrap();
+        let m = re.find("aabbbc").unwrap();
+
+        assert_eq!(offset_match(&m, 0), Ok(2..5));
+        assert_eq!(offset_match(&m, 10), Ok(12..15));
+        assert_eq!(
+            offset_match(&m, usize::MAX - 5),
+            Ok((usize::MAX - 3)..usize::MAX)
+        );
+
+        let err = offset_match(&m, usize::MAX - 4).unwrap_err();
+        assert_eq!((err.offset(), err.text_len()), (usize::MAX - 4, 5));
+    }
+
+    #[test]
+    fn test_char_ranges_of_match() {
+        let text = "a∈🗻b";
+        let re = Regex::new("∈🗻").unwrap();
+        let m = re.find(text).unwrap();
+
+        let chars = char_ranges_of_match(text, &m, 0)
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(chars, [(1..4, '∈'), (4..8, '🗻')]);
+
+        let chars = char_ranges_of_match(text, &m, 5)
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(chars, [(6..9, '∈'), (9..13, '🗻')]);
+
+        // Not the haystack
+        assert!(char_ranges_of_match("ab", &m, 0).is_none());
+        assert!(char_ranges_of_match("a🗻🗻", &m, 0).is_none());
+
+        // Overflow
+        assert!(char_ranges_of_match(text, &m, usize::MAX - 7).is_none());
+        assert!(char_ranges_of_match(text, &m, usize::MAX - 8).is_some());
+    }
+
+    #[test]
+    fn test_char_ranges_of_bytes_match() {
+        let text = "🗻∈";
+        let re = regex::bytes::Regex::new(r"(?-u)[\x80-\xFF]{2}").unwrap();
+
+        let ranges = re
+            .find_iter(text.as_bytes())
+            .map(|m| char_ranges_of_bytes_match(text, &m, 0).map(|chars| chars.collect::<Vec<_>>()))
+            .collect::<Vec<_>>();
+        assert_eq!(ranges.len(), 3);
+        assert!(ranges.iter().all(Option::is_none));
+
+        let re = regex::bytes::Regex::new("∈").unwrap();
+        let m = re.find(text.as_bytes()).unwrap();
+        let chars = char_ranges_of_bytes_match(text, &m, 1).unwrap();
+        assert!(chars.eq([(5..8, '∈')]));
+    }
+
+    #[test]
+    fn test_capture_ranges() {
+        let re = Regex::new(r"(\d+)(?<a>x)?(?<b>y)(z)?(?<c>w)").unwrap();
+        let caps = re.captures("12yw").unwrap();
+
+        let groups = capture_ranges(&re, &caps, 0).unwrap();
+        assert_eq!(groups, [("b", 2..3), ("c", 3..4)]);
+
+        let groups = capture_ranges(&re, &caps, 100).unwrap();
+        assert_eq!(groups, [("b", 102..103), ("c", 103..104)]);
+
+        // No named groups
+        let re = Regex::new(r"(\d)").unwrap();
+        let caps = re.captures("a1").unwrap();
+        assert!(capture_ranges(&re, &caps, 0).unwrap().is_empty());
+
+        // Overflow
+        let re = Regex::new(r"(?<a>a)").unwrap();
+        let caps = re.captures("a").unwrap();
+        assert!(capture_ranges(&re, &caps, usize::MAX - 1).is_ok());
+        assert!(capture_ranges(&re, &caps, usize::MAX).is_err());
+    }
+}
@@ -0,0 +1,193 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::CharRangesOffset;
+
+/// An iterator over [`char`]s and their start and end byte positions,
+/// in reverse order.
+///
+/// Unlike <code>[Rev]<[CharRanges]></code>, this iterator retains
+/// [`as_str()`] and [`offset()`].
+///
+/// Note: This iterator is [`Copy`], i.e. it can be stored and restored
+/// freely, e.g. for backtracking, without explicitly cloning it.
+///
+/// See [`CharRanges::reversed()`] for more information.
+///
+/// [Rev]: core::iter::Rev
+/// [CharRanges]: crate::CharRanges
+/// [`as_str()`]: CharRangesRev::as_str
+/// [`offset()`]: CharRangesRev::offset
+/// [`CharRanges::reversed()`]: crate::CharRanges::reversed
+#[derive(Clone, Copy)]
+pub struct CharRangesRev<'a> {
+    iter: CharRangesOffset<'a>,
+}
+
+impl<'a> CharRangesRev<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>) -> Self {
+        Self { iter }
+    }
+
+    /// Returns the remaining substring.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "ABCDE";
+    ///
+    /// let mut chars = text.char_ranges_rev();
+    /// assert_eq!(chars.as_str(), "ABCDE");
+    ///
+    /// assert_eq!(chars.next(), Some((4..5, 'E')));
+    /// assert_eq!(chars.next_back(), Some((0..1, 'A')));
+    ///
+    /// assert_eq!(chars.as_str(), "BCD");
+    /// ```
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+}
+
+impl Iterator for CharRangesRev<'_> {
+    type Item = (Range<usize>, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next_back()
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.iter.count()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<(Range<usize>, char)> {
+        self.iter.next()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth_back(n)
+    }
+}
+
+impl DoubleEndedIterator for CharRangesRev<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth(n)
+    }
+}
+
+impl FusedIterator for CharRangesRev<'_> {}
+
+impl fmt::Debug for CharRangesRev<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CharRangesRev(")?;
+        f.debug_list().entries(*self).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::CharRangesExt;
+
+    #[test]
+    fn test_rev() {
+        let cases = [
+            "",
+            "Hello World",
+            "Hello 👋 World 🌏",
+            "🗻12∈45🌏",
+            "Hello 🗻12∈45🌏 World",
+        ];
+        for text in cases {
+            assert!(text.char_ranges_rev().eq(text.char_ranges().rev()));
+            assert!(text.char_ranges_rev().rev().eq(text.char_ranges()));
+            assert_eq!(text.char_ranges_rev().count(), text.chars().count());
+            assert_eq!(text.char_ranges_rev().last(), text.char_ranges().next());
+        }
+    }
+
+    #[test]
+    fn test_rev_as_str() {
+        let text = "🗻12∈45🌏";
+
+        let mut chars = text.char_ranges_rev();
+        assert_eq!(chars.as_str(), "🗻12∈45🌏");
+
+        assert_eq!(chars.next(), Some((11..15, '🌏')));
+        assert_eq!(chars.as_str(), "🗻12∈45");
+
+        assert_eq!(chars.next_back(), Some((0..4, '🗻')));
+        assert_eq!(chars.as_str(), "12∈45");
+
+        assert_eq!(chars.next(), Some((10..11, '5')));
+        assert_eq!(chars.as_str(), "12∈4");
+
+        assert_eq!(chars.next_back(), Some((4..5, '1')));
+        assert_eq!(chars.as_str(), "2∈4");
+
+        assert_eq!(chars.next(), Some((9..10, '4')));
+        assert_eq!(chars.as_str(), "2∈");
+
+        assert_eq!(chars.next(), Some((6..9, '∈')));
+        assert_eq!(chars.as_str(), "2");
+
+        assert_eq!(chars.next_back(), Some((5..6, '2')));
+        assert_eq!(chars.as_str(), "");
+
+        assert_eq!(chars.next(), None);
+        assert_eq!(chars.next_back(), None);
+        assert_eq!(chars.as_str(), "");
+    }
+
+    #[test]
+    fn test_rev_nth() {
+        let text = "Hello 🗻12∈45🌏 World";
+
+        let mut chars = text.char_ranges_rev();
+        assert_eq!(chars.nth(1), Some((25..26, 'l')));
+        assert_eq!(chars.nth_back(6), Some((6..10, '🗻')));
+        assert_eq!(chars.as_str(), "12∈45🌏 Wor");
+        assert_eq!(chars.nth(100), None);
+        assert_eq!(chars.as_str(), "");
+    }
+
+    #[test]
+    fn test_rev_offset() {
+        let text = "Hello 👋 World 🌏";
+
+        let start = 11;
+        let mut chars = text[start..].char_ranges_offset(start).reversed();
+        assert_eq!(chars.offset(), start);
+
+        assert_eq!(chars.next(), Some((17..21, '🌏')));
+        assert_eq!(chars.next_back(), Some((11..12, 'W')));
+        assert_eq!(chars.as_str(), "orld ");
+    }
+}
@@ -0,0 +1,217 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use ropey::iter::Chunks;
+use ropey::{Rope, RopeSlice};
+
+use crate::{CharRangesExt, CharRangesOffset};
+
+/// Returns an iterator over the [`char`]s of `rope` and their start and end
+/// byte positions, without first collecting `rope` into a [`String`].
+///
+/// See [`RopeCharRanges`] for more information.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::rope_char_ranges;
+/// use ropey::Rope;
+///
+/// let rope = Rope::from_str("Hello 🗻12∈45🌏 World");
+///
+/// let mut chars = rope_char_ranges(&rope);
+/// assert_eq!(chars.nth(6), Some((6..10, '🗻')));
+/// assert_eq!(chars.nth(2), Some((12..15, '∈')));
+/// ```
+///
+/// [`String`]: std::string::String
+#[inline]
+pub fn rope_char_ranges(rope: &Rope) -> RopeCharRanges<'_> {
+    RopeCharRanges::new(rope.chunks(), 0)
+}
+
+/// Returns an iterator over the [`char`]s of `slice` and their start and end
+/// byte positions, relative to the start of `slice`.
+///
+/// See [`rope_slice_char_ranges_offset()`] to produce positions relative
+/// to the start of the whole [`Rope`] instead.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::rope_slice_char_ranges;
+/// use ropey::Rope;
+///
+/// let rope = Rope::from_str("Hello 🗻12∈45🌏 World");
+/// let slice = rope.byte_slice(6..15);
+///
+/// let mut chars = rope_slice_char_ranges(slice);
+/// assert_eq!(chars.next(), Some((0..4, '🗻')));
+/// assert_eq!(chars.last(), Some((6..9, '∈')));
+/// ```
+#[inline]
+pub fn rope_slice_char_ranges(slice: RopeSlice<'_>) -> RopeCharRanges<'_> {
+    rope_slice_char_ranges_offset(slice, 0)
+}
+
+/// Returns an iterator over the [`char`]s of `slice` and their start and end
+/// byte positions, with `offset` applied to all positions.
+///
+/// When `slice` is a slice of a [`Rope`], then using the byte position of
+/// the start of `slice` as `offset`, produces positions relative to the
+/// start of the whole [`Rope`].
+///
+/// # Panics
+///
+/// Panics if a position overflows `usize`.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::rope_slice_char_ranges_offset;
+/// use ropey::Rope;
+///
+/// let rope = Rope::from_str("Hello 🗻12∈45🌏 World");
+/// let slice = rope.byte_slice(10..15);
+///
+/// let chars = rope_slice_char_ranges_offset(slice, 10);
+/// assert!(chars.eq([(10..11, '1'), (11..12, '2'), (12..15, '∈')]));
+/// ```
+#[inline]
+pub fn rope_slice_char_ranges_offset(slice: RopeSlice<'_>, offset: usize) -> RopeCharRanges<'_> {
+    RopeCharRanges::new(slice.chunks(), offset)
+}
+
+/// An iterator over the [`char`]s of a [`Rope`] or [`RopeSlice`] and
+/// their start and end byte positions.
+///
+/// The chunks of the [`Rope`] are iterated one at a time, and positions
+/// are accumulated across chunks. Since [`Rope`] never splits a [`char`]
+/// across chunks, no buffering is needed.
+///
+/// See [`rope_char_ranges()`], [`rope_slice_char_ranges()`], and
+/// [`rope_slice_char_ranges_offset()`] for more information.
+#[derive(Clone)]
+pub struct RopeCharRanges<'a> {
+    chunks: Chunks<'a>,
+    /// The [`char`]s of the current chunk.
+    chars: CharRangesOffset<'a>,
+    /// Byte position following the current chunk.
+    end: usize,
+}
+
+impl<'a> RopeCharRanges<'a> {
+    #[inline]
+    fn new(chunks: Chunks<'a>, offset: usize) -> Self {
+        Self {
+            chunks,
+            chars: "".char_ranges_offset(offset),
+            end: offset,
+        }
+    }
+}
+
+impl Iterator for RopeCharRanges<'_> {
+    type Item = (Range<usize>, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.chars.next() {
+                return Some(item);
+            }
+
+            let chunk = self.chunks.next()?;
+            let start = self.end;
+            self.end = start
+                .checked_add(chunk.len())
+                .expect("position overflows usize");
+            self.chars = chunk.char_ranges_offset(start);
+        }
+    }
+}
+
+impl FusedIterator for RopeCharRanges<'_> {}
+
+impl fmt::Debug for RopeCharRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RopeCharRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::string::String;
+    use std::vec::Vec;
+
+    use ropey::Rope;
+
+    use super::{rope_char_ranges, rope_slice_char_ranges, rope_slice_char_ranges_offset};
+    use crate::CharRangesExt;
+
+    const TEXT: &str = "Hello 🗻12∈45🌏 World ¢\r\n";
+
+    /// Returns a large text, which is split into multiple chunks.
+    fn large_text() -> String {
+        let mut text = String::new();
+        for i in 0..1000 {
+            text.push_str(TEXT);
+            text.push_str(["a", "é", "∈", "🌏"][i % 4]);
+        }
+        text
+    }
+
+    #[test]
+    fn test_rope_char_ranges() {
+        let rope = Rope::from_str(TEXT);
+        assert!(rope_char_ranges(&rope).eq(TEXT.char_ranges()));
+
+        let rope = Rope::from_str("");
+        assert_eq!(rope_char_ranges(&rope).next(), None);
+    }
+
+    #[test]
+    fn test_rope_char_ranges_chunks() {
+        let text = large_text();
+        let rope = Rope::from_str(&text);
+        assert!(rope.chunks().count() > 1);
+
+        let chars = rope_char_ranges(&rope).collect::<Vec<_>>();
+        let expected = text.char_ranges().collect::<Vec<_>>();
+        assert_eq!(chars, expected);
+
+        // A rope built from many edits
+        let mut rope = Rope::new();
+        for (i, c) in text.chars().enumerate() {
+            rope.insert_char(i, c);
+        }
+        assert!(rope.chunks().count() > 1);
+        assert!(rope_char_ranges(&rope).eq(text.char_ranges()));
+    }
+
+    #[test]
+    fn test_rope_slice_char_ranges() {
+        let text = large_text();
+        let rope = Rope::from_str(&text);
+
+        let start = text.char_ranges().nth(1000).unwrap().0.start;
+        let end = text.char_ranges().nth_back(1000).unwrap().0.end;
+        let range = start..end;
+
+        let slice = rope.byte_slice(range.clone());
+        assert!(slice.chunks().count() > 1);
+
+        let chars = rope_slice_char_ranges(slice).collect::<Vec<_>>();
+        let expected = text[range.clone()].char_ranges().collect::<Vec<_>>();
+        assert_eq!(chars, expected);
+
+        // Positions relative to the whole rope
+        let chars = rope_slice_char_ranges_offset(slice, range.start).collect::<Vec<_>>();
+        let expected = text.char_ranges_in(range).collect::<Vec<_>>();
+        assert_eq!(chars, expected);
+    }
+}
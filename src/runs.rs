@@ -0,0 +1,189 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::CharRangesOffset;
+
+/// An iterator over maximal runs of [`char`]s, where each run either
+/// entirely matches a predicate or entirely doesn't.
+///
+/// Produced items are `(range, run, matched)`, where `matched` is the
+/// result of the predicate for all [`char`]s in the run. Runs alternate
+/// between matching and not matching, and together they cover the
+/// whole input.
+///
+/// See [`CharRanges::char_runs()`](crate::CharRanges::char_runs).
+#[derive(Clone)]
+pub struct CharRuns<'a, P> {
+    iter: CharRangesOffset<'a>,
+    pred: P,
+    /// The result of `pred` for the first [`char`] of the next run,
+    /// if it has already been evaluated.
+    matched: Option<bool>,
+}
+
+impl<'a, P> CharRuns<'a, P>
+where
+    P: FnMut(char) -> bool,
+{
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>, pred: P) -> Self {
+        Self {
+            iter,
+            pred,
+            matched: None,
+        }
+    }
+
+    /// Returns the remaining substring, i.e. the part of the input
+    /// which is not covered by any run returned so far.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "123 abc";
+    ///
+    /// let mut runs = text.char_ranges().char_runs(|c| c.is_ascii_digit());
+    /// assert_eq!(runs.as_str(), "123 abc");
+    ///
+    /// assert_eq!(runs.next(), Some((0..3, "123", true)));
+    /// assert_eq!(runs.as_str(), " abc");
+    /// ```
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all produced ranges.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+}
+
+impl<'a, P> Iterator for CharRuns<'a, P>
+where
+    P: FnMut(char) -> bool,
+{
+    type Item = (Range<usize>, &'a str, bool);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let text = self.iter.as_str();
+
+        let (first, c) = self.iter.next()?;
+        let matched = match self.matched.take() {
+            Some(matched) => matched,
+            None => (self.pred)(c),
+        };
+
+        let mut count = 0;
+        let mut len = first.end - first.start;
+        for c in self.iter.as_str().chars() {
+            let m = (self.pred)(c);
+            if m != matched {
+                self.matched = Some(m);
+                break;
+            }
+            count += 1;
+            len += c.len_utf8();
+        }
+
+        if count > 0 {
+            _ = self.iter.nth(count - 1);
+        }
+
+        Some((first.start..(first.start + len), &text[..len], matched))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (lower.min(1), upper)
+    }
+}
+
+impl<P> FusedIterator for CharRuns<'_, P> where P: FnMut(char) -> bool {}
+
+impl<P> fmt::Debug for CharRuns<'_, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CharRuns")
+            .field("iter", &self.iter)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::CharRangesExt;
+
+    #[test]
+    fn test_char_runs() {
+        let text = "  foo123 bar ";
+
+        let mut runs = text.char_ranges().char_runs(char::is_alphanumeric);
+        assert_eq!(runs.next(), Some((0..2, "  ", false)));
+        assert_eq!(runs.as_str(), "foo123 bar ");
+        assert_eq!(runs.next(), Some((2..8, "foo123", true)));
+        assert_eq!(runs.next(), Some((8..9, " ", false)));
+        assert_eq!(runs.next(), Some((9..12, "bar", true)));
+        assert_eq!(runs.as_str(), " ");
+        assert_eq!(runs.next(), Some((12..13, " ", false)));
+        assert_eq!(runs.as_str(), "");
+        assert_eq!(runs.next(), None);
+    }
+
+    #[test]
+    fn test_char_runs_single_run() {
+        let text = "🗻∈🌏";
+
+        let mut runs = text.char_ranges().char_runs(|_| true);
+        assert_eq!(runs.next(), Some((0..11, "🗻∈🌏", true)));
+        assert_eq!(runs.next(), None);
+
+        let mut runs = text.char_ranges().char_runs(|_| false);
+        assert_eq!(runs.next(), Some((0..11, "🗻∈🌏", false)));
+        assert_eq!(runs.next(), None);
+
+        let mut runs = "".char_ranges().char_runs(|_| true);
+        assert_eq!(runs.next(), None);
+    }
+
+    #[test]
+    fn test_char_runs_multi_byte() {
+        let text = "🗻∈12🌏 45∈";
+
+        let mut runs = text.char_ranges().char_runs(|c| c.is_ascii_digit());
+        assert_eq!(runs.next(), Some((0..7, "🗻∈", false)));
+        assert_eq!(runs.next(), Some((7..9, "12", true)));
+        assert_eq!(runs.next(), Some((9..14, "🌏 ", false)));
+        assert_eq!(runs.next(), Some((14..16, "45", true)));
+        assert_eq!(runs.next(), Some((16..19, "∈", false)));
+        assert_eq!(runs.next(), None);
+    }
+
+    #[test]
+    fn test_char_runs_tile() {
+        let text = "Hello 🗻12∈45🌏 World";
+
+        let mut end = 0;
+        for (r, run, _matched) in text.char_ranges().char_runs(char::is_alphabetic) {
+            assert_eq!(r.start, end);
+            assert_eq!(&text[r.clone()], run);
+            end = r.end;
+        }
+        assert_eq!(end, text.len());
+    }
+
+    #[test]
+    fn test_char_runs_offset() {
+        let text = "ab  cd";
+
+        let mut runs = text.char_ranges_offset(10).char_runs(char::is_whitespace);
+        assert_eq!(runs.next(), Some((10..12, "ab", false)));
+        assert_eq!(runs.next(), Some((12..14, "  ", true)));
+        assert_eq!(runs.next(), Some((14..16, "cd", false)));
+        assert_eq!(runs.next(), None);
+    }
+}
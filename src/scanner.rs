@@ -0,0 +1,829 @@
+use core::ops::Range;
+
+use crate::{CharRangesOffset, IntOverflowError, UnterminatedString};
+
+/// A scanner for writing lexers, which consumes [`char`]s and groups
+/// them into tokens, i.e. spans of the text.
+///
+/// A token starts where the previous token finished, i.e. everything
+/// consumed since the last call to [`finish_token()`] is a part of the
+/// next token.
+///
+/// See [`tokens()`] for driving a scanner with a closure, which
+/// produces one token at a time.
+///
+/// Note: Cloning the scanner is essentially a copy.
+///
+/// See [`CharRanges::scanner()`] for more information.
+///
+/// [`finish_token()`]: Scanner::finish_token
+/// [`tokens()`]: crate::tokens
+/// [`CharRanges::scanner()`]: crate::CharRanges::scanner
+#[derive(Clone, Debug)]
+pub struct Scanner<'a> {
+    iter: CharRangesOffset<'a>,
+    /// The remaining text at the start of the current token.
+    token: &'a str,
+    /// Start byte position of the current token, with the offset applied.
+    token_start: usize,
+}
+
+impl<'a> Scanner<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>) -> Self {
+        Self {
+            token: iter.as_str(),
+            token_start: iter.front_offset(),
+            iter,
+        }
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+
+    /// Returns `true` if all [`char`]s have been consumed.
+    #[inline]
+    pub fn is_eof(&self) -> bool {
+        self.iter.as_str().is_empty()
+    }
+
+    /// Returns the next [`char`] and its start and end byte positions,
+    /// without consuming it.
+    #[inline]
+    pub fn peek(&self) -> Option<(Range<usize>, char)> {
+        self.iter.peek()
+    }
+
+    /// Consumes and returns the next [`char`] and its start and end
+    /// byte positions.
+    #[inline]
+    pub fn bump(&mut self) -> Option<(Range<usize>, char)> {
+        self.iter.next()
+    }
+
+    /// Consumes the next [`char`] only if it is `expected`, and returns
+    /// `true` if it was consumed.
+    #[inline]
+    pub fn accept(&mut self, expected: char) -> bool {
+        self.iter.next_if_eq(expected).is_some()
+    }
+
+    /// Consumes the next [`char`] only if `pred` returns `true` for it,
+    /// and returns `true` if it was consumed.
+    #[inline]
+    pub fn accept_if<P>(&mut self, pred: P) -> bool
+    where
+        P: FnOnce(char) -> bool,
+    {
+        self.iter.next_if(pred).is_some()
+    }
+
+    /// Consumes the longest prefix of [`char`]s, for which `pred` returns
+    /// `true`, and returns its byte range.
+    ///
+    /// If nothing is consumed, then an empty range at the current
+    /// position is returned.
+    #[inline]
+    pub fn accept_while<P>(&mut self, pred: P) -> Range<usize>
+    where
+        P: FnMut(char) -> bool,
+    {
+        let (r, _s) = self.iter.take_while_range(pred);
+        r
+    }
+
+    /// Consumes an unsigned integer in `radix`, and returns its byte range
+    /// along with its value.
+    ///
+    /// The integer is a maximal run of digits in `radix`, see
+    /// [`char::to_digit()`], where leading zeros are allowed. If
+    /// `underscores` is `true`, then `'_'` is allowed as a separator
+    /// anywhere after the first digit, e.g. `1_000`. If `radix` is `16`,
+    /// `8`, or `2`, then the integer can be prefixed by respectively
+    /// `0x`, `0o`, or `0b`, in any case, if a digit follows the prefix.
+    /// The prefix is included in the range.
+    ///
+    /// Returns `None` and consumes nothing, if the next [`char`] is not a
+    /// digit. If the integer overflows `u128`, then the whole integer is
+    /// still consumed, and an error with its byte range is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is not in the range `2..=36`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "0xFF_FF+007";
+    ///
+    /// let mut scanner = text.char_ranges().scanner();
+    /// assert_eq!(scanner.scan_unsigned(16, true), Some(Ok((0..7, 0xFFFF))));
+    /// assert_eq!(scanner.scan_unsigned(10, false), None);
+    /// assert!(scanner.accept('+'));
+    /// assert_eq!(scanner.scan_unsigned(10, false), Some(Ok((8..11, 7))));
+    /// ```
+    pub fn scan_unsigned(
+        &mut self,
+        radix: u32,
+        underscores: bool,
+    ) -> Option<Result<(Range<usize>, u128), IntOverflowError>> {
+        let start = self.iter.front_offset();
+        let (len, value) = scan_digits(self.iter.as_str(), radix, underscores)?;
+        self.iter.iter.start += len;
+
+        let r = start..(start + len);
+        Some(
+            value
+                .map(|value| (r.clone(), value))
+                .ok_or(IntOverflowError::new(r)),
+        )
+    }
+
+    /// Consumes a signed integer in `radix`, i.e. an unsigned integer
+    /// optionally preceded by `'-'` or `'+'`, and returns its byte range
+    /// along with its value.
+    ///
+    /// Returns `None` and consumes nothing, if the sign, if any, is not
+    /// followed by a digit. If the integer overflows `i128`, then the whole
+    /// integer is still consumed, and an error with its byte range is
+    /// returned.
+    ///
+    /// See [`scan_unsigned()`] for more information.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is not in the range `2..=36`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "-12 +0b101 -x";
+    ///
+    /// let mut scanner = text.char_ranges().scanner();
+    /// assert_eq!(scanner.scan_signed(10, false), Some(Ok((0..3, -12))));
+    /// assert!(scanner.accept(' '));
+    /// assert_eq!(scanner.scan_signed(2, false), Some(Ok((4..10, 5))));
+    /// assert!(scanner.accept(' '));
+    /// assert_eq!(scanner.scan_signed(10, false), None);
+    /// assert_eq!(scanner.peek(), Some((11..12, '-')));
+    /// ```
+    ///
+    /// [`scan_unsigned()`]: Self::scan_unsigned
+    pub fn scan_signed(
+        &mut self,
+        radix: u32,
+        underscores: bool,
+    ) -> Option<Result<(Range<usize>, i128), IntOverflowError>> {
+        let start = self.iter.front_offset();
+        let rest = self.iter.as_str();
+
+        let (negative, sign_len) = match rest.as_bytes().first() {
+            Some(b'-') => (true, 1),
+            Some(b'+') => (false, 1),
+            _ => (false, 0),
+        };
+
+        let (len, value) = scan_digits(&rest[sign_len..], radix, underscores)?;
+        let len = sign_len + len;
+        self.iter.iter.start += len;
+
+        let value = value.and_then(|value| {
+            if negative {
+                0i128.checked_sub_unsigned(value)
+            } else {
+                i128::try_from(value).ok()
+            }
+        });
+
+        let r = start..(start + len);
+        Some(
+            value
+                .map(|value| (r.clone(), value))
+                .ok_or(IntOverflowError::new(r)),
+        )
+    }
+
+    /// Consumes a float-shaped token, and returns its byte range, such that
+    /// the caller can parse it, e.g. with [`str::parse()`].
+    ///
+    /// The token consists of ASCII digits, optionally followed by a fraction,
+    /// i.e. `'.'` and ASCII digits, and optionally followed by an exponent,
+    /// i.e. `'e'` or `'E'`, an optional `'+'` or `'-'`, and ASCII digits.
+    /// Integers like `42` are also float-shaped. No sign is consumed,
+    /// i.e. a leading `'-'` is left to the caller.
+    ///
+    /// A fraction or an exponent is only consumed if it contains at least
+    /// one digit. So `1.` only consumes `1`, leaving `.` for e.g. `1..2` or
+    /// `1.max(2)`, and `1e` only consumes `1`. A leading `.` is not
+    /// consumed, i.e. `.5` returns `None`.
+    ///
+    /// Returns `None` and consumes nothing, if the next [`char`] is not an
+    /// ASCII digit.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "1.5e-3*2.";
+    ///
+    /// let mut scanner = text.char_ranges().scanner();
+    /// let r = scanner.scan_float_range().unwrap();
+    /// assert_eq!(r, 0..6);
+    /// assert_eq!(text[r].parse::<f64>(), Ok(0.0015));
+    ///
+    /// assert_eq!(scanner.scan_float_range(), None);
+    /// assert!(scanner.accept('*'));
+    /// assert_eq!(scanner.scan_float_range(), Some(7..8));
+    /// assert_eq!(scanner.peek(), Some((8..9, '.')));
+    /// ```
+    pub fn scan_float_range(&mut self) -> Option<Range<usize>> {
+        let bytes = self.iter.as_str().as_bytes();
+        let digits = |i: usize| {
+            bytes
+                .get(i..)
+                .map_or(0, |b| b.iter().take_while(|b| b.is_ascii_digit()).count())
+        };
+
+        let mut len = digits(0);
+        if len == 0 {
+            return None;
+        }
+
+        if bytes.get(len) == Some(&b'.') {
+            let n = digits(len + 1);
+            if n > 0 {
+                len += 1 + n;
+            }
+        }
+
+        if matches!(bytes.get(len), Some(b'e' | b'E')) {
+            let mut i = len + 1;
+            if matches!(bytes.get(i), Some(b'+' | b'-')) {
+                i += 1;
+            }
+            let n = digits(i);
+            if n > 0 {
+                len = i + n;
+            }
+        }
+
+        let start = self.iter.front_offset();
+        self.iter.iter.start += len;
+        Some(start..(start + len))
+    }
+
+    /// Consumes a quoted string delimited by `quote`, e.g. `"foo"`, and
+    /// returns the byte ranges both including and excluding the quotes.
+    ///
+    /// See [`scan_quoted_pair()`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{CharRangesExt, QuotedRange};
+    ///
+    /// let text = r#""a\"b" x"#;
+    ///
+    /// let mut scanner = text.char_ranges().scanner();
+    /// let quoted = scanner.scan_quoted('"', Some('\\')).unwrap().unwrap();
+    /// assert_eq!(quoted, QuotedRange { outer: 0..6, inner: 1..5 });
+    /// assert_eq!(&text[quoted.inner], r#"a\"b"#);
+    /// assert_eq!(scanner.peek(), Some((6..7, ' ')));
+    /// ```
+    ///
+    /// [`scan_quoted_pair()`]: Self::scan_quoted_pair
+    #[inline]
+    pub fn scan_quoted(
+        &mut self,
+        quote: char,
+        escape: Option<char>,
+    ) -> Option<Result<QuotedRange, UnterminatedString>> {
+        self.scan_quoted_pair(quote, quote, escape)
+    }
+
+    /// Consumes a quoted string starting with `open` and ending with
+    /// `close`, e.g. `«foo»`, and returns the byte ranges both including
+    /// and excluding the quotes. The scanner is left right after the
+    /// closing quote.
+    ///
+    /// If `escape` is `Some`, then the escape [`char`] escapes the [`char`]
+    /// following it, i.e. an escaped `close` or an escaped `escape` does not
+    /// end the string. The escapes are not processed, i.e. the inner range
+    /// includes them as is. If `escape` is the same as `close`, then a
+    /// doubled `close` is an escaped `close`, e.g. `'it''s'` in SQL.
+    ///
+    /// Returns `None` and consumes nothing, if the next [`char`] is not
+    /// `open`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error with the byte range of the opening quote, if there
+    /// is no closing quote, e.g. `"foo` or `"foo\"`. In that case the
+    /// scanner is left at the end, i.e. everything is consumed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{CharRangesExt, QuotedRange};
+    ///
+    /// let text = "«🗻»«∈";
+    ///
+    /// let mut scanner = text.char_ranges().scanner();
+    /// let quoted = scanner.scan_quoted_pair('«', '»', None).unwrap().unwrap();
+    /// assert_eq!(quoted, QuotedRange { outer: 0..8, inner: 2..6 });
+    ///
+    /// let err = scanner.scan_quoted_pair('«', '»', None).unwrap().unwrap_err();
+    /// assert_eq!(err.range(), 8..10);
+    /// assert!(scanner.is_eof());
+    /// ```
+    pub fn scan_quoted_pair(
+        &mut self,
+        open: char,
+        close: char,
+        escape: Option<char>,
+    ) -> Option<Result<QuotedRange, UnterminatedString>> {
+        let (open, _c) = self.iter.next_if_eq(open)?;
+
+        while let Some((r, c)) = self.iter.next() {
+            if c == close {
+                if (escape == Some(close)) && self.accept(close) {
+                    continue;
+                }
+                return Some(Ok(QuotedRange {
+                    outer: open.start..r.end,
+                    inner: open.end..r.start,
+                }));
+            } else if Some(c) == escape {
+                self.iter.next();
+            }
+        }
+
+        Some(Err(UnterminatedString::new(open)))
+    }
+
+    /// Returns the start byte position of the current token.
+    #[inline]
+    pub fn token_start(&self) -> usize {
+        self.token_start
+    }
+
+    /// Returns the byte range and substring of everything consumed since
+    /// the current token started, and starts the next token at the
+    /// current position.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "foo+12";
+    ///
+    /// let mut scanner = text.char_ranges().scanner();
+    /// scanner.accept_while(char::is_alphabetic);
+    /// assert_eq!(scanner.finish_token(), (0..3, "foo"));
+    ///
+    /// assert!(scanner.accept('+'));
+    /// assert_eq!(scanner.finish_token(), (3..4, "+"));
+    ///
+    /// assert_eq!(scanner.token_start(), 4);
+    /// ```
+    pub fn finish_token(&mut self) -> (Range<usize>, &'a str) {
+        let start = self.token_start;
+        let end = self.iter.front_offset();
+        let token = &self.token[..(end - start)];
+
+        self.token = self.iter.as_str();
+        self.token_start = end;
+
+        (start..end, token)
+    }
+}
+
+/// The byte ranges of a quoted string, both including and excluding
+/// the quotes.
+///
+/// See [`Scanner::scan_quoted()`] and [`Scanner::scan_quoted_pair()`].
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct QuotedRange {
+    /// The start and end byte positions of the string, including the
+    /// opening and closing quotes.
+    pub outer: Range<usize>,
+    /// The start and end byte positions of the contents of the string,
+    /// excluding the quotes.
+    pub inner: Range<usize>,
+}
+
+/// Returns the length in bytes of the integer in `radix` at the start
+/// of `s`, and its value, or `None` if it overflows `u128`.
+fn scan_digits(s: &str, radix: u32, underscores: bool) -> Option<(usize, Option<u128>)> {
+    assert!(
+        (2..=36).contains(&radix),
+        "radix must be in the range 2..=36"
+    );
+
+    let prefix = match radix {
+        2 => "0b",
+        8 => "0o",
+        16 => "0x",
+        _ => "",
+    };
+
+    let mut len = 0;
+    if !prefix.is_empty()
+        && s.get(..2).is_some_and(|p| p.eq_ignore_ascii_case(prefix))
+        && s[2..].chars().next().is_some_and(|c| c.is_digit(radix))
+    {
+        len = 2;
+    }
+
+    let mut value = Some(0u128);
+    let mut digits = 0;
+    for c in s[len..].chars() {
+        if let Some(digit) = c.to_digit(radix) {
+            value = value
+                .and_then(|value| value.checked_mul(u128::from(radix)))
+                .and_then(|value| value.checked_add(u128::from(digit)));
+            digits += 1;
+        } else if !(underscores && (c == '_') && (digits > 0)) {
+            break;
+        }
+        // Digits and '_' are ASCII
+        len += 1;
+    }
+
+    if digits == 0 {
+        return None;
+    }
+    Some((len, value))
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use super::{QuotedRange, Scanner};
+    use crate::CharRangesExt;
+
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    enum Token {
+        Ident,
+        Int,
+        Op,
+        Space,
+    }
+
+    fn lex<'a>(mut scanner: Scanner<'a>) -> Vec<(Token, core::ops::Range<usize>, &'a str)> {
+        let mut tokens = Vec::new();
+        while let Some((_r, c)) = scanner.bump() {
+            let kind = if c.is_alphabetic() || (c == '_') {
+                scanner.accept_while(|c| c.is_alphanumeric() || (c == '_'));
+                Token::Ident
+            } else if c.is_ascii_digit() {
+                scanner.accept_while(|c| c.is_ascii_digit());
+                Token::Int
+            } else if c.is_whitespace() {
+                scanner.accept_while(char::is_whitespace);
+                Token::Space
+            } else {
+                match c {
+                    '=' | '<' | '>' | '!' => _ = scanner.accept('='),
+                    '-' => _ = scanner.accept_if(|c| c == '>'),
+                    _ => {}
+                }
+                Token::Op
+            };
+            let (r, s) = scanner.finish_token();
+            tokens.push((kind, r, s));
+        }
+        tokens
+    }
+
+    #[test]
+    fn test_scanner_lexer() {
+        let text = "let bär_2 = 12+x->y  >= 345 ∈ z";
+
+        let tokens = lex(text.char_ranges().scanner());
+        for (_kind, r, s) in &tokens {
+            assert_eq!(&text[r.clone()], *s);
+        }
+
+        let tokens = tokens
+            .iter()
+            .filter(|(kind, _r, _s)| *kind != Token::Space)
+            .map(|(kind, _r, s)| (*kind, *s))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            tokens,
+            [
+                (Token::Ident, "let"),
+                (Token::Ident, "bär_2"),
+                (Token::Op, "="),
+                (Token::Int, "12"),
+                (Token::Op, "+"),
+                (Token::Ident, "x"),
+                (Token::Op, "->"),
+                (Token::Ident, "y"),
+                (Token::Op, ">="),
+                (Token::Int, "345"),
+                (Token::Op, "∈"),
+                (Token::Ident, "z"),
+            ]
+        );
+
+        // Tokens are contiguous and cover the whole text
+        let tokens = lex(text.char_ranges().scanner());
+        assert_eq!(tokens.first().unwrap().1.start, 0);
+        assert_eq!(tokens.last().unwrap().1.end, text.len());
+        for pair in tokens.windows(2) {
+            assert_eq!(pair[0].1.end, pair[1].1.start);
+        }
+    }
+
+    #[test]
+    fn test_scanner_offset() {
+        let file = "fn main() { x >= 12 }";
+
+        let start = 12;
+        let mut scanner = file[start..].char_ranges_offset(start).scanner();
+        assert_eq!(scanner.offset(), start);
+        assert_eq!(scanner.token_start(), start);
+
+        let tokens = lex(scanner.clone());
+        for (_kind, r, s) in &tokens {
+            assert_eq!(&file[r.clone()], *s);
+        }
+        assert_eq!(tokens[0], (Token::Ident, 12..13, "x"));
+        assert_eq!(tokens[2], (Token::Op, 14..16, ">="));
+        assert_eq!(tokens[4], (Token::Int, 17..19, "12"));
+
+        assert_eq!(scanner.accept_while(char::is_alphabetic), 12..13);
+        assert_eq!(scanner.accept_while(char::is_alphabetic), 13..13);
+        assert!(!scanner.accept('='));
+        assert_eq!(scanner.finish_token(), (12..13, "x"));
+        assert_eq!(scanner.finish_token(), (13..13, ""));
+        assert_eq!(scanner.peek(), Some((13..14, ' ')));
+    }
+
+    #[test]
+    fn test_scanner_eof() {
+        let mut scanner = "".char_ranges().scanner();
+        assert!(scanner.is_eof());
+        assert_eq!(scanner.bump(), None);
+        assert_eq!(scanner.finish_token(), (0..0, ""));
+
+        let mut scanner = "∈".char_ranges().scanner();
+        assert!(!scanner.is_eof());
+        assert!(scanner.accept('∈'));
+        assert!(scanner.is_eof());
+        assert_eq!(scanner.finish_token(), (0..3, "∈"));
+    }
+
+    #[test]
+    fn test_scan_unsigned() {
+        // Leading zeros
+        let mut scanner = "000123x".char_ranges().scanner();
+        assert_eq!(scanner.scan_unsigned(10, false), Some(Ok((0..6, 123))));
+        assert_eq!(scanner.peek(), Some((6..7, 'x')));
+
+        // Underscores
+        let mut scanner = "1_000_ _1".char_ranges().scanner();
+        assert_eq!(scanner.scan_unsigned(10, true), Some(Ok((0..6, 1000))));
+        assert!(scanner.accept(' '));
+        assert_eq!(scanner.scan_unsigned(10, true), None);
+        assert_eq!(scanner.peek(), Some((7..8, '_')));
+
+        let mut scanner = "1_000".char_ranges().scanner();
+        assert_eq!(scanner.scan_unsigned(10, false), Some(Ok((0..1, 1))));
+
+        // At EOF
+        let mut scanner = "x42".char_ranges_offset(10).scanner();
+        assert!(scanner.accept('x'));
+        assert_eq!(scanner.scan_unsigned(10, false), Some(Ok((11..13, 42))));
+        assert!(scanner.is_eof());
+        assert_eq!(scanner.scan_unsigned(10, false), None);
+    }
+
+    #[test]
+    fn test_scan_unsigned_prefix() {
+        for (text, radix, expected) in [
+            ("0x1F", 16, Some((0..4, 0x1F))),
+            ("0X1f", 16, Some((0..4, 0x1F))),
+            ("1F", 16, Some((0..2, 0x1F))),
+            ("0o17", 8, Some((0..4, 0o17))),
+            ("0b101", 2, Some((0..5, 0b101))),
+            // The prefix must match the radix
+            ("0x1F", 10, Some((0..1, 0))),
+            ("0b1", 16, Some((0..3, 0xB1))),
+            // The prefix must be followed by a digit
+            ("0x", 16, Some((0..1, 0))),
+            ("0xg", 16, Some((0..1, 0))),
+            ("0b2", 2, Some((0..1, 0))),
+            ("x1", 16, None),
+        ] {
+            let mut scanner = text.char_ranges().scanner();
+            let actual = scanner.scan_unsigned(radix, false).map(Result::unwrap);
+            assert_eq!(actual, expected, "{text:?}");
+        }
+    }
+
+    #[test]
+    fn test_scan_unsigned_overflow() {
+        let max = std::format!("{}", u128::MAX);
+        let mut scanner = max.char_ranges().scanner();
+        assert_eq!(
+            scanner.scan_unsigned(10, false),
+            Some(Ok((0..39, u128::MAX)))
+        );
+
+        let text = std::format!("{max}0+");
+        let mut scanner = text.char_ranges().scanner();
+        let err = scanner.scan_unsigned(10, false).unwrap().unwrap_err();
+        assert_eq!(err.range(), 0..40);
+        assert_eq!(scanner.peek(), Some((40..41, '+')));
+    }
+
+    #[test]
+    fn test_scan_signed() {
+        let mut scanner = "-0x1F+7 - 1".char_ranges().scanner();
+        assert_eq!(scanner.scan_signed(16, false), Some(Ok((0..5, -0x1F))));
+        assert_eq!(scanner.scan_signed(10, false), Some(Ok((5..7, 7))));
+        assert!(scanner.accept(' '));
+        assert_eq!(scanner.scan_signed(10, false), None);
+        assert_eq!(scanner.peek(), Some((8..9, '-')));
+
+        let min = std::format!("{}", i128::MIN);
+        let mut scanner = min.char_ranges().scanner();
+        assert_eq!(scanner.scan_signed(10, false), Some(Ok((0..40, i128::MIN))));
+
+        let max = std::format!("+{}", i128::MAX);
+        let mut scanner = max.char_ranges().scanner();
+        assert_eq!(scanner.scan_signed(10, false), Some(Ok((0..40, i128::MAX))));
+
+        let text = std::format!("{}", i128::MAX as u128 + 1);
+        let mut scanner = text.char_ranges().scanner();
+        let err = scanner.scan_signed(10, false).unwrap().unwrap_err();
+        assert_eq!(err.range(), 0..39);
+        assert!(scanner.is_eof());
+    }
+
+    #[test]
+    fn test_scan_float_range() {
+        for (text, expected) in [
+            ("0", Some(0..1)),
+            ("42", Some(0..2)),
+            ("3.25", Some(0..4)),
+            ("1e9", Some(0..3)),
+            ("1E+9", Some(0..4)),
+            ("2.5e-10", Some(0..7)),
+            // Incomplete fractions and exponents are not consumed
+            ("1.", Some(0..1)),
+            ("1..2", Some(0..1)),
+            ("1.x", Some(0..1)),
+            ("1e", Some(0..1)),
+            ("1e+", Some(0..1)),
+            ("1.5e", Some(0..3)),
+            // No leading digit
+            (".5", None),
+            ("-1", None),
+            ("", None),
+        ] {
+            let mut scanner = text.char_ranges().scanner();
+            assert_eq!(scanner.scan_float_range(), expected.clone(), "{text:?}");
+
+            let end = expected.map_or(0, |r| r.end);
+            assert_eq!(scanner.as_str(), &text[end..], "{text:?}");
+        }
+
+        let text = "x = 1.5;";
+        let mut scanner = text[4..].char_ranges_offset(4).scanner();
+        let r = scanner.scan_float_range().unwrap();
+        assert_eq!(r, 4..7);
+        assert_eq!(text[r].parse::<f64>(), Ok(1.5));
+    }
+
+    #[test]
+    fn test_scan_quoted() {
+        // Empty string
+        let mut scanner = "\"\"x".char_ranges().scanner();
+        let quoted = scanner.scan_quoted('"', Some('\\')).unwrap().unwrap();
+        assert_eq!(
+            quoted,
+            QuotedRange {
+                outer: 0..2,
+                inner: 1..1
+            }
+        );
+        assert_eq!(scanner.peek(), Some((2..3, 'x')));
+
+        // Not at a quote
+        assert_eq!(scanner.scan_quoted('"', Some('\\')), None);
+        assert_eq!(scanner.peek(), Some((2..3, 'x')));
+
+        // Escaped quotes and escaped escapes
+        let text = r#"'a\'b\\' 'c\\\'d'"#;
+        let mut scanner = text.char_ranges().scanner();
+        let quoted = scanner.scan_quoted('\'', Some('\\')).unwrap().unwrap();
+        assert_eq!(&text[quoted.outer], r#"'a\'b\\'"#);
+        assert_eq!(&text[quoted.inner], r#"a\'b\\"#);
+        assert!(scanner.accept(' '));
+        let quoted = scanner.scan_quoted('\'', Some('\\')).unwrap().unwrap();
+        assert_eq!(&text[quoted.inner], r#"c\\\'d"#);
+        assert!(scanner.is_eof());
+
+        // Without an escape
+        let text = r#""a\"b"#;
+        let mut scanner = text.char_ranges().scanner();
+        let quoted = scanner.scan_quoted('"', None).unwrap().unwrap();
+        assert_eq!(
+            quoted,
+            QuotedRange {
+                outer: 0..4,
+                inner: 1..3
+            }
+        );
+        assert_eq!(scanner.as_str(), "b");
+    }
+
+    #[test]
+    fn test_scan_quoted_doubled() {
+        let text = "'it''s' ''''";
+        let mut scanner = text.char_ranges().scanner();
+        let quoted = scanner.scan_quoted('\'', Some('\'')).unwrap().unwrap();
+        assert_eq!(&text[quoted.inner], "it''s");
+        assert!(scanner.accept(' '));
+        let quoted = scanner.scan_quoted('\'', Some('\'')).unwrap().unwrap();
+        assert_eq!(
+            quoted,
+            QuotedRange {
+                outer: 8..12,
+                inner: 9..11
+            }
+        );
+    }
+
+    #[test]
+    fn test_scan_quoted_pair() {
+        let text = "x «a «b» \\» c» y";
+        let mut scanner = text[2..].char_ranges_offset(2).scanner();
+        let quoted = scanner
+            .scan_quoted_pair('«', '»', Some('\\'))
+            .unwrap()
+            .unwrap();
+        assert_eq!(&text[quoted.outer.clone()], "«a «b»");
+        assert_eq!(&text[quoted.inner.clone()], "a «b");
+        assert_eq!(
+            quoted,
+            QuotedRange {
+                outer: 2..11,
+                inner: 4..9
+            }
+        );
+
+        // Multi-byte escape
+        let text = "«a∈»b»";
+        let mut scanner = text.char_ranges().scanner();
+        let quoted = scanner
+            .scan_quoted_pair('«', '»', Some('∈'))
+            .unwrap()
+            .unwrap();
+        assert_eq!(&text[quoted.inner], "a∈»b");
+        assert!(scanner.is_eof());
+    }
+
+    #[test]
+    fn test_scan_quoted_unterminated() {
+        for text in [r#"x "abc"#, r#"x "abc\""#, r#"x "abc\"#, r#"x ""#] {
+            let mut scanner = text.char_ranges().scanner();
+            assert!(scanner.accept('x'));
+            assert!(scanner.accept(' '));
+            let err = scanner.scan_quoted('"', Some('\\')).unwrap().unwrap_err();
+            assert_eq!(err.range(), 2..3, "{text:?}");
+            assert!(scanner.is_eof(), "{text:?}");
+        }
+
+        let mut scanner = "«🗻".char_ranges_offset(5).scanner();
+        let err = scanner
+            .scan_quoted_pair('«', '»', None)
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(err.range(), 5..7);
+        assert!(scanner.is_eof());
+    }
+}
@@ -0,0 +1,221 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use unicode_script::{Script, UnicodeScript};
+
+use crate::CharRangesOffset;
+
+/// Returns the script of `c`, or `None` if `c` is [`Script::Common`] or
+/// [`Script::Inherited`], i.e. if it is shared by multiple scripts.
+#[inline]
+pub(crate) fn specific_script(c: char) -> Option<Script> {
+    match c.script() {
+        Script::Common | Script::Inherited => None,
+        script => Some(script),
+    }
+}
+
+/// An iterator over maximal runs of [`char`]s sharing the same [`Script`],
+/// producing the start and end byte positions of each run, the script,
+/// and the substring.
+///
+/// Scripts are determined by the [`unicode-script`] crate. [`char`]s of
+/// [`Script::Common`], e.g. spaces, punctuation, digits, and emoji, and
+/// [`Script::Inherited`], e.g. combining marks, are merged into runs as
+/// follows:
+///
+/// - They are merged into the preceding run, i.e. the run always
+///   continues until a [`char`] of a different specific script.
+/// - At the start, i.e. without a preceding run, they are merged into
+///   the following run.
+/// - If a run only contains such [`char`]s, i.e. if the whole input only
+///   contains such [`char`]s, then the run is [`Script::Common`].
+///
+/// Paired punctuation is not matched, e.g. in `"abc (где)"` the `'('`
+/// is part of the Latin run, while the `')'` is part of the Cyrillic run.
+///
+/// The runs tile the input, i.e. adjacent runs have different scripts,
+/// and together they cover the whole input.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRangesExt::script_runs()`] for more information.
+///
+/// [`unicode-script`]: https://docs.rs/unicode-script
+/// [`CharRangesExt::script_runs()`]: crate::CharRangesExt::script_runs
+#[derive(Clone)]
+pub struct ScriptRuns<'a> {
+    iter: CharRangesOffset<'a>,
+}
+
+impl<'a> ScriptRuns<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>) -> Self {
+        Self { iter }
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+}
+
+impl<'a> Iterator for ScriptRuns<'a> {
+    type Item = (Range<usize>, Script, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let text = self.iter.as_str();
+        if text.is_empty() {
+            return None;
+        }
+
+        let mut script = None;
+        let mut len = 0;
+        for c in text.chars() {
+            match (specific_script(c), script) {
+                (Some(next), Some(script)) if next != script => break,
+                (Some(next), None) => script = Some(next),
+                _ => {}
+            }
+            len += c.len_utf8();
+        }
+
+        let start = self.iter.iter.start;
+        self.iter.iter.start += len;
+
+        let r = self.iter.apply_offset(start..(start + len));
+        Some((r, script.unwrap_or(Script::Common), &text[..len]))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (lower.min(1), upper)
+    }
+}
+
+impl FusedIterator for ScriptRuns<'_> {}
+
+impl fmt::Debug for ScriptRuns<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ScriptRuns(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use unicode_script::Script;
+
+    use crate::CharRangesExt;
+
+    #[test]
+    fn test_script_runs_latin_cyrillic() {
+        let text = "Hello Мир";
+
+        let runs = text.script_runs().collect::<Vec<_>>();
+        assert_eq!(
+            runs,
+            [
+                (0..6, Script::Latin, "Hello "),
+                (6..12, Script::Cyrillic, "Мир"),
+            ]
+        );
+
+        // Mixed within a single word, e.g. a homoglyph
+        let text = "pаypal";
+        let runs = text.script_runs().map(|(_r, script, s)| (script, s));
+        assert!(runs.eq([
+            (Script::Latin, "p"),
+            (Script::Cyrillic, "а"),
+            (Script::Latin, "ypal"),
+        ]));
+    }
+
+    #[test]
+    fn test_script_runs_common() {
+        // Spaces and punctuation between runs are merged into the
+        // preceding run, while leading ones are merged into the first run
+        let text = "¡ abc, (где)! 123";
+
+        let runs = text
+            .script_runs()
+            .map(|(_r, script, s)| (script, s))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            runs,
+            [(Script::Latin, "¡ abc, ("), (Script::Cyrillic, "где)! 123")]
+        );
+
+        // Combining marks are inherited
+        let text = "e\u{301}ж\u{301}";
+        let runs = text.script_runs();
+        assert!(runs.eq([
+            (0..3, Script::Latin, "e\u{301}"),
+            (3..7, Script::Cyrillic, "ж\u{301}"),
+        ]));
+    }
+
+    #[test]
+    fn test_script_runs_emoji() {
+        let text = "🌏 日本語🗻!";
+
+        let runs = text.script_runs().collect::<Vec<_>>();
+        assert_eq!(runs, [(0..19, Script::Han, "🌏 日本語🗻!")]);
+
+        // Only common
+        let text = "🌏 123 🗻";
+        let runs = text.script_runs();
+        assert!(runs.eq([(0..13, Script::Common, "🌏 123 🗻")]));
+
+        assert_eq!("".script_runs().next(), None);
+    }
+
+    #[test]
+    fn test_script_runs_tiling() {
+        let text = "ab Γειά σου, мир! שלום 🌏 ok\u{301}";
+
+        let runs = text.char_ranges_offset(3).script_runs();
+        assert_eq!(runs.offset(), 3);
+
+        let mut end = 3;
+        let mut prev = None;
+        for (r, script, s) in runs {
+            assert_eq!(r.start, end);
+            assert_eq!(&text[(r.start - 3)..(r.end - 3)], s);
+            assert_eq!(s.script_of_range(..), Some(script));
+            assert_ne!(prev, Some(script));
+            end = r.end;
+            prev = Some(script);
+        }
+        assert_eq!(end, text.len() + 3);
+    }
+
+    #[test]
+    fn test_script_of_range() {
+        let text = "Hello, Мир! 🌏";
+
+        assert_eq!(text.script_of_range(0..5), Some(Script::Latin));
+        assert_eq!(text.script_of_range(0..7), Some(Script::Latin));
+        assert_eq!(text.script_of_range(5..13), Some(Script::Cyrillic));
+        assert_eq!(text.script_of_range(0..13), None);
+        assert_eq!(text.script_of_range(..), None);
+        assert_eq!(text.script_of_range(5..7), Some(Script::Common));
+        assert_eq!(text.script_of_range(14..), Some(Script::Common));
+        assert_eq!(text.script_of_range(3..3), Some(Script::Common));
+    }
+}
@@ -0,0 +1,219 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+use core::slice;
+
+use crate::{SegmentError, SpanError};
+
+/// An iterator over the segments of a string partitioned by a set of
+/// ranges, i.e. their start and end byte positions, substrings, and
+/// whether they are inside one of the ranges.
+///
+/// The segments alternate between the ranges and the text between them,
+/// and together tile the whole string in order. Empty ranges are skipped,
+/// and no empty segments are produced.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRangesExt::segment_by_ranges()`] for more information.
+///
+/// [`CharRangesExt::segment_by_ranges()`]: crate::CharRangesExt::segment_by_ranges
+#[derive(Clone)]
+pub struct SegmentByRanges<'a, 'b> {
+    text: &'a str,
+    ranges: slice::Iter<'b, Range<usize>>,
+    /// Start byte position of the next segment.
+    pos: usize,
+    /// The range following the segment between ranges,
+    /// which was just produced.
+    next: Option<Range<usize>>,
+}
+
+impl<'a, 'b> SegmentByRanges<'a, 'b> {
+    /// Returns an error if `ranges` are not sorted, overlapping, out of
+    /// bounds, or not on [`char`] boundaries.
+    pub(crate) fn new(text: &'a str, ranges: &'b [Range<usize>]) -> Result<Self, SegmentError> {
+        let mut end = 0;
+        for (index, r) in ranges.iter().enumerate() {
+            let error = if r.start > r.end {
+                SpanError::Reversed
+            } else if r.end > text.len() {
+                SpanError::OutOfBounds
+            } else if !text.is_char_boundary(r.start) || !text.is_char_boundary(r.end) {
+                SpanError::NotCharBoundary
+            } else if r.start < end {
+                return Err(SegmentError::Unsorted { index });
+            } else {
+                end = r.end;
+                continue;
+            };
+            return Err(SegmentError::Span { index, error });
+        }
+
+        Ok(Self {
+            text,
+            ranges: ranges.iter(),
+            pos: 0,
+            next: None,
+        })
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+}
+
+impl<'a> Iterator for SegmentByRanges<'a, '_> {
+    type Item = (Range<usize>, &'a str, bool);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self
+            .next
+            .take()
+            .or_else(|| self.ranges.find(|r| !r.is_empty()).cloned());
+
+        let (r, inside) = match next {
+            Some(r) if r.start == self.pos => (r, true),
+            Some(r) => {
+                let between = self.pos..r.start;
+                self.next = Some(r);
+                (between, false)
+            }
+            None if self.pos < self.text.len() => (self.pos..self.text.len(), false),
+            None => return None,
+        };
+        self.pos = r.end;
+
+        Some((r.clone(), &self.text[r], inside))
+    }
+}
+
+impl FusedIterator for SegmentByRanges<'_, '_> {}
+
+impl fmt::Debug for SegmentByRanges<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SegmentByRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::String;
+    use std::vec::Vec;
+
+    use crate::{CharRangesExt, SegmentError, SpanError};
+
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn test_segment_by_ranges() {
+        let text = "foo 🗻 bar ∈ baz";
+
+        let segments = text
+            .segment_by_ranges(&[4..8, 9..12, 16..20])
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            segments,
+            [
+                (0..4, "foo ", false),
+                (4..8, "🗻", true),
+                (8..9, " ", false),
+                (9..12, "bar", true),
+                (12..16, " ∈", false),
+                (16..20, " baz", true),
+            ]
+        );
+
+        // Tiles the whole text
+        let joined = segments.iter().map(|(_, s, _)| *s).collect::<String>();
+        assert_eq!(joined, text);
+
+        // Adjacent ranges, and ranges at the start and end
+        let segments = text.segment_by_ranges(&[0..3, 3..4, 12..20]).unwrap();
+        assert!(segments.eq([
+            (0..3, "foo", true),
+            (3..4, " ", true),
+            (4..12, "🗻 bar", false),
+            (12..20, " ∈ baz", true),
+        ]));
+
+        assert!(text
+            .segment_by_ranges(&[])
+            .unwrap()
+            .eq([(0..20, text, false)]));
+        assert!(text
+            .segment_by_ranges(&[0..20])
+            .unwrap()
+            .eq([(0..20, text, true)]));
+        assert_eq!("".segment_by_ranges(&[]).unwrap().next(), None);
+    }
+
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn test_segment_by_ranges_empty_ranges() {
+        let text = "foo bar";
+
+        // Empty ranges are skipped
+        let segments = text.segment_by_ranges(&[0..0, 1..1, 4..7, 7..7]).unwrap();
+        assert!(segments.eq([(0..4, "foo ", false), (4..7, "bar", true)]));
+
+        // But must still be valid
+        assert_eq!(
+            text.segment_by_ranges(&[4..7, 5..5]).err(),
+            Some(SegmentError::Unsorted { index: 1 })
+        );
+        assert_eq!(
+            text.segment_by_ranges(&[8..8]).err(),
+            Some(SegmentError::Span {
+                index: 0,
+                error: SpanError::OutOfBounds
+            })
+        );
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn test_segment_by_ranges_invalid() {
+        let text = "foo 🗻 bar";
+
+        let cases = [
+            ([0..3, 2..4], SegmentError::Unsorted { index: 1 }),
+            ([4..8, 0..3], SegmentError::Unsorted { index: 1 }),
+            (
+                [0..3, 5..8],
+                SegmentError::Span {
+                    index: 1,
+                    error: SpanError::NotCharBoundary,
+                },
+            ),
+            (
+                [0..3, 9..13],
+                SegmentError::Span {
+                    index: 1,
+                    error: SpanError::OutOfBounds,
+                },
+            ),
+            (
+                [3..0, 4..8],
+                SegmentError::Span {
+                    index: 0,
+                    error: SpanError::Reversed,
+                },
+            ),
+        ];
+        for (ranges, expected) in cases {
+            assert_eq!(
+                text.segment_by_ranges(&ranges).err(),
+                Some(expected),
+                "{ranges:?}"
+            );
+        }
+    }
+}
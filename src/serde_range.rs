@@ -0,0 +1,148 @@
+//! Serializes a <code>[Range]&lt;[usize]&gt;</code> as `{ "start": n, "end": n }`,
+//! the same as the `range` of [`Spanned`], for use with
+//! `#[serde(with = "char_ranges::serde_range")]`.
+//!
+//! Deserializing fails if `start` is after `end`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::ops::Range;
+//!
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, PartialEq, Debug)]
+//! struct Token {
+//!     kind: String,
+//!     #[serde(with = "char_ranges::serde_range")]
+//!     range: Range<usize>,
+//! }
+//!
+//! let token = Token {
+//!     kind: "ident".to_owned(),
+//!     range: 4..8,
+//! };
+//!
+//! let json = serde_json::to_string(&token).unwrap();
+//! assert_eq!(json, r#"{"kind":"ident","range":{"start":4,"end":8}}"#);
+//!
+//! assert_eq!(serde_json::from_str::<Token>(&json).unwrap(), token);
+//! ```
+//!
+//! [`Spanned`]: crate::Spanned
+
+use core::ops::Range;
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Range")]
+struct RangeRepr {
+    start: usize,
+    end: usize,
+}
+
+/// Serializes `range` as `{ "start": n, "end": n }`.
+#[inline]
+pub fn serialize<S>(range: &Range<usize>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    RangeRepr {
+        start: range.start,
+        end: range.end,
+    }
+    .serialize(serializer)
+}
+
+/// Deserializes `{ "start": n, "end": n }` as a range.
+///
+/// # Errors
+///
+/// Returns an error if `start` is after `end`.
+#[inline]
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Range<usize>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let RangeRepr { start, end } = RangeRepr::deserialize(deserializer)?;
+    if start > end {
+        return Err(D::Error::custom(format_args!(
+            "range start {start} is after end {end}"
+        )));
+    }
+    Ok(start..end)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use core::ops::Range;
+    use std::string::{String, ToString};
+    use std::vec::Vec;
+
+    use serde::{Deserialize, Serialize};
+
+    use crate::{CharRangesExt, LineColumn, Spanned};
+
+    #[test]
+    fn test_serde_spanned() {
+        let text = "🗻∈🌏";
+
+        let chars = text.char_ranges().spanned().collect::<Vec<_>>();
+        let spanned = Spanned::new(0..text.len(), chars);
+
+        let json = serde_json::to_string(&spanned).unwrap();
+        assert_eq!(
+            json,
+            concat!(
+                r#"{"range":{"start":0,"end":11},"value":["#,
+                r#"{"range":{"start":0,"end":4},"value":"🗻"},"#,
+                r#"{"range":{"start":4,"end":7},"value":"∈"},"#,
+                r#"{"range":{"start":7,"end":11},"value":"🌏"}"#,
+                r#"]}"#,
+            ),
+        );
+
+        let actual: Spanned<Vec<Spanned<char>>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(actual, spanned);
+    }
+
+    #[test]
+    fn test_serde_line_column() {
+        let pos = LineColumn::new(2, 7);
+
+        let json = serde_json::to_string(&pos).unwrap();
+        assert_eq!(json, r#"{"line":2,"column":7}"#);
+        assert_eq!(serde_json::from_str::<LineColumn>(&json).unwrap(), pos);
+    }
+
+    #[test]
+    fn test_serde_range() {
+        #[derive(Serialize, Deserialize, PartialEq, Debug)]
+        struct Token {
+            kind: String,
+            #[serde(with = "crate::serde_range")]
+            range: Range<usize>,
+        }
+
+        let token = Token {
+            kind: String::from("ident"),
+            range: 4..8,
+        };
+
+        // Same representation as the range of `Spanned`
+        let json = serde_json::to_string(&token).unwrap();
+        assert_eq!(json, r#"{"kind":"ident","range":{"start":4,"end":8}}"#);
+        assert_eq!(serde_json::from_str::<Token>(&json).unwrap(), token);
+
+        let json = r#"{"kind":"ident","range":{"start":8,"end":4}}"#;
+        let err = serde_json::from_str::<Token>(json).unwrap_err();
+        assert!(err.to_string().contains("range start 8 is after end 4"));
+
+        let json = r#"{"kind":"ident","range":{"start":4}}"#;
+        assert!(serde_json::from_str::<Token>(json).is_err());
+    }
+}
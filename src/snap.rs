@@ -0,0 +1,13 @@
+/// How [`snap_range()`] aligns a range to [`char`] boundaries.
+///
+/// [`snap_range()`]: crate::CharRangesExt::snap_range
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Snap {
+    /// Shrinks the range to the largest subrange on [`char`] boundaries,
+    /// i.e. the start is moved forward and the end is moved backward.
+    Inner,
+    /// Grows the range to the smallest superrange on [`char`] boundaries,
+    /// i.e. the start is moved backward and the end is moved forward.
+    Outer,
+}
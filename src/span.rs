@@ -0,0 +1,312 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::ops::{Range, RangeInclusive};
+
+use crate::CharRangesOffset;
+
+/// A type representing the start and end byte positions of a span of text,
+/// which can be converted from and into <code>[Range]&lt;usize&gt;</code>.
+///
+/// See [`CharRanges::map_span()`] for more information.
+///
+/// # Example
+///
+/// ```rust
+/// use std::ops::Range;
+///
+/// use char_ranges::{CharRangesExt, Span};
+///
+/// #[derive(PartialEq, Debug)]
+/// struct MySpan {
+///     lo: u32,
+///     hi: u32,
+/// }
+///
+/// impl Span for MySpan {
+///     fn from_range(r: Range<usize>) -> Self {
+///         let r = <Range<u32>>::from_range(r);
+///         Self { lo: r.start, hi: r.end }
+///     }
+///
+///     fn into_range(self) -> Range<usize> {
+///         (self.lo as usize)..(self.hi as usize)
+///     }
+/// }
+///
+/// let mut chars = "a🌏".char_ranges().map_span::<MySpan>();
+/// assert_eq!(chars.next(), Some((MySpan { lo: 0, hi: 1 }, 'a')));
+/// assert_eq!(chars.next(), Some((MySpan { lo: 1, hi: 5 }, '🌏')));
+/// ```
+///
+/// [`CharRanges::map_span()`]: crate::CharRanges::map_span
+pub trait Span: Sized {
+    /// Converts the start and end byte positions `r` into `Self`.
+    fn from_range(r: Range<usize>) -> Self;
+
+    /// Converts `self` into start and end byte positions.
+    fn into_range(self) -> Range<usize>;
+}
+
+impl Span for Range<usize> {
+    #[inline]
+    fn from_range(r: Range<usize>) -> Self {
+        r
+    }
+
+    #[inline]
+    fn into_range(self) -> Range<usize> {
+        self
+    }
+}
+
+/// # Panics
+///
+/// [`from_range()`](Span::from_range) panics if a position overflows `u32`.
+impl Span for Range<u32> {
+    #[inline]
+    fn from_range(r: Range<usize>) -> Self {
+        let to_u32 = |pos: usize| match u32::try_from(pos) {
+            Ok(pos) => pos,
+            Err(_) => panic!("position {pos} overflows u32"),
+        };
+        to_u32(r.start)..to_u32(r.end)
+    }
+
+    #[inline]
+    fn into_range(self) -> Range<usize> {
+        (self.start as usize)..(self.end as usize)
+    }
+}
+
+impl Span for (usize, usize) {
+    #[inline]
+    fn from_range(r: Range<usize>) -> Self {
+        (r.start, r.end)
+    }
+
+    #[inline]
+    fn into_range(self) -> Range<usize> {
+        self.0..self.1
+    }
+}
+
+/// # Panics
+///
+/// [`from_range()`](Span::from_range) panics if the range is empty,
+/// which never happens for the range of a [`char`].
+impl Span for RangeInclusive<usize> {
+    #[inline]
+    fn from_range(r: Range<usize>) -> Self {
+        assert!(
+            r.start < r.end,
+            "empty range {r:?} cannot be converted into an inclusive range",
+        );
+        r.start..=(r.end - 1)
+    }
+
+    #[inline]
+    fn into_range(self) -> Range<usize> {
+        let (start, end) = self.into_inner();
+        start..(end + 1)
+    }
+}
+
+/// An iterator over [`char`]s and their start and end byte positions,
+/// converted into the [`Span`] `S`.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRanges::map_span()`] for more information.
+///
+/// [`CharRanges::map_span()`]: crate::CharRanges::map_span
+pub struct CharRangesAs<'a, S> {
+    iter: CharRangesOffset<'a>,
+    span: PhantomData<fn() -> S>,
+}
+
+impl<'a, S> CharRangesAs<'a, S>
+where
+    S: Span,
+{
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>) -> Self {
+        Self {
+            iter,
+            span: PhantomData,
+        }
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+}
+
+impl<S> Clone for CharRangesAs<'_, S> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter,
+            span: PhantomData,
+        }
+    }
+}
+
+impl<S> Iterator for CharRangesAs<'_, S>
+where
+    S: Span,
+{
+    type Item = (S, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (r, c) = self.iter.next()?;
+        Some((S::from_range(r), c))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.iter.count()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let (r, c) = self.iter.nth(n)?;
+        Some((S::from_range(r), c))
+    }
+}
+
+impl<S> DoubleEndedIterator for CharRangesAs<'_, S>
+where
+    S: Span,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (r, c) = self.iter.next_back()?;
+        Some((S::from_range(r), c))
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        let (r, c) = self.iter.nth_back(n)?;
+        Some((S::from_range(r), c))
+    }
+}
+
+impl<S> FusedIterator for CharRangesAs<'_, S> where S: Span {}
+
+impl<S> fmt::Debug for CharRangesAs<'_, S>
+where
+    S: Span + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CharRangesAs(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::ops::{Range, RangeInclusive};
+
+    use super::Span;
+    use crate::CharRangesExt;
+
+    const TEXT: &str = "Hello 🗻12∈45🌏 World ¢";
+
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    struct MySpan {
+        lo: u32,
+        hi: u32,
+    }
+
+    impl Span for MySpan {
+        fn from_range(r: Range<usize>) -> Self {
+            let r = <Range<u32>>::from_range(r);
+            Self {
+                lo: r.start,
+                hi: r.end,
+            }
+        }
+
+        fn into_range(self) -> Range<usize> {
+            (self.lo as usize)..(self.hi as usize)
+        }
+    }
+
+    fn assert_round_trip<S>()
+    where
+        S: Span,
+    {
+        let expected = TEXT.char_ranges_offset(100);
+        let actual = TEXT
+            .char_ranges_offset(100)
+            .map_span::<S>()
+            .map(|(s, c)| (s.into_range(), c));
+        assert!(actual.eq(expected));
+
+        let actual = TEXT
+            .char_ranges_offset(100)
+            .map_span::<S>()
+            .rev()
+            .map(|(s, c)| (s.into_range(), c));
+        assert!(actual.eq(expected.rev()));
+    }
+
+    #[test]
+    fn test_span_round_trip() {
+        assert_round_trip::<Range<usize>>();
+        assert_round_trip::<Range<u32>>();
+        assert_round_trip::<(usize, usize)>();
+        assert_round_trip::<RangeInclusive<usize>>();
+        assert_round_trip::<MySpan>();
+    }
+
+    #[test]
+    fn test_map_span() {
+        let mut chars = TEXT.char_ranges().map_span::<RangeInclusive<usize>>();
+        assert_eq!(chars.next(), Some((0..=0, 'H')));
+        assert_eq!(chars.nth(5), Some((6..=9, '🗻')));
+        assert_eq!(chars.nth_back(1), Some((27..=27, ' ')));
+        assert_eq!(chars.as_str(), "12∈45🌏 World");
+        assert_eq!(chars.clone().count(), 12);
+        assert_eq!(chars.last(), Some((26..=26, 'd')));
+
+        // Offsets are applied before converting
+        let text = "Hello 👋 World 🌏";
+        let start = 11;
+        let mut chars = text[start..].char_ranges_offset(start).map_span::<MySpan>();
+        assert_eq!(chars.offset(), start);
+        assert_eq!(chars.next(), Some((MySpan { lo: 11, hi: 12 }, 'W')));
+        assert_eq!(chars.next_back(), Some((MySpan { lo: 17, hi: 21 }, '🌏')));
+    }
+
+    #[test]
+    #[should_panic(expected = "overflows u32")]
+    fn test_map_span_overflow() {
+        let offset = (u32::MAX as usize) - 1;
+        let mut chars = "ab".char_ranges_offset(offset).map_span::<Range<u32>>();
+        assert_eq!(chars.next(), Some(((u32::MAX - 1)..u32::MAX, 'a')));
+        chars.next();
+    }
+}
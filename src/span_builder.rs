@@ -0,0 +1,208 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+type Entry = (Range<usize>, Option<Range<usize>>);
+
+/// A builder of an output string, which records the range each pushed
+/// fragment occupies in the output, e.g. for mapping ranges of generated
+/// code back to the source fragments it was built from.
+///
+/// Every push records an entry of the output range of the fragment, and
+/// the original range it is associated with, if pushed with
+/// [`push_spanned()`](Self::push_spanned). Since fragments are whole
+/// `&str`s and [`char`]s, all output ranges are on [`char`] boundaries.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::SpanBuilder;
+///
+/// let source = "let x = 1;";
+///
+/// let mut out = SpanBuilder::new();
+/// out.push_str("const ");
+/// out.push_spanned(&source[4..5], 4..5);
+/// out.push_str(": i32 = ");
+/// out.push_spanned(&source[8..9], 8..9);
+/// out.push_char(';');
+///
+/// // Maps the output position of `1` back to the source
+/// assert_eq!(out.lookup(15), Some(&(15..16, Some(8..9))));
+/// assert_eq!(out.lookup(2), Some(&(0..6, None)));
+///
+/// let (out, entries) = out.finish();
+/// assert_eq!(out, "const x: i32 = 1;");
+/// assert_eq!(entries.len(), 5);
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct SpanBuilder {
+    out: String,
+    /// The output range of each pushed fragment, and its original
+    /// range, if any. Output ranges are contiguous and ascending.
+    entries: Vec<Entry>,
+}
+
+impl SpanBuilder {
+    /// Creates an empty builder.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            out: String::new(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Returns the output string built so far.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.out
+    }
+
+    /// Returns the byte length of the output string built so far.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.out.len()
+    }
+
+    /// Returns `true` if the output string built so far is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.out.is_empty()
+    }
+
+    /// Returns the entries recorded so far, i.e. the output range of
+    /// each pushed fragment, and its original range, if any.
+    #[inline]
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Appends `s` to the output, and returns the range it occupies
+    /// in the output.
+    #[inline]
+    pub fn push_str(&mut self, s: &str) -> Range<usize> {
+        self.push(s, None)
+    }
+
+    /// Appends `c` to the output, and returns the range it occupies
+    /// in the output.
+    #[inline]
+    pub fn push_char(&mut self, c: char) -> Range<usize> {
+        self.push(c.encode_utf8(&mut [0; 4]), None)
+    }
+
+    /// Appends `s` to the output, associated with the range `original`,
+    /// and returns the range it occupies in the output.
+    ///
+    /// The `original` range is stored as is, e.g. it can be the range of
+    /// `s` in a source text, and is not required to have the same length
+    /// as `s`.
+    #[inline]
+    pub fn push_spanned(&mut self, s: &str, original: Range<usize>) -> Range<usize> {
+        self.push(s, Some(original))
+    }
+
+    fn push(&mut self, s: &str, original: Option<Range<usize>>) -> Range<usize> {
+        let start = self.out.len();
+        self.out.push_str(s);
+        let r = start..self.out.len();
+        self.entries.push((r.clone(), original));
+        r
+    }
+
+    /// Returns the entry of the fragment containing the output byte
+    /// position `pos`, or `None` if `pos` is out of bounds, i.e.
+    /// `pos >= len`.
+    ///
+    /// Empty fragments never contain a position.
+    pub fn lookup(&self, pos: usize) -> Option<&Entry> {
+        let i = self.entries.partition_point(|(r, _)| r.end <= pos);
+        self.entries.get(i)
+    }
+
+    /// Returns the output string and the recorded entries.
+    #[inline]
+    pub fn finish(self) -> (String, Vec<Entry>) {
+        (self.out, self.entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use super::SpanBuilder;
+
+    #[test]
+    fn test_span_builder() {
+        let source = "fn 🗻() { ∈🌏 }";
+
+        let mut out = SpanBuilder::new();
+        assert!(out.is_empty());
+        assert_eq!(out.lookup(0), None);
+
+        assert_eq!(out.push_str("pub "), 0..4);
+        assert_eq!(out.push_spanned(&source[..7], 0..7), 4..11);
+        assert_eq!(out.push_char('¢'), 11..13);
+        assert_eq!(out.push_str(""), 13..13);
+        assert_eq!(out.push_spanned(&source[12..19], 12..19), 13..20);
+        assert_eq!(out.push_spanned("", 19..19), 20..20);
+        assert_eq!(out.push_str(" }"), 20..22);
+
+        assert_eq!(out.as_str(), "pub fn 🗻¢∈🌏 }");
+        assert_eq!(out.len(), 22);
+
+        // At the fragment edges
+        assert_eq!(out.lookup(3), Some(&(0..4, None)));
+        assert_eq!(out.lookup(4), Some(&(4..11, Some(0..7))));
+        assert_eq!(out.lookup(10), Some(&(4..11, Some(0..7))));
+        assert_eq!(out.lookup(11), Some(&(11..13, None)));
+        // Skips the empty fragments
+        assert_eq!(out.lookup(13), Some(&(13..20, Some(12..19))));
+        assert_eq!(out.lookup(19), Some(&(13..20, Some(12..19))));
+        assert_eq!(out.lookup(20), Some(&(20..22, None)));
+        assert_eq!(out.lookup(22), None);
+
+        let (s, entries) = out.finish();
+        assert_eq!(s, "pub fn 🗻¢∈🌏 }");
+        assert_eq!(entries.len(), 7);
+
+        // Output ranges tile the output and are on `char` boundaries
+        let mut end = 0;
+        for (r, original) in &entries {
+            assert_eq!(r.start, end);
+            assert!(s.get(r.clone()).is_some());
+            if let Some(original) = original {
+                assert_eq!(&s[r.clone()], &source[original.clone()]);
+            }
+            end = r.end;
+        }
+        assert_eq!(end, s.len());
+    }
+
+    #[test]
+    fn test_span_builder_lookup() {
+        let mut out = SpanBuilder::new();
+        let spanned = (0..10)
+            .map(|i| {
+                out.push_str("∈");
+                out.push_spanned("🌏", (i * 10)..(i * 10 + 4))
+            })
+            .collect::<Vec<_>>();
+
+        for (i, r) in spanned.iter().enumerate() {
+            for pos in r.clone() {
+                let (found, original) = out.lookup(pos).unwrap();
+                assert_eq!(found, r);
+                assert_eq!(original.clone(), Some((i * 10)..(i * 10 + 4)));
+            }
+            assert_eq!(
+                out.lookup(r.start - 1),
+                Some(&((r.start - 3)..r.start, None))
+            );
+        }
+    }
+}
@@ -0,0 +1,236 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+/// An iterator over the lines touched by a byte range, producing the
+/// zero-based line number, the byte range of the line, and the byte
+/// range of the intersection of the range with the line.
+///
+/// Line ranges exclude the line terminator, i.e. `'\n'` or `"\r\n"`,
+/// and so do the intersections. The intersection of the first and last
+/// lines can be partial, while the intersection of all lines in between
+/// covers the whole line.
+///
+/// A range ending after a `'\n'`, i.e. covering the terminator of a line,
+/// does not touch the following line. An empty range touches exactly one
+/// line, i.e. the line of the caret.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRangesExt::span_lines()`] for more information.
+///
+/// [`CharRangesExt::span_lines()`]: crate::CharRangesExt::span_lines
+#[derive(Clone)]
+pub struct SpanLines<'a> {
+    text: &'a str,
+    range: Range<usize>,
+    /// The start of the last [`char`] within the range, or the end of
+    /// the range if it is empty, i.e. the line containing it is the last.
+    last: usize,
+    /// Zero-based line number of the next line.
+    line: usize,
+    /// Start byte position of the next line.
+    line_start: usize,
+    done: bool,
+}
+
+impl<'a> SpanLines<'a> {
+    /// Creates an iterator over the lines touched by `range` in `text`,
+    /// where `range` must already be on [`char`] boundaries.
+    pub(crate) fn new(text: &'a str, range: Range<usize>) -> Self {
+        let line_start = text[..range.start].rfind('\n').map_or(0, |i| i + 1);
+        let line = text[..line_start].bytes().filter(|&b| b == b'\n').count();
+
+        let last = match text[..range.end].chars().next_back() {
+            Some(c) if !range.is_empty() => range.end - c.len_utf8(),
+            _ => range.end,
+        };
+
+        Self {
+            text,
+            range,
+            last,
+            line,
+            line_start,
+            done: false,
+        }
+    }
+}
+
+impl Iterator for SpanLines<'_> {
+    type Item = (usize, Range<usize>, Range<usize>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let (line, start) = (self.line, self.line_start);
+        let (end, newline) = match self.text[start..].find('\n') {
+            Some(i) => {
+                let newline = start + i;
+                if self.text[start..newline].ends_with('\r') {
+                    (newline - 1, Some(newline))
+                } else {
+                    (newline, Some(newline))
+                }
+            }
+            None => (self.text.len(), None),
+        };
+
+        let intersection_start = self.range.start.clamp(start, end);
+        let intersection_end = self.range.end.clamp(intersection_start, end);
+
+        match newline {
+            Some(newline) if self.last > newline => {
+                self.line += 1;
+                self.line_start = newline + 1;
+            }
+            _ => self.done = true,
+        }
+
+        Some((line, start..end, intersection_start..intersection_end))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            (0, Some(0))
+        } else {
+            let len = self.last.saturating_sub(self.line_start);
+            (1, Some(len + 1))
+        }
+    }
+}
+
+impl FusedIterator for SpanLines<'_> {}
+
+impl fmt::Debug for SpanLines<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SpanLines(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use crate::CharRangesExt;
+
+    const TEXT: &str = "foo\r\n🗻∈🌏\nbar\n\nbaz";
+
+    #[test]
+    fn test_span_lines() {
+        let lines = TEXT.span_lines(1..19).unwrap().collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [(0, 0..3, 1..3), (1, 5..16, 5..16), (2, 17..20, 17..19)]
+        );
+
+        // Through to the end
+        let lines = TEXT.span_lines(9..TEXT.len()).unwrap().collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                (1, 5..16, 9..16),
+                (2, 17..20, 17..20),
+                (3, 21..21, 21..21),
+                (4, 22..25, 22..25),
+            ]
+        );
+
+        // Snapped outwards to `char` boundaries
+        assert!(TEXT.span_lines(6..10).unwrap().eq([(1, 5..16, 5..12)]));
+
+        // Invalid ranges
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = 8..2;
+        assert!(TEXT.span_lines(reversed).is_none());
+        assert!(TEXT.span_lines(100..200).is_none());
+    }
+
+    #[test]
+    fn test_span_lines_full_line() {
+        // Exactly one full line
+        assert!(TEXT.span_lines(5..16).unwrap().eq([(1, 5..16, 5..16)]));
+        // Including its terminator
+        assert!(TEXT.span_lines(5..17).unwrap().eq([(1, 5..16, 5..16)]));
+
+        // Ending right before a '\n'
+        assert!(TEXT.span_lines(18..20).unwrap().eq([(2, 17..20, 18..20)]));
+
+        // Ending right after a '\n'
+        assert!(TEXT.span_lines(18..21).unwrap().eq([(2, 17..20, 18..20)]));
+
+        // Up to the start of the next line, after the empty line
+        assert!(TEXT
+            .span_lines(18..22)
+            .unwrap()
+            .eq([(2, 17..20, 18..20), (3, 21..21, 21..21)]));
+    }
+
+    #[test]
+    fn test_span_lines_crlf() {
+        // Spanning a CRLF
+        assert!(TEXT
+            .span_lines(2..7)
+            .unwrap()
+            .eq([(0, 0..3, 2..3), (1, 5..16, 5..9)]));
+
+        // Within a CRLF
+        assert!(TEXT.span_lines(3..5).unwrap().eq([(0, 0..3, 3..3)]));
+        assert!(TEXT
+            .span_lines(3..6)
+            .unwrap()
+            .eq([(0, 0..3, 3..3), (1, 5..16, 5..9)]));
+
+        // A lone '\r' is not a terminator
+        assert!("a\rb".span_lines(0..3).unwrap().eq([(0, 0..3, 0..3)]));
+    }
+
+    #[test]
+    fn test_span_lines_empty() {
+        // Exactly one line for a caret
+        for (pos, expected) in [
+            (0, (0, 0..3, 0..0)),
+            (3, (0, 0..3, 3..3)),
+            (4, (0, 0..3, 3..3)),
+            (5, (1, 5..16, 5..5)),
+            (16, (1, 5..16, 16..16)),
+            (21, (3, 21..21, 21..21)),
+            (25, (4, 22..25, 25..25)),
+        ] {
+            assert!(TEXT.span_lines(pos..pos).unwrap().eq([expected]), "{pos}");
+        }
+
+        assert!("".span_lines(0..0).unwrap().eq([(0, 0..0, 0..0)]));
+        assert!("a\n".span_lines(2..2).unwrap().eq([(1, 2..2, 2..2)]));
+    }
+
+    #[test]
+    fn test_span_lines_line_range_at() {
+        // Every line agrees with `line_range_at()`
+        for start in 0..=TEXT.len() {
+            for end in start..=TEXT.len() {
+                let lines = TEXT.span_lines(start..end).unwrap().collect::<Vec<_>>();
+                assert!(!lines.is_empty());
+
+                for (i, (line, range, intersection)) in lines.iter().cloned().enumerate() {
+                    let (expected, expected_line, _) = TEXT.line_range_at(range.start).unwrap();
+                    assert_eq!((line, range.clone()), (expected_line, expected));
+                    assert!(range.start <= intersection.start);
+                    assert!(intersection.start <= intersection.end);
+                    assert!(intersection.end <= range.end);
+                    if (i > 0) && (i + 1 < lines.len()) {
+                        assert_eq!(intersection, range);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,335 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::CharRangesOffset;
+
+/// A `value` along with the start and end byte positions it spans.
+///
+/// With the `serde` feature enabled, this implements `Serialize` and
+/// `Deserialize`, where the `range` is `{ "start": n, "end": n }`,
+/// see [`serde_range`](crate::serde_range).
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::Spanned;
+///
+/// let a = Spanned::new(0..5, "Hello");
+/// let b = Spanned::new(6..11, "World");
+///
+/// assert_eq!(a.join(&b), 0..11);
+/// assert_eq!(a.map(str::len), Spanned::new(0..5, 5));
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Spanned<T> {
+    /// The start and end byte positions of `value`.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_range"))]
+    pub range: Range<usize>,
+    pub value: T,
+}
+
+impl<T> Spanned<T> {
+    #[inline]
+    pub const fn new(range: Range<usize>, value: T) -> Self {
+        Self { range, value }
+    }
+
+    /// Maps the `value` using `f`, while retaining the `range`.
+    #[inline]
+    pub fn map<U, F>(self, f: F) -> Spanned<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        Spanned::new(self.range, f(self.value))
+    }
+
+    /// Returns a `Spanned<&T>` with the same `range`.
+    #[inline]
+    pub fn as_ref(&self) -> Spanned<&T> {
+        Spanned::new(self.range.clone(), &self.value)
+    }
+
+    /// Returns the `value`, discarding the `range`.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Returns the range spanning both `self` and `other`, i.e. from the
+    /// smallest start position to the largest end position.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{CharRangesExt, Spanned};
+    ///
+    /// let text = "🗻∈🌏";
+    ///
+    /// let mut chars = text.char_ranges().spanned();
+    /// let first = chars.next().unwrap();
+    /// let last = chars.next_back().unwrap();
+    ///
+    /// assert_eq!(first.join(&last), 0..11);
+    /// assert_eq!(last.join(&first), 0..11);
+    /// ```
+    #[inline]
+    pub fn join<U>(&self, other: &Spanned<U>) -> Range<usize> {
+        let start = self.range.start.min(other.range.start);
+        let end = self.range.end.max(other.range.end);
+        start..end
+    }
+}
+
+impl<T> From<(Range<usize>, T)> for Spanned<T> {
+    #[inline]
+    fn from((range, value): (Range<usize>, T)) -> Self {
+        Self::new(range, value)
+    }
+}
+
+impl<T> From<Spanned<T>> for (Range<usize>, T) {
+    #[inline]
+    fn from(spanned: Spanned<T>) -> Self {
+        (spanned.range, spanned.value)
+    }
+}
+
+/// An iterator over [`char`]s as [`Spanned<char>`], i.e. along with
+/// their start and end byte positions.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRanges::spanned()`] for more information.
+///
+/// [`CharRanges::spanned()`]: crate::CharRanges::spanned
+#[derive(Clone)]
+pub struct SpannedCharRanges<'a> {
+    iter: CharRangesOffset<'a>,
+}
+
+impl<'a> SpannedCharRanges<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>) -> Self {
+        Self { iter }
+    }
+
+    /// Returns the remaining substring.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{CharRangesExt, Spanned};
+    ///
+    /// let text = "🗻∈🌏";
+    ///
+    /// let mut chars = text.char_ranges().spanned();
+    /// assert_eq!(chars.next(), Some(Spanned::new(0..4, '🗻')));
+    ///
+    /// assert_eq!(chars.as_str(), "∈🌏");
+    /// ```
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+}
+
+impl Iterator for SpannedCharRanges<'_> {
+    type Item = Spanned<char>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(Spanned::from)
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.iter.count()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth(n).map(Spanned::from)
+    }
+}
+
+impl DoubleEndedIterator for SpannedCharRanges<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(Spanned::from)
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth_back(n).map(Spanned::from)
+    }
+}
+
+impl FusedIterator for SpannedCharRanges<'_> {}
+
+impl fmt::Debug for SpannedCharRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SpannedCharRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use super::Spanned;
+    use crate::CharRangesExt;
+
+    const TEXT: &str = "Hello 🗻12∈45🌏 World ¢";
+
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    enum Token<'a> {
+        Ident(&'a str),
+        Int(u32),
+        Punct(char),
+    }
+
+    fn lex(text: &str) -> Vec<Spanned<Token<'_>>> {
+        let mut tokens = Vec::new();
+        let mut chars = text.char_ranges().spanned();
+        while let Some(first) = chars.next() {
+            let mut last = first.clone();
+            if first.value.is_whitespace() {
+                continue;
+            } else if first.value.is_alphabetic() {
+                while let Some(c) = chars.clone().next().filter(|c| c.value.is_alphanumeric()) {
+                    last = c;
+                    chars.next();
+                }
+                let range = first.join(&last);
+                tokens.push(Spanned::new(range.clone(), Token::Ident(&text[range])));
+            } else if first.value.is_ascii_digit() {
+                while let Some(c) = chars.clone().next().filter(|c| c.value.is_ascii_digit()) {
+                    last = c;
+                    chars.next();
+                }
+                let range = first.join(&last);
+                let value = text[range.clone()].parse().unwrap();
+                tokens.push(Spanned::new(range, Token::Int(value)));
+            } else {
+                tokens.push(first.map(Token::Punct));
+            }
+        }
+        tokens
+    }
+
+    #[test]
+    fn test_spanned_lexer() {
+        let tokens = lex("foo = 12 + bär3;");
+        assert_eq!(
+            tokens,
+            [
+                Spanned::new(0..3, Token::Ident("foo")),
+                Spanned::new(4..5, Token::Punct('=')),
+                Spanned::new(6..8, Token::Int(12)),
+                Spanned::new(9..10, Token::Punct('+')),
+                Spanned::new(11..16, Token::Ident("bär3")),
+                Spanned::new(16..17, Token::Punct(';')),
+            ]
+        );
+
+        let range = tokens[0].join(&tokens[2]);
+        assert_eq!(range, 0..8);
+        assert_eq!(&"foo = 12 + bär3;"[range], "foo = 12");
+    }
+
+    #[test]
+    fn test_spanned() {
+        let expected = TEXT.char_ranges().map(Spanned::from);
+        assert!(TEXT.char_ranges().spanned().eq(expected));
+
+        let expected = TEXT.char_ranges().rev().map(Spanned::from);
+        assert!(TEXT.char_ranges().spanned().rev().eq(expected));
+
+        assert_eq!(
+            TEXT.char_ranges().spanned().count(),
+            TEXT.char_ranges().count()
+        );
+        assert_eq!(
+            TEXT.char_ranges().spanned().last(),
+            Some(Spanned::new(28..30, '¢'))
+        );
+        assert_eq!("".char_ranges().spanned().last(), None);
+    }
+
+    #[test]
+    fn test_spanned_nth() {
+        let count = TEXT.char_ranges().count();
+        for n in 0..=count {
+            let mut chars = TEXT.char_ranges().spanned();
+            let mut expected = TEXT.char_ranges();
+            loop {
+                let item = chars.nth(n);
+                assert_eq!(item, expected.nth(n).map(Spanned::from));
+                assert_eq!(chars.as_str(), expected.as_str());
+                if item.is_none() {
+                    break;
+                }
+            }
+
+            let mut chars = TEXT.char_ranges().spanned();
+            let mut expected = TEXT.char_ranges();
+            loop {
+                let item = chars.nth_back(n);
+                assert_eq!(item, expected.nth_back(n).map(Spanned::from));
+                assert_eq!(chars.as_str(), expected.as_str());
+                if item.is_none() {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_spanned_methods() {
+        let spanned = Spanned::new(6..10, '🗻');
+        assert_eq!(spanned.as_ref(), Spanned::new(6..10, &'🗻'));
+        assert_eq!(spanned.clone().map(char::len_utf8).into_inner(), 4);
+        assert_eq!(<(_, _)>::from(spanned.clone()), (6..10, '🗻'));
+
+        // Disjoint and overlapping spans
+        assert_eq!(spanned.join(&Spanned::new(0..1, ())), 0..10);
+        assert_eq!(spanned.join(&Spanned::new(7..12, ())), 6..12);
+        assert_eq!(spanned.join(&spanned), 6..10);
+    }
+
+    #[test]
+    fn test_spanned_offset() {
+        let text = "Hello 👋 World 🌏";
+
+        let start = 11;
+        let mut chars = text[start..].char_ranges_offset(start).spanned();
+        assert_eq!(chars.offset(), start);
+        assert_eq!(chars.next(), Some(Spanned::new(11..12, 'W')));
+        assert_eq!(chars.next_back(), Some(Spanned::new(17..21, '🌏')));
+        assert_eq!(chars.as_str(), "orld ");
+    }
+}
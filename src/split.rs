@@ -0,0 +1,376 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+use core::str::RSplit;
+
+use crate::pattern::CharPattern;
+use crate::substr_range;
+
+/// Returns the byte range of `piece` within `text`, where `piece` is
+/// a subslice of `text`, along with `piece`.
+#[inline]
+fn piece_range<'a>(text: &'a str, piece: &'a str) -> (Range<usize>, &'a str) {
+    let r = substr_range(text, piece).expect("piece is a subslice of the text");
+    (r, piece)
+}
+
+/// An iterator over the substrings of a string, separated by a [`char`],
+/// along with their start and end byte positions, in reverse order.
+///
+/// This produces the same substrings as [`str::rsplit()`].
+///
+/// See [`CharRangesExt::rsplit_char_ranges()`] for more information.
+///
+/// [`CharRangesExt::rsplit_char_ranges()`]: crate::CharRangesExt::rsplit_char_ranges
+#[derive(Clone)]
+pub struct RSplitCharRanges<'a> {
+    text: &'a str,
+    iter: RSplit<'a, char>,
+}
+
+impl<'a> RSplitCharRanges<'a> {
+    #[inline]
+    pub(crate) fn new(text: &'a str, delim: char) -> Self {
+        Self {
+            text,
+            iter: text.rsplit(delim),
+        }
+    }
+}
+
+impl<'a> Iterator for RSplitCharRanges<'a> {
+    type Item = (Range<usize>, &'a str);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let piece = self.iter.next()?;
+        Some(piece_range(self.text, piece))
+    }
+}
+
+impl DoubleEndedIterator for RSplitCharRanges<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let piece = self.iter.next_back()?;
+        Some(piece_range(self.text, piece))
+    }
+}
+
+impl FusedIterator for RSplitCharRanges<'_> {}
+
+impl fmt::Debug for RSplitCharRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RSplitCharRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+/// An iterator over the substrings of a string, separated by a `&str`,
+/// along with their start and end byte positions, in reverse order.
+///
+/// This produces the same substrings as [`str::rsplit()`].
+///
+/// See [`CharRangesExt::rsplit_str_ranges()`] for more information.
+///
+/// [`CharRangesExt::rsplit_str_ranges()`]: crate::CharRangesExt::rsplit_str_ranges
+#[derive(Clone)]
+pub struct RSplitStrRanges<'a, 'b> {
+    text: &'a str,
+    iter: RSplit<'a, &'b str>,
+}
+
+impl<'a, 'b> RSplitStrRanges<'a, 'b> {
+    #[inline]
+    pub(crate) fn new(text: &'a str, delim: &'b str) -> Self {
+        Self {
+            text,
+            iter: text.rsplit(delim),
+        }
+    }
+}
+
+impl<'a> Iterator for RSplitStrRanges<'a, '_> {
+    type Item = (Range<usize>, &'a str);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let piece = self.iter.next()?;
+        Some(piece_range(self.text, piece))
+    }
+}
+
+impl FusedIterator for RSplitStrRanges<'_, '_> {}
+
+impl fmt::Debug for RSplitStrRanges<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RSplitStrRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+/// An iterator over the substrings of a string, separated by matches
+/// of a [`CharPattern`], where each substring includes the match ending
+/// it, along with their start and end byte positions.
+///
+/// This produces the same substrings as [`str::split_inclusive()`], i.e.
+/// every substring ends with a match, except possibly the last, and the
+/// substrings tile the string exactly. A match at the very end doesn't
+/// produce a final empty substring.
+///
+/// See [`CharRangesExt::split_inclusive_ranges()`] for more information.
+///
+/// [`CharRangesExt::split_inclusive_ranges()`]: crate::CharRangesExt::split_inclusive_ranges
+#[derive(Clone)]
+pub struct SplitInclusiveRanges<'a, P> {
+    text: &'a str,
+    pat: P,
+    /// Start byte position of the next substring.
+    start: usize,
+    /// Byte position to search for the next match from, or `None` if
+    /// no matches remain.
+    search: Option<usize>,
+}
+
+impl<'a, P> SplitInclusiveRanges<'a, P>
+where
+    P: CharPattern,
+{
+    #[inline]
+    pub(crate) fn new(text: &'a str, pat: P) -> Self {
+        Self {
+            text,
+            pat,
+            start: 0,
+            search: Some(0),
+        }
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        &self.text[self.start..]
+    }
+}
+
+impl<'a, P> Iterator for SplitInclusiveRanges<'a, P>
+where
+    P: CharPattern,
+{
+    type Item = (Range<usize>, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.start;
+
+        let found = self.search.and_then(|search| {
+            let r = self.pat.find_in(&self.text[search..])?;
+            Some((search + r.start)..(search + r.end))
+        });
+
+        let end = match found {
+            Some(r) => {
+                // An empty match must not be found again at the same position
+                self.search = if r.is_empty() {
+                    let next = self.text[r.end..].chars().next();
+                    next.map(|c| r.end + c.len_utf8())
+                } else {
+                    Some(r.end)
+                };
+                r.end
+            }
+            None => {
+                self.search = None;
+                if start == self.text.len() {
+                    return None;
+                }
+                self.text.len()
+            }
+        };
+
+        self.start = end;
+        Some((start..end, &self.text[start..end]))
+    }
+}
+
+impl<P> FusedIterator for SplitInclusiveRanges<'_, P> where P: CharPattern {}
+
+impl<P> fmt::Debug for SplitInclusiveRanges<'_, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SplitInclusiveRanges")
+            .field("remaining", &&self.text[self.start..])
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use core::ops::Range;
+    use std::vec::Vec;
+
+    use crate::{substr_range, CharRangesExt};
+
+    /// Returns the forward split of `text` with ranges, reversed.
+    fn split_reversed<'a>(text: &'a str, delim: &str) -> Vec<(Range<usize>, &'a str)> {
+        let mut pieces = text
+            .split(delim)
+            .map(|piece| (substr_range(text, piece).unwrap(), piece))
+            .collect::<Vec<_>>();
+        pieces.reverse();
+        pieces
+    }
+
+    #[test]
+    fn test_rsplit_char_ranges() {
+        let text = "a🌏b🌏🌏c";
+        let pieces = text.rsplit_char_ranges('🌏').collect::<Vec<_>>();
+        assert_eq!(
+            pieces,
+            [(14..15, "c"), (10..10, ""), (5..6, "b"), (0..1, "a")]
+        );
+
+        for text in ["", "🌏", "a🌏", "🌏a", "🌏🌏", "a🌏b🌏🌏c", "abc"] {
+            let pieces = text.rsplit_char_ranges('🌏').collect::<Vec<_>>();
+            assert_eq!(pieces, split_reversed(text, "🌏"), "{text:?}");
+
+            let rev = text.rsplit_char_ranges('🌏').rev().collect::<Vec<_>>();
+            assert!(rev.into_iter().eq(pieces.into_iter().rev()), "{text:?}");
+
+            // Same as `str::rsplit()`
+            let pieces = text.rsplit_char_ranges('🌏').map(|(_r, piece)| piece);
+            assert!(pieces.eq(text.rsplit('🌏')), "{text:?}");
+        }
+    }
+
+    #[test]
+    fn test_rsplit_str_ranges() {
+        let text = "key=a, b=🗻, c=∈";
+        let pieces = text.rsplit_str_ranges(", ").collect::<Vec<_>>();
+        assert_eq!(pieces, [(15..20, "c=∈"), (7..13, "b=🗻"), (0..5, "key=a")]);
+
+        for text in ["", "::", "a::", "::a", "a::::b", "a:::b", "🗻::∈::🌏"] {
+            for delim in ["::", ":", "", "🗻"] {
+                let pieces = text.rsplit_str_ranges(delim).map(|(_r, piece)| piece);
+                assert!(pieces.eq(text.rsplit(delim)), "{text:?} {delim:?}");
+
+                // Matches can overlap in "a:::b"
+                if (text, delim) != ("a:::b", "::") {
+                    let pieces = text.rsplit_str_ranges(delim).collect::<Vec<_>>();
+                    assert_eq!(pieces, split_reversed(text, delim), "{text:?} {delim:?}");
+                }
+            }
+        }
+
+        // Overlapping matches are found from the right, like `str::rsplit()`
+        let pieces = "a:::b".rsplit_str_ranges("::").collect::<Vec<_>>();
+        assert_eq!(pieces, [(4..5, "b"), (0..2, "a:")]);
+    }
+
+    #[test]
+    fn test_rsplit_once_range() {
+        let text = "archive.tar.gz";
+        assert_eq!(
+            text.rsplit_once_range('.'),
+            Some(((0..11, "archive.tar"), (12..14, "gz")))
+        );
+        assert_eq!(
+            text.rsplit_once_range(".tar"),
+            Some(((0..7, "archive"), (11..14, ".gz")))
+        );
+
+        // Absent
+        assert_eq!(text.rsplit_once_range('/'), None);
+        assert_eq!("".rsplit_once_range('.'), None);
+
+        // At position 0
+        assert_eq!(
+            ".bashrc".rsplit_once_range('.'),
+            Some(((0..0, ""), (1..7, "bashrc")))
+        );
+
+        // At the very end
+        assert_eq!(
+            "🗻∈🌏".rsplit_once_range('🌏'),
+            Some(((0..7, "🗻∈"), (11..11, "")))
+        );
+
+        // Same as `str::rsplit_once()`
+        for text in ["", "a=b", "a=b=c", "=", "==", "🗻=∈=🌏", "abc"] {
+            let once = text
+                .rsplit_once_range('=')
+                .map(|((_, before), (_, after))| (before, after));
+            assert_eq!(once, text.rsplit_once('='), "{text:?}");
+
+            if let Some(((before, _), (after, _))) = text.rsplit_once_range('=') {
+                assert_eq!(&text[before.end..after.start], "=");
+                assert_eq!((before.start, after.end), (0, text.len()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_split_inclusive_ranges() {
+        let text = "let a = 1;\nlet 🗻 = 2;\n";
+        let pieces = text.split_inclusive_ranges('\n').collect::<Vec<_>>();
+        assert_eq!(pieces, [(0..11, "let a = 1;\n"), (11..25, "let 🗻 = 2;\n")]);
+
+        // Multi-byte delimiters
+        let text = "a🌏b🌏🌏c";
+        let pieces = text.split_inclusive_ranges('🌏').collect::<Vec<_>>();
+        assert_eq!(
+            pieces,
+            [(0..5, "a🌏"), (5..10, "b🌏"), (10..14, "🌏"), (14..15, "c")]
+        );
+    }
+
+    #[test]
+    fn test_split_inclusive_ranges_std() {
+        fn check<P>(text: &str, pat: P, std_pieces: Vec<&str>)
+        where
+            P: crate::CharPattern,
+        {
+            let pieces = text.split_inclusive_ranges(pat).collect::<Vec<_>>();
+
+            // Same as `str::split_inclusive()`
+            let strs = pieces.iter().map(|(_r, piece)| *piece).collect::<Vec<_>>();
+            assert_eq!(strs, std_pieces, "{text:?}");
+
+            // The pieces tile the input exactly
+            let mut end = 0;
+            for (r, piece) in &pieces {
+                assert_eq!(r.start, end, "{text:?}");
+                assert_eq!(&text[r.clone()], *piece, "{text:?}");
+                end = r.end;
+            }
+            assert_eq!(end, text.len(), "{text:?}");
+            assert_eq!(strs.concat(), text);
+        }
+
+        for text in ["", ";", ";;", "a;", ";a", "a;b", "a;;b;", "🗻;∈;🌏", "abc"] {
+            check(text, ';', text.split_inclusive(';').collect());
+            check(text, "🌏", text.split_inclusive("🌏").collect());
+            check(text, ";;", text.split_inclusive(";;").collect());
+            check(text, "", text.split_inclusive("").collect());
+            check(text, [';', '∈'], text.split_inclusive([';', '∈']).collect());
+            check(
+                text,
+                &['a', 'b'][..],
+                text.split_inclusive(&['a', 'b'][..]).collect(),
+            );
+            check(
+                text,
+                |c: char| !c.is_ascii(),
+                text.split_inclusive(|c: char| !c.is_ascii()).collect(),
+            );
+        }
+
+        for text in ["a🌏b🌏🌏c", "🌏🌏", "🌏", "a🌏"] {
+            check(text, '🌏', text.split_inclusive('🌏').collect());
+            check(text, "🌏🌏", text.split_inclusive("🌏🌏").collect());
+        }
+    }
+}
@@ -0,0 +1,276 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::CharRangesOffset;
+
+/// An iterator over every `n`th [`char`] and its start and end byte
+/// positions, starting with the first [`char`].
+///
+/// Unlike [`Iterator::step_by()`], this iterator is double-ended, and
+/// retains [`as_str()`](Self::as_str). Skipped [`char`]s are skipped
+/// without decoding them.
+///
+/// Iterating from the back produces the same items as iterating from the
+/// front, in reverse order. In other words, the first item returned by
+/// [`next_back()`] is the last item of the forward sequence, i.e. it is
+/// not necessarily the last [`char`]. This requires counting the
+/// remaining [`char`]s on the first call to [`next_back()`].
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRanges::char_step_by()`] for more information.
+///
+/// [`next_back()`]: DoubleEndedIterator::next_back
+/// [`CharRanges::char_step_by()`]: crate::CharRanges::char_step_by
+#[derive(Clone)]
+pub struct CharStepBy<'a> {
+    iter: CharRangesOffset<'a>,
+    /// The number of [`char`]s skipped between items, i.e. `n - 1`.
+    skip: usize,
+    /// `true` if the next item from the front is the next [`char`],
+    /// i.e. if [`next()`] has not been called yet.
+    ///
+    /// [`next()`]: Iterator::next
+    first_take: bool,
+    /// `true` if the remaining [`char`]s end with an item, i.e. if
+    /// [`next_back()`] has been called.
+    ///
+    /// [`next_back()`]: DoubleEndedIterator::next_back
+    back_aligned: bool,
+}
+
+impl<'a> CharStepBy<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>, n: usize) -> Self {
+        assert!(n != 0, "step must be non-zero");
+        Self {
+            iter,
+            skip: n - 1,
+            first_take: true,
+            back_aligned: false,
+        }
+    }
+
+    /// Returns the remaining substring.
+    ///
+    /// The substring starts at the next item from the front, unless
+    /// [`next()`] has been called, in which case it starts after the
+    /// previous item. Likewise it ends at the previous item from the
+    /// back, if [`next_back()`] has been called.
+    ///
+    /// [`next()`]: Iterator::next
+    /// [`next_back()`]: DoubleEndedIterator::next_back
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all ranges.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+
+    /// Returns the number of remaining items, given the number of
+    /// remaining [`char`]s.
+    #[inline]
+    fn len_from(&self, chars: usize) -> usize {
+        let step = self.skip + 1;
+        if self.first_take {
+            if chars == 0 {
+                0
+            } else {
+                1 + (chars - 1) / step
+            }
+        } else {
+            chars / step
+        }
+    }
+}
+
+impl Iterator for CharStepBy<'_> {
+    type Item = (Range<usize>, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.first_take {
+            self.first_take = false;
+            self.iter.next()
+        } else {
+            self.iter.nth(self.skip)
+        }
+    }
+
+    fn nth(&mut self, mut n: usize) -> Option<Self::Item> {
+        if self.first_take {
+            self.first_take = false;
+            let first = self.iter.next();
+            if n == 0 {
+                return first;
+            }
+            n -= 1;
+        }
+
+        // Skipping more than `usize::MAX` chars exhausts the iterator
+        // regardless, so saturating is fine
+        let n = n.saturating_mul(self.skip + 1).saturating_add(self.skip);
+        self.iter.nth(n)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (
+            self.len_from(lower),
+            upper.map(|upper| self.len_from(upper)),
+        )
+    }
+}
+
+impl DoubleEndedIterator for CharStepBy<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.back_aligned {
+            return self.iter.nth_back(self.skip);
+        }
+        self.back_aligned = true;
+
+        let chars = self.iter.count();
+        let len = self.len_from(chars);
+        if len == 0 {
+            return None;
+        }
+
+        // Skip the trailing chars following the last item of the
+        // forward sequence
+        let last = if self.first_take {
+            (len - 1) * (self.skip + 1)
+        } else {
+            len * (self.skip + 1) - 1
+        };
+        self.iter.nth_back(chars - 1 - last)
+    }
+}
+
+impl FusedIterator for CharStepBy<'_> {}
+
+impl fmt::Debug for CharStepBy<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CharStepBy(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::String;
+    use std::vec::Vec;
+
+    use crate::CharRangesExt;
+
+    const CHARS: [char; 8] = ['a', '\n', '\u{80}', 'é', '∈', '⊂', '🌏', '🗻'];
+
+    #[test]
+    fn test_char_step_by() {
+        let text = "a🗻b∈c🌏de";
+
+        let chars = text.char_ranges().char_step_by(3).collect::<Vec<_>>();
+        assert_eq!(chars, [(0..1, 'a'), (6..9, '∈'), (14..15, 'd')]);
+
+        // The last item of the forward sequence, not the last char
+        let mut chars = text.char_ranges().char_step_by(3);
+        assert_eq!(chars.next_back(), Some((14..15, 'd')));
+        assert_eq!(chars.as_str(), "a🗻b∈c🌏");
+        assert_eq!(chars.next_back(), Some((6..9, '∈')));
+        assert_eq!(chars.next(), Some((0..1, 'a')));
+        assert_eq!(chars.next(), None);
+        assert_eq!(chars.next_back(), None);
+
+        let mut chars = text.char_ranges().char_step_by(3);
+        assert_eq!(chars.next(), Some((0..1, 'a')));
+        assert_eq!(chars.as_str(), "🗻b∈c🌏de");
+        assert_eq!(chars.next_back(), Some((14..15, 'd')));
+        assert_eq!(chars.next_back(), Some((6..9, '∈')));
+        assert_eq!(chars.next_back(), None);
+
+        let chars = text.char_ranges().char_step_by(1);
+        assert!(chars.eq(text.char_ranges()));
+        let chars = text.char_ranges().char_step_by(1).rev();
+        assert!(chars.eq(text.char_ranges().rev()));
+
+        assert_eq!("".char_ranges().char_step_by(2).next_back(), None);
+    }
+
+    #[test]
+    fn test_char_step_by_offset() {
+        let text = "foo∈bar";
+
+        let chars = text.char_ranges().offset(10).char_step_by(2);
+        assert_eq!(chars.offset(), 10);
+        assert!(chars.eq([(10..11, 'f'), (12..13, 'o'), (16..17, 'b'), (18..19, 'r')]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_char_step_by_zero() {
+        _ = "foo".char_ranges().char_step_by(0);
+    }
+
+    #[test]
+    fn test_char_step_by_random() {
+        let mut next = crate::test_util::random();
+
+        for _ in 0..300 {
+            let len = next() % 24;
+            let text = (0..len)
+                .map(|_| CHARS[next() % CHARS.len()])
+                .collect::<String>();
+            let n = 1 + next() % 5;
+
+            let expected = text.char_ranges().step_by(n).collect::<Vec<_>>();
+            let chars = text.char_ranges().char_step_by(n);
+            assert!(chars.clone().eq(expected.iter().cloned()));
+            assert!(chars.clone().rev().eq(expected.iter().cloned().rev()));
+
+            let (lower, upper) = chars.size_hint();
+            assert!(lower <= expected.len());
+            assert!(upper.unwrap() >= expected.len());
+
+            for i in 0..(expected.len() + 2) {
+                assert_eq!(chars.clone().nth(i), expected.get(i).cloned());
+            }
+
+            // `nth()` after `next()`
+            let mut skipped = chars.clone();
+            skipped.next();
+            assert_eq!(skipped.nth(1), expected.get(2).cloned());
+            assert_eq!(skipped.nth(usize::MAX), None);
+            assert_eq!(skipped.next(), None);
+
+            // Alternating from both ends
+            let mut chars = chars;
+            let mut front = Vec::new();
+            let mut back = Vec::new();
+            loop {
+                if next().is_multiple_of(2) {
+                    match chars.next() {
+                        Some(item) => front.push(item),
+                        None => break,
+                    }
+                } else {
+                    match chars.next_back() {
+                        Some(item) => back.push(item),
+                        None => break,
+                    }
+                }
+            }
+            back.reverse();
+            front.extend(back);
+            assert_eq!(front, expected, "{text:?} {n}");
+        }
+    }
+}
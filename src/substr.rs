@@ -0,0 +1,108 @@
+use core::ops::Range;
+
+/// Returns the byte range of `child` within `parent`, where `child` is
+/// a subslice of `parent`, e.g. produced by [`str::split()`] or [`str::trim()`].
+///
+/// The range is computed from the addresses of `parent` and `child`, i.e.
+/// not by searching for `child`. So the range is correct, even if the
+/// content of `child` appears multiple times in `parent`.
+///
+/// Returns `None` if `child` does not lie within the bounds of `parent`,
+/// e.g. if `child` originates from a different allocation.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::substr_range;
+///
+/// let text = "foo bar foo";
+///
+/// let mut words = text.split(' ');
+/// let first = words.next().unwrap();
+/// let last = words.last().unwrap();
+///
+/// assert_eq!(first, last);
+/// assert_eq!(substr_range(text, first), Some(0..3));
+/// assert_eq!(substr_range(text, last), Some(8..11));
+///
+/// let other = String::from("foo");
+/// assert_eq!(substr_range(text, &other), None);
+/// ```
+pub fn substr_range(parent: &str, child: &str) -> Option<Range<usize>> {
+    let parent_start = parent.as_ptr() as usize;
+    let parent_end = parent_start + parent.len();
+
+    let child_start = child.as_ptr() as usize;
+    let child_end = child_start.checked_add(child.len())?;
+
+    if (child_start < parent_start) || (child_end > parent_end) {
+        return None;
+    }
+
+    let start = child_start - parent_start;
+    Some(start..(start + child.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::String;
+
+    use super::substr_range;
+    use crate::CharRangesExt;
+
+    #[test]
+    fn test_substr_range_repeated() {
+        let text = "🗻∈🌏 🗻∈🌏";
+
+        let (a, b) = text.split_once(' ').unwrap();
+        assert_eq!(a, b);
+        assert_eq!(substr_range(text, a), Some(0..11));
+        assert_eq!(substr_range(text, b), Some(12..23));
+
+        assert_eq!(substr_range(text, text), Some(0..text.len()));
+        assert_eq!(substr_range(a, b), None);
+        assert_eq!(substr_range(b, a), None);
+    }
+
+    #[test]
+    fn test_substr_range_empty() {
+        let text = "Hello World";
+
+        assert_eq!(substr_range(text, &text[..0]), Some(0..0));
+        assert_eq!(substr_range(text, &text[text.len()..]), Some(11..11));
+        assert_eq!(substr_range(text, &text[5..5]), Some(5..5));
+
+        let empty = &text[3..3];
+        assert_eq!(substr_range(empty, empty), Some(0..0));
+        assert_eq!(substr_range(empty, &text[3..4]), None);
+    }
+
+    #[test]
+    fn test_substr_range_different_allocation() {
+        let text = String::from("Hello World");
+        let other = String::from("Hello World");
+
+        assert_eq!(substr_range(&text, &other), None);
+        assert_eq!(substr_range(&text, &other[6..]), None);
+        assert_eq!(substr_range(&text, "World"), None);
+    }
+
+    #[test]
+    fn test_char_ranges_within() {
+        let text = "foo 🗻∈🌏 foo";
+
+        let last = text.rsplit(' ').next().unwrap();
+        let mut chars = last.char_ranges_within(text).unwrap();
+        assert_eq!(chars.offset(), 16);
+        assert_eq!(chars.next(), Some((16..17, 'f')));
+
+        let word = text.split(' ').nth(1).unwrap();
+        let chars = word.char_ranges_within(text).unwrap();
+        assert!(chars.eq(text.char_ranges().skip(4).take(3)));
+
+        let other = String::from("foo");
+        assert!(other.char_ranges_within(text).is_none());
+    }
+}
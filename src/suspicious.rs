@@ -0,0 +1,352 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::CharRangesOffset;
+
+/// The category of a suspicious [`char`], produced by [`SuspiciousCharRanges`].
+///
+/// The categories are listed from most to least specific, e.g. `'\u{202E}'`
+/// is both a [`BidiControl`](Self::BidiControl) and [`NonAscii`](Self::NonAscii),
+/// but is categorized as the former.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CharIssue {
+    /// A bidirectional formatting control, e.g. `'\u{202E}'` (RIGHT-TO-LEFT
+    /// OVERRIDE), which can make text render in a different order than
+    /// it is read, i.e. U+061C, U+200E, U+200F, U+202A to U+202E, and
+    /// U+2066 to U+2069.
+    BidiControl,
+    /// An invisible zero-width [`char`], e.g. `'\u{200B}'` (ZERO WIDTH SPACE),
+    /// i.e. U+00AD, U+034F, U+180E, U+200B to U+200D, U+2060 to U+2064,
+    /// and U+FEFF.
+    ZeroWidth,
+    /// A control character, i.e. [`char::is_control()`], except for `'\n'`
+    /// and `'\r'`. Whether `'\t'` is reported is configurable with
+    /// [`SuspiciousCharRanges::allow_tab()`].
+    Control,
+    /// Any other non-ASCII [`char`].
+    NonAscii,
+}
+
+impl CharIssue {
+    const ALL: [Self; 4] = [
+        Self::BidiControl,
+        Self::ZeroWidth,
+        Self::Control,
+        Self::NonAscii,
+    ];
+
+    /// Returns the most specific category of `c`, or `None`
+    /// if `c` is not suspicious.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharIssue;
+    ///
+    /// assert_eq!(CharIssue::of('\u{202E}'), Some(CharIssue::BidiControl));
+    /// assert_eq!(CharIssue::of('\u{200B}'), Some(CharIssue::ZeroWidth));
+    /// assert_eq!(CharIssue::of('\0'), Some(CharIssue::Control));
+    /// assert_eq!(CharIssue::of('é'), Some(CharIssue::NonAscii));
+    /// assert_eq!(CharIssue::of('e'), None);
+    /// assert_eq!(CharIssue::of('\n'), None);
+    /// ```
+    #[inline]
+    pub fn of(c: char) -> Option<Self> {
+        Self::ALL.into_iter().find(|issue| issue.matches(c))
+    }
+
+    /// Returns `true` if `c` is in this category, regardless
+    /// of whether a more specific category also matches.
+    #[inline]
+    fn matches(self, c: char) -> bool {
+        match self {
+            Self::BidiControl => matches!(
+                c,
+                '\u{061C}'
+                    | '\u{200E}'
+                    | '\u{200F}'
+                    | '\u{202A}'..='\u{202E}'
+                    | '\u{2066}'..='\u{2069}'
+            ),
+            Self::ZeroWidth => matches!(
+                c,
+                '\u{00AD}'
+                    | '\u{034F}'
+                    | '\u{180E}'
+                    | '\u{200B}'..='\u{200D}'
+                    | '\u{2060}'..='\u{2064}'
+                    | '\u{FEFF}'
+            ),
+            Self::Control => c.is_control() && !matches!(c, '\n' | '\r'),
+            Self::NonAscii => !c.is_ascii(),
+        }
+    }
+
+    #[inline]
+    const fn bit(self) -> u8 {
+        1 << (self as u8)
+    }
+}
+
+impl fmt::Display for CharIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BidiControl => write!(f, "bidirectional control character"),
+            Self::ZeroWidth => write!(f, "zero-width character"),
+            Self::Control => write!(f, "control character"),
+            Self::NonAscii => write!(f, "non-ASCII character"),
+        }
+    }
+}
+
+/// An iterator over suspicious [`char`]s, their start and end byte
+/// positions, and their [`CharIssue`].
+///
+/// By default, all categories are reported, except for `'\t'`. Each
+/// [`char`] is reported as its most specific reported category, e.g.
+/// if [`CharIssue::BidiControl`] is not reported, then `'\u{202E}'`
+/// is reported as [`CharIssue::NonAscii`], if that is reported.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRangesExt::suspicious_char_ranges()`] for more information.
+///
+/// [`CharRangesExt::suspicious_char_ranges()`]: crate::CharRangesExt::suspicious_char_ranges
+#[derive(Clone)]
+pub struct SuspiciousCharRanges<'a> {
+    iter: CharRangesOffset<'a>,
+    /// Bit set of the reported [`CharIssue`]s.
+    report: u8,
+    allow_tab: bool,
+}
+
+impl<'a> SuspiciousCharRanges<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>) -> Self {
+        Self {
+            iter,
+            report: CharIssue::ALL
+                .iter()
+                .fold(0, |bits, issue| bits | issue.bit()),
+            allow_tab: true,
+        }
+    }
+
+    /// Sets whether [`char`]s in the category `issue` are reported.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{CharIssue, CharRangesExt};
+    ///
+    /// let text = "café\u{202E}";
+    ///
+    /// let mut chars = text
+    ///     .suspicious_char_ranges()
+    ///     .report(CharIssue::NonAscii, false);
+    /// assert_eq!(chars.next(), Some((5..8, '\u{202E}', CharIssue::BidiControl)));
+    /// assert_eq!(chars.next(), None);
+    /// ```
+    #[inline]
+    pub fn report(mut self, issue: CharIssue, report: bool) -> Self {
+        if report {
+            self.report |= issue.bit();
+        } else {
+            self.report &= !issue.bit();
+        }
+        self
+    }
+
+    /// Sets whether `'\t'` is allowed, i.e. not reported as
+    /// [`CharIssue::Control`]. By default, `'\t'` is allowed.
+    #[inline]
+    pub fn allow_tab(mut self, allow: bool) -> Self {
+        self.allow_tab = allow;
+        self
+    }
+
+    /// Returns `true` if [`char`]s in the category `issue` are reported.
+    #[inline]
+    pub fn reports(&self, issue: CharIssue) -> bool {
+        (self.report & issue.bit()) != 0
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+
+    /// Returns the most specific reported category of `c`.
+    #[inline]
+    fn issue(&self, c: char) -> Option<CharIssue> {
+        if (c == '\t') && self.allow_tab {
+            return None;
+        }
+        CharIssue::ALL
+            .into_iter()
+            .find(|&issue| self.reports(issue) && issue.matches(c))
+    }
+}
+
+impl Iterator for SuspiciousCharRanges<'_> {
+    type Item = (Range<usize>, char, CharIssue);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (r, c) = self.iter.next()?;
+            if let Some(issue) = self.issue(c) {
+                return Some((r, c, issue));
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, upper) = self.iter.size_hint();
+        (0, upper)
+    }
+}
+
+impl DoubleEndedIterator for SuspiciousCharRanges<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            let (r, c) = self.iter.next_back()?;
+            if let Some(issue) = self.issue(c) {
+                return Some((r, c, issue));
+            }
+        }
+    }
+}
+
+impl FusedIterator for SuspiciousCharRanges<'_> {}
+
+impl fmt::Debug for SuspiciousCharRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SuspiciousCharRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    use super::CharIssue;
+    use crate::CharRangesExt;
+
+    #[test]
+    fn test_suspicious_char_ranges() {
+        let text = "let x\u{200B} = \"\u{202E}é\t\"; // \u{7}";
+
+        let chars = text.suspicious_char_ranges().collect::<Vec<_>>();
+        assert_eq!(
+            chars,
+            [
+                (5..8, '\u{200B}', CharIssue::ZeroWidth),
+                (12..15, '\u{202E}', CharIssue::BidiControl),
+                (15..17, 'é', CharIssue::NonAscii),
+                (24..25, '\u{7}', CharIssue::Control),
+            ],
+        );
+
+        let mut rev = text.suspicious_char_ranges().rev().collect::<Vec<_>>();
+        rev.reverse();
+        assert_eq!(rev, chars);
+
+        let chars = text.char_ranges_offset(10).suspicious();
+        assert_eq!(chars.offset(), 10);
+        assert_eq!(chars.map(|(r, _c, _issue)| r.start).next(), Some(15));
+    }
+
+    #[test]
+    fn test_suspicious_char_ranges_report() {
+        let text = "café\t\u{202E}\u{200D}\n\r";
+
+        // Allow plain non-ASCII, but not bidi controls
+        let chars = text
+            .suspicious_char_ranges()
+            .report(CharIssue::NonAscii, false)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            chars,
+            [
+                (6..9, '\u{202E}', CharIssue::BidiControl),
+                (9..12, '\u{200D}', CharIssue::ZeroWidth),
+            ],
+        );
+
+        // Tab
+        let chars = text
+            .suspicious_char_ranges()
+            .allow_tab(false)
+            .report(CharIssue::NonAscii, false)
+            .collect::<Vec<_>>();
+        assert_eq!(chars[0], (5..6, '\t', CharIssue::Control));
+        assert_eq!(chars.len(), 3);
+
+        // Falls back to the next reported category
+        let chars = text
+            .suspicious_char_ranges()
+            .report(CharIssue::BidiControl, false)
+            .report(CharIssue::ZeroWidth, false)
+            .map(|(_r, c, issue)| (c, issue))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            chars,
+            [
+                ('é', CharIssue::NonAscii),
+                ('\u{202E}', CharIssue::NonAscii),
+                ('\u{200D}', CharIssue::NonAscii),
+            ],
+        );
+
+        // Nothing reported
+        let mut chars = text.suspicious_char_ranges();
+        for issue in CharIssue::ALL {
+            assert!(chars.reports(issue));
+            chars = chars.report(issue, false);
+            assert!(!chars.reports(issue));
+        }
+        assert_eq!(chars.allow_tab(false).next(), None);
+    }
+
+    #[test]
+    fn test_char_issue() {
+        for c in ['\u{202E}', '\u{2066}', '\u{200E}', '\u{061C}'] {
+            assert_eq!(CharIssue::of(c), Some(CharIssue::BidiControl));
+        }
+        for c in ['\u{200B}', '\u{200D}', '\u{2060}', '\u{FEFF}', '\u{AD}'] {
+            assert_eq!(CharIssue::of(c), Some(CharIssue::ZeroWidth));
+        }
+        for c in ['\0', '\t', '\u{1B}', '\u{7F}', '\u{85}'] {
+            assert_eq!(CharIssue::of(c), Some(CharIssue::Control));
+        }
+        for c in ['é', 'Ø', '∈', '🌏', '\u{301}', '\u{A0}'] {
+            assert_eq!(CharIssue::of(c), Some(CharIssue::NonAscii));
+        }
+        for c in ['a', ' ', '~', '\n', '\r'] {
+            assert_eq!(CharIssue::of(c), None);
+        }
+
+        assert_eq!(
+            CharIssue::BidiControl.to_string(),
+            "bidirectional control character"
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::{CharRangesExt, CharRangesOffset};
+
+/// An iterator over [`char`]s and their start and end byte positions,
+/// bounded by a budget of bytes.
+///
+/// Iteration stops before the first [`char`] whose end would exceed the
+/// budget, i.e. a [`char`] is never split. The budget is counted from the
+/// front of the iterator, when the iterator is created.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRanges::take_bytes()`] for more information.
+///
+/// [`CharRanges::take_bytes()`]: crate::CharRanges::take_bytes
+#[derive(Clone)]
+pub struct TakeBytes<'a> {
+    iter: CharRangesOffset<'a>,
+    /// Byte position where iteration stops, without the offset applied.
+    stop: usize,
+}
+
+impl<'a> TakeBytes<'a> {
+    #[inline]
+    pub(crate) fn new(mut iter: CharRangesOffset<'a>, max: usize) -> Self {
+        let len = CharRangesExt::floor_char_boundary(iter.as_str(), max);
+        let stop = iter.iter.start + len;
+        iter.iter.end = stop;
+        Self { iter, stop }
+    }
+
+    /// Returns the byte position where iteration stops, i.e. the end
+    /// of the last [`char`] within the budget, with the offset applied.
+    ///
+    /// This is the same regardless of how much has been consumed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharRangesExt;
+    ///
+    /// let text = "ab🌏c";
+    ///
+    /// let mut chars = text.char_ranges().take_bytes(4);
+    /// assert_eq!(chars.stopped_at(), 2);
+    /// assert!(chars.eq([(0..1, 'a'), (1..2, 'b')]));
+    ///
+    /// let chars = text.char_ranges().take_bytes(6);
+    /// assert_eq!(chars.stopped_at(), 6);
+    /// assert_eq!(&text[6..], "c");
+    /// ```
+    #[inline]
+    pub fn stopped_at(&self) -> usize {
+        self.iter.offset() + self.stop
+    }
+
+    /// Returns the remaining substring within the budget.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+}
+
+impl Iterator for TakeBytes<'_> {
+    type Item = (Range<usize>, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for TakeBytes<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back()
+    }
+}
+
+impl FusedIterator for TakeBytes<'_> {}
+
+impl fmt::Debug for TakeBytes<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TakeBytes(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use crate::CharRangesExt;
+
+    #[test]
+    fn test_take_bytes() {
+        let text = "a¢∈🌏b🌏∈¢a";
+
+        for max in 0..(text.len() + 2) {
+            let chars = text.char_ranges().take_bytes(max);
+            let stop = chars.stopped_at();
+            let items = chars.collect::<Vec<_>>();
+
+            // Maximal, but within the budget
+            assert!(stop <= max);
+            assert!(text.is_char_boundary(stop));
+            if stop < text.len() {
+                let next_len = text[stop..].chars().next().unwrap().len_utf8();
+                assert!(stop + next_len > max);
+            }
+
+            assert_eq!(items, text.char_ranges_in(..stop).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_take_bytes_offset() {
+        let text = "Hello 🗻12∈45🌏 World";
+
+        // The budget is counted from the current position
+        let mut chars = text.char_ranges();
+        chars.advance_by(6).unwrap();
+        let mut chars = chars.take_bytes(7);
+        assert_eq!(chars.as_str(), "🗻12");
+        assert_eq!(chars.stopped_at(), 12);
+        assert_eq!(chars.next_back(), Some((11..12, '2')));
+        assert_eq!(chars.next(), Some((6..10, '🗻')));
+        assert_eq!(chars.stopped_at(), 12);
+
+        let mut chars = text[6..].char_ranges_offset(6).take_bytes(3);
+        assert_eq!(chars.offset(), 6);
+        assert_eq!(chars.stopped_at(), 6);
+        assert_eq!(chars.next(), None);
+
+        let chars = text[10..].char_ranges_offset(10).take_bytes(4);
+        assert_eq!(chars.stopped_at(), 12);
+        assert!(chars.eq([(10..11, '1'), (11..12, '2')]));
+    }
+}
@@ -0,0 +1,11 @@
+/// Returns a deterministic pseudo-random number generator (xorshift),
+/// for tests producing random inputs.
+pub(crate) fn random() -> impl FnMut() -> usize {
+    let mut state = 0x2545_F491_u32;
+    move || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state as usize
+    }
+}
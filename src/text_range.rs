@@ -0,0 +1,283 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::num::TryFromIntError;
+use core::ops::Range;
+
+use text_size::{TextRange, TextSize};
+
+use crate::{CharRanges32, Span, U32OverflowError};
+
+/// Converts the start and end byte positions `r` into a [`TextRange`].
+///
+/// This is a fallible alternative to <code>TextRange::[from_range]\(r)</code>,
+/// which panics instead.
+///
+/// # Errors
+///
+/// Returns an error if the start or end of `r` overflows `u32`.
+///
+/// # Panics
+///
+/// Panics if the start of `r` is after the end.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::to_text_range;
+/// use text_size::TextRange;
+///
+/// assert_eq!(to_text_range(2..5), Ok(TextRange::new(2.into(), 5.into())));
+/// assert!(to_text_range(2..(u32::MAX as usize + 1)).is_err());
+/// ```
+///
+/// [from_range]: Span::from_range
+#[inline]
+#[track_caller]
+pub fn to_text_range(r: Range<usize>) -> Result<TextRange, TryFromIntError> {
+    let start = u32::try_from(r.start)?;
+    let end = u32::try_from(r.end)?;
+    Ok(TextRange::new(start.into(), end.into()))
+}
+
+/// # Panics
+///
+/// [`from_range()`](Span::from_range) panics if a position overflows `u32`,
+/// or if the start is after the end. Use [`to_text_range()`] to handle
+/// overflow instead.
+impl Span for TextRange {
+    #[inline]
+    #[track_caller]
+    fn from_range(r: Range<usize>) -> Self {
+        let r = <Range<u32>>::from_range(r);
+        TextRange::new(r.start.into(), r.end.into())
+    }
+
+    #[inline]
+    fn into_range(self) -> Range<usize> {
+        self.into()
+    }
+}
+
+/// An iterator over [`char`]s and their start and end byte positions,
+/// as a [`TextRange`] instead of <code>[Range]&lt;[usize]&gt;</code>.
+///
+/// Since [`TextRange`] uses `u32` positions, it is checked that
+/// `offset + text.len()` fits in a `u32` when the iterator is created.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRangesExt::char_text_ranges()`] for more information.
+///
+/// [`CharRangesExt::char_text_ranges()`]: crate::CharRangesExt::char_text_ranges
+#[derive(Clone)]
+pub struct CharTextRanges<'a> {
+    iter: CharRanges32<'a>,
+}
+
+impl<'a> CharTextRanges<'a> {
+    /// Creates an iterator over [`char`]s and their start and end byte positions,
+    /// with an offset applied to all positions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `offset + text.len()` overflows `u32`.
+    /// Use [`try_new()`](Self::try_new) to handle this instead.
+    #[inline]
+    pub fn new(offset: TextSize, text: &'a str) -> Self {
+        match Self::try_new(offset, text) {
+            Ok(chars) => chars,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Creates an iterator over [`char`]s and their start and end byte positions,
+    /// with an offset applied to all positions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `offset + text.len()` overflows `u32`,
+    /// i.e. if any position could overflow.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::CharTextRanges;
+    ///
+    /// assert!(CharTextRanges::try_new((u32::MAX - 3).into(), "abc").is_ok());
+    /// assert!(CharTextRanges::try_new((u32::MAX - 2).into(), "abc").is_err());
+    /// ```
+    #[inline]
+    pub fn try_new(offset: TextSize, text: &'a str) -> Result<Self, U32OverflowError> {
+        let iter = CharRanges32::try_new(offset.into(), text)?;
+        Ok(Self { iter })
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> TextSize {
+        self.iter.offset().into()
+    }
+}
+
+#[inline]
+fn text_range((r, c): (Range<u32>, char)) -> (TextRange, char) {
+    (TextRange::new(r.start.into(), r.end.into()), c)
+}
+
+impl Iterator for CharTextRanges<'_> {
+    type Item = (TextRange, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(text_range)
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.iter.count()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth(n).map(text_range)
+    }
+}
+
+impl DoubleEndedIterator for CharTextRanges<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(text_range)
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth_back(n).map(text_range)
+    }
+}
+
+impl FusedIterator for CharTextRanges<'_> {}
+
+impl fmt::Debug for CharTextRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CharTextRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::ops::Range;
+
+    use text_size::{TextRange, TextSize};
+
+    use super::{to_text_range, CharTextRanges};
+    use crate::{CharRangesExt, Span};
+
+    const TEXT: &str = "Hello 🗻12∈45🌏 World ¢";
+
+    fn widen((r, c): (TextRange, char)) -> (Range<usize>, char) {
+        (r.into(), c)
+    }
+
+    #[test]
+    fn test_char_text_ranges() {
+        let chars = TEXT.char_text_ranges().unwrap();
+        assert!(chars.map(widen).eq(TEXT.char_ranges()));
+
+        let chars = TEXT.char_text_ranges().unwrap();
+        assert!(chars.rev().map(widen).eq(TEXT.char_ranges().rev()));
+
+        let mut chars = TEXT.char_text_ranges().unwrap();
+        assert_eq!(chars.nth(6).map(widen), Some((6..10, '🗻')));
+        assert_eq!(chars.nth_back(0).map(widen), Some((28..30, '¢')));
+        assert_eq!(chars.as_str(), &TEXT[10..28]);
+
+        let mut chars = CharTextRanges::new(TextSize::from(10), "∈🌏");
+        assert_eq!(chars.offset(), TextSize::from(10));
+        assert_eq!(
+            chars.next(),
+            Some((TextRange::new(10.into(), 13.into()), '∈')),
+        );
+    }
+
+    #[test]
+    fn test_char_text_ranges_in() {
+        let range = TextRange::new(6.into(), 21.into());
+
+        let chars = TEXT.char_text_ranges_in(range);
+        assert!(chars.map(widen).eq(TEXT.char_ranges_in(6..21)));
+
+        let mut chars = TEXT.char_text_ranges_in(range);
+        assert_eq!(
+            chars.next(),
+            Some((TextRange::new(6.into(), 10.into()), '🗻'))
+        );
+        assert_eq!(chars.as_str(), "12∈45🌏");
+    }
+
+    #[test]
+    #[should_panic(expected = "byte index 7 is not a char boundary")]
+    fn test_char_text_ranges_in_not_char_boundary() {
+        _ = TEXT.char_text_ranges_in(TextRange::new(7.into(), 21.into()));
+    }
+
+    #[test]
+    fn test_char_text_ranges_overflow() {
+        let err = CharTextRanges::try_new(TextSize::from(u32::MAX - 2), "abc").unwrap_err();
+        assert_eq!(err.offset(), u32::MAX - 2);
+        assert_eq!(err.text_len(), 3);
+
+        assert!(CharTextRanges::try_new(TextSize::from(u32::MAX - 3), "abc").is_ok());
+        assert!(CharTextRanges::try_new(TextSize::from(u32::MAX), "").is_ok());
+    }
+
+    #[test]
+    fn test_to_text_range() {
+        assert_eq!(
+            to_text_range(6..10),
+            Ok(TextRange::new(6.into(), 10.into())),
+        );
+        assert_eq!(
+            to_text_range(0..(u32::MAX as usize)),
+            Ok(TextRange::up_to(u32::MAX.into())),
+        );
+
+        // Not silently truncated
+        assert!(to_text_range(0..(u32::MAX as usize + 1)).is_err());
+        assert!(to_text_range((u32::MAX as usize + 1)..(u32::MAX as usize + 2)).is_err());
+
+        // Same as `Span`
+        assert_eq!(TextRange::from_range(6..10), to_text_range(6..10).unwrap(),);
+        assert_eq!(TextRange::new(6.into(), 10.into()).into_range(), 6..10);
+
+        let mut chars = TEXT.char_ranges().map_span::<TextRange>();
+        assert_eq!(
+            chars.nth(6),
+            Some((TextRange::new(6.into(), 10.into()), '🗻')),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "position 4294967296 overflows u32")]
+    fn test_text_range_from_range_overflow() {
+        _ = TextRange::from_range(0..(u32::MAX as usize + 1));
+    }
+}
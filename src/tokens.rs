@@ -0,0 +1,281 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+
+use crate::{CharRangesOffset, Scanner, Spanned};
+
+/// An iterator over the tokens of a text, which repeatedly calls a
+/// closure with a [`Scanner`], producing each token along with the start
+/// and end byte positions of everything the closure consumed for it.
+///
+/// The produced ranges tile the consumed text, i.e. each token starts
+/// where the previous token ended.
+///
+/// If the closure returns `None`, then everything it consumed in that
+/// call is restored, and the iterator ends, where
+/// [`remainder()`](Self::remainder) returns the rest of the text.
+///
+/// See [`tokens()`] for more information.
+#[derive(Clone)]
+pub struct Tokens<'a, T, F> {
+    scanner: Scanner<'a>,
+    f: F,
+    done: bool,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T, F> Tokens<'a, T, F>
+where
+    F: FnMut(&mut Scanner<'a>) -> Option<T>,
+{
+    #[inline]
+    fn new(text: &'a str, f: F) -> Self {
+        Self {
+            scanner: CharRangesOffset::from_text(text, 0).scanner(),
+            f,
+            done: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the remaining substring, i.e. the part of the text which
+    /// has not been consumed by any token produced so far.
+    #[inline]
+    pub fn remainder(&self) -> &'a str {
+        self.scanner.as_str()
+    }
+}
+
+impl<'a, T, F> Iterator for Tokens<'a, T, F>
+where
+    F: FnMut(&mut Scanner<'a>) -> Option<T>,
+{
+    type Item = Spanned<T>;
+
+    /// # Panics
+    ///
+    /// Panics if the closure returns a token without consuming anything,
+    /// as it would otherwise produce the same token indefinitely.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.scanner.is_eof() {
+            return None;
+        }
+
+        let prev = self.scanner.clone();
+        let Some(token) = (self.f)(&mut self.scanner) else {
+            self.scanner = prev;
+            self.done = true;
+            return None;
+        };
+
+        let (r, _s) = self.scanner.finish_token();
+        assert!(
+            !r.is_empty(),
+            "token at position {} consumed nothing",
+            r.start,
+        );
+
+        Some(Spanned::new(r, token))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            (0, Some(0))
+        } else {
+            (0, Some(self.scanner.as_str().len()))
+        }
+    }
+}
+
+impl<'a, T, F> FusedIterator for Tokens<'a, T, F> where F: FnMut(&mut Scanner<'a>) -> Option<T> {}
+
+impl<T, F> fmt::Debug for Tokens<'_, T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tokens")
+            .field("scanner", &self.scanner)
+            .field("done", &self.done)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Returns an iterator over the tokens of `text`, where `f` is called
+/// with a [`Scanner`] to consume and return the next token, and the
+/// iterator produces each token as a [`Spanned`], covering exactly what
+/// `f` consumed.
+///
+/// `f` is called until all of `text` is consumed, or until `f` returns
+/// `None`, which restores anything it consumed in that call, leaving it
+/// in [`Tokens::remainder()`]. To keep going after unexpected input,
+/// `f` should consume it and return an error token instead.
+///
+/// # Panics
+///
+/// The iterator panics if `f` returns a token without consuming anything,
+/// which would otherwise loop indefinitely.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::{tokens, Spanned};
+///
+/// #[derive(PartialEq, Debug)]
+/// enum Token {
+///     Ident,
+///     Space,
+///     Error,
+/// }
+///
+/// let text = "foo bar?";
+///
+/// let mut tokens = tokens(text, |scanner| {
+///     if !scanner.accept_while(char::is_alphabetic).is_empty() {
+///         Some(Token::Ident)
+///     } else if !scanner.accept_while(char::is_whitespace).is_empty() {
+///         Some(Token::Space)
+///     } else {
+///         scanner.bump()?;
+///         Some(Token::Error)
+///     }
+/// });
+///
+/// assert_eq!(tokens.next(), Some(Spanned::new(0..3, Token::Ident)));
+/// assert_eq!(tokens.next(), Some(Spanned::new(3..4, Token::Space)));
+/// assert_eq!(tokens.next(), Some(Spanned::new(4..7, Token::Ident)));
+/// assert_eq!(tokens.next(), Some(Spanned::new(7..8, Token::Error)));
+/// assert_eq!(tokens.next(), None);
+/// ```
+#[inline]
+pub fn tokens<'a, T, F>(text: &'a str, f: F) -> Tokens<'a, T, F>
+where
+    F: FnMut(&mut Scanner<'a>) -> Option<T>,
+{
+    Tokens::new(text, f)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use super::tokens;
+    use crate::{Scanner, Spanned};
+
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    enum Token {
+        Ident,
+        Int(u128),
+        Punct(char),
+        Space,
+        Error,
+    }
+
+    fn lex(scanner: &mut Scanner<'_>) -> Option<Token> {
+        let (_r, c) = scanner.peek()?;
+        if c.is_alphabetic() || (c == '_') {
+            scanner.accept_while(|c| c.is_alphanumeric() || (c == '_'));
+            Some(Token::Ident)
+        } else if c.is_whitespace() {
+            scanner.accept_while(char::is_whitespace);
+            Some(Token::Space)
+        } else if let Some(res) = scanner.scan_unsigned(10, true) {
+            Some(res.map_or(Token::Error, |(_r, n)| Token::Int(n)))
+        } else if "+-*/=;()".contains(c) {
+            scanner.bump();
+            Some(Token::Punct(c))
+        } else {
+            // Consume a run of unknown chars as a single error token
+            scanner.accept_while(|c| !c.is_alphanumeric() && !c.is_whitespace());
+            Some(Token::Error)
+        }
+    }
+
+    #[test]
+    fn test_tokens() {
+        let text = "let x_1 = 2 + foo;";
+
+        let toks = tokens(text, lex)
+            .filter(|tok| tok.value != Token::Space)
+            .map(|tok| (&text[tok.range], tok.value))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            toks,
+            [
+                ("let", Token::Ident),
+                ("x_1", Token::Ident),
+                ("=", Token::Punct('=')),
+                ("2", Token::Int(2)),
+                ("+", Token::Punct('+')),
+                ("foo", Token::Ident),
+                (";", Token::Punct(';')),
+            ]
+        );
+
+        assert_eq!(tokens("", lex).next(), None);
+    }
+
+    #[test]
+    fn test_tokens_error_recovery() {
+        let text = "a 🌏∈ 1 @@ b";
+
+        let toks = tokens(text, lex).collect::<Vec<_>>();
+        assert_eq!(
+            toks,
+            [
+                Spanned::new(0..1, Token::Ident),
+                Spanned::new(1..2, Token::Space),
+                Spanned::new(2..9, Token::Error),
+                Spanned::new(9..10, Token::Space),
+                Spanned::new(10..11, Token::Int(1)),
+                Spanned::new(11..12, Token::Space),
+                Spanned::new(12..14, Token::Error),
+                Spanned::new(14..15, Token::Space),
+                Spanned::new(15..16, Token::Ident),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokens_tiling() {
+        let text = "fn main() {\n    let x = 1_000 * (y - 🗻);\n}\n";
+
+        let mut end = 0;
+        for tok in tokens(text, lex) {
+            assert_eq!(tok.range.start, end);
+            assert!(!tok.range.is_empty());
+            end = tok.range.end;
+        }
+        assert_eq!(end, text.len());
+    }
+
+    #[test]
+    fn test_tokens_remainder() {
+        let text = "12 34 end";
+
+        // Stops at the first non-integer, restoring what was consumed
+        let mut toks = tokens(text, |scanner| {
+            scanner.accept_while(char::is_whitespace);
+            let (_r, n) = scanner.scan_unsigned(10, false)?.ok()?;
+            Some(n)
+        });
+        assert_eq!(toks.next(), Some(Spanned::new(0..2, 12)));
+        assert_eq!(toks.remainder(), " 34 end");
+        assert_eq!(toks.next(), Some(Spanned::new(2..5, 34)));
+        assert_eq!(toks.next(), None);
+        assert_eq!(toks.remainder(), " end");
+        assert_eq!(toks.next(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "token at position 3 consumed nothing")]
+    fn test_tokens_consumed_nothing() {
+        let text = "abc+";
+
+        let toks = tokens(text, |scanner| {
+            scanner.accept_while(char::is_alphabetic);
+            Some(())
+        });
+        toks.for_each(drop);
+    }
+}
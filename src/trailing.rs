@@ -0,0 +1,180 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::CharRangesOffset;
+
+/// An iterator over the lines of a string, which end with whitespace,
+/// producing the zero-based line number and the start and end byte
+/// positions of the trailing whitespace.
+///
+/// Lines are split like [`str::lines()`], i.e. at `'\n'` and `"\r\n"`.
+/// The `'\r'` of a `"\r\n"` terminator is part of the terminator, and
+/// is never included in a range, while any other `'\r'` is whitespace.
+/// Lines which only contain whitespace produce their whole content.
+///
+/// Whitespace is [`char::is_whitespace()`], which notably includes the
+/// no-break space `'\u{A0}'`, i.e. it is reported as trailing whitespace.
+///
+/// Line numbers are relative to the start of the remaining substring,
+/// when the iterator is created.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRanges::trailing_whitespace_ranges()`] for more information.
+///
+/// [`CharRanges::trailing_whitespace_ranges()`]: crate::CharRanges::trailing_whitespace_ranges
+#[derive(Clone)]
+pub struct TrailingWhitespaceRanges<'a> {
+    iter: CharRangesOffset<'a>,
+    /// The line number of the next line.
+    line: usize,
+}
+
+impl<'a> TrailingWhitespaceRanges<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>) -> Self {
+        Self { iter, line: 0 }
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+}
+
+impl Iterator for TrailingWhitespaceRanges<'_> {
+    type Item = (usize, Range<usize>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let rest = self.iter.as_str();
+            if rest.is_empty() {
+                return None;
+            }
+
+            let start = self.iter.iter.start;
+            let (line, next) = match rest.find('\n') {
+                Some(i) => {
+                    let line = &rest[..i];
+                    (line.strip_suffix('\r').unwrap_or(line), start + i + 1)
+                }
+                None => (rest, self.iter.iter.end),
+            };
+            self.iter.iter.start = next;
+
+            let n = self.line;
+            self.line += 1;
+
+            let content = line.trim_end();
+            if content.len() < line.len() {
+                let r = (start + content.len())..(start + line.len());
+                return Some((n, self.iter.apply_offset(r)));
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.iter.as_str().len()))
+    }
+}
+
+impl FusedIterator for TrailingWhitespaceRanges<'_> {}
+
+impl fmt::Debug for TrailingWhitespaceRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TrailingWhitespaceRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use crate::CharRangesExt;
+
+    #[test]
+    fn test_trailing_whitespace_ranges() {
+        let text = "fn main() {\t\n    let x = 1; \t\n}";
+
+        let ranges = text
+            .char_ranges()
+            .trailing_whitespace_ranges()
+            .collect::<Vec<_>>();
+        assert_eq!(ranges, [(0, 11..12), (1, 27..29)]);
+        assert_eq!(&text[27..29], " \t");
+    }
+
+    #[test]
+    fn test_trailing_whitespace_ranges_crlf() {
+        let text = "a \r\nb\r\nc\t\r\n\r\n \r\n";
+
+        let ranges = text
+            .char_ranges()
+            .trailing_whitespace_ranges()
+            .collect::<Vec<_>>();
+        assert_eq!(ranges, [(0, 1..2), (2, 8..9), (4, 13..14)]);
+
+        // A lone '\r' is not a terminator
+        let text = "a\r\r\nb\r";
+        let ranges = text.char_ranges().trailing_whitespace_ranges();
+        assert!(ranges.eq([(0, 1..2), (1, 5..6)]));
+    }
+
+    #[test]
+    fn test_trailing_whitespace_ranges_no_break_space() {
+        // U+00A0 is whitespace
+        let text = "a\u{A0}\nb \u{A0} \n";
+
+        let ranges = text.char_ranges().trailing_whitespace_ranges();
+        assert!(ranges.eq([(0, 1..3), (1, 5..9)]));
+    }
+
+    #[test]
+    fn test_trailing_whitespace_ranges_blank_last_line() {
+        let text = "foo\n\n  \t";
+
+        let ranges = text
+            .char_ranges()
+            .trailing_whitespace_ranges()
+            .collect::<Vec<_>>();
+        assert_eq!(ranges, [(2, 5..8)]);
+
+        // Removing the ranges removes all trailing whitespace
+        let mut fixed = std::string::String::from(text);
+        for (_, r) in ranges.into_iter().rev() {
+            fixed.replace_range(r, "");
+        }
+        assert_eq!(fixed, "foo\n\n");
+    }
+
+    #[test]
+    fn test_trailing_whitespace_ranges_clean() {
+        let text = "fn main() {\r\n    foo();\n\n}\n";
+        assert_eq!(text.char_ranges().trailing_whitespace_ranges().next(), None);
+
+        assert_eq!("".char_ranges().trailing_whitespace_ranges().next(), None);
+    }
+
+    #[test]
+    fn test_trailing_whitespace_ranges_offset() {
+        let text = "x \ny \nz ";
+
+        let ranges = text[3..].char_ranges_offset(3).trailing_whitespace_ranges();
+        assert_eq!(ranges.offset(), 3);
+        assert!(ranges.eq([(0, 4..5), (1, 7..8)]));
+    }
+}
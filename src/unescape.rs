@@ -0,0 +1,398 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::{CharRanges, CharRangesOffset, UnescapeError};
+
+/// The kind of an [`UnescapeError`], before the range of the escape is known.
+#[derive(Clone, Copy)]
+enum ErrorKind {
+    Unterminated,
+    UnknownEscape,
+    InvalidHex,
+    OutOfRange,
+    LoneSurrogate,
+}
+
+impl ErrorKind {
+    #[inline]
+    fn with_range(self, r: Range<usize>) -> UnescapeError {
+        match self {
+            Self::Unterminated => UnescapeError::Unterminated(r),
+            Self::UnknownEscape => UnescapeError::UnknownEscape(r),
+            Self::InvalidHex => UnescapeError::InvalidHex(r),
+            Self::OutOfRange => UnescapeError::OutOfRange(r),
+            Self::LoneSurrogate => UnescapeError::LoneSurrogate(r),
+        }
+    }
+}
+
+/// The set of escapes accepted by [`UnescapeRanges`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum EscapeStyle {
+    /// Rust string literal escapes, i.e. `\n`, `\r`, `\t`, `\\`, `\0`,
+    /// `\'`, `\"`, `\xNN` up to `\x7F`, and `\u{N}` with 1 to 6 hex digits.
+    ///
+    /// Line continuations, i.e. `\` followed by a newline, are not supported.
+    #[default]
+    Rust,
+    /// JSON string escapes, i.e. `\n`, `\r`, `\t`, `\\`, `\"`, `\/`, `\b`,
+    /// `\f`, and `\uNNNN` with exactly 4 hex digits, where a surrogate
+    /// pair of `\uNNNN` escapes is decoded into a single [`char`].
+    Json,
+}
+
+/// An iterator over the decoded [`char`]s of the body of a string literal,
+/// and the start and end byte positions of their source.
+///
+/// Plain [`char`]s are produced with their own range, while decoded escapes
+/// are produced with the range of the whole escape, e.g. `\u{1F30D}` has
+/// a range of 9 bytes. Which escapes are accepted is determined by the
+/// [`EscapeStyle`], which defaults to [`EscapeStyle::Rust`].
+///
+/// Invalid escapes produce an [`UnescapeError`] with the range of the
+/// escape, after which iteration continues. The range of an invalid escape
+/// ends before the first [`char`] that made it invalid, which is then
+/// produced as is.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRangesExt::unescape_ranges()`] for more information.
+///
+/// [`CharRangesExt::unescape_ranges()`]: crate::CharRangesExt::unescape_ranges
+#[derive(Clone)]
+pub struct UnescapeRanges<'a> {
+    iter: CharRangesOffset<'a>,
+    style: EscapeStyle,
+}
+
+impl<'a> UnescapeRanges<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>) -> Self {
+        Self {
+            iter,
+            style: EscapeStyle::default(),
+        }
+    }
+
+    /// Sets the set of accepted escapes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::{CharRangesExt, EscapeStyle};
+    ///
+    /// let text = r"\uD83C\uDF0D\/";
+    ///
+    /// let mut chars = text.unescape_ranges().style(EscapeStyle::Json);
+    /// assert_eq!(chars.next(), Some(Ok((0..12, '🌍'))));
+    /// assert_eq!(chars.next(), Some(Ok((12..14, '/'))));
+    /// assert_eq!(chars.next(), None);
+    /// ```
+    #[inline]
+    pub fn style(mut self, style: EscapeStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Returns the set of accepted escapes.
+    #[inline]
+    pub fn escape_style(&self) -> EscapeStyle {
+        self.style
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+
+    /// Decodes the escape following a `\`.
+    fn unescape(&mut self) -> Result<char, ErrorKind> {
+        let chars = &mut self.iter.iter;
+        let (_r, c) = chars.next().ok_or(ErrorKind::Unterminated)?;
+        let c = match (self.style, c) {
+            (_, 'n') => '\n',
+            (_, 'r') => '\r',
+            (_, 't') => '\t',
+            (_, '\\') => '\\',
+            (_, '"') => '"',
+            (EscapeStyle::Rust, '0') => '\0',
+            (EscapeStyle::Rust, '\'') => '\'',
+            (EscapeStyle::Rust, 'x') => {
+                let value = hex_digits(chars, 2)?;
+                if value > 0x7F {
+                    return Err(ErrorKind::OutOfRange);
+                }
+                to_char(value)?
+            }
+            (EscapeStyle::Rust, 'u') => to_char(braced_hex_digits(chars)?)?,
+            (EscapeStyle::Json, '/') => '/',
+            (EscapeStyle::Json, 'b') => '\u{8}',
+            (EscapeStyle::Json, 'f') => '\u{C}',
+            (EscapeStyle::Json, 'u') => {
+                let value = hex_digits(chars, 4)?;
+                match value {
+                    0xD800..=0xDBFF => {
+                        let low = low_surrogate(chars).ok_or(ErrorKind::LoneSurrogate)?;
+                        let value = 0x10000 + ((value - 0xD800) << 10) + (low - 0xDC00);
+                        to_char(value)?
+                    }
+                    _ => to_char(value)?,
+                }
+            }
+            _ => return Err(ErrorKind::UnknownEscape),
+        };
+        Ok(c)
+    }
+}
+
+/// Consumes exactly `n` hex digits.
+fn hex_digits(chars: &mut CharRanges<'_>, n: usize) -> Result<u32, ErrorKind> {
+    let mut value = 0;
+    for _ in 0..n {
+        value = (value << 4) | hex_digit(chars)?;
+    }
+    Ok(value)
+}
+
+/// Consumes a single hex digit, or nothing if the next [`char`] is not one.
+fn hex_digit(chars: &mut CharRanges<'_>) -> Result<u32, ErrorKind> {
+    let (_r, c) = chars.peek().ok_or(ErrorKind::Unterminated)?;
+    let digit = c.to_digit(16).ok_or(ErrorKind::InvalidHex)?;
+    chars.next();
+    Ok(digit)
+}
+
+/// Consumes `{N}`, with 1 to 6 hex digits.
+fn braced_hex_digits(chars: &mut CharRanges<'_>) -> Result<u32, ErrorKind> {
+    match chars.peek() {
+        Some((_r, '{')) => {
+            chars.next();
+        }
+        Some(_) => return Err(ErrorKind::InvalidHex),
+        None => return Err(ErrorKind::Unterminated),
+    }
+
+    let mut value = 0;
+    let mut digits = 0;
+    loop {
+        if chars.next_if_eq('}').is_some() {
+            break;
+        }
+        if digits == 6 {
+            return Err(ErrorKind::InvalidHex);
+        }
+        value = (value << 4) | hex_digit(chars)?;
+        digits += 1;
+    }
+
+    if digits == 0 {
+        return Err(ErrorKind::InvalidHex);
+    }
+    Ok(value)
+}
+
+/// Consumes a `\uNNNN` escape of a low surrogate, or nothing if the
+/// next [`char`]s are not one.
+fn low_surrogate(chars: &mut CharRanges<'_>) -> Option<u32> {
+    let mut iter = *chars;
+    iter.next_if_eq('\\')?;
+    iter.next_if_eq('u')?;
+    let value = hex_digits(&mut iter, 4).ok()?;
+    if (0xDC00..=0xDFFF).contains(&value) {
+        *chars = iter;
+        Some(value)
+    } else {
+        None
+    }
+}
+
+#[inline]
+fn to_char(value: u32) -> Result<char, ErrorKind> {
+    match value {
+        0xD800..=0xDFFF => Err(ErrorKind::LoneSurrogate),
+        _ => char::from_u32(value).ok_or(ErrorKind::OutOfRange),
+    }
+}
+
+impl Iterator for UnescapeRanges<'_> {
+    type Item = Result<(Range<usize>, char), UnescapeError>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (r, c) = self.iter.iter.next()?;
+        if c != '\\' {
+            return Some(Ok((self.iter.apply_offset(r), c)));
+        }
+
+        let res = self.unescape();
+        let r = self.iter.apply_offset(r.start..self.iter.iter.start);
+        Some(match res {
+            Ok(c) => Ok((r, c)),
+            Err(kind) => Err(kind.with_range(r)),
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, upper) = self.iter.size_hint();
+        (usize::from(!self.as_str().is_empty()), upper)
+    }
+}
+
+impl FusedIterator for UnescapeRanges<'_> {}
+
+impl fmt::Debug for UnescapeRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "UnescapeRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use super::EscapeStyle;
+    use crate::{CharRangesExt, UnescapeError};
+
+    #[test]
+    fn test_unescape_ranges() {
+        let text = r#"a\n\t\r\\\"\0\'\x41\u{E9}\u{1F30D}∈"#;
+        let chars = text.unescape_ranges().collect::<Result<Vec<_>, _>>();
+        assert_eq!(
+            chars,
+            Ok(Vec::from([
+                (0..1, 'a'),
+                (1..3, '\n'),
+                (3..5, '\t'),
+                (5..7, '\r'),
+                (7..9, '\\'),
+                (9..11, '"'),
+                (11..13, '\0'),
+                (13..15, '\''),
+                (15..19, 'A'),
+                (19..25, 'é'),
+                (25..34, '🌍'),
+                (34..37, '∈'),
+            ])),
+        );
+
+        let mut chars = text[19..].char_ranges_offset(19).unescape();
+        assert_eq!(chars.offset(), 19);
+        assert_eq!(chars.escape_style(), EscapeStyle::Rust);
+        assert_eq!(chars.next(), Some(Ok((19..25, 'é'))));
+        assert_eq!(chars.as_str(), r"\u{1F30D}∈");
+    }
+
+    #[test]
+    fn test_unescape_ranges_plain() {
+        let text = "Hello 🗻12∈45🌏 World ¢";
+        let chars = text.unescape_ranges().map(Result::unwrap);
+        assert!(chars.eq(text.char_ranges()));
+    }
+
+    #[test]
+    fn test_unescape_ranges_json() {
+        let text = r#"\n\t\r\\\"\/\b\f\u0041\u00e9\uD83C\uDF0D"#;
+        let chars = text
+            .unescape_ranges()
+            .style(EscapeStyle::Json)
+            .collect::<Result<Vec<_>, _>>();
+        assert_eq!(
+            chars,
+            Ok(Vec::from([
+                (0..2, '\n'),
+                (2..4, '\t'),
+                (4..6, '\r'),
+                (6..8, '\\'),
+                (8..10, '"'),
+                (10..12, '/'),
+                (12..14, '\u{8}'),
+                (14..16, '\u{C}'),
+                (16..22, 'A'),
+                (22..28, 'é'),
+                (28..40, '🌍'),
+            ])),
+        );
+
+        // Rust-only escapes
+        let mut chars = r"\0\x41\u{41}".unescape_ranges().style(EscapeStyle::Json);
+        assert_eq!(chars.next(), Some(Err(UnescapeError::UnknownEscape(0..2))));
+        assert_eq!(chars.next(), Some(Err(UnescapeError::UnknownEscape(2..4))));
+        assert_eq!(chars.nth(2), Some(Err(UnescapeError::InvalidHex(6..8))));
+
+        // JSON-only escapes
+        let mut chars = r"\/\b".unescape_ranges();
+        assert_eq!(chars.next(), Some(Err(UnescapeError::UnknownEscape(0..2))));
+        assert_eq!(chars.next(), Some(Err(UnescapeError::UnknownEscape(2..4))));
+    }
+
+    #[test]
+    fn test_unescape_ranges_errors() {
+        use UnescapeError::*;
+
+        #[rustfmt::skip]
+        let cases: [(&str, EscapeStyle, Vec<_>); 13] = [
+            (r"\q", EscapeStyle::Rust, Vec::from([Err(UnknownEscape(0..2))])),
+            (r"\x4g", EscapeStyle::Rust, Vec::from([Err(InvalidHex(0..3)), Ok((3..4, 'g'))])),
+            (r"\xFF", EscapeStyle::Rust, Vec::from([Err(OutOfRange(0..4))])),
+            (r"\u{}", EscapeStyle::Rust, Vec::from([Err(InvalidHex(0..4))])),
+            (r"\u{1234567}", EscapeStyle::Rust, Vec::from([Err(InvalidHex(0..9)), Ok((9..10, '7')), Ok((10..11, '}'))])),
+            (r"\u41", EscapeStyle::Rust, Vec::from([Err(InvalidHex(0..2)), Ok((2..3, '4')), Ok((3..4, '1'))])),
+            (r"\u{110000}", EscapeStyle::Rust, Vec::from([Err(OutOfRange(0..10))])),
+            (r"\u{D800}", EscapeStyle::Rust, Vec::from([Err(LoneSurrogate(0..8))])),
+            (r"\uDF0D", EscapeStyle::Json, Vec::from([Err(LoneSurrogate(0..6))])),
+            (r"\uD83Ca", EscapeStyle::Json, Vec::from([Err(LoneSurrogate(0..6)), Ok((6..7, 'a'))])),
+            (r"\uD83C\u0041", EscapeStyle::Json, Vec::from([Err(LoneSurrogate(0..6)), Ok((6..12, 'A'))])),
+            (r"\u00G1", EscapeStyle::Json, Vec::from([Err(InvalidHex(0..4)), Ok((4..5, 'G')), Ok((5..6, '1'))])),
+            (r"\u{41}\q\n", EscapeStyle::Rust, Vec::from([Ok((0..6, 'A')), Err(UnknownEscape(6..8)), Ok((8..10, '\n'))])),
+        ];
+        for (text, style, expected) in cases {
+            let chars = text.unescape_ranges().style(style).collect::<Vec<_>>();
+            assert_eq!(chars, expected, "{text}");
+        }
+    }
+
+    #[test]
+    fn test_unescape_ranges_unterminated() {
+        use UnescapeError::Unterminated;
+
+        for text in [r"\", r"\x", r"\x4", r"\u", r"\u{", r"\u{1F3"] {
+            let mut chars = text.unescape_ranges();
+            assert_eq!(
+                chars.next(),
+                Some(Err(Unterminated(0..text.len()))),
+                "{text}"
+            );
+            assert_eq!(chars.next(), None);
+        }
+        for text in [r"\u", r"\u00", r"\u004"] {
+            let mut chars = text.unescape_ranges().style(EscapeStyle::Json);
+            assert_eq!(
+                chars.next(),
+                Some(Err(Unterminated(0..text.len()))),
+                "{text}"
+            );
+        }
+
+        let mut chars = r"ab\".unescape_ranges();
+        assert_eq!(chars.nth(2), Some(Err(Unterminated(2..3))));
+
+        let err = r"12\u{1F3".unescape_ranges().nth(2).unwrap().unwrap_err();
+        assert_eq!(err.range(), 2..8);
+        assert_eq!(std::format!("{err}"), "unterminated escape at bytes 2..8");
+    }
+}
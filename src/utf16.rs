@@ -0,0 +1,418 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::UnpairedSurrogate;
+
+/// Extension trait for iterating the [`char`]s of UTF-16 encoded text,
+/// where positions are measured in `u16` code units.
+pub trait Utf16CharRangesExt {
+    /// Returns an iterator over [`char`]s and their start and end
+    /// positions in `u16` code units.
+    ///
+    /// [`char`]s encoded as a surrogate pair span 2 code units. Unpaired
+    /// surrogates produce an [`UnpairedSurrogate`] error spanning 1 code
+    /// unit, after which decoding continues.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::Utf16CharRangesExt;
+    ///
+    /// let units = [0x61, 0xD83C, 0xDF0F, 0xDC00, 0x2208];
+    ///
+    /// let mut chars = units.utf16_char_ranges();
+    /// assert_eq!(chars.next(), Some(Ok((0..1, 'a'))));
+    /// assert_eq!(chars.next(), Some(Ok((1..3, '🌏'))));
+    /// assert_eq!(chars.next().unwrap().unwrap_err().range(), 3..4);
+    /// assert_eq!(chars.next(), Some(Ok((4..5, '∈'))));
+    /// assert_eq!(chars.next(), None);
+    /// ```
+    fn utf16_char_ranges(&self) -> Utf16CharRanges<'_>;
+
+    /// Returns an iterator over [`char`]s and their start and end
+    /// positions in `u16` code units, with an offset applied to all
+    /// positions.
+    ///
+    /// See [`utf16_char_ranges()`](Self::utf16_char_ranges) for more information.
+    #[inline]
+    fn utf16_char_ranges_offset(&self, offset: usize) -> Utf16CharRangesOffset<'_> {
+        self.utf16_char_ranges().offset(offset)
+    }
+}
+
+impl Utf16CharRangesExt for [u16] {
+    #[inline]
+    fn utf16_char_ranges(&self) -> Utf16CharRanges<'_> {
+        Utf16CharRanges {
+            units: self,
+            start: 0,
+            end: self.len(),
+        }
+    }
+}
+
+#[inline]
+const fn is_high_surrogate(unit: u16) -> bool {
+    matches!(unit, 0xD800..=0xDBFF)
+}
+
+#[inline]
+const fn is_low_surrogate(unit: u16) -> bool {
+    matches!(unit, 0xDC00..=0xDFFF)
+}
+
+#[inline]
+fn decode_surrogate_pair(high: u16, low: u16) -> char {
+    let c = 0x10000 + ((((high as u32) - 0xD800) << 10) | ((low as u32) - 0xDC00));
+    char::from_u32(c).expect("surrogate pair is a valid char")
+}
+
+/// Returns the length of `text` in UTF-16 code units, by only looking at
+/// the leading bytes of [`char`]s, where 4-byte [`char`]s are 2 code units.
+#[inline]
+pub(crate) fn utf16_len(text: &str) -> usize {
+    text.bytes()
+        .map(|b| match b {
+            0x80..=0xBF => 0,
+            0xF0..=0xFF => 2,
+            _ => 1,
+        })
+        .sum()
+}
+
+/// Returns the byte position in `text` of the UTF-16 code unit position
+/// `units`, or `None` if it is within a surrogate pair or out of bounds.
+#[inline]
+pub(crate) fn utf16_to_byte_offset(text: &str, units: usize) -> Option<usize> {
+    let mut pos = 0;
+    for (i, c) in text.char_indices() {
+        if pos >= units {
+            return (pos == units).then_some(i);
+        }
+        pos += c.len_utf16();
+    }
+    (pos == units).then_some(text.len())
+}
+
+/// An iterator over the [`char`]s of UTF-16 encoded text and their
+/// start and end positions in `u16` code units.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`Utf16CharRangesExt::utf16_char_ranges()`] for more information.
+#[derive(Clone)]
+pub struct Utf16CharRanges<'a> {
+    units: &'a [u16],
+    /// Start position of the remaining code units.
+    start: usize,
+    /// End position of the remaining code units.
+    end: usize,
+}
+
+impl<'a> Utf16CharRanges<'a> {
+    /// Returns the remaining code units.
+    #[inline]
+    pub fn as_slice(&self) -> &'a [u16] {
+        &self.units[self.start..self.end]
+    }
+
+    /// Returns an iterator over the remaining [`char`]s and their start and
+    /// end positions in `u16` code units, with an offset applied to all
+    /// positions.
+    ///
+    /// This is useful when the code units are a window into a larger buffer.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::Utf16CharRangesExt;
+    ///
+    /// let buffer = [0x61, 0x62, 0xD83C, 0xDF0F];
+    ///
+    /// let mut chars = buffer[2..].utf16_char_ranges().offset(2);
+    /// assert_eq!(chars.next(), Some(Ok((2..4, '🌏'))));
+    /// ```
+    #[inline]
+    pub fn offset(self, offset: usize) -> Utf16CharRangesOffset<'a> {
+        Utf16CharRangesOffset { iter: self, offset }
+    }
+}
+
+impl Iterator for Utf16CharRanges<'_> {
+    type Item = Result<(Range<usize>, char), UnpairedSurrogate>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            return None;
+        }
+
+        let start = self.start;
+        let unit = self.units[start];
+        self.start += 1;
+
+        if let Some(c) = char::from_u32(unit as u32) {
+            return Some(Ok((start..self.start, c)));
+        }
+
+        if is_high_surrogate(unit) && (self.start < self.end) {
+            let low = self.units[self.start];
+            if is_low_surrogate(low) {
+                self.start += 1;
+                let c = decode_surrogate_pair(unit, low);
+                return Some(Ok((start..self.start, c)));
+            }
+        }
+
+        Some(Err(UnpairedSurrogate::new(start, unit)))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len.div_ceil(2), Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Utf16CharRanges<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            return None;
+        }
+
+        let end = self.end;
+        self.end -= 1;
+        let unit = self.units[self.end];
+
+        if let Some(c) = char::from_u32(unit as u32) {
+            return Some(Ok((self.end..end, c)));
+        }
+
+        if is_low_surrogate(unit) && (self.start < self.end) {
+            let high = self.units[self.end - 1];
+            if is_high_surrogate(high) {
+                self.end -= 1;
+                let c = decode_surrogate_pair(high, unit);
+                return Some(Ok((self.end..end, c)));
+            }
+        }
+
+        Some(Err(UnpairedSurrogate::new(self.end, unit)))
+    }
+}
+
+impl FusedIterator for Utf16CharRanges<'_> {}
+
+impl fmt::Debug for Utf16CharRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Utf16CharRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+/// An iterator over the [`char`]s of UTF-16 encoded text and their
+/// start and end positions in `u16` code units, with an offset applied
+/// to all positions.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`Utf16CharRanges::offset()`] for more information.
+#[derive(Clone)]
+pub struct Utf16CharRangesOffset<'a> {
+    iter: Utf16CharRanges<'a>,
+    offset: usize,
+}
+
+impl<'a> Utf16CharRangesOffset<'a> {
+    /// Returns the remaining code units.
+    #[inline]
+    pub fn as_slice(&self) -> &'a [u16] {
+        self.iter.as_slice()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    #[inline]
+    fn apply_offset(
+        &self,
+        item: Result<(Range<usize>, char), UnpairedSurrogate>,
+    ) -> Result<(Range<usize>, char), UnpairedSurrogate> {
+        let apply = |pos: usize| match pos.checked_add(self.offset) {
+            Some(pos) => pos,
+            None => panic!("position {pos} with offset {} overflows usize", self.offset),
+        };
+        match item {
+            Ok((r, c)) => Ok((apply(r.start)..apply(r.end), c)),
+            Err(err) => Err(UnpairedSurrogate::new(
+                apply(err.range().start),
+                err.unpaired_surrogate(),
+            )),
+        }
+    }
+}
+
+impl Iterator for Utf16CharRangesOffset<'_> {
+    type Item = Result<(Range<usize>, char), UnpairedSurrogate>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        Some(self.apply_offset(item))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Utf16CharRangesOffset<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next_back()?;
+        Some(self.apply_offset(item))
+    }
+}
+
+impl FusedIterator for Utf16CharRangesOffset<'_> {}
+
+impl fmt::Debug for Utf16CharRangesOffset<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Utf16CharRangesOffset(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use super::Utf16CharRangesExt;
+    use crate::UnpairedSurrogate;
+
+    fn encode(text: &str) -> Vec<u16> {
+        text.encode_utf16().collect()
+    }
+
+    #[test]
+    fn test_utf16_char_ranges_bmp() {
+        let text = "Hello ∈ World ¢";
+        let units = encode(text);
+
+        let mut chars = units.utf16_char_ranges();
+        assert!(chars
+            .clone()
+            .map(Result::unwrap)
+            .eq(text.chars().enumerate().map(|(i, c)| (i..(i + 1), c))));
+
+        assert_eq!(chars.nth(6), Some(Ok((6..7, '∈'))));
+        assert_eq!(chars.next_back(), Some(Ok((14..15, '¢'))));
+        assert_eq!(chars.as_slice(), &units[7..14]);
+    }
+
+    #[test]
+    fn test_utf16_char_ranges_astral() {
+        let text = "a🗻∈🌏b";
+        let units = encode(text);
+
+        let expected = [
+            (0..1, 'a'),
+            (1..3, '🗻'),
+            (3..4, '∈'),
+            (4..6, '🌏'),
+            (6..7, 'b'),
+        ];
+        let chars = units.utf16_char_ranges().map(Result::unwrap);
+        assert!(chars.eq(expected.clone()));
+
+        let chars = units.utf16_char_ranges().rev().map(Result::unwrap);
+        assert!(chars.eq(expected.into_iter().rev()));
+
+        // The chars match `char::decode_utf16()`
+        let chars = units.utf16_char_ranges().map(|item| item.unwrap().1);
+        assert!(chars.eq(char::decode_utf16(units.iter().copied()).map(Result::unwrap)));
+    }
+
+    #[test]
+    fn test_utf16_char_ranges_unpaired() {
+        // Lone high surrogate at the end
+        let mut units = encode("a🗻");
+        units.push(0xD83C);
+
+        let mut chars = units.utf16_char_ranges();
+        assert_eq!(chars.next(), Some(Ok((0..1, 'a'))));
+        assert_eq!(chars.next(), Some(Ok((1..3, '🗻'))));
+        let err = chars.next().unwrap().unwrap_err();
+        assert_eq!(err, UnpairedSurrogate::new(3, 0xD83C));
+        assert_eq!(err.range(), 3..4);
+        assert_eq!(chars.next(), None);
+
+        let mut chars = units.utf16_char_ranges();
+        assert_eq!(
+            chars.next_back(),
+            Some(Err(UnpairedSurrogate::new(3, 0xD83C)))
+        );
+        assert_eq!(chars.next_back(), Some(Ok((1..3, '🗻'))));
+
+        // Lone low surrogate at the start
+        let mut units = std::vec![0xDF0F];
+        units.extend(encode("∈🌏"));
+
+        let mut chars = units.utf16_char_ranges();
+        assert_eq!(chars.next(), Some(Err(UnpairedSurrogate::new(0, 0xDF0F))));
+        assert_eq!(chars.next(), Some(Ok((1..2, '∈'))));
+        assert_eq!(chars.next(), Some(Ok((2..4, '🌏'))));
+        assert_eq!(chars.next(), None);
+
+        let chars = units.utf16_char_ranges().rev();
+        assert!(chars.eq([
+            Ok((2..4, '🌏')),
+            Ok((1..2, '∈')),
+            Err(UnpairedSurrogate::new(0, 0xDF0F)),
+        ]));
+    }
+
+    #[test]
+    fn test_utf16_char_ranges_tiles() {
+        let units = [0xD800, 0xD800, 0xDC00, 0xDC00, 0x61, 0xDBFF];
+
+        let expected = [
+            Err(UnpairedSurrogate::new(0, 0xD800)),
+            Ok((1..3, '\u{10000}')),
+            Err(UnpairedSurrogate::new(3, 0xDC00)),
+            Ok((4..5, 'a')),
+            Err(UnpairedSurrogate::new(5, 0xDBFF)),
+        ];
+        assert!(units.utf16_char_ranges().eq(expected.clone()));
+        assert!(units
+            .utf16_char_ranges()
+            .rev()
+            .eq(expected.into_iter().rev()));
+    }
+
+    #[test]
+    fn test_utf16_char_ranges_offset() {
+        let units = encode("Hello 🌏 World");
+
+        let start = 6;
+        let mut chars = units[start..].utf16_char_ranges_offset(start);
+        assert_eq!(chars.offset(), start);
+        assert_eq!(chars.next(), Some(Ok((6..8, '🌏'))));
+        assert_eq!(chars.next_back(), Some(Ok((13..14, 'd'))));
+        assert_eq!(chars.as_slice(), &units[8..13]);
+
+        let units = [0x61, 0xDC00];
+        let mut chars = units[1..].utf16_char_ranges_offset(1);
+        assert_eq!(chars.next(), Some(Err(UnpairedSurrogate::new(1, 0xDC00))));
+    }
+}
@@ -0,0 +1,498 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+use core::str;
+
+use crate::{CharRangesExt, CharRangesOffset, InvalidUtf8Range};
+
+/// Extension trait for iterating the [`char`]s of bytes, which are
+/// mostly, but not necessarily entirely, valid UTF-8.
+pub trait Utf8CharRangesExt {
+    /// Returns an iterator over the [`char`]s of the valid UTF-8 sequences
+    /// and their start and end byte positions, along with the ranges of
+    /// the invalid byte sequences in between.
+    ///
+    /// Invalid bytes are split the same way as [`Utf8Chunks`], i.e. each
+    /// error is the maximal invalid prefix of 1 to 3 bytes, after which
+    /// iteration continues. Together all items cover the whole input.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::Utf8CharRangesExt;
+    ///
+    /// let bytes = b"a\xF0\x9F\x97\xBB\xFFb\xE2\x88";
+    ///
+    /// let mut chars = bytes.utf8_char_ranges();
+    /// assert_eq!(chars.next(), Some(Ok((0..1, 'a'))));
+    /// assert_eq!(chars.next(), Some(Ok((1..5, '🗻'))));
+    /// assert_eq!(chars.next().unwrap().unwrap_err().range(), 5..6);
+    /// assert_eq!(chars.next(), Some(Ok((6..7, 'b'))));
+    /// // Truncated '∈'
+    /// assert_eq!(chars.next().unwrap().unwrap_err().range(), 7..9);
+    /// assert_eq!(chars.next(), None);
+    /// ```
+    ///
+    /// [`Utf8Chunks`]: https://doc.rust-lang.org/std/str/struct.Utf8Chunks.html
+    fn utf8_char_ranges(&self) -> Utf8CharRanges<'_>;
+
+    /// Returns an iterator over the [`char`]s of the valid UTF-8 sequences
+    /// and their start and end byte positions, along with the ranges of
+    /// the invalid byte sequences in between. With `offset` applied to
+    /// all ranges.
+    ///
+    /// See [`utf8_char_ranges()`](Self::utf8_char_ranges) for more information.
+    ///
+    /// # Panics
+    ///
+    /// If `offset` plus the length of the bytes overflows `usize`, then the
+    /// returned iterator panics when producing a position that overflows,
+    /// in both debug and release builds.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::Utf8CharRangesExt;
+    ///
+    /// let bytes = b"a\xFFb";
+    ///
+    /// let mut chars = bytes.utf8_char_ranges_offset(10);
+    /// assert_eq!(chars.next(), Some(Ok((10..11, 'a'))));
+    /// assert_eq!(chars.next().unwrap().unwrap_err().range(), 11..12);
+    /// assert_eq!(chars.next(), Some(Ok((12..13, 'b'))));
+    /// assert_eq!(chars.next(), None);
+    /// ```
+    #[inline]
+    fn utf8_char_ranges_offset(&self, offset: usize) -> Utf8CharRanges<'_> {
+        Utf8CharRanges::new(self.utf8_char_ranges().as_bytes(), offset)
+    }
+
+    /// Returns an iterator over the [`char`]s of the valid UTF-8 sequences
+    /// and their start and end byte positions, where invalid byte sequences
+    /// are replaced by [`char::REPLACEMENT_CHARACTER`] `'\u{FFFD}'`.
+    ///
+    /// The produced [`char`]s are the same as [`String::from_utf8_lossy()`],
+    /// and each `'\u{FFFD}'` carries the range of the invalid bytes it
+    /// replaces. Together all ranges cover the whole input.
+    ///
+    /// See [`utf8_char_ranges()`](Self::utf8_char_ranges) for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::Utf8CharRangesExt;
+    ///
+    /// let bytes = b"a\xFFb\xE2\x88";
+    ///
+    /// let mut chars = bytes.utf8_char_ranges_lossy();
+    /// assert_eq!(chars.next(), Some((0..1, 'a')));
+    /// assert_eq!(chars.next(), Some((1..2, '\u{FFFD}')));
+    /// assert_eq!(chars.next(), Some((2..3, 'b')));
+    /// assert_eq!(chars.next(), Some((3..5, '\u{FFFD}')));
+    /// assert_eq!(chars.next(), None);
+    /// ```
+    ///
+    /// [`String::from_utf8_lossy()`]: https://doc.rust-lang.org/std/string/struct.String.html#method.from_utf8_lossy
+    #[inline]
+    fn utf8_char_ranges_lossy(&self) -> Utf8CharRangesLossy<'_> {
+        Utf8CharRangesLossy {
+            iter: self.utf8_char_ranges(),
+        }
+    }
+
+    /// Returns an iterator over the [`char`]s of the valid UTF-8 sequences
+    /// and their start and end byte positions, where invalid byte sequences
+    /// are replaced by [`char::REPLACEMENT_CHARACTER`] `'\u{FFFD}'`. With
+    /// `offset` applied to all ranges.
+    ///
+    /// See [`utf8_char_ranges_lossy()`](Self::utf8_char_ranges_lossy) for
+    /// more information.
+    #[inline]
+    fn utf8_char_ranges_lossy_offset(&self, offset: usize) -> Utf8CharRangesLossy<'_> {
+        Utf8CharRangesLossy {
+            iter: self.utf8_char_ranges_offset(offset),
+        }
+    }
+}
+
+impl Utf8CharRangesExt for [u8] {
+    #[inline]
+    fn utf8_char_ranges(&self) -> Utf8CharRanges<'_> {
+        Utf8CharRanges::new(self, 0)
+    }
+}
+
+/// An iterator over the [`char`]s of bytes and their start and end byte
+/// positions, along with the ranges of invalid UTF-8 byte sequences.
+///
+/// This iterator is forward-only, as the invalid sequences are found
+/// by validating from the front.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`Utf8CharRangesExt::utf8_char_ranges()`] for more information.
+#[derive(Clone)]
+pub struct Utf8CharRanges<'a> {
+    bytes: &'a [u8],
+    /// The [`char`]s of the current valid chunk.
+    chars: CharRangesOffset<'a>,
+    /// The invalid bytes following the current valid chunk.
+    invalid: Option<Range<usize>>,
+    /// Start byte position of the bytes, which haven't been validated.
+    rest: usize,
+    offset: usize,
+}
+
+impl<'a> Utf8CharRanges<'a> {
+    #[inline]
+    fn new(bytes: &'a [u8], offset: usize) -> Self {
+        Self {
+            bytes,
+            chars: "".char_ranges_offset(offset),
+            invalid: None,
+            rest: 0,
+            offset,
+        }
+    }
+
+    /// Returns the remaining bytes.
+    #[inline]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        let start = if !self.chars.as_str().is_empty() {
+            self.chars.front_offset() - self.offset
+        } else if let Some(invalid) = &self.invalid {
+            invalid.start - self.offset
+        } else {
+            self.rest
+        };
+        &self.bytes[start..]
+    }
+
+    /// Returns the `offset` applied to all ranges.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Applies the offset to a position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position overflows `usize`, regardless of
+    /// whether overflow checks are enabled.
+    #[inline]
+    fn apply_offset_to(&self, pos: usize) -> usize {
+        match pos.checked_add(self.offset) {
+            Some(pos) => pos,
+            None => panic!("position {pos} with offset {} overflows usize", self.offset),
+        }
+    }
+
+    /// Validates the next valid chunk and the invalid bytes following it.
+    fn next_chunk(&mut self) {
+        let rest = &self.bytes[self.rest..];
+        match str::from_utf8(rest) {
+            Ok(valid) => {
+                self.chars = valid.char_ranges_offset(self.apply_offset_to(self.rest));
+                self.rest = self.bytes.len();
+            }
+            Err(err) => {
+                let (valid, invalid) = rest.split_at(err.valid_up_to());
+                let valid = str::from_utf8(valid).expect("bytes up to the error are valid UTF-8");
+                self.chars = valid.char_ranges_offset(self.apply_offset_to(self.rest));
+
+                // If `error_len()` is `None`, then the end of the input
+                // was reached in the middle of a sequence
+                let len = err.error_len().unwrap_or(invalid.len());
+                let start = self.rest + valid.len();
+                self.invalid = Some(self.apply_offset_to(start)..self.apply_offset_to(start + len));
+                self.rest = start + len;
+            }
+        }
+    }
+}
+
+impl Iterator for Utf8CharRanges<'_> {
+    type Item = Result<(Range<usize>, char), InvalidUtf8Range>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.chars.next() {
+                return Some(Ok(item));
+            }
+            if let Some(invalid) = self.invalid.take() {
+                return Some(Err(InvalidUtf8Range::new(invalid)));
+            }
+            if self.rest >= self.bytes.len() {
+                return None;
+            }
+            self.next_chunk();
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.as_bytes().len();
+        (len.div_ceil(4), Some(len))
+    }
+}
+
+impl FusedIterator for Utf8CharRanges<'_> {}
+
+impl fmt::Debug for Utf8CharRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Utf8CharRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+/// An iterator over the [`char`]s of bytes and their start and end byte
+/// positions, where invalid UTF-8 byte sequences are replaced by
+/// [`char::REPLACEMENT_CHARACTER`].
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`Utf8CharRangesExt::utf8_char_ranges_lossy()`] for more information.
+#[derive(Clone)]
+pub struct Utf8CharRangesLossy<'a> {
+    iter: Utf8CharRanges<'a>,
+}
+
+impl<'a> Utf8CharRangesLossy<'a> {
+    /// Returns the remaining bytes.
+    #[inline]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.iter.as_bytes()
+    }
+
+    /// Returns the `offset` applied to all ranges.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+}
+
+impl Iterator for Utf8CharRangesLossy<'_> {
+    type Item = (Range<usize>, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.iter.next()? {
+            Ok(item) => Some(item),
+            Err(err) => Some((err.range(), char::REPLACEMENT_CHARACTER)),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl FusedIterator for Utf8CharRangesLossy<'_> {}
+
+impl fmt::Debug for Utf8CharRangesLossy<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Utf8CharRangesLossy(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::String;
+    use std::vec::Vec;
+
+    use super::Utf8CharRangesExt;
+    use crate::CharRangesExt;
+
+    /// Asserts that the items tile `bytes`, and that the invalid ranges
+    /// match the invalid chunks of `Utf8Chunks`.
+    fn assert_tiles(bytes: &[u8]) {
+        let mut pos = 0;
+        let mut invalid = Vec::new();
+        for item in bytes.utf8_char_ranges() {
+            let r = match item {
+                Ok((r, c)) => {
+                    let mut buf = [0; 4];
+                    assert_eq!(&bytes[r.clone()], c.encode_utf8(&mut buf).as_bytes());
+                    r
+                }
+                Err(err) => {
+                    let r = err.range();
+                    assert!((1..=3).contains(&r.len()), "{r:?}");
+                    invalid.push(&bytes[r.clone()]);
+                    r
+                }
+            };
+            assert_eq!(r.start, pos, "{bytes:?}");
+            pos = r.end;
+        }
+        assert_eq!(pos, bytes.len());
+
+        let expected = bytes
+            .utf8_chunks()
+            .map(|chunk| chunk.invalid())
+            .filter(|invalid| !invalid.is_empty())
+            .collect::<Vec<_>>();
+        assert_eq!(invalid, expected);
+    }
+
+    #[test]
+    fn test_utf8_char_ranges_valid() {
+        let text = "Hello 🗻12∈45🌏 World ¢";
+
+        let chars = text.as_bytes().utf8_char_ranges().map(Result::unwrap);
+        assert!(chars.eq(text.char_ranges()));
+        assert_tiles(text.as_bytes());
+
+        assert_eq!(b"".utf8_char_ranges().next(), None);
+    }
+
+    #[test]
+    fn test_utf8_char_ranges_truncated() {
+        // '🗻' is F0 9F 97 BB
+        for len in 1..4 {
+            let mut bytes = b"ab".to_vec();
+            bytes.extend_from_slice(&"🗻".as_bytes()[..len]);
+            assert_tiles(&bytes);
+
+            let mut chars = bytes.utf8_char_ranges();
+            assert_eq!(chars.next(), Some(Ok((0..1, 'a'))));
+            assert_eq!(chars.next(), Some(Ok((1..2, 'b'))));
+            assert_eq!(chars.as_bytes(), &bytes[2..]);
+            assert_eq!(chars.next().unwrap().unwrap_err().range(), 2..(2 + len));
+            assert_eq!(chars.as_bytes(), b"");
+            assert_eq!(chars.next(), None);
+        }
+    }
+
+    #[test]
+    fn test_utf8_char_ranges_continuation_bytes() {
+        let bytes = b"\x80a\xBF\xBF\xE2\x88\xE2\x88\x88";
+        assert_tiles(bytes);
+
+        let mut chars = bytes.utf8_char_ranges();
+        assert_eq!(chars.next().unwrap().unwrap_err().range(), 0..1);
+        assert_eq!(chars.next(), Some(Ok((1..2, 'a'))));
+        assert_eq!(chars.next().unwrap().unwrap_err().range(), 2..3);
+        assert_eq!(chars.next().unwrap().unwrap_err().range(), 3..4);
+        assert_eq!(chars.next().unwrap().unwrap_err().range(), 4..6);
+        assert_eq!(chars.next(), Some(Ok((6..9, '∈'))));
+        assert_eq!(chars.next(), None);
+    }
+
+    #[test]
+    fn test_utf8_char_ranges_invalid() {
+        let cases: &[&[u8]] = &[
+            b"\xFF",
+            b"\xC0\x80",
+            b"\xED\xA0\x80",
+            b"\xF4\x90\x80\x80",
+            b"a\xF0\x9F\x97b\xF0\x9F",
+            b"\xE2\x88\x88\xFF\xFE\xE2\x88\x88",
+        ];
+        for bytes in cases {
+            assert_tiles(bytes);
+        }
+    }
+
+    fn assert_lossy(bytes: &[u8]) {
+        let mut pos = 0;
+        let mut lossy = String::new();
+        for (r, c) in bytes.utf8_char_ranges_lossy() {
+            assert_eq!(r.start, pos, "{bytes:?}");
+            pos = r.end;
+            lossy.push(c);
+        }
+        assert_eq!(pos, bytes.len());
+        assert_eq!(lossy, String::from_utf8_lossy(bytes));
+
+        let len = bytes
+            .utf8_char_ranges_lossy()
+            .map(|(r, _c)| r.len())
+            .sum::<usize>();
+        assert_eq!(len, bytes.len());
+    }
+
+    #[test]
+    fn test_utf8_char_ranges_lossy() {
+        let cases: &[&[u8]] = &[
+            b"",
+            "Hello 🗻12∈45🌏 World ¢".as_bytes(),
+            // ISO-8859-1 encoded "café naïve"
+            b"caf\xE9 na\xEFve",
+            // CESU-8 encoded U+1F5FB, i.e. a surrogate pair encoded separately
+            b"a\xED\xA0\xBD\xED\xB7\xBBb",
+            // Truncated emoji
+            b"\xF0\x9F\x97",
+            b"\xF0\x9F\x97a\xF0\x9F\xF0\x9F\x97\xBB",
+            b"\x80\x80\xC0\x80\xFF\xFE",
+        ];
+        for bytes in cases {
+            assert_lossy(bytes);
+        }
+
+        let mut chars = b"caf\xE9!".utf8_char_ranges_lossy();
+        assert_eq!(chars.nth(3), Some((3..4, '\u{FFFD}')));
+        assert_eq!(chars.as_bytes(), b"!");
+        assert_eq!(chars.next(), Some((4..5, '!')));
+
+        // Each surrogate half is replaced byte by byte
+        let chars = b"\xED\xA0\xBD".utf8_char_ranges_lossy();
+        assert!(chars.eq([(0..1, '\u{FFFD}'), (1..2, '\u{FFFD}'), (2..3, '\u{FFFD}'),]));
+    }
+
+    #[test]
+    fn test_utf8_char_ranges_offset() {
+        let cases: &[&[u8]] = &[
+            b"",
+            "Hello 🗻12∈45🌏 World ¢".as_bytes(),
+            b"\x80a\xBF\xBF\xE2\x88\xE2\x88\x88",
+            b"\xF0\x9F\x97a\xF0\x9F\xF0\x9F\x97\xBB",
+        ];
+        for bytes in cases {
+            let chars = bytes.utf8_char_ranges_offset(7);
+            assert_eq!(chars.offset(), 7);
+            let expected = bytes.utf8_char_ranges().map(|item| match item {
+                Ok((r, c)) => Ok(((r.start + 7)..(r.end + 7), c)),
+                Err(err) => Err((err.range().start + 7)..(err.range().end + 7)),
+            });
+            assert!(chars
+                .map(|item| item.map_err(|err| err.range()))
+                .eq(expected));
+
+            let chars = bytes.utf8_char_ranges_lossy_offset(7);
+            let expected = bytes
+                .utf8_char_ranges_lossy()
+                .map(|(r, c)| ((r.start + 7)..(r.end + 7), c));
+            assert!(chars.eq(expected));
+        }
+
+        let mut chars = b"a\xFF\xE2\x88\x88".utf8_char_ranges_offset(3);
+        assert_eq!(chars.as_bytes(), b"a\xFF\xE2\x88\x88");
+        chars.next();
+        assert_eq!(chars.as_bytes(), b"\xFF\xE2\x88\x88");
+        chars.next();
+        assert_eq!(chars.as_bytes(), b"\xE2\x88\x88");
+        assert_eq!(chars.next(), Some(Ok((5..8, '∈'))));
+        assert_eq!(chars.as_bytes(), b"");
+    }
+
+    #[test]
+    #[should_panic(expected = "position 2 with offset 18446744073709551614 overflows usize")]
+    fn test_utf8_char_ranges_offset_overflow() {
+        let mut chars = b"\xFF\xFF".utf8_char_ranges_offset(usize::MAX - 1);
+        assert_eq!(
+            chars.next().unwrap().unwrap_err().range(),
+            (usize::MAX - 1)..usize::MAX,
+        );
+        chars.next();
+    }
+}
@@ -0,0 +1,232 @@
+use core::ops::Range;
+
+use crate::{CharRangesExt, CharRangesOffset, SpanError};
+
+/// A range of byte positions, which is guaranteed to be within the
+/// bounds of the text it was validated against, and to start and end
+/// on [`char`] boundaries of that text.
+///
+/// Since the text is kept alongside the range, slicing it with
+/// [`as_str()`](Self::as_str) never panics, and a span cannot
+/// accidentally be used with a different text.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::{SpanError, ValidSpan};
+///
+/// let text = "Hello 🌏 World";
+///
+/// let hello = ValidSpan::new(text, 0..5).unwrap();
+/// let world = ValidSpan::new(text, 11..16).unwrap();
+/// assert_eq!(hello.as_str(), "Hello");
+///
+/// let joined = hello.join(&world).unwrap();
+/// assert_eq!(joined.range(), 0..16);
+/// assert_eq!(joined.as_str(), "Hello 🌏 World");
+///
+/// // Within '🌏'
+/// assert_eq!(ValidSpan::new(text, 7..11), Err(SpanError::NotCharBoundary));
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ValidSpan<'a> {
+    text: &'a str,
+    range: Range<usize>,
+}
+
+impl<'a> ValidSpan<'a> {
+    /// Creates a span of `range` within `text`.
+    ///
+    /// Returns an error if `range` is reversed, out of bounds of `text`,
+    /// or if its start or end is not a [`char`] boundary.
+    pub fn new(text: &'a str, range: Range<usize>) -> Result<Self, SpanError> {
+        if range.start > range.end {
+            return Err(SpanError::Reversed);
+        }
+        if range.end > text.len() {
+            return Err(SpanError::OutOfBounds);
+        }
+        if !text.is_char_boundary(range.start) || !text.is_char_boundary(range.end) {
+            return Err(SpanError::NotCharBoundary);
+        }
+        Ok(Self { text, range })
+    }
+
+    /// Returns the text this span was validated against.
+    #[inline]
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// Returns the start and end byte positions of this span.
+    #[inline]
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// Returns the substring of this span.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        &self.text[self.range.clone()]
+    }
+
+    /// Returns the length of this span in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.range.len()
+    }
+
+    /// Returns `true` if this span is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.range.is_empty()
+    }
+
+    /// Returns the smallest span covering both `self` and `other`,
+    /// including anything between them.
+    ///
+    /// Returns [`SpanError::DifferentText`] if `other` was validated
+    /// against a different text. Texts are compared by address and
+    /// length, i.e. equal contents at different addresses are different.
+    pub fn join(&self, other: &Self) -> Result<Self, SpanError> {
+        if !core::ptr::eq(self.text, other.text) {
+            return Err(SpanError::DifferentText);
+        }
+        let start = self.range.start.min(other.range.start);
+        let end = self.range.end.max(other.range.end);
+        Ok(Self {
+            text: self.text,
+            range: start..end,
+        })
+    }
+
+    /// Returns an iterator over the [`char`]s of this span and their
+    /// start and end byte positions within the [`text()`](Self::text).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use char_ranges::ValidSpan;
+    ///
+    /// let span = ValidSpan::new("Hello 🌏 World", 5..11).unwrap();
+    ///
+    /// let mut chars = span.char_ranges();
+    /// assert_eq!(chars.next(), Some((5..6, ' ')));
+    /// assert_eq!(chars.next(), Some((6..10, '🌏')));
+    /// assert_eq!(chars.next(), Some((10..11, ' ')));
+    /// assert_eq!(chars.next(), None);
+    /// ```
+    #[inline]
+    pub fn char_ranges(&self) -> CharRangesOffset<'a> {
+        self.as_str().char_ranges_offset(self.range.start)
+    }
+}
+
+impl From<ValidSpan<'_>> for Range<usize> {
+    #[inline]
+    fn from(span: ValidSpan<'_>) -> Self {
+        span.range
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::String;
+
+    use super::ValidSpan;
+    use crate::{CharRangesExt, SpanError};
+
+    #[test]
+    fn test_valid_span() {
+        let text = "🗻∈🌏";
+        for start in 0..=(text.len() + 1) {
+            for end in 0..=(text.len() + 1) {
+                #[allow(clippy::reversed_empty_ranges)]
+                let range = start..end;
+                let span = ValidSpan::new(text, range.clone());
+                match text.get(range.clone()) {
+                    Some(s) => {
+                        let span = span.unwrap();
+                        assert_eq!(span.as_str(), s);
+                        assert_eq!(span.range(), range);
+                        assert_eq!(span.len(), s.len());
+                        assert!(span.char_ranges().eq(text
+                            .char_ranges()
+                            .filter(|(r, _c)| (start <= r.start) && (r.end <= end))));
+                    }
+                    None => assert!(span.is_err()),
+                }
+            }
+        }
+
+        assert_eq!(ValidSpan::new(text, 4..13), Err(SpanError::OutOfBounds));
+        assert_eq!(ValidSpan::new(text, 5..7), Err(SpanError::NotCharBoundary));
+        #[allow(clippy::reversed_empty_ranges)]
+        let range = 7..4;
+        assert_eq!(ValidSpan::new(text, range), Err(SpanError::Reversed));
+    }
+
+    #[test]
+    fn test_valid_span_empty() {
+        let span = ValidSpan::new("", 0..0).unwrap();
+        assert!(span.is_empty());
+        assert_eq!(span.as_str(), "");
+        assert_eq!(span.char_ranges().next(), None);
+
+        let text = "a🌏";
+        let span = ValidSpan::new(text, 5..5).unwrap();
+        assert!(span.is_empty());
+        assert_eq!(span.as_str(), "");
+
+        // Joining empty spans covers the gap between them
+        let first = ValidSpan::new(text, 1..1).unwrap();
+        let joined = first.join(&span).unwrap();
+        assert_eq!(joined.range(), 1..5);
+        assert_eq!(joined.as_str(), "🌏");
+
+        assert_eq!(first.join(&first), Ok(first.clone()));
+        assert_eq!(ValidSpan::new(text, 2..2), Err(SpanError::NotCharBoundary));
+    }
+
+    #[test]
+    fn test_valid_span_join_different_text() {
+        let a = "Hello World";
+        let b = String::from(a);
+
+        let span_a = ValidSpan::new(a, 0..5).unwrap();
+        let span_b = ValidSpan::new(&b, 6..11).unwrap();
+        assert_eq!(span_a.join(&span_b), Err(SpanError::DifferentText));
+
+        // Same address, different length
+        let span_c = ValidSpan::new(&a[..5], 0..5).unwrap();
+        assert_eq!(span_a.join(&span_c), Err(SpanError::DifferentText));
+
+        let span_d = ValidSpan::new(a, 6..11).unwrap();
+        assert_eq!(span_a.join(&span_d).unwrap().as_str(), "Hello World");
+    }
+
+    #[test]
+    fn test_valid_span_from_iter() {
+        let text = "Hello 🗻∈🌏";
+
+        let mut chars = text.char_ranges();
+        chars.next_back();
+        for (r, c) in chars {
+            let span = chars.valid_span(r.clone()).unwrap();
+            assert_eq!(span.range(), r);
+            assert_eq!(span.as_str().chars().next(), Some(c));
+        }
+
+        let chars = text[6..].char_ranges_offset(6);
+        for (r, c) in chars {
+            let span = chars.valid_span(r.clone()).unwrap();
+            assert_eq!(span.range(), (r.start - 6)..(r.end - 6));
+            assert_eq!(span.text(), "🗻∈🌏");
+            assert_eq!(span.as_str().chars().next(), Some(c));
+        }
+        assert_eq!(chars.valid_span(2..3), Err(SpanError::OutOfBounds));
+        assert_eq!(chars.valid_span(6..8), Err(SpanError::NotCharBoundary));
+    }
+}
@@ -0,0 +1,369 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use unicode_width::UnicodeWidthChar;
+
+use crate::{CharRangesExt, CharRangesOffset};
+
+/// Returns the number of terminal cells `c` occupies.
+///
+/// Control characters, for which [`UnicodeWidthChar::width()`]
+/// returns `None`, occupy `0` cells.
+#[inline]
+pub(crate) fn char_width(c: char) -> usize {
+    c.width().unwrap_or(0)
+}
+
+/// An iterator over [`char`]s, their start and end byte positions,
+/// and the number of terminal cells they occupy.
+///
+/// Widths are determined by the [`unicode-width`] crate, e.g. `'中'` and
+/// `'🌏'` are `2` cells wide, while combining marks like `'\u{301}'` are
+/// `0` cells wide. Control characters, e.g. `'\t'` and `'\n'`, for which
+/// [`unicode-width`] returns `None`, are `0` cells wide.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRangesExt::char_width_ranges()`] for more information.
+///
+/// [`unicode-width`]: https://docs.rs/unicode-width
+/// [`CharRangesExt::char_width_ranges()`]: crate::CharRangesExt::char_width_ranges
+#[derive(Clone)]
+pub struct CharWidthRanges<'a> {
+    iter: CharRangesOffset<'a>,
+}
+
+impl<'a> CharWidthRanges<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>) -> Self {
+        Self { iter }
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+}
+
+impl Iterator for CharWidthRanges<'_> {
+    type Item = (Range<usize>, char, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (r, c) = self.iter.next()?;
+        Some((r, c, char_width(c)))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for CharWidthRanges<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (r, c) = self.iter.next_back()?;
+        Some((r, c, char_width(c)))
+    }
+}
+
+impl FusedIterator for CharWidthRanges<'_> {}
+
+impl fmt::Debug for CharWidthRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CharWidthRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+/// An iterator over [`char`]s, their start and end byte positions,
+/// and the start and end terminal cell columns they occupy.
+///
+/// Produced items are `(range, char, start_column, end_column)`, where
+/// columns are zero-based, and each [`char`] advances the column by its
+/// width, as produced by [`CharWidthRanges`]. A `'\n'` resets the column
+/// to `0` for the next [`char`].
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRanges::width_columns()`] for more information.
+///
+/// [`CharRanges::width_columns()`]: crate::CharRanges::width_columns
+#[derive(Clone)]
+pub struct WidthColumns<'a> {
+    iter: CharRangesOffset<'a>,
+    column: usize,
+}
+
+impl<'a> WidthColumns<'a> {
+    #[inline]
+    pub(crate) fn new(iter: CharRangesOffset<'a>) -> Self {
+        Self { iter, column: 0 }
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+
+    /// Returns the column of the next [`char`].
+    #[inline]
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+impl Iterator for WidthColumns<'_> {
+    type Item = (Range<usize>, char, usize, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (r, c) = self.iter.next()?;
+
+        let start = self.column;
+        let end = start + char_width(c);
+        self.column = if c == '\n' { 0 } else { end };
+
+        Some((r, c, start, end))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl FusedIterator for WidthColumns<'_> {}
+
+impl fmt::Debug for WidthColumns<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "WidthColumns(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+/// Returns the [`char`] and its start and end byte positions, whose
+/// terminal cells cover the zero-based cell `column` of `line`, i.e. the
+/// inverse of [`WidthColumns`], e.g. for mapping a mouse click to a
+/// position.
+///
+/// If `column` lands on the second cell of a wide [`char`], e.g. `'中'`,
+/// then the wide [`char`] is returned. Zero-width [`char`]s, e.g.
+/// combining marks and control characters, cover no cells, so they are
+/// never returned.
+///
+/// Only the first line of `line` is considered, i.e. up to the first
+/// `'\n'` or `"\r\n"`, which is excluded. Returns `None` if `column` is
+/// past the end of the line. Use `line.len()` as the position in that
+/// case, e.g. for placing a caret at the end of the line.
+///
+/// See [`char_at_visual_column()`](crate::char_at_visual_column) for
+/// expanding tabs instead.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::char_at_width_column;
+///
+/// let line = "a中e\u{301}🌏";
+///
+/// assert_eq!(char_at_width_column(line, 0), Some((0..1, 'a')));
+/// assert_eq!(char_at_width_column(line, 1), Some((1..4, '中')));
+/// assert_eq!(char_at_width_column(line, 2), Some((1..4, '中')));
+/// assert_eq!(char_at_width_column(line, 3), Some((4..5, 'e')));
+/// assert_eq!(char_at_width_column(line, 4), Some((7..11, '🌏')));
+/// assert_eq!(char_at_width_column(line, 6), None);
+/// ```
+#[inline]
+pub fn char_at_width_column(line: &str, column: usize) -> Option<(Range<usize>, char)> {
+    let line = line.lines().next().unwrap_or_default();
+    line.char_ranges()
+        .width_columns()
+        .find(|&(_, _, start, end)| (start..end).contains(&column))
+        .map(|(r, c, _, _)| (r, c))
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use super::char_at_width_column;
+    use crate::CharRangesExt;
+
+    #[test]
+    fn test_char_width_ranges() {
+        // CJK
+        let text = "a中文b";
+        let widths = text.char_width_ranges().collect::<Vec<_>>();
+        assert_eq!(
+            widths,
+            [
+                (0..1, 'a', 1),
+                (1..4, '中', 2),
+                (4..7, '文', 2),
+                (7..8, 'b', 1),
+            ],
+        );
+
+        // Emoji
+        let mut chars = "🗻∈🌏".char_width_ranges();
+        assert_eq!(chars.next(), Some((0..4, '🗻', 2)));
+        assert_eq!(chars.next_back(), Some((7..11, '🌏', 2)));
+        assert_eq!(chars.next(), Some((4..7, '∈', 1)));
+        assert_eq!(chars.next(), None);
+
+        // Combining marks
+        let text = "e\u{301}a\u{308}";
+        let widths = text
+            .char_width_ranges()
+            .map(|(_r, _c, width)| width)
+            .collect::<Vec<_>>();
+        assert_eq!(widths, [1, 0, 1, 0]);
+
+        // Control characters
+        let text = "\t\n\r\0\u{7F}";
+        assert!(text.char_width_ranges().all(|(_r, _c, width)| width == 0));
+
+        let chars = "中".char_ranges_offset(10).widths();
+        assert_eq!(chars.offset(), 10);
+        assert!(chars.eq([(10..13, '中', 2)]));
+    }
+
+    #[test]
+    fn test_width_columns() {
+        let text = "中a\u{301}🌏\n文";
+
+        let mut chars = text.char_ranges().width_columns();
+        assert_eq!(chars.next(), Some((0..3, '中', 0, 2)));
+        assert_eq!(chars.next(), Some((3..4, 'a', 2, 3)));
+        assert_eq!(chars.next(), Some((4..6, '\u{301}', 3, 3)));
+        assert_eq!(chars.column(), 3);
+        assert_eq!(chars.next(), Some((6..10, '🌏', 3, 5)));
+        assert_eq!(chars.next(), Some((10..11, '\n', 5, 5)));
+        assert_eq!(chars.column(), 0);
+        assert_eq!(chars.next(), Some((11..14, '文', 0, 2)));
+        assert_eq!(chars.next(), None);
+    }
+
+    #[test]
+    fn test_width_of_range() {
+        let text = "a中\u{301}🌏\tb";
+
+        assert_eq!(text.width_of_range(..), 6);
+        assert_eq!(text.width_of_range(1..4), 2);
+        assert_eq!(text.width_of_range(1..6), 2);
+        assert_eq!(text.width_of_range(6..10), 2);
+        assert_eq!(text.width_of_range(10..11), 0);
+        assert_eq!(text.width_of_range(4..4), 0);
+        assert_eq!("".width_of_range(..), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "byte index 2 is not a char boundary")]
+    fn test_width_of_range_not_char_boundary() {
+        _ = "a中".width_of_range(2..);
+    }
+
+    #[test]
+    fn test_range_for_width() {
+        let text = "a中🌏b";
+
+        assert_eq!(text.range_for_width(0), 0..0);
+        assert_eq!(text.range_for_width(1), 0..1);
+        // '中' doesn't fit in the remaining cell
+        assert_eq!(text.range_for_width(2), 0..1);
+        assert_eq!(text.range_for_width(3), 0..4);
+        // '🌏' doesn't fit in the remaining cell
+        assert_eq!(text.range_for_width(4), 0..4);
+        assert_eq!(text.range_for_width(5), 0..8);
+        assert_eq!(text.range_for_width(6), 0..9);
+        assert_eq!(text.range_for_width(100), 0..9);
+
+        // A wide char doesn't fit in a single cell
+        assert_eq!("中".range_for_width(1), 0..0);
+
+        // Zero-width chars following the prefix are included
+        let text = "e\u{301}\u{308}a\u{301}";
+        assert_eq!(text.range_for_width(0), 0..0);
+        assert_eq!(text.range_for_width(1), 0..5);
+        assert_eq!(text.range_for_width(2), 0..8);
+
+        // Leading zero-width chars always fit
+        assert_eq!("\u{301}\ta".range_for_width(0), 0..3);
+
+        assert_eq!("".range_for_width(10), 0..0);
+    }
+
+    #[test]
+    fn test_truncate_with_ellipsis_width() {
+        let text = "a中🌏b";
+
+        assert_eq!(text.truncate_with_ellipsis_width(6), (0..9, false));
+        assert_eq!(text.truncate_with_ellipsis_width(100), (0..9, false));
+        // '🌏' doesn't fit in the remaining cells
+        assert_eq!(text.truncate_with_ellipsis_width(5), (0..4, true));
+        assert_eq!(text.truncate_with_ellipsis_width(4), (0..4, true));
+        // '中' doesn't fit in the remaining cells
+        assert_eq!(text.truncate_with_ellipsis_width(3), (0..1, true));
+        assert_eq!(text.truncate_with_ellipsis_width(1), (0..0, true));
+        assert_eq!(text.truncate_with_ellipsis_width(0), (0..0, true));
+
+        // Zero-width chars never cause truncation
+        assert_eq!("e\u{301}".truncate_with_ellipsis_width(1), (0..3, false));
+        assert_eq!("".truncate_with_ellipsis_width(0), (0..0, false));
+    }
+
+    #[test]
+    fn test_char_at_width_column() {
+        let line = "\t中a\u{301}🌏;";
+
+        // '\t' is zero-width
+        assert_eq!(char_at_width_column(line, 0), Some((1..4, '中')));
+        assert_eq!(char_at_width_column(line, 1), Some((1..4, '中')));
+        assert_eq!(char_at_width_column(line, 2), Some((4..5, 'a')));
+        assert_eq!(char_at_width_column(line, 3), Some((7..11, '🌏')));
+        assert_eq!(char_at_width_column(line, 4), Some((7..11, '🌏')));
+        assert_eq!(char_at_width_column(line, 5), Some((11..12, ';')));
+        assert_eq!(char_at_width_column(line, 6), None);
+
+        assert_eq!(char_at_width_column("中\n文", 2), None);
+        assert_eq!(char_at_width_column("", 0), None);
+    }
+
+    #[test]
+    fn test_char_at_width_column_round_trip() {
+        for line in ["\t中a\u{301}🌏;", "中文\t🗻∈🌏\tx", "e\u{301}\u{308}a"] {
+            let mut end_column = 0;
+            for (r, c, start, end) in line.char_ranges().width_columns() {
+                for column in start..end {
+                    let found = char_at_width_column(line, column);
+                    assert_eq!(found, Some((r.clone(), c)), "{line:?} at {column}");
+                }
+                end_column = end;
+            }
+            assert_eq!(char_at_width_column(line, end_column), None);
+        }
+    }
+}
@@ -0,0 +1,425 @@
+use core::ops::Range;
+
+#[cfg(feature = "unicode-segmentation")]
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Returns the position after the next word in `text` at or after `from`,
+/// along with the range of the word, skipping any whitespace before it.
+///
+/// If only whitespace remains, then `len` is returned with an empty range.
+/// Returns `None` if `from >= len`. `from` must be a [`char`] boundary.
+#[cfg(not(feature = "unicode-segmentation"))]
+pub(crate) fn next_word_boundary(text: &str, from: usize) -> Option<(usize, Range<usize>)> {
+    if from >= text.len() {
+        return None;
+    }
+
+    let start = text.len() - text[from..].trim_start().len();
+    let end = match text[start..].chars().next() {
+        Some(c) => {
+            let rest = &text[start..];
+            let word = is_word_char(c);
+            text.len() - rest.trim_start_matches(|c| is_same_class(c, word)).len()
+        }
+        None => start,
+    };
+
+    Some((end, start..end))
+}
+
+/// Returns the position of the previous word in `text` before `from`,
+/// along with the range of the word, skipping any whitespace after it.
+///
+/// If only whitespace precedes, then `0` is returned with an empty range.
+/// Returns `None` if `from == 0`. `from` must be a [`char`] boundary.
+#[cfg(not(feature = "unicode-segmentation"))]
+pub(crate) fn prev_word_boundary(text: &str, from: usize) -> Option<(usize, Range<usize>)> {
+    if from == 0 {
+        return None;
+    }
+
+    let end = text[..from].trim_end().len();
+    let start = match text[..end].chars().next_back() {
+        Some(c) => {
+            let word = is_word_char(c);
+            text[..end]
+                .trim_end_matches(|c| is_same_class(c, word))
+                .len()
+        }
+        None => 0,
+    };
+
+    Some((start, start..end))
+}
+
+/// Returns `range` grown outward to the start and end of the runs of
+/// [`char`]s it touches, where a run is a word, a run of other
+/// non-whitespace [`char`]s, or a run of whitespace.
+///
+/// `range` must be on [`char`] boundaries. See [`caret_range()`] for
+/// empty ranges.
+#[cfg(not(feature = "unicode-segmentation"))]
+pub(crate) fn expand_to_word(text: &str, range: Range<usize>) -> Range<usize> {
+    let range = caret_range(text, range);
+    let (Some(first), Some(last)) = (
+        text[range.start..].chars().next(),
+        text[..range.end].chars().next_back(),
+    ) else {
+        return range;
+    };
+
+    let first = char_class(first);
+    let start = text[..range.start]
+        .trim_end_matches(|c| char_class(c) == first)
+        .len();
+
+    let last = char_class(last);
+    let end = text.len()
+        - text[range.end..]
+            .trim_start_matches(|c| char_class(c) == last)
+            .len();
+
+    start..end
+}
+
+#[cfg(not(feature = "unicode-segmentation"))]
+#[inline]
+fn char_class(c: char) -> (bool, bool) {
+    (c.is_whitespace(), is_word_char(c))
+}
+
+/// Returns `true` if `c` is alphanumeric or `'_'`.
+#[inline]
+pub(crate) fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || (c == '_')
+}
+
+#[cfg(not(feature = "unicode-segmentation"))]
+#[inline]
+fn is_same_class(c: char, word: bool) -> bool {
+    !c.is_whitespace() && (is_word_char(c) == word)
+}
+
+/// Returns the position after the next word in `text` at or after `from`,
+/// along with the range of the word, skipping any whitespace before it.
+///
+/// If only whitespace remains, then `len` is returned with an empty range.
+/// Returns `None` if `from >= len`. `from` must be a [`char`] boundary.
+#[cfg(feature = "unicode-segmentation")]
+pub(crate) fn next_word_boundary(text: &str, from: usize) -> Option<(usize, Range<usize>)> {
+    if from >= text.len() {
+        return None;
+    }
+
+    // The whole text is segmented, as the word boundaries
+    // depend on the surrounding chars
+    let range = text
+        .split_word_bound_indices()
+        .map(|(i, s)| i..(i + s.len()))
+        .find(|r| (r.end > from) && !is_whitespace(&text[r.clone()]))
+        .map_or(text.len()..text.len(), |r| r.start.max(from)..r.end);
+
+    Some((range.end, range))
+}
+
+/// Returns the position of the previous word in `text` before `from`,
+/// along with the range of the word, skipping any whitespace after it.
+///
+/// If only whitespace precedes, then `0` is returned with an empty range.
+/// Returns `None` if `from == 0`. `from` must be a [`char`] boundary.
+#[cfg(feature = "unicode-segmentation")]
+pub(crate) fn prev_word_boundary(text: &str, from: usize) -> Option<(usize, Range<usize>)> {
+    if from == 0 {
+        return None;
+    }
+
+    let range = text
+        .split_word_bound_indices()
+        .rev()
+        .map(|(i, s)| i..(i + s.len()))
+        .find(|r| (r.start < from) && !is_whitespace(&text[r.clone()]))
+        .map_or(0..0, |r| r.start..r.end.min(from));
+
+    Some((range.start, range))
+}
+
+/// Returns `range` grown outward to the start and end of the
+/// [Unicode word boundaries] it touches.
+///
+/// `range` must be on [`char`] boundaries. See [`caret_range()`] for
+/// empty ranges.
+///
+/// [Unicode word boundaries]: https://www.unicode.org/reports/tr29/#Word_Boundaries
+#[cfg(feature = "unicode-segmentation")]
+pub(crate) fn expand_to_word(text: &str, range: Range<usize>) -> Range<usize> {
+    let range = caret_range(text, range);
+    if range.is_empty() {
+        return range;
+    }
+
+    let mut segments = text
+        .split_word_bound_indices()
+        .map(|(i, s)| i..(i + s.len()));
+
+    let Some(first) = segments.find(|r| r.end > range.start) else {
+        return range;
+    };
+    let end = if first.end >= range.end {
+        first.end
+    } else {
+        segments
+            .find(|r| r.end >= range.end)
+            .map_or(range.end, |r| r.end)
+    };
+
+    first.start..end
+}
+
+#[cfg(feature = "unicode-segmentation")]
+#[inline]
+fn is_whitespace(s: &str) -> bool {
+    s.chars().all(char::is_whitespace)
+}
+
+/// Returns `range` if it is not empty. Otherwise returns the range of
+/// the [`char`] under the caret at `range.start`, i.e. the [`char`] after
+/// it, unless that is whitespace and the [`char`] before it is not, e.g.
+/// for a caret at the end of a word.
+///
+/// An empty text returns the empty `range` as is.
+fn caret_range(text: &str, range: Range<usize>) -> Range<usize> {
+    if !range.is_empty() {
+        return range;
+    }
+
+    let pos = range.start;
+    let prev = text[..pos].chars().next_back();
+    let next = text[pos..].chars().next();
+    match (prev, next) {
+        (Some(prev), Some(next)) if next.is_whitespace() && !prev.is_whitespace() => {
+            (pos - prev.len_utf8())..pos
+        }
+        (_, Some(next)) => pos..(pos + next.len_utf8()),
+        (Some(prev), None) => (pos - prev.len_utf8())..pos,
+        (None, None) => range,
+    }
+}
+
+/// Returns the range of the run of [`char`]s matching `is_ident_char`
+/// around `pos`, preferring the run ending at `pos` if any.
+///
+/// Returns `None` if neither the [`char`] before nor after `pos` match.
+/// `pos` must be a [`char`] boundary.
+pub(crate) fn identifier_at<F>(text: &str, pos: usize, is_ident_char: F) -> Option<Range<usize>>
+where
+    F: Fn(char) -> bool,
+{
+    let start = text[..pos].trim_end_matches(&is_ident_char).len();
+    let end = text.len() - text[pos..].trim_start_matches(&is_ident_char).len();
+    (start < end).then_some(start..end)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use crate::CharRangesExt;
+
+    #[test]
+    fn test_word_boundary_walk() {
+        let text = "  Hello, wörld! 🌏 foo_bar\t42\n";
+
+        let mut forwards = Vec::new();
+        let mut pos = 0;
+        while let Some((next, r)) = text.next_word_boundary(pos) {
+            assert_eq!(next, r.end);
+            forwards.push(r);
+            pos = next;
+        }
+        assert_eq!(pos, text.len());
+
+        let mut backwards = Vec::new();
+        while let Some((prev, r)) = text.prev_word_boundary(pos) {
+            assert_eq!(prev, r.start);
+            backwards.push(r);
+            pos = prev;
+        }
+        assert_eq!(pos, 0);
+
+        let words = forwards
+            .iter()
+            .map(|r| &text[r.clone()])
+            .collect::<Vec<_>>();
+        assert_eq!(
+            words,
+            ["Hello", ",", "wörld", "!", "🌏", "foo_bar", "42", ""],
+        );
+
+        backwards.reverse();
+        assert_eq!(backwards[0], 0..0);
+        assert_eq!(backwards[1..], forwards[..(forwards.len() - 1)]);
+    }
+
+    #[test]
+    fn test_word_boundary_snapping() {
+        let text = "ab 🌏cd";
+
+        // Within '🌏', so starts at '🌏'
+        assert_eq!(text.next_word_boundary(4), text.next_word_boundary(3));
+        assert_eq!(text.prev_word_boundary(5), Some((0, 0..2)));
+
+        // Within a word
+        assert_eq!(text.next_word_boundary(1), Some((2, 1..2)));
+        assert_eq!(text.prev_word_boundary(1), Some((0, 0..1)));
+
+        assert_eq!(text.next_word_boundary(text.len()), None);
+        assert_eq!(text.next_word_boundary(100), None);
+        assert_eq!(text.prev_word_boundary(100), text.prev_word_boundary(9));
+        assert_eq!(text.prev_word_boundary(0), None);
+        assert_eq!("".next_word_boundary(0), None);
+        assert_eq!("   ".next_word_boundary(0), Some((3, 3..3)));
+        assert_eq!("   ".prev_word_boundary(3), Some((0, 0..0)));
+    }
+
+    #[cfg(not(feature = "unicode-segmentation"))]
+    #[test]
+    fn test_word_boundary_heuristic() {
+        let text = "can't -> 3.14";
+        assert_eq!(text.next_word_boundary(0), Some((3, 0..3)));
+        assert_eq!(text.next_word_boundary(3), Some((4, 3..4)));
+        assert_eq!(text.next_word_boundary(5), Some((8, 6..8)));
+        assert_eq!(text.prev_word_boundary(text.len()), Some((11, 11..13)));
+    }
+
+    #[test]
+    fn test_expand_to_word() {
+        let text = "let wörld = foo_bar(42);";
+
+        // Caret inside a word
+        assert_eq!(text.expand_to_word(6..6), Some(4..10));
+        assert_eq!(text.expand_to_word(4..4), Some(4..10));
+        // Caret at the end of a word
+        assert_eq!(text.expand_to_word(10..10), Some(4..10));
+        // Caret before a word
+        assert_eq!(text.expand_to_word(0..0), Some(0..3));
+        assert_eq!("foo bar".expand_to_word(7..7), Some(4..7));
+
+        // Already spanning multiple words
+        assert_eq!(text.expand_to_word(1..7), Some(0..10));
+        assert_eq!(text.expand_to_word(14..22), Some(13..23));
+        assert_eq!(text.expand_to_word(4..10), Some(4..10));
+
+        // Within 'ö'
+        assert_eq!(text.expand_to_word(6..7), Some(4..10));
+
+        assert_eq!(text.expand_to_word(0..100), Some(0..text.len()));
+        assert_eq!(text.expand_to_word(100..100), None);
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = text.expand_to_word(5..4);
+        assert_eq!(reversed, None);
+        assert_eq!("".expand_to_word(0..0), Some(0..0));
+    }
+
+    #[test]
+    fn test_expand_to_word_whitespace() {
+        let text = "foo   bar";
+
+        // Between whitespace, selects the whitespace
+        assert_eq!(text.expand_to_word(4..4), Some(3..6));
+        assert_eq!(text.expand_to_word(3..4), Some(3..6));
+        assert_eq!(text.expand_to_word(2..4), Some(0..6));
+        assert_eq!("   ".expand_to_word(1..1), Some(0..3));
+    }
+
+    #[cfg(not(feature = "unicode-segmentation"))]
+    #[test]
+    fn test_expand_to_word_heuristic() {
+        let text = "can't -> 3.14";
+        assert_eq!(text.expand_to_word(1..1), Some(0..3));
+        assert_eq!(text.expand_to_word(7..7), Some(6..8));
+        assert_eq!(text.expand_to_word(10..10), Some(10..11));
+        assert_eq!(text.expand_to_word(12..12), Some(11..13));
+    }
+
+    #[cfg(feature = "unicode-segmentation")]
+    #[test]
+    fn test_expand_to_word_uax29() {
+        let text = "can't -> 3.14";
+        assert_eq!(text.expand_to_word(1..1), Some(0..5));
+        assert_eq!(text.expand_to_word(7..7), Some(7..8));
+        assert_eq!(text.expand_to_word(10..10), Some(9..13));
+    }
+
+    #[cfg(feature = "unicode-segmentation")]
+    #[test]
+    fn test_word_boundary_uax29() {
+        let text = "can't -> 3.14";
+        assert_eq!(text.next_word_boundary(0), Some((5, 0..5)));
+        assert_eq!(text.next_word_boundary(5), Some((7, 6..7)));
+        assert_eq!(text.prev_word_boundary(text.len()), Some((9, 9..13)));
+        assert_eq!(text.prev_word_boundary(11), Some((9, 9..11)));
+    }
+
+    #[test]
+    fn test_identifier_at() {
+        let text = "let wörld = foo_bar(42);";
+
+        // Within an identifier
+        assert_eq!(text.identifier_at_default(6), Some((4..10, "wörld")));
+        assert_eq!(text.identifier_at_default(4), Some((4..10, "wörld")));
+        // At the end of an identifier
+        assert_eq!(text.identifier_at_default(10), Some((4..10, "wörld")));
+        // Within 'ö'
+        assert_eq!(text.identifier_at_default(7), Some((4..10, "wörld")));
+
+        assert_eq!(text.identifier_at_default(14), Some((13..20, "foo_bar")));
+        assert_eq!(text.identifier_at_default(20), Some((13..20, "foo_bar")));
+        assert_eq!(text.identifier_at_default(21), Some((21..23, "42")));
+
+        // Not an identifier on either side
+        assert_eq!(text.identifier_at_default(11), None);
+        assert_eq!(text.identifier_at_default(text.len()), None);
+        assert_eq!(text.identifier_at_default(100), None);
+        assert_eq!("".identifier_at_default(0), None);
+    }
+
+    #[test]
+    fn test_identifier_at_prefers_preceding() {
+        // Between two identifiers, the preceding one is preferred
+        let text = "foo+bar";
+        assert_eq!(text.identifier_at_default(3), Some((0..3, "foo")));
+        assert_eq!(text.identifier_at_default(4), Some((4..7, "bar")));
+
+        let text = "🗻∈x.y∈";
+        let is_ident_char = |c: char| c != '.';
+        assert_eq!(text.identifier_at(8, is_ident_char), Some((0..8, "🗻∈x")));
+        assert_eq!(text.identifier_at(9, is_ident_char), Some((9..13, "y∈")));
+    }
+
+    #[test]
+    fn test_identifier_at_predicate() {
+        // Multi-byte identifier chars
+        let text = "a 🗻∈🌏 b";
+        let is_ident_char = |c: char| !c.is_ascii();
+        for pos in 2..=13 {
+            assert_eq!(
+                text.identifier_at(pos, is_ident_char),
+                Some((2..13, "🗻∈🌏")),
+                "{pos}"
+            );
+        }
+        assert_eq!(text.identifier_at(0, is_ident_char), None);
+        assert_eq!(text.identifier_at(1, is_ident_char), None);
+
+        // Kebab case
+        let text = "(foo-bar baz)";
+        let is_ident_char = |c: char| c.is_alphanumeric() || (c == '-');
+        assert_eq!(
+            text.identifier_at(5, is_ident_char),
+            Some((1..8, "foo-bar"))
+        );
+        assert_eq!(text.identifier_at_default(5), Some((5..8, "bar")));
+    }
+}
@@ -0,0 +1,352 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::CharRangesOffset;
+
+/// An iterator over soft wrapped lines, i.e. their start and end byte
+/// positions and substrings, where no line is wider than `max_width`.
+///
+/// Lines are broken greedily, preferring to break at whitespace. If a
+/// single word is wider than `max_width`, then it is broken mid-word.
+/// A [`char`] is never split, so a [`char`] wider than `max_width` is
+/// produced on a line by itself.
+///
+/// With the `unicode-width` feature, widths are measured in terminal
+/// cells, see [`CharRangesExt::char_width_ranges()`]. Otherwise, every
+/// [`char`] is a single column wide.
+///
+/// Every `'\n'` forces a break, like [`str::lines()`]. The yielded ranges,
+/// along with the following dropped [`char`]s, cover the whole text:
+///
+/// - The `'\n'` of a forced break, along with a directly preceding `'\r'`.
+/// - The whitespace at a soft break, i.e. the whole run of whitespace
+///   [`char`]s between the last word of a line and the first word of
+///   the next line, along with a following forced break.
+///
+/// All other whitespace is retained, e.g. indentation and trailing
+/// whitespace that fits within `max_width`.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`CharRanges::wrap_ranges()`] for more information.
+///
+/// [`CharRangesExt::char_width_ranges()`]: crate::CharRangesExt::char_width_ranges
+/// [`CharRanges::wrap_ranges()`]: crate::CharRanges::wrap_ranges
+#[derive(Clone)]
+pub struct WrapRanges<'a> {
+    iter: CharRangesOffset<'a>,
+    max_width: usize,
+}
+
+impl<'a> WrapRanges<'a> {
+    #[inline]
+    #[track_caller]
+    pub(crate) fn new(iter: CharRangesOffset<'a>, max_width: usize) -> Self {
+        assert!(max_width > 0, "max width must be greater than 0");
+        Self { iter, max_width }
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the `offset` applied to all positions.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.iter.offset()
+    }
+
+    /// Returns the maximum width of a line.
+    #[inline]
+    pub fn max_width(&self) -> usize {
+        self.max_width
+    }
+}
+
+impl<'a> Iterator for WrapRanges<'a> {
+    type Item = (Range<usize>, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chars = &self.iter.iter;
+        let (text, start, end) = (chars.text, chars.start, chars.end);
+        if start >= end {
+            return None;
+        }
+
+        let mut width = 0;
+        // The start of the current run of whitespace
+        let mut ws_start = None;
+        // The last run of whitespace followed by a word
+        let mut soft_break = None;
+
+        let mut line = start..end;
+        let mut next = end;
+
+        for (i, c) in text[start..end].char_indices() {
+            let i = start + i;
+
+            if c == '\n' {
+                line.end = if text[start..i].ends_with('\r') {
+                    i - 1
+                } else {
+                    i
+                };
+                next = i + 1;
+                break;
+            }
+
+            if c.is_whitespace() {
+                ws_start.get_or_insert(i);
+            } else if let Some(ws_start) = ws_start.take() {
+                // Leading whitespace is never a break opportunity,
+                // as that would produce an empty line
+                if ws_start > start {
+                    soft_break = Some(ws_start..i);
+                }
+            }
+
+            let w = char_width(c);
+            // The first char is always included to ensure progress
+            if (width + w > self.max_width) && (i > start) {
+                match (ws_start, soft_break) {
+                    // Break at the current run of whitespace
+                    (Some(ws_start), _) if ws_start > start => {
+                        line.end = ws_start;
+                        next = skip_break(text, i, end);
+                    }
+                    // Break at the last run of whitespace
+                    (None, Some(soft_break)) => {
+                        line.end = soft_break.start;
+                        next = soft_break.end;
+                    }
+                    // Break mid-word
+                    _ => {
+                        line.end = i;
+                        next = i;
+                    }
+                }
+                break;
+            }
+            width += w;
+        }
+
+        self.iter.iter.start = next;
+
+        Some((self.iter.apply_offset(line.clone()), &text[line]))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.iter.iter.end - self.iter.iter.start;
+        (usize::from(len > 0), Some(len))
+    }
+}
+
+impl FusedIterator for WrapRanges<'_> {}
+
+impl fmt::Debug for WrapRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "WrapRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+/// Returns the position after the whitespace starting at `pos`,
+/// including a single `'\n'`.
+#[inline]
+fn skip_break(text: &str, pos: usize, end: usize) -> usize {
+    let rest = &text[pos..end];
+    let trimmed = rest.trim_start_matches(|c: char| c.is_whitespace() && (c != '\n'));
+    let trimmed = trimmed.strip_prefix('\n').unwrap_or(trimmed);
+    end - trimmed.len()
+}
+
+#[cfg(feature = "unicode-width")]
+#[inline]
+fn char_width(c: char) -> usize {
+    crate::width::char_width(c)
+}
+
+#[cfg(not(feature = "unicode-width"))]
+#[inline]
+fn char_width(_c: char) -> usize {
+    1
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::String;
+    use std::vec::Vec;
+
+    use crate::CharRangesExt;
+
+    fn wrap(text: &str, max_width: usize) -> Vec<&str> {
+        text.char_ranges()
+            .wrap_ranges(max_width)
+            .map(|(r, line)| {
+                assert_eq!(&text[r], line);
+                line
+            })
+            .collect()
+    }
+
+    /// Asserts that the lines and the dropped break whitespace
+    /// cover the whole `text`.
+    fn assert_covered(text: &str, max_width: usize) {
+        let mut pos = 0;
+        for (r, _line) in text.char_ranges().wrap_ranges(max_width) {
+            let dropped = &text[pos..r.start];
+            assert!(dropped.chars().all(char::is_whitespace), "{dropped:?}");
+            pos = r.end;
+        }
+        assert!(text[pos..].chars().all(char::is_whitespace));
+    }
+
+    #[test]
+    fn test_wrap_ranges() {
+        let text = "The quick brown fox jumps over the lazy dog";
+        assert_eq!(
+            wrap(text, 10),
+            ["The quick", "brown fox", "jumps over", "the lazy", "dog"],
+        );
+        assert_eq!(wrap(text, 100), [text]);
+        assert_covered(text, 10);
+
+        let mut lines = text.char_ranges().wrap_ranges(16);
+        assert_eq!(lines.next(), Some((0..15, "The quick brown")));
+        assert_eq!(lines.as_str(), "fox jumps over the lazy dog");
+        assert_eq!(lines.next(), Some((16..30, "fox jumps over")));
+        assert_eq!(lines.next(), Some((31..43, "the lazy dog")));
+        assert_eq!(lines.next(), None);
+
+        assert_eq!(wrap("", 10), [""; 0]);
+    }
+
+    #[test]
+    fn test_wrap_ranges_long_words() {
+        assert_eq!(wrap("abcdefghij", 4), ["abcd", "efgh", "ij"]);
+        assert_eq!(
+            wrap("ab abcdefghij cd", 4),
+            ["ab", "abcd", "efgh", "ij", "cd"]
+        );
+        assert_eq!(wrap("abcdefgh", 1).len(), 8);
+        assert_covered("ab abcdefghij cd", 4);
+    }
+
+    #[test]
+    fn test_wrap_ranges_whitespace() {
+        // Trailing whitespace is retained if it fits
+        assert_eq!(wrap("ab  ", 4), ["ab  "]);
+        assert_eq!(wrap("ab  \ncd", 4), ["ab  ", "cd"]);
+
+        // Whitespace at a soft break is dropped
+        assert_eq!(wrap("ab    ", 3), ["ab"]);
+        assert_eq!(wrap("ab    cd", 3), ["ab", "cd"]);
+        assert_eq!(wrap("ab \t cd", 4), ["ab", "cd"]);
+        assert_eq!(wrap("ab    \ncd", 3), ["ab", "cd"]);
+
+        // Indentation is retained
+        assert_eq!(wrap("  ab cd", 5), ["  ab", "cd"]);
+        assert_eq!(wrap("     ab", 3), ["   ", "  a", "b"]);
+
+        for text in ["ab    cd", "ab \t cd", "ab    \ncd", "  ab cd", "     ab"] {
+            assert_covered(text, 3);
+        }
+    }
+
+    #[test]
+    fn test_wrap_ranges_newlines() {
+        assert_eq!(wrap("ab\ncd", 10), ["ab", "cd"]);
+        assert_eq!(wrap("ab\r\ncd\r\n", 10), ["ab", "cd"]);
+        assert_eq!(wrap("ab\n\n\ncd\n", 10), ["ab", "", "", "cd"]);
+        assert_eq!(wrap("\n", 10), [""]);
+        assert_eq!(wrap("abc def\nghi", 3), ["abc", "def", "ghi"]);
+        assert_eq!(wrap("abcdef\ngh", 4), ["abcd", "ef", "gh"]);
+
+        // Same as `str::lines()`, when nothing is wrapped
+        let text = "foo\r\nbar\n\nbaz\n";
+        assert!(wrap(text, 10).into_iter().eq(text.lines()));
+
+        assert_covered("ab\r\ncd\r\n", 10);
+        assert_covered("abc def\nghi", 3);
+    }
+
+    #[test]
+    fn test_wrap_ranges_multi_byte() {
+        let text = "🗻∈🌏 🗻∈🌏";
+
+        let mut lines = text.char_ranges_offset(10).wrap_ranges(5);
+        assert_eq!(lines.next(), Some((10..21, "🗻∈🌏")));
+        assert_eq!(lines.next(), Some((22..33, "🗻∈🌏")));
+        assert_eq!(lines.next(), None);
+
+        assert_covered(text, 2);
+        let lines = wrap(text, 2);
+        assert!(lines.iter().all(|line| !line.is_empty()));
+        assert_eq!(lines.concat(), text.replace(' ', ""));
+    }
+
+    #[cfg(not(feature = "unicode-width"))]
+    #[test]
+    fn test_wrap_ranges_char_count() {
+        let text = "🗻∈🌏 🗻∈🌏";
+        assert_eq!(wrap(text, 3), ["🗻∈🌏", "🗻∈🌏"]);
+        assert_eq!(wrap(text, 2), ["🗻∈", "🌏", "🗻∈", "🌏"]);
+    }
+
+    #[cfg(feature = "unicode-width")]
+    #[test]
+    fn test_wrap_ranges_cell_width() {
+        // '🗻' and '🌏' are 2 cells wide
+        let text = "🗻∈🌏 🗻∈🌏";
+        assert_eq!(wrap(text, 5), ["🗻∈🌏", "🗻∈🌏"]);
+        assert_eq!(wrap(text, 4), ["🗻∈", "🌏", "🗻∈", "🌏"]);
+        assert_eq!(wrap(text, 3), ["🗻∈", "🌏", "🗻∈", "🌏"]);
+
+        // A char wider than the max width is on a line by itself
+        assert_eq!(wrap("a🌏b", 1), ["a", "🌏", "b"]);
+
+        // Zero-width chars don't cause a break
+        assert_eq!(
+            wrap("e\u{301}e\u{301}e\u{301}", 2),
+            ["e\u{301}e\u{301}", "e\u{301}"]
+        );
+
+        assert_eq!(wrap("中文 中文", 4), ["中文", "中文"]);
+    }
+
+    #[test]
+    fn test_wrap_ranges_random() {
+        let mut random = crate::test_util::random();
+
+        let pieces = ["a", "bc", "🗻", "∈", " ", "  ", "\n", "\r\n", "\t", "word"];
+        for _ in 0..200 {
+            let mut text = String::new();
+            for _ in 0..(random() % 30) {
+                text.push_str(pieces[random() % pieces.len()]);
+            }
+            for max_width in 1..8 {
+                assert_covered(&text, max_width);
+
+                for (_r, line) in text.char_ranges().wrap_ranges(max_width) {
+                    assert!(!line.contains('\n'));
+                    let width = line.chars().map(super::char_width).sum::<usize>();
+                    assert!(width <= max_width || line.chars().count() == 1);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "max width must be greater than 0")]
+    fn test_wrap_ranges_max_width_0() {
+        _ = "abc".char_ranges().wrap_ranges(0);
+    }
+}
@@ -0,0 +1,469 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::{CharRanges, CharRangesExt};
+
+/// A value from one or both sides of [`ZipLongestCharRanges`].
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum EitherOrBoth<T> {
+    /// Both sides have a value.
+    Both(T, T),
+    /// Only the left side has a value, i.e. the right side has ended.
+    Left(T),
+    /// Only the right side has a value, i.e. the left side has ended.
+    Right(T),
+}
+
+impl<T> EitherOrBoth<T> {
+    /// Returns the value of the left side, if any.
+    #[inline]
+    pub fn left(self) -> Option<T> {
+        match self {
+            Self::Both(left, _) | Self::Left(left) => Some(left),
+            Self::Right(_) => None,
+        }
+    }
+
+    /// Returns the value of the right side, if any.
+    #[inline]
+    pub fn right(self) -> Option<T> {
+        match self {
+            Self::Both(_, right) | Self::Right(right) => Some(right),
+            Self::Left(_) => None,
+        }
+    }
+}
+
+/// An iterator over the [`char`]s of two strings in lockstep, and their
+/// start and end byte positions in their respective string, until the
+/// shorter string ends.
+///
+/// Iterating from the back produces the same pairs as iterating from the
+/// front, in reverse order. In other words, if the strings have different
+/// [`char`] counts, then the first call to [`next_back()`] skips the
+/// trailing [`char`]s of the longer string, which are never paired. This
+/// requires counting the remaining [`char`]s of both strings.
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`char_ranges_zip()`] for more information.
+///
+/// [`next_back()`]: DoubleEndedIterator::next_back
+#[derive(Clone)]
+pub struct ZipCharRanges<'a> {
+    a: CharRanges<'a>,
+    b: CharRanges<'a>,
+    /// `true` if the remaining [`char`] counts of `a` and `b` are equal,
+    /// i.e. if [`next_back()`] has been called.
+    ///
+    /// [`next_back()`]: DoubleEndedIterator::next_back
+    trimmed: bool,
+}
+
+impl<'a> ZipCharRanges<'a> {
+    #[inline]
+    fn new(a: &'a str, b: &'a str) -> Self {
+        Self {
+            a: a.char_ranges(),
+            b: b.char_ranges(),
+            trimmed: false,
+        }
+    }
+
+    /// Returns the remaining substrings of both strings.
+    #[inline]
+    pub fn as_strs(&self) -> (&'a str, &'a str) {
+        (self.a.as_str(), self.b.as_str())
+    }
+}
+
+impl Iterator for ZipCharRanges<'_> {
+    type Item = ((Range<usize>, char), (Range<usize>, char));
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let a = self.a.next()?;
+        let b = self.b.next()?;
+        Some((a, b))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a_lower, a_upper) = self.a.size_hint();
+        let (b_lower, b_upper) = self.b.size_hint();
+        let upper = match (a_upper, b_upper) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (upper, None) | (None, upper) => upper,
+        };
+        (a_lower.min(b_lower), upper)
+    }
+}
+
+impl DoubleEndedIterator for ZipCharRanges<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if !self.trimmed {
+            self.trimmed = true;
+
+            let a_len = self.a.count();
+            let b_len = self.b.count();
+            if a_len > b_len {
+                _ = self.a.advance_back_by(a_len - b_len);
+            } else if b_len > a_len {
+                _ = self.b.advance_back_by(b_len - a_len);
+            }
+        }
+
+        let a = self.a.next_back()?;
+        let b = self.b.next_back()?;
+        Some((a, b))
+    }
+}
+
+impl FusedIterator for ZipCharRanges<'_> {}
+
+impl fmt::Debug for ZipCharRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ZipCharRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+/// An iterator over the [`char`]s of two strings in lockstep, and their
+/// start and end byte positions in their respective string, until both
+/// strings end.
+///
+/// After the shorter string ends, the remaining [`char`]s of the longer
+/// string are produced as [`EitherOrBoth::Left`] or
+/// [`EitherOrBoth::Right`].
+///
+/// Iterating from the back produces the same items as iterating from the
+/// front, in reverse order. In other words, if the strings have different
+/// [`char`] counts, then [`next_back()`] first produces the trailing
+/// [`char`]s of the longer string, before producing pairs. This requires
+/// counting the remaining [`char`]s of both strings on the first call to
+/// [`next_back()`].
+///
+/// Note: Cloning this iterator is essentially a copy.
+///
+/// See [`char_ranges_zip_longest()`] for more information.
+///
+/// [`next_back()`]: DoubleEndedIterator::next_back
+#[derive(Clone)]
+pub struct ZipLongestCharRanges<'a> {
+    a: CharRanges<'a>,
+    b: CharRanges<'a>,
+    /// The number of remaining [`char`]s of `a` and `b` respectively,
+    /// which are not paired, i.e. at most one is non-zero. Computed on
+    /// the first [`next_back()`].
+    ///
+    /// [`next_back()`]: DoubleEndedIterator::next_back
+    excess: Option<(usize, usize)>,
+}
+
+impl<'a> ZipLongestCharRanges<'a> {
+    #[inline]
+    fn new(a: &'a str, b: &'a str) -> Self {
+        Self {
+            a: a.char_ranges(),
+            b: b.char_ranges(),
+            excess: None,
+        }
+    }
+
+    /// Returns the remaining substrings of both strings.
+    #[inline]
+    pub fn as_strs(&self) -> (&'a str, &'a str) {
+        (self.a.as_str(), self.b.as_str())
+    }
+}
+
+impl Iterator for ZipLongestCharRanges<'_> {
+    type Item = EitherOrBoth<(Range<usize>, char)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match (self.a.next(), self.b.next()) {
+            (Some(a), Some(b)) => Some(EitherOrBoth::Both(a, b)),
+            (Some(a), None) => {
+                if let Some((excess, _)) = &mut self.excess {
+                    *excess -= 1;
+                }
+                Some(EitherOrBoth::Left(a))
+            }
+            (None, Some(b)) => {
+                if let Some((_, excess)) = &mut self.excess {
+                    *excess -= 1;
+                }
+                Some(EitherOrBoth::Right(b))
+            }
+            (None, None) => None,
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a_lower, a_upper) = self.a.size_hint();
+        let (b_lower, b_upper) = self.b.size_hint();
+        let upper = match (a_upper, b_upper) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            _ => None,
+        };
+        (a_lower.max(b_lower), upper)
+    }
+}
+
+impl DoubleEndedIterator for ZipLongestCharRanges<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (a_excess, b_excess) = self.excess.get_or_insert_with(|| {
+            let a_len = self.a.count();
+            let b_len = self.b.count();
+            (a_len.saturating_sub(b_len), b_len.saturating_sub(a_len))
+        });
+
+        if *a_excess > 0 {
+            *a_excess -= 1;
+            self.a.next_back().map(EitherOrBoth::Left)
+        } else if *b_excess > 0 {
+            *b_excess -= 1;
+            self.b.next_back().map(EitherOrBoth::Right)
+        } else {
+            let a = self.a.next_back()?;
+            let b = self.b.next_back()?;
+            Some(EitherOrBoth::Both(a, b))
+        }
+    }
+}
+
+impl FusedIterator for ZipLongestCharRanges<'_> {}
+
+impl fmt::Debug for ZipLongestCharRanges<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ZipLongestCharRanges(")?;
+        f.debug_list().entries(self.clone()).finish()?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+/// Returns an iterator over the [`char`]s of `a` and `b` in lockstep, and
+/// their start and end byte positions in respectively `a` and `b`, until
+/// the shorter string ends.
+///
+/// This is useful for comparing two strings [`char`] by [`char`], while
+/// being able to point at both sides, e.g. the expected and actual text.
+///
+/// See [`ZipCharRanges`] for more information, including the semantics
+/// of [`next_back()`] for strings with different [`char`] counts.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::char_ranges_zip;
+///
+/// let expected = "a∈b";
+/// let actual = "a🌏b";
+///
+/// let mismatch = char_ranges_zip(expected, actual).find(|((_, a), (_, b))| a != b);
+/// assert_eq!(mismatch, Some(((1..4, '∈'), (1..5, '🌏'))));
+///
+/// // The trailing "cd" is skipped, as it is not paired
+/// let mut chars = char_ranges_zip("ab", "xycd");
+/// assert_eq!(chars.next_back(), Some(((1..2, 'b'), (1..2, 'y'))));
+/// assert_eq!(chars.as_strs(), ("a", "x"));
+/// ```
+///
+/// [`next_back()`]: DoubleEndedIterator::next_back
+#[inline]
+pub fn char_ranges_zip<'a>(a: &'a str, b: &'a str) -> ZipCharRanges<'a> {
+    ZipCharRanges::new(a, b)
+}
+
+/// Returns an iterator over the [`char`]s of `a` and `b` in lockstep, and
+/// their start and end byte positions in respectively `a` and `b`, until
+/// both strings end.
+///
+/// See [`ZipLongestCharRanges`] for more information, including the
+/// semantics of [`next_back()`] for strings with different [`char`]
+/// counts.
+///
+/// # Example
+///
+/// ```rust
+/// use char_ranges::{char_ranges_zip_longest, EitherOrBoth};
+///
+/// let mut chars = char_ranges_zip_longest("a∈", "abc");
+/// assert_eq!(chars.next(), Some(EitherOrBoth::Both((0..1, 'a'), (0..1, 'a'))));
+/// assert_eq!(chars.next(), Some(EitherOrBoth::Both((1..4, '∈'), (1..2, 'b'))));
+/// assert_eq!(chars.next(), Some(EitherOrBoth::Right((2..3, 'c'))));
+/// assert_eq!(chars.next(), None);
+///
+/// let mut chars = char_ranges_zip_longest("a∈", "abc");
+/// assert_eq!(chars.next_back(), Some(EitherOrBoth::Right((2..3, 'c'))));
+/// assert_eq!(chars.next_back(), Some(EitherOrBoth::Both((1..4, '∈'), (1..2, 'b'))));
+/// ```
+///
+/// [`next_back()`]: DoubleEndedIterator::next_back
+#[inline]
+pub fn char_ranges_zip_longest<'a>(a: &'a str, b: &'a str) -> ZipLongestCharRanges<'a> {
+    ZipLongestCharRanges::new(a, b)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::String;
+    use std::vec::Vec;
+
+    use super::{char_ranges_zip, char_ranges_zip_longest, EitherOrBoth};
+    use crate::CharRangesExt;
+
+    use EitherOrBoth::{Both, Left, Right};
+
+    const CHARS: [char; 8] = ['a', '\n', '\u{80}', 'é', '∈', '⊂', '🌏', '🗻'];
+
+    #[test]
+    fn test_char_ranges_zip_equal_len() {
+        let a = "a🗻∈b";
+        let b = "x∈🗻y";
+
+        let expected = [
+            ((0..1, 'a'), (0..1, 'x')),
+            ((1..5, '🗻'), (1..4, '∈')),
+            ((5..8, '∈'), (4..8, '🗻')),
+            ((8..9, 'b'), (8..9, 'y')),
+        ];
+
+        let chars = char_ranges_zip(a, b);
+        assert!(chars.clone().eq(expected.iter().cloned()));
+        assert!(chars.rev().eq(expected.iter().cloned().rev()));
+
+        let chars = char_ranges_zip_longest(a, b);
+        assert!(chars
+            .clone()
+            .eq(expected.iter().cloned().map(|(a, b)| Both(a, b))));
+        assert!(chars
+            .rev()
+            .eq(expected.iter().cloned().rev().map(|(a, b)| Both(a, b))));
+    }
+
+    #[test]
+    fn test_char_ranges_zip_empty() {
+        assert_eq!(char_ranges_zip("", "foo").next(), None);
+        assert_eq!(char_ranges_zip("foo", "").next_back(), None);
+
+        let chars = char_ranges_zip_longest("", "a∈");
+        assert!(chars.clone().eq([Right((0..1, 'a')), Right((1..4, '∈'))]));
+        assert!(chars.rev().eq([Right((1..4, '∈')), Right((0..1, 'a'))]));
+
+        let chars = char_ranges_zip_longest("a∈", "");
+        assert!(chars.clone().eq([Left((0..1, 'a')), Left((1..4, '∈'))]));
+        assert!(chars.rev().eq([Left((1..4, '∈')), Left((0..1, 'a'))]));
+
+        assert_eq!(char_ranges_zip_longest("", "").next_back(), None);
+    }
+
+    #[test]
+    fn test_char_ranges_zip_equal_bytes() {
+        // Both are 4 bytes, but have 1 and 4 chars
+        let a = "🌏";
+        let b = "abcd";
+
+        let chars = char_ranges_zip(a, b).collect::<Vec<_>>();
+        assert_eq!(chars, [((0..4, '🌏'), (0..1, 'a'))]);
+
+        let mut chars = char_ranges_zip(a, b);
+        assert_eq!(chars.next_back(), Some(((0..4, '🌏'), (0..1, 'a'))));
+        assert_eq!(chars.as_strs(), ("", ""));
+        assert_eq!(chars.next(), None);
+
+        let chars = char_ranges_zip_longest(a, b).collect::<Vec<_>>();
+        assert_eq!(
+            chars,
+            [
+                Both((0..4, '🌏'), (0..1, 'a')),
+                Right((1..2, 'b')),
+                Right((2..3, 'c')),
+                Right((3..4, 'd')),
+            ]
+        );
+
+        let mut rev = char_ranges_zip_longest(a, b).rev().collect::<Vec<_>>();
+        rev.reverse();
+        assert_eq!(rev, chars);
+
+        assert_eq!(chars[0].clone().left(), Some((0..4, '🌏')));
+        assert_eq!(chars[1].clone().left(), None);
+        assert_eq!(chars[1].clone().right(), Some((1..2, 'b')));
+    }
+
+    #[test]
+    fn test_char_ranges_zip_random() {
+        let mut next = crate::test_util::random();
+
+        for _ in 0..300 {
+            let mut gen = || {
+                let len = next() % 12;
+                (0..len)
+                    .map(|_| CHARS[next() % CHARS.len()])
+                    .collect::<String>()
+            };
+            let a = gen();
+            let b = gen();
+
+            let expected = a.char_ranges().zip(b.char_ranges()).collect::<Vec<_>>();
+
+            let mut chars = char_ranges_zip(&a, &b);
+            let mut front = Vec::new();
+            let mut back = Vec::new();
+            loop {
+                if next().is_multiple_of(2) {
+                    match chars.next() {
+                        Some(item) => front.push(item),
+                        None => break,
+                    }
+                } else {
+                    match chars.next_back() {
+                        Some(item) => back.push(item),
+                        None => break,
+                    }
+                }
+            }
+            back.reverse();
+            front.extend(back);
+            assert_eq!(front, expected, "{a:?} {b:?}");
+
+            let (mut a_iter, mut b_iter) = (a.char_ranges(), b.char_ranges());
+            let expected = core::iter::from_fn(|| match (a_iter.next(), b_iter.next()) {
+                (Some(a), Some(b)) => Some(Both(a, b)),
+                (Some(a), None) => Some(Left(a)),
+                (None, Some(b)) => Some(Right(b)),
+                (None, None) => None,
+            })
+            .collect::<Vec<_>>();
+
+            let mut chars = char_ranges_zip_longest(&a, &b);
+            let mut front = Vec::new();
+            let mut back = Vec::new();
+            loop {
+                if next().is_multiple_of(2) {
+                    match chars.next() {
+                        Some(item) => front.push(item),
+                        None => break,
+                    }
+                } else {
+                    match chars.next_back() {
+                        Some(item) => back.push(item),
+                        None => break,
+                    }
+                }
+            }
+            back.reverse();
+            front.extend(back);
+            assert_eq!(front, expected, "{a:?} {b:?}");
+        }
+    }
+}
@@ -0,0 +1,66 @@
+#![cfg(all(feature = "bstr", feature = "alloc"))]
+
+use bstr::{BStr, BString, ByteSlice};
+use char_ranges::{from_bstr_char_index, to_bstr_char_index, Utf8CharRangesExt};
+
+/// Invalid UTF-8 byte sequences from bstr's documentation.
+const CASES: &[&[u8]] = &[
+    b"\xE2\x98\x83\xFF\xF0\x9D\x9E\x83\xE2\x98\x61",
+    b"\x61\xF1\x80\x80\xE1\x80\xC2\x62",
+    b"foo\xFFbar",
+    b"foo\xFD\xFEbar\xFF",
+    b"FOO\xFFBAR\xE2\x98BAZ",
+    b"foobar\xF1\x80\x80",
+    b"\xED\xA0\x80",
+    b"\xF0\x9D\x9Ca",
+    b"\xF0\x82\x82\xAC",
+    b"Hello \xF0\x9F\x97\xBB World",
+];
+
+#[test]
+fn test_bstr_char_indices() {
+    for &bytes in CASES {
+        let bs = BStr::new(bytes);
+        let expected = bs.char_indices().collect::<Vec<_>>();
+
+        let chars = bs.utf8_char_ranges_lossy().collect::<Vec<_>>();
+        let actual = chars.iter().cloned().map(to_bstr_char_index);
+        assert!(actual.eq(expected.iter().copied()), "{bs:?}");
+
+        let chars = expected.iter().copied().map(from_bstr_char_index);
+        assert!(chars.eq(bs.utf8_char_ranges_lossy()), "{bs:?}");
+    }
+}
+
+#[test]
+fn test_bstr_replacement_boundaries() {
+    let bs = BStr::new(b"\xE2\x98\x83\xFF\xF0\x9D\x9E\x83\xE2\x98\x61");
+
+    let invalid = bs
+        .utf8_char_ranges()
+        .filter_map(Result::err)
+        .map(|err| err.range())
+        .collect::<Vec<_>>();
+    assert_eq!(invalid, [3..4, 8..10]);
+
+    let replaced = bs
+        .char_indices()
+        .filter(|&(_, _, c)| c == '\u{FFFD}')
+        .map(|(start, end, _)| start..end)
+        .collect::<Vec<_>>();
+    assert_eq!(replaced, invalid);
+}
+
+#[test]
+fn test_bstring_char_ranges_offset() {
+    for &bytes in CASES {
+        let bs = BString::from(bytes);
+
+        let chars = bs.utf8_char_ranges_lossy_offset(5);
+        assert_eq!(chars.offset(), 5);
+        let expected = bs
+            .char_indices()
+            .map(|(start, end, c)| ((start + 5)..(end + 5), c));
+        assert!(chars.eq(expected), "{bs:?}");
+    }
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "codespan")]
+
+use codespan_reporting::diagnostic::Diagnostic;
+use codespan_reporting::files::SimpleFile;
+use codespan_reporting::term::{self, Config};
+
+use char_ranges::{clamp_label, label_primary, label_secondary, CharRangesExt};
+
+#[test]
+fn test_codespan_render() {
+    let source = "let x = 🗻 ∈ 🌏;\n";
+    let file = SimpleFile::new("main.txt", source);
+
+    let mut chars = source.char_ranges().spanned();
+    let mountain = chars.find(|c| c.value == '🗻').unwrap();
+    let (elem, _c) = source.char_ranges().find(|(_r, c)| *c == '∈').unwrap();
+
+    let diagnostic = Diagnostic::error()
+        .with_message("unexpected operator")
+        .with_labels(vec![
+            label_primary((), elem).with_message("expected `==`"),
+            mountain.label_secondary(()).with_message("left operand"),
+        ]);
+
+    let output = term::emit_into_string(&Config::default(), &file, &diagnostic).unwrap();
+    assert!(output.contains("error: unexpected operator"), "{output}");
+    assert!(output.contains("main.txt:1:"), "{output}");
+    assert!(output.contains("expected `==`"), "{output}");
+    assert!(output.contains("left operand"), "{output}");
+}
+
+#[test]
+fn test_codespan_render_clamped() {
+    let source = "a 🌏";
+    let file = SimpleFile::new("main.txt", source);
+
+    // Ranges from a larger text, which fall outside this file
+    let text = "xx a 🌏 more";
+    let ranges = text[2..].char_ranges_offset(2).map(|(r, _c)| r);
+
+    let labels = ranges
+        .map(|r| clamp_label(source, label_secondary((), r)))
+        .collect::<Vec<_>>();
+    for label in &labels {
+        assert!(label.range.end <= source.len());
+        assert!(source.is_char_boundary(label.range.start));
+        assert!(source.is_char_boundary(label.range.end));
+    }
+
+    let diagnostic = Diagnostic::warning()
+        .with_message("clamped")
+        .with_labels(labels);
+    let output = term::emit_into_string(&Config::default(), &file, &diagnostic).unwrap();
+    assert!(output.contains("warning: clamped"), "{output}");
+}
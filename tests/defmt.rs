@@ -0,0 +1,40 @@
+#![cfg(feature = "defmt")]
+
+use char_ranges::{
+    Base, CharIssue, CharRanges, CharRangesOffset, CharTooLargeError, ChunkedUtf8Error, ColumnUnit,
+    EscapeStyle, IndentKind, InvalidUtf8Range, LineColumn, Mark, OffsetOverflowError,
+    OverlappingEditError, SeekError, SegmentError, Snap, SpanError, Spanned, U32OverflowError,
+    UnbalancedError, UnescapeError, UnpairedSurrogate,
+};
+
+fn assert_format<T: defmt::Format + ?Sized>() {}
+
+#[test]
+fn test_defmt_format() {
+    assert_format::<CharRanges<'_>>();
+    assert_format::<CharRangesOffset<'_>>();
+
+    assert_format::<LineColumn>();
+    assert_format::<Base>();
+    assert_format::<ColumnUnit>();
+    assert_format::<Spanned<char>>();
+    assert_format::<Spanned<&str>>();
+    assert_format::<Mark>();
+    assert_format::<Snap>();
+    assert_format::<CharIssue>();
+    assert_format::<EscapeStyle>();
+    assert_format::<IndentKind>();
+
+    assert_format::<SeekError>();
+    assert_format::<SpanError>();
+    assert_format::<SegmentError>();
+    assert_format::<OffsetOverflowError>();
+    assert_format::<U32OverflowError>();
+    assert_format::<OverlappingEditError>();
+    assert_format::<CharTooLargeError>();
+    assert_format::<InvalidUtf8Range>();
+    assert_format::<UnpairedSurrogate>();
+    assert_format::<ChunkedUtf8Error>();
+    assert_format::<UnbalancedError>();
+    assert_format::<UnescapeError>();
+}
@@ -0,0 +1,63 @@
+#![cfg(feature = "lsp")]
+
+use lsp_types::{Position, Range};
+
+use char_ranges::{
+    from_lsp_position, from_lsp_range, to_lsp_position, to_lsp_range, CharRangesExt,
+};
+
+const DOCUMENT: &str =
+    "fn main() {\r\n    let 🗻 = \"∈🌏\";\n\n    println!(\"{🗻} 👋🏽 ¢\");\n}\n🦀";
+
+#[test]
+fn test_lsp_round_trip() {
+    let mut expected = Position::new(0, 0);
+    for (r, c) in DOCUMENT.char_ranges() {
+        let pos = to_lsp_position(DOCUMENT, r.start).unwrap();
+        assert_eq!(pos, expected, "{c:?} at {r:?}");
+        assert_eq!(from_lsp_position(DOCUMENT, pos), Some(r.start));
+
+        // Within a surrogate pair
+        if c.len_utf16() == 2 {
+            let mid = Position::new(pos.line, pos.character + 1);
+            assert_eq!(from_lsp_position(DOCUMENT, mid), None);
+        }
+
+        let range = to_lsp_range(DOCUMENT, r.clone()).unwrap();
+        assert_eq!(from_lsp_range(DOCUMENT, range), Some(r.clone()));
+
+        if c == '\n' {
+            expected = Position::new(expected.line + 1, 0);
+        } else {
+            expected.character += c.len_utf16() as u32;
+        }
+
+        for i in (r.start + 1)..r.end {
+            assert_eq!(to_lsp_position(DOCUMENT, i), None);
+        }
+    }
+
+    let end = to_lsp_position(DOCUMENT, DOCUMENT.len()).unwrap();
+    assert_eq!(end, Position::new(5, 2));
+    assert_eq!(from_lsp_position(DOCUMENT, end), Some(DOCUMENT.len()));
+}
+
+#[test]
+fn test_lsp_range() {
+    let (r, _c) = DOCUMENT.char_ranges().find(|(_r, c)| *c == '👋').unwrap();
+    let range = to_lsp_range(DOCUMENT, r.start..(r.end + 4)).unwrap();
+    assert_eq!(
+        range,
+        Range::new(Position::new(3, 19), Position::new(3, 23))
+    );
+    assert_eq!(&DOCUMENT[from_lsp_range(DOCUMENT, range).unwrap()], "👋🏽");
+
+    // Reversed
+    let reversed = Range::new(range.end, range.start);
+    assert_eq!(from_lsp_range(DOCUMENT, reversed), None);
+
+    // Clamped to the end of the lines
+    let range = Range::new(Position::new(0, 99), Position::new(2, 99));
+    let r = from_lsp_range(DOCUMENT, range).unwrap();
+    assert_eq!(&DOCUMENT[r], "\n    let 🗻 = \"∈🌏\";\n");
+}